@@ -0,0 +1,48 @@
+// Full-screen colour grading — fragment shader
+//
+// Runs after tonemapping over the whole 2D view: exposure, contrast and
+// saturation, then a night blue shift, then a vignette whose strength the
+// CPU raises with the player's depth.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct ColorGradingUniform {
+    night_tint: vec3<f32>,
+    exposure: f32,
+    contrast: f32,
+    saturation: f32,
+    vignette: f32,
+    night_shift: f32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+@group(0) @binding(2) var<uniform> grading: ColorGradingUniform;
+
+/// Linear mid grey, the pivot contrast scales around.
+const MID_GREY: f32 = 0.18;
+/// Distance from the centre (0 centre, 1 corner) where the vignette starts.
+const VIGNETTE_INNER: f32 = 0.35;
+
+fn luminance(c: vec3<f32>) -> f32 {
+    return dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let src = textureSample(screen_texture, screen_sampler, in.uv);
+
+    var color = src.rgb * grading.exposure;
+    color = max((color - MID_GREY) * grading.contrast + MID_GREY, vec3<f32>(0.0));
+    color = mix(vec3<f32>(luminance(color)), color, grading.saturation);
+
+    // Night: pull toward the tint at the same brightness.
+    let tint = grading.night_tint / max(luminance(grading.night_tint), 1e-4);
+    color = mix(color, luminance(color) * tint, grading.night_shift);
+
+    let dist = distance(in.uv, vec2<f32>(0.5)) * 1.41421356;
+    let falloff = smoothstep(VIGNETTE_INNER, 1.0, dist);
+    color *= 1.0 - grading.vignette * falloff;
+
+    return vec4<f32>(max(color, vec3<f32>(0.0)), src.a);
+}
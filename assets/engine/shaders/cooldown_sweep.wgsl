@@ -0,0 +1,26 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+struct CooldownSweep {
+    color: vec4<f32>,
+    fraction: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(1) @binding(0) var<uniform> sweep: CooldownSweep;
+
+const TAU: f32 = 6.28318530718;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    // UI uvs grow downwards: measure the angle clockwise from twelve o'clock,
+    // as a fraction of a full turn.
+    let p = in.uv - vec2<f32>(0.5);
+    let turn = fract(atan2(p.x, -p.y) / TAU + 1.0);
+    // The cleared wedge grows clockwise; the rest is still cooling down.
+    if turn < 1.0 - sweep.fraction {
+        discard;
+    }
+    return sweep.color;
+}
@@ -20,11 +20,11 @@ struct RcUniforms {
     viewport_offset: vec2<u32>,  // 16..24
     viewport_size: vec2<u32>,    // 24..32
     bounce_damping: f32,         // 32..36
-    _pad0: f32,                  // 36..40
+    shadow_softness: f32,        // 36..40  penumbra width for grazing rays (0 = off)
     grid_origin: vec2<i32>,      // 40..48  world-space origin (min_tx, min_ty)
     bounce_offset: vec2<i32>,    // 48..56  offset for lightmap_prev reads on grid snap
     sun_color: vec3<f32>,        // 56..68
-    _pad1: f32,                  // 68..72
+    sdf_max_distance: f32,       // 68..72  tiles encoded by sdf_map = 1.0
 }
 
 @group(0) @binding(0) var<uniform> uniforms: RcUniforms;
@@ -34,9 +34,26 @@ struct RcUniforms {
 @group(0) @binding(4) var lightmap_prev: texture_2d<f32>;
 @group(0) @binding(5) var cascade_read: texture_2d<f32>;
 @group(0) @binding(6) var cascade_write: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(7) var sdf_map: texture_2d<f32>;
 
 const PI: f32 = 3.14159265359;
 const BRANCHING: u32 = 4u;
+// Albedo alpha marking a light filter (tinted glass) instead of a surface
+// albedo. Must match FILTER_ALBEDO_ALPHA in rc_lighting.rs.
+const FILTER_ALPHA: f32 = 128.0 / 255.0;
+
+fn is_light_filter(texel: vec4<f32>) -> bool {
+    return abs(texel.a - FILTER_ALPHA) < 0.002;
+}
+
+// Per-channel factor a ray keeps after crossing a tile.
+fn light_filter(texel: vec4<f32>) -> vec3<f32> {
+    return select(vec3<f32>(1.0), texel.rgb, is_light_filter(texel));
+}
+
+fn max_channel(v: vec3<f32>) -> f32 {
+    return max(v.r, max(v.g, v.b));
+}
 
 // 4^exp via bit-shift: 4^n = 1 << (2n).
 fn pow4(exp: u32) -> u32 {
@@ -154,7 +171,9 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         let ray_dir = vec2<f32>(cos(angle), sin(angle));
 
         var radiance = vec3<f32>(0.0);
-        var transmittance = 1.0; // remaining light energy (1 = full, 0 = fully blocked)
+        // Remaining light energy per channel (1 = full, 0 = fully blocked).
+        // Light filters tint it, so it is a colour rather than a scalar.
+        var transmittance = vec3<f32>(1.0);
         var hit = false;
 
         // Raymarch through the interval [ray_start, ray_end)
@@ -198,7 +217,8 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
             if opacity > 0.01 {
                 // Partially or fully opaque tile — accumulate light and attenuate.
                 let emissive = textureLoad(emissive_map, sample_px, 0).rgb;
-                let albedo = textureLoad(albedo_map, sample_px, 0).rgb;
+                let albedo_texel = textureLoad(albedo_map, sample_px, 0);
+                let albedo = select(albedo_texel.rgb, vec3<f32>(0.0), is_light_filter(albedo_texel));
 
                 // Bounce light: read previous frame's lightmap at hit position.
                 let bounce_px = sample_px + uniforms.bounce_offset;
@@ -211,15 +231,36 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
 
                 let surface_light = emissive + reflected;
                 radiance += surface_light * opacity * transmittance;
-                transmittance *= (1.0 - opacity);
+                transmittance *= (1.0 - opacity) * light_filter(albedo_texel);
 
-                if transmittance < 0.01 {
+                if max_channel(transmittance) < 0.01 {
                     hit = true;
                     break;
                 }
                 continue;
             }
 
+            // Soft shadows: a ray passing `open` tiles from the nearest
+            // occluder after travelling `dist` keeps at most
+            // open / (softness * dist) of its energy, so penumbrae widen
+            // with distance from the occluder instead of cutting off hard.
+            if uniforms.shadow_softness > 0.0 {
+                let open = textureLoad(sdf_map, sample_px, 0).r * uniforms.sdf_max_distance;
+                let visibility = clamp(open / (uniforms.shadow_softness * dist), 0.0, 1.0);
+                transmittance = min(transmittance, vec3<f32>(visibility));
+                if max_channel(transmittance) < 0.01 {
+                    hit = true;
+                    break;
+                }
+            }
+
+            // Tinted glass: clear to the density map, filters per channel.
+            transmittance *= light_filter(textureLoad(albedo_map, sample_px, 0));
+            if max_channel(transmittance) < 0.01 {
+                hit = true;
+                break;
+            }
+
             // Check for emissive air (sun edge emitters, lava glow, etc.)
             let air_emissive = textureLoad(emissive_map, sample_px, 0).rgb;
             let air_brightness = air_emissive.r + air_emissive.g + air_emissive.b;
@@ -9,6 +9,7 @@ struct FinalizeUniforms {
     input_size: vec2<u32>,
     viewport_offset: vec2<u32>,  // unused (kept for struct alignment)
     viewport_size: vec2<u32>,
+    sky_ambient: vec3<f32>,      // ambient sky fill from the sky gradient
 }
 
 @group(0) @binding(0) var<uniform> uniforms: FinalizeUniforms;
@@ -21,6 +22,10 @@ const DIRS_SIDE: u32 = 4u;
 /// HDR brightness multiplier applied to final irradiance.
 const BRIGHTNESS: f32 = 1.5;
 
+/// Irradiance luminance at which a probe gets the full ambient sky fill.
+/// Dimmer probes get proportionally less, so sealed caves stay dark.
+const AMBIENT_FULL_LUMA: f32 = 0.1;
+
 /// Read the average radiance of a single probe (all directions).
 fn probe_radiance(ix: i32, iy: i32) -> vec3<f32> {
     var sum = vec3<f32>(0.0);
@@ -48,7 +53,12 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let iy = i32(py);
 
     // Direct probe readout (no blur).
-    let irradiance = probe_radiance(ix, iy) * BRIGHTNESS;
+    let direct = probe_radiance(ix, iy) * BRIGHTNESS;
+
+    // Ambient sky fill, scaled by how lit the probe already is.
+    let luma = dot(direct, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let coverage = clamp(luma / AMBIENT_FULL_LUMA, 0.0, 1.0);
+    let irradiance = direct + uniforms.sky_ambient * coverage;
 
     textureStore(lightmap_out, vec2<i32>(ix, iy), vec4<f32>(irradiance, 1.0));
 }
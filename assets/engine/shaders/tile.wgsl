@@ -1,30 +1,50 @@
 #import bevy_sprite::mesh2d_functions as mesh_functions
-#import bevy_sprite::mesh2d_view_bindings::view
+#import bevy_sprite::mesh2d_view_bindings::{view, globals}
 
 struct VertexInput {
     @builtin(instance_index) instance_index: u32,
     @location(0) position: vec3<f32>,
     @location(1) uv: vec2<f32>,
+    // 1.0 on the top edge of swaying tiles, 0.0 elsewhere.
+    @location(2) sway: f32,
+    // Per-tile colour variation multiplier, baked at mesh build time.
+    @location(3) tint: vec3<f32>,
+    // Autotile animation: (frames, seconds per frame, UV width of a column).
+    @location(4) anim: vec3<f32>,
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) world_pos: vec2<f32>,
+    @location(2) tint: vec3<f32>,
 }
 
 @vertex
 fn vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     let world_from_local = mesh_functions::get_world_from_local(in.instance_index);
-    out.clip_position = mesh_functions::mesh2d_position_local_to_clip(
-        world_from_local,
-        vec4<f32>(in.position, 1.0),
-    );
+    var world = world_from_local * vec4<f32>(in.position, 1.0);
+
+    // Foliage sway: shift the top edge horizontally. Phase depends on world x
+    // so neighbouring plants don't move in lockstep.
+    if in.sway > 0.0 {
+        let wave = sin(globals.time * sway.speed + world.x * sway.phase);
+        world.x += wave * in.sway * sway.amplitude * sway.wind_scale;
+    }
+
+    out.clip_position = mesh_functions::mesh2d_position_world_to_clip(world);
     out.uv = in.uv;
+    // Animated autotiles keep their frames in the columns right of the
+    // first one; step across them instead of rebuilding the mesh.
+    if in.anim.x > 1.0 && in.anim.y > 0.0 {
+        let frame = floor(globals.time / in.anim.y) % in.anim.x;
+        out.uv.x += frame * in.anim.z;
+    }
     // Pass world position directly — avoids precision loss from
     // clip→NDC→world round-trip that causes subpixel shimmer.
-    out.world_pos = (world_from_local * vec4<f32>(in.position, 1.0)).xy;
+    out.world_pos = world.xy;
+    out.tint = in.tint;
     return out;
 }
 
@@ -34,11 +54,19 @@ struct TileUniforms {
 
 // Pre-computed affine transform: world position → lightmap UV.
 // lightmap_uv = world_pos * lm_xform.xy + lm_xform.zw
-// Computed on CPU from grid_origin, input_size, tile_size.
-// Stable: only changes when the RC grid snaps (every max_spacing tiles).
+// Computed on CPU from grid_origin, input_size and the texel size
+// tx = tile_size / texels_per_tile (see lightmap_uv_transform).
+// Stable: only changes when the RC grid snaps.
 struct LightmapXform {
-    scale: vec2<f32>,   // (1/(ts*input_w), -1/(ts*input_h))
-    offset: vec2<f32>,  // (-grid_origin_x/input_w, 1+grid_origin_y/input_h)
+    scale: vec2<f32>,   // (1/(tx*input_w), -1/(tx*input_h))
+    offset: vec2<f32>,  // (-origin_px_x/(tx*input_w), 1+origin_px_y/(tx*input_h))
+}
+
+struct SwayParams {
+    amplitude: f32,   // max horizontal offset in pixels
+    speed: f32,       // radians per second
+    phase: f32,       // radians per world pixel
+    wind_scale: f32,  // 0..1, driven by weather wind strength
 }
 
 @group(2) @binding(0) var atlas_texture: texture_2d<f32>;
@@ -47,6 +75,7 @@ struct LightmapXform {
 @group(2) @binding(3) var lightmap_texture: texture_2d<f32>;
 @group(2) @binding(4) var lightmap_sampler: sampler;
 @group(2) @binding(5) var<uniform> lm_xform: LightmapXform;
+@group(2) @binding(6) var<uniform> sway: SwayParams;
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
@@ -65,5 +94,5 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 
     let light = textureSample(lightmap_texture, lightmap_sampler, lightmap_uv).rgb;
 
-    return vec4<f32>(color.rgb * light * uniforms.dim, color.a);
+    return vec4<f32>(color.rgb * in.tint * light * uniforms.dim, color.a);
 }
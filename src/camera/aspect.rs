@@ -0,0 +1,145 @@
+//! Optional aspect-ratio lock for the game camera.
+//!
+//! Without it an ultrawide or unusually tall window shows more of the world
+//! than intended, which costs lighting work (the RC grid follows the
+//! viewport) and shows players more than others see. With [`AspectLock`] set
+//! the camera renders into the largest centred viewport of that aspect that
+//! fits the window, and the rest of the window is cleared to black bars.
+//! Systems sizing things to the visible world use [`view_size`].
+
+use bevy::camera::Viewport;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Colour of the letterbox bars.
+const BAR_COLOR: Color = Color::BLACK;
+
+/// Width / height the visible world is locked to, or `None` to fill the
+/// window.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct AspectLock(pub Option<f32>);
+
+/// Largest viewport of `aspect` centred in a window of `window` physical
+/// pixels, as `(position, size)`. Wider windows get bars left and right,
+/// taller ones above and below.
+pub fn locked_viewport(window: UVec2, aspect: f32) -> (UVec2, UVec2) {
+    if window.x == 0 || window.y == 0 || aspect <= 0.0 {
+        return (UVec2::ZERO, window);
+    }
+    let window_aspect = window.x as f32 / window.y as f32;
+    let size = if window_aspect > aspect {
+        UVec2::new(
+            ((window.y as f32 * aspect).round() as u32).min(window.x),
+            window.y,
+        )
+    } else {
+        UVec2::new(
+            window.x,
+            ((window.x as f32 / aspect).round() as u32).min(window.y),
+        )
+    };
+    ((window - size) / 2, size)
+}
+
+/// Logical size of the window area the game camera draws into.
+pub fn view_size(window: &Window, lock: Option<&AspectLock>) -> Vec2 {
+    match lock.and_then(|l| l.0) {
+        Some(aspect) => {
+            let (_, size) = locked_viewport(window.physical_size(), aspect);
+            size.as_vec2() / window.scale_factor()
+        }
+        None => window.size(),
+    }
+}
+
+/// Fit the game camera's viewport to [`AspectLock`] and clear the bars
+/// around it.
+pub fn apply_aspect_lock(
+    lock: Res<AspectLock>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<Camera2d>>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+    let (viewport, clear_color) = match lock.0 {
+        Some(aspect) => {
+            let (physical_position, physical_size) =
+                locked_viewport(window.physical_size(), aspect);
+            let viewport = Viewport {
+                physical_position,
+                physical_size,
+                ..default()
+            };
+            // Clearing covers the whole window, so the bars take the clear
+            // colour.
+            (Some(viewport), ClearColorConfig::Custom(BAR_COLOR))
+        }
+        None => (None, ClearColorConfig::Default),
+    };
+    let unchanged = camera
+        .viewport
+        .as_ref()
+        .map(|v| (v.physical_position, v.physical_size))
+        == viewport
+            .as_ref()
+            .map(|v| (v.physical_position, v.physical_size));
+    if !unchanged {
+        camera.viewport = viewport;
+        camera.clear_color = clear_color;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_viewport_keeps_the_aspect_for_any_window() {
+        let aspect = 16.0 / 9.0;
+        for window in [
+            UVec2::new(1920, 1080),
+            UVec2::new(3440, 1440),
+            UVec2::new(1080, 1920),
+            UVec2::new(1280, 1024),
+            UVec2::new(5120, 1440),
+        ] {
+            let (pos, size) = locked_viewport(window, aspect);
+            let got = size.x as f32 / size.y as f32;
+            assert!((got - aspect).abs() < 0.01, "{window}: {size}");
+            assert!(size.x <= window.x && size.y <= window.y);
+            // Fills one axis and is centred on the other.
+            assert!(size.x == window.x || size.y == window.y);
+            assert_eq!(pos * 2 + size, window - (window - size) % 2);
+        }
+    }
+
+    #[test]
+    fn ultrawide_gets_pillarbox_and_tall_gets_letterbox() {
+        let (pos, size) = locked_viewport(UVec2::new(3440, 1440), 16.0 / 9.0);
+        assert_eq!(size, UVec2::new(2560, 1440));
+        assert_eq!(pos, UVec2::new(440, 0));
+
+        let (pos, size) = locked_viewport(UVec2::new(1280, 1024), 16.0 / 9.0);
+        assert_eq!(size, UVec2::new(1280, 720));
+        assert_eq!(pos, UVec2::new(0, 152));
+    }
+
+    #[test]
+    fn visible_world_stays_bounded_as_the_window_widens() {
+        let lock = AspectLock(Some(16.0 / 9.0));
+        let mut narrow = Window::default();
+        narrow.resolution.set(1920.0, 1080.0);
+        let mut wide = Window::default();
+        wide.resolution.set(5760.0, 1080.0);
+
+        assert_eq!(
+            view_size(&wide, Some(&lock)),
+            view_size(&narrow, Some(&lock))
+        );
+        assert_eq!(view_size(&wide, None), Vec2::new(5760.0, 1080.0));
+    }
+}
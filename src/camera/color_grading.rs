@@ -0,0 +1,457 @@
+//! Full-screen colour grading: exposure, contrast and saturation, a vignette
+//! that closes in as the player goes deeper, and a blue shift at night.
+//!
+//! [`ColorGrading`] holds the settings in the main world.
+//! [`update_color_grading`] feeds it the player's depth and the time of day
+//! each frame, and it is extracted to the render world, where a fullscreen
+//! pass (`color_grading.wgsl`) runs over the 2D view after tonemapping.
+//! Turning [`ColorGrading::enabled`] off skips the pass, leaving the raw
+//! image.
+
+use bevy::core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy::core_pipeline::FullscreenShader;
+use bevy::ecs::query::QueryItem;
+use bevy::image::BevyDefault;
+use bevy::prelude::*;
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_graph::{
+    NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    encase, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
+    BufferInitDescriptor, BufferUsages, CachedRenderPipelineId, ColorTargetState, ColorWrites,
+    FragmentState, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    ShaderType, TextureFormat, TextureSampleType,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice};
+use bevy::render::view::ViewTarget;
+use bevy::render::{RenderApp, RenderStartup};
+use serde::{Deserialize, Serialize};
+
+use crate::player::Player;
+use crate::world::chunk::world_to_tile;
+use crate::world::ctx::WorldCtx;
+use crate::world::day_night::{DayPhase, WorldTime};
+
+/// Colour grading settings, plus the depth and night values the pass
+/// currently grades for.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize, ExtractResource)]
+#[serde(default)]
+pub struct ColorGrading {
+    /// Off skips the pass entirely.
+    pub enabled: bool,
+    /// Brightness change in stops.
+    pub exposure: f32,
+    /// 1.0 leaves contrast unchanged.
+    pub contrast: f32,
+    /// 0.0 is greyscale, 1.0 leaves colours unchanged.
+    pub saturation: f32,
+    /// Vignette strength at depths above `vignette_start_depth`.
+    pub vignette_surface: f32,
+    /// Vignette strength at depths below `vignette_full_depth`.
+    pub vignette_deep: f32,
+    /// Depth fraction where the vignette starts to strengthen.
+    pub vignette_start_depth: f32,
+    /// Depth fraction where it reaches `vignette_deep`.
+    pub vignette_full_depth: f32,
+    /// Colour the image shifts toward at night.
+    pub night_tint: [f32; 3],
+    /// How far toward `night_tint` the image shifts at full night.
+    pub night_shift: f32,
+    /// Player's depth fraction, set every frame.
+    #[serde(skip)]
+    pub depth: f32,
+    /// How much of night it is (0.0–1.0), set every frame.
+    #[serde(skip)]
+    pub night: f32,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exposure: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            vignette_surface: 0.0,
+            vignette_deep: 0.6,
+            vignette_start_depth: 0.3,
+            vignette_full_depth: 0.9,
+            night_tint: [0.55, 0.65, 1.0],
+            night_shift: 0.25,
+            depth: 0.0,
+            night: 0.0,
+        }
+    }
+}
+
+impl ColorGrading {
+    /// Vignette strength at `depth` (0.0 at the top of the world, 1.0 at
+    /// the bottom): `vignette_surface` down to `vignette_start_depth`, then
+    /// easing into `vignette_deep` at `vignette_full_depth`.
+    pub fn depth_vignette(&self, depth: f32) -> f32 {
+        let span = self.vignette_full_depth - self.vignette_start_depth;
+        let t = if span <= 0.0 {
+            if depth >= self.vignette_full_depth {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            ((depth - self.vignette_start_depth) / span).clamp(0.0, 1.0)
+        };
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.vignette_surface + (self.vignette_deep - self.vignette_surface) * eased
+    }
+
+    /// Shader parameters for the current depth and time of day.
+    pub fn uniform(&self) -> ColorGradingUniform {
+        ColorGradingUniform {
+            night_tint: Vec3::from_array(self.night_tint),
+            exposure: self.exposure.exp2(),
+            contrast: self.contrast,
+            saturation: self.saturation,
+            vignette: self.depth_vignette(self.depth).clamp(0.0, 1.0),
+            night_shift: (self.night_shift * self.night).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Uniforms for `color_grading.wgsl`; must match its layout.
+#[derive(ShaderType, Debug, Clone, Copy, PartialEq)]
+pub struct ColorGradingUniform {
+    pub night_tint: Vec3,
+    /// Linear brightness multiplier.
+    pub exposure: f32,
+    pub contrast: f32,
+    pub saturation: f32,
+    pub vignette: f32,
+    pub night_shift: f32,
+}
+
+/// How much of night it is: 0.0 through the day, rising over sunset to 1.0
+/// at night and falling back over dawn.
+pub fn night_amount(phase: DayPhase, progress: f32) -> f32 {
+    match phase {
+        DayPhase::Dawn => 1.0 - progress,
+        DayPhase::Day => 0.0,
+        DayPhase::Sunset => progress,
+        DayPhase::Night => 1.0,
+    }
+    .clamp(0.0, 1.0)
+}
+
+/// Track the player's depth and the time of day for the grading pass.
+pub fn update_color_grading(
+    ctx: WorldCtx,
+    player: Query<&Transform, With<Player>>,
+    world_time: Option<Res<WorldTime>>,
+    mut grading: ResMut<ColorGrading>,
+) {
+    let night = world_time.map_or(0.0, |wt| night_amount(wt.phase, wt.phase_progress));
+    let depth = player.single().map_or(grading.depth, |tf| {
+        let ctx_ref = ctx.as_ref();
+        let (_, ty) = world_to_tile(tf.translation.x, tf.translation.y, ctx_ref.config.tile_size);
+        ctx_ref.depth_fraction(ty)
+    });
+    // Only write on change so the extract doesn't copy an unchanged resource.
+    if grading.depth != depth || grading.night != night {
+        grading.depth = depth;
+        grading.night = night;
+    }
+}
+
+/// Registers the resource and the render-side pass. Called from
+/// `CameraPlugin::build`.
+pub(crate) fn setup_color_grading(app: &mut App) {
+    app.init_resource::<ColorGrading>()
+        .add_plugins(ExtractResourcePlugin::<ColorGrading>::default());
+
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    render_app
+        .add_systems(RenderStartup, init_color_grading_pipeline)
+        .add_render_graph_node::<ViewNodeRunner<ColorGradingNode>>(Core2d, ColorGradingLabel)
+        .add_render_graph_edges(
+            Core2d,
+            (
+                Node2d::Tonemapping,
+                ColorGradingLabel,
+                Node2d::EndMainPassPostProcessing,
+            ),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, RenderLabel)]
+struct ColorGradingLabel;
+
+/// Layout, sampler and pipeline IDs, one pipeline per view target format.
+#[derive(Resource)]
+struct ColorGradingPipeline {
+    layout: BindGroupLayoutDescriptor,
+    sampler: Sampler,
+    pipeline: CachedRenderPipelineId,
+    pipeline_hdr: CachedRenderPipelineId,
+}
+
+fn init_color_grading_pipeline(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    asset_server: Res<AssetServer>,
+    fullscreen_shader: Res<FullscreenShader>,
+    pipeline_cache: Res<PipelineCache>,
+) {
+    // Matches color_grading.wgsl @group(0).
+    let layout = BindGroupLayoutDescriptor::new(
+        "color_grading_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_2d(TextureSampleType::Float { filterable: true }), // @binding(0) screen
+                sampler(SamplerBindingType::Filtering),                    // @binding(1)
+                uniform_buffer::<ColorGradingUniform>(false),              // @binding(2)
+            ),
+        ),
+    );
+    let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+    let shader = asset_server.load("engine/shaders/color_grading.wgsl");
+
+    let mut desc = RenderPipelineDescriptor {
+        label: Some("color_grading_pipeline".into()),
+        layout: vec![layout.clone()],
+        vertex: fullscreen_shader.to_vertex_state(),
+        fragment: Some(FragmentState {
+            shader,
+            targets: vec![Some(ColorTargetState {
+                format: TextureFormat::bevy_default(),
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            })],
+            ..default()
+        }),
+        ..default()
+    };
+    let pipeline = pipeline_cache.queue_render_pipeline(desc.clone());
+    if let Some(target) = desc
+        .fragment
+        .as_mut()
+        .and_then(|fragment| fragment.targets[0].as_mut())
+    {
+        target.format = ViewTarget::TEXTURE_FORMAT_HDR;
+    }
+    let pipeline_hdr = pipeline_cache.queue_render_pipeline(desc);
+
+    commands.insert_resource(ColorGradingPipeline {
+        layout,
+        sampler,
+        pipeline,
+        pipeline_hdr,
+    });
+}
+
+#[derive(Default)]
+struct ColorGradingNode;
+
+impl ViewNode for ColorGradingNode {
+    type ViewQuery = &'static ViewTarget;
+
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        view_target: QueryItem<'w, '_, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Some(grading) = world.get_resource::<ColorGrading>() else {
+            return Ok(());
+        };
+        if !grading.enabled {
+            return Ok(());
+        }
+        let Some(grading_pipeline) = world.get_resource::<ColorGradingPipeline>() else {
+            return Ok(());
+        };
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let pipeline_id = if view_target.is_hdr() {
+            grading_pipeline.pipeline_hdr
+        } else {
+            grading_pipeline.pipeline
+        };
+        // Still compiling (or failed to): show the ungraded image.
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id) else {
+            return Ok(());
+        };
+
+        let mut uniform_buf = encase::UniformBuffer::new(Vec::<u8>::new());
+        if let Err(e) = uniform_buf.write(&grading.uniform()) {
+            warn!("Color grading uniform write failed: {e}");
+            return Ok(());
+        }
+        let render_device = render_context.render_device();
+        let uniform = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("color_grading_uniform"),
+            contents: &uniform_buf.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let post_process = view_target.post_process_write();
+        let bind_group = render_device.create_bind_group(
+            "color_grading_bind_group",
+            &pipeline_cache.get_bind_group_layout(&grading_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &grading_pipeline.sampler,
+                uniform.as_entire_binding(),
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("color_grading_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    #[test]
+    fn vignette_strengthens_with_depth() {
+        let grading = ColorGrading::default();
+        assert_eq!(grading.depth_vignette(0.0), grading.vignette_surface);
+        assert_eq!(
+            grading.depth_vignette(grading.vignette_start_depth),
+            grading.vignette_surface
+        );
+        assert_eq!(grading.depth_vignette(1.0), grading.vignette_deep);
+        assert_eq!(
+            grading.depth_vignette(grading.vignette_full_depth),
+            grading.vignette_deep
+        );
+
+        let mid = (grading.vignette_start_depth + grading.vignette_full_depth) / 2.0;
+        let halfway = (grading.vignette_surface + grading.vignette_deep) / 2.0;
+        assert!((grading.depth_vignette(mid) - halfway).abs() < 1e-5);
+
+        let samples: Vec<f32> = (0..=20)
+            .map(|i| grading.depth_vignette(i as f32 / 20.0))
+            .collect();
+        assert!(samples.windows(2).all(|w| w[0] <= w[1]), "{samples:?}");
+
+        // A zero-width ramp steps straight to the deep strength.
+        let step = ColorGrading {
+            vignette_start_depth: 0.5,
+            vignette_full_depth: 0.5,
+            ..default()
+        };
+        assert_eq!(step.depth_vignette(0.49), step.vignette_surface);
+        assert_eq!(step.depth_vignette(0.5), step.vignette_deep);
+    }
+
+    #[test]
+    fn night_amount_follows_the_day_cycle() {
+        assert_eq!(night_amount(DayPhase::Day, 0.7), 0.0);
+        assert_eq!(night_amount(DayPhase::Sunset, 0.25), 0.25);
+        assert_eq!(night_amount(DayPhase::Night, 0.5), 1.0);
+        assert_eq!(night_amount(DayPhase::Dawn, 0.25), 0.75);
+        // Continuous across phase changes.
+        assert_eq!(night_amount(DayPhase::Sunset, 1.0), 1.0);
+        assert_eq!(night_amount(DayPhase::Dawn, 1.0), 0.0);
+    }
+
+    #[test]
+    fn settings_round_trip_and_reach_the_uniform() {
+        let grading = ColorGrading {
+            enabled: false,
+            exposure: 1.0,
+            contrast: 1.2,
+            saturation: 0.5,
+            vignette_deep: 0.8,
+            night_tint: [0.2, 0.3, 0.9],
+            night_shift: 0.4,
+            depth: 0.7,
+            night: 0.5,
+            ..default()
+        };
+        let text = ron::ser::to_string(&grading).unwrap();
+        let loaded: ColorGrading = ron::de::from_str(&text).unwrap();
+        // The live depth and night values aren't settings.
+        assert_eq!(
+            loaded,
+            ColorGrading {
+                depth: 0.0,
+                night: 0.0,
+                ..grading.clone()
+            }
+        );
+        // Missing fields take their defaults.
+        let partial: ColorGrading = ron::de::from_str("(saturation: 0.0)").unwrap();
+        assert_eq!(
+            partial,
+            ColorGrading {
+                saturation: 0.0,
+                ..default()
+            }
+        );
+
+        let uniform = grading.uniform();
+        assert_eq!(uniform.exposure, 2.0);
+        assert_eq!(uniform.contrast, 1.2);
+        assert_eq!(uniform.saturation, 0.5);
+        assert_eq!(uniform.night_tint, Vec3::new(0.2, 0.3, 0.9));
+        assert_eq!(uniform.vignette, grading.depth_vignette(0.7));
+        assert!((uniform.night_shift - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn grading_tracks_player_depth_and_night() {
+        let mut app = fixtures::test_app();
+        app.init_resource::<ColorGrading>()
+            .insert_resource(WorldTime {
+                phase: DayPhase::Night,
+                ..default()
+            })
+            .add_systems(Update, update_color_grading);
+        let ts = 32.0;
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_xyz(0.0, 100.5 * ts, 0.0)))
+            .id();
+        app.update();
+
+        let grading = app.world().resource::<ColorGrading>();
+        assert!((grading.depth - (1023.0 - 100.0) / 1023.0).abs() < 1e-6);
+        assert_eq!(grading.night, 1.0);
+        assert!(grading.uniform().vignette > 0.5);
+
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .y = 1000.5 * ts;
+        app.world_mut().resource_mut::<WorldTime>().phase = DayPhase::Day;
+        app.update();
+        let grading = app.world().resource::<ColorGrading>();
+        assert!(grading.depth < 0.05);
+        assert_eq!(grading.night, 0.0);
+        assert_eq!(grading.uniform().vignette, grading.vignette_surface);
+    }
+}
@@ -1,17 +1,79 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use crate::physics::Velocity;
 use crate::player::Player;
+use crate::registry::biome::PlanetConfig;
 use crate::registry::world::ActiveWorld;
+use crate::ui::input_capture::InputCapture;
 
-#[allow(clippy::type_complexity)]
+use super::aspect::{view_size, AspectLock};
+
+/// Tiles of world border the camera may show past the edge of a walled,
+/// non-wrapping world.
+const BORDER_VIEW_TILES: f32 = 2.0;
+
+/// Vertical framing of the player: the camera sits below them so more of
+/// the ground being dug into is on screen, and further below while looking
+/// down. Chunk streaming and lighting follow the camera, so they cover the
+/// shifted view too.
+#[derive(Resource, Debug, Clone)]
+pub struct CameraOffset {
+    /// Pixels the camera always sits below the player; negative raises it.
+    pub below: f32,
+    /// Extra pixels below while looking down.
+    pub look_down: f32,
+    /// Key held to look down; `None` leaves looking down to falling.
+    pub look_down_key: Option<KeyCode>,
+    /// Falling faster than this (px/s) looks down too; `None` turns it off.
+    pub look_down_fall_speed: Option<f32>,
+    /// How quickly the camera eases to a new offset (1/s).
+    pub ease_rate: f32,
+}
+
+impl Default for CameraOffset {
+    fn default() -> Self {
+        Self {
+            below: 32.0,
+            look_down: 160.0,
+            look_down_key: Some(KeyCode::KeyZ),
+            // Faster than a jump comes back down, so only long drops count.
+            look_down_fall_speed: Some(600.0),
+            ease_rate: 6.0,
+        }
+    }
+}
+
+impl CameraOffset {
+    /// Pixels below the player the camera aims at.
+    pub fn target(&self, looking_down: bool) -> f32 {
+        if looking_down {
+            self.below + self.look_down
+        } else {
+            self.below
+        }
+    }
+}
+
+/// Camera Y for a player at `player_y` with the camera `offset` px below
+/// them, kept inside the world's vertical bounds.
+pub fn follow_y(player_y: f32, offset: f32, half_h: f32, world_h: f32) -> f32 {
+    (player_y - offset).clamp(half_h, (world_h - half_h).max(half_h))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn camera_follow_player(
-    player_query: Query<&Transform, (With<Player>, Without<Camera2d>)>,
+    player_query: Query<(&Transform, Option<&Velocity>), (With<Player>, Without<Camera2d>)>,
     mut camera_query: Query<(&mut Transform, &Projection), (With<Camera2d>, Without<Player>)>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    aspect_lock: Option<Res<AspectLock>>,
     world_config: Res<ActiveWorld>,
+    planet_config: Option<Res<PlanetConfig>>,
+    offset: Res<CameraOffset>,
+    look: (Res<ButtonInput<KeyCode>>, Res<InputCapture>, Res<Time>),
+    mut current_offset: Local<Option<f32>>,
 ) {
-    let Ok(player_transform) = player_query.single() else {
+    let Ok((player_transform, velocity)) = player_query.single() else {
         return;
     };
     let Ok((mut camera_transform, projection)) = camera_query.single_mut() else {
@@ -25,15 +87,36 @@ pub fn camera_follow_player(
         Projection::Orthographic(ortho) => ortho.scale,
         _ => 1.0,
     };
-    let half_h = window.height() / 2.0 * proj_scale;
+    let view = view_size(window, aspect_lock.as_deref());
+    let half_h = view.y / 2.0 * proj_scale;
     let world_h = world_config.world_pixel_height();
 
+    let (keyboard, capture, time) = look;
+    let key_held = !capture.keyboard
+        && offset
+            .look_down_key
+            .is_some_and(|key| keyboard.pressed(key));
+    let falling = offset
+        .look_down_fall_speed
+        .zip(velocity)
+        .is_some_and(|(speed, v)| v.y < -speed);
+    let wanted = offset.target(key_held || falling);
+    let current = current_offset.get_or_insert(offset.below);
+    *current += (wanted - *current) * (1.0 - (-offset.ease_rate * time.delta_secs()).exp());
+
     let mut target = player_transform.translation;
-    target.y = target.y.clamp(half_h, (world_h - half_h).max(half_h));
+    target.y = follow_y(target.y, *current, half_h, world_h);
 
-    // Clamp camera X for non-wrapping worlds so it doesn't scroll past edges
+    // Clamp camera X for non-wrapping worlds so it doesn't scroll past edges,
+    // apart from a glimpse of the border wall if the planet has one.
     if !world_config.wrap_x {
-        let half_w = window.width() / 2.0 * proj_scale;
+        let walled = planet_config.is_some_and(|p| p.border_tile.is_some());
+        let border = if walled {
+            BORDER_VIEW_TILES * world_config.tile_size
+        } else {
+            0.0
+        };
+        let half_w = view.x / 2.0 * proj_scale - border;
         let world_w = world_config.world_pixel_width();
         target.x = target.x.clamp(half_w, (world_w - half_w).max(half_w));
     }
@@ -44,3 +127,37 @@ pub fn camera_follow_player(
     camera_transform.translation.x = (target.x / pixel).round() * pixel;
     camera_transform.translation.y = (target.y / pixel).round() * pixel;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_moves_the_camera_target_below_the_player() {
+        let (half_h, world_h) = (300.0, 8192.0);
+        assert_eq!(follow_y(1000.0, 0.0, half_h, world_h), 1000.0);
+        assert_eq!(follow_y(1000.0, 48.0, half_h, world_h), 952.0);
+        assert_eq!(follow_y(1000.0, -48.0, half_h, world_h), 1048.0);
+
+        let offset = CameraOffset {
+            below: 32.0,
+            look_down: 160.0,
+            ..default()
+        };
+        assert_eq!(offset.target(false), 32.0);
+        assert_eq!(offset.target(true), 192.0);
+        assert_eq!(
+            follow_y(1000.0, offset.target(true), half_h, world_h),
+            808.0
+        );
+    }
+
+    #[test]
+    fn offset_target_stays_inside_the_world_bounds() {
+        let (half_h, world_h) = (300.0, 8192.0);
+        // Near the bottom the view can't drop below the world.
+        assert_eq!(follow_y(320.0, 192.0, half_h, world_h), half_h);
+        // Near the top a raised camera still stops at the sky edge.
+        assert_eq!(follow_y(8000.0, -192.0, half_h, world_h), world_h - half_h);
+    }
+}
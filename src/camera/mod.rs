@@ -1,3 +1,5 @@
+pub mod aspect;
+pub mod color_grading;
 pub mod follow;
 pub mod snap;
 
@@ -6,6 +8,7 @@ use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
 use bevy_egui::PrimaryEguiContext;
 
+use crate::inventory::HotbarScroll;
 use crate::player::respawn_player_on_warp;
 use crate::registry::AppState;
 use crate::sets::GameSet;
@@ -20,7 +23,9 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Loading), spawn_camera)
+        app.init_resource::<aspect::AspectLock>()
+            .init_resource::<follow::CameraOffset>()
+            .add_systems(OnEnter(AppState::Loading), spawn_camera)
             .add_systems(
                 OnEnter(AppState::InGame),
                 snap::snap_camera_to_player.after(respawn_player_on_warp),
@@ -28,12 +33,17 @@ impl Plugin for CameraPlugin {
             .add_systems(
                 Update,
                 (
+                    aspect::apply_aspect_lock.in_set(GameSet::Camera),
                     camera_zoom.in_set(GameSet::Camera),
                     follow::camera_follow_player
                         .after(camera_zoom)
                         .in_set(GameSet::Camera),
+                    color_grading::update_color_grading
+                        .after(follow::camera_follow_player)
+                        .in_set(GameSet::Camera),
                 ),
             );
+        color_grading::setup_color_grading(app);
     }
 }
 
@@ -52,8 +62,11 @@ fn camera_zoom(
     mut scroll_events: MessageReader<MouseWheel>,
     mut camera_query: Query<&mut Projection, With<Camera2d>>,
     chat_state: Res<crate::chat::ChatState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotbar_scroll: Res<HotbarScroll>,
 ) {
-    if chat_state.is_active {
+    // Without the zoom modifier the wheel belongs to the hotbar.
+    if chat_state.keyboard_captured() || !hotbar_scroll.wheel_zooms(&keyboard) {
         // Consume events so they don't queue up
         scroll_events.read().count();
         return;
@@ -1,8 +1,8 @@
 //! One-shot camera snap that runs on `OnEnter(InGame)`.
 //!
 //! Ensures the camera is positioned on the player *before* the first `Update`
-//! frame.  Without this, `chunk_loading_system` (which runs in
-//! `GameSet::WorldUpdate`, before `GameSet::Camera`) would use the stale camera
+//! frame.  Without this, `chunk_loading_system` (which runs in `WorldSet::Gen`
+//! of `GameSet::WorldUpdate`, before `GameSet::Camera`) would use the stale camera
 //! position from the previous world, loading chunks in the wrong area and
 //! causing a lightmap-coverage mismatch (permanent darkness on first chunks).
 
@@ -12,6 +12,9 @@ use bevy::window::PrimaryWindow;
 use crate::player::Player;
 use crate::registry::world::ActiveWorld;
 
+use super::aspect::{view_size, AspectLock};
+use super::follow::{follow_y, CameraOffset};
+
 /// Immediately places the camera at the player position with proper Y clamping.
 ///
 /// Mirrors the logic of [`super::follow::camera_follow_player`] so that the
@@ -20,7 +23,9 @@ pub fn snap_camera_to_player(
     player_query: Query<&Transform, (With<Player>, Without<Camera2d>)>,
     mut camera_query: Query<(&mut Transform, &Projection), (With<Camera2d>, Without<Player>)>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    aspect_lock: Option<Res<AspectLock>>,
     world_config: Res<ActiveWorld>,
+    offset: Option<Res<CameraOffset>>,
 ) {
     let Ok(player_tf) = player_query.single() else {
         return;
@@ -36,12 +41,14 @@ pub fn snap_camera_to_player(
         Projection::Orthographic(ortho) => ortho.scale,
         _ => 1.0,
     };
+    let view = view_size(window, aspect_lock.as_deref());
 
-    let half_h = window.height() / 2.0 * proj_scale;
+    let half_h = view.y / 2.0 * proj_scale;
     let world_h = world_config.world_pixel_height();
 
     let mut target = player_tf.translation;
-    target.y = target.y.clamp(half_h, (world_h - half_h).max(half_h));
+    let below = offset.map_or(0.0, |o| o.below);
+    target.y = follow_y(target.y, below, half_h, world_h);
 
     let pixel = proj_scale;
     cam_tf.translation.x = (target.x / pixel).round() * pixel;
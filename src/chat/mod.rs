@@ -22,7 +22,6 @@ pub struct ChatMessage {
 
 /// Event fired when the player enters a `/command`.
 #[derive(Message, Debug, Clone)]
-#[allow(dead_code)]
 pub struct ChatCommandEvent {
     pub command: String,
     pub args: Vec<String>,
@@ -33,6 +32,9 @@ pub struct ChatCommandEvent {
 pub struct ChatState {
     pub messages: Vec<ChatMessage>,
     pub is_active: bool,
+    /// Another UI text field (e.g. the creative catalog search) has keyboard
+    /// focus; Enter doesn't open chat while it is set.
+    pub text_field_focused: bool,
     pub input_buffer: String,
     pub scroll_offset: i32,
     pub max_messages: usize,
@@ -43,12 +45,19 @@ impl ChatState {
         Self {
             messages: Vec::new(),
             is_active: false,
+            text_field_focused: false,
             input_buffer: String::new(),
             scroll_offset: 0,
             max_messages,
         }
     }
 
+    /// True while typing goes to chat or another text field, so gameplay key
+    /// bindings should be ignored.
+    pub fn keyboard_captured(&self) -> bool {
+        self.is_active || self.text_field_focused
+    }
+
     /// Add a system message.
     pub fn send_system(&mut self, text: &str, time: f64) {
         self.push(text.to_string(), MessageCategory::System, time);
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
 use super::{Health, InvincibilityTimer};
+use crate::game_mode::GameMode;
 use crate::physics::Velocity;
+use crate::player::Player;
 
 const INVINCIBILITY_DURATION: f32 = 0.5;
 
@@ -15,13 +17,15 @@ pub struct DamageEvent {
 pub fn process_damage(
     mut commands: Commands,
     mut reader: bevy::ecs::message::MessageReader<DamageEvent>,
-    mut query: Query<(&mut Health, Option<&InvincibilityTimer>)>,
+    mut query: Query<(&mut Health, Option<&InvincibilityTimer>, Has<Player>)>,
+    game_mode: Res<GameMode>,
 ) {
     for event in reader.read() {
-        let Ok((mut health, invincibility)) = query.get_mut(event.target) else {
+        let Ok((mut health, invincibility, is_player)) = query.get_mut(event.target) else {
             continue;
         };
-        if invincibility.is_some() {
+        // The player takes no damage in creative.
+        if invincibility.is_some() || (is_player && game_mode.is_creative()) {
             continue;
         }
         health.take_damage(event.amount);
@@ -33,13 +37,14 @@ pub fn process_damage(
 
 pub fn apply_damage_knockback(
     mut reader: bevy::ecs::message::MessageReader<DamageEvent>,
-    mut query: Query<(&mut Velocity, Option<&InvincibilityTimer>)>,
+    mut query: Query<(&mut Velocity, Option<&InvincibilityTimer>, Has<Player>)>,
+    game_mode: Res<GameMode>,
 ) {
     for event in reader.read() {
-        let Ok((mut vel, invincibility)) = query.get_mut(event.target) else {
+        let Ok((mut vel, invincibility, is_player)) = query.get_mut(event.target) else {
             continue;
         };
-        if invincibility.is_some() {
+        if invincibility.is_some() || (is_player && game_mode.is_creative()) {
             continue;
         }
         vel.x += event.knockback.x;
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
 use super::Health;
+use crate::physics::Velocity;
 use crate::player::Player;
+use crate::world::spawn_point::WorldSpawnPoint;
 
 #[derive(Message, Debug)]
 pub struct PlayerDeathEvent;
@@ -17,13 +19,22 @@ pub fn detect_player_death(
     }
 }
 
+/// Heal the player and put them back at the world's spawn point.
 pub fn handle_player_death(
     mut reader: bevy::ecs::message::MessageReader<PlayerDeathEvent>,
-    mut query: Query<&mut Health, With<Player>>,
+    spawn_point: Option<Res<WorldSpawnPoint>>,
+    mut query: Query<(&mut Health, &mut Transform, Option<&mut Velocity>), With<Player>>,
 ) {
     for _event in reader.read() {
-        for mut health in &mut query {
+        for (mut health, mut transform, velocity) in &mut query {
             health.current = health.max;
+            if let Some(spawn) = &spawn_point {
+                transform.translation.x = spawn.position.x;
+                transform.translation.y = spawn.position.y;
+                if let Some(mut velocity) = velocity {
+                    *velocity = Velocity::default();
+                }
+            }
         }
         warn!("Player died! Respawning...");
     }
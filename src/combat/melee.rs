@@ -4,6 +4,7 @@ use crate::enemy::Enemy;
 use crate::inventory::Hotbar;
 use crate::item::ItemRegistry;
 use crate::player::Player;
+use crate::ui::input_capture::InputCapture;
 
 use super::ranged::is_ranged_weapon;
 use super::DamageEvent;
@@ -32,16 +33,19 @@ impl Default for MeleeAttack {
 pub fn melee_attack_system(
     time: Res<Time>,
     mouse: Res<ButtonInput<MouseButton>>,
+    capture: Res<InputCapture>,
     item_registry: Option<Res<ItemRegistry>>,
     mut writer: bevy::ecs::message::MessageWriter<DamageEvent>,
     mut player_query: Query<(&Transform, &Hotbar, &mut MeleeAttack), With<Player>>,
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
 ) {
     let dt = time.delta_secs();
+    // Clicks on a UI panel, or while typing, must not reach the world.
+    let clicked = mouse.just_pressed(MouseButton::Left) && !capture.pointer && !capture.keyboard;
 
     for (player_tf, hotbar, mut melee) in &mut player_query {
         melee.timer -= dt;
-        if melee.timer > 0.0 || !mouse.just_pressed(MouseButton::Left) {
+        if melee.timer > 0.0 || !clicked {
             continue;
         }
 
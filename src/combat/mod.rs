@@ -9,7 +9,7 @@ pub mod projectile;
 pub mod ranged;
 
 use bevy::prelude::*;
-use crate::sets::GameSet;
+use crate::sets::{GameSet, WorldSet};
 
 pub use block_damage::*;
 pub use damage::*;
@@ -23,7 +23,7 @@ impl Plugin for CombatPlugin {
         app.init_resource::<block_damage::BlockDamageMap>()
             .add_systems(
                 Update,
-                block_damage::tick_block_damage_regen.in_set(GameSet::WorldUpdate),
+                block_damage::tick_block_damage_regen.in_set(WorldSet::Sim),
             )
             .add_message::<DamageEvent>()
             .add_message::<PlayerDeathEvent>()
@@ -54,16 +54,16 @@ impl Plugin for CombatPlugin {
                 Update,
                 (
                     melee::melee_attack_system,
-                    ranged::ranged_attack_system,
+                    ranged::ranged_attack_system
+                        .after(crate::interaction::target::target_resolution),
                 )
                     .in_set(GameSet::Input),
             )
             .add_systems(
                 Update,
-                (
-                    projectile::tick_projectiles,
-                    projectile::projectile_hit_detection,
-                )
+                projectile::apply_projectile_damage
+                    .after(crate::projectile::move_projectiles)
+                    .before(damage::process_damage)
                     .in_set(GameSet::Physics),
             )
             .add_systems(
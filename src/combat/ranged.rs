@@ -1,9 +1,10 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
+use crate::interaction::target::TargetTile;
 use crate::inventory::{Hotbar, Inventory};
 use crate::item::ItemRegistry;
 use crate::player::Player;
+use crate::ui::input_capture::InputCapture;
 
 use super::projectile;
 
@@ -17,13 +18,14 @@ pub fn is_ranged_weapon(item_id: &str) -> bool {
 
 pub fn ranged_attack_system(
     mouse: Res<ButtonInput<MouseButton>>,
+    capture: Res<InputCapture>,
     item_registry: Option<Res<ItemRegistry>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    target: Res<TargetTile>,
     mut commands: Commands,
     mut player_query: Query<(Entity, &GlobalTransform, &Hotbar, &mut Inventory), With<Player>>,
 ) {
-    if !mouse.just_pressed(MouseButton::Left) {
+    // Clicks on a UI panel, or while typing, must not reach the world.
+    if !mouse.just_pressed(MouseButton::Left) || capture.pointer || capture.keyboard {
         return;
     }
 
@@ -31,17 +33,8 @@ pub fn ranged_attack_system(
         return;
     };
 
-    // Resolve cursor world position
-    let Ok(window) = window_query.single() else {
-        return;
-    };
-    let Some(cursor_pos) = window.cursor_position() else {
-        return;
-    };
-    let Ok((camera, camera_gt)) = camera_query.single() else {
-        return;
-    };
-    let Ok(world_pos) = camera.viewport_to_world_2d(camera_gt, cursor_pos) else {
+    // Aim at the cursor's world position
+    let Some(world_pos) = target.0.map(|target| target.world_pos) else {
         return;
     };
 
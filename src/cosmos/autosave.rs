@@ -0,0 +1,990 @@
+//! Crash recovery: the current world's modified chunks and the player are
+//! journaled to disk every few minutes, so a crash costs at most one interval
+//! of play.
+//!
+//! The journal is a directory of chunk files and a manifest naming them.
+//! [`autosave`] clones only the chunks modified since the previous autosave
+//! (by their [`ChunkRevisions`] stamp) and hands them to a background task,
+//! which writes each under a fresh name through a temporary file and a
+//! rename, commits the manifest the same way and only then deletes the files
+//! the new manifest no longer names. Until that last rename the previous
+//! manifest and every file it names stay intact, so a crash mid-write leaves
+//! the last complete autosave behind. A toast reports how each autosave
+//! went.
+//!
+//! A journal newer than the main save is offered from the main menu at
+//! launch. Restoring it puts its chunks into the [`Universe`], warps to its
+//! world if the game starts elsewhere and puts the player back. A clean exit
+//! from the game stamps the main save and removes the journal, so there is
+//! nothing to offer at the next launch.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::address::CelestialAddress;
+use super::chunk_format::{decode_chunk, encode_chunk, ChunkCompression, ChunkFormatError};
+use super::persistence::{ChunkRevisions, DirtyChunks, Universe, WorldSave};
+use super::warp::{WarpToBody, WarpToShip};
+use crate::combat::Health;
+use crate::crafting::KnownRecipes;
+use crate::game_mode::GameMode;
+use crate::inventory::{BagCapacity, Equipment, Inventory, InventorySlot};
+use crate::item::EquipmentSlot;
+use crate::player::Player;
+use crate::registry::world::ActiveWorld;
+use crate::registry::AppState;
+use crate::sets::WorldSet;
+use crate::tutorial::TutorialProgress;
+use crate::ui::game_ui::notifications::{NotificationKind, Notify};
+use crate::world::chunk::{ChunkData, WorldMap};
+use crate::world::exploration::ExploredTiles;
+
+/// Journal directory, relative to the working directory.
+pub const AUTOSAVE_DIR: &str = "saves/autosave";
+/// Stamped on a clean exit; an autosave older than it has nothing to restore.
+pub const MAIN_SAVE_FILE: &str = "saves/universe.ron";
+/// Default minutes between autosaves.
+const DEFAULT_INTERVAL_MINS: f32 = 5.0;
+const MANIFEST_FILE: &str = "manifest.ron";
+const CHUNK_EXTENSION: &str = "ron";
+const TMP_EXTENSION: &str = "tmp";
+
+/// Autosave settings.
+#[derive(Resource, Debug, Clone)]
+pub struct AutosaveConfig {
+    /// Minutes of play between autosaves; `None` turns autosave off.
+    pub interval_mins: Option<f32>,
+    pub dir: PathBuf,
+    pub main_save: PathBuf,
+    /// How chunk files are stored.
+    pub compression: ChunkCompression,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            interval_mins: Some(DEFAULT_INTERVAL_MINS),
+            dir: PathBuf::from(AUTOSAVE_DIR),
+            main_save: PathBuf::from(MAIN_SAVE_FILE),
+            compression: ChunkCompression::default(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AutosaveError {
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{path}: {source}")]
+    Ron {
+        path: PathBuf,
+        source: Box<ron::error::SpannedError>,
+    },
+    #[error("{path}: {source}")]
+    Chunk {
+        path: PathBuf,
+        source: ChunkFormatError,
+    },
+    #[error("failed to serialize autosave: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> AutosaveError {
+    AutosaveError::Io {
+        path: path.to_owned(),
+        source,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Journal format
+// ---------------------------------------------------------------------------
+
+/// Player state kept in the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub x: f32,
+    pub y: f32,
+    pub health: Option<f32>,
+    pub main_bag: Vec<Option<InventorySlot>>,
+    pub material_bag: Vec<Option<InventorySlot>>,
+    /// Open main-bag rows. Journals from before capacity existed have none
+    /// and restore with every row open, as they were saved.
+    #[serde(default)]
+    pub capacity: Option<BagCapacity>,
+    /// Bag worn in the Back slot, which `capacity.bag_rows` comes from.
+    #[serde(default)]
+    pub back: Option<String>,
+    /// What has unlocked recipes so far.
+    #[serde(default)]
+    pub known_recipes: KnownRecipes,
+    /// Tutorial objectives completed so far.
+    #[serde(default)]
+    pub tutorial: TutorialProgress,
+}
+
+impl SavedPlayer {
+    fn capture(
+        tf: &Transform,
+        health: Option<&Health>,
+        inventory: Option<&Inventory>,
+        equipment: Option<&Equipment>,
+        known_recipes: Option<&KnownRecipes>,
+        tutorial: Option<&TutorialProgress>,
+    ) -> Self {
+        Self {
+            x: tf.translation.x,
+            y: tf.translation.y,
+            health: health.map(|h| h.current),
+            main_bag: inventory.map(|i| i.main_bag.clone()).unwrap_or_default(),
+            material_bag: inventory
+                .map(|i| i.material_bag.clone())
+                .unwrap_or_default(),
+            capacity: inventory.map(Inventory::capacity),
+            back: equipment.and_then(|e| e.get(EquipmentSlot::Back)).cloned(),
+            known_recipes: known_recipes.cloned().unwrap_or_default(),
+            tutorial: tutorial.cloned().unwrap_or_default(),
+        }
+    }
+
+    fn apply(
+        &self,
+        tf: &mut Transform,
+        health: Option<Mut<Health>>,
+        inventory: Option<Mut<Inventory>>,
+        equipment: Option<Mut<Equipment>>,
+    ) {
+        tf.translation.x = self.x;
+        tf.translation.y = self.y;
+        if let Some((mut health, current)) = health.zip(self.health) {
+            health.current = current.min(health.max);
+        }
+        if let Some(mut inventory) = inventory {
+            inventory.main_bag = self.main_bag.clone();
+            inventory.material_bag = self.material_bag.clone();
+            inventory.restore_capacity(self.capacity.unwrap_or(BagCapacity::FULL));
+            inventory.mark_all_changed();
+        }
+        if let Some(mut equipment) = equipment {
+            match &self.back {
+                Some(id) => equipment.equip(EquipmentSlot::Back, id.clone()),
+                None => {
+                    equipment.unequip(EquipmentSlot::Back);
+                }
+            }
+        }
+    }
+}
+
+/// Index of an autosave, written last so it only ever names complete files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutosaveManifest {
+    pub address: CelestialAddress,
+    #[serde(default)]
+    pub game_mode: GameMode,
+    /// Seconds since the Unix epoch.
+    pub saved_at: u64,
+    pub player: Option<SavedPlayer>,
+    /// File of each modified chunk, relative to the journal directory.
+    pub chunks: HashMap<(i32, i32), String>,
+    #[serde(default)]
+    pub explored: HashMap<(i32, i32), ExploredTiles>,
+}
+
+/// One autosave: snapshotted on the main thread, written in the background.
+pub struct AutosaveJob {
+    pub dir: PathBuf,
+    pub manifest: AutosaveManifest,
+    /// Chunks modified since the last autosave, with the file each goes to.
+    pub chunks: Vec<(String, ChunkData)>,
+    pub compression: ChunkCompression,
+}
+
+impl AutosaveJob {
+    /// Write the new chunk files, commit the manifest, then delete the files
+    /// it no longer names.
+    pub fn write(self) -> Result<(), AutosaveError> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| io_error(&self.dir, e))?;
+        for (name, chunk) in &self.chunks {
+            let ron = encode_chunk(chunk, self.compression)?;
+            write_atomic(&self.dir.join(name), ron.as_bytes())?;
+        }
+        let manifest = ron::ser::to_string_pretty(
+            &self.manifest,
+            ron::ser::PrettyConfig::default().depth_limit(1),
+        )?;
+        write_atomic(&self.dir.join(MANIFEST_FILE), manifest.as_bytes())?;
+        remove_unnamed_files(&self.dir, &self.manifest)
+    }
+}
+
+/// Write `contents` to `path` through a temporary file renamed over it, so
+/// readers find either the old file or all of the new one.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), AutosaveError> {
+    let tmp = path.with_extension(TMP_EXTENSION);
+    std::fs::File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|source| io_error(path, source))
+}
+
+/// Delete journal files the manifest doesn't name: chunks replaced since and
+/// temporaries left by an interrupted write.
+fn remove_unnamed_files(dir: &Path, manifest: &AutosaveManifest) -> Result<(), AutosaveError> {
+    let named: HashSet<&str> = manifest.chunks.values().map(String::as_str).collect();
+    let entries = std::fs::read_dir(dir).map_err(|e| io_error(dir, e))?;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let ours = path
+            .extension()
+            .is_some_and(|ext| ext == CHUNK_EXTENSION || ext == TMP_EXTENSION);
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if ours && name != MANIFEST_FILE && !named.contains(name) {
+            std::fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn read_ron<T: DeserializeOwned>(path: &Path) -> Result<T, AutosaveError> {
+    let bytes = std::fs::read(path).map_err(|e| io_error(path, e))?;
+    ron::de::from_bytes(&bytes).map_err(|source| AutosaveError::Ron {
+        path: path.to_owned(),
+        source: Box::new(source),
+    })
+}
+
+/// Read the journal in `dir`: its manifest and the chunks it names.
+#[allow(clippy::type_complexity)]
+pub fn read_journal(
+    dir: &Path,
+) -> Result<(AutosaveManifest, HashMap<(i32, i32), ChunkData>), AutosaveError> {
+    let manifest: AutosaveManifest = read_ron(&dir.join(MANIFEST_FILE))?;
+    let chunks = manifest
+        .chunks
+        .iter()
+        .map(|(&coords, name)| {
+            let path = dir.join(name);
+            let bytes = std::fs::read(&path).map_err(|e| io_error(&path, e))?;
+            let chunk =
+                decode_chunk(&bytes).map_err(|source| AutosaveError::Chunk { path, source })?;
+            Ok((coords, chunk))
+        })
+        .collect::<Result<_, AutosaveError>>()?;
+    Ok((manifest, chunks))
+}
+
+// ---------------------------------------------------------------------------
+// Detection
+// ---------------------------------------------------------------------------
+
+/// Whether an autosave taken at `autosave` should be offered over a main
+/// save written at `main_save`.
+pub fn autosave_is_newer(autosave: Option<SystemTime>, main_save: Option<SystemTime>) -> bool {
+    match (autosave, main_save) {
+        (Some(autosave), Some(main_save)) => autosave > main_save,
+        (autosave, None) => autosave.is_some(),
+        (None, Some(_)) => false,
+    }
+}
+
+/// Whether there is a journal worth offering: one written after the main
+/// save, or with no main save at all.
+pub fn recoverable_autosave(config: &AutosaveConfig) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    autosave_is_newer(
+        modified(&config.dir.join(MANIFEST_FILE)),
+        modified(&config.main_save),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Autosave
+// ---------------------------------------------------------------------------
+
+/// Modified chunks whose revision differs from the one last written, sorted.
+pub fn chunks_to_write(
+    dirty: &ChunkRevisions,
+    written_revision: impl Fn(&(i32, i32)) -> Option<u64>,
+) -> Vec<(i32, i32)> {
+    let mut coords: Vec<(i32, i32)> = dirty
+        .iter()
+        .copied()
+        .filter(|c| written_revision(c) != dirty.revision(c))
+        .collect();
+    coords.sort_unstable();
+    coords
+}
+
+/// What has been journaled so far this session.
+#[derive(Resource, Default)]
+pub struct AutosaveJournal {
+    /// Seconds of play since the last autosave.
+    elapsed: f32,
+    /// Tags this session's chunk files so they never replace a file the
+    /// previous session's manifest names.
+    session: u64,
+    /// World the written chunks belong to.
+    address: Option<CelestialAddress>,
+    /// Revision and file of each chunk the last manifest names.
+    written: HashMap<(i32, i32), (u64, String)>,
+    task: Option<Task<Result<(), AutosaveError>>>,
+}
+
+impl AutosaveJournal {
+    /// Clone the chunks modified since the last autosave and name their new
+    /// files. Returns them with the files of every modified chunk for the
+    /// manifest. A modified chunk missing from `world_map` keeps the file it
+    /// was last written to and stays due, so the next autosave that finds it
+    /// loaded writes it.
+    #[allow(clippy::type_complexity)]
+    pub fn prepare(
+        &mut self,
+        address: &CelestialAddress,
+        world_map: &WorldMap,
+        dirty: &DirtyChunks,
+    ) -> (Vec<(String, ChunkData)>, HashMap<(i32, i32), String>) {
+        if self.session == 0 {
+            self.session = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(1, |d| d.as_millis() as u64);
+        }
+        if self.address.as_ref() != Some(address) {
+            self.address = Some(address.clone());
+            self.written.clear();
+        }
+        self.written.retain(|coords, _| dirty.0.contains(coords));
+
+        let mut chunks = Vec::new();
+        for (cx, cy) in chunks_to_write(&dirty.0, |c| self.written.get(c).map(|w| w.0)) {
+            let (Some(chunk), Some(revision)) =
+                (world_map.chunk(cx, cy), dirty.0.revision(&(cx, cy)))
+            else {
+                continue;
+            };
+            let name = format!("{}_{cx}_{cy}_{revision}.{CHUNK_EXTENSION}", self.session);
+            chunks.push((name.clone(), chunk.clone()));
+            self.written.insert((cx, cy), (revision, name));
+        }
+        let files = self
+            .written
+            .iter()
+            .map(|(&coords, (_, name))| (coords, name.clone()))
+            .collect();
+        (chunks, files)
+    }
+}
+
+/// Every [`AutosaveConfig::interval_mins`] of play, snapshot the changed
+/// chunks and the player and write them on the IO task pool.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn autosave(
+    time: Res<Time>,
+    config: Res<AutosaveConfig>,
+    mut journal: ResMut<AutosaveJournal>,
+    active_world: Res<ActiveWorld>,
+    world_map: Res<WorldMap>,
+    dirty_chunks: Res<DirtyChunks>,
+    game_mode: Res<GameMode>,
+    known_recipes: Option<Res<KnownRecipes>>,
+    tutorial: Option<Res<TutorialProgress>>,
+    player: Query<
+        (
+            &Transform,
+            Option<&Health>,
+            Option<&Inventory>,
+            Option<&Equipment>,
+        ),
+        With<Player>,
+    >,
+    mut notify: MessageWriter<Notify>,
+) {
+    if let Some(task) = journal.task.as_mut() {
+        let Some(result) = block_on(poll_once(task)) else {
+            return;
+        };
+        journal.task = None;
+        match result {
+            Ok(()) => {
+                notify.write(Notify {
+                    kind: NotificationKind::Success,
+                    text: "Game saved".into(),
+                    icon: None,
+                });
+            }
+            Err(err) => {
+                warn!("Autosave failed: {err}");
+                notify.write(Notify {
+                    kind: NotificationKind::Error,
+                    text: "Autosave failed".into(),
+                    icon: None,
+                });
+                // The manifest on disk may not name what was prepared;
+                // rewrite everything next time.
+                journal.written.clear();
+            }
+        }
+    }
+    let Some(interval_mins) = config.interval_mins else {
+        return;
+    };
+    journal.elapsed += time.delta_secs();
+    if journal.elapsed < interval_mins * 60.0 {
+        return;
+    }
+    journal.elapsed = 0.0;
+
+    let (chunks, files) = journal.prepare(&active_world.address, &world_map, &dirty_chunks);
+    let job = AutosaveJob {
+        dir: config.dir.clone(),
+        manifest: AutosaveManifest {
+            address: active_world.address.clone(),
+            game_mode: *game_mode,
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            player: player
+                .single()
+                .ok()
+                .map(|(tf, health, inventory, equipment)| {
+                    SavedPlayer::capture(
+                        tf,
+                        health,
+                        inventory,
+                        equipment,
+                        known_recipes.as_deref(),
+                        tutorial.as_deref(),
+                    )
+                }),
+            chunks: files,
+            explored: world_map.explored.clone(),
+        },
+        chunks,
+        compression: config.compression,
+    };
+    journal.task = Some(IoTaskPool::get().spawn(async move { job.write() }));
+}
+
+/// Stamp the main save with the time of a clean exit, then remove the
+/// journal it supersedes.
+pub fn end_session(config: &AutosaveConfig) -> Result<(), AutosaveError> {
+    if let Some(parent) = config.main_save.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    let saved_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    write_atomic(
+        &config.main_save,
+        format!("(saved_at: {saved_at})\n").as_bytes(),
+    )?;
+    match std::fs::remove_dir_all(&config.dir) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(io_error(&config.dir, err)),
+        _ => Ok(()),
+    }
+}
+
+/// On a successful exit from the game, finish any autosave still being
+/// written, so it can't bring the journal back, then [`end_session`].
+pub fn end_session_on_exit(
+    mut exits: MessageReader<AppExit>,
+    config: Res<AutosaveConfig>,
+    mut journal: ResMut<AutosaveJournal>,
+) {
+    if !exits.read().any(AppExit::is_success) {
+        return;
+    }
+    if let Some(task) = journal.task.take() {
+        // Whatever it wrote is about to be removed.
+        let _ = block_on(task);
+    }
+    if let Err(err) = end_session(&config) {
+        warn!("Couldn't record the clean exit: {err}");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Restore
+// ---------------------------------------------------------------------------
+
+/// A restored journal waiting for the game to reach its world.
+#[derive(Resource, Debug)]
+pub struct PendingRestore {
+    pub manifest: AutosaveManifest,
+    /// Whether the warp to the journal's world was already requested.
+    warped: bool,
+}
+
+/// Read the journal in `dir` and put its chunks into `universe`, so the
+/// world loads with them.
+pub fn load_restore(dir: &Path, universe: &mut Universe) -> Result<PendingRestore, AutosaveError> {
+    let (manifest, chunks) = read_journal(dir)?;
+    universe.planets.insert(
+        manifest.address.clone(),
+        WorldSave {
+            chunks,
+            explored: manifest.explored.clone(),
+            ..default()
+        },
+    );
+    Ok(PendingRestore {
+        manifest,
+        warped: false,
+    })
+}
+
+/// Bring the game to the restored session: warp to the journal's world if
+/// the game started elsewhere, then put the player back.
+#[allow(clippy::type_complexity)]
+pub fn finish_restore(
+    mut commands: Commands,
+    restore: Option<ResMut<PendingRestore>>,
+    active_world: Res<ActiveWorld>,
+    mut player: Query<
+        (
+            &mut Transform,
+            Option<&mut Health>,
+            Option<&mut Inventory>,
+            Option<&mut Equipment>,
+        ),
+        With<Player>,
+    >,
+    mut warps: (MessageWriter<WarpToBody>, MessageWriter<WarpToShip>),
+) {
+    let Some(mut restore) = restore else {
+        return;
+    };
+    let address = &restore.manifest.address;
+    if *address != active_world.address {
+        if restore.warped {
+            warn!("Couldn't return to the autosaved world {address:?}");
+            commands.remove_resource::<PendingRestore>();
+            return;
+        }
+        match (address, address.orbit()) {
+            (CelestialAddress::Ship { ship_id }, _) => {
+                warps.1.write(WarpToShip { ship_id: *ship_id });
+            }
+            (_, Some(orbit)) => {
+                warps.0.write(WarpToBody { orbit });
+            }
+            (_, None) => {}
+        }
+        restore.warped = true;
+        return;
+    }
+    let Ok((mut tf, health, inventory, equipment)) = player.single_mut() else {
+        return;
+    };
+    if let Some(saved) = &restore.manifest.player {
+        saved.apply(&mut tf, health, inventory, equipment);
+        commands.insert_resource(saved.known_recipes.clone());
+        commands.insert_resource(saved.tutorial.clone());
+    }
+    commands.remove_resource::<PendingRestore>();
+}
+
+pub struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutosaveConfig>()
+            .init_resource::<AutosaveJournal>()
+            .add_systems(
+                Update,
+                (
+                    finish_restore.in_set(WorldSet::Gen),
+                    autosave.in_set(WorldSet::Sim),
+                ),
+            )
+            // Last, so it sees exits requested anywhere in the frame.
+            .add_systems(Last, end_session_on_exit.run_if(in_state(AppState::InGame)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{BagSlot, BagTarget};
+    use crate::test_helpers::fixtures;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("autosave-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn manifest(chunks: &[((i32, i32), &str)]) -> AutosaveManifest {
+        AutosaveManifest {
+            address: CelestialAddress::Ship { ship_id: 0 },
+            game_mode: GameMode::default(),
+            saved_at: 0,
+            player: None,
+            chunks: chunks
+                .iter()
+                .map(|&(coords, name)| (coords, name.to_owned()))
+                .collect(),
+            explored: HashMap::new(),
+        }
+    }
+
+    /// A world map with chunks (0, 0) and (1, 0) generated, both modified.
+    fn modified_world() -> (WorldMap, DirtyChunks) {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut world_map = WorldMap::default();
+        let mut dirty = DirtyChunks::default();
+        for coords in [(0, 0), (1, 0)] {
+            world_map.get_or_generate_chunk(coords.0, coords.1, &ctx);
+            dirty.0.insert(coords);
+        }
+        (world_map, dirty)
+    }
+
+    #[test]
+    fn atomic_write_leaves_no_partial_files() {
+        let dir = test_dir("atomic");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.ron");
+
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"second");
+        assert_eq!(file_names(&dir), ["a.ron"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn interrupted_autosave_keeps_the_previous_one() {
+        let dir = test_dir("interrupted");
+        let (world_map, _) = modified_world();
+        let chunk = world_map.chunk(0, 0).unwrap().clone();
+        AutosaveJob {
+            dir: dir.clone(),
+            manifest: manifest(&[((0, 0), "s_0_0_1.ron")]),
+            chunks: vec![("s_0_0_1.ron".into(), chunk.clone())],
+            compression: ChunkCompression::Rle,
+        }
+        .write()
+        .unwrap();
+
+        // A crash after a new chunk file and halfway through the manifest.
+        write_atomic(&dir.join("s_0_0_2.ron"), b"(garbage").unwrap();
+        std::fs::write(dir.join("manifest.tmp"), b"(address: Sh").unwrap();
+
+        let (restored, chunks) = read_journal(&dir).unwrap();
+        assert_eq!(restored.chunks[&(0, 0)], "s_0_0_1.ron");
+        assert_eq!(chunks[&(0, 0)].fg.tiles, chunk.fg.tiles);
+
+        // The next autosave sweeps up what the crash left behind.
+        AutosaveJob {
+            dir: dir.clone(),
+            manifest: manifest(&[((0, 0), "s_0_0_3.ron")]),
+            chunks: vec![("s_0_0_3.ron".into(), chunk)],
+            compression: ChunkCompression::None,
+        }
+        .write()
+        .unwrap();
+        assert_eq!(file_names(&dir), ["manifest.ron", "s_0_0_3.ron"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn only_chunks_modified_since_the_last_autosave_are_rewritten() {
+        let (world_map, mut dirty) = modified_world();
+        let ship = CelestialAddress::Ship { ship_id: 0 };
+        let mut journal = AutosaveJournal::default();
+
+        let (chunks, files) = journal.prepare(&ship, &world_map, &dirty);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(files.len(), 2);
+
+        let (chunks, files_again) = journal.prepare(&ship, &world_map, &dirty);
+        assert!(chunks.is_empty());
+        assert_eq!(files_again, files);
+
+        dirty.0.insert((1, 0));
+        let (chunks, files_after) = journal.prepare(&ship, &world_map, &dirty);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(files_after[&(0, 0)], files[&(0, 0)]);
+        assert_ne!(files_after[&(1, 0)], files[&(1, 0)]);
+        assert_eq!(chunks[0].0, files_after[&(1, 0)]);
+
+        // Another world starts the journal over.
+        let planet = CelestialAddress::planet(IVec2::ZERO, IVec2::ZERO, 1);
+        let (chunks, _) = journal.prepare(&planet, &world_map, &dirty);
+        assert_eq!(chunks.len(), 2);
+    }
+
+    #[test]
+    fn chunks_missing_from_the_map_are_written_once_loaded() {
+        let (mut world_map, mut dirty) = modified_world();
+        let ship = CelestialAddress::Ship { ship_id: 0 };
+        let mut journal = AutosaveJournal::default();
+        dirty.0.insert((2, 0));
+
+        let (chunks, files) = journal.prepare(&ship, &world_map, &dirty);
+        assert_eq!(chunks.len(), 2);
+        assert!(!files.contains_key(&(2, 0)));
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        world_map.get_or_generate_chunk(2, 0, &ctx);
+        let (chunks, files) = journal.prepare(&ship, &world_map, &dirty);
+        assert_eq!(chunks.len(), 1, "only the chunk that was missing");
+        assert_eq!(chunks[0].0, files[&(2, 0)]);
+    }
+
+    #[test]
+    fn chunks_to_write_compares_revisions() {
+        let mut dirty = ChunkRevisions::default();
+        dirty.insert((0, 0));
+        dirty.insert((2, 1));
+        let written = HashMap::from([((0, 0), dirty.revision(&(0, 0)).unwrap())]);
+        assert_eq!(
+            chunks_to_write(&dirty, |c| written.get(c).copied()),
+            [(2, 1)]
+        );
+        assert_eq!(chunks_to_write(&dirty, |_| None), [(0, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn autosave_newer_than_the_main_save_is_offered() {
+        let now = SystemTime::now();
+        let earlier = now - std::time::Duration::from_secs(60);
+        assert!(autosave_is_newer(Some(now), None));
+        assert!(autosave_is_newer(Some(now), Some(earlier)));
+        assert!(!autosave_is_newer(Some(earlier), Some(now)));
+        assert!(!autosave_is_newer(None, Some(now)));
+        assert!(!autosave_is_newer(None, None));
+
+        let dir = test_dir("detect");
+        let config = AutosaveConfig {
+            interval_mins: None,
+            dir: dir.join("autosave"),
+            main_save: dir.join("universe.ron"),
+            compression: ChunkCompression::Rle,
+        };
+        assert!(!recoverable_autosave(&config));
+        AutosaveJob {
+            dir: config.dir.clone(),
+            manifest: manifest(&[]),
+            chunks: Vec::new(),
+            compression: config.compression,
+        }
+        .write()
+        .unwrap();
+        assert!(recoverable_autosave(&config));
+
+        let set_modified = |path: &Path, time: SystemTime| {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        };
+        std::fs::write(&config.main_save, b"()").unwrap();
+        set_modified(&config.dir.join(MANIFEST_FILE), earlier);
+        set_modified(&config.main_save, now);
+        assert!(!recoverable_autosave(&config));
+        set_modified(&config.dir.join(MANIFEST_FILE), now);
+        set_modified(&config.main_save, earlier);
+        assert!(recoverable_autosave(&config));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn clean_exit_leaves_nothing_to_restore() {
+        let dir = test_dir("exit");
+        let config = AutosaveConfig {
+            interval_mins: None,
+            dir: dir.join("autosave"),
+            main_save: dir.join("saves").join("universe.ron"),
+            compression: ChunkCompression::Rle,
+        };
+        let mut app = App::new();
+        app.add_plugins(bevy::state::app::StatesPlugin)
+            .insert_state(AppState::InGame)
+            .add_message::<AppExit>()
+            .insert_resource(config.clone())
+            .init_resource::<AutosaveJournal>()
+            .add_systems(Last, end_session_on_exit.run_if(in_state(AppState::InGame)));
+        AutosaveJob {
+            dir: config.dir.clone(),
+            manifest: manifest(&[]),
+            chunks: Vec::new(),
+            compression: config.compression,
+        }
+        .write()
+        .unwrap();
+        assert!(recoverable_autosave(&config));
+
+        app.update();
+        assert!(recoverable_autosave(&config), "no exit yet");
+        app.world_mut().write_message(AppExit::Success);
+        app.update();
+        assert!(config.main_save.exists());
+        assert!(!config.dir.exists());
+        assert!(!recoverable_autosave(&config));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bag_capacity_is_saved_with_the_player() {
+        let mut inventory = Inventory::new();
+        let mut equipment = Equipment::new();
+        inventory.unlock_rows(1);
+        inventory.try_add_item("backpack", 1, 1, BagTarget::Main);
+        equipment.equip_bag("backpack", 1, &mut inventory).unwrap();
+        let last = inventory.slot_count() - 1;
+        inventory.main_bag[last] = Some(InventorySlot {
+            item_id: "torch".into(),
+            count: 2,
+            durability: None,
+        });
+        let saved = SavedPlayer::capture(
+            &Transform::default(),
+            None,
+            Some(&inventory),
+            Some(&equipment),
+            None,
+            None,
+        );
+        let text = ron::ser::to_string(&saved).unwrap();
+        let loaded: SavedPlayer = ron::de::from_str(&text).unwrap();
+        assert_eq!(loaded, saved);
+
+        let mut world = World::new();
+        world.spawn((Transform::default(), Inventory::new(), Equipment::new()));
+        let mut restore = |saved: &SavedPlayer| {
+            let mut query = world.query::<(&mut Transform, &mut Inventory, &mut Equipment)>();
+            let (mut tf, inventory, equipment) = query.single_mut(&mut world).unwrap();
+            saved.apply(&mut tf, None, Some(inventory), Some(equipment));
+            let mut query = world.query::<(&Inventory, &Equipment)>();
+            let (inventory, equipment) = query.single(&world).unwrap();
+            (
+                inventory.capacity(),
+                inventory.slot(BagSlot::Main(last)).cloned(),
+                equipment.get(EquipmentSlot::Back).cloned(),
+            )
+        };
+        let (capacity, torch, back) = restore(&loaded);
+        assert_eq!(capacity, inventory.capacity());
+        assert_eq!(torch.map(|s| s.count), Some(2));
+        assert_eq!(back.as_deref(), Some("backpack"));
+
+        // Journals from before capacity restore with every row open.
+        let legacy: SavedPlayer =
+            ron::de::from_str("(x: 0.0, y: 0.0, health: None, main_bag: [], material_bag: [])")
+                .unwrap();
+        let (capacity, _, back) = restore(&legacy);
+        assert_eq!(capacity, BagCapacity::FULL);
+        assert_eq!(back, None);
+    }
+
+    #[test]
+    fn known_recipes_are_restored_with_the_player() {
+        let mut known = KnownRecipes::default();
+        known.obtained.insert("iron_ore".into());
+        known.blueprints.insert("wooden_sword".into());
+        known.crafted.insert("bow".into());
+        let saved =
+            SavedPlayer::capture(&Transform::default(), None, None, None, Some(&known), None);
+        let text = ron::ser::to_string(&saved).unwrap();
+        let loaded: SavedPlayer = ron::de::from_str(&text).unwrap();
+        assert_eq!(loaded.known_recipes, known);
+
+        let mut app = fixtures::test_app();
+        app.add_message::<WarpToBody>()
+            .add_message::<WarpToShip>()
+            .init_resource::<KnownRecipes>()
+            .add_systems(Update, finish_restore);
+        let address = app.world().resource::<ActiveWorld>().address.clone();
+        app.insert_resource(PendingRestore {
+            manifest: AutosaveManifest {
+                address,
+                player: Some(loaded),
+                ..manifest(&[])
+            },
+            warped: false,
+        });
+        app.world_mut().spawn((Player, Transform::default()));
+        app.update();
+        assert_eq!(*app.world().resource::<KnownRecipes>(), known);
+        assert!(!app.world().contains_resource::<PendingRestore>());
+
+        // Journals from before recipe discovery start with nothing known.
+        let legacy: SavedPlayer =
+            ron::de::from_str("(x: 0.0, y: 0.0, health: None, main_bag: [], material_bag: [])")
+                .unwrap();
+        assert_eq!(legacy.known_recipes, KnownRecipes::default());
+    }
+
+    #[test]
+    fn tutorial_progress_is_restored_with_the_player() {
+        let mut progress = TutorialProgress::default();
+        progress.completed.insert("open_inventory".into());
+        progress.counts.insert("dig_dirt".into(), 3);
+        let saved = SavedPlayer::capture(
+            &Transform::default(),
+            None,
+            None,
+            None,
+            None,
+            Some(&progress),
+        );
+        let text = ron::ser::to_string(&saved).unwrap();
+        let loaded: SavedPlayer = ron::de::from_str(&text).unwrap();
+        assert_eq!(loaded.tutorial, progress);
+
+        let mut app = fixtures::test_app();
+        app.add_message::<WarpToBody>()
+            .add_message::<WarpToShip>()
+            .init_resource::<TutorialProgress>()
+            .add_systems(Update, finish_restore);
+        let address = app.world().resource::<ActiveWorld>().address.clone();
+        app.insert_resource(PendingRestore {
+            manifest: AutosaveManifest {
+                address,
+                player: Some(loaded),
+                ..manifest(&[])
+            },
+            warped: false,
+        });
+        app.world_mut().spawn((Player, Transform::default()));
+        app.update();
+        assert_eq!(*app.world().resource::<TutorialProgress>(), progress);
+
+        // Journals from before the tutorial start it from the beginning.
+        let legacy: SavedPlayer =
+            ron::de::from_str("(x: 0.0, y: 0.0, health: None, main_bag: [], material_bag: [])")
+                .unwrap();
+        assert_eq!(legacy.tutorial, TutorialProgress::default());
+    }
+}
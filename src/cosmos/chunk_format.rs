@@ -0,0 +1,329 @@
+//! On-disk format of saved chunks: a version header and optionally
+//! run-length encoded tile arrays.
+//!
+//! A chunk's per-tile arrays are mostly long runs (air, stone, empty liquid,
+//! no objects), so run-length encoding shrinks them many times over. Every
+//! file carries [`CHUNK_FORMAT_VERSION`]: [`decode_chunk`] reads older
+//! versions and rejects newer ones with
+//! [`ChunkFormatError::UnsupportedVersion`] instead of misreading them. A
+//! file without a header is version 0, a bare [`ChunkData`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::liquid::{LiquidCell, LiquidLayer};
+use crate::object::{OccupancyRef, PlacedObject};
+use crate::registry::tile::TileId;
+use crate::world::chunk::{ChunkData, TileLayer};
+
+/// Version written into new chunk files. Bump it when the format changes
+/// and teach [`decode_chunk`] to read the previous one.
+pub const CHUNK_FORMAT_VERSION: u32 = 1;
+/// Most tiles a chunk can have; longer arrays mean a corrupt file.
+const MAX_CHUNK_TILES: usize = 256 * 256;
+
+/// How chunk arrays are stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChunkCompression {
+    /// Arrays as they are in memory.
+    None,
+    /// Arrays as runs of equal values.
+    #[default]
+    Rle,
+}
+
+#[derive(Debug, Error)]
+pub enum ChunkFormatError {
+    #[error(
+        "chunk saved in format version {found}, newer than this build reads \
+         (up to {supported})"
+    )]
+    UnsupportedVersion { found: u32, supported: u32 },
+    #[error(transparent)]
+    Ron(Box<ron::error::SpannedError>),
+    #[error("{array} holds {found} tiles, expected {expected}")]
+    Malformed {
+        array: &'static str,
+        found: usize,
+        expected: usize,
+    },
+}
+
+impl From<ron::error::SpannedError> for ChunkFormatError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(Box::new(err))
+    }
+}
+
+/// Runs of equal values, as `(value, count)` pairs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Runs<T>(pub Vec<(T, u32)>);
+
+impl<T: Clone + PartialEq> Runs<T> {
+    pub fn encode(values: &[T]) -> Self {
+        let mut runs: Vec<(T, u32)> = Vec::new();
+        for value in values {
+            match runs.last_mut() {
+                Some((last, count)) if last == value => *count += 1,
+                _ => runs.push((value.clone(), 1)),
+            }
+        }
+        Self(runs)
+    }
+
+    pub fn decode(&self) -> Vec<T> {
+        self.0
+            .iter()
+            .flat_map(|(value, count)| std::iter::repeat_n(value.clone(), *count as usize))
+            .collect()
+    }
+
+    /// Number of values the runs expand to.
+    pub fn expanded_len(&self) -> usize {
+        self.0.iter().map(|&(_, count)| count as usize).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RleLayer {
+    tiles: Runs<TileId>,
+    bitmasks: Runs<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RleChunk {
+    fg: RleLayer,
+    bg: RleLayer,
+    liquid: Runs<LiquidCell>,
+    objects: Vec<PlacedObject>,
+    occupancy: Runs<Option<OccupancyRef>>,
+    damage: Runs<u8>,
+    tile_state: Runs<u8>,
+    signs: HashMap<usize, String>,
+}
+
+impl RleChunk {
+    fn encode(chunk: &ChunkData) -> Self {
+        let layer = |layer: &TileLayer| RleLayer {
+            tiles: Runs::encode(&layer.tiles),
+            bitmasks: Runs::encode(&layer.bitmasks),
+        };
+        Self {
+            fg: layer(&chunk.fg),
+            bg: layer(&chunk.bg),
+            liquid: Runs::encode(&chunk.liquid.cells),
+            objects: chunk.objects.clone(),
+            occupancy: Runs::encode(&chunk.occupancy),
+            damage: Runs::encode(&chunk.damage),
+            tile_state: Runs::encode(&chunk.tile_state),
+            signs: chunk.signs.clone(),
+        }
+    }
+
+    /// Expand the runs, checking every array has a tile count before
+    /// allocating it. Liquid and tile state may be empty, as in old saves.
+    fn decode(self) -> Result<ChunkData, ChunkFormatError> {
+        let expected = self.fg.tiles.expanded_len();
+        let arrays = [
+            ("fg tiles", self.fg.tiles.expanded_len(), false),
+            ("fg bitmasks", self.fg.bitmasks.expanded_len(), false),
+            ("bg tiles", self.bg.tiles.expanded_len(), false),
+            ("bg bitmasks", self.bg.bitmasks.expanded_len(), false),
+            ("liquid", self.liquid.expanded_len(), true),
+            ("occupancy", self.occupancy.expanded_len(), false),
+            ("damage", self.damage.expanded_len(), false),
+            ("tile state", self.tile_state.expanded_len(), true),
+        ];
+        for (array, found, may_be_empty) in arrays {
+            if found > MAX_CHUNK_TILES || (found != expected && !(may_be_empty && found == 0)) {
+                return Err(ChunkFormatError::Malformed {
+                    array,
+                    found,
+                    expected,
+                });
+            }
+        }
+        let layer = |layer: &RleLayer| TileLayer {
+            tiles: layer.tiles.decode(),
+            bitmasks: layer.bitmasks.decode(),
+        };
+        Ok(ChunkData {
+            fg: layer(&self.fg),
+            bg: layer(&self.bg),
+            liquid: LiquidLayer {
+                cells: self.liquid.decode(),
+            },
+            objects: self.objects,
+            occupancy: self.occupancy.decode(),
+            damage: self.damage.decode(),
+            tile_state: self.tile_state.decode(),
+            signs: self.signs,
+        })
+    }
+}
+
+#[derive(Serialize)]
+enum ChunkBodyRef<'a> {
+    Raw(&'a ChunkData),
+    Rle(Box<RleChunk>),
+}
+
+#[derive(Serialize)]
+struct ChunkFileRef<'a> {
+    version: u32,
+    body: ChunkBodyRef<'a>,
+}
+
+#[derive(Deserialize)]
+enum ChunkBody {
+    Raw(ChunkData),
+    Rle(RleChunk),
+}
+
+#[derive(Deserialize)]
+struct ChunkFile {
+    body: ChunkBody,
+}
+
+/// Just the header; every other field is skipped. Files without one are
+/// version 0.
+#[derive(Deserialize)]
+struct ChunkHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Serialize a chunk in the current format.
+pub fn encode_chunk(
+    chunk: &ChunkData,
+    compression: ChunkCompression,
+) -> Result<String, ron::Error> {
+    let body = match compression {
+        ChunkCompression::None => ChunkBodyRef::Raw(chunk),
+        ChunkCompression::Rle => ChunkBodyRef::Rle(Box::new(RleChunk::encode(chunk))),
+    };
+    ron::ser::to_string(&ChunkFileRef {
+        version: CHUNK_FORMAT_VERSION,
+        body,
+    })
+}
+
+/// Read a chunk written by [`encode_chunk`] of this or an earlier version.
+pub fn decode_chunk(bytes: &[u8]) -> Result<ChunkData, ChunkFormatError> {
+    let header: ChunkHeader = ron::de::from_bytes(bytes)?;
+    match header.version {
+        0 => Ok(ron::de::from_bytes(bytes)?),
+        found if found > CHUNK_FORMAT_VERSION => Err(ChunkFormatError::UnsupportedVersion {
+            found,
+            supported: CHUNK_FORMAT_VERSION,
+        }),
+        _ => match ron::de::from_bytes::<ChunkFile>(bytes)?.body {
+            ChunkBody::Raw(chunk) => Ok(chunk),
+            ChunkBody::Rle(chunk) => chunk.decode(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::{Layer, WorldMap};
+
+    /// A generated surface chunk with a sign written on it.
+    fn test_chunk() -> ChunkData {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut world_map = WorldMap::default();
+        let cy = 500 / ctx.config.chunk_size as i32;
+        world_map.get_or_generate_chunk(0, cy, &ctx);
+        world_map.set_tile(3, 500, Layer::Fg, tr.by_name("stone"), &ctx);
+        world_map.set_tile_state(3, 500, 1, &ctx);
+        world_map.set_sign_text(3, 500, "hello".into(), &ctx);
+        world_map.chunk(0, cy).unwrap().clone()
+    }
+
+    fn assert_same(a: &ChunkData, b: &ChunkData) {
+        assert_eq!(a.fg.tiles, b.fg.tiles);
+        assert_eq!(a.fg.bitmasks, b.fg.bitmasks);
+        assert_eq!(a.bg.tiles, b.bg.tiles);
+        assert_eq!(a.bg.bitmasks, b.bg.bitmasks);
+        assert_eq!(a.liquid.cells, b.liquid.cells);
+        assert_eq!(a.occupancy, b.occupancy);
+        assert_eq!(a.damage, b.damage);
+        assert_eq!(a.tile_state, b.tile_state);
+        assert_eq!(a.signs, b.signs);
+        assert_eq!(a.objects.len(), b.objects.len());
+    }
+
+    #[test]
+    fn runs_round_trip() {
+        let values = [0u8, 0, 0, 5, 5, 0, 7];
+        let runs = Runs::encode(&values);
+        assert_eq!(runs.0, [(0, 3), (5, 2), (0, 1), (7, 1)]);
+        assert_eq!(runs.expanded_len(), values.len());
+        assert_eq!(runs.decode(), values);
+        assert!(Runs::<u8>::encode(&[]).decode().is_empty());
+    }
+
+    #[test]
+    fn chunk_round_trips_with_either_compression() {
+        let chunk = test_chunk();
+        let raw = encode_chunk(&chunk, ChunkCompression::None).unwrap();
+        let rle = encode_chunk(&chunk, ChunkCompression::Rle).unwrap();
+        assert_same(&decode_chunk(raw.as_bytes()).unwrap(), &chunk);
+        assert_same(&decode_chunk(rle.as_bytes()).unwrap(), &chunk);
+        assert!(
+            rle.len() * 4 < raw.len(),
+            "RLE {} bytes vs raw {}",
+            rle.len(),
+            raw.len()
+        );
+    }
+
+    #[test]
+    fn headerless_chunk_reads_as_version_zero() {
+        let chunk = test_chunk();
+        let legacy = ron::ser::to_string(&chunk).unwrap();
+        assert_same(&decode_chunk(legacy.as_bytes()).unwrap(), &chunk);
+    }
+
+    #[test]
+    fn newer_version_is_rejected_with_a_clear_error() {
+        let file = format!("(version: {}, body: Raw(()))", CHUNK_FORMAT_VERSION + 1);
+        let err = decode_chunk(file.as_bytes()).unwrap_err();
+        assert!(matches!(
+            err,
+            ChunkFormatError::UnsupportedVersion { found, supported }
+                if found == CHUNK_FORMAT_VERSION + 1 && supported == CHUNK_FORMAT_VERSION
+        ));
+        assert!(
+            err.to_string().contains("newer than this build reads"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn runs_of_the_wrong_length_are_rejected() {
+        let mut chunk = RleChunk::encode(&test_chunk());
+        chunk.damage.0.push((0, 1));
+        let file = ron::ser::to_string(&ChunkFileRef {
+            version: CHUNK_FORMAT_VERSION,
+            body: ChunkBodyRef::Rle(Box::new(chunk)),
+        })
+        .unwrap();
+        let err = decode_chunk(file.as_bytes()).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ChunkFormatError::Malformed {
+                    array: "damage",
+                    ..
+                }
+            ),
+            "{err}"
+        );
+    }
+}
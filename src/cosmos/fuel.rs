@@ -49,7 +49,7 @@ pub fn travel_duration(from_orbit: u32, to_orbit: u32) -> f32 {
 pub fn orbit_biome_for_planet_type(planet_type: &str) -> &str {
     match planet_type {
         "garden" => "orbit_garden",
-        "barren" => "orbit_barren",
+        "barren" | "moon" => "orbit_barren",
         _ => "deep_space",
     }
 }
@@ -95,6 +95,7 @@ mod tests {
     fn orbit_biome_mapping() {
         assert_eq!(orbit_biome_for_planet_type("garden"), "orbit_garden");
         assert_eq!(orbit_biome_for_planet_type("barren"), "orbit_barren");
+        assert_eq!(orbit_biome_for_planet_type("moon"), "orbit_barren");
         assert_eq!(orbit_biome_for_planet_type("unknown"), "deep_space");
     }
 }
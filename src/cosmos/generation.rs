@@ -370,24 +370,28 @@ mod tests {
                     terrain_frequency: 0.02,
                     terrain_amplitude: 40.0,
                     depth_ratio: 0.30,
+                    cave_threshold_scale: (1.0, 1.0),
                 },
                 underground: LayerConfigAsset {
                     primary_biome: Some("underground_dirt".into()),
                     terrain_frequency: 0.07,
                     terrain_amplitude: 1.0,
                     depth_ratio: 0.25,
+                    cave_threshold_scale: (1.0, 1.0),
                 },
                 deep_underground: LayerConfigAsset {
                     primary_biome: Some("underground_rock".into()),
                     terrain_frequency: 0.05,
                     terrain_amplitude: 1.0,
                     depth_ratio: 0.33,
+                    cave_threshold_scale: (1.0, 1.0),
                 },
                 core: LayerConfigAsset {
                     primary_biome: Some("core_magma".into()),
                     terrain_frequency: 0.04,
                     terrain_amplitude: 1.0,
                     depth_ratio: 0.12,
+                    cave_threshold_scale: (1.0, 1.0),
                 },
             },
             region_width_min: 300,
@@ -410,8 +414,20 @@ mod tests {
             temperature_modifiers: None,
             temperature_celsius_offsets: None,
             wrap_x: None,
+            border_tile: None,
+            bedrock_tile: None,
+            bedrock_depth: None,
+            layer_blend: None,
+            thin_air: None,
             base_temperature: None,
             weather: None,
+            gravity_scale: None,
+            atmosphere_density: None,
+            day_length: None,
+            starter_biome: None,
+            biome_separation: None,
+            difficulty: Default::default(),
+            biome_borders: Vec::new(),
         }
     }
 
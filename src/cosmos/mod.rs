@@ -1,6 +1,8 @@
 pub mod address;
 pub mod assets;
+pub mod autosave;
 pub mod capsule;
+pub mod chunk_format;
 pub mod current;
 pub mod fuel;
 pub mod generation;
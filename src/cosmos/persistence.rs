@@ -4,20 +4,25 @@
 //! Each visited world has a [`WorldSave`] containing only dirty (player-modified)
 //! chunks and dropped items. Unmodified terrain is regenerated from seed.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use bevy::prelude::*;
 use bevy::sprite_render::MeshMaterial2d;
 use serde::{Deserialize, Serialize};
 
 use super::address::CelestialAddress;
-use crate::item::{DroppedItem, ItemRegistry};
+use crate::game_mode::GameMode;
+use crate::item::{lifetime_timer, DroppedItem, DroppedItemLimits, ItemRegistry};
 use crate::physics::{Bounce, Friction, Gravity, Grounded, TileCollider, Velocity};
+use crate::registry::world::ActiveWorld;
 use crate::ui::game_ui::icon_registry::ItemIconRegistry;
-use crate::world::chunk::{ChunkData, WorldMap};
+use crate::world::chunk::{tile_to_chunk, world_to_tile, ChunkData, LoadedChunks, WorldMap};
+use crate::world::culling::DistanceBand;
+use crate::world::exploration::ExploredTiles;
 use crate::world::lit_sprite::{
     FallbackItemImage, FallbackLightmap, LitSprite, LitSpriteMaterial, SharedLitQuad,
 };
+use crate::world::spatial_index::TrackedInIndex;
 
 // ---------------------------------------------------------------------------
 // Saved dropped item
@@ -30,11 +35,12 @@ pub struct SavedDroppedItem {
     pub count: u16,
     pub x: f32,
     pub y: f32,
-    /// Remaining lifetime in seconds (max [`DROPPED_ITEM_LIFETIME_SECS`]).
+    /// Remaining lifetime in seconds.
     pub remaining_secs: f32,
 }
 
-/// Maximum lifetime for dropped items (30 minutes).
+/// Default lifetime for common dropped items (30 minutes); see
+/// [`DroppedItemLimits`](crate::item::DroppedItemLimits).
 pub const DROPPED_ITEM_LIFETIME_SECS: f32 = 1800.0;
 
 // ---------------------------------------------------------------------------
@@ -51,6 +57,9 @@ pub struct WorldSave {
     pub dropped_items: Vec<SavedDroppedItem>,
     /// Game time when the player left this world (for offline simulation).
     pub left_at: Option<f64>,
+    /// Tiles the player has explored, per chunk (modified or not).
+    #[serde(default)]
+    pub explored: HashMap<(i32, i32), ExploredTiles>,
 }
 
 // ---------------------------------------------------------------------------
@@ -64,6 +73,9 @@ pub struct WorldSave {
 #[derive(Resource, Debug, Default, Serialize, Deserialize)]
 pub struct Universe {
     pub planets: HashMap<CelestialAddress, WorldSave>,
+    /// Mode the universe was last played in.
+    #[serde(default)]
+    pub game_mode: GameMode,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,18 +87,120 @@ pub struct Universe {
 /// Populated by `set_tile()`, `place_object()`, `remove_object()`.
 /// Used during warp to determine which chunks to save.
 #[derive(Resource, Debug, Default)]
-pub struct DirtyChunks(pub HashSet<(i32, i32)>);
+pub struct DirtyChunks(pub ChunkRevisions);
+
+/// Set of modified chunk coordinates that also stamps each chunk with a
+/// revision on every modification, so incremental savers (see
+/// [`autosave`](super::autosave)) can tell what changed since they last
+/// looked. Reads like a `HashSet<(i32, i32)>`.
+#[derive(Debug, Default)]
+pub struct ChunkRevisions {
+    revisions: HashMap<(i32, i32), u64>,
+    /// Last stamp handed out. Never reset, so a chunk cleared and modified
+    /// again can't repeat an old revision.
+    latest: u64,
+}
+
+impl ChunkRevisions {
+    /// Mark a chunk modified. Returns whether it was clean before.
+    pub fn insert(&mut self, coords: (i32, i32)) -> bool {
+        self.latest += 1;
+        self.revisions.insert(coords, self.latest).is_none()
+    }
+
+    pub fn contains(&self, coords: &(i32, i32)) -> bool {
+        self.revisions.contains_key(coords)
+    }
+
+    /// Stamp of the chunk's latest modification, `None` while clean.
+    pub fn revision(&self, coords: &(i32, i32)) -> Option<u64> {
+        self.revisions.get(coords).copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.revisions.clear();
+    }
+
+    #[allow(dead_code)] // public API, used by tests
+    pub fn is_empty(&self) -> bool {
+        self.revisions.is_empty()
+    }
+
+    #[allow(dead_code)] // public API, used by tests
+    pub fn len(&self) -> usize {
+        self.revisions.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(i32, i32)> {
+        self.revisions.keys()
+    }
+}
+
+impl Extend<(i32, i32)> for ChunkRevisions {
+    fn extend<I: IntoIterator<Item = (i32, i32)>>(&mut self, coords: I) {
+        for c in coords {
+            self.insert(c);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ChunkRevisions {
+    type Item = &'a (i32, i32);
+    type IntoIter = std::collections::hash_map::Keys<'a, (i32, i32), u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.revisions.keys()
+    }
+}
 
 // ---------------------------------------------------------------------------
-// Pending dropped items (for respawn after warp)
+// Dropped items of unloaded chunks
 // ---------------------------------------------------------------------------
 
-/// Resource holding dropped items to respawn after arriving on a world.
+/// Dropped items lying in chunks that are not currently loaded, keyed by the
+/// data chunk containing each item's centre.
 ///
-/// Inserted during warp, consumed by `respawn_saved_dropped_items` on
-/// `OnEnter(InGame)`.
-#[derive(Resource, Default)]
-pub struct PendingDroppedItems(pub Vec<SavedDroppedItem>);
+/// Items are captured when their chunk unloads and respawned, with the
+/// lifetime they had left, once it loads again. On warp the store is saved
+/// together with the live items and rebuilt from the destination's save, so
+/// arriving items also appear as their chunks stream in. Chunks that were
+/// never modified still keep their items here.
+#[derive(Resource, Debug, Default)]
+pub struct UnloadedDroppedItems(pub HashMap<(i32, i32), Vec<SavedDroppedItem>>);
+
+impl UnloadedDroppedItems {
+    /// Group saved items by the chunk they lie in.
+    pub fn from_items(items: Vec<SavedDroppedItem>, config: &ActiveWorld) -> Self {
+        let mut store = Self::default();
+        for item in items {
+            store.insert(item, config);
+        }
+        store
+    }
+
+    /// Store an item under its chunk, with its x wrapped into the world.
+    pub fn insert(&mut self, mut item: SavedDroppedItem, config: &ActiveWorld) {
+        if config.wrap_x {
+            item.x = item
+                .x
+                .rem_euclid(config.width_tiles as f32 * config.tile_size);
+        }
+        let chunk = item_chunk(item.x, item.y, config);
+        self.0.entry(chunk).or_default().push(item);
+    }
+
+    /// All stored items, in no particular order.
+    pub fn items(&self) -> impl Iterator<Item = &SavedDroppedItem> {
+        self.0.values().flatten()
+    }
+}
+
+/// Data chunk owning an item centred at world position (`x`, `y`). Items
+/// straddling a chunk border belong to the chunk their centre is in.
+pub fn item_chunk(x: f32, y: f32, config: &ActiveWorld) -> (i32, i32) {
+    let (tile_x, tile_y) = world_to_tile(x, y, config.tile_size);
+    tile_to_chunk(config.wrap_tile_x(tile_x), tile_y, config.chunk_size)
+}
 
 // ---------------------------------------------------------------------------
 // Save / Load
@@ -113,6 +227,8 @@ pub fn save_current_world(
         }
     }
 
+    save.explored = world_map.explored.clone();
+
     // Save dropped items
     save.dropped_items = dropped_items;
 
@@ -132,6 +248,7 @@ pub fn load_world_save(
     current_game_time: f64,
 ) -> Vec<SavedDroppedItem> {
     dirty_chunks.0.clear();
+    world_map.explored.clear();
 
     let Some(save) = universe.planets.get(address) else {
         return Vec::new();
@@ -142,6 +259,7 @@ pub fn load_world_save(
         world_map.chunks.insert(coords, chunk_data.clone());
         dirty_chunks.0.insert(coords);
     }
+    world_map.explored = save.explored.clone();
 
     // Compute elapsed time and filter dropped items
     let elapsed = save
@@ -167,7 +285,7 @@ pub fn load_world_save(
 }
 
 // ---------------------------------------------------------------------------
-// Respawn saved dropped items
+// Capture / respawn dropped items
 // ---------------------------------------------------------------------------
 
 /// Dropped item display size in pixels (icons are 16×16).
@@ -175,72 +293,146 @@ const DROPPED_ITEM_SIZE: f32 = 16.0;
 /// Fallback size for items without an icon.
 const DROPPED_ITEM_FALLBACK_SIZE: f32 = 8.0;
 
-/// Respawn saved dropped items after arriving on a world via warp.
+/// Spawn a grounded [`DroppedItem`] for a saved item at `position`, with its
+/// remaining lifetime and the same `LitSpriteMaterial` setup as fresh tile
+/// drops.
+#[allow(clippy::too_many_arguments)]
+fn spawn_saved_dropped_item(
+    commands: &mut Commands,
+    saved: &SavedDroppedItem,
+    position: Vec2,
+    item_registry: &ItemRegistry,
+    icon_registry: &ItemIconRegistry,
+    quad: &SharedLitQuad,
+    fallback_lm: &FallbackLightmap,
+    fallback_img: &FallbackItemImage,
+    lit_materials: &mut Assets<LitSpriteMaterial>,
+    drop_limits: &DroppedItemLimits,
+) {
+    let item = item_registry.by_name(&saved.item_id);
+    let rarity = item
+        .map(|id| item_registry.get(id).rarity)
+        .unwrap_or_default();
+
+    // Resolve sprite texture from icon registry
+    let (sprite_image, size) = item
+        .and_then(|id| icon_registry.get(id).cloned())
+        .map(|img| (img, DROPPED_ITEM_SIZE))
+        .unwrap_or_else(|| (fallback_img.0.clone(), DROPPED_ITEM_FALLBACK_SIZE));
+
+    let material = lit_materials.add(LitSpriteMaterial {
+        sprite: sprite_image,
+        lightmap: fallback_lm.0.clone(),
+        lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
+        sprite_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
+        submerge_tint: Vec4::ZERO,
+        highlight: Vec4::ZERO,
+        tint: Vec4::ONE,
+    });
+
+    commands.spawn((
+        DroppedItem {
+            item_id: saved.item_id.clone(),
+            count: saved.count,
+            lifetime: lifetime_timer(drop_limits.lifetime(rarity), saved.remaining_secs),
+        },
+        LitSprite,
+        Velocity::default(),
+        Gravity(400.0),
+        Grounded(true),
+        TileCollider {
+            width: 4.0,
+            height: 4.0,
+        },
+        Friction(0.9),
+        Bounce(0.3),
+        DistanceBand::default(),
+        TrackedInIndex::default(),
+        Mesh2d(quad.0.clone()),
+        MeshMaterial2d(material),
+        Transform::from_translation(position.extend(1.0)).with_scale(Vec3::new(size, size, 1.0)),
+    ));
+}
+
+/// Move dropped items whose display chunk is no longer loaded into
+/// [`UnloadedDroppedItems`] and despawn their entities.
 ///
-/// Runs on `OnEnter(InGame)`. Consumes [`PendingDroppedItems`] and spawns
-/// grounded [`DroppedItem`] entities with their remaining lifetime timers.
-/// Items are spawned at their saved positions with no velocity (already
-/// grounded), using the same `LitSpriteMaterial` setup as fresh tile drops.
+/// Runs after chunk streaming, so items are captured in the frame their
+/// chunk despawns (or as soon as they fall or get thrown outside the loaded
+/// area).
+pub fn capture_unloaded_dropped_items(
+    mut commands: Commands,
+    mut unloaded: ResMut<UnloadedDroppedItems>,
+    loaded_chunks: Res<LoadedChunks>,
+    config: Res<ActiveWorld>,
+    items: Query<(Entity, &DroppedItem, &Transform)>,
+) {
+    for (entity, item, transform) in &items {
+        let (x, y) = (transform.translation.x, transform.translation.y);
+        let (tile_x, tile_y) = world_to_tile(x, y, config.tile_size);
+        let display_chunk = tile_to_chunk(tile_x, tile_y, config.chunk_size);
+        if loaded_chunks.map.contains_key(&display_chunk) {
+            continue;
+        }
+        unloaded.insert(
+            SavedDroppedItem {
+                item_id: item.item_id.clone(),
+                count: item.count,
+                x,
+                y,
+                remaining_secs: item.lifetime.remaining_secs(),
+            },
+            &config,
+        );
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Respawn stored dropped items for every loaded chunk, placed in whichever
+/// display copy of their data chunk is loaded.
 #[allow(clippy::too_many_arguments)]
-pub fn respawn_saved_dropped_items(
+pub fn restore_loaded_dropped_items(
     mut commands: Commands,
-    pending: Res<PendingDroppedItems>,
+    mut unloaded: ResMut<UnloadedDroppedItems>,
+    loaded_chunks: Res<LoadedChunks>,
+    config: Res<ActiveWorld>,
     item_registry: Res<ItemRegistry>,
     icon_registry: Res<ItemIconRegistry>,
-    quad: Res<SharedLitQuad>,
-    fallback_lm: Res<FallbackLightmap>,
-    fallback_img: Res<FallbackItemImage>,
-    mut lit_materials: ResMut<Assets<LitSpriteMaterial>>,
+    visuals: (
+        Res<SharedLitQuad>,
+        Res<FallbackLightmap>,
+        Res<FallbackItemImage>,
+        ResMut<Assets<LitSpriteMaterial>>,
+    ),
+    drop_limits: Res<DroppedItemLimits>,
 ) {
-    if pending.0.is_empty() {
-        commands.remove_resource::<PendingDroppedItems>();
+    if unloaded.0.is_empty() {
         return;
     }
+    let (quad, fallback_lm, fallback_img, mut lit_materials) = visuals;
+    let chunk_px = config.chunk_size as f32 * config.tile_size;
 
-    info!("Respawning {} saved dropped items", pending.0.len());
-
-    for saved in &pending.0 {
-        // Resolve sprite texture from icon registry
-        let (sprite_image, size) = item_registry
-            .by_name(&saved.item_id)
-            .and_then(|id| icon_registry.get(id).cloned())
-            .map(|img| (img, DROPPED_ITEM_SIZE))
-            .unwrap_or_else(|| (fallback_img.0.clone(), DROPPED_ITEM_FALLBACK_SIZE));
-
-        let material = lit_materials.add(LitSpriteMaterial {
-            sprite: sprite_image,
-            lightmap: fallback_lm.0.clone(),
-            lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
-            sprite_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
-            submerge_tint: Vec4::ZERO,
-            highlight: Vec4::ZERO,
-            tint: Vec4::ONE,
-        });
-
-        commands.spawn((
-            DroppedItem {
-                item_id: saved.item_id.clone(),
-                count: saved.count,
-                lifetime: Timer::from_seconds(saved.remaining_secs, TimerMode::Once),
-            },
-            LitSprite,
-            Velocity::default(),
-            Gravity(400.0),
-            Grounded(true),
-            TileCollider {
-                width: 4.0,
-                height: 4.0,
-            },
-            Friction(0.9),
-            Bounce(0.3),
-            Mesh2d(quad.0.clone()),
-            MeshMaterial2d(material),
-            Transform::from_translation(Vec3::new(saved.x, saved.y, 1.0))
-                .with_scale(Vec3::new(size, size, 1.0)),
-        ));
+    for &(display_cx, cy) in loaded_chunks.map.keys() {
+        let data_cx = config.wrap_chunk_x(display_cx);
+        let Some(items) = unloaded.0.remove(&(data_cx, cy)) else {
+            continue;
+        };
+        let shift = (display_cx - data_cx) as f32 * chunk_px;
+        for saved in &items {
+            spawn_saved_dropped_item(
+                &mut commands,
+                saved,
+                Vec2::new(saved.x + shift, saved.y),
+                &item_registry,
+                &icon_registry,
+                &quad,
+                &fallback_lm,
+                &fallback_img,
+                &mut lit_materials,
+                &drop_limits,
+            );
+        }
     }
-
-    commands.remove_resource::<PendingDroppedItems>();
 }
 
 // ---------------------------------------------------------------------------
@@ -253,7 +445,8 @@ mod tests {
     use crate::liquid::data::{LiquidCell, LiquidId};
     use crate::liquid::LiquidLayer;
     use crate::registry::tile::TileId;
-    use crate::world::chunk::TileLayer;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::{ChunkEntities, ChunkState, TileLayer};
     use bevy::math::IVec2;
 
     fn test_address() -> CelestialAddress {
@@ -280,6 +473,23 @@ mod tests {
         assert!(d.0.is_empty());
     }
 
+    #[test]
+    fn every_modification_gets_a_newer_revision() {
+        let mut d = DirtyChunks::default();
+        assert!(d.0.insert((0, 0)));
+        let first = d.0.revision(&(0, 0)).unwrap();
+        assert!(!d.0.insert((0, 0)));
+        let second = d.0.revision(&(0, 0)).unwrap();
+        assert!(second > first);
+        assert_eq!(d.0.len(), 1);
+
+        // Clearing forgets the chunks but not the stamps.
+        d.0.clear();
+        assert_eq!(d.0.revision(&(0, 0)), None);
+        d.0.insert((0, 0));
+        assert!(d.0.revision(&(0, 0)).unwrap() > second);
+    }
+
     #[test]
     fn universe_insert_and_get() {
         let mut u = Universe::default();
@@ -328,6 +538,31 @@ mod tests {
         assert!(items.is_empty()); // no dropped items saved
     }
 
+    #[test]
+    fn sign_text_survives_save_and_reload() {
+        let (wc, bm, br, tr, pc, nc) = crate::test_helpers::fixtures::test_world_ctx();
+        let ctx = crate::test_helpers::fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut universe = Universe::default();
+        let addr = test_address();
+        let mut world_map = WorldMap::default();
+        let mut dirty = DirtyChunks::default();
+
+        world_map.get_or_generate_chunk(3, 15, &ctx);
+        crate::world::sign::write_sign(&mut world_map, &mut dirty, 100, 500, "Home", &ctx);
+        save_current_world(&mut universe, &addr, &world_map, &dirty, vec![], 10.0);
+
+        // Round-trip through RON so the text survives serialization too.
+        let ron_text = ron::to_string(&universe.planets[&addr]).unwrap();
+        let restored: WorldSave = ron::from_str(&ron_text).unwrap();
+        universe.planets.insert(addr.clone(), restored);
+
+        // Chunks evicted, then reloaded from the save.
+        world_map.chunks.clear();
+        dirty.0.clear();
+        load_world_save(&universe, &addr, &mut world_map, &mut dirty, 20.0);
+        assert_eq!(world_map.get_sign_text(100, 500, &ctx), Some("Home"));
+    }
+
     #[test]
     fn load_subtracts_elapsed_from_dropped_items() {
         let mut universe = Universe::default();
@@ -353,6 +588,7 @@ mod tests {
                     },
                 ],
                 left_at: Some(1000.0),
+                explored: HashMap::new(),
             },
         );
 
@@ -449,6 +685,7 @@ mod tests {
                     remaining_secs: 600.0,
                 }],
                 left_at: Some(1000.0),
+                explored: HashMap::new(),
             },
         );
 
@@ -476,6 +713,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
         let chunk_b = chunk_a.clone();
 
@@ -524,6 +763,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
 
         let mut world_map = WorldMap::default();
@@ -612,6 +853,7 @@ mod tests {
                     remaining_secs: 900.0,
                 }],
                 left_at: None, // no departure time recorded
+                explored: HashMap::new(),
             },
         );
 
@@ -638,6 +880,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
 
         let mut world_map = WorldMap::default();
@@ -659,4 +903,135 @@ mod tests {
         assert!(!save.chunks.contains_key(&(0, 0)));
         assert!(!save.chunks.contains_key(&(2, 0)));
     }
+
+    /// Width of a chunk in world pixels for the fixture world.
+    const CHUNK_PX: f32 = 32.0 * 32.0;
+
+    fn saved(item_id: &str, x: f32, y: f32) -> SavedDroppedItem {
+        SavedDroppedItem {
+            item_id: item_id.into(),
+            count: 1,
+            x,
+            y,
+            remaining_secs: 600.0,
+        }
+    }
+
+    #[test]
+    fn items_belong_to_the_chunk_holding_their_centre() {
+        let config = fixtures::test_active_world();
+        assert_eq!(item_chunk(CHUNK_PX - 0.1, 0.0, &config), (0, 0));
+        assert_eq!(item_chunk(CHUNK_PX, 5.0, &config), (1, 0));
+        // Left of the seam wraps to the last column of chunks.
+        assert_eq!(item_chunk(-0.5, CHUNK_PX, &config), (63, 1));
+
+        let store = UnloadedDroppedItems::from_items(
+            vec![
+                saved("dirt", CHUNK_PX - 2.0, 10.0),
+                saved("stone", CHUNK_PX + 2.0, 10.0),
+                saved("torch", -10.0, 10.0),
+            ],
+            &config,
+        );
+        assert_eq!(store.0[&(0, 0)][0].item_id, "dirt");
+        assert_eq!(store.0[&(1, 0)][0].item_id, "stone");
+        let wrapped = &store.0[&(63, 0)][0];
+        assert_eq!(wrapped.x, 2048.0 * 32.0 - 10.0);
+    }
+
+    fn streaming_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(fixtures::test_active_world())
+            .insert_resource(ItemRegistry::from_defs(Vec::new()))
+            .init_resource::<ItemIconRegistry>()
+            .init_resource::<DroppedItemLimits>()
+            .init_resource::<LoadedChunks>()
+            .init_resource::<UnloadedDroppedItems>()
+            .insert_resource(SharedLitQuad(Handle::default()))
+            .insert_resource(FallbackLightmap(Handle::default()))
+            .insert_resource(FallbackItemImage(Handle::default()))
+            .insert_resource(Assets::<LitSpriteMaterial>::default())
+            .add_systems(
+                Update,
+                (capture_unloaded_dropped_items, restore_loaded_dropped_items).chain(),
+            );
+        app
+    }
+
+    fn load_chunk(app: &mut App, coords: (i32, i32)) {
+        app.world_mut().resource_mut::<LoadedChunks>().map.insert(
+            coords,
+            ChunkEntities {
+                fg: Entity::PLACEHOLDER,
+                bg: Entity::PLACEHOLDER,
+                liquid: Entity::PLACEHOLDER,
+                state: ChunkState::Visible,
+            },
+        );
+    }
+
+    fn dropped(app: &mut App) -> Vec<(String, Vec2, f32)> {
+        let mut query = app.world_mut().query::<(&DroppedItem, &Transform)>();
+        query
+            .iter(app.world())
+            .map(|(item, tf)| {
+                (
+                    item.item_id.clone(),
+                    tf.translation.truncate(),
+                    item.lifetime.remaining_secs(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn items_in_unloaded_chunks_are_captured_and_respawned() {
+        let mut app = streaming_app();
+        load_chunk(&mut app, (0, 0));
+        for (id, x) in [("near", 100.0), ("far", CHUNK_PX + 76.0)] {
+            app.world_mut().spawn((
+                DroppedItem {
+                    item_id: id.into(),
+                    count: 3,
+                    lifetime: lifetime_timer(1800.0, 600.0),
+                },
+                Transform::from_xyz(x, 100.0, 1.0),
+            ));
+        }
+
+        app.update();
+        let items = dropped(&mut app);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].0, "near");
+        let stored = &app.world().resource::<UnloadedDroppedItems>().0[&(1, 0)];
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].count, 3);
+
+        load_chunk(&mut app, (1, 0));
+        app.update();
+        let far = dropped(&mut app)
+            .into_iter()
+            .find(|(id, ..)| id == "far")
+            .expect("item respawned when its chunk loaded");
+        assert_eq!(far.1, Vec2::new(CHUNK_PX + 76.0, 100.0));
+        assert!((far.2 - 600.0).abs() < 1e-3, "lifetime kept: {}", far.2);
+        assert!(app.world().resource::<UnloadedDroppedItems>().0.is_empty());
+    }
+
+    #[test]
+    fn stored_items_respawn_in_the_loaded_copy_across_the_seam() {
+        let mut app = streaming_app();
+        let config = fixtures::test_active_world();
+        app.world_mut()
+            .resource_mut::<UnloadedDroppedItems>()
+            .insert(saved("torch", -10.0, 40.0), &config);
+
+        // Display chunk -1 shows data chunk 63 left of the seam.
+        load_chunk(&mut app, (-1, 0));
+        app.update();
+        let items = dropped(&mut app);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1, Vec2::new(-10.0, 40.0));
+    }
 }
@@ -214,7 +214,7 @@ fn update_in_vacuum(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::registry::tile::{TileDef, TileRegistry};
+    use crate::registry::tile::{TileDef, TileMaterial, TileRegistry};
 
     fn test_tile_registry() -> TileRegistry {
         TileRegistry::from_defs(vec![
@@ -233,7 +233,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "hull".into(),
@@ -250,7 +262,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
         ])
     }
@@ -303,6 +327,8 @@ mod tests {
                     objects: Vec::new(),
                     occupancy: vec![None; len],
                     damage: vec![0; len],
+                    tile_state: vec![0; len],
+                    signs: HashMap::new(),
                 };
                 world_map.chunks.insert((cx, cy), chunk);
             }
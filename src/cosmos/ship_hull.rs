@@ -213,12 +213,13 @@ mod tests {
     use crate::object::definition::{ObjectDef, ObjectType, PlacementRule};
     use crate::object::placement::get_object_at;
     use crate::object::registry::ObjectRegistry;
+    use crate::registry::assets::DifficultyCurve;
     use crate::registry::biome::{
         BiomeDef, BiomeRegistry, LayerBoundaries, LayerConfig, LayerConfigs, PlanetConfig,
     };
-    use crate::registry::tile::{TileDef, TileId, TileRegistry};
+    use crate::registry::tile::{TileDef, TileId, TileMaterial, TileRegistry};
     use crate::registry::world::ActiveWorld;
-    use crate::world::biome_map::BiomeMap;
+    use crate::world::biome_map::{BiomeMap, BorderCliffs};
     use crate::world::chunk::WorldMap;
     use crate::world::ctx::WorldCtxRef;
     use crate::world::terrain_gen::TerrainNoiseCache;
@@ -257,6 +258,7 @@ mod tests {
                 cave_threshold: 1.0,
                 parallax_path: None,
                 temperature_offset: 0.0,
+                decorations: vec![],
             },
         );
         reg
@@ -269,24 +271,28 @@ mod tests {
                 terrain_frequency: 0.0,
                 terrain_amplitude: 0.0,
                 depth_ratio: 1.0,
+                cave_threshold_scale: (1.0, 1.0),
             },
             underground: LayerConfig {
                 primary_biome: Some("deep_space".into()),
                 terrain_frequency: 0.0,
                 terrain_amplitude: 0.0,
                 depth_ratio: 0.0,
+                cave_threshold_scale: (1.0, 1.0),
             },
             deep_underground: LayerConfig {
                 primary_biome: Some("deep_space".into()),
                 terrain_frequency: 0.0,
                 terrain_amplitude: 0.0,
                 depth_ratio: 0.0,
+                cave_threshold_scale: (1.0, 1.0),
             },
             core: LayerConfig {
                 primary_biome: Some("deep_space".into()),
                 terrain_frequency: 0.0,
                 terrain_amplitude: 0.0,
                 depth_ratio: 0.0,
+                cave_threshold_scale: (1.0, 1.0),
             },
         };
         let layer_boundaries = LayerBoundaries::from_layers(&layers, 64);
@@ -296,9 +302,22 @@ mod tests {
             secondary_biomes: vec![],
             layers,
             layer_boundaries,
+            layer_blend: 0,
             region_width_min: 128,
             region_width_max: 128,
             primary_region_ratio: 1.0,
+            gravity_scale: 1.0,
+            atmosphere_density: 1.0,
+            day_length: None,
+            starter_biome: None,
+            biome_separation: 1,
+            border_tile: None,
+            bedrock_tile: None,
+            bedrock_depth: 0,
+            thin_air: None,
+            difficulty: DifficultyCurve::default(),
+            biome_borders: Vec::new(),
+            border_cliffs: BorderCliffs::default(),
         }
     }
 
@@ -319,7 +338,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "stone".into(),
@@ -336,7 +367,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
         ])
     }
@@ -437,6 +480,7 @@ mod tests {
             tile_registry: tr,
             planet_config: pc,
             noise_cache: nc,
+            biome_overrides: None,
         }
     }
 
@@ -451,7 +495,18 @@ mod tests {
     fn hull_generates_stone_walls() {
         let wc = ship_world();
         let br = ship_biome_registry();
-        let bm = BiomeMap::generate("deep_space", &["deep_space"], 42, 128, 128, 128, 1.0, &br);
+        let bm = BiomeMap::generate(
+            "deep_space",
+            &["deep_space"],
+            42,
+            128,
+            128,
+            128,
+            1.0,
+            1,
+            None,
+            &br,
+        );
         let tr = ship_tile_registry();
         let pc = ship_planet_config();
         let nc = TerrainNoiseCache::new(42);
@@ -524,7 +579,18 @@ mod tests {
     fn hull_background_is_stone() {
         let wc = ship_world();
         let br = ship_biome_registry();
-        let bm = BiomeMap::generate("deep_space", &["deep_space"], 42, 128, 128, 128, 1.0, &br);
+        let bm = BiomeMap::generate(
+            "deep_space",
+            &["deep_space"],
+            42,
+            128,
+            128,
+            128,
+            1.0,
+            1,
+            None,
+            &br,
+        );
         let tr = ship_tile_registry();
         let pc = ship_planet_config();
         let nc = TerrainNoiseCache::new(42);
@@ -554,7 +620,18 @@ mod tests {
     fn hull_objects_are_placed() {
         let wc = ship_world();
         let br = ship_biome_registry();
-        let bm = BiomeMap::generate("deep_space", &["deep_space"], 42, 128, 128, 128, 1.0, &br);
+        let bm = BiomeMap::generate(
+            "deep_space",
+            &["deep_space"],
+            42,
+            128,
+            128,
+            128,
+            1.0,
+            1,
+            None,
+            &br,
+        );
         let tr = ship_tile_registry();
         let pc = ship_planet_config();
         let nc = TerrainNoiseCache::new(42);
@@ -591,7 +668,18 @@ mod tests {
     fn hull_not_regenerated_twice() {
         let wc = ship_world();
         let br = ship_biome_registry();
-        let bm = BiomeMap::generate("deep_space", &["deep_space"], 42, 128, 128, 128, 1.0, &br);
+        let bm = BiomeMap::generate(
+            "deep_space",
+            &["deep_space"],
+            42,
+            128,
+            128,
+            128,
+            1.0,
+            1,
+            None,
+            &br,
+        );
         let tr = ship_tile_registry();
         let pc = ship_planet_config();
         let nc = TerrainNoiseCache::new(42);
@@ -620,7 +708,18 @@ mod tests {
     fn dirty_chunks_are_marked() {
         let wc = ship_world();
         let br = ship_biome_registry();
-        let bm = BiomeMap::generate("deep_space", &["deep_space"], 42, 128, 128, 128, 1.0, &br);
+        let bm = BiomeMap::generate(
+            "deep_space",
+            &["deep_space"],
+            42,
+            128,
+            128,
+            128,
+            1.0,
+            1,
+            None,
+            &br,
+        );
         let tr = ship_tile_registry();
         let pc = ship_planet_config();
         let nc = TerrainNoiseCache::new(42);
@@ -12,7 +12,7 @@ use crate::cosmos::current::CurrentSystem;
 use crate::cosmos::pressurization::PressureMap;
 use crate::cosmos::ship_location::{GlobalBiome, ShipLocation, ShipManifest};
 use crate::cosmos::persistence::{
-    save_current_world, DirtyChunks, PendingDroppedItems, SavedDroppedItem, Universe,
+    save_current_world, DirtyChunks, SavedDroppedItem, Universe, UnloadedDroppedItems,
 };
 use crate::item::DroppedItem;
 use crate::object::spawn::PlacedObjectEntity;
@@ -57,7 +57,11 @@ pub fn handle_warp(
     asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<AppState>>,
     rc_state: (ResMut<RcLightingConfig>, ResMut<RcInputData>),
-    persistence: (ResMut<Universe>, Res<DirtyChunks>),
+    persistence: (
+        ResMut<Universe>,
+        Res<DirtyChunks>,
+        Res<UnloadedDroppedItems>,
+    ),
     despawn_queries: (
         Query<Entity, With<PlacedObjectEntity>>,
         Query<Entity, With<DroppedItem>>,
@@ -94,7 +98,7 @@ pub fn handle_warp(
     );
 
     let (mut rc_config, mut rc_input) = rc_state;
-    let (mut universe, dirty_chunks) = persistence;
+    let (mut universe, dirty_chunks, unloaded_items) = persistence;
     let (object_entity_query, dropped_entity_query) = despawn_queries;
 
     // --- 0. SAVE current world ---
@@ -110,6 +114,7 @@ pub fn handle_warp(
                 y: transform.translation.y,
                 remaining_secs: item.lifetime.remaining_secs(),
             })
+            .chain(unloaded_items.items().cloned())
             .collect();
 
         save_current_world(
@@ -183,7 +188,6 @@ pub fn handle_warp(
     } else {
         Vec::new()
     };
-    commands.insert_resource(PendingDroppedItems(pending_items));
 
     // --- 7. Rebuild ActiveWorld ---
     let seeds = CelestialSeeds::derive(current_system.universe_seed, &body.address);
@@ -203,6 +207,10 @@ pub fn handle_warp(
         weather_config: None,
     };
     commands.insert_resource(TerrainNoiseCache::new(new_active_world.seed));
+    commands.insert_resource(UnloadedDroppedItems::from_items(
+        pending_items,
+        &new_active_world,
+    ));
     commands.insert_resource(new_active_world);
 
     // --- 8. Rebuild DayNightConfig + WorldTime ---
@@ -220,6 +228,7 @@ pub fn handle_warp(
         planet_type: planet_handle,
         biomes: Vec::new(),
         parallax_configs: Vec::new(),
+        parallax_images: Vec::new(),
     });
 
     // --- 10. Reset RC lighting state ---
@@ -267,7 +276,11 @@ pub fn handle_warp_to_ship(
     asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<AppState>>,
     rc_state: (ResMut<RcLightingConfig>, ResMut<RcInputData>),
-    persistence: (ResMut<Universe>, Res<DirtyChunks>),
+    persistence: (
+        ResMut<Universe>,
+        Res<DirtyChunks>,
+        Res<UnloadedDroppedItems>,
+    ),
     despawn_queries: (
         Query<Entity, With<PlacedObjectEntity>>,
         Query<Entity, With<DroppedItem>>,
@@ -303,7 +316,7 @@ pub fn handle_warp_to_ship(
     );
 
     let (mut rc_config, mut rc_input) = rc_state;
-    let (mut universe, dirty_chunks) = persistence;
+    let (mut universe, dirty_chunks, unloaded_items) = persistence;
     let (object_entity_query, dropped_entity_query) = despawn_queries;
 
     // --- 0. SAVE current world ---
@@ -319,6 +332,7 @@ pub fn handle_warp_to_ship(
                 y: transform.translation.y,
                 remaining_secs: item.lifetime.remaining_secs(),
             })
+            .chain(unloaded_items.items().cloned())
             .collect();
 
         save_current_world(
@@ -390,7 +404,6 @@ pub fn handle_warp_to_ship(
     } else {
         Vec::new()
     };
-    commands.insert_resource(PendingDroppedItems(pending_items));
 
     // --- 7. Rebuild ActiveWorld for the ship ---
     let seeds = CelestialSeeds::derive(current_system.universe_seed, &ship_address);
@@ -409,6 +422,10 @@ pub fn handle_warp_to_ship(
         weather_config: None,
     };
     commands.insert_resource(TerrainNoiseCache::new(new_active_world.seed));
+    commands.insert_resource(UnloadedDroppedItems::from_items(
+        pending_items,
+        &new_active_world,
+    ));
     commands.insert_resource(new_active_world);
 
     // --- 8. DayNightConfig for ship (permanent "day" lighting) ---
@@ -439,6 +456,7 @@ pub fn handle_warp_to_ship(
         planet_type: planet_handle,
         biomes: Vec::new(),
         parallax_configs: Vec::new(),
+        parallax_images: Vec::new(),
     });
 
     // --- 10. Reset RC lighting state ---
@@ -0,0 +1,197 @@
+//! Recipe discovery: which recipes the player knows.
+//!
+//! A recipe whose [`UnlockCondition`] isn't `Always` starts unknown.
+//! [`KnownRecipes`] records what can unlock one: items the player has held,
+//! blueprints read and recipes crafted. A recipe is known once its condition
+//! is met. The record is saved with the player, so unlocks survive a reload.
+//! [`RecipeDiscovery`] picks whether the crafting panel hides unknown recipes,
+//! shows them as locked silhouettes, or skips discovery altogether.
+
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::recipe::{Recipe, UnlockCondition};
+use crate::inventory::Inventory;
+use crate::player::Player;
+
+/// What the player has done that unlocks recipes.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KnownRecipes {
+    /// Items that have been in the player's inventory.
+    pub obtained: BTreeSet<String>,
+    /// Items whose blueprints have been read.
+    pub blueprints: BTreeSet<String>,
+    /// Recipes crafted at least once.
+    pub crafted: BTreeSet<String>,
+}
+
+impl KnownRecipes {
+    /// Whether `recipe`'s unlock condition has been met.
+    pub fn knows(&self, recipe: &Recipe) -> bool {
+        match &recipe.unlocked_by {
+            UnlockCondition::Always => true,
+            UnlockCondition::PickupItem(item) => self.obtained.contains(item),
+            UnlockCondition::Blueprint(item) => self.blueprints.contains(item),
+            UnlockCondition::Ingredient => recipe
+                .ingredients
+                .iter()
+                .any(|ing| self.obtained.contains(&ing.item_id)),
+            UnlockCondition::Crafted(id) => self.crafted.contains(id),
+            UnlockCondition::Station(_) => false,
+        }
+    }
+
+    /// Items in `inventory` not yet recorded as obtained, sorted.
+    pub fn new_items(&self, inventory: &Inventory) -> Vec<String> {
+        let mut items: Vec<String> = inventory
+            .main_bag
+            .iter()
+            .chain(&inventory.material_bag)
+            .flatten()
+            .map(|slot| &slot.item_id)
+            .filter(|id| !self.obtained.contains(*id))
+            .cloned()
+            .collect();
+        items.sort();
+        items.dedup();
+        items
+    }
+}
+
+/// How the crafting panel treats recipes the player doesn't know yet.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecipeDiscovery {
+    /// Every recipe is known from the start.
+    Off,
+    /// Unknown recipes aren't listed.
+    #[default]
+    Hidden,
+    /// Unknown recipes are listed as locked silhouettes.
+    Silhouette,
+}
+
+impl RecipeDiscovery {
+    /// Whether the player can see and craft `recipe`.
+    pub fn knows(self, known: &KnownRecipes, recipe: &Recipe) -> bool {
+        self == RecipeDiscovery::Off || known.knows(recipe)
+    }
+}
+
+/// Record every item that enters the player's inventory as obtained.
+pub fn record_obtained_items(
+    mut known: ResMut<KnownRecipes>,
+    player: Query<&Inventory, (With<Player>, Changed<Inventory>)>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+    // Only touch the resource when something is new, so the crafting panel
+    // doesn't rebuild on every inventory change.
+    let new_items = known.new_items(inventory);
+    if !new_items.is_empty() {
+        known.obtained.extend(new_items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crafting::recipe::{Ingredient, RecipeResult};
+    use crate::inventory::BagTarget;
+
+    fn recipe(id: &str, ingredients: &[&str], unlocked_by: UnlockCondition) -> Recipe {
+        Recipe {
+            id: id.into(),
+            result: RecipeResult {
+                item_id: id.into(),
+                count: 1,
+            },
+            ingredients: ingredients
+                .iter()
+                .map(|&item_id| Ingredient {
+                    item_id: item_id.into(),
+                    count: 1,
+                })
+                .collect(),
+            craft_time: 1.0,
+            station: None,
+            unlocked_by,
+        }
+    }
+
+    #[test]
+    fn conditions_unlock_from_what_the_player_has_done() {
+        let mut known = KnownRecipes::default();
+        let always = recipe("sign", &["wood"], UnlockCondition::Always);
+        let pickup = recipe(
+            "iron_pickaxe",
+            &["iron_ore", "wood"],
+            UnlockCondition::PickupItem("iron_ore".into()),
+        );
+        let ingredient = recipe("rope", &["wood", "fiber"], UnlockCondition::Ingredient);
+        let blueprint = recipe(
+            "wooden_sword",
+            &["wood"],
+            UnlockCondition::Blueprint("wooden_sword".into()),
+        );
+        let crafted = recipe("arrow", &["wood"], UnlockCondition::Crafted("bow".into()));
+
+        assert!(known.knows(&always));
+        for r in [&pickup, &ingredient, &blueprint, &crafted] {
+            assert!(!known.knows(r), "{} known from the start", r.id);
+        }
+
+        known.obtained.insert("fiber".into());
+        assert!(known.knows(&ingredient));
+        assert!(!known.knows(&pickup), "any ingredient isn't the gating one");
+        known.obtained.insert("iron_ore".into());
+        assert!(known.knows(&pickup));
+        known.blueprints.insert("wooden_sword".into());
+        assert!(known.knows(&blueprint));
+        known.crafted.insert("bow".into());
+        assert!(known.knows(&crafted));
+
+        // Discovery switched off knows everything.
+        let fresh = KnownRecipes::default();
+        assert!(RecipeDiscovery::Off.knows(&fresh, &crafted));
+        assert!(!RecipeDiscovery::Hidden.knows(&fresh, &crafted));
+    }
+
+    #[test]
+    fn picking_up_an_ingredient_unlocks_the_recipes_it_gates() {
+        let mut app = App::new();
+        app.init_resource::<KnownRecipes>()
+            .add_systems(Update, record_obtained_items);
+        let player = app.world_mut().spawn((Player, Inventory::new())).id();
+        let pickaxe = recipe(
+            "iron_pickaxe",
+            &["iron_ore", "wood"],
+            UnlockCondition::PickupItem("iron_ore".into()),
+        );
+        let rope = recipe("rope", &["wood"], UnlockCondition::Ingredient);
+
+        app.update();
+        let known = app.world().resource::<KnownRecipes>();
+        assert!(!known.knows(&pickaxe) && !known.knows(&rope));
+
+        app.world_mut()
+            .get_mut::<Inventory>(player)
+            .unwrap()
+            .try_add_item("iron_ore", 3, 99, BagTarget::Material);
+        app.update();
+        let known = app.world().resource::<KnownRecipes>();
+        assert!(known.knows(&pickaxe));
+        assert!(!known.knows(&rope));
+
+        // Unlocks stay once the item is gone.
+        let mut inventory = app.world_mut().get_mut::<Inventory>(player).unwrap();
+        inventory.remove_item("iron_ore", 3);
+        inventory.try_add_item("wood", 1, 99, BagTarget::Material);
+        app.update();
+        let known = app.world().resource::<KnownRecipes>();
+        assert!(known.knows(&pickaxe) && known.knows(&rope));
+    }
+}
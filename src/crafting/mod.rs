@@ -1,7 +1,9 @@
+pub mod known;
 pub mod plugin;
 pub mod recipe;
 pub mod registry;
 
-pub use plugin::CraftingPlugin;
+pub use known::*;
+pub use plugin::{CraftingPlugin, ItemCrafted};
 pub use recipe::*;
 pub use registry::*;
@@ -1,19 +1,38 @@
 use bevy::prelude::*;
 
+use super::known::{record_obtained_items, KnownRecipes, RecipeDiscovery};
 use super::recipe::{CraftingStation, HandCraftState};
 use crate::inventory::{BagTarget, Inventory};
 use crate::item::{ItemRegistry, ItemType};
 use crate::player::Player;
-use crate::sets::GameSet;
+use crate::sets::WorldSet;
+
+/// A hand or station craft finished and its result went to the player.
+#[allow(dead_code)] // recipe and count are for listeners beyond the tutorial
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct ItemCrafted {
+    pub recipe_id: String,
+    pub item_id: String,
+    pub count: u16,
+}
 
 pub struct CraftingPlugin;
 
 impl Plugin for CraftingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (tick_crafting_stations, tick_hand_craft).in_set(GameSet::WorldUpdate),
-        );
+        app.init_resource::<KnownRecipes>()
+            .add_message::<ItemCrafted>()
+            .init_resource::<RecipeDiscovery>()
+            .add_systems(
+                Update,
+                (
+                    tick_crafting_stations,
+                    tick_hand_craft,
+                    record_obtained_items,
+                )
+                    .chain()
+                    .in_set(WorldSet::Sim),
+            );
     }
 }
 
@@ -39,6 +58,8 @@ fn tick_crafting_stations(
     mut stations: Query<&mut CraftingStation>,
     mut player_query: Query<&mut Inventory, With<Player>>,
     item_registry: Res<ItemRegistry>,
+    mut known: ResMut<KnownRecipes>,
+    mut crafted: MessageWriter<ItemCrafted>,
 ) {
     let dt = time.delta_secs();
 
@@ -52,6 +73,12 @@ fn tick_crafting_stations(
         if craft.is_complete() {
             let result_id = craft.result.item_id.clone();
             let result_count = craft.result.count;
+            known.crafted.insert(craft.recipe_id.clone());
+            crafted.write(ItemCrafted {
+                recipe_id: craft.recipe_id.clone(),
+                item_id: result_id.clone(),
+                count: result_count,
+            });
             station.active_craft = None;
 
             // Add result to player inventory
@@ -69,6 +96,8 @@ fn tick_hand_craft(
     time: Res<Time>,
     mut query: Query<(&mut HandCraftState, &mut Inventory), With<Player>>,
     item_registry: Res<ItemRegistry>,
+    mut known: ResMut<KnownRecipes>,
+    mut crafted: MessageWriter<ItemCrafted>,
 ) {
     let dt = time.delta_secs();
 
@@ -85,6 +114,12 @@ fn tick_hand_craft(
     if craft.is_complete() {
         let result_id = craft.result.item_id.clone();
         let result_count = craft.result.count;
+        known.crafted.insert(craft.recipe_id.clone());
+        crafted.write(ItemCrafted {
+            recipe_id: craft.recipe_id.clone(),
+            item_id: result_id.clone(),
+            count: result_count,
+        });
         hand_craft.active_craft = None;
 
         let (target, max_stack) = bag_target_for(&result_id, &item_registry);
@@ -1,5 +1,3 @@
-use std::collections::HashSet;
-
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -25,23 +23,19 @@ pub struct Ingredient {
     pub count: u16,
 }
 
+/// What makes a recipe known; see [`KnownRecipes`](super::KnownRecipes).
 #[derive(Debug, Clone, Deserialize)]
 pub enum UnlockCondition {
     Always,
+    /// Having held this item.
     PickupItem(String),
+    /// Reading the blueprint for this item.
     Blueprint(String),
     Station(String),
-}
-
-impl UnlockCondition {
-    pub fn is_unlocked(&self, unlocked_items: &HashSet<String>) -> bool {
-        match self {
-            UnlockCondition::Always => true,
-            UnlockCondition::PickupItem(item) => unlocked_items.contains(item),
-            UnlockCondition::Blueprint(id) => unlocked_items.contains(id),
-            UnlockCondition::Station(_) => false,
-        }
-    }
+    /// Having held any of the recipe's own ingredients.
+    Ingredient,
+    /// Having crafted this recipe.
+    Crafted(String),
 }
 
 /// Progress state for an active craft on a station or player hand-craft.
@@ -89,12 +83,6 @@ pub struct HandCraftState {
     pub active_craft: Option<ActiveCraft>,
 }
 
-/// Tracks which recipes the player has unlocked via blueprints.
-#[derive(Component, Debug, Default)]
-pub struct UnlockedRecipes {
-    pub blueprints: std::collections::HashSet<String>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,26 +115,6 @@ mod tests {
         assert_eq!(recipe.ingredients.len(), 2);
     }
 
-    #[test]
-    fn recipe_can_check_if_unlocked() {
-        let always = UnlockCondition::Always;
-        assert!(always.is_unlocked(&HashSet::new()));
-
-        let pickup = UnlockCondition::PickupItem("stone".into());
-        let mut unlocked = HashSet::new();
-        assert!(!pickup.is_unlocked(&unlocked));
-
-        unlocked.insert("stone".into());
-        assert!(pickup.is_unlocked(&unlocked));
-
-        let blueprint = UnlockCondition::Blueprint("wooden_sword".into());
-        let mut bp_unlocked = HashSet::new();
-        assert!(!blueprint.is_unlocked(&bp_unlocked));
-
-        bp_unlocked.insert("wooden_sword".into());
-        assert!(blueprint.is_unlocked(&bp_unlocked));
-    }
-
     #[test]
     fn active_craft_progress() {
         let recipe = Recipe {
@@ -40,17 +40,18 @@ impl RecipeRegistry {
             .collect()
     }
 
-    /// Get all recipes that can be crafted with current inventory.
+    /// Get all recipes that can be crafted with current inventory, among
+    /// those `knows` accepts.
     pub fn craftable_recipes(
         &self,
         station: Option<&str>,
         inventory: &crate::inventory::Inventory,
-        unlocked: &std::collections::HashSet<String>,
+        knows: impl Fn(&Recipe) -> bool,
     ) -> Vec<&Recipe> {
         self.for_station(station)
             .into_iter()
             .filter(|r| {
-                r.unlocked_by.is_unlocked(unlocked)
+                knows(r)
                     && r.ingredients
                         .iter()
                         .all(|ing| inventory.count_item(&ing.item_id) >= ing.count as u32)
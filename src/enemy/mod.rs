@@ -13,7 +13,7 @@ pub use components::*;
 pub use loot::*;
 pub use spawner::MobSpawnConfig;
 
-use crate::sets::GameSet;
+use crate::sets::{GameSet, WorldSet};
 
 pub struct EnemyPlugin;
 
@@ -21,7 +21,7 @@ impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MobSpawnConfig>()
             .add_systems(Update, ai::enemy_ai_tick.in_set(GameSet::Input))
-            .add_systems(Update, loot::enemy_death_system.in_set(GameSet::WorldUpdate))
+            .add_systems(Update, loot::enemy_death_system.in_set(WorldSet::Sim))
             .add_systems(
                 Update,
                 slime::contact_damage_system.in_set(GameSet::Physics),
@@ -36,7 +36,7 @@ impl Plugin for EnemyPlugin {
             )
             .add_systems(
                 Update,
-                spawner::mob_spawn_system.in_set(GameSet::WorldUpdate),
+                spawner::mob_spawn_system.in_set(WorldSet::Sim),
             );
     }
 }
@@ -0,0 +1,253 @@
+//! The bobber's state machine: cast → floating → bite → caught or missed.
+//!
+//! Kept free of ECS access so the timing can be tested directly; the
+//! systems in [`super::systems`] feed it the frame time and whether the
+//! bobber is in liquid, and act on the [`BobberEvent`]s it returns.
+
+use bevy::prelude::*;
+
+use super::loot::BiteTiming;
+use crate::math::SplitMix64;
+
+/// Bobbing amplitude (px) while floating.
+const BOB_AMPLITUDE: f32 = 1.5;
+/// Bobbing speed (radians/s) while floating.
+const BOB_SPEED: f32 = 3.0;
+/// How far (px) the bobber is pulled under during a bite.
+const BITE_DIP: f32 = 4.0;
+/// Salt stream for the catch roll, kept apart from the wait rolls.
+const LOOT_STREAM: u64 = 0x6c6f_6f74;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BobberState {
+    /// In flight or lying on the ground; nothing will bite.
+    Cast,
+    /// Floating on a liquid, `remaining` seconds from a bite.
+    Floating { remaining: f32 },
+    /// A fish is biting; reel in within `remaining` seconds to catch it.
+    Bite { remaining: f32 },
+}
+
+/// What a [`Bobber::tick`] changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BobberEvent {
+    /// Touched liquid and started floating.
+    Landed,
+    /// A fish bit.
+    Bite,
+    /// The bite window passed without a reel; waiting again.
+    Missed,
+    /// The liquid under a floating bobber is gone.
+    Stranded,
+}
+
+/// What reeling in a bobber yields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReelOutcome {
+    /// Reeled during a bite: roll the loot table.
+    Catch,
+    /// No bite: just get the bobber back.
+    Retrieve,
+}
+
+/// A cast fishing bobber. Lives on a [`Projectile`](crate::projectile::Projectile)
+/// entity, which carries it until it lands.
+#[derive(Component, Debug, Clone)]
+pub struct Bobber {
+    pub state: BobberState,
+    /// Per-cast salt for the shared seeded RNG, so every cast rolls its own
+    /// waits and catch.
+    pub salt: u64,
+    /// Waits rolled so far this cast; a miss rolls the next one.
+    pub waits: u32,
+    /// Seconds spent on the liquid, for the bobbing.
+    pub float_time: f32,
+    /// World y the bobber floats at.
+    pub rest_y: f32,
+}
+
+impl Bobber {
+    pub fn new(salt: u64) -> Self {
+        Self {
+            state: BobberState::Cast,
+            salt,
+            waits: 0,
+            float_time: 0.0,
+            rest_y: 0.0,
+        }
+    }
+
+    /// Seconds until the next bite, rolled from `seed` and this cast's salt.
+    fn roll_wait(&mut self, seed: u64, timing: &BiteTiming) -> f32 {
+        let mut rng = SplitMix64::salted(seed, self.salt.wrapping_add(self.waits as u64));
+        self.waits += 1;
+        let (min, max) = timing.wait;
+        min + (max - min).max(0.0) * rng.next_f32()
+    }
+
+    /// Advance the state machine by `dt` seconds.
+    pub fn tick(
+        &mut self,
+        dt: f32,
+        in_liquid: bool,
+        seed: u64,
+        timing: &BiteTiming,
+    ) -> Option<BobberEvent> {
+        match self.state {
+            BobberState::Cast => {
+                if !in_liquid {
+                    return None;
+                }
+                self.float_time = 0.0;
+                self.state = BobberState::Floating {
+                    remaining: self.roll_wait(seed, timing),
+                };
+                Some(BobberEvent::Landed)
+            }
+            _ if !in_liquid => {
+                self.state = BobberState::Cast;
+                Some(BobberEvent::Stranded)
+            }
+            BobberState::Floating { remaining } => {
+                self.float_time += dt;
+                let remaining = remaining - dt;
+                if remaining > 0.0 {
+                    self.state = BobberState::Floating { remaining };
+                    return None;
+                }
+                self.state = BobberState::Bite {
+                    remaining: timing.window,
+                };
+                Some(BobberEvent::Bite)
+            }
+            BobberState::Bite { remaining } => {
+                self.float_time += dt;
+                let remaining = remaining - dt;
+                if remaining > 0.0 {
+                    self.state = BobberState::Bite { remaining };
+                    return None;
+                }
+                self.state = BobberState::Floating {
+                    remaining: self.roll_wait(seed, timing),
+                };
+                Some(BobberEvent::Missed)
+            }
+        }
+    }
+
+    pub fn reel(&self) -> ReelOutcome {
+        match self.state {
+            BobberState::Bite { .. } => ReelOutcome::Catch,
+            _ => ReelOutcome::Retrieve,
+        }
+    }
+
+    /// RNG for this cast's catch.
+    pub fn loot_rng(&self, seed: u64) -> SplitMix64 {
+        SplitMix64::salted(seed, self.salt ^ LOOT_STREAM)
+    }
+
+    /// Vertical draw offset (px) from `rest_y`.
+    pub fn bob_offset(&self) -> f32 {
+        match self.state {
+            BobberState::Cast => 0.0,
+            BobberState::Floating { .. } => (self.float_time * BOB_SPEED).sin() * BOB_AMPLITUDE,
+            BobberState::Bite { .. } => -BITE_DIP,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 42;
+    /// Exact in binary, so the 0.5 s window is exactly eight ticks.
+    const DT: f32 = 0.0625;
+
+    fn timing() -> BiteTiming {
+        BiteTiming {
+            wait: (1.0, 3.0),
+            window: 0.5,
+        }
+    }
+
+    /// Tick in liquid until `event` fires; returns the seconds it took.
+    fn run_until(bobber: &mut Bobber, event: BobberEvent) -> f32 {
+        let mut elapsed = 0.0;
+        for _ in 0..1000 {
+            elapsed += DT;
+            if bobber.tick(DT, true, SEED, &timing()) == Some(event) {
+                return elapsed;
+            }
+        }
+        panic!("{event:?} never fired; state {:?}", bobber.state);
+    }
+
+    #[test]
+    fn cast_floats_bites_and_catches() {
+        let mut bobber = Bobber::new(1);
+        assert_eq!(bobber.tick(DT, false, SEED, &timing()), None);
+        assert_eq!(bobber.state, BobberState::Cast);
+        assert_eq!(bobber.reel(), ReelOutcome::Retrieve);
+
+        assert_eq!(
+            bobber.tick(DT, true, SEED, &timing()),
+            Some(BobberEvent::Landed)
+        );
+        let BobberState::Floating { remaining } = bobber.state else {
+            panic!("{:?}", bobber.state);
+        };
+        assert!((1.0..=3.0).contains(&remaining), "{remaining}");
+        assert_eq!(bobber.reel(), ReelOutcome::Retrieve);
+
+        let waited = run_until(&mut bobber, BobberEvent::Bite);
+        assert!((waited - remaining).abs() <= DT, "{waited} vs {remaining}");
+        assert_eq!(bobber.reel(), ReelOutcome::Catch);
+        assert!(bobber.bob_offset() < 0.0);
+    }
+
+    #[test]
+    fn bite_window_expires_into_a_new_wait() {
+        let mut bobber = Bobber::new(2);
+        bobber.tick(DT, true, SEED, &timing());
+        run_until(&mut bobber, BobberEvent::Bite);
+
+        // Still catchable just before the window closes...
+        for _ in 0..7 {
+            assert_eq!(bobber.tick(DT, true, SEED, &timing()), None);
+        }
+        assert_eq!(bobber.reel(), ReelOutcome::Catch);
+        // ...and missed once it has.
+        assert_eq!(
+            bobber.tick(DT, true, SEED, &timing()),
+            Some(BobberEvent::Missed)
+        );
+        assert_eq!(bobber.reel(), ReelOutcome::Retrieve);
+        assert!(matches!(bobber.state, BobberState::Floating { .. }));
+        assert_eq!(bobber.waits, 2);
+        run_until(&mut bobber, BobberEvent::Bite);
+    }
+
+    #[test]
+    fn waits_are_seeded_per_cast() {
+        let landed_wait = |salt| {
+            let mut bobber = Bobber::new(salt);
+            bobber.tick(DT, true, SEED, &timing());
+            bobber.state
+        };
+        assert_eq!(landed_wait(5), landed_wait(5));
+        assert!((6..20).any(|salt| landed_wait(salt) != landed_wait(5)));
+    }
+
+    #[test]
+    fn drained_liquid_strands_the_bobber() {
+        let mut bobber = Bobber::new(3);
+        bobber.tick(DT, true, SEED, &timing());
+        assert_eq!(
+            bobber.tick(DT, false, SEED, &timing()),
+            Some(BobberEvent::Stranded)
+        );
+        assert_eq!(bobber.state, BobberState::Cast);
+    }
+}
@@ -0,0 +1,246 @@
+//! Fishing table loaded from `fishing.ron`: bite timing and what can be
+//! caught where.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::math::SplitMix64;
+use crate::registry::biome::WorldLayer;
+
+/// One possible catch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FishingLootEntry {
+    pub item_id: String,
+    /// Relative chance among the entries that apply where the bobber floats.
+    pub weight: f32,
+    /// Stack size range (inclusive).
+    #[serde(default = "default_count")]
+    pub count: (u16, u16),
+    /// Biome names the catch lives in; empty = every biome.
+    #[serde(default)]
+    pub biomes: Vec<String>,
+    /// Layers the catch lives in; empty = every layer.
+    #[serde(default)]
+    pub layers: Vec<WorldLayer>,
+    /// How region difficulty scales the weight: the effective weight is
+    /// `weight * (1 + difficulty_weight * difficulty)`. Positive for loot
+    /// that gets commoner far from spawn, negative for loot that thins out.
+    #[serde(default)]
+    pub difficulty_weight: f32,
+}
+
+fn default_count() -> (u16, u16) {
+    (1, 1)
+}
+
+impl FishingLootEntry {
+    /// Whether the entry can be caught in `biome` on `layer`.
+    pub fn applies(&self, biome: &str, layer: WorldLayer) -> bool {
+        (self.biomes.is_empty() || self.biomes.iter().any(|b| b == biome))
+            && (self.layers.is_empty() || self.layers.contains(&layer))
+    }
+
+    /// Weight at region difficulty `difficulty` (0.0–1.0), never negative.
+    pub fn weight_at(&self, difficulty: f32) -> f32 {
+        (self.weight * (1.0 + self.difficulty_weight * difficulty)).max(0.0)
+    }
+}
+
+/// Bite timing in seconds.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BiteTiming {
+    /// Range the wait for a bite is rolled from.
+    pub wait: (f32, f32),
+    /// How long a bite lasts; reeling in during it catches something.
+    pub window: f32,
+}
+
+impl Default for BiteTiming {
+    fn default() -> Self {
+        Self {
+            wait: (2.0, 6.0),
+            window: 0.8,
+        }
+    }
+}
+
+/// Asset loaded from `fishing.ron`, inserted as a resource once loaded.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, Deserialize)]
+pub struct FishingTable {
+    #[serde(default)]
+    pub timing: BiteTiming,
+    pub loot: Vec<FishingLootEntry>,
+}
+
+impl FishingTable {
+    /// Roll a catch for a bobber floating in `biome` on `layer`, in a region
+    /// of difficulty `difficulty` (see [`FishingLootEntry::weight_at`]).
+    ///
+    /// Returns the item id and stack size, or `None` if nothing with a
+    /// positive weight lives there.
+    pub fn roll(
+        &self,
+        biome: &str,
+        layer: WorldLayer,
+        difficulty: f32,
+        rng: &mut SplitMix64,
+    ) -> Option<(&str, u16)> {
+        let candidates: Vec<(&FishingLootEntry, f32)> = self
+            .loot
+            .iter()
+            .filter(|e| e.applies(biome, layer))
+            .map(|e| (e, e.weight_at(difficulty)))
+            .filter(|&(_, weight)| weight > 0.0)
+            .collect();
+        let total: f32 = candidates.iter().map(|&(_, weight)| weight).sum();
+        let &(last, _) = candidates.last()?;
+
+        let mut pick = rng.next_f32() * total;
+        // Float rounding can leave `pick` just past the last weight.
+        let entry = candidates
+            .iter()
+            .find(|&&(_, weight)| {
+                pick -= weight;
+                pick < 0.0
+            })
+            .map_or(last, |&(e, _)| e);
+        let (min, max) = entry.count;
+        let count = rng.range(min as u32, max.max(min) as u32) as u16;
+        Some((&entry.item_id, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(item_id: &str, weight: f32) -> FishingLootEntry {
+        FishingLootEntry {
+            item_id: item_id.into(),
+            weight,
+            count: (1, 1),
+            biomes: vec![],
+            layers: vec![],
+            difficulty_weight: 0.0,
+        }
+    }
+
+    #[test]
+    fn roll_follows_weights_and_filters() {
+        let table = FishingTable {
+            timing: BiteTiming::default(),
+            loot: vec![
+                entry("fish", 3.0),
+                entry("boot", 1.0),
+                entry("never", 0.0),
+                FishingLootEntry {
+                    biomes: vec!["tundra".into()],
+                    ..entry("ice_fish", 100.0)
+                },
+                FishingLootEntry {
+                    layers: vec![WorldLayer::Core],
+                    ..entry("magma_eel", 100.0)
+                },
+            ],
+        };
+
+        let mut fish = 0;
+        for salt in 0..4000 {
+            let mut rng = SplitMix64::salted(42, salt);
+            match table.roll("meadow", WorldLayer::Surface, 0.0, &mut rng) {
+                Some(("fish", 1)) => fish += 1,
+                Some(("boot", 1)) => {}
+                other => panic!("unexpected catch {other:?}"),
+            }
+        }
+        // 3:1 weights → ~3000 fish.
+        assert!((2800..3200).contains(&fish), "{fish}");
+
+        let mut rng = SplitMix64::salted(42, 0);
+        let (item, _) = table
+            .roll("tundra", WorldLayer::Surface, 0.0, &mut rng)
+            .unwrap();
+        assert_eq!(item, "ice_fish");
+        assert!(table
+            .roll("tundra", WorldLayer::Core, 0.0, &mut rng)
+            .is_some());
+    }
+
+    #[test]
+    fn difficulty_shifts_weights_toward_rare_loot() {
+        let table = FishingTable {
+            timing: BiteTiming::default(),
+            loot: vec![
+                FishingLootEntry {
+                    difficulty_weight: -1.0,
+                    ..entry("fish", 1.0)
+                },
+                FishingLootEntry {
+                    difficulty_weight: 3.0,
+                    ..entry("crystal", 1.0)
+                },
+            ],
+        };
+        assert_eq!(table.loot[1].weight_at(0.5), 2.5);
+        assert_eq!(table.loot[0].weight_at(1.0), 0.0);
+
+        let crystals = |difficulty| {
+            (0..2000)
+                .filter(|&salt| {
+                    let mut rng = SplitMix64::salted(42, salt);
+                    let catch = table.roll("meadow", WorldLayer::Surface, difficulty, &mut rng);
+                    catch.map(|(item, _)| item) == Some("crystal")
+                })
+                .count()
+        };
+        // 1:1 at spawn, 1:5 halfway, only crystal on the far side.
+        assert!((900..1100).contains(&crystals(0.0)));
+        assert!((1550..1780).contains(&crystals(0.5)));
+        assert_eq!(crystals(1.0), 2000);
+    }
+
+    #[test]
+    fn roll_is_deterministic_per_salt_and_handles_empty_tables() {
+        let table = FishingTable {
+            timing: BiteTiming::default(),
+            loot: vec![
+                FishingLootEntry {
+                    count: (1, 5),
+                    ..entry("fish", 1.0)
+                },
+                entry("boot", 1.0),
+            ],
+        };
+        let roll = |salt| {
+            table.roll(
+                "meadow",
+                WorldLayer::Surface,
+                0.0,
+                &mut SplitMix64::salted(7, salt),
+            )
+        };
+        for salt in 0..50 {
+            assert_eq!(roll(salt), roll(salt));
+            let (_, count) = roll(salt).unwrap();
+            assert!((1..=5).contains(&count));
+        }
+        assert!((0..50).any(|salt| roll(salt) != roll(0)));
+
+        let empty = FishingTable::default();
+        assert!(empty
+            .roll("meadow", WorldLayer::Surface, 0.0, &mut SplitMix64::new(1))
+            .is_none());
+    }
+
+    #[test]
+    fn fishing_ron_parses() {
+        let ron_str = std::fs::read_to_string("assets/content/fishing.ron")
+            .expect("fishing.ron should exist");
+        let table: FishingTable = ron::from_str(&ron_str).expect("fishing.ron should parse");
+        assert!(!table.loot.is_empty());
+        assert!(table.timing.wait.0 <= table.timing.wait.1);
+        assert!(table
+            .roll("meadow", WorldLayer::Surface, 0.0, &mut SplitMix64::new(0))
+            .is_some());
+    }
+}
@@ -0,0 +1,51 @@
+//! Fishing: a rod casts a bobber that floats on liquid, waits for a bite and
+//! is reeled in for a catch rolled from the `fishing.ron` loot table.
+
+pub mod bobber;
+pub mod loot;
+pub mod systems;
+
+use bevy::prelude::*;
+pub use bobber::{Bobber, BobberEvent, BobberState, ReelOutcome};
+pub use loot::{BiteTiming, FishingLootEntry, FishingTable};
+
+use crate::sets::GameSet;
+
+/// A fish bit a bobber. Hook for bite sounds and other feedback.
+#[derive(Message, Debug, Clone)]
+pub struct FishingBite {
+    pub bobber: Entity,
+    pub position: Vec2,
+}
+
+/// Counter handing out per-cast salts for the seeded RNG.
+#[derive(Resource, Debug, Default)]
+pub struct FishingCasts(pub u64);
+
+impl FishingCasts {
+    pub fn next_salt(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(1);
+        self.0
+    }
+}
+
+pub struct FishingPlugin;
+
+impl Plugin for FishingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<FishingBite>()
+            .init_resource::<FishingCasts>()
+            .add_systems(
+                Update,
+                systems::fishing_rod_system
+                    .after(crate::interaction::hand_action::tick_hand_cooldowns)
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                Update,
+                systems::update_bobbers
+                    .after(crate::projectile::move_projectiles)
+                    .in_set(GameSet::Physics),
+            );
+    }
+}
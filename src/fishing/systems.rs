@@ -0,0 +1,267 @@
+//! Casting, reeling and floating the bobber.
+
+use bevy::prelude::*;
+
+use super::bobber::{Bobber, BobberEvent, BobberState, ReelOutcome};
+use super::loot::FishingTable;
+use super::{FishingBite, FishingCasts};
+use crate::interaction::block_action::spawn_dropped_item;
+use crate::interaction::hand_action::{resolve_hand_action, use_cooldown, HandCooldowns};
+use crate::interaction::target::TargetTile;
+use crate::inventory::{BagTarget, Hand, Hotbar, Inventory};
+use crate::item::{DroppedItemLimits, ItemAction, ItemRegistry, ItemType};
+use crate::particles::pool::ParticlePool;
+use crate::physics::MAX_DELTA_SECS;
+use crate::player::Player;
+use crate::projectile::{spawn_projectile, OnHit, Projectile};
+use crate::ui::game_ui::icon_registry::ItemIconRegistry;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{world_to_tile, WorldMap};
+use crate::world::ctx::WorldCtx;
+use crate::world::lit_sprite::{
+    FallbackItemImage, FallbackLightmap, LitSpriteMaterial, SharedLitQuad,
+};
+
+/// Line length (px) past which the bobber snaps off and is lost.
+const MAX_LINE_LENGTH: f32 = 640.0;
+/// Size (px) of the bobber when the rod has no sprite for it.
+const BOBBER_SIZE: f32 = 6.0;
+/// Dots in the exclamation mark shown on a bite, bottom dot included.
+const BITE_MARK_DOTS: usize = 4;
+/// Height (px) of the exclamation mark's bottom dot above the bobber.
+const BITE_MARK_OFFSET: f32 = 10.0;
+
+/// Cast a bobber with the fishing rod in the clicked hand, or reel in the
+/// one that is already out.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn fishing_rod_system(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    capture: Res<InputCapture>,
+    target: Res<TargetTile>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Hotbar,
+            &mut Inventory,
+            &mut HandCooldowns,
+        ),
+        With<Player>,
+    >,
+    bobbers: Query<(Entity, &Transform, &Bobber, &Projectile)>,
+    ctx: WorldCtx,
+    item_registry: Res<ItemRegistry>,
+    icon_registry: Res<ItemIconRegistry>,
+    table: Res<FishingTable>,
+    mut casts: ResMut<FishingCasts>,
+    drops: (
+        Res<SharedLitQuad>,
+        Res<FallbackLightmap>,
+        Res<FallbackItemImage>,
+        Res<DroppedItemLimits>,
+        ResMut<Assets<LitSpriteMaterial>>,
+    ),
+    asset_server: Res<AssetServer>,
+) {
+    let (quad, fallback_lm, fallback_img, drop_limits, mut lit_materials) = drops;
+    // Clicks on a UI panel, or while typing, must not reach the world.
+    if capture.pointer || capture.keyboard {
+        return;
+    }
+    let Ok((player_entity, player_tf, hotbar, mut inventory, mut cooldowns)) =
+        player_query.single_mut()
+    else {
+        return;
+    };
+    let Some((hand, rod)) = [Hand::Left, Hand::Right].into_iter().find_map(|hand| {
+        if !mouse.just_pressed(hand.button()) || !cooldowns.ready(hand) {
+            return None;
+        }
+        let def = hotbar
+            .get_item_for_hand(hand == Hand::Left)
+            .and_then(|id| item_registry.by_name(id))
+            .map(|id| item_registry.get(id))?;
+        (resolve_hand_action(Some(def), hand) == ItemAction::Fish).then_some((hand, def))
+    }) else {
+        return;
+    };
+    let player_pos = player_tf.translation.truncate();
+    let cooldown = use_cooldown(Some(rod), ItemAction::Fish);
+
+    // One bobber per player: a click with a bobber out reels it in.
+    if let Some((entity, bobber_tf, bobber, _)) = bobbers
+        .iter()
+        .find(|(_, _, _, projectile)| projectile.owner == Some(player_entity))
+    {
+        commands.entity(entity).despawn();
+        cooldowns.start(hand, cooldown);
+        if bobber.reel() == ReelOutcome::Retrieve {
+            return;
+        }
+
+        let ctx_ref = ctx.as_ref();
+        let pos = bobber_tf.translation.truncate();
+        let (tile_x, tile_y) = world_to_tile(pos.x, pos.y, ctx_ref.config.tile_size);
+        let tile_x = ctx_ref.config.wrap_tile_x(tile_x);
+        let biome = ctx_ref
+            .biome_registry
+            .name_of(ctx_ref.biome_at_tile(tile_x, tile_y));
+        let layer = ctx_ref.layer_at(tile_y);
+        let difficulty = ctx_ref.difficulty_at(tile_x, tile_y);
+        let mut rng = bobber.loot_rng(ctx_ref.config.seed as u64);
+        let Some((item_id, count)) = table.roll(biome, layer, difficulty, &mut rng) else {
+            return;
+        };
+
+        let (target, max_stack) = item_registry
+            .by_name(item_id)
+            .map(|id| {
+                let def = item_registry.get(id);
+                let target = match def.item_type {
+                    ItemType::Block | ItemType::Material => BagTarget::Material,
+                    _ => BagTarget::Main,
+                };
+                (target, def.max_stack)
+            })
+            .unwrap_or((BagTarget::Main, 99));
+        let left = inventory.try_add_item(item_id, count, max_stack, target);
+        if left > 0 {
+            spawn_dropped_item(
+                &mut commands,
+                item_id.to_owned(),
+                left,
+                player_pos,
+                Vec2::ZERO,
+                &item_registry,
+                &drop_limits,
+                &icon_registry,
+                &quad,
+                &fallback_lm,
+                &mut lit_materials,
+                &fallback_img.0,
+            );
+        }
+        return;
+    }
+
+    let Some(spec) = &rod.projectile else {
+        return;
+    };
+    let Some(world_pos) = target.0.map(|target| target.world_pos) else {
+        return;
+    };
+
+    // The bobber anchors wherever it lands so it can be reeled back in.
+    let projectile = Projectile {
+        on_hit: OnHit::Anchor,
+        ..Projectile::from_spec(spec, world_pos - player_pos, Some(player_entity))
+    };
+    let sprite = spec
+        .sprite
+        .as_ref()
+        .map(|path| asset_server.load(path.clone()));
+    let has_sprite = sprite.is_some();
+    let entity = spawn_projectile(&mut commands, projectile, player_pos, sprite);
+    let mut bobber = commands.entity(entity);
+    bobber.insert(Bobber::new(casts.next_salt()));
+    if !has_sprite {
+        bobber.insert(Sprite::from_color(
+            Color::srgb(0.9, 0.2, 0.2),
+            Vec2::splat(BOBBER_SIZE),
+        ));
+    }
+    cooldowns.start(hand, cooldown);
+}
+
+/// Float bobbers on liquid, run their bite timers and snap lines that
+/// were stretched too far or lost their rod.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_bobbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    table: Res<FishingTable>,
+    owners: Query<&Transform, Without<Bobber>>,
+    mut bobbers: Query<(Entity, &mut Transform, &mut Bobber, &mut Projectile)>,
+    mut bites: MessageWriter<FishingBite>,
+    mut particle_pool: ResMut<ParticlePool>,
+) {
+    let dt = time.delta_secs().min(MAX_DELTA_SECS);
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let tile_size = config.tile_size;
+    let seed = config.seed as u64;
+
+    for (entity, mut tf, mut bobber, mut projectile) in &mut bobbers {
+        let pos = tf.translation.truncate();
+        let Some(owner_tf) = projectile.owner.and_then(|owner| owners.get(owner).ok()) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let mut offset = owner_tf.translation.truncate() - pos;
+        if config.wrap_x {
+            let width = config.world_pixel_width();
+            offset.x -= width * (offset.x / width).round();
+        }
+        if offset.length() > MAX_LINE_LENGTH {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // A floating bobber sits on the surface; probe just under it.
+        let probe_y = match bobber.state {
+            BobberState::Cast => pos.y,
+            _ => bobber.rest_y - 1.0,
+        };
+        let (tile_x, tile_y) = world_to_tile(pos.x, probe_y, tile_size);
+        let liquid = world_map.get_liquid(tile_x, tile_y, &ctx_ref);
+        let in_liquid = !liquid.is_empty();
+        // Follow the surface as the liquid level moves.
+        let surface_y = (tile_y as f32 + liquid.level.min(1.0)) * tile_size;
+
+        match bobber.tick(dt, in_liquid, seed, &table.timing) {
+            Some(BobberEvent::Landed) => {
+                projectile.stuck = true;
+                projectile.velocity = Vec2::ZERO;
+                tf.rotation = Quat::IDENTITY;
+            }
+            Some(BobberEvent::Bite) => {
+                bites.write(FishingBite {
+                    bobber: entity,
+                    position: pos,
+                });
+                spawn_bite_mark(&mut particle_pool, Vec2::new(pos.x, surface_y));
+            }
+            Some(BobberEvent::Stranded) => {
+                projectile.stuck = false;
+            }
+            Some(BobberEvent::Missed) | None => {}
+        }
+
+        if bobber.state != BobberState::Cast {
+            bobber.rest_y = surface_y;
+            tf.translation.y = bobber.rest_y + bobber.bob_offset();
+        }
+    }
+}
+
+/// Exclamation mark of particles rising above a biting bobber.
+fn spawn_bite_mark(pool: &mut ParticlePool, at: Vec2) {
+    const COLOR: [f32; 4] = [1.0, 0.9, 0.3, 1.0];
+    for dot in 0..BITE_MARK_DOTS {
+        // Gap between the bottom dot and the bar above it.
+        let gap = if dot == 0 { 0.0 } else { 3.0 };
+        let y = BITE_MARK_OFFSET + gap + dot as f32 * 2.5;
+        pool.spawn(
+            at + Vec2::new(0.0, y),
+            Vec2::new(0.0, 12.0),
+            0.6,
+            2.0,
+            COLOR,
+            0.0,
+            true,
+        );
+    }
+}
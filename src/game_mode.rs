@@ -0,0 +1,213 @@
+//! Survival / Creative game mode.
+//!
+//! Chosen on the main menu before starting a world and switchable in game with
+//! the cheats-gated `/gamemode` chat command. Gameplay systems ask
+//! [`GameMode::is_creative`] instead of comparing variants themselves.
+
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::chat::{ChatCommandEvent, ChatState};
+use crate::cosmos::persistence::Universe;
+
+/// Current game mode.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Survival,
+    /// Instant block breaking, free placement, item catalog, double reach and
+    /// no damage to the player.
+    Creative,
+}
+
+impl GameMode {
+    pub fn is_creative(self) -> bool {
+        self == GameMode::Creative
+    }
+
+    /// Lower-case name, as typed in `/gamemode` and shown in menus.
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "survival" | "s" => Some(GameMode::Survival),
+            "creative" | "c" => Some(GameMode::Creative),
+            _ => None,
+        }
+    }
+
+    /// The other mode.
+    pub fn toggled(self) -> Self {
+        match self {
+            GameMode::Survival => GameMode::Creative,
+            GameMode::Creative => GameMode::Survival,
+        }
+    }
+
+    /// Multiplier on the player's break and place reach.
+    pub fn reach_multiplier(self) -> f32 {
+        if self.is_creative() {
+            2.0
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Whether cheat chat commands such as `/gamemode` are accepted.
+#[derive(Resource, Debug, Clone)]
+pub struct CheatsEnabled(pub bool);
+
+impl Default for CheatsEnabled {
+    fn default() -> Self {
+        Self(cfg!(debug_assertions))
+    }
+}
+
+/// `/gamemode [survival|creative]`: switch modes, or report the current one
+/// when called without an argument.
+pub fn handle_gamemode_command(
+    mut commands_in: MessageReader<ChatCommandEvent>,
+    cheats: Res<CheatsEnabled>,
+    mut mode: ResMut<GameMode>,
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    for cmd in commands_in.read() {
+        if cmd.command != "gamemode" {
+            continue;
+        }
+        let reply = if !cheats.0 {
+            "Cheats are disabled.".to_string()
+        } else {
+            match cmd.args.first() {
+                None => format!("Game mode: {}", mode.name()),
+                Some(arg) => match GameMode::from_name(arg) {
+                    Some(new_mode) => {
+                        *mode = new_mode;
+                        format!("Game mode set to {}", new_mode.name())
+                    }
+                    None => format!("Unknown game mode '{arg}' (survival, creative)"),
+                },
+            }
+        };
+        if let Some(chat) = chat.as_mut() {
+            chat.send_system(&reply, time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Keep the save header's mode in step with the live one.
+pub fn record_game_mode(mode: Res<GameMode>, mut universe: ResMut<Universe>) {
+    universe.game_mode = *mode;
+}
+
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<CheatsEnabled>()
+            .add_systems(
+                Update,
+                (
+                    handle_gamemode_command,
+                    record_game_mode.run_if(resource_changed::<GameMode>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{BagTarget, Hotbar, Inventory};
+    use crate::player::Player;
+
+    fn command_app(cheats: bool) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<ChatCommandEvent>()
+            .init_resource::<Universe>()
+            .insert_resource(ChatState::new(10))
+            .add_plugins(GameModePlugin)
+            .insert_resource(CheatsEnabled(cheats));
+        app
+    }
+
+    fn send(app: &mut App, command: &str, args: &[&str]) {
+        app.world_mut().write_message(ChatCommandEvent {
+            command: command.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        });
+        app.update();
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for mode in [GameMode::Survival, GameMode::Creative] {
+            assert_eq!(GameMode::from_name(mode.name()), Some(mode));
+            assert_eq!(mode.toggled().toggled(), mode);
+        }
+        assert_eq!(GameMode::from_name("CREATIVE"), Some(GameMode::Creative));
+        assert_eq!(GameMode::from_name("peaceful"), None);
+    }
+
+    #[test]
+    fn command_switches_mode_and_updates_save_header() {
+        let mut app = command_app(true);
+        send(&mut app, "gamemode", &["creative"]);
+        assert_eq!(*app.world().resource::<GameMode>(), GameMode::Creative);
+        assert_eq!(
+            app.world().resource::<Universe>().game_mode,
+            GameMode::Creative
+        );
+
+        send(&mut app, "gamemode", &["bogus"]);
+        assert_eq!(*app.world().resource::<GameMode>(), GameMode::Creative);
+    }
+
+    #[test]
+    fn command_requires_cheats() {
+        let mut app = command_app(false);
+        send(&mut app, "gamemode", &["creative"]);
+        assert_eq!(*app.world().resource::<GameMode>(), GameMode::Survival);
+        let chat = app.world().resource::<ChatState>();
+        assert_eq!(chat.messages.last().unwrap().text, "Cheats are disabled.");
+    }
+
+    #[test]
+    fn switching_modes_keeps_inventory() {
+        let mut app = command_app(true);
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("dirt", 37, 999, BagTarget::Main);
+        inventory.try_add_item("torch", 5, 99, BagTarget::Main);
+        let mut hotbar = Hotbar::new();
+        hotbar.slots[0].left_hand = Some("dirt".into());
+        let player = app.world_mut().spawn((Player, inventory, hotbar)).id();
+
+        for mode in ["creative", "survival", "creative", "survival"] {
+            send(&mut app, "gamemode", &[mode]);
+        }
+
+        let inventory = app.world().get::<Inventory>(player).unwrap();
+        assert_eq!(inventory.count_item("dirt"), 37);
+        assert_eq!(inventory.count_item("torch"), 5);
+        let filled = inventory
+            .main_bag
+            .iter()
+            .chain(&inventory.material_bag)
+            .filter(|s| s.is_some())
+            .count();
+        assert_eq!(filled, 2);
+        let hotbar = app.world().get::<Hotbar>(player).unwrap();
+        assert_eq!(hotbar.slots[0].left_hand.as_deref(), Some("dirt"));
+    }
+}
@@ -1,111 +1,286 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
 use bevy::sprite_render::MeshMaterial2d;
-use bevy::window::PrimaryWindow;
 
 use crate::combat::block_damage::{BlockDamageMap, BlockDamageState};
+use crate::combat::Health;
 use crate::particles::pool::ParticlePool;
-use crate::cosmos::persistence::{DirtyChunks, DROPPED_ITEM_LIFETIME_SECS};
+use crate::cosmos::persistence::DirtyChunks;
 use crate::cosmos::pressurization::PressureMap;
 use crate::crafting::CraftingStation;
-use crate::inventory::{Hotbar, Inventory};
-use crate::item::{calculate_drops, DropDef, DroppedItem, ItemRegistry, SpawnParams};
+use crate::crafting::KnownRecipes;
+use crate::game_mode::GameMode;
+use crate::inventory::{BagTarget, Hand, Hotbar, Inventory, ItemPickupEvent};
+use crate::item::{
+    calculate_drops, DropDef, DroppedItem, DroppedItemLimits, ItemAction, ItemDef, ItemRegistry,
+    ItemType, SpawnParams,
+};
 use crate::object::definition::ObjectType;
 use crate::object::placement::{can_place_object, get_object_at, place_object, remove_object};
 use crate::object::plugin::{ObjectAnimation, ObjectSpriteMaterials};
 use crate::object::registry::ObjectRegistry;
 use crate::object::spawn::{ObjectDisplayChunk, PlacedObjectEntity};
 use crate::physics::{Bounce, Friction, Gravity, Grounded, TileCollider, Velocity};
+use crate::player::stats::SpeedBoost;
 use crate::player::Player;
-use crate::registry::tile::TileId;
+use crate::projectile::{spawn_projectile, Projectile};
+use crate::registry::player::PlayerConfig;
+use crate::registry::tile::{TileId, TILE_STATE_OFF};
 use crate::ui::game_ui::icon_registry::ItemIconRegistry;
+use crate::ui::game_ui::sign_editor::SignEditor;
+use crate::ui::input_capture::InputCapture;
 use crate::world::chunk::{
-    tile_to_chunk, update_bitmasks_around, world_to_tile, ChunkDirty, Layer, LoadedChunks, WorldMap,
+    tile_to_chunk, update_bitmasks_around, ChunkDirty, Layer, LoadedChunks, TileBroken,
+    TileChanged, WorldMap,
 };
-use crate::world::ctx::WorldCtx;
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::culling::DistanceBand;
 use crate::world::lit_sprite::{
     FallbackItemImage, FallbackLightmap, LitSprite, LitSpriteMaterial, SharedLitQuad,
 };
 use crate::world::rc_lighting::RcGridDirty;
+use crate::world::spatial_index::TrackedInIndex;
 
-use super::use_item::ItemUsedThisFrame;
+use super::hand_action::{resolve_hand_action, use_cooldown, Cooldowns, HandCooldowns};
+use super::layer_target::resolve_layer;
+use super::orientation::PlacementOrientation;
+use super::rope;
+use super::schematic::SchematicTool;
+use super::target::TargetTile;
+use super::use_item::consume_item;
 
 /// Dropped item display size in pixels (icons are 16×16).
 const DROPPED_ITEM_SIZE: f32 = 16.0;
 /// Fallback size for items without an icon.
 const DROPPED_ITEM_FALLBACK_SIZE: f32 = 8.0;
+/// Launch speed (px/s) of thrown items.
+const THROW_SPEED: f32 = 300.0;
+
+/// Game modes in which drops from broken blocks go straight into the
+/// inventory instead of spawning as items to collect.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DirectDrops {
+    pub survival: bool,
+    pub creative: bool,
+}
+
+impl DirectDrops {
+    pub fn enabled(&self, mode: GameMode) -> bool {
+        match mode {
+            GameMode::Survival => self.survival,
+            GameMode::Creative => self.creative,
+        }
+    }
+}
+
+/// Roll `tile_drops`, moving what fits into `stow` (the inventory, when
+/// drops go straight there). Returns what is left to spawn in the world.
+fn roll_drops(
+    tile_drops: &[DropDef],
+    stow: Option<&mut Inventory>,
+    item_registry: &ItemRegistry,
+    pickups: &mut MessageWriter<ItemPickupEvent>,
+) -> Vec<(String, u16)> {
+    let drops = calculate_drops(tile_drops);
+    let Some(inventory) = stow else {
+        return drops;
+    };
+    let mut left = Vec::new();
+    for (item_id, count) in drops {
+        let Some(def) = item_registry
+            .by_name(&item_id)
+            .map(|id| item_registry.get(id))
+        else {
+            left.push((item_id, count));
+            continue;
+        };
+        let target = match def.item_type {
+            ItemType::Block | ItemType::Material => BagTarget::Material,
+            _ => BagTarget::Main,
+        };
+        let remaining = inventory.try_add_item(&item_id, count, def.max_stack, target);
+        if remaining < count {
+            pickups.write(ItemPickupEvent {
+                item_id: item_id.clone(),
+                count: count - remaining,
+            });
+        }
+        if remaining > 0 {
+            left.push((item_id, remaining));
+        }
+    }
+    left
+}
 
 /// Spawn dropped items at a tile position with random trajectories and lit-sprite materials.
+#[allow(clippy::too_many_arguments)]
 fn spawn_tile_drops(
     commands: &mut Commands,
-    tile_drops: &[DropDef],
+    drops: Vec<(String, u16)>,
     tile_center: Vec2,
     item_registry: &ItemRegistry,
+    drop_limits: &DroppedItemLimits,
     icon_registry: &ItemIconRegistry,
     quad: &SharedLitQuad,
     fallback_lm: &FallbackLightmap,
     lit_materials: &mut Assets<LitSpriteMaterial>,
     fallback_image: &Handle<Image>,
 ) {
-    let drops = calculate_drops(tile_drops);
     for (item_id, count) in drops {
         let params = SpawnParams::random(tile_center);
+        spawn_dropped_item(
+            commands,
+            item_id,
+            count,
+            tile_center,
+            params.velocity(),
+            item_registry,
+            drop_limits,
+            icon_registry,
+            quad,
+            fallback_lm,
+            lit_materials,
+            fallback_image,
+        );
+    }
+}
 
-        // Resolve sprite texture from icon registry
-        let (sprite_image, size) = item_registry
-            .by_name(&item_id)
-            .and_then(|id| icon_registry.get(id).cloned())
-            .map(|img| (img, DROPPED_ITEM_SIZE))
-            .unwrap_or_else(|| (fallback_image.clone(), DROPPED_ITEM_FALLBACK_SIZE));
-
-        let material = lit_materials.add(LitSpriteMaterial {
-            sprite: sprite_image,
-            lightmap: fallback_lm.0.clone(),
-            lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
-            sprite_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
-            submerge_tint: Vec4::ZERO,
-            highlight: Vec4::ZERO,
-            tint: Vec4::ONE,
-        });
+/// Spawn a dropped item stack with a lit-sprite material and return its entity.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_dropped_item(
+    commands: &mut Commands,
+    item_id: String,
+    count: u16,
+    position: Vec2,
+    vel: Vec2,
+    item_registry: &ItemRegistry,
+    drop_limits: &DroppedItemLimits,
+    icon_registry: &ItemIconRegistry,
+    quad: &SharedLitQuad,
+    fallback_lm: &FallbackLightmap,
+    lit_materials: &mut Assets<LitSpriteMaterial>,
+    fallback_image: &Handle<Image>,
+) -> Entity {
+    let item = item_registry.by_name(&item_id);
+    let rarity = item
+        .map(|id| item_registry.get(id).rarity)
+        .unwrap_or_default();
+    let lifetime_secs = drop_limits.lifetime(rarity);
 
-        let vel = params.velocity();
+    // Resolve sprite texture from icon registry
+    let (sprite_image, size) = item
+        .and_then(|id| icon_registry.get(id).cloned())
+        .map(|img| (img, DROPPED_ITEM_SIZE))
+        .unwrap_or_else(|| (fallback_image.clone(), DROPPED_ITEM_FALLBACK_SIZE));
 
-        commands.spawn((
-            DroppedItem {
-                item_id,
-                count,
-                lifetime: Timer::from_seconds(DROPPED_ITEM_LIFETIME_SECS, TimerMode::Once),
-            },
-            LitSprite,
-            Velocity { x: vel.x, y: vel.y },
-            Gravity(400.0),
-            Grounded(false),
-            TileCollider {
-                width: 4.0,
-                height: 4.0,
-            },
-            Friction(0.9),
-            Bounce(0.3),
-            Mesh2d(quad.0.clone()),
-            MeshMaterial2d(material),
-            Transform::from_translation(tile_center.extend(1.0))
-                .with_scale(Vec3::new(size, size, 1.0)),
-        ));
-    }
+    let material = lit_materials.add(LitSpriteMaterial {
+        sprite: sprite_image,
+        lightmap: fallback_lm.0.clone(),
+        lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
+        sprite_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0),
+        submerge_tint: Vec4::ZERO,
+        highlight: Vec4::ZERO,
+        tint: Vec4::ONE,
+    });
+
+    commands.spawn((
+        DroppedItem {
+            item_id,
+            count,
+            lifetime: Timer::from_seconds(lifetime_secs, TimerMode::Once),
+        },
+        LitSprite,
+        Velocity { x: vel.x, y: vel.y },
+        Gravity(400.0),
+        Grounded(false),
+        TileCollider {
+            width: 4.0,
+            height: 4.0,
+        },
+        Friction(0.9),
+        Bounce(0.3),
+        DistanceBand::default(),
+        TrackedInIndex::default(),
+        Mesh2d(quad.0.clone()),
+        MeshMaterial2d(material),
+        Transform::from_translation(position.extend(1.0)).with_scale(Vec3::new(size, size, 1.0)),
+    ))
+    .id()
 }
 
-const BLOCK_REACH: f32 = 5.0;
+/// Wrap-aware per-axis distance (in tiles) from the player's tile to a target tile.
+pub(super) fn reach_offset(
+    player_pos: Vec2,
+    tile_x: i32,
+    tile_y: i32,
+    tile_size: f32,
+    width_tiles: i32,
+) -> (f32, f32) {
+    let player_tile_x = (player_pos.x / tile_size).floor();
+    let player_tile_y = (player_pos.y / tile_size).floor();
+    let raw_dx = (tile_x as f32 - player_tile_x).abs();
+    let dx = raw_dx.min(width_tiles as f32 - raw_dx);
+    let dy = (tile_y as f32 - player_tile_y).abs();
+    (dx, dy)
+}
 
-#[allow(clippy::too_many_arguments)]
+/// Whether a tile at the given offset from the player can be broken, with the
+/// configured reach scaled by `scale`.
+pub(super) fn within_break_reach((dx, dy): (f32, f32), config: &PlayerConfig, scale: f32) -> bool {
+    let reach = config.break_reach * scale;
+    dx <= reach && dy <= reach
+}
+
+/// Whether a tile at the given offset from the player can be placed, with the
+/// configured reach scaled by `scale`.
+pub(super) fn within_place_reach((dx, dy): (f32, f32), config: &PlayerConfig, scale: f32) -> bool {
+    let reach = config.place_reach * scale;
+    dx <= reach && dy <= reach
+}
+
+/// Items consumed from the inventory per placed tile or object; creative
+/// placement is free.
+pub(super) fn placement_cost(mode: GameMode) -> u16 {
+    if mode.is_creative() { 0 } else { 1 }
+}
+
+/// Item definition for a held item id.
+pub(super) fn held_item_def<'a>(registry: &'a ItemRegistry, item_id: Option<&str>) -> Option<&'a ItemDef> {
+    item_id
+        .and_then(|id| registry.by_name(id))
+        .map(|id| registry.get(id))
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn block_interaction_system(
     mut commands: Commands,
-    mouse: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
-    mut player_query: Query<(&Transform, &mut Hotbar, &mut Inventory), With<Player>>,
+    input: (
+        Res<ButtonInput<MouseButton>>,
+        Res<TargetTile>,
+        Res<GameMode>,
+        Option<Res<SchematicTool>>,
+        Option<Res<PlacementOrientation>>,
+        Option<ResMut<Cooldowns>>,
+        Option<Res<DirectDrops>>,
+    ),
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Hotbar,
+            &mut Inventory,
+            &mut HandCooldowns,
+            Option<&mut Health>,
+            Option<&mut SpeedBoost>,
+        ),
+        With<Player>,
+    >,
     ctx: WorldCtx,
     mut world_map: ResMut<WorldMap>,
     loaded_chunks: Res<LoadedChunks>,
     item_registry: Res<ItemRegistry>,
+    mut known_recipes: ResMut<KnownRecipes>,
     icon_registry: Res<ItemIconRegistry>,
     quad: Res<SharedLitQuad>,
     fallbacks: (
@@ -116,6 +291,8 @@ pub fn block_interaction_system(
         Option<ResMut<PressureMap>>,
         Res<Time>,
         ResMut<BlockDamageMap>,
+        Res<DroppedItemLimits>,
+        Res<AssetServer>,
     ),
     mut lit_materials: ResMut<Assets<LitSpriteMaterial>>,
     object_registry: Option<Res<ObjectRegistry>>,
@@ -123,72 +300,259 @@ pub fn block_interaction_system(
     object_params: (
         Query<(Entity, &PlacedObjectEntity)>,
         Option<ResMut<crate::liquid::LiquidSimState>>,
-        Res<ItemUsedThisFrame>,
-        Res<crate::chat::ChatState>,
+        Res<InputCapture>,
         ResMut<ParticlePool>,
+        ResMut<SignEditor>,
+        (
+            MessageWriter<TileChanged>,
+            MessageWriter<TileBroken>,
+            MessageWriter<ItemPickupEvent>,
+        ),
     ),
 ) {
-    let (object_entities, mut liquid_sim, item_used, chat_state, mut particle_pool) = object_params;
+    let (mouse, target, game_mode, schematic_tool, orientation, mut item_cooldowns, direct_drops) =
+        input;
+    let direct_drops = direct_drops.is_some_and(|d| d.enabled(*game_mode));
+    let orientation = orientation.map(|o| *o).unwrap_or_default();
+    let (
+        object_entities,
+        mut liquid_sim,
+        capture,
+        mut particle_pool,
+        mut sign_editor,
+        (mut tile_changes, mut tile_breaks, mut pickups),
+    ) = object_params;
 
-    if chat_state.is_active {
+    // Clicks on a UI panel, or while typing, must not reach the world.
+    if capture.pointer || capture.keyboard {
         return;
     }
-    let (fallback_lm, fallback_img, mut rc_dirty, mut dirty_chunks, mut pressure_map, time, mut block_damage_map) = fallbacks;
-    let left_held = mouse.pressed(MouseButton::Left);
-    let right_click = mouse.just_pressed(MouseButton::Right);
-    if !left_held && !right_click {
+    // Selecting or pasting a schematic owns the clicks.
+    if schematic_tool.is_some_and(|tool| tool.claims_pointer()) {
         return;
     }
-
-    if right_click && item_used.0 {
+    let (
+        fallback_lm,
+        fallback_img,
+        mut rc_dirty,
+        mut dirty_chunks,
+        mut pressure_map,
+        time,
+        mut block_damage_map,
+        drop_limits,
+        asset_server,
+    ) = fallbacks;
+    if !mouse.any_pressed([MouseButton::Left, MouseButton::Right]) {
         return;
     }
 
-    let Ok(window) = windows.single() else { return };
-    let Ok((camera, camera_gt)) = camera_query.single() else {
+    // Nothing aimed at: no cursor, window unfocused or pointer over UI.
+    let Some(target) = target.0 else {
         return;
     };
-    let Ok((player_tf, mut hotbar, mut inventory)) = player_query.single_mut() else {
+    let Ok((
+        player_entity,
+        player_tf,
+        mut hotbar,
+        mut inventory,
+        mut cooldowns,
+        mut health,
+        mut speed_boost,
+    )) = player_query.single_mut()
+    else {
         return;
     };
 
-    let Some(cursor_pos) = window.cursor_position() else {
+    let ctx_ref = ctx.as_ref();
+    let world_pos = target.world_pos;
+    let (tile_x, tile_y) = target.cursor_tile;
+    let player_pos = player_tf.translation.truncate();
+
+    // Each branch applies its own reach.
+    let (can_break, can_place) = (target.can_break, target.can_place);
+    let cost = placement_cost(*game_mode);
+    let tile_reachable = target.reachable();
+    let bg_modifier = target.bg_modifier;
+
+    // Right-click on a sign opens the text prompt for it.
+    if tile_reachable
+        && mouse.pressed(MouseButton::Right)
+        && !bg_modifier
+        && let Some(fg) = world_map.get_tile(tile_x, tile_y, Layer::Fg, &ctx_ref)
+        && ctx_ref.tile_registry.get(fg).sign
+    {
+        if mouse.just_pressed(MouseButton::Right) {
+            let text = world_map.get_sign_text(tile_x, tile_y, &ctx_ref);
+            sign_editor.open((tile_x, tile_y), text);
+        }
         return;
-    };
-    let Ok(world_pos) = camera.viewport_to_world_2d(camera_gt, cursor_pos) else {
+    }
+
+    // Right-click on a switchable foreground tile (lamp) toggles it on/off,
+    // unless the layer modifier redirects the click to the wall behind it.
+    // Holding the button afterwards must not mine the wall behind the lamp.
+    if tile_reachable
+        && mouse.pressed(MouseButton::Right)
+        && !bg_modifier
+        && let Some(fg) = world_map.get_tile(tile_x, tile_y, Layer::Fg, &ctx_ref)
+        && ctx_ref.tile_registry.get(fg).switchable
+    {
+        if !mouse.just_pressed(MouseButton::Right) {
+            return;
+        }
+        let state = world_map.get_tile_state(tile_x, tile_y, &ctx_ref);
+        world_map.set_tile_state(tile_x, tile_y, state ^ TILE_STATE_OFF, &ctx_ref);
+        let wrapped_x = ctx_ref.config.wrap_tile_x(tile_x);
+        dirty_chunks
+            .0
+            .insert(tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size));
+        // Emission changed: rebuild the cached lighting grids.
+        rc_dirty.0 = true;
+        return;
+    }
+
+    // Left mouse uses the left hand and right mouse the right hand. Take the
+    // first hand that is off cooldown, holding an item that is off cooldown,
+    // and whose action fires this frame.
+    let Some((hand, item_id, action)) = [Hand::Left, Hand::Right].into_iter().find_map(|hand| {
+        let button = hand.button();
+        if !mouse.pressed(button) || !cooldowns.ready(hand) {
+            return None;
+        }
+        let item_id = hotbar
+            .get_item_for_hand(hand == Hand::Left)
+            .map(str::to_owned);
+        let action = resolve_hand_action(held_item_def(&item_registry, item_id.as_deref()), hand);
+        let item_ready = item_id
+            .as_deref()
+            .zip(item_cooldowns.as_deref())
+            .is_none_or(|(id, item_cooldowns)| item_cooldowns.ready(id, action));
+        (item_ready && (action.repeats_while_held() || mouse.just_pressed(button)))
+            .then_some((hand, item_id, action))
+    }) else {
         return;
     };
+    let item_def = held_item_def(&item_registry, item_id.as_deref());
+    let cooldown = use_cooldown(item_def, action);
 
-    let ctx_ref = ctx.as_ref();
-    let (tile_x, tile_y) = world_to_tile(world_pos.x, world_pos.y, ctx_ref.config.tile_size);
+    match action {
+        ItemAction::Consume => {
+            if let Some(def) = item_def
+                && consume_item(
+                    def,
+                    &mut inventory,
+                    &mut known_recipes,
+                    health.as_deref_mut(),
+                    speed_boost.as_deref_mut(),
+                )
+            {
+                cooldowns.start(hand, cooldown);
+                if let Some(item_cooldowns) = item_cooldowns.as_deref_mut() {
+                    item_cooldowns.start(def, action);
+                }
+            }
+            return;
+        }
+        ItemAction::Throw => {
+            let Some(item_id) = item_id else {
+                return;
+            };
+            if !inventory.remove_item(&item_id, 1) {
+                return;
+            }
+            if let Some(def) = item_def
+                && let Some(item_cooldowns) = item_cooldowns.as_deref_mut()
+            {
+                item_cooldowns.start(def, action);
+            }
+            let direction = world_pos - player_pos;
+            // Items with a projectile spec fly as projectiles (bombs, hooks);
+            // anything else is tossed as a dropped item.
+            if let Some(def) = item_def
+                && let Some(spec) = &def.projectile
+            {
+                let stats = def.stats.as_ref();
+                let projectile = Projectile {
+                    damage: stats.and_then(|s| s.damage).unwrap_or(0.0),
+                    knockback: stats.and_then(|s| s.knockback).unwrap_or(0.0),
+                    ..Projectile::from_spec(spec, direction, Some(player_entity))
+                };
+                let sprite = match &spec.sprite {
+                    Some(path) => Some(asset_server.load(path.clone())),
+                    None => item_registry
+                        .by_name(&item_id)
+                        .and_then(|id| icon_registry.get(id).cloned()),
+                };
+                spawn_projectile(&mut commands, projectile, player_pos, sprite);
+                cooldowns.start(hand, cooldown);
+                return;
+            }
+            let velocity = direction.normalize_or_zero() * THROW_SPEED;
+            spawn_dropped_item(
+                &mut commands,
+                item_id,
+                1,
+                player_pos,
+                velocity,
+                &item_registry,
+                &drop_limits,
+                &icon_registry,
+                &quad,
+                &fallback_lm,
+                &mut lit_materials,
+                &fallback_img.0,
+            );
+            cooldowns.start(hand, cooldown);
+            return;
+        }
+        // Casting and reeling are handled by `fishing::fishing_rod_system`.
+        ItemAction::Fish => return,
+        ItemAction::Mine | ItemAction::PlaceFg | ItemAction::PlaceBg | ItemAction::ModifyTile => {}
+    }
 
-    // Range check (wrap-aware on X axis)
-    let player_tile_x = (player_tf.translation.x / ctx_ref.config.tile_size).floor();
-    let player_tile_y = (player_tf.translation.y / ctx_ref.config.tile_size).floor();
-    let raw_dx = (tile_x as f32 - player_tile_x).abs();
-    let dx = raw_dx.min(ctx_ref.config.width_tiles as f32 - raw_dx);
-    let dy = (tile_y as f32 - player_tile_y).abs();
-    if dx > BLOCK_REACH || dy > BLOCK_REACH {
+    if !tile_reachable {
         return;
     }
+    let Some(layer) = resolve_layer(action, hand, bg_modifier) else {
+        return;
+    };
+    let mining = action == ItemAction::Mine;
+    let fg_present = world_map
+        .get_tile(tile_x, tile_y, Layer::Fg, &ctx_ref)
+        .is_some_and(|t| ctx_ref.tile_registry.is_solid(t));
+    let bg_present = world_map
+        .get_tile(tile_x, tile_y, Layer::Bg, &ctx_ref)
+        .is_some_and(|t| t != TileId::AIR);
+    // Tiles whose bitmasks need refreshing afterwards.
+    let mut edited = vec![(tile_x, tile_y)];
 
-    if left_held {
+    if layer == Layer::Fg {
         // Check for object first
         if let Some(ref obj_reg) = object_registry {
             if let Some((anchor_x, anchor_y, obj_idx, obj_id)) =
                 get_object_at(&world_map, tile_x, tile_y, &ctx_ref)
             {
+                if !mining || !can_break {
+                    return;
+                }
                 // Break object
                 let def = obj_reg.get(obj_id);
                 let tile_center = Vec2::new(
                     tile_x as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
                     tile_y as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
                 );
+                let drops = roll_drops(
+                    &def.drops,
+                    direct_drops.then_some(&mut inventory).map(|i| &mut **i),
+                    &item_registry,
+                    &mut pickups,
+                );
                 spawn_tile_drops(
                     &mut commands,
-                    &def.drops,
+                    drops,
                     tile_center,
                     &item_registry,
+                    &drop_limits,
                     &icon_registry,
                     &quad,
                     &fallback_lm,
@@ -214,7 +578,12 @@ pub fn block_interaction_system(
                     obj_idx,
                     &ctx_ref,
                 );
+                tile_changes.write(TileChanged {
+                    tile_x: anchor_x,
+                    tile_y: anchor_y,
+                });
                 dirty_chunks.0.insert((data_cx, data_cy));
+                cooldowns.start(hand, cooldown);
                 return;
             }
         }
@@ -224,18 +593,42 @@ pub fn block_interaction_system(
             return;
         };
 
-        if ctx_ref.tile_registry.is_solid(current) {
+        if action == ItemAction::ModifyTile {
+            // Only tiles the item lists turn into something; the rest are
+            // left alone.
+            let current_name = &ctx_ref.tile_registry.get(current).id;
+            let Some(converted) = item_def
+                .and_then(|def| def.converted_tile(current_name))
+                .and_then(|name| ctx_ref.tile_registry.try_by_name(name))
+            else {
+                return;
+            };
+            if !can_break {
+                return;
+            }
+            world_map.set_tile(tile_x, tile_y, Layer::Fg, converted, &ctx_ref);
+            tile_changes.write(TileChanged { tile_x, tile_y });
+            let wrapped_x = ctx_ref.config.wrap_tile_x(tile_x);
+            dirty_chunks
+                .0
+                .insert(tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size));
+            wear_tool(&mut hotbar, hand);
+            cooldowns.start(hand, cooldown);
+        } else if mining {
+            // Bedrock holds even in creative mode. Climbable tiles are not
+            // solid but still mine like blocks.
+            let breakable = fg_present || ctx_ref.tile_registry.is_climbable(current);
+            if !breakable || !can_break || ctx_ref.tile_registry.is_unbreakable(current) {
+                return;
+            }
             // Accumulate mining damage instead of instant break
             let dt = time.delta_secs();
             let tile_def = ctx_ref.tile_registry.get(current);
             let hardness = tile_def.hardness;
 
-            // Get mining_power from active left-hand item, default 1.0
-            let mining_power = hotbar.slots[hotbar.active_slot]
-                .left_hand
-                .as_deref()
-                .and_then(|item_id| item_registry.by_name(item_id))
-                .and_then(|id| item_registry.get(id).stats.as_ref())
+            // Get mining_power from the mining hand's item, default 1.0
+            let mining_power = item_def
+                .and_then(|def| def.stats.as_ref())
                 .and_then(|stats| stats.mining_power)
                 .unwrap_or(1.0);
 
@@ -247,7 +640,11 @@ pub fn block_interaction_system(
                     regen_timer: 0.0,
                     particle_timer: 0.0,
                 });
-            state.accumulated += mining_power * dt;
+            if game_mode.is_creative() {
+                state.accumulated = hardness;
+            } else {
+                state.accumulated += mining_power * dt;
+            }
             state.regen_timer = 0.0;
 
             state.particle_timer += dt;
@@ -286,28 +683,24 @@ pub fn block_interaction_system(
                 // Block destroyed
                 block_damage_map.damage.remove(&(tile_x, tile_y));
 
-                // Decrement tool durability
-                {
-                    let active = hotbar.active_slot;
-                    let slot = &mut hotbar.slots[active];
-                    if let Some(ref mut dur) = slot.left_durability {
-                        *dur = dur.saturating_sub(1);
-                        if *dur == 0 {
-                            slot.left_hand = None;
-                            slot.left_durability = None;
-                        }
-                    }
-                }
+                wear_tool(&mut hotbar, hand);
 
                 let tile_center = Vec2::new(
                     tile_x as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
                     tile_y as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
                 );
+                let drops = roll_drops(
+                    &tile_def.drops,
+                    direct_drops.then_some(&mut inventory).map(|i| &mut **i),
+                    &item_registry,
+                    &mut pickups,
+                );
                 spawn_tile_drops(
                     &mut commands,
-                    &tile_def.drops,
+                    drops,
                     tile_center,
                     &item_registry,
+                    &drop_limits,
                     &icon_registry,
                     &quad,
                     &fallback_lm,
@@ -315,6 +708,13 @@ pub fn block_interaction_system(
                     &fallback_img.0,
                 );
                 world_map.set_tile(tile_x, tile_y, Layer::Fg, TileId::AIR, &ctx_ref);
+                tile_changes.write(TileChanged { tile_x, tile_y });
+                tile_breaks.write(TileBroken {
+                    tile_x,
+                    tile_y,
+                    layer: Layer::Fg,
+                    tile: current,
+                });
                 // Wake liquid neighbors when a solid tile is removed.
                 if let Some(ref mut sim) = liquid_sim {
                     sim.sleep.wake_with_neighbors(tile_x, tile_y);
@@ -323,17 +723,57 @@ pub fn block_interaction_system(
                 let (dirty_cx, dirty_cy) =
                     tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size);
                 dirty_chunks.0.insert((dirty_cx, dirty_cy));
+
+                // Ropes hanging from the broken tile come down with it.
+                let hanging = rope::hanging_below(&world_map, tile_x, tile_y, &ctx_ref);
+                for ((x, y), hanging) in hanging {
+                    let center = Vec2::new(
+                        x as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
+                        y as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
+                    );
+                    let drops = roll_drops(
+                        &ctx_ref.tile_registry.get(hanging).drops,
+                        direct_drops.then_some(&mut inventory).map(|i| &mut **i),
+                        &item_registry,
+                        &mut pickups,
+                    );
+                    spawn_tile_drops(
+                        &mut commands,
+                        drops,
+                        center,
+                        &item_registry,
+                        &drop_limits,
+                        &icon_registry,
+                        &quad,
+                        &fallback_lm,
+                        &mut lit_materials,
+                        &fallback_img.0,
+                    );
+                    world_map.set_tile(x, y, Layer::Fg, TileId::AIR, &ctx_ref);
+                    tile_changes.write(TileChanged {
+                        tile_x: x,
+                        tile_y: y,
+                    });
+                    let wrapped_x = ctx_ref.config.wrap_tile_x(x);
+                    dirty_chunks
+                        .0
+                        .insert(tile_to_chunk(wrapped_x, y, ctx_ref.config.chunk_size));
+                    edited.push((x, y));
+                }
+                cooldowns.start(hand, cooldown);
             } else {
                 // Damage accumulated but block not yet destroyed — skip post-break logic
                 return;
             }
         } else {
-            // Left-click on air = place from left hand (objects then tiles).
-            // This is intentional: left-hand items use left-click, right-hand items use right-click.
-            let Some(item_id) = hotbar.slots[hotbar.active_slot].left_hand.as_deref() else {
+            // Place from the clicking hand (objects then tiles).
+            if fg_present || !can_place {
+                return;
+            }
+            let Some(item_id) = item_id.as_deref() else {
                 return;
             };
-            if inventory.count_item(item_id) == 0 {
+            if inventory.count_item(item_id) < cost as u32 {
                 return;
             }
 
@@ -343,7 +783,8 @@ pub fn block_interaction_system(
                     if let Some(obj_id) = obj_reg.by_name(&obj_name) {
                         if can_place_object(&world_map, obj_reg, obj_id, tile_x, tile_y, &ctx_ref) {
                             place_object(&mut world_map, obj_reg, obj_id, tile_x, tile_y, &ctx_ref);
-                            inventory.remove_item(item_id, 1);
+                            tile_changes.write(TileChanged { tile_x, tile_y });
+                            inventory.remove_item(item_id, cost);
 
                             // Spawn entity for the new object
                             let def = obj_reg.get(obj_id);
@@ -469,6 +910,7 @@ pub fn block_interaction_system(
                                     }
                                 }
                             }
+                            cooldowns.start(hand, cooldown);
                             return;
                         }
                     }
@@ -476,61 +918,90 @@ pub fn block_interaction_system(
             }
 
             // Fall back to tile placement
-            let has_neighbor = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|&(dx, dy)| {
-                let nx = tile_x + dx;
-                let ny = tile_y + dy;
-                world_map
-                    .get_tile(nx, ny, Layer::Fg, &ctx_ref)
-                    .is_some_and(|t| ctx_ref.tile_registry.is_solid(t))
-                    || world_map
-                        .get_tile(nx, ny, Layer::Bg, &ctx_ref)
-                        .is_some_and(|t| t != TileId::AIR)
-            });
-            if !has_neighbor {
-                return;
-            }
-
             let Some(place_id) = resolve_placeable(item_id, &item_registry, &ctx_ref) else {
                 return;
             };
 
+            // Climbable tiles are not built over; placing more of a rope on
+            // it lengthens the rope at the bottom instead.
+            let (place_x, place_y) = if ctx_ref.tile_registry.is_climbable(current) {
+                if place_id != current || !ctx_ref.tile_registry.is_hanging(current) {
+                    return;
+                }
+                let Some(bottom) = rope::extension_tile(&world_map, tile_x, tile_y, &ctx_ref)
+                else {
+                    return;
+                };
+                bottom
+            } else {
+                if !has_place_neighbor(&world_map, tile_x, tile_y, Layer::Fg, false, &ctx_ref) {
+                    return;
+                }
+                (tile_x, tile_y)
+            };
+            if ctx_ref.tile_registry.is_hanging(place_id)
+                && !rope::can_hang(&world_map, place_x, place_y, &ctx_ref)
+            {
+                return;
+            }
+            edited = vec![(place_x, place_y)];
+
             // Displace liquid when placing a solid tile.
             if let Some(ref mut sim) = liquid_sim {
-                let liquid = world_map.get_liquid(tile_x, tile_y, &ctx_ref);
+                let liquid = world_map.get_liquid(place_x, place_y, &ctx_ref);
                 if !liquid.is_empty() {
                     world_map.set_liquid(
-                        tile_x,
-                        tile_y,
+                        place_x,
+                        place_y,
                         crate::liquid::data::LiquidCell::EMPTY,
                         &ctx_ref,
                     );
-                    sim.sleep.wake_with_neighbors(tile_x, tile_y);
+                    sim.sleep.wake_with_neighbors(place_x, place_y);
                 }
             }
-            world_map.set_tile(tile_x, tile_y, Layer::Fg, place_id, &ctx_ref);
-            let wrapped_x = ctx_ref.config.wrap_tile_x(tile_x);
-            let (dirty_cx, dirty_cy) = tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size);
+            world_map.set_tile(place_x, place_y, Layer::Fg, place_id, &ctx_ref);
+            if ctx_ref.tile_registry.is_orientable(place_id) {
+                let facing = orientation.resolve(&world_map, place_x, place_y, &ctx_ref);
+                world_map.set_tile_state(place_x, place_y, facing.apply(0), &ctx_ref);
+            }
+            tile_changes.write(TileChanged {
+                tile_x: place_x,
+                tile_y: place_y,
+            });
+            let wrapped_x = ctx_ref.config.wrap_tile_x(place_x);
+            let (dirty_cx, dirty_cy) = tile_to_chunk(wrapped_x, place_y, ctx_ref.config.chunk_size);
             dirty_chunks.0.insert((dirty_cx, dirty_cy));
-            inventory.remove_item(item_id, 1);
+            inventory.remove_item(item_id, cost);
+            cooldowns.start(hand, cooldown);
         }
-    } else if right_click {
+    } else {
         // Background layer interaction
         let Some(current_bg) = world_map.get_tile(tile_x, tile_y, Layer::Bg, &ctx_ref) else {
             return;
         };
 
-        if current_bg != TileId::AIR {
+        if mining {
+            if !bg_present || !can_break || ctx_ref.tile_registry.is_unbreakable(current_bg) {
+                return;
+            }
             // Break bg tile
             let tile_def = ctx_ref.tile_registry.get(current_bg);
             let tile_center = Vec2::new(
                 tile_x as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
                 tile_y as f32 * ctx_ref.config.tile_size + ctx_ref.config.tile_size / 2.0,
             );
+            let drops = roll_drops(
+                &tile_def.drops,
+                direct_drops.then_some(&mut inventory).map(|i| &mut **i),
+                &item_registry,
+                &mut pickups,
+            );
             spawn_tile_drops(
                 &mut commands,
-                &tile_def.drops,
+                drops,
                 tile_center,
                 &item_registry,
+                &drop_limits,
                 &icon_registry,
                 &quad,
                 &fallback_lm,
@@ -538,32 +1009,33 @@ pub fn block_interaction_system(
                 &fallback_img.0,
             );
             world_map.set_tile(tile_x, tile_y, Layer::Bg, TileId::AIR, &ctx_ref);
+            tile_breaks.write(TileBroken {
+                tile_x,
+                tile_y,
+                layer: Layer::Bg,
+                tile: current_bg,
+            });
             let wrapped_x = ctx_ref.config.wrap_tile_x(tile_x);
             let (dirty_cx, dirty_cy) = tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size);
             dirty_chunks.0.insert((dirty_cx, dirty_cy));
+            cooldowns.start(hand, cooldown);
         } else {
-            // Place bg tile from right hand of active hotbar slot
-            let has_neighbor = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|&(dx, dy)| {
-                let nx = tile_x + dx;
-                let ny = tile_y + dy;
-                world_map
-                    .get_tile(nx, ny, Layer::Fg, &ctx_ref)
-                    .is_some_and(|t| t != TileId::AIR)
-                    || world_map
-                        .get_tile(nx, ny, Layer::Bg, &ctx_ref)
-                        .is_some_and(|t| t != TileId::AIR)
-            });
-            if !has_neighbor {
+            if bg_present || !can_place {
+                return;
+            }
+            // Place bg tile from the clicking hand.
+            // With the modifier held only walls count as anchors.
+            if !has_place_neighbor(&world_map, tile_x, tile_y, Layer::Bg, bg_modifier, &ctx_ref) {
                 return;
             }
 
-            let Some(item_id) = hotbar.slots[hotbar.active_slot].right_hand.as_deref() else {
+            let Some(item_id) = item_id.as_deref() else {
                 return;
             };
             let Some(place_id) = resolve_placeable(item_id, &item_registry, &ctx_ref) else {
                 return;
             };
-            if inventory.count_item(item_id) == 0 {
+            if inventory.count_item(item_id) < cost as u32 {
                 return;
             }
 
@@ -571,10 +1043,9 @@ pub fn block_interaction_system(
             let wrapped_x = ctx_ref.config.wrap_tile_x(tile_x);
             let (dirty_cx, dirty_cy) = tile_to_chunk(wrapped_x, tile_y, ctx_ref.config.chunk_size);
             dirty_chunks.0.insert((dirty_cx, dirty_cy));
-            inventory.remove_item(item_id, 1);
+            inventory.remove_item(item_id, cost);
+            cooldowns.start(hand, cooldown);
         }
-    } else {
-        return;
     }
 
     // Notify RC lighting that tiles changed — density/albedo/flat grids
@@ -587,11 +1058,11 @@ pub fn block_interaction_system(
     }
 
     // Update bitmasks for the modified layer
-    let modified_layer = if left_held { Layer::Fg } else { Layer::Bg };
-    let bitmask_dirty =
-        update_bitmasks_around(&mut world_map, tile_x, tile_y, modified_layer, &ctx_ref);
-
-    let all_dirty = bitmask_dirty;
+    let mut all_dirty = HashSet::new();
+    for (x, y) in edited {
+        let dirty = update_bitmasks_around(&mut world_map, x, y, layer, &ctx_ref);
+        all_dirty.extend(dirty);
+    }
 
     for (cx, cy) in all_dirty {
         for (&(display_cx, display_cy), entities) in &loaded_chunks.map {
@@ -603,6 +1074,56 @@ pub fn block_interaction_system(
     }
 }
 
+/// Take one durability point off the tool in `hand`'s active slot, breaking
+/// it at zero. Items without durability don't wear.
+fn wear_tool(hotbar: &mut Mut<Hotbar>, hand: Hand) {
+    let active = hotbar.active_slot;
+    let is_left = hand == Hand::Left;
+    let Some(dur) = hotbar.slots[active].durability(is_left) else {
+        return;
+    };
+    let slot = hotbar.slot_mut(active);
+    let dur = dur.saturating_sub(1);
+    if dur == 0 {
+        match hand {
+            Hand::Left => slot.left_hand = None,
+            Hand::Right => slot.right_hand = None,
+        }
+        slot.set_durability(is_left, None);
+    } else {
+        slot.set_durability(is_left, Some(dur));
+    }
+}
+
+/// Whether a tile placed at (tile_x, tile_y) on `layer` has an adjacent anchor.
+///
+/// Foreground blocks anchor to solid blocks or walls; walls anchor to any block
+/// or wall. With `bg_only` (layer modifier held) walls anchor to walls only.
+fn has_place_neighbor(
+    world_map: &WorldMap,
+    tile_x: i32,
+    tile_y: i32,
+    layer: Layer,
+    bg_only: bool,
+    ctx: &WorldCtxRef,
+) -> bool {
+    [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|&(dx, dy)| {
+        let nx = tile_x + dx;
+        let ny = tile_y + dy;
+        let fg_anchor = !bg_only
+            && world_map
+                .get_tile(nx, ny, Layer::Fg, ctx)
+                .is_some_and(|t| match layer {
+                    Layer::Fg => ctx.tile_registry.is_solid(t),
+                    Layer::Bg => t != TileId::AIR,
+                });
+        fg_anchor
+            || world_map
+                .get_tile(nx, ny, Layer::Bg, ctx)
+                .is_some_and(|t| t != TileId::AIR)
+    })
+}
+
 /// Look up item_id → placeable_object name. Returns None if not an object placer.
 fn resolve_placeable_object(item_id: &str, item_registry: &ItemRegistry) -> Option<String> {
     let item_def_id = item_registry.by_name(item_id)?;
@@ -611,13 +1132,432 @@ fn resolve_placeable_object(item_id: &str, item_registry: &ItemRegistry) -> Opti
 }
 
 /// Look up item_id → placeable tile name → TileId. Returns None if not placeable.
-fn resolve_placeable(
+pub(super) fn resolve_placeable(
     item_id: &str,
     item_registry: &ItemRegistry,
-    ctx: &crate::world::ctx::WorldCtxRef<'_>,
+    ctx: &WorldCtxRef<'_>,
 ) -> Option<TileId> {
     let item_def_id = item_registry.by_name(item_id)?;
     let item_def = item_registry.get(item_def_id);
     let tile_name = item_def.placeable.as_deref()?;
     Some(ctx.tile_registry.by_name(tile_name))
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::window::PrimaryWindow;
+
+    use super::*;
+    use crate::interaction::layer_target::LayerModifierKeys;
+    use crate::interaction::line_of_sight::EditLineOfSight;
+    use crate::interaction::target::target_resolution;
+    use crate::test_helpers::fixtures;
+
+    #[test]
+    fn reach_offset_wraps_across_seam() {
+        // Player standing in tile 1, target at the last column of a 2048-wide world.
+        let offset = reach_offset(Vec2::new(40.0, 100.0), 2047, 3, 32.0, 2048);
+        assert_eq!(offset, (2.0, 0.0));
+    }
+
+    #[test]
+    fn equal_reach_allows_break_and_place() {
+        let config = fixtures::test_player_config();
+        let offset = (5.0, 4.0);
+        assert!(within_break_reach(offset, &config, 1.0));
+        assert!(within_place_reach(offset, &config, 1.0));
+        assert!(!within_break_reach((6.0, 0.0), &config, 1.0));
+        assert!(!within_place_reach((0.0, 6.0), &config, 1.0));
+    }
+
+    #[test]
+    fn tile_beyond_break_reach_can_only_be_placed() {
+        let mut config = fixtures::test_player_config();
+        config.break_reach = 3.0;
+        config.place_reach = 6.0;
+        let offset = reach_offset(Vec2::new(16.0, 16.0), 5, 0, 32.0, 2048);
+        assert!(within_place_reach(offset, &config, 1.0));
+        assert!(!within_break_reach(offset, &config, 1.0));
+    }
+
+    #[test]
+    fn tile_beyond_place_reach_can_only_be_broken() {
+        let mut config = fixtures::test_player_config();
+        config.break_reach = 6.0;
+        config.place_reach = 3.0;
+        let offset = reach_offset(Vec2::new(16.0, 16.0), 0, -5, 32.0, 2048);
+        assert!(within_break_reach(offset, &config, 1.0));
+        assert!(!within_place_reach(offset, &config, 1.0));
+    }
+
+    #[test]
+    fn creative_doubles_reach_and_places_for_free() {
+        let config = fixtures::test_player_config();
+        let far = (config.break_reach + 1.0, 0.0);
+        let survival = GameMode::Survival.reach_multiplier();
+        let creative = GameMode::Creative.reach_multiplier();
+        assert!(!within_break_reach(far, &config, survival));
+        assert!(within_break_reach(far, &config, creative));
+        assert!(within_place_reach(far, &config, creative));
+
+        assert_eq!(placement_cost(GameMode::Survival), 1);
+        assert_eq!(placement_cost(GameMode::Creative), 0);
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("dirt", 3, 999, crate::inventory::BagTarget::Main);
+        assert!(inventory.remove_item("dirt", placement_cost(GameMode::Creative)));
+        assert_eq!(inventory.count_item("dirt"), 3);
+    }
+
+    /// Sky tile with nothing around it (fixture surface is far below).
+    const SKY: (i32, i32) = (100, 1000);
+
+    #[test]
+    fn wall_anchors_to_block_unless_modifier_held() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let (x, y) = SKY;
+        map.set_tile(x + 1, y, Layer::Fg, tr.by_name("stone"), &ctx);
+
+        assert!(has_place_neighbor(&map, x, y, Layer::Bg, false, &ctx));
+        assert!(!has_place_neighbor(&map, x, y, Layer::Bg, true, &ctx));
+    }
+
+    #[test]
+    fn wall_anchors_to_wall_with_modifier_held() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let (x, y) = SKY;
+        assert!(!has_place_neighbor(&map, x, y, Layer::Bg, true, &ctx));
+
+        map.set_tile(x, y - 1, Layer::Bg, tr.by_name("dirt"), &ctx);
+        assert!(has_place_neighbor(&map, x, y, Layer::Bg, true, &ctx));
+        assert!(has_place_neighbor(&map, x, y, Layer::Bg, false, &ctx));
+    }
+
+    #[test]
+    fn block_anchors_to_solid_block_or_wall() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let (x, y) = SKY;
+        map.get_or_generate_chunk(3, 31, &ctx);
+        assert!(!has_place_neighbor(&map, x, y, Layer::Fg, false, &ctx));
+
+        map.set_tile(x - 1, y, Layer::Bg, tr.by_name("dirt"), &ctx);
+        assert!(has_place_neighbor(&map, x, y, Layer::Fg, false, &ctx));
+    }
+
+    /// Target of the right-click in [`click_app`]: a switchable stone tile.
+    const LAMP: (i32, i32) = (100, 500);
+
+    /// Headless app running the interaction system with the cursor centred
+    /// on [`LAMP`] and the right mouse button held.
+    fn click_app() -> App {
+        let mut app = fixtures::test_app();
+        app.add_plugins(bevy::asset::AssetPlugin::default())
+            .add_message::<TileChanged>()
+            .add_message::<TileBroken>()
+            .add_message::<ItemPickupEvent>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LayerModifierKeys>()
+            .insert_resource(EditLineOfSight { enabled: false })
+            .init_resource::<GameMode>()
+            .init_resource::<LoadedChunks>()
+            .insert_resource(ItemRegistry::from_defs(Vec::new()))
+            .insert_resource(ItemIconRegistry::new())
+            .insert_resource(SharedLitQuad(Handle::default()))
+            .insert_resource(FallbackLightmap(Handle::default()))
+            .insert_resource(FallbackItemImage(Handle::default()))
+            .insert_resource(Assets::<LitSpriteMaterial>::default())
+            .init_resource::<RcGridDirty>()
+            .init_resource::<DirtyChunks>()
+            .init_resource::<BlockDamageMap>()
+            .init_resource::<DroppedItemLimits>()
+            .init_resource::<KnownRecipes>()
+            .insert_resource(crate::chat::ChatState::new(10))
+            .insert_resource(ParticlePool::new(16))
+            .init_resource::<SignEditor>()
+            .init_resource::<InputCapture>()
+            .init_resource::<TargetTile>()
+            .add_systems(
+                Update,
+                (target_resolution, block_interaction_system).chain(),
+            );
+
+        let stone = {
+            let mut registry = app
+                .world_mut()
+                .resource_mut::<crate::registry::tile::TileRegistry>();
+            let stone = registry.by_name("stone");
+            registry.defs[stone.0 as usize].switchable = true;
+            stone
+        };
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world_mut()
+            .resource_mut::<WorldMap>()
+            .set_tile(LAMP.0, LAMP.1, Layer::Fg, stone, &ctx);
+
+        let ts = wc.tile_size;
+        let centre = Vec2::new(LAMP.0 as f32 + 0.5, LAMP.1 as f32 + 0.5) * ts;
+        let mut window = Window::default();
+        let size = window.resolution.size();
+        window.set_cursor_position(Some(size / 2.0));
+        app.world_mut().spawn((window, PrimaryWindow));
+        let (half_w, half_h) = (size.x / 2.0, size.y / 2.0);
+        app.world_mut().spawn((
+            Camera2d,
+            Camera {
+                computed: bevy::camera::ComputedCameraValues {
+                    clip_from_view: Mat4::orthographic_rh(
+                        -half_w, half_w, -half_h, half_h, -1000.0, 1000.0,
+                    ),
+                    target_info: Some(bevy::camera::RenderTargetInfo {
+                        physical_size: size.as_uvec2(),
+                        scale_factor: 1.0,
+                    }),
+                    ..default()
+                },
+                ..default()
+            },
+            GlobalTransform::from_translation(centre.extend(0.0)),
+        ));
+        app.world_mut().spawn((
+            Player,
+            Transform::from_translation((centre - Vec2::X * 2.0 * ts).extend(0.0)),
+            Hotbar::new(),
+            Inventory::new(),
+            HandCooldowns::default(),
+        ));
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app
+    }
+
+    fn lamp_state(app: &App) -> u8 {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world()
+            .resource::<WorldMap>()
+            .get_tile_state(LAMP.0, LAMP.1, &ctx)
+    }
+
+    #[test]
+    fn click_reaches_the_world_without_capture() {
+        let mut app = click_app();
+        app.update();
+        assert_eq!(lamp_state(&app), TILE_STATE_OFF);
+        let target = app.world().resource::<TargetTile>().0.unwrap();
+        assert_eq!(target.tile, LAMP);
+    }
+
+    #[test]
+    fn click_over_a_ui_panel_leaves_the_world_alone() {
+        let mut app = click_app();
+        app.world_mut().resource_mut::<InputCapture>().pointer = true;
+        app.update();
+        assert_eq!(lamp_state(&app), 0);
+        assert!(app.world().resource::<DirtyChunks>().0.is_empty());
+    }
+
+    #[test]
+    fn unbreakable_tile_survives_creative_mining() {
+        let mut app = click_app();
+        *app.world_mut().resource_mut::<GameMode>() = GameMode::Creative;
+        {
+            let mut registry = app
+                .world_mut()
+                .resource_mut::<crate::registry::tile::TileRegistry>();
+            let stone = registry.by_name("stone");
+            registry.defs[stone.0 as usize].hardness = -1.0;
+        }
+        let mut mouse = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse.release(MouseButton::Right);
+        mouse.press(MouseButton::Left);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let tile = app
+            .world()
+            .resource::<WorldMap>()
+            .get_tile(LAMP.0, LAMP.1, Layer::Fg, &ctx);
+        assert_eq!(tile, Some(tr.by_name("stone")));
+        assert!(app.world().resource::<BlockDamageMap>().damage.is_empty());
+    }
+
+    /// [`click_app`] with a hoe in the left hand, the left button held and
+    /// `tile` at [`LAMP`]. The tile registry gains a `tilled_soil` tile.
+    fn hoe_app(tile: &str) -> App {
+        use crate::item::definition::{ItemType, TileConversion};
+        use crate::registry::tile::{TileDef, TileRegistry};
+
+        let mut app = click_app();
+        let hoe = ItemDef {
+            display_name: "Hoe".into(),
+            max_stack: 1,
+            tile_conversions: vec![TileConversion {
+                from: "dirt".into(),
+                to: "tilled_soil".into(),
+            }],
+            ..fixtures::item_def("hoe", ItemType::Tool)
+        };
+        app.insert_resource(ItemRegistry::from_defs(vec![hoe]));
+        {
+            let mut registry = app.world_mut().resource_mut::<TileRegistry>();
+            let mut defs = registry.defs.clone();
+            let dirt = registry.by_name("dirt");
+            defs.push(TileDef {
+                id: "tilled_soil".into(),
+                ..defs[dirt.0 as usize].clone()
+            });
+            *registry = TileRegistry::from_defs(defs);
+        }
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world_mut()
+            .resource_mut::<WorldMap>()
+            .set_tile(LAMP.0, LAMP.1, Layer::Fg, tr.by_name(tile), &ctx);
+        let mut hotbars = app
+            .world_mut()
+            .query_filtered::<&mut Hotbar, With<Player>>();
+        hotbars
+            .single_mut(app.world_mut())
+            .unwrap()
+            .assign(0, Hand::Left, "hoe".into(), None);
+        let mut mouse = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse.release(MouseButton::Right);
+        mouse.press(MouseButton::Left);
+        app
+    }
+
+    fn lamp_tile(app: &App) -> Option<TileId> {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world()
+            .resource::<WorldMap>()
+            .get_tile(LAMP.0, LAMP.1, Layer::Fg, &ctx)
+    }
+
+    #[test]
+    fn hoe_tills_dirt() {
+        let mut app = hoe_app("dirt");
+        app.update();
+        let tilled = app
+            .world()
+            .resource::<crate::registry::tile::TileRegistry>()
+            .by_name("tilled_soil");
+        assert_eq!(lamp_tile(&app), Some(tilled));
+        assert!(!app.world().resource::<DirtyChunks>().0.is_empty());
+    }
+
+    #[test]
+    fn hoe_leaves_stone_alone() {
+        let mut app = hoe_app("stone");
+        for _ in 0..3 {
+            app.update();
+        }
+        let stone = app
+            .world()
+            .resource::<crate::registry::tile::TileRegistry>()
+            .by_name("stone");
+        assert_eq!(lamp_tile(&app), Some(stone));
+        // Nothing mined either.
+        assert!(app.world().resource::<BlockDamageMap>().damage.is_empty());
+    }
+
+    /// [`click_app`] mining the stone at [`LAMP`] in creative mode, where
+    /// it breaks at once and drops one stone item, with drops going straight
+    /// to the inventory.
+    fn direct_drop_app() -> App {
+        use crate::registry::tile::TileRegistry;
+
+        let mut app = click_app();
+        *app.world_mut().resource_mut::<GameMode>() = GameMode::Creative;
+        app.insert_resource(DirectDrops {
+            survival: false,
+            creative: true,
+        });
+        let stone = ItemDef {
+            display_name: "Stone".into(),
+            max_stack: 999,
+            placeable: Some("stone".into()),
+            ..fixtures::item_def("stone", ItemType::Block)
+        };
+        app.insert_resource(ItemRegistry::from_defs(vec![stone]));
+        {
+            let mut registry = app.world_mut().resource_mut::<TileRegistry>();
+            let stone = registry.by_name("stone");
+            registry.defs[stone.0 as usize].drops = vec![DropDef {
+                item_id: "stone".into(),
+                min: 1,
+                max: 1,
+                chance: 1.0,
+            }];
+        }
+        let mut mouse = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse.release(MouseButton::Right);
+        mouse.press(MouseButton::Left);
+        app
+    }
+
+    /// Stone in the player's inventory and dropped stacks in the world.
+    fn stone_kept_and_dropped(app: &mut App) -> (u32, usize) {
+        let mut inventories = app.world_mut().query_filtered::<&Inventory, With<Player>>();
+        let kept = inventories.single(app.world()).unwrap().count_item("stone");
+        let mut drops = app.world_mut().query::<&DroppedItem>();
+        (kept, drops.iter(app.world()).count())
+    }
+
+    #[test]
+    fn direct_drops_go_into_the_inventory() {
+        let mut app = direct_drop_app();
+        app.update();
+        assert_eq!(lamp_tile(&app), Some(TileId::AIR));
+        assert_eq!(stone_kept_and_dropped(&mut app), (1, 0));
+
+        // In survival the setting is off: the same break drops an item.
+        let mut app = direct_drop_app();
+        *app.world_mut().resource_mut::<GameMode>() = GameMode::Survival;
+        app.world_mut()
+            .resource_mut::<crate::registry::tile::TileRegistry>()
+            .defs
+            .iter_mut()
+            .for_each(|def| def.hardness = 0.0);
+        app.update();
+        assert_eq!(lamp_tile(&app), Some(TileId::AIR));
+        assert_eq!(stone_kept_and_dropped(&mut app), (0, 1));
+    }
+
+    #[test]
+    fn direct_drops_spill_into_the_world_when_the_inventory_is_full() {
+        use crate::inventory::InventorySlot;
+
+        let mut app = direct_drop_app();
+        let mut inventories = app
+            .world_mut()
+            .query_filtered::<&mut Inventory, With<Player>>();
+        let mut inventory = inventories.single_mut(app.world_mut()).unwrap();
+        let inventory = &mut *inventory;
+        for slot in inventory
+            .main_bag
+            .iter_mut()
+            .chain(inventory.material_bag.iter_mut())
+        {
+            *slot = Some(InventorySlot {
+                item_id: "junk".into(),
+                count: 1,
+                durability: None,
+            });
+        }
+        app.update();
+        assert_eq!(lamp_tile(&app), Some(TileId::AIR));
+        assert_eq!(stone_kept_and_dropped(&mut app), (0, 1));
+    }
+}
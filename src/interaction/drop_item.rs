@@ -0,0 +1,252 @@
+//! Dropping the held item on purpose.
+//!
+//! The drop key (Q by default) tosses one of the item in the active hotbar
+//! slot toward the way the player faces; holding the stack modifier (Shift)
+//! tosses a whole stack. The left hand is dropped first, then the right.
+
+use bevy::prelude::*;
+
+use crate::inventory::{Hotbar, Inventory};
+use crate::item::{DroppedItemLimits, ItemRegistry, PickupDelay};
+use crate::player::animation::AnimationState;
+use crate::player::Player;
+use crate::ui::game_ui::icon_registry::ItemIconRegistry;
+use crate::ui::input_capture::InputCapture;
+use crate::world::lit_sprite::{
+    FallbackItemImage, FallbackLightmap, LitSpriteMaterial, SharedLitQuad,
+};
+
+use super::block_action::spawn_dropped_item;
+
+/// Horizontal toss speed (px/s) of a dropped item.
+const DROP_TOSS_SPEED: f32 = 120.0;
+/// Upward toss speed (px/s) of a dropped item.
+const DROP_TOSS_LIFT: f32 = 80.0;
+/// Seconds before the player can pick a dropped item back up.
+const DROP_PICKUP_DELAY_SECS: f32 = 1.5;
+
+/// Key binding for dropping the held item.
+#[derive(Resource, Debug, Clone)]
+pub struct DropItemKeys {
+    pub drop: KeyCode,
+    /// Keys that, while held, drop the whole stack instead of one item.
+    pub stack_modifier: Vec<KeyCode>,
+}
+
+impl Default for DropItemKeys {
+    fn default() -> Self {
+        Self {
+            drop: KeyCode::KeyQ,
+            stack_modifier: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+        }
+    }
+}
+
+impl DropItemKeys {
+    pub fn whole_stack(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_pressed(self.stack_modifier.iter().copied())
+    }
+}
+
+/// How many of `held` items one press drops: one, or up to a full stack.
+pub fn drop_count(held: u32, max_stack: u16, whole_stack: bool) -> u16 {
+    if held == 0 {
+        0
+    } else if whole_stack {
+        held.min(max_stack.max(1) as u32) as u16
+    } else {
+        1
+    }
+}
+
+/// Launch velocity of a dropped item: outward the way the player faces, with
+/// a little lift.
+pub fn toss_velocity(facing_right: bool) -> Vec2 {
+    let dir = if facing_right { 1.0 } else { -1.0 };
+    Vec2::new(dir * DROP_TOSS_SPEED, DROP_TOSS_LIFT)
+}
+
+/// Drop the item held in the active hotbar slot when the drop key is pressed.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn drop_held_item_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keys: Res<DropItemKeys>,
+    capture: Res<InputCapture>,
+    mut player_query: Query<
+        (&Transform, &Hotbar, &mut Inventory, Option<&AnimationState>),
+        With<Player>,
+    >,
+    item_registry: Res<ItemRegistry>,
+    icon_registry: Res<ItemIconRegistry>,
+    drops: (
+        Res<SharedLitQuad>,
+        Res<FallbackLightmap>,
+        Res<FallbackItemImage>,
+        Res<DroppedItemLimits>,
+        ResMut<Assets<LitSpriteMaterial>>,
+    ),
+) {
+    let (quad, fallback_lm, fallback_img, drop_limits, mut lit_materials) = drops;
+    if capture.keyboard || !keyboard.just_pressed(keys.drop) {
+        return;
+    }
+    let Ok((player_tf, hotbar, mut inventory, anim)) = player_query.single_mut() else {
+        return;
+    };
+    let Some(item_id) = [true, false]
+        .into_iter()
+        .filter_map(|is_left| hotbar.get_item_for_hand(is_left))
+        .find(|id| inventory.count_item(id) > 0)
+    else {
+        return;
+    };
+    let max_stack = item_registry
+        .by_name(item_id)
+        .map(|id| item_registry.get(id).max_stack)
+        .unwrap_or(99);
+    let count = drop_count(
+        inventory.count_item(item_id),
+        max_stack,
+        keys.whole_stack(&keyboard),
+    );
+    if !inventory.remove_item(item_id, count) {
+        return;
+    }
+
+    let facing_right = anim.is_none_or(|a| a.facing_right);
+    let entity = spawn_dropped_item(
+        &mut commands,
+        item_id.to_owned(),
+        count,
+        player_tf.translation.truncate(),
+        toss_velocity(facing_right),
+        &item_registry,
+        &drop_limits,
+        &icon_registry,
+        &quad,
+        &fallback_lm,
+        &mut lit_materials,
+        &fallback_img.0,
+    );
+    commands
+        .entity(entity)
+        .insert(PickupDelay::new(DROP_PICKUP_DELAY_SECS));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{BagTarget, Hand};
+    use crate::item::{DroppedItem, ItemDef, ItemType};
+    use crate::physics::Velocity;
+    use crate::test_helpers::fixtures;
+
+    fn stone() -> ItemDef {
+        ItemDef {
+            display_name: "Stone".into(),
+            max_stack: 20,
+            ..fixtures::item_def("stone", ItemType::Block)
+        }
+    }
+
+    fn drop_app(count: u16) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::asset::AssetPlugin::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<DropItemKeys>()
+            .init_resource::<InputCapture>()
+            .init_resource::<DroppedItemLimits>()
+            .insert_resource(ItemRegistry::from_defs(vec![stone()]))
+            .insert_resource(ItemIconRegistry::new())
+            .insert_resource(SharedLitQuad(Handle::default()))
+            .insert_resource(FallbackLightmap(Handle::default()))
+            .insert_resource(FallbackItemImage(Handle::default()))
+            .insert_resource(Assets::<LitSpriteMaterial>::default())
+            .add_systems(Update, drop_held_item_system);
+
+        let mut hotbar = Hotbar::new();
+        hotbar.assign(0, Hand::Left, "stone".into(), None);
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("stone", count, 20, BagTarget::Material);
+        app.world_mut().spawn((
+            Player,
+            Transform::from_xyz(100.0, 50.0, 0.0),
+            hotbar,
+            inventory,
+        ));
+        app
+    }
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.reset_all();
+            for key in keys {
+                input.press(*key);
+            }
+        }
+        app.update();
+    }
+
+    fn held(app: &mut App) -> u32 {
+        let mut query = app.world_mut().query::<&Inventory>();
+        query.single(app.world()).unwrap().count_item("stone")
+    }
+
+    fn dropped(app: &mut App) -> Vec<(u16, Vec2, bool)> {
+        let mut query = app
+            .world_mut()
+            .query::<(&DroppedItem, &Velocity, Has<PickupDelay>)>();
+        query
+            .iter(app.world())
+            .map(|(item, vel, delayed)| (item.count, Vec2::new(vel.x, vel.y), delayed))
+            .collect()
+    }
+
+    #[test]
+    fn drop_count_is_one_or_a_stack() {
+        assert_eq!(drop_count(0, 20, false), 0);
+        assert_eq!(drop_count(0, 20, true), 0);
+        assert_eq!(drop_count(35, 20, false), 1);
+        assert_eq!(drop_count(35, 20, true), 20);
+        assert_eq!(drop_count(7, 20, true), 7);
+    }
+
+    #[test]
+    fn drop_key_tosses_one_item_outward() {
+        let mut app = drop_app(5);
+        press(&mut app, &[KeyCode::KeyQ]);
+
+        assert_eq!(held(&mut app), 4);
+        let drops = dropped(&mut app);
+        assert_eq!(drops.len(), 1);
+        let (count, vel, delayed) = drops[0];
+        assert_eq!(count, 1);
+        assert!(vel.x > 0.0 && vel.y > 0.0, "tossed outward: {vel:?}");
+        assert!(delayed, "not picked straight back up");
+    }
+
+    #[test]
+    fn stack_modifier_drops_the_whole_stack() {
+        let mut app = drop_app(25);
+        press(&mut app, &[KeyCode::KeyQ, KeyCode::ShiftLeft]);
+
+        assert_eq!(held(&mut app), 5);
+        assert_eq!(dropped(&mut app)[0].0, 20);
+    }
+
+    #[test]
+    fn empty_hands_drop_nothing() {
+        let mut app = drop_app(0);
+        press(&mut app, &[KeyCode::KeyQ]);
+        assert!(dropped(&mut app).is_empty());
+    }
+
+    #[test]
+    fn toss_follows_facing() {
+        assert!(toss_velocity(true).x > 0.0);
+        assert!(toss_velocity(false).x < 0.0);
+    }
+}
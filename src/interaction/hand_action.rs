@@ -0,0 +1,280 @@
+//! Per-hand item use.
+//!
+//! Left mouse uses the item in the active slot's left hand and right mouse the
+//! item in its right hand. What a click does comes from the held item's
+//! [`ItemAction`]; each hand has its own cooldown so a slow tool in one hand
+//! doesn't gate the other. Items with an `item_cooldown` also go on a
+//! cooldown of their own ([`Cooldowns`]) that follows the item into any slot
+//! or hand.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::inventory::Hand;
+use crate::item::{ItemAction, ItemDef};
+
+/// Action for the item in `hand`. Empty hands and items without a use of their
+/// own mine with bare hands.
+pub fn resolve_hand_action(item: Option<&ItemDef>, hand: Hand) -> ItemAction {
+    item.and_then(|def| def.action(hand))
+        .unwrap_or(ItemAction::Mine)
+}
+
+/// Hand cooldown (seconds) after using `item` for `action`.
+pub fn use_cooldown(item: Option<&ItemDef>, action: ItemAction) -> f32 {
+    item.map_or_else(|| action.default_cooldown(), |def| def.cooldown(action))
+}
+
+/// Countdown for one hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Cooldown {
+    /// Seconds left before the hand can be used again.
+    pub remaining: f32,
+    /// Length of the cooldown that is running, for the hotbar overlay.
+    pub duration: f32,
+}
+
+/// Per-hand use cooldowns on the player.
+#[derive(Component, Debug, Clone, Default)]
+pub struct HandCooldowns {
+    pub left: Cooldown,
+    pub right: Cooldown,
+}
+
+impl HandCooldowns {
+    fn get(&self, hand: Hand) -> &Cooldown {
+        match hand {
+            Hand::Left => &self.left,
+            Hand::Right => &self.right,
+        }
+    }
+
+    pub fn ready(&self, hand: Hand) -> bool {
+        self.get(hand).remaining <= 0.0
+    }
+
+    /// Start a cooldown of `secs` on `hand`; non-positive values are ignored.
+    pub fn start(&mut self, hand: Hand, secs: f32) {
+        if secs <= 0.0 {
+            return;
+        }
+        let cooldown = match hand {
+            Hand::Left => &mut self.left,
+            Hand::Right => &mut self.right,
+        };
+        *cooldown = Cooldown {
+            remaining: secs,
+            duration: secs,
+        };
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        for cooldown in [&mut self.left, &mut self.right] {
+            cooldown.remaining = (cooldown.remaining - dt).max(0.0);
+        }
+    }
+
+    /// Fraction of the hand's cooldown still to run (1.0 just after use,
+    /// 0.0 when ready).
+    pub fn fraction(&self, hand: Hand) -> f32 {
+        let cooldown = self.get(hand);
+        if cooldown.duration <= 0.0 {
+            return 0.0;
+        }
+        (cooldown.remaining / cooldown.duration).clamp(0.0, 1.0)
+    }
+}
+
+/// Count down both hands' cooldowns.
+pub fn tick_hand_cooldowns(time: Res<Time>, mut query: Query<&mut HandCooldowns>) {
+    let dt = time.delta_secs();
+    for mut cooldowns in &mut query {
+        cooldowns.tick(dt);
+    }
+}
+
+/// Per-item use cooldowns, keyed by item id and action: eating one apple
+/// puts every apple on cooldown, but not potions or the other hand's tool.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Cooldowns {
+    running: HashMap<(String, ItemAction), Cooldown>,
+}
+
+impl Cooldowns {
+    pub fn ready(&self, item_id: &str, action: ItemAction) -> bool {
+        self.running
+            .get(&(item_id.to_owned(), action))
+            .is_none_or(|cooldown| cooldown.remaining <= 0.0)
+    }
+
+    /// Start `item`'s own cooldown for `action`; items without one are left
+    /// alone.
+    pub fn start(&mut self, item: &ItemDef, action: ItemAction) {
+        let Some(secs) = item.item_cooldown.filter(|&secs| secs > 0.0) else {
+            return;
+        };
+        self.running.insert(
+            (item.id.clone(), action),
+            Cooldown {
+                remaining: secs,
+                duration: secs,
+            },
+        );
+    }
+
+    /// Count every cooldown down, forgetting the ones that ran out.
+    pub fn tick(&mut self, dt: f32) {
+        self.running.retain(|_, cooldown| {
+            cooldown.remaining -= dt;
+            cooldown.remaining > 0.0
+        });
+    }
+
+    /// Fraction of the longest cooldown running on `item_id` still to go,
+    /// over all its actions (0.0 when none is).
+    pub fn fraction(&self, item_id: &str) -> f32 {
+        self.running
+            .iter()
+            .filter(|((id, _), _)| id == item_id)
+            .map(|(_, cooldown)| (cooldown.remaining / cooldown.duration).clamp(0.0, 1.0))
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Count down per-item cooldowns.
+pub fn tick_item_cooldowns(time: Res<Time>, mut cooldowns: ResMut<Cooldowns>) {
+    if !cooldowns.running.is_empty() {
+        cooldowns.tick(time.delta_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::ItemType;
+    use crate::test_helpers::fixtures;
+
+    fn block_item() -> ItemDef {
+        ItemDef {
+            display_name: "Dirt".into(),
+            max_stack: 999,
+            placeable: Some("dirt".into()),
+            ..fixtures::item_def("dirt", ItemType::Block)
+        }
+    }
+
+    #[test]
+    fn empty_and_inert_hands_mine() {
+        let wood = ItemDef {
+            placeable: None,
+            item_type: ItemType::Material,
+            ..block_item()
+        };
+        assert_eq!(resolve_hand_action(None, Hand::Right), ItemAction::Mine);
+        assert_eq!(
+            resolve_hand_action(Some(&wood), Hand::Left),
+            ItemAction::Mine
+        );
+
+        let dirt = block_item();
+        assert_eq!(
+            resolve_hand_action(Some(&dirt), Hand::Left),
+            ItemAction::PlaceFg
+        );
+        assert_eq!(
+            resolve_hand_action(Some(&dirt), Hand::Right),
+            ItemAction::PlaceBg
+        );
+    }
+
+    #[test]
+    fn hand_cooldowns_gate_independently() {
+        let mut cooldowns = HandCooldowns::default();
+        assert!(cooldowns.ready(Hand::Left) && cooldowns.ready(Hand::Right));
+
+        cooldowns.start(Hand::Left, 0.5);
+        assert!(!cooldowns.ready(Hand::Left));
+        assert!(cooldowns.ready(Hand::Right));
+        assert_eq!(cooldowns.fraction(Hand::Left), 1.0);
+        assert_eq!(cooldowns.fraction(Hand::Right), 0.0);
+
+        cooldowns.start(Hand::Right, 0.2);
+        cooldowns.tick(0.25);
+        assert!(!cooldowns.ready(Hand::Left));
+        assert!(cooldowns.ready(Hand::Right));
+        assert!((cooldowns.fraction(Hand::Left) - 0.5).abs() < 1e-6);
+
+        cooldowns.tick(0.25);
+        assert!(cooldowns.ready(Hand::Left));
+        assert_eq!(cooldowns.fraction(Hand::Left), 0.0);
+    }
+
+    #[test]
+    fn zero_cooldown_keeps_hand_ready() {
+        let mut cooldowns = HandCooldowns::default();
+        let dirt = block_item();
+        cooldowns.start(Hand::Left, use_cooldown(Some(&dirt), ItemAction::PlaceFg));
+        assert!(cooldowns.ready(Hand::Left));
+        cooldowns.start(Hand::Left, use_cooldown(None, ItemAction::Mine));
+        assert!(!cooldowns.ready(Hand::Left));
+    }
+
+    fn potion(id: &str, secs: f32) -> ItemDef {
+        ItemDef {
+            id: id.into(),
+            placeable: None,
+            item_type: ItemType::Consumable,
+            item_cooldown: Some(secs),
+            ..block_item()
+        }
+    }
+
+    #[test]
+    fn item_on_cooldown_is_rejected_until_it_elapses() {
+        let mut cooldowns = Cooldowns::default();
+        let potion = potion("healing_potion", 2.0);
+        assert!(cooldowns.ready(&potion.id, ItemAction::Consume));
+
+        cooldowns.start(&potion, ItemAction::Consume);
+        assert!(!cooldowns.ready(&potion.id, ItemAction::Consume));
+        assert_eq!(cooldowns.fraction(&potion.id), 1.0);
+
+        cooldowns.tick(1.5);
+        assert!(!cooldowns.ready(&potion.id, ItemAction::Consume));
+        assert!((cooldowns.fraction(&potion.id) - 0.25).abs() < 1e-6);
+
+        cooldowns.tick(0.5);
+        assert!(cooldowns.ready(&potion.id, ItemAction::Consume));
+        assert_eq!(cooldowns.fraction(&potion.id), 0.0);
+    }
+
+    #[test]
+    fn independent_items_have_independent_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+        let healing = potion("healing_potion", 2.0);
+        let tonic = potion("swiftness_tonic", 5.0);
+
+        cooldowns.start(&healing, ItemAction::Consume);
+        assert!(!cooldowns.ready(&healing.id, ItemAction::Consume));
+        assert!(cooldowns.ready(&tonic.id, ItemAction::Consume));
+        // The same item used another way isn't gated either.
+        assert!(cooldowns.ready(&healing.id, ItemAction::Throw));
+
+        cooldowns.start(&tonic, ItemAction::Consume);
+        cooldowns.tick(2.0);
+        assert!(cooldowns.ready(&healing.id, ItemAction::Consume));
+        assert!(!cooldowns.ready(&tonic.id, ItemAction::Consume));
+        assert_eq!(cooldowns.fraction(&healing.id), 0.0);
+        assert!((cooldowns.fraction(&tonic.id) - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn items_without_their_own_cooldown_never_wait() {
+        let mut cooldowns = Cooldowns::default();
+        let dirt = block_item();
+        cooldowns.start(&dirt, ItemAction::PlaceFg);
+        assert!(cooldowns.ready(&dirt.id, ItemAction::PlaceFg));
+        assert_eq!(cooldowns.fraction(&dirt.id), 0.0);
+    }
+}
@@ -139,7 +139,7 @@ pub fn handle_interaction_input(
         bevy::ecs::message::MessageWriter<WarpToShip>,
     ),
 ) {
-    if chat_state.is_active {
+    if chat_state.keyboard_captured() {
         return;
     }
     // E key: toggle station interaction or trigger warp
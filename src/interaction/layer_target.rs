@@ -0,0 +1,165 @@
+//! Which tile layer a block interaction targets.
+//!
+//! Mining works on the foreground from the left hand and on the background
+//! from the right hand. Holding the layer modifier (Alt by default) sends
+//! mining and foreground placement to the background, so walls can be edited
+//! behind blocks, torches and lamps. Tile modifiers (hoes) always work on
+//! the foreground.
+//!
+//! Scrolling the wheel with the modifier held switches a [`LayerPreference`]
+//! that keeps doing the same without holding anything: down to the
+//! background, up back to the foreground.
+
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+
+use crate::inventory::Hand;
+use crate::item::ItemAction;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::Layer;
+
+/// Keys that, while held, force block interaction onto the background layer.
+#[derive(Resource, Debug, Clone)]
+pub struct LayerModifierKeys(pub Vec<KeyCode>);
+
+impl Default for LayerModifierKeys {
+    fn default() -> Self {
+        Self(vec![KeyCode::AltLeft, KeyCode::AltRight])
+    }
+}
+
+impl LayerModifierKeys {
+    pub fn held(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_pressed(self.0.iter().copied())
+    }
+}
+
+/// Persistent layer choice, switched with the wheel while the layer modifier
+/// is held. Preferring the background acts as if the modifier were held.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayerPreference {
+    pub bg: bool,
+}
+
+/// Switch the [`LayerPreference`] with the wheel while the modifier is held.
+pub fn switch_layer_preference(
+    mut scroll_events: MessageReader<MouseWheel>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keys: Res<LayerModifierKeys>,
+    capture: Res<InputCapture>,
+    mut preference: ResMut<LayerPreference>,
+) {
+    let steps: i32 = scroll_events.read().map(|e| e.y.signum() as i32).sum();
+    if steps == 0 || capture.pointer || capture.keyboard || !keys.held(&keyboard) {
+        return;
+    }
+    preference.set_if_neq(LayerPreference { bg: steps < 0 });
+}
+
+/// Layer a hand action works on, or `None` for actions that don't touch
+/// tiles (consuming, throwing, fishing).
+pub fn resolve_layer(action: ItemAction, hand: Hand, bg_modifier: bool) -> Option<Layer> {
+    let layer = match action {
+        ItemAction::Mine if bg_modifier || hand == Hand::Right => Layer::Bg,
+        ItemAction::Mine => Layer::Fg,
+        ItemAction::PlaceFg if bg_modifier => Layer::Bg,
+        ItemAction::PlaceFg => Layer::Fg,
+        ItemAction::PlaceBg => Layer::Bg,
+        ItemAction::ModifyTile => Layer::Fg,
+        ItemAction::Consume | ItemAction::Throw | ItemAction::Fish => return None,
+    };
+    Some(layer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layer_resolution_matrix() {
+        use ItemAction::*;
+        use Layer::{Bg, Fg};
+
+        // (action, hand, modifier) -> layer
+        let cases = [
+            (Mine, Hand::Left, false, Some(Fg)),
+            (Mine, Hand::Left, true, Some(Bg)),
+            (Mine, Hand::Right, false, Some(Bg)),
+            (Mine, Hand::Right, true, Some(Bg)),
+            (PlaceFg, Hand::Left, false, Some(Fg)),
+            (PlaceFg, Hand::Left, true, Some(Bg)),
+            (PlaceFg, Hand::Right, false, Some(Fg)),
+            (PlaceFg, Hand::Right, true, Some(Bg)),
+            (PlaceBg, Hand::Left, false, Some(Bg)),
+            (PlaceBg, Hand::Right, true, Some(Bg)),
+            (Consume, Hand::Left, false, None),
+            (Throw, Hand::Right, true, None),
+            (Fish, Hand::Left, false, None),
+            (ModifyTile, Hand::Left, true, Some(Fg)),
+            (ModifyTile, Hand::Right, false, Some(Fg)),
+        ];
+        for (action, hand, modifier, expected) in cases {
+            assert_eq!(
+                resolve_layer(action, hand, modifier),
+                expected,
+                "{action:?} {hand:?} modifier={modifier}"
+            );
+        }
+    }
+
+    #[test]
+    fn modifier_held_with_any_bound_key() {
+        let keys = LayerModifierKeys::default();
+        let mut input = ButtonInput::<KeyCode>::default();
+        assert!(!keys.held(&input));
+        input.press(KeyCode::AltRight);
+        assert!(keys.held(&input));
+    }
+
+    #[test]
+    fn modifier_is_rebindable() {
+        let keys = LayerModifierKeys(vec![KeyCode::ShiftLeft]);
+        let mut input = ButtonInput::<KeyCode>::default();
+        input.press(KeyCode::AltLeft);
+        assert!(!keys.held(&input));
+        input.press(KeyCode::ShiftLeft);
+        assert!(keys.held(&input));
+    }
+
+    #[test]
+    fn wheel_with_modifier_switches_the_preference() {
+        let mut app = App::new();
+        app.add_message::<MouseWheel>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LayerModifierKeys>()
+            .init_resource::<InputCapture>()
+            .init_resource::<LayerPreference>()
+            .add_systems(Update, switch_layer_preference);
+        let scroll = |app: &mut App, y: f32| {
+            app.world_mut().write_message(MouseWheel {
+                unit: bevy::input::mouse::MouseScrollUnit::Line,
+                x: 0.0,
+                y,
+                window: Entity::PLACEHOLDER,
+            });
+            app.update();
+            app.world().resource::<LayerPreference>().bg
+        };
+
+        // Without the modifier the wheel is the hotbar's.
+        assert!(!scroll(&mut app, -1.0));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::AltLeft);
+        assert!(scroll(&mut app, -1.0));
+        // The choice sticks once the modifier is released.
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::AltLeft);
+        assert!(scroll(&mut app, 1.0));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::AltRight);
+        assert!(!scroll(&mut app, 1.0));
+    }
+}
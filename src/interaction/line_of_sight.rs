@@ -0,0 +1,93 @@
+//! Line-of-sight check for block interaction.
+//!
+//! A tile can only be mined or placed if the straight line from the player to
+//! the tile's centre doesn't pass through a solid tile first, so blocks can't
+//! be edited through walls.
+
+use bevy::prelude::*;
+
+use crate::math::sweep_segment;
+
+/// Toggle for the line-of-sight requirement on block interaction.
+#[derive(Resource, Debug, Clone)]
+pub struct EditLineOfSight {
+    /// When false, any tile within reach can be edited (open-air editing).
+    pub enabled: bool,
+}
+
+impl Default for EditLineOfSight {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// First solid tile crossed by the segment from `from` (world px) to the
+/// centre of `target`, excluding the start and target tiles.
+///
+/// Where the segment passes exactly through a tile corner, it counts as
+/// blocked only if both tiles beside the corner are solid (see
+/// [`sweep_segment`]).
+pub fn first_blocking_tile(
+    from: Vec2,
+    target: (i32, i32),
+    tile_size: f32,
+    mut is_solid: impl FnMut(i32, i32) -> bool,
+) -> Option<(i32, i32)> {
+    let to = (Vec2::new(target.0 as f32, target.1 as f32) + 0.5) * tile_size;
+    sweep_segment(from, to, tile_size, |x, y| {
+        (x, y) != target && is_solid(x, y)
+    })
+    .map(|hit| hit.tile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const TS: f32 = 8.0;
+
+    fn centre(x: i32, y: i32) -> Vec2 {
+        (Vec2::new(x as f32, y as f32) + 0.5) * TS
+    }
+
+    fn blocked_by(solid: &[(i32, i32)], from: Vec2, target: (i32, i32)) -> Option<(i32, i32)> {
+        let solid: HashSet<(i32, i32)> = solid.iter().copied().collect();
+        first_blocking_tile(from, target, TS, |x, y| solid.contains(&(x, y)))
+    }
+
+    #[test]
+    fn target_behind_wall_is_blocked() {
+        // Wall column at x = 3 between the player (x = 0) and the target.
+        let wall = [(3, 9), (3, 10), (3, 11)];
+        assert_eq!(blocked_by(&wall, centre(0, 10), (5, 10)), Some((3, 10)));
+        assert_eq!(blocked_by(&wall, centre(0, 10), (5, 12)), Some((3, 11)));
+    }
+
+    #[test]
+    fn unobstructed_target_is_clear() {
+        let solid = [(0, 9), (1, 9), (2, 9), (5, 12)];
+        assert_eq!(blocked_by(&solid, centre(0, 10), (4, 13)), None);
+        assert_eq!(blocked_by(&solid, centre(0, 10), (-3, 10)), None);
+    }
+
+    #[test]
+    fn solid_target_itself_does_not_block() {
+        let solid = [(4, 10), (5, 10)];
+        assert_eq!(blocked_by(&solid, centre(0, 10), (4, 10)), None);
+        assert_eq!(blocked_by(&solid, centre(0, 10), (5, 10)), Some((4, 10)));
+    }
+
+    #[test]
+    fn diagonal_corner_gap() {
+        // The segment passes exactly through the corner between (1, 0) and (0, 1).
+        let from = centre(0, 0);
+        assert_eq!(blocked_by(&[(1, 0)], from, (1, 1)), None);
+        assert_eq!(blocked_by(&[(1, 0), (0, 1)], from, (1, 1)), Some((1, 0)));
+    }
+
+    #[test]
+    fn same_tile_is_clear() {
+        assert_eq!(blocked_by(&[(2, 2)], centre(2, 2), (2, 2)), None);
+    }
+}
@@ -1,6 +1,17 @@
 pub mod block_action;
 pub mod crack_overlay;
+pub mod drop_item;
+pub mod hand_action;
 pub mod interactable;
+pub mod layer_target;
+pub mod line_of_sight;
+pub mod mode;
+pub mod orientation;
+pub mod rope;
+pub mod schematic;
+pub mod smart_torch;
+pub mod target;
+pub mod target_outline;
 pub mod use_item;
 
 use bevy::prelude::*;
@@ -11,9 +22,11 @@ use interactable::{HandCraftOpen, NearbyInteractable, OpenStation};
 /// Internal ordering sets for interaction systems.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 enum InteractionSet {
-    /// Runs first: consume items (e.g. blueprints) on right-click.
-    UseItem,
-    /// Runs after UseItem: block placement / breaking, interactables, etc.
+    /// Runs first: resolve the tile under the pointer.
+    Target,
+    /// Count down per-hand and per-item cooldowns.
+    Cooldowns,
+    /// Runs after Cooldowns: block placement / breaking, interactables, etc.
     BlockAction,
 }
 
@@ -24,21 +37,59 @@ impl Plugin for InteractionPlugin {
         app.init_resource::<NearbyInteractable>()
             .init_resource::<OpenStation>()
             .init_resource::<HandCraftOpen>()
-            .init_resource::<use_item::ItemUsedThisFrame>()
+            .init_resource::<block_action::DirectDrops>()
+            .init_resource::<layer_target::LayerModifierKeys>()
+            .init_resource::<layer_target::LayerPreference>()
+            .init_resource::<line_of_sight::EditLineOfSight>()
+            .init_resource::<drop_item::DropItemKeys>()
+            .init_resource::<hand_action::Cooldowns>()
+            .init_resource::<orientation::OrientationKeys>()
+            .init_resource::<orientation::PlacementOrientation>()
+            .init_resource::<target::TargetTile>()
+            .init_resource::<mode::InteractionMode>()
+            .init_resource::<smart_torch::SmartTorch>()
+            .init_resource::<smart_torch::SmartTorchState>()
             .configure_sets(
                 Update,
-                (InteractionSet::UseItem, InteractionSet::BlockAction)
+                (
+                    InteractionSet::Target,
+                    InteractionSet::Cooldowns,
+                    InteractionSet::BlockAction,
+                )
                     .chain()
                     .in_set(GameSet::Input),
             )
             .add_systems(
                 Update,
-                use_item::use_item_system.in_set(InteractionSet::UseItem),
+                (
+                    layer_target::switch_layer_preference.before(target::target_resolution),
+                    target::target_resolution,
+                    orientation::cycle_orientation_system,
+                )
+                    .in_set(InteractionSet::Target),
+            )
+            .add_systems(
+                Update,
+                (
+                    hand_action::tick_hand_cooldowns,
+                    hand_action::tick_item_cooldowns,
+                )
+                    .in_set(InteractionSet::Cooldowns),
             )
             .add_systems(
                 Update,
                 block_action::block_interaction_system.in_set(InteractionSet::BlockAction),
             )
+            .add_systems(
+                Update,
+                smart_torch::smart_torch_system
+                    .before(block_action::block_interaction_system)
+                    .in_set(InteractionSet::BlockAction),
+            )
+            .add_systems(
+                Update,
+                drop_item::drop_held_item_system.in_set(InteractionSet::BlockAction),
+            )
             .add_systems(
                 Update,
                 interactable::detect_nearby_interactable.in_set(InteractionSet::BlockAction),
@@ -50,7 +101,17 @@ impl Plugin for InteractionPlugin {
             .add_systems(
                 Update,
                 interactable::update_interactable_highlight.in_set(InteractionSet::BlockAction),
+            )
+            .add_systems(
+                Update,
+                orientation::draw_orientation_hint.in_set(InteractionSet::BlockAction),
+            )
+            .add_systems(
+                Update,
+                mode::update_interaction_mode.in_set(InteractionSet::BlockAction),
             );
         crack_overlay::register(app);
+        target_outline::register(app);
+        schematic::register(app);
     }
 }
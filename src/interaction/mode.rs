@@ -0,0 +1,357 @@
+//! What each mouse button would do right now, for the hint by the hotbar.
+//!
+//! [`update_interaction_mode`] resolves each hand's [`ItemAction`] and layer
+//! the same way block interaction does and words it against what is at the
+//! [`TargetTile`] ("LMB: Mine stone", "RMB: Place torch on wall"). It only
+//! rewrites [`InteractionMode`] when the targeted tile, what is on it, the
+//! held items or the layer modifier change.
+
+use bevy::prelude::*;
+
+use crate::inventory::{Hand, Hotbar};
+use crate::item::{ItemAction, ItemDef, ItemRegistry};
+use crate::object::definition::ObjectId;
+use crate::object::placement::get_object_at;
+use crate::object::registry::ObjectRegistry;
+use crate::player::Player;
+use crate::registry::tile::TileId;
+use crate::world::chunk::{Layer, WorldMap};
+use crate::world::ctx::WorldCtx;
+
+use super::block_action::held_item_def;
+use super::hand_action::resolve_hand_action;
+use super::layer_target::{resolve_layer, LayerPreference};
+use super::target::TargetTile;
+
+/// What is at the targeted tile, as far as the hint is concerned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TargetContents {
+    /// Foreground tile id, if not air.
+    pub fg_tile: Option<String>,
+    /// Display name of the object covering the tile.
+    pub object: Option<String>,
+    /// Background tile id, if not air.
+    pub bg_tile: Option<String>,
+    /// Within break reach, in sight and inside the world.
+    pub can_break: bool,
+    /// Within place reach, in sight and inside the world.
+    pub can_place: bool,
+}
+
+/// Readable name of a tile or item id (`"stone_pickaxe"` -> `"stone pickaxe"`).
+fn pretty(name: &str) -> String {
+    name.replace('_', " ").to_lowercase()
+}
+
+/// What a click with `hand` holding `item` would do, e.g. "Mine stone" or
+/// "Place torch on wall". `at` is `None` while nothing is aimed at.
+pub fn describe_hand(
+    item: Option<&ItemDef>,
+    hand: Hand,
+    bg_modifier: bool,
+    at: Option<&TargetContents>,
+) -> String {
+    let action = resolve_hand_action(item, hand);
+    let name = item
+        .map(|def| pretty(&def.display_name))
+        .unwrap_or_default();
+    let layer = resolve_layer(action, hand, bg_modifier);
+    let (text, reachable) = match action {
+        ItemAction::Mine => {
+            let what = at.and_then(|at| match layer? {
+                Layer::Fg => at
+                    .object
+                    .clone()
+                    .or_else(|| at.fg_tile.as_deref().map(pretty)),
+                Layer::Bg => at.bg_tile.as_deref().map(|t| format!("{} wall", pretty(t))),
+            });
+            let text = what.map_or_else(|| "Mine".to_owned(), |what| format!("Mine {what}"));
+            (text, at.is_none_or(|at| at.can_break))
+        }
+        ItemAction::PlaceFg | ItemAction::PlaceBg => {
+            let text = if layer == Some(Layer::Bg) {
+                format!("Place {name} as wall")
+            } else if at.is_some_and(|at| at.fg_tile.is_none() && at.bg_tile.is_some()) {
+                format!("Place {name} on wall")
+            } else {
+                format!("Place {name}")
+            };
+            (text, at.is_none_or(|at| at.can_place))
+        }
+        ItemAction::ModifyTile => {
+            let on = at
+                .and_then(|at| at.fg_tile.as_deref())
+                .filter(|tile| item.is_some_and(|def| def.converted_tile(tile).is_some()));
+            let text = match on {
+                Some(tile) => format!("Use {name} on {}", pretty(tile)),
+                None => format!("Use {name}"),
+            };
+            (text, at.is_none_or(|at| at.can_break))
+        }
+        ItemAction::Consume => (format!("Use {name}"), true),
+        ItemAction::Throw => (format!("Throw {name}"), true),
+        ItemAction::Fish => (format!("Cast {name}"), true),
+    };
+    if reachable {
+        text
+    } else {
+        format!("{text} (out of reach)")
+    }
+}
+
+/// The targeted tile and what is on it, as last worded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TargetKey {
+    tile: (i32, i32),
+    fg: TileId,
+    bg: TileId,
+    object: Option<ObjectId>,
+    can_break: bool,
+    can_place: bool,
+}
+
+/// What the hint was last worded for. Held items are tracked through
+/// `Hotbar` changes instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ModeKey {
+    target: Option<TargetKey>,
+    bg_modifier: bool,
+}
+
+/// The current action of each mouse button, worded for the hint.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InteractionMode {
+    /// "LMB: ..." line.
+    pub left: String,
+    /// "RMB: ..." line.
+    pub right: String,
+    key: Option<ModeKey>,
+}
+
+/// Reword [`InteractionMode`] when what it depends on changed.
+#[allow(clippy::too_many_arguments)]
+pub fn update_interaction_mode(
+    target: Res<TargetTile>,
+    preference: Option<Res<LayerPreference>>,
+    player_query: Query<Ref<Hotbar>, With<Player>>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    item_registry: Res<ItemRegistry>,
+    object_registry: Option<Res<ObjectRegistry>>,
+    mut mode: ResMut<InteractionMode>,
+) {
+    let Ok(hotbar) = player_query.single() else {
+        return;
+    };
+    let ctx_ref = ctx.as_ref();
+    let target = target.0;
+    let bg_modifier = target.map_or_else(
+        || preference.is_some_and(|p| p.bg),
+        |target| target.bg_modifier,
+    );
+    let target = target.map(|target| {
+        let (x, y) = target.tile;
+        let tile = |layer| {
+            world_map
+                .get_tile(x, y, layer, &ctx_ref)
+                .unwrap_or(TileId::AIR)
+        };
+        let in_reach = target.in_sight && !target.outside;
+        TargetKey {
+            tile: target.tile,
+            fg: tile(Layer::Fg),
+            bg: tile(Layer::Bg),
+            object: get_object_at(&world_map, x, y, &ctx_ref).map(|(.., object)| object),
+            can_break: in_reach && target.can_break,
+            can_place: in_reach && target.can_place,
+        }
+    });
+    let key = ModeKey {
+        target,
+        bg_modifier,
+    };
+    if mode.key == Some(key) && !hotbar.is_changed() {
+        return;
+    }
+
+    let contents = target.map(|target| {
+        let tile = |tile: TileId| {
+            (tile != TileId::AIR).then(|| ctx_ref.tile_registry.get(tile).id.clone())
+        };
+        TargetContents {
+            fg_tile: tile(target.fg),
+            object: target
+                .object
+                .zip(object_registry.as_deref())
+                .map(|(object, registry)| pretty(&registry.get(object).display_name)),
+            bg_tile: tile(target.bg),
+            can_break: target.can_break,
+            can_place: target.can_place,
+        }
+    });
+    let line = |hand: Hand| {
+        let def = held_item_def(&item_registry, hotbar.get_item_for_hand(hand == Hand::Left));
+        describe_hand(def, hand, bg_modifier, contents.as_ref())
+    };
+    mode.left = format!("LMB: {}", line(Hand::Left));
+    mode.right = format!("RMB: {}", line(Hand::Right));
+    mode.key = Some(key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::ItemType;
+    use crate::test_helpers::fixtures;
+
+    fn item(id: &str, display_name: &str) -> ItemDef {
+        ItemDef {
+            display_name: display_name.into(),
+            max_stack: 999,
+            ..fixtures::item_def(id, ItemType::Material)
+        }
+    }
+
+    fn items() -> Vec<ItemDef> {
+        vec![
+            ItemDef {
+                placeable: Some("dirt".into()),
+                item_type: ItemType::Block,
+                ..item("dirt", "Dirt")
+            },
+            ItemDef {
+                placeable_object: Some("torch_object".into()),
+                ..item("torch", "Torch")
+            },
+            ItemDef {
+                tile_conversions: vec![crate::item::TileConversion {
+                    from: "dirt".into(),
+                    to: "tilled_dirt".into(),
+                }],
+                ..item("hoe", "Wooden Hoe")
+            },
+            ItemDef {
+                action: Some(ItemAction::Consume),
+                ..item("apple", "Apple")
+            },
+        ]
+    }
+
+    fn stone_on_dirt_wall() -> TargetContents {
+        TargetContents {
+            fg_tile: Some("stone".into()),
+            object: None,
+            bg_tile: Some("dirt".into()),
+            can_break: true,
+            can_place: true,
+        }
+    }
+
+    #[test]
+    fn summaries_follow_the_action_layer_and_target() {
+        use Hand::{Left, Right};
+
+        let items = items();
+        let [dirt, torch, hoe, apple] = [0, 1, 2, 3].map(|i| Some(&items[i]));
+        let stone = stone_on_dirt_wall();
+        let wall = TargetContents {
+            fg_tile: None,
+            ..stone_on_dirt_wall()
+        };
+        let bare_dirt = TargetContents {
+            fg_tile: Some("dirt".into()),
+            bg_tile: None,
+            ..stone_on_dirt_wall()
+        };
+        let lamp = TargetContents {
+            object: Some("lamp".into()),
+            ..wall.clone()
+        };
+        let far = TargetContents {
+            can_break: false,
+            can_place: false,
+            ..stone_on_dirt_wall()
+        };
+
+        // (item, hand, bg modifier, target) -> summary
+        let cases = [
+            (None, Left, false, Some(&stone), "Mine stone"),
+            (None, Right, false, Some(&stone), "Mine dirt wall"),
+            (None, Left, true, Some(&stone), "Mine dirt wall"),
+            (None, Left, false, Some(&lamp), "Mine lamp"),
+            (None, Left, false, Some(&far), "Mine stone (out of reach)"),
+            (None, Left, false, None, "Mine"),
+            (torch, Right, false, Some(&wall), "Place torch on wall"),
+            (torch, Right, false, None, "Place torch"),
+            (dirt, Left, false, Some(&wall), "Place dirt on wall"),
+            (dirt, Right, false, Some(&stone), "Place dirt as wall"),
+            (dirt, Left, true, Some(&stone), "Place dirt as wall"),
+            (dirt, Left, false, Some(&far), "Place dirt (out of reach)"),
+            (hoe, Left, false, Some(&bare_dirt), "Use wooden hoe on dirt"),
+            (hoe, Left, false, Some(&stone), "Use wooden hoe"),
+            (apple, Right, false, Some(&far), "Use apple"),
+        ];
+        for (item, hand, bg_modifier, at, expected) in cases {
+            assert_eq!(
+                describe_hand(item, hand, bg_modifier, at),
+                expected,
+                "{:?} {hand:?} modifier={bg_modifier}",
+                item.map(|d| &d.id)
+            );
+        }
+    }
+
+    fn mode_app() -> App {
+        let mut app = fixtures::test_app();
+        app.insert_resource(ItemRegistry::from_defs(items()))
+            .init_resource::<TargetTile>()
+            .init_resource::<InteractionMode>()
+            .add_systems(Update, update_interaction_mode);
+        let mut hotbar = Hotbar::new();
+        hotbar.slots[0].right_hand = Some("torch".into());
+        app.world_mut().spawn((Player, hotbar));
+        app
+    }
+
+    fn lines(app: &App) -> (String, String) {
+        let mode = app.world().resource::<InteractionMode>();
+        (mode.left.clone(), mode.right.clone())
+    }
+
+    /// Overwrite the hint without tripping change detection, so a later
+    /// recompute shows.
+    fn scribble(app: &mut App) {
+        let mut mode = app.world_mut().resource_mut::<InteractionMode>();
+        let mode = mode.bypass_change_detection();
+        mode.left = "stale".into();
+        mode.right = "stale".into();
+    }
+
+    #[test]
+    fn hint_is_reworded_only_when_its_inputs_change() {
+        let mut app = mode_app();
+        app.update();
+        assert_eq!(lines(&app), ("LMB: Mine".into(), "RMB: Place torch".into()));
+
+        // Nothing changed: the hint is left alone.
+        scribble(&mut app);
+        app.update();
+        app.update();
+        assert_eq!(lines(&app).0, "stale");
+
+        // The held items changed.
+        let mut query = app.world_mut().query::<&mut Hotbar>();
+        query.single_mut(app.world_mut()).unwrap().slots[0].right_hand = None;
+        app.update();
+        assert_eq!(lines(&app).1, "RMB: Mine");
+
+        // The preferred layer changed while nothing is aimed at.
+        scribble(&mut app);
+        app.insert_resource(LayerPreference { bg: true });
+        app.update();
+        assert_eq!(lines(&app).0, "LMB: Mine");
+        scribble(&mut app);
+        app.update();
+        assert_eq!(lines(&app).0, "stale");
+    }
+}
@@ -0,0 +1,215 @@
+//! Orientation of orientable tiles (torches, stairs) when they are placed.
+//!
+//! By default a tile attaches to a solid neighbour, trying the floor first,
+//! then the left and right walls, then the ceiling ([`auto_orientation`]).
+//! The cycle key (R) switches to a fixed orientation instead, one step per
+//! press, and back to automatic after the last. While an orientable tile is
+//! held, the target outline gets a bar on the side it would attach to. The
+//! orientation goes into the tile's state byte (see [`TileOrientation`]).
+
+use bevy::prelude::*;
+
+use crate::inventory::{Hand, Hotbar};
+use crate::item::ItemRegistry;
+use crate::player::Player;
+use crate::registry::tile::TileOrientation;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{Layer, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+
+use super::block_action::resolve_placeable;
+use super::target::TargetTile;
+use super::target_outline::{tile_outline_rect, TargetOutlineGizmos};
+
+/// Colour of the attachment bar drawn along the target outline.
+const HINT_COLOR: Color = Color::srgba(1.0, 0.85, 0.35, 0.9);
+
+/// Key binding for cycling the placement orientation.
+#[derive(Resource, Debug, Clone)]
+pub struct OrientationKeys {
+    pub cycle: KeyCode,
+}
+
+impl Default for OrientationKeys {
+    fn default() -> Self {
+        Self {
+            cycle: KeyCode::KeyR,
+        }
+    }
+}
+
+/// Orientation for the next orientable tile placed; `None` picks it from
+/// the tile's neighbours.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlacementOrientation(pub Option<TileOrientation>);
+
+impl PlacementOrientation {
+    /// Step to the next fixed orientation, or back to automatic after the
+    /// last one.
+    pub fn cycle(&mut self) {
+        self.0 = match self.0 {
+            None => Some(TileOrientation::ALL[0]),
+            Some(current) => TileOrientation::ALL.get(current as usize + 1).copied(),
+        };
+    }
+
+    /// Orientation of a tile placed at (`x`, `y`).
+    pub fn resolve(
+        self,
+        world_map: &WorldMap,
+        x: i32,
+        y: i32,
+        ctx: &WorldCtxRef,
+    ) -> TileOrientation {
+        self.0.unwrap_or_else(|| auto_orientation(world_map, x, y, ctx))
+    }
+}
+
+/// First orientation, in [`TileOrientation::ALL`] order, whose supporting
+/// neighbour is solid; the floor when none is.
+pub fn auto_orientation(
+    world_map: &WorldMap,
+    x: i32,
+    y: i32,
+    ctx: &WorldCtxRef,
+) -> TileOrientation {
+    TileOrientation::ALL
+        .into_iter()
+        .find(|orientation| {
+            let support = IVec2::new(x, y) + orientation.support_offset();
+            world_map.is_solid(support.x, support.y, ctx)
+        })
+        .unwrap_or_default()
+}
+
+/// Cycle [`PlacementOrientation`] when the cycle key is pressed.
+pub fn cycle_orientation_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keys: Res<OrientationKeys>,
+    capture: Res<InputCapture>,
+    mut orientation: ResMut<PlacementOrientation>,
+) {
+    if !capture.keyboard && keyboard.just_pressed(keys.cycle) {
+        orientation.cycle();
+    }
+}
+
+/// Mark the side of the targeted tile an orientable tile held in either hand
+/// would attach to.
+pub fn draw_orientation_hint(
+    target: Res<TargetTile>,
+    orientation: Res<PlacementOrientation>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    item_registry: Res<ItemRegistry>,
+    player_query: Query<&Hotbar, With<Player>>,
+    mut gizmos: Gizmos<TargetOutlineGizmos>,
+) {
+    let Some(target) = target.0 else {
+        return;
+    };
+    if !target.can_place || !target.in_sight || target.bg_modifier {
+        return;
+    }
+    let Ok(hotbar) = player_query.single() else {
+        return;
+    };
+    let ctx_ref = ctx.as_ref();
+    let holds_orientable = [Hand::Left, Hand::Right].into_iter().any(|hand| {
+        hotbar
+            .get_item_for_hand(hand == Hand::Left)
+            .and_then(|item_id| resolve_placeable(item_id, &item_registry, &ctx_ref))
+            .is_some_and(|tile| ctx_ref.tile_registry.is_orientable(tile))
+    });
+    let (tile_x, tile_y) = target.cursor_tile;
+    let occupied = world_map
+        .get_tile(tile_x, tile_y, Layer::Fg, &ctx_ref)
+        .is_some_and(|t| ctx_ref.tile_registry.is_solid(t));
+    if !holds_orientable || occupied {
+        return;
+    }
+
+    let rect = tile_outline_rect(target.world_pos, ctx_ref.config.tile_size);
+    let (a, b) = match orientation.resolve(&world_map, tile_x, tile_y, &ctx_ref) {
+        TileOrientation::Floor => (rect.min, Vec2::new(rect.max.x, rect.min.y)),
+        TileOrientation::LeftWall => (rect.min, Vec2::new(rect.min.x, rect.max.y)),
+        TileOrientation::RightWall => (Vec2::new(rect.max.x, rect.min.y), rect.max),
+        TileOrientation::Ceiling => (Vec2::new(rect.min.x, rect.max.y), rect.max),
+    };
+    gizmos.line_2d(a, b, HINT_COLOR);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::tile::TileId;
+    use crate::test_helpers::fixtures;
+
+    const X: i32 = 100;
+    const Y: i32 = 520;
+
+    /// Orientation picked for a tile at (X, Y) in open air with stone at
+    /// each of the given offsets.
+    fn auto_with(solid: &[(i32, i32)]) -> TileOrientation {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let stone = tr.by_name("stone");
+        let mut map = WorldMap::default();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let tile = if solid.contains(&(dx, dy)) {
+                    stone
+                } else {
+                    TileId::AIR
+                };
+                map.set_tile(X + dx, Y + dy, Layer::Fg, tile, &ctx);
+            }
+        }
+        auto_orientation(&map, X, Y, &ctx)
+    }
+
+    #[test]
+    fn floor_wins_over_walls_and_ceiling() {
+        let all = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+        assert_eq!(auto_with(&all), TileOrientation::Floor);
+    }
+
+    #[test]
+    fn walls_come_before_the_ceiling_left_first() {
+        assert_eq!(
+            auto_with(&[(-1, 0), (1, 0), (0, 1)]),
+            TileOrientation::LeftWall
+        );
+        assert_eq!(auto_with(&[(1, 0), (0, 1)]), TileOrientation::RightWall);
+        assert_eq!(auto_with(&[(0, 1)]), TileOrientation::Ceiling);
+    }
+
+    #[test]
+    fn no_support_defaults_to_floor() {
+        // Diagonal neighbours don't hold anything up.
+        assert_eq!(auto_with(&[(-1, -1), (1, 1)]), TileOrientation::Floor);
+    }
+
+    #[test]
+    fn cycle_steps_through_every_orientation_and_back_to_auto() {
+        let mut orientation = PlacementOrientation::default();
+        let mut seen = Vec::new();
+        for _ in 0..TileOrientation::ALL.len() {
+            orientation.cycle();
+            seen.extend(orientation.0);
+        }
+        assert_eq!(seen, TileOrientation::ALL);
+        orientation.cycle();
+        assert_eq!(orientation, PlacementOrientation(None));
+    }
+
+    #[test]
+    fn manual_orientation_overrides_the_neighbours() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.set_tile(X, Y - 1, Layer::Fg, tr.by_name("stone"), &ctx);
+        let manual = PlacementOrientation(Some(TileOrientation::Ceiling));
+        assert_eq!(manual.resolve(&map, X, Y, &ctx), TileOrientation::Ceiling);
+    }
+}
@@ -0,0 +1,162 @@
+//! Hanging tiles (ropes): each hangs from the tile above it. New rope is
+//! added at the bottom of a run, and whatever hangs below a broken tile
+//! breaks with it.
+
+use crate::registry::tile::TileId;
+use crate::world::chunk::{Layer, WorldMap};
+use crate::world::ctx::WorldCtxRef;
+
+/// Longest run of one hanging tile that placement will extend.
+pub const MAX_ROPE_LENGTH: i32 = 32;
+
+fn fg(world_map: &WorldMap, x: i32, y: i32, ctx: &WorldCtxRef) -> Option<TileId> {
+    world_map.get_tile(x, y, Layer::Fg, ctx)
+}
+
+/// Whether a hanging tile at (x, y) has something to hang from: a solid
+/// tile or another hanging tile right above it.
+pub fn can_hang(world_map: &WorldMap, x: i32, y: i32, ctx: &WorldCtxRef) -> bool {
+    fg(world_map, x, y + 1, ctx)
+        .is_some_and(|t| ctx.tile_registry.is_solid(t) || ctx.tile_registry.is_hanging(t))
+}
+
+/// Tile where placing more of the rope at (x, y) goes: the air right below
+/// the bottom of its run. None when the run is already
+/// [`MAX_ROPE_LENGTH`] long or ends on something other than air.
+pub fn extension_tile(
+    world_map: &WorldMap,
+    x: i32,
+    y: i32,
+    ctx: &WorldCtxRef,
+) -> Option<(i32, i32)> {
+    let rope = fg(world_map, x, y, ctx)?;
+    let is_rope = |y: i32| fg(world_map, x, y, ctx) == Some(rope);
+    let mut top = y;
+    while is_rope(top + 1) && y - top < MAX_ROPE_LENGTH {
+        top += 1;
+    }
+    let mut bottom = y;
+    while is_rope(bottom - 1) && top - bottom < MAX_ROPE_LENGTH {
+        bottom -= 1;
+    }
+    if top - bottom + 1 >= MAX_ROPE_LENGTH {
+        return None;
+    }
+    (fg(world_map, x, bottom - 1, ctx) == Some(TileId::AIR)).then_some((x, bottom - 1))
+}
+
+/// Hanging tiles held up, directly or through each other, by the tile at
+/// (x, y), top first.
+pub fn hanging_below(
+    world_map: &WorldMap,
+    x: i32,
+    y: i32,
+    ctx: &WorldCtxRef,
+) -> Vec<((i32, i32), TileId)> {
+    let mut hanging = Vec::new();
+    let mut ty = y - 1;
+    while let Some(tile) = fg(world_map, x, ty, ctx).filter(|&t| ctx.tile_registry.is_hanging(t)) {
+        hanging.push(((x, ty), tile));
+        ty -= 1;
+    }
+    hanging
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::tile::{TileDef, TileRegistry};
+    use crate::test_helpers::fixtures;
+
+    const X: i32 = 100;
+    const CEILING: i32 = 520;
+
+    /// Test registry plus a climbable, hanging "rope" tile.
+    fn rope_registry() -> TileRegistry {
+        let mut defs = fixtures::test_tile_registry().defs;
+        defs.push(TileDef {
+            id: "rope".into(),
+            climbable: true,
+            hanging: true,
+            ..defs[0].clone()
+        });
+        TileRegistry::from_defs(defs)
+    }
+
+    /// A stone ceiling at [`CEILING`] with `len` rope tiles hanging from it
+    /// over air, and a stone floor at `CEILING - 40`.
+    fn rope_map(len: i32, ctx: &WorldCtxRef) -> WorldMap {
+        let stone = ctx.tile_registry.by_name("stone");
+        let rope = ctx.tile_registry.by_name("rope");
+        let mut map = WorldMap::default();
+        map.set_tile(X, CEILING, Layer::Fg, stone, ctx);
+        for y in CEILING - 39..CEILING {
+            let tile = if y >= CEILING - len {
+                rope
+            } else {
+                TileId::AIR
+            };
+            map.set_tile(X, y, Layer::Fg, tile, ctx);
+        }
+        map.set_tile(X, CEILING - 40, Layer::Fg, stone, ctx);
+        map
+    }
+
+    #[test]
+    fn rope_extends_below_the_bottom_of_its_run() {
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let tr = rope_registry();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let map = rope_map(4, &ctx);
+
+        // Clicking any tile of the run extends it at the bottom.
+        for y in CEILING - 4..CEILING {
+            assert_eq!(
+                extension_tile(&map, X, y, &ctx),
+                Some((X, CEILING - 5)),
+                "clicked {y}"
+            );
+        }
+        assert!(can_hang(&map, X, CEILING - 1, &ctx));
+        assert!(can_hang(&map, X, CEILING - 5, &ctx));
+        assert!(!can_hang(&map, X, CEILING - 6, &ctx));
+    }
+
+    #[test]
+    fn rope_extension_stops_at_the_cap_and_at_the_floor() {
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let tr = rope_registry();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+
+        let map = rope_map(MAX_ROPE_LENGTH - 1, &ctx);
+        assert_eq!(
+            extension_tile(&map, X, CEILING - 1, &ctx),
+            Some((X, CEILING - MAX_ROPE_LENGTH))
+        );
+        let map = rope_map(MAX_ROPE_LENGTH, &ctx);
+        assert_eq!(extension_tile(&map, X, CEILING - 1, &ctx), None);
+
+        // A rope reaching down to the floor has nowhere to grow.
+        let mut map = rope_map(4, &ctx);
+        map.set_tile(X, CEILING - 5, Layer::Fg, tr.by_name("stone"), &ctx);
+        assert_eq!(extension_tile(&map, X, CEILING - 2, &ctx), None);
+    }
+
+    #[test]
+    fn breaking_a_tile_takes_everything_hanging_below() {
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let tr = rope_registry();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let rope = tr.by_name("rope");
+        let map = rope_map(5, &ctx);
+
+        let below = hanging_below(&map, X, CEILING - 2, &ctx);
+        assert_eq!(
+            below,
+            [CEILING - 3, CEILING - 4, CEILING - 5].map(|y| ((X, y), rope))
+        );
+        // The anchor holds the whole rope; the bottom tile holds nothing.
+        assert_eq!(hanging_below(&map, X, CEILING, &ctx).len(), 5);
+        assert!(hanging_below(&map, X, CEILING - 5, &ctx).is_empty());
+    }
+}
@@ -0,0 +1,891 @@
+//! Copy-paste schematics for creative building.
+//!
+//! `/schematic copy` starts a selection: left-click two opposite corners and
+//! the region's foreground and background tiles, tile state and sign text are
+//! copied into the clipboard as a [`Schematic`]. `/schematic paste` shows the
+//! clipboard as a translucent ghost anchored at the cursor; left-click pastes
+//! it through [`apply_tile_edits`] and right-click leaves paste mode.
+//! `/schematic mirror` flips pastes horizontally, `/schematic air
+//! skip|overwrite` chooses whether air cells clear what is under them, and
+//! `/schematic save|load <name>` keeps clipboards as RON files in
+//! [`SCHEMATICS_DIR`]. The command needs cheats and creative mode. Placed
+//! objects are not copied.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy::sprite_render::AlphaMode2d;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::chat::{ChatCommandEvent, ChatState};
+use crate::cosmos::persistence::DirtyChunks;
+use crate::cosmos::pressurization::PressureMap;
+use crate::game_mode::{CheatsEnabled, GameMode};
+use crate::liquid::LiquidSimState;
+use crate::registry::tile::TileId;
+use crate::registry::AppState;
+use crate::sets::GameSet;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{
+    apply_tile_edits, ChunkDirty, Layer, LoadedChunks, TileChanged, TileEdit, WorldMap,
+};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::rc_lighting::RcGridDirty;
+
+use super::target::TargetTile;
+
+/// Directory (relative to the working directory) schematics are saved in.
+pub const SCHEMATICS_DIR: &str = "schematics";
+/// File name suffix of saved schematics.
+const SCHEMATIC_EXTENSION: &str = "schematic.ron";
+/// Opacity of the paste preview.
+const GHOST_ALPHA: f32 = 0.45;
+/// Brightness of background tiles in the preview relative to their albedo.
+const GHOST_BG_SHADE: f32 = 0.5;
+/// Colour of the selection rectangle while copying.
+const SELECTION_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.25];
+/// Preview z-layer: above tiles and objects, below particles.
+const GHOST_Z: f32 = 0.6;
+
+#[derive(Debug, Error)]
+pub enum SchematicError {
+    #[error("invalid schematic name '{0}' (use letters, digits, '-' and '_')")]
+    InvalidName(String),
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{path}: {source}")]
+    Ron {
+        path: PathBuf,
+        source: Box<ron::error::SpannedError>,
+    },
+    #[error("failed to serialize schematic: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("{path}: tile data doesn't match the {width}x{height} size")]
+    Malformed {
+        path: PathBuf,
+        width: u32,
+        height: u32,
+    },
+}
+
+/// What pasting does with the air cells of a schematic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PasteAir {
+    /// Air clears whatever is in the target cell.
+    #[default]
+    Overwrite,
+    /// Air leaves the target cell as it is.
+    Skip,
+}
+
+/// A copied rectangle of tiles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schematic {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major from the anchor, the bottom-left corner:
+    /// index = y * width + x.
+    pub fg: Vec<TileId>,
+    pub bg: Vec<TileId>,
+    /// Non-zero foreground tile state bytes, by index.
+    #[serde(default)]
+    pub tile_state: BTreeMap<usize, u8>,
+    /// Sign text, by index.
+    #[serde(default)]
+    pub signs: BTreeMap<usize, String>,
+}
+
+/// Tiles written by a paste and the data chunks it left needing a mesh
+/// rebuild.
+pub struct PasteResult {
+    pub edits: Vec<TileEdit>,
+    pub dirty_chunks: HashSet<(i32, i32)>,
+}
+
+impl Schematic {
+    /// Copy the rectangle spanned by two opposite corner tiles (inclusive).
+    /// Chunks that haven't generated yet are generated.
+    pub fn copy(
+        world_map: &mut WorldMap,
+        corner_a: (i32, i32),
+        corner_b: (i32, i32),
+        ctx: &WorldCtxRef,
+    ) -> Self {
+        let (min_x, max_x) = (corner_a.0.min(corner_b.0), corner_a.0.max(corner_b.0));
+        let (min_y, max_y) = (corner_a.1.min(corner_b.1), corner_a.1.max(corner_b.1));
+        let width = (max_x - min_x + 1) as u32;
+        let height = (max_y - min_y + 1) as u32;
+        let mut schematic = Self {
+            width,
+            height,
+            fg: Vec::with_capacity((width * height) as usize),
+            bg: Vec::with_capacity((width * height) as usize),
+            tile_state: BTreeMap::new(),
+            signs: BTreeMap::new(),
+        };
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let idx = schematic.fg.len();
+                schematic
+                    .fg
+                    .push(world_map.get_tile_mut(x, y, Layer::Fg, ctx));
+                schematic
+                    .bg
+                    .push(world_map.get_tile_mut(x, y, Layer::Bg, ctx));
+                let state = world_map.get_tile_state(x, y, ctx);
+                if state != 0 {
+                    schematic.tile_state.insert(idx, state);
+                }
+                if let Some(text) = world_map.get_sign_text(x, y, ctx) {
+                    schematic.signs.insert(idx, text.to_owned());
+                }
+            }
+        }
+        schematic
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y * self.width + x) as usize
+    }
+
+    /// The schematic flipped left to right.
+    pub fn mirrored(&self) -> Self {
+        let flip = |idx: usize| {
+            let (x, y) = (idx as u32 % self.width, idx as u32 / self.width);
+            self.index(self.width - 1 - x, y)
+        };
+        let flip_tiles = |tiles: &[TileId]| (0..tiles.len()).map(|i| tiles[flip(i)]).collect();
+        Self {
+            width: self.width,
+            height: self.height,
+            fg: flip_tiles(&self.fg),
+            bg: flip_tiles(&self.bg),
+            tile_state: self
+                .tile_state
+                .iter()
+                .map(|(&i, &s)| (flip(i), s))
+                .collect(),
+            signs: self
+                .signs
+                .iter()
+                .map(|(&i, text)| (flip(i), text.clone()))
+                .collect(),
+        }
+    }
+
+    /// Tile writes that paste the schematic with its bottom-left corner at
+    /// `anchor`. Coordinates are unwrapped; the world wraps them on write.
+    pub fn paste_edits(&self, anchor: (i32, i32), air: PasteAir) -> Vec<TileEdit> {
+        let mut edits = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let idx = self.index(x, y);
+                for (layer, tile) in [(Layer::Bg, self.bg[idx]), (Layer::Fg, self.fg[idx])] {
+                    if tile == TileId::AIR && air == PasteAir::Skip {
+                        continue;
+                    }
+                    edits.push(TileEdit {
+                        tile_x: anchor.0 + x as i32,
+                        tile_y: anchor.1 + y as i32,
+                        layer,
+                        tile,
+                    });
+                }
+            }
+        }
+        edits
+    }
+
+    /// Paste with the bottom-left corner at `anchor`, restoring tile state
+    /// and sign text of the foreground tiles it writes.
+    pub fn paste(
+        &self,
+        world_map: &mut WorldMap,
+        anchor: (i32, i32),
+        air: PasteAir,
+        ctx: &WorldCtxRef,
+    ) -> PasteResult {
+        let edits = self.paste_edits(anchor, air);
+        let dirty_chunks = apply_tile_edits(world_map, &edits, ctx);
+        for edit in edits.iter().filter(|e| e.layer == Layer::Fg) {
+            let x = (edit.tile_x - anchor.0) as u32;
+            let y = (edit.tile_y - anchor.1) as u32;
+            let idx = self.index(x, y);
+            if let Some(&state) = self.tile_state.get(&idx) {
+                world_map.set_tile_state(edit.tile_x, edit.tile_y, state, ctx);
+            }
+            if let Some(text) = self.signs.get(&idx) {
+                world_map.set_sign_text(edit.tile_x, edit.tile_y, text.clone(), ctx);
+            }
+        }
+        PasteResult {
+            edits,
+            dirty_chunks,
+        }
+    }
+
+    /// Path of schematic `name` in `dir`. Names are limited to letters,
+    /// digits, `-` and `_` so they can't leave the directory.
+    pub fn path(dir: &Path, name: &str) -> Result<PathBuf, SchematicError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(SchematicError::InvalidName(name.to_owned()));
+        }
+        Ok(dir.join(format!("{name}.{SCHEMATIC_EXTENSION}")))
+    }
+
+    /// Write the schematic to `dir` as `name`, creating the directory.
+    pub fn save(&self, dir: &Path, name: &str) -> Result<PathBuf, SchematicError> {
+        let path = Self::path(dir, name)?;
+        let ron =
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default().depth_limit(1))?;
+        std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(&path, ron + "\n"))
+            .map_err(|source| SchematicError::Io {
+                path: path.clone(),
+                source,
+            })?;
+        Ok(path)
+    }
+
+    /// Read schematic `name` from `dir`.
+    pub fn load(dir: &Path, name: &str) -> Result<Self, SchematicError> {
+        let path = Self::path(dir, name)?;
+        let bytes = std::fs::read(&path).map_err(|source| SchematicError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let schematic: Self =
+            ron::de::from_bytes(&bytes).map_err(|source| SchematicError::Ron {
+                path: path.clone(),
+                source: Box::new(source),
+            })?;
+        let len = (schematic.width * schematic.height) as usize;
+        if schematic.fg.len() != len || schematic.bg.len() != len {
+            return Err(SchematicError::Malformed {
+                path,
+                width: schematic.width,
+                height: schematic.height,
+            });
+        }
+        Ok(schematic)
+    }
+}
+
+/// What the schematic tool is doing with the mouse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchematicMode {
+    #[default]
+    Idle,
+    /// Waiting for the corners of the region to copy.
+    Selecting { first: Option<(i32, i32)> },
+    /// Previewing the clipboard at the cursor; clicks paste it.
+    Pasting,
+}
+
+/// Clipboard and settings of the schematic tool.
+#[derive(Resource, Debug, Default)]
+pub struct SchematicTool {
+    pub mode: SchematicMode,
+    pub clipboard: Option<Schematic>,
+    /// Paste the clipboard flipped left to right.
+    pub mirror: bool,
+    pub air: PasteAir,
+    /// The click that ended a selection or paste is still held; it must not
+    /// reach block interaction once the tool goes idle.
+    awaiting_release: bool,
+    /// The preview mesh no longer matches the clipboard.
+    ghost_stale: bool,
+}
+
+impl SchematicTool {
+    /// Whether world clicks belong to the tool instead of block interaction.
+    pub fn claims_pointer(&self) -> bool {
+        self.mode != SchematicMode::Idle || self.awaiting_release
+    }
+
+    /// The clipboard as it will be pasted.
+    pub fn paste_source(&self) -> Option<Cow<'_, Schematic>> {
+        let clipboard = self.clipboard.as_ref()?;
+        Some(if self.mirror {
+            Cow::Owned(clipboard.mirrored())
+        } else {
+            Cow::Borrowed(clipboard)
+        })
+    }
+}
+
+/// `/schematic copy|paste|mirror|air <skip|overwrite>|save <name>|load <name>|cancel`.
+pub fn handle_schematic_command(
+    mut commands_in: MessageReader<ChatCommandEvent>,
+    cheats: Res<CheatsEnabled>,
+    game_mode: Res<GameMode>,
+    mut tool: ResMut<SchematicTool>,
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    for cmd in commands_in.read() {
+        if cmd.command != "schematic" && cmd.command != "schem" {
+            continue;
+        }
+        let reply = if !cheats.0 {
+            "Cheats are disabled.".to_string()
+        } else if !game_mode.is_creative() {
+            "Schematics need creative mode.".to_string()
+        } else {
+            run_schematic_command(&mut tool, &cmd.args, Path::new(SCHEMATICS_DIR))
+        };
+        if let Some(chat) = chat.as_mut() {
+            chat.send_system(&reply, time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Apply one `/schematic` command to the tool and describe the result.
+fn run_schematic_command(tool: &mut SchematicTool, args: &[String], dir: &Path) -> String {
+    let arg = |i: usize| args.get(i).map(String::as_str);
+    match arg(0) {
+        Some("copy") => {
+            tool.mode = SchematicMode::Selecting { first: None };
+            "Click two opposite corners of the region to copy.".to_string()
+        }
+        Some("paste") if tool.clipboard.is_none() => "The clipboard is empty.".to_string(),
+        Some("paste") => {
+            tool.mode = SchematicMode::Pasting;
+            "Left-click to paste, right-click to stop.".to_string()
+        }
+        Some("mirror") => {
+            tool.mirror = !tool.mirror;
+            tool.ghost_stale = true;
+            format!("Mirroring {}", if tool.mirror { "on" } else { "off" })
+        }
+        Some("air") => match arg(1) {
+            Some("skip") => {
+                tool.air = PasteAir::Skip;
+                "Air cells keep existing tiles.".to_string()
+            }
+            Some("overwrite") => {
+                tool.air = PasteAir::Overwrite;
+                "Air cells clear existing tiles.".to_string()
+            }
+            _ => "Usage: /schematic air <skip|overwrite>".to_string(),
+        },
+        Some("save") => match (arg(1), &tool.clipboard) {
+            (None, _) => "Usage: /schematic save <name>".to_string(),
+            (Some(_), None) => "The clipboard is empty.".to_string(),
+            (Some(name), Some(clipboard)) => match clipboard.save(dir, name) {
+                Ok(path) => format!("Saved {}", path.display()),
+                Err(e) => e.to_string(),
+            },
+        },
+        Some("load") => match arg(1).map(|name| Schematic::load(dir, name)) {
+            None => "Usage: /schematic load <name>".to_string(),
+            Some(Ok(schematic)) => {
+                let reply = format!("Loaded {}x{}", schematic.width, schematic.height);
+                tool.clipboard = Some(schematic);
+                tool.ghost_stale = true;
+                reply
+            }
+            Some(Err(e)) => e.to_string(),
+        },
+        Some("cancel") => {
+            tool.mode = SchematicMode::Idle;
+            "Schematic tool off.".to_string()
+        }
+        _ => "Usage: /schematic <copy|paste|mirror|air|save|load|cancel>".to_string(),
+    }
+}
+
+/// Corner clicks while selecting, paste clicks while pasting.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn schematic_tool_input(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    capture: Res<InputCapture>,
+    game_mode: Res<GameMode>,
+    target: Res<TargetTile>,
+    ctx: WorldCtx,
+    mut world_map: ResMut<WorldMap>,
+    loaded_chunks: Res<LoadedChunks>,
+    mut tool: ResMut<SchematicTool>,
+    effects: (
+        ResMut<RcGridDirty>,
+        ResMut<DirtyChunks>,
+        Option<ResMut<PressureMap>>,
+        Option<ResMut<LiquidSimState>>,
+        MessageWriter<TileChanged>,
+    ),
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    let (mut rc_dirty, mut saved_chunks, pressure_map, mut liquid_sim, mut tile_changes) = effects;
+    if tool.awaiting_release && !mouse.any_pressed([MouseButton::Left, MouseButton::Right]) {
+        tool.awaiting_release = false;
+    }
+    if tool.mode == SchematicMode::Idle {
+        return;
+    }
+    if !game_mode.is_creative() {
+        tool.mode = SchematicMode::Idle;
+        return;
+    }
+    if capture.pointer || capture.keyboard {
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Right) {
+        tool.mode = SchematicMode::Idle;
+        tool.awaiting_release = true;
+        return;
+    }
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(tile) = target.0.map(|t| t.cursor_tile) else {
+        return;
+    };
+    let ctx_ref = ctx.as_ref();
+
+    let reply = match tool.mode {
+        SchematicMode::Idle => return,
+        SchematicMode::Selecting { first: None } => {
+            tool.mode = SchematicMode::Selecting { first: Some(tile) };
+            return;
+        }
+        SchematicMode::Selecting { first: Some(first) } => {
+            let schematic = Schematic::copy(&mut world_map, first, tile, &ctx_ref);
+            let reply = format!("Copied {}x{}", schematic.width, schematic.height);
+            tool.clipboard = Some(schematic);
+            tool.ghost_stale = true;
+            tool.mode = SchematicMode::Idle;
+            tool.awaiting_release = true;
+            reply
+        }
+        SchematicMode::Pasting => {
+            let Some(source) = tool.paste_source() else {
+                tool.mode = SchematicMode::Idle;
+                return;
+            };
+            let result = source.paste(&mut world_map, tile, tool.air, &ctx_ref);
+            let reply = format!("Pasted {}x{}", source.width, source.height);
+            for edit in result.edits.iter().filter(|e| e.layer == Layer::Fg) {
+                tile_changes.write(TileChanged {
+                    tile_x: edit.tile_x,
+                    tile_y: edit.tile_y,
+                });
+                if let Some(sim) = liquid_sim.as_mut() {
+                    sim.sleep.wake_with_neighbors(edit.tile_x, edit.tile_y);
+                }
+            }
+            rc_dirty.0 = true;
+            if let Some(mut pm) = pressure_map {
+                pm.dirty = true;
+            }
+            saved_chunks.0.extend(result.dirty_chunks.iter().copied());
+            for (&(display_cx, display_cy), entities) in &loaded_chunks.map {
+                let data_chunk = (ctx_ref.config.wrap_chunk_x(display_cx), display_cy);
+                if result.dirty_chunks.contains(&data_chunk) {
+                    commands.entity(entities.fg).insert(ChunkDirty);
+                    commands.entity(entities.bg).insert(ChunkDirty);
+                }
+            }
+            reply
+        }
+    };
+    if let Some(chat) = chat.as_mut() {
+        chat.send_system(&reply, time.elapsed_secs_f64());
+    }
+}
+
+/// Marker for the paste preview and selection rectangle.
+#[derive(Component)]
+pub struct SchematicGhost;
+
+/// A coloured rectangle of tiles: min tile, max tile (inclusive), rgba.
+type GhostQuad = ((i32, i32), (i32, i32), [f32; 4]);
+
+/// Mesh of coloured quads, tile coordinates scaled by `tile_size`.
+fn quad_mesh(quads: &[GhostQuad], tile_size: f32) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(quads.len() * 4);
+    let mut colors: Vec<[f32; 4]> = Vec::with_capacity(quads.len() * 4);
+    let mut indices: Vec<u32> = Vec::with_capacity(quads.len() * 6);
+    for &((x0, y0), (x1, y1), color) in quads {
+        let base = positions.len() as u32;
+        let (l, b) = (x0 as f32 * tile_size, y0 as f32 * tile_size);
+        let (r, t) = ((x1 + 1) as f32 * tile_size, (y1 + 1) as f32 * tile_size);
+        positions.extend([[l, b, 0.0], [r, b, 0.0], [r, t, 0.0], [l, t, 0.0]]);
+        colors.extend([color; 4]);
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+/// Preview quads of a schematic relative to its anchor: foreground tiles in
+/// their albedo, background-only cells shaded darker, air left out.
+fn ghost_quads(schematic: &Schematic, ctx: &WorldCtxRef) -> Vec<GhostQuad> {
+    let mut quads = Vec::new();
+    for y in 0..schematic.height {
+        for x in 0..schematic.width {
+            let idx = schematic.index(x, y);
+            let (tile, shade) = if schematic.fg[idx] != TileId::AIR {
+                (schematic.fg[idx], 1.0)
+            } else if schematic.bg[idx] != TileId::AIR {
+                (schematic.bg[idx], GHOST_BG_SHADE)
+            } else {
+                continue;
+            };
+            let [r, g, b] = ctx
+                .tile_registry
+                .albedo(tile)
+                .map(|c| c as f32 / 255.0 * shade);
+            let cell = (x as i32, y as i32);
+            quads.push((cell, cell, [r, g, b, GHOST_ALPHA]));
+        }
+    }
+    quads
+}
+
+/// Show the clipboard at the cursor while pasting and the selection
+/// rectangle while copying.
+#[allow(clippy::too_many_arguments)]
+pub fn update_schematic_ghost(
+    mut commands: Commands,
+    mut tool: ResMut<SchematicTool>,
+    target: Res<TargetTile>,
+    ctx: WorldCtx,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut ghost: Query<(&Mesh2d, &mut Transform, &mut Visibility), With<SchematicGhost>>,
+) {
+    let Ok((mesh, mut transform, mut visibility)) = ghost.single_mut() else {
+        let material = materials.add(ColorMaterial {
+            color: Color::WHITE,
+            alpha_mode: AlphaMode2d::Blend,
+            ..default()
+        });
+        commands.spawn((
+            SchematicGhost,
+            Mesh2d(meshes.add(quad_mesh(&[], 1.0))),
+            MeshMaterial2d(material),
+            Transform::from_xyz(0.0, 0.0, GHOST_Z),
+            Visibility::Hidden,
+        ));
+        tool.ghost_stale = true;
+        return;
+    };
+    *visibility = Visibility::Hidden;
+    let ctx_ref = ctx.as_ref();
+    let tile_size = ctx_ref.config.tile_size;
+    let Some(cursor) = target.0.map(|t| t.cursor_tile) else {
+        return;
+    };
+
+    let anchor = match tool.mode {
+        SchematicMode::Idle | SchematicMode::Selecting { first: None } => return,
+        SchematicMode::Selecting { first: Some(first) } => {
+            let min = (first.0.min(cursor.0), first.1.min(cursor.1));
+            let max = (first.0.max(cursor.0), first.1.max(cursor.1));
+            let size = (max.0 - min.0, max.1 - min.1);
+            let quads = [((0, 0), size, SELECTION_COLOR)];
+            if let Some(mesh) = meshes.get_mut(&mesh.0) {
+                *mesh = quad_mesh(&quads, tile_size);
+            }
+            // The next paste preview has to replace the rectangle.
+            tool.ghost_stale = true;
+            min
+        }
+        SchematicMode::Pasting => {
+            if tool.ghost_stale {
+                let quads = tool
+                    .paste_source()
+                    .map_or_else(Vec::new, |source| ghost_quads(&source, &ctx_ref));
+                if let Some(mesh) = meshes.get_mut(&mesh.0) {
+                    *mesh = quad_mesh(&quads, tile_size);
+                }
+                tool.ghost_stale = false;
+            }
+            cursor
+        }
+    };
+    transform.translation.x = anchor.0 as f32 * tile_size;
+    transform.translation.y = anchor.1 as f32 * tile_size;
+    *visibility = Visibility::Inherited;
+}
+
+pub fn register(app: &mut App) {
+    app.init_resource::<SchematicTool>().add_systems(
+        Update,
+        (
+            handle_schematic_command,
+            (
+                schematic_tool_input
+                    .after(super::target::target_resolution)
+                    .after(super::block_action::block_interaction_system),
+                update_schematic_ghost,
+            )
+                .chain()
+                .in_set(GameSet::Input),
+        )
+            .run_if(in_state(AppState::InGame)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+    use crate::world::autotile::compute_bitmask;
+    use crate::world::chunk::{tile_to_chunk, tile_to_local};
+
+    /// Fill a rectangle of both layers with `tile`.
+    fn fill(map: &mut WorldMap, min: (i32, i32), max: (i32, i32), tile: TileId, ctx: &WorldCtxRef) {
+        for y in min.1..=max.1 {
+            for x in min.0..=max.0 {
+                map.set_tile(x, y, Layer::Fg, tile, ctx);
+                map.set_tile(x, y, Layer::Bg, tile, ctx);
+            }
+        }
+    }
+
+    fn bitmask(map: &WorldMap, x: i32, y: i32, layer: Layer, ctx: &WorldCtxRef) -> u8 {
+        let x = ctx.config.wrap_tile_x(x);
+        let (cx, cy) = tile_to_chunk(x, y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(x, y, ctx.config.chunk_size);
+        map.chunk(cx, cy).unwrap().layer(layer).bitmasks[(ly * ctx.config.chunk_size + lx) as usize]
+    }
+
+    /// An L of stone with a dirt wall behind its corner and air elsewhere:
+    ///
+    /// ```text
+    /// S . .
+    /// S S S   (bg dirt under the left column)
+    /// ```
+    fn ell(ctx: &WorldCtxRef) -> Schematic {
+        let stone = ctx.tile_registry.by_name("stone");
+        let dirt = ctx.tile_registry.by_name("dirt");
+        let a = TileId::AIR;
+        Schematic {
+            width: 3,
+            height: 2,
+            fg: vec![stone, stone, stone, stone, a, a],
+            bg: vec![dirt, a, a, dirt, a, a],
+            tile_state: BTreeMap::from([(4, 1)]),
+            signs: BTreeMap::from([(3, "west".to_string())]),
+        }
+    }
+
+    #[test]
+    fn copy_paste_round_trips_tiles_state_and_signs() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let (stone, dirt) = (tr.by_name("stone"), tr.by_name("dirt"));
+        let source = (100, 700);
+        fill(
+            &mut map,
+            source,
+            (source.0 + 5, source.1 + 3),
+            TileId::AIR,
+            &ctx,
+        );
+        map.set_tile(101, 700, Layer::Fg, stone, &ctx);
+        map.set_tile(102, 701, Layer::Fg, dirt, &ctx);
+        map.set_tile(105, 703, Layer::Bg, dirt, &ctx);
+        map.set_tile_state(102, 701, 3, &ctx);
+        map.set_sign_text(101, 700, "hello".into(), &ctx);
+
+        let copied = Schematic::copy(&mut map, (105, 703), source, &ctx);
+        assert_eq!((copied.width, copied.height), (6, 4));
+        assert_eq!(copied.fg[1], stone);
+        assert_eq!(copied.bg[copied.index(5, 3)], dirt);
+
+        let target = (400, 650);
+        fill(&mut map, target, (target.0 + 5, target.1 + 3), stone, &ctx);
+        let result = copied.paste(&mut map, target, PasteAir::Overwrite, &ctx);
+        assert_eq!(result.edits.len(), 2 * 6 * 4);
+        let (cx, cy) = tile_to_chunk(target.0, target.1, wc.chunk_size);
+        assert!(result.dirty_chunks.contains(&(cx, cy)));
+
+        let pasted = Schematic::copy(&mut map, target, (target.0 + 5, target.1 + 3), &ctx);
+        assert_eq!(pasted, copied);
+        assert_eq!(map.get_tile_state(402, 651, &ctx), 3);
+        assert_eq!(map.get_sign_text(401, 650, &ctx), Some("hello"));
+    }
+
+    #[test]
+    fn mirrored_paste_flips_tiles_and_recomputes_bitmasks() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let ell = ell(&ctx);
+        let mirrored = ell.mirrored();
+        assert_eq!(mirrored.mirrored(), ell);
+        // Bottom row is full either way; the top row's stone moves right.
+        assert_eq!(&mirrored.fg[..3], &ell.fg[..3]);
+        assert_eq!(mirrored.fg[5], tr.by_name("stone"));
+        assert_eq!(mirrored.fg[3], TileId::AIR);
+        assert_eq!(mirrored.bg[2], tr.by_name("dirt"));
+        assert_eq!(mirrored.tile_state, BTreeMap::from([(4, 1)]));
+        assert_eq!(mirrored.signs, BTreeMap::from([(5, "west".to_string())]));
+
+        let mut map = WorldMap::default();
+        let anchor = (300, 700);
+        // Open air around the paste, so every mask depends on the paste.
+        fill(&mut map, (299, 699), (303, 702), TileId::AIR, &ctx);
+        mirrored.paste(&mut map, anchor, PasteAir::Overwrite, &ctx);
+        assert_eq!(
+            map.get_tile(302, 701, Layer::Fg, &ctx),
+            Some(tr.by_name("stone"))
+        );
+        assert_eq!(map.get_tile(300, 701, Layer::Fg, &ctx), Some(TileId::AIR));
+        assert_eq!(map.get_sign_text(302, 701, &ctx), Some("west"));
+
+        for layer in [Layer::Fg, Layer::Bg] {
+            for y in 699..=702 {
+                for x in 299..=303 {
+                    let expected = compute_bitmask(
+                        |bx, by| {
+                            map.get_tile(bx, by, layer, &ctx)
+                                .is_some_and(|t| tr.is_solid(t))
+                        },
+                        x,
+                        y,
+                    );
+                    assert_eq!(
+                        bitmask(&map, x, y, layer, &ctx),
+                        expected,
+                        "({x}, {y}) {layer:?}"
+                    );
+                }
+            }
+        }
+        // The corner of the mirrored L: stone west and north of the
+        // bottom-right tile, the rest air.
+        assert_ne!(bitmask(&map, 302, 700, Layer::Fg, &ctx), 0);
+    }
+
+    #[test]
+    fn skip_air_keeps_existing_tiles() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let grass = tr.by_name("grass");
+        let ell = ell(&ctx);
+
+        for (air, expected) in [(PasteAir::Skip, grass), (PasteAir::Overwrite, TileId::AIR)] {
+            let mut map = WorldMap::default();
+            fill(&mut map, (200, 700), (202, 701), grass, &ctx);
+            let result = ell.paste(&mut map, (200, 700), air, &ctx);
+            let written = result.edits.len();
+            assert_eq!(written, if air == PasteAir::Skip { 6 } else { 12 });
+            assert_eq!(map.get_tile(201, 701, Layer::Fg, &ctx), Some(expected));
+            assert_eq!(map.get_tile(202, 700, Layer::Bg, &ctx), Some(expected));
+            assert_eq!(
+                map.get_tile(202, 700, Layer::Fg, &ctx),
+                Some(tr.by_name("stone"))
+            );
+        }
+    }
+
+    #[test]
+    fn paste_wraps_across_the_seam() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let ell = ell(&ctx).mirrored();
+        let mut map = WorldMap::default();
+        let w = wc.width_tiles;
+        fill(&mut map, (w - 3, 700), (w + 1, 702), TileId::AIR, &ctx);
+        let result = ell.paste(&mut map, (w - 1, 700), PasteAir::Overwrite, &ctx);
+
+        // Columns w-1, w and w+1 land on w-1, 0 and 1.
+        let stone = Some(tr.by_name("stone"));
+        assert_eq!(map.get_tile(w - 1, 700, Layer::Fg, &ctx), stone);
+        assert_eq!(map.get_tile(1, 701, Layer::Fg, &ctx), stone);
+        assert_eq!(map.get_tile(0, 701, Layer::Fg, &ctx), Some(TileId::AIR));
+        assert_eq!(map.get_sign_text(1, 701, &ctx), Some("west"));
+        let last_chunk = (w - 1) / wc.chunk_size as i32;
+        assert!(result
+            .dirty_chunks
+            .contains(&(last_chunk, 700 / wc.chunk_size as i32)));
+        assert!(result
+            .dirty_chunks
+            .contains(&(0, 700 / wc.chunk_size as i32)));
+        // Bitmasks see neighbours on the other side of the seam.
+        assert_eq!(
+            Schematic::copy(&mut map, (w - 1, 700), (w + 1, 701), &ctx),
+            Schematic::copy(&mut map, (-1, 700), (1, 701), &ctx)
+        );
+        assert_ne!(bitmask(&map, w - 1, 700, Layer::Fg, &ctx), 0);
+    }
+
+    #[test]
+    fn schematics_save_and_load_by_name() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let dir = std::env::temp_dir().join(format!("schematics-{}", std::process::id()));
+        let ell = ell(&ctx);
+        let path = ell.save(&dir, "l_shape").unwrap();
+        assert!(path.ends_with("l_shape.schematic.ron"));
+        assert_eq!(Schematic::load(&dir, "l_shape").unwrap(), ell);
+
+        assert!(matches!(
+            ell.save(&dir, "../escape"),
+            Err(SchematicError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Schematic::load(&dir, "missing"),
+            Err(SchematicError::Io { .. })
+        ));
+        let truncated = Schematic {
+            fg: vec![TileId::AIR],
+            ..ell
+        };
+        truncated.save(&dir, "truncated").unwrap();
+        assert!(matches!(
+            Schematic::load(&dir, "truncated"),
+            Err(SchematicError::Malformed { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn commands_drive_the_tool_modes() {
+        let dir = std::env::temp_dir().join(format!("schematic-cmd-{}", std::process::id()));
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut tool = SchematicTool::default();
+        assert!(!tool.claims_pointer());
+
+        run_schematic_command(&mut tool, &args(&["paste"]), &dir);
+        assert_eq!(tool.mode, SchematicMode::Idle, "nothing to paste");
+        run_schematic_command(&mut tool, &args(&["copy"]), &dir);
+        assert_eq!(tool.mode, SchematicMode::Selecting { first: None });
+        assert!(tool.claims_pointer());
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        tool.clipboard = Some(ell(&ctx));
+        run_schematic_command(&mut tool, &args(&["paste"]), &dir);
+        assert_eq!(tool.mode, SchematicMode::Pasting);
+        run_schematic_command(&mut tool, &args(&["mirror"]), &dir);
+        assert_eq!(*tool.paste_source().unwrap(), ell(&ctx).mirrored());
+        run_schematic_command(&mut tool, &args(&["air", "skip"]), &dir);
+        assert_eq!(tool.air, PasteAir::Skip);
+        run_schematic_command(&mut tool, &args(&["cancel"]), &dir);
+        assert!(!tool.claims_pointer());
+    }
+}
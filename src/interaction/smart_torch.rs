@@ -0,0 +1,424 @@
+//! Smart torches: an opt-in helper that lights the way while mining.
+//!
+//! While a hand is held down mining and the estimated light at the player's
+//! tile (see [`light_at`]) stays under [`SmartTorch::threshold`] for
+//! [`SmartTorch::dwell_secs`], one torch from anywhere in the inventory is
+//! placed at the best spot within [`SmartTorch::reach`] tiles (see
+//! [`find_torch_spot`]). Placement follows the torch object's normal rules
+//! and charges the mining hand the torch's placement cooldown. Another torch
+//! goes down only once the player is [`SmartTorch::spacing`] tiles away
+//! from where the last one was placed.
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::DirtyChunks;
+use crate::game_mode::GameMode;
+use crate::inventory::{Hand, Hotbar, Inventory};
+use crate::item::{ItemAction, ItemRegistry};
+use crate::object::definition::ObjectId;
+use crate::object::placement::{can_place_object, place_object};
+use crate::object::plugin::ObjectSpriteMaterials;
+use crate::object::registry::ObjectRegistry;
+use crate::object::spawn::spawn_object_entity;
+use crate::player::Player;
+use crate::registry::tile::TileId;
+use crate::registry::world::ActiveWorld;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{
+    tile_to_chunk, world_to_tile, Layer, LoadedChunks, TileChanged, WorldMap,
+};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::day_night::WorldTime;
+use crate::world::growth::{light_at, sunlight_at};
+use crate::world::lit_sprite::{LitSpriteMaterial, SharedLitQuad};
+use crate::world::rc_lighting::RcGridDirty;
+
+use super::block_action::{held_item_def, placement_cost};
+use super::hand_action::{resolve_hand_action, use_cooldown, HandCooldowns};
+use super::target::TargetTile;
+
+/// Smart torch settings. Off by default; toggled from the debug panel.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SmartTorch {
+    pub enabled: bool,
+    /// Item placed; its `placeable_object` is the object put down.
+    pub item: String,
+    /// Light (0–1) under which the player's tile counts as dark.
+    pub threshold: f32,
+    /// Seconds of mining in the dark before a torch goes down.
+    pub dwell_secs: f32,
+    /// Furthest a torch is placed from the player's tile, in tiles.
+    pub reach: i32,
+    /// Tiles the player must move from the last auto-placed torch.
+    pub spacing: i32,
+}
+
+impl Default for SmartTorch {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            item: "torch".into(),
+            threshold: 0.2,
+            dwell_secs: 2.0,
+            reach: 2,
+            spacing: 8,
+        }
+    }
+}
+
+/// Darkness timer and the last auto-placed torch.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SmartTorchState {
+    /// Seconds the player has been mining in the dark.
+    pub dark_secs: f32,
+    /// Player tile the last torch was placed from.
+    pub last_placed: Option<(i32, i32)>,
+}
+
+impl SmartTorchState {
+    /// Advance the timer by `dt` (reset when not `dark`). True once it has
+    /// been dark for `dwell` seconds.
+    pub fn tick(&mut self, dark: bool, dt: f32, dwell: f32) -> bool {
+        if dark {
+            self.dark_secs += dt;
+        } else {
+            self.dark_secs = 0.0;
+        }
+        self.dark_secs >= dwell
+    }
+
+    /// Whether the player at `tile` is at least `spacing` tiles (Chebyshev,
+    /// across the wrap seam) from the last auto-placed torch.
+    pub fn spaced(&self, tile: (i32, i32), spacing: i32, config: &ActiveWorld) -> bool {
+        let Some((x, y)) = self.last_placed else {
+            return true;
+        };
+        let mut dx = (tile.0 - x).abs();
+        if config.wrap_x {
+            let width = config.width_tiles;
+            dx = dx.rem_euclid(width);
+            dx = dx.min(width - dx);
+        }
+        dx.max((tile.1 - y).abs()) >= spacing
+    }
+
+    /// Record a torch placed from `tile` and restart the timer.
+    pub fn placed(&mut self, tile: (i32, i32)) {
+        self.last_placed = Some(tile);
+        self.dark_secs = 0.0;
+    }
+}
+
+/// How a candidate spot is held up; lower is preferred.
+fn support_rank(world_map: &WorldMap, x: i32, y: i32, ctx: &WorldCtxRef) -> u8 {
+    let wall_behind = world_map
+        .get_tile(x, y, Layer::Bg, ctx)
+        .is_some_and(|tile| tile != TileId::AIR);
+    if wall_behind {
+        0
+    } else if world_map.is_solid(x, y - 1, ctx) {
+        1
+    } else if world_map.is_solid(x - 1, y, ctx) || world_map.is_solid(x + 1, y, ctx) {
+        2
+    } else {
+        3
+    }
+}
+
+/// Best spot for `object` within `reach` tiles of `(px, py)`, or `None` if
+/// nothing there passes [`can_place_object`].
+///
+/// Nearest spots come first. Among equally near ones a wall behind beats a
+/// floor below, which beats a solid tile beside; remaining ties go bottom to
+/// top, then left to right.
+pub fn find_torch_spot(
+    world_map: &WorldMap,
+    object_registry: &ObjectRegistry,
+    object: ObjectId,
+    (px, py): (i32, i32),
+    reach: i32,
+    ctx: &WorldCtxRef,
+) -> Option<(i32, i32)> {
+    let mut candidates: Vec<(i32, u8, i32, i32)> = Vec::new();
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            let dist = dx * dx + dy * dy;
+            if dist > reach * reach {
+                continue;
+            }
+            let (x, y) = (px + dx, py + dy);
+            if can_place_object(world_map, object_registry, object, x, y, ctx) {
+                candidates.push((dist, support_rank(world_map, x, y, ctx), y, x));
+            }
+        }
+    }
+    candidates.into_iter().min().map(|(_, _, y, x)| (x, y))
+}
+
+/// Place a torch near the player once they have mined in the dark for a
+/// while. Runs before `block_interaction_system`, so a hand about to mine
+/// this frame is still off cooldown.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn smart_torch_system(
+    mut commands: Commands,
+    settings: Res<SmartTorch>,
+    mut state: ResMut<SmartTorchState>,
+    input: (
+        Res<ButtonInput<MouseButton>>,
+        Res<TargetTile>,
+        Res<InputCapture>,
+        Res<GameMode>,
+        Res<Time>,
+    ),
+    mut player_query: Query<
+        (&Transform, &Hotbar, &mut Inventory, &mut HandCooldowns),
+        With<Player>,
+    >,
+    ctx: WorldCtx,
+    mut world_map: ResMut<WorldMap>,
+    loaded_chunks: Res<LoadedChunks>,
+    item_registry: Res<ItemRegistry>,
+    world_time: Option<Res<WorldTime>>,
+    objects: (
+        Option<Res<ObjectRegistry>>,
+        Option<Res<ObjectSpriteMaterials>>,
+        Option<Res<SharedLitQuad>>,
+        Option<ResMut<Assets<LitSpriteMaterial>>>,
+    ),
+    mut changes: (
+        ResMut<DirtyChunks>,
+        MessageWriter<TileChanged>,
+        Option<ResMut<RcGridDirty>>,
+    ),
+) {
+    if !settings.enabled {
+        return;
+    }
+    let (mouse, target, capture, game_mode, time) = input;
+    let Ok((player_tf, hotbar, mut inventory, mut cooldowns)) = player_query.single_mut() else {
+        return;
+    };
+
+    // Mining: a held button whose hand mines, aimed at something.
+    let aiming = target.0.is_some() && !capture.pointer && !capture.keyboard;
+    let mining_hand = [Hand::Left, Hand::Right].into_iter().find(|&hand| {
+        let item = hotbar.get_item_for_hand(hand == Hand::Left);
+        aiming
+            && mouse.pressed(hand.button())
+            && resolve_hand_action(held_item_def(&item_registry, item), hand) == ItemAction::Mine
+    });
+    let Some(hand) = mining_hand else {
+        state.dark_secs = 0.0;
+        return;
+    };
+
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let pos = player_tf.translation;
+    let tile = world_to_tile(pos.x, pos.y, config.tile_size);
+    let (object_registry, object_sprites, quad, mut lit_materials) = objects;
+    let sun = world_time.map_or(1.0, |wt| wt.sun_intensity);
+    let sky = sunlight_at(&world_map, tile.0, tile.1, [sun; 3], &ctx_ref)
+        .into_iter()
+        .fold(0.0, f32::max);
+    let light = light_at(
+        &world_map,
+        tile.0,
+        tile.1,
+        sky,
+        object_registry.as_deref(),
+        &ctx_ref,
+    );
+
+    if !state.tick(
+        light < settings.threshold,
+        time.delta_secs(),
+        settings.dwell_secs,
+    ) || !state.spaced(tile, settings.spacing, config)
+        || !cooldowns.ready(hand)
+        || inventory.count_item(&settings.item) == 0
+    {
+        return;
+    }
+    let Some(registry) = object_registry.as_deref() else {
+        return;
+    };
+    let torch_def = held_item_def(&item_registry, Some(&settings.item));
+    let Some(object_id) = torch_def
+        .and_then(|def| def.placeable_object.as_deref())
+        .and_then(|name| registry.by_name(name))
+    else {
+        return;
+    };
+    let Some((x, y)) = find_torch_spot(
+        &world_map,
+        registry,
+        object_id,
+        tile,
+        settings.reach,
+        &ctx_ref,
+    ) else {
+        return;
+    };
+    if !place_object(&mut world_map, registry, object_id, x, y, &ctx_ref) {
+        return;
+    }
+
+    inventory.remove_item(&settings.item, placement_cost(*game_mode));
+    cooldowns.start(hand, use_cooldown(torch_def, ItemAction::PlaceFg));
+    state.placed(tile);
+
+    let (dirty_chunks, tile_changes, rc_dirty) = &mut changes;
+    let anchor_chunk = tile_to_chunk(config.wrap_tile_x(x), y, config.chunk_size);
+    dirty_chunks.0.insert(anchor_chunk);
+    let object_index = world_map
+        .chunk(anchor_chunk.0, anchor_chunk.1)
+        .map_or(0, |c| c.objects.len() - 1) as u16;
+    for &(display_cx, display_cy) in loaded_chunks.map.keys() {
+        if let Some(lit_materials) = lit_materials.as_deref_mut()
+            && config.wrap_chunk_x(display_cx) == anchor_chunk.0
+            && display_cy == anchor_chunk.1
+        {
+            spawn_object_entity(
+                &mut commands,
+                &world_map,
+                registry,
+                object_sprites.as_deref(),
+                quad.as_deref(),
+                lit_materials,
+                anchor_chunk,
+                display_cx,
+                object_index,
+                config.tile_size,
+                config.chunk_size,
+            );
+        }
+    }
+    tile_changes.write(TileChanged {
+        tile_x: x,
+        tile_y: y,
+    });
+    if let Some(rc_dirty) = rc_dirty.as_deref_mut() {
+        rc_dirty.0 = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{ObjectDef, ObjectType, PlacementRule};
+    use crate::test_helpers::fixtures;
+
+    fn torch_registry() -> ObjectRegistry {
+        ObjectRegistry::from_defs(vec![ObjectDef {
+            id: "torch_object".into(),
+            display_name: "Torch".into(),
+            size: (1, 1),
+            sprite: "torch.png".into(),
+            solid_mask: vec![false],
+            placement: PlacementRule::FloorOrWall,
+            light_emission: [255, 170, 40],
+            object_type: ObjectType::LightSource,
+            drops: vec![],
+            sprite_columns: 1,
+            sprite_rows: 1,
+            sprite_fps: 0.0,
+            flicker_speed: 0.0,
+            flicker_strength: 0.0,
+            flicker_min: 1.0,
+            auto_item: None,
+            background: false,
+        }])
+    }
+
+    /// Clear a 7×7 pocket of fg and bg around `(px, py)`.
+    fn carve(map: &mut WorldMap, (px, py): (i32, i32), ctx: &WorldCtxRef) {
+        for y in py - 3..=py + 3 {
+            for x in px - 3..=px + 3 {
+                map.set_tile(x, y, Layer::Fg, TileId::AIR, ctx);
+                map.set_tile(x, y, Layer::Bg, TileId::AIR, ctx);
+            }
+        }
+    }
+
+    #[test]
+    fn spot_search_prefers_near_then_wall_then_floor() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let registry = torch_registry();
+        let torch = ObjectId(0);
+        let player = (100, 200);
+        carve(&mut map, player, &ctx);
+
+        // Nothing to hang on in open air.
+        assert_eq!(
+            find_torch_spot(&map, &registry, torch, player, 2, &ctx),
+            None
+        );
+
+        // A floor two tiles down makes the tile above it the nearest spot.
+        let solid = TileId(1);
+        for x in 97..=103 {
+            map.set_tile(x, 197, Layer::Fg, solid, &ctx);
+        }
+        assert_eq!(
+            find_torch_spot(&map, &registry, torch, player, 2, &ctx),
+            Some((100, 198))
+        );
+
+        // Ledges beside the player: a floor beats the side of a solid, and
+        // equally good spots go left to right.
+        map.set_tile(99, 199, Layer::Fg, solid, &ctx);
+        map.set_tile(101, 199, Layer::Fg, solid, &ctx);
+        assert_eq!(
+            find_torch_spot(&map, &registry, torch, player, 2, &ctx),
+            Some((99, 200))
+        );
+
+        // A wall behind beats a bare floor.
+        map.set_tile(101, 200, Layer::Bg, solid, &ctx);
+        assert_eq!(
+            find_torch_spot(&map, &registry, torch, player, 2, &ctx),
+            Some((101, 200))
+        );
+
+        // A solid beside the player's own tile wins on distance.
+        map.set_tile(99, 200, Layer::Fg, solid, &ctx);
+        assert_eq!(
+            find_torch_spot(&map, &registry, torch, player, 2, &ctx),
+            Some((100, 200))
+        );
+    }
+
+    #[test]
+    fn dwell_timer_needs_continuous_darkness() {
+        let mut state = SmartTorchState::default();
+        assert!(!state.tick(true, 1.5, 2.0));
+        assert!(!state.tick(false, 0.1, 2.0), "light resets the timer");
+        assert!(!state.tick(true, 1.5, 2.0));
+        assert!(state.tick(true, 0.5, 2.0));
+
+        state.placed((0, 0));
+        assert_eq!(state.dark_secs, 0.0);
+    }
+
+    #[test]
+    fn spacing_is_measured_from_the_last_torch() {
+        let (wc, ..) = fixtures::test_world_ctx();
+        let mut state = SmartTorchState::default();
+        assert!(state.spaced((5, 5), 8, &wc), "first torch is always spaced");
+
+        state.placed((5, 5));
+        assert!(!state.spaced((5, 5), 8, &wc));
+        assert!(!state.spaced((12, 0), 8, &wc));
+        assert!(state.spaced((13, 5), 8, &wc));
+        assert!(state.spaced((5, 13), 8, &wc));
+
+        // Across the wrap seam.
+        let width = wc.width_tiles;
+        state.placed((1, 5));
+        assert!(!state.spaced((width - 3, 5), 8, &wc));
+        assert!(state.spaced((width - 7, 5), 8, &wc));
+    }
+}
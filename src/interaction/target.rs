@@ -0,0 +1,287 @@
+//! The tile the player is aiming at, resolved once per frame.
+//!
+//! [`target_resolution`] runs first in [`GameSet::Input`](crate::sets::GameSet::Input)
+//! and turns the pointer into a [`TargetTile`]: the tile under it, the layer
+//! the modifier selects, and whether it is within reach and line of sight.
+//! Block interaction, the target outline, the schematic preview, fishing,
+//! ranged attacks and arm aiming all read it instead of projecting the cursor
+//! themselves, so they always agree and a different input source only has to
+//! replace this one system.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::game_mode::GameMode;
+use crate::player::Player;
+use crate::registry::player::PlayerConfig;
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{world_to_tile, Layer, WorldMap};
+use crate::world::ctx::WorldCtx;
+
+use super::block_action::{reach_offset, within_break_reach, within_place_reach};
+use super::layer_target::{LayerModifierKeys, LayerPreference};
+use super::line_of_sight::{first_blocking_tile, EditLineOfSight};
+
+/// Input that produced a [`Target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetSource {
+    /// The mouse cursor over the primary window.
+    Cursor,
+}
+
+/// A tile the player is aiming at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    /// World position aimed at, unwrapped: past the seam it stays where the
+    /// pointer is.
+    pub world_pos: Vec2,
+    /// Tile under `world_pos`, unwrapped, for drawing and block damage.
+    pub cursor_tile: (i32, i32),
+    /// `cursor_tile` with x wrapped into the world.
+    pub tile: (i32, i32),
+    /// Layer clicks go to by default: the background while the layer
+    /// modifier is held. Each hand's action can still redirect it (see
+    /// [`resolve_layer`](super::layer_target::resolve_layer)).
+    pub layer: Layer,
+    /// Whether the layer modifier is held or the background is the
+    /// [`LayerPreference`].
+    pub bg_modifier: bool,
+    /// Within break reach of the player.
+    pub can_break: bool,
+    /// Within place reach of the player.
+    pub can_place: bool,
+    /// No solid tile blocks the line from the player (always true with the
+    /// line-of-sight rule off).
+    pub in_sight: bool,
+    /// Past the edge of a non-wrapping world, where nothing is edited.
+    pub outside: bool,
+    pub source: TargetSource,
+}
+
+impl Target {
+    /// Whether the tile can be edited at all: in some reach, in sight and
+    /// inside the world.
+    pub fn reachable(&self) -> bool {
+        (self.can_break || self.can_place) && self.in_sight && !self.outside
+    }
+}
+
+/// The current target, or `None` when nothing in the world is aimed at: no
+/// cursor, the window unfocused, the pointer over UI or the letterbox bars,
+/// or a text field typing.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TargetTile(pub Option<Target>);
+
+/// Resolve the cursor into [`TargetTile`].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn target_resolution(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    player_query: Query<&Transform, With<Player>>,
+    input: (
+        Res<InputCapture>,
+        Res<ButtonInput<KeyCode>>,
+        Res<LayerModifierKeys>,
+        Res<EditLineOfSight>,
+        Res<GameMode>,
+        Option<Res<LayerPreference>>,
+    ),
+    player_config: Res<PlayerConfig>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    mut target: ResMut<TargetTile>,
+) {
+    let (capture, keyboard, modifier_keys, line_of_sight, game_mode, preference) = input;
+    target.0 = None;
+    if capture.pointer || capture.keyboard {
+        return;
+    }
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    if !window.focused {
+        return;
+    }
+    let Some(world_pos) = window
+        .cursor_position()
+        .zip(camera_query.single().ok())
+        // The letterbox bars around a locked aspect are outside the world.
+        .filter(|(cursor, (camera, _))| {
+            camera
+                .logical_viewport_rect()
+                .is_none_or(|rect| rect.contains(*cursor))
+        })
+        .and_then(|(cursor, (camera, gt))| camera.viewport_to_world_2d(gt, cursor).ok())
+    else {
+        return;
+    };
+    let Ok(player_tf) = player_query.single() else {
+        return;
+    };
+
+    let ctx_ref = ctx.as_ref();
+    let tile_size = ctx_ref.config.tile_size;
+    let cursor_tile = world_to_tile(world_pos.x, world_pos.y, tile_size);
+    let player_pos = player_tf.translation.truncate();
+    let offset = reach_offset(
+        player_pos,
+        cursor_tile.0,
+        cursor_tile.1,
+        tile_size,
+        ctx_ref.config.width_tiles,
+    );
+    let reach_scale = game_mode.reach_multiplier();
+    let in_sight = !line_of_sight.enabled
+        || first_blocking_tile(player_pos, cursor_tile, tile_size, |x, y| {
+            world_map.is_solid(x, y, &ctx_ref)
+        })
+        .is_none();
+    let bg_modifier = modifier_keys.held(&keyboard) || preference.is_some_and(|p| p.bg);
+    target.0 = Some(Target {
+        world_pos,
+        cursor_tile,
+        tile: (ctx_ref.config.wrap_tile_x(cursor_tile.0), cursor_tile.1),
+        layer: if bg_modifier { Layer::Bg } else { Layer::Fg },
+        bg_modifier,
+        can_break: within_break_reach(offset, &player_config, reach_scale),
+        can_place: within_place_reach(offset, &player_config, reach_scale),
+        in_sight,
+        outside: ctx_ref.config.outside_x(cursor_tile.0),
+        source: TargetSource::Cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    const TILE: f32 = 32.0;
+
+    /// App with a window whose cursor sits `cursor` pixels off the centre
+    /// (y up), a camera centred on `centre` and the player at `player`.
+    fn target_app(centre: Vec2, cursor: Vec2, player: Vec2) -> App {
+        let mut app = fixtures::test_app();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LayerModifierKeys>()
+            .insert_resource(EditLineOfSight { enabled: false })
+            .init_resource::<GameMode>()
+            .init_resource::<InputCapture>()
+            .init_resource::<TargetTile>()
+            .add_systems(Update, target_resolution);
+
+        let mut window = Window::default();
+        let size = window.resolution.size();
+        window.set_cursor_position(Some(size / 2.0 + Vec2::new(cursor.x, -cursor.y)));
+        app.world_mut().spawn((window, PrimaryWindow));
+        let (half_w, half_h) = (size.x / 2.0, size.y / 2.0);
+        app.world_mut().spawn((
+            Camera2d,
+            Camera {
+                computed: bevy::camera::ComputedCameraValues {
+                    clip_from_view: Mat4::orthographic_rh(
+                        -half_w, half_w, -half_h, half_h, -1000.0, 1000.0,
+                    ),
+                    target_info: Some(bevy::camera::RenderTargetInfo {
+                        physical_size: size.as_uvec2(),
+                        scale_factor: 1.0,
+                    }),
+                    ..default()
+                },
+                ..default()
+            },
+            GlobalTransform::from_translation(centre.extend(0.0)),
+        ));
+        app.world_mut()
+            .spawn((Player, Transform::from_translation(player.extend(0.0))));
+        app
+    }
+
+    fn target(app: &App) -> Option<Target> {
+        app.world().resource::<TargetTile>().0
+    }
+
+    #[test]
+    fn cursor_resolves_to_the_tile_under_it() {
+        let centre = Vec2::new(100.5, 500.5) * TILE;
+        let mut app = target_app(centre, Vec2::new(TILE, 0.0), centre);
+        app.update();
+
+        let target = target(&app).unwrap();
+        assert_eq!(target.cursor_tile, (101, 500));
+        assert_eq!(target.tile, (101, 500));
+        assert_eq!(target.layer, Layer::Fg);
+        assert_eq!(target.source, TargetSource::Cursor);
+        assert!(target.reachable());
+    }
+
+    #[test]
+    fn layer_modifier_targets_the_background() {
+        let centre = Vec2::new(100.5, 500.5) * TILE;
+        let mut app = target_app(centre, Vec2::ZERO, centre);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::AltLeft);
+        app.update();
+
+        let target = target(&app).unwrap();
+        assert!(target.bg_modifier);
+        assert_eq!(target.layer, Layer::Bg);
+    }
+
+    #[test]
+    fn background_preference_acts_as_the_modifier() {
+        let centre = Vec2::new(100.5, 500.5) * TILE;
+        let mut app = target_app(centre, Vec2::ZERO, centre);
+        app.insert_resource(LayerPreference { bg: true });
+        app.update();
+
+        let target = target(&app).unwrap();
+        assert!(target.bg_modifier);
+        assert_eq!(target.layer, Layer::Bg);
+    }
+
+    #[test]
+    fn far_tiles_are_out_of_reach() {
+        let centre = Vec2::new(100.5, 500.5) * TILE;
+        let player = centre - Vec2::X * 40.0 * TILE;
+        let mut app = target_app(centre, Vec2::ZERO, player);
+        app.update();
+
+        let target = target(&app).unwrap();
+        assert!(!target.can_break && !target.can_place);
+        assert!(!target.reachable());
+    }
+
+    #[test]
+    fn tile_wraps_past_the_seam() {
+        // Camera centred just past the right edge of the 2048-tile world.
+        let centre = Vec2::new(2048.5, 500.5) * TILE;
+        let mut app = target_app(centre, Vec2::ZERO, centre);
+        app.update();
+
+        let target = target(&app).unwrap();
+        assert_eq!(target.cursor_tile, (2048, 500));
+        assert_eq!(target.tile, (0, 500));
+    }
+
+    #[test]
+    fn ui_capture_and_unfocused_window_clear_the_target() {
+        let centre = Vec2::new(100.5, 500.5) * TILE;
+        let mut app = target_app(centre, Vec2::ZERO, centre);
+        app.update();
+        assert!(target(&app).is_some());
+
+        app.world_mut().resource_mut::<InputCapture>().pointer = true;
+        app.update();
+        assert_eq!(target(&app), None);
+
+        app.world_mut().resource_mut::<InputCapture>().pointer = false;
+        let mut windows = app
+            .world_mut()
+            .query_filtered::<&mut Window, With<PrimaryWindow>>();
+        windows.single_mut(app.world_mut()).unwrap().focused = false;
+        app.update();
+        assert_eq!(target(&app), None);
+    }
+}
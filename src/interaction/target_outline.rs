@@ -0,0 +1,317 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::inventory::{Hand, Hotbar};
+use crate::item::{ItemAction, ItemRegistry};
+use crate::object::placement::get_object_at;
+use crate::player::Player;
+use crate::registry::tile::TileId;
+use crate::registry::AppState;
+use crate::sets::GameSet;
+use crate::ui::game_ui::theme::{TileOutlineStyle, UiTheme};
+use crate::world::chunk::{world_to_tile, Layer, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+
+use super::block_action::held_item_def;
+use super::hand_action::resolve_hand_action;
+use super::layer_target::resolve_layer;
+use super::target::TargetTile;
+
+/// Texture size of the outline images in pixels (scaled up to tile size).
+const OUTLINE_SIZE: usize = 16;
+/// Length of each dash (and gap) of the background outline, in texels.
+const DASH_LEN: usize = 2;
+const OUTLINE_ALPHA: u8 = 170;
+
+/// Marker for the outline drawn around the tile under the cursor.
+#[derive(Component)]
+pub struct TargetOutline;
+
+/// Outline textures: solid when clicks target the foreground, dashed when the
+/// layer modifier redirects them to the background.
+#[derive(Resource)]
+pub struct OutlineTextures {
+    pub solid: Handle<Image>,
+    pub dashed: Handle<Image>,
+}
+
+/// Generate a 16x16 RGBA tile border, optionally dashed.
+fn generate_outline_image(dashed: bool) -> Image {
+    let n = OUTLINE_SIZE;
+    let mut data = vec![0u8; n * n * 4];
+
+    for y in 0..n {
+        for x in 0..n {
+            let on_edge = x == 0 || y == 0 || x == n - 1 || y == n - 1;
+            if !on_edge {
+                continue;
+            }
+            // Dash phase follows the edge the pixel lies on.
+            let along = if y == 0 || y == n - 1 { x } else { y };
+            if dashed && (along / DASH_LEN) % 2 == 1 {
+                continue;
+            }
+            let idx = (y * n + x) * 4;
+            data[idx..idx + 4].copy_from_slice(&[255, 255, 255, OUTLINE_ALPHA]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: n as u32,
+            height: n as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// System that runs on `OnEnter(AppState::InGame)` to create the OutlineTextures resource.
+pub fn init_outline_textures(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(OutlineTextures {
+        solid: images.add(generate_outline_image(false)),
+        dashed: images.add(generate_outline_image(true)),
+    });
+}
+
+/// Gizmo group for the core line of the targeted-tile outline.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct TargetOutlineGizmos;
+
+/// Gizmo group for the wider, faded pass drawn under the core line so its
+/// edges blend into the scene instead of stair-stepping.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct TargetOutlineFeatherGizmos;
+
+/// Alpha multiplier of the feathered pass relative to the outline colour.
+const FEATHER_ALPHA: f32 = 0.35;
+
+/// World-space rect of the tile under `world_pos`.
+///
+/// The rect follows the unwrapped cursor position, so past the horizontal seam
+/// it is drawn where the cursor is rather than at the wrapped tile column.
+pub fn tile_outline_rect(world_pos: Vec2, tile_size: f32) -> Rect {
+    let (tile_x, tile_y) = world_to_tile(world_pos.x, world_pos.y, tile_size);
+    let min = Vec2::new(tile_x as f32, tile_y as f32) * tile_size;
+    Rect::from_corners(min, min + Vec2::splat(tile_size))
+}
+
+/// Whether either hand can act on the tile: break something that is there,
+/// convert a tile its held tool modifies, or place its held block into an
+/// empty cell.
+#[allow(clippy::too_many_arguments)]
+fn has_block_target(
+    hotbar: &Hotbar,
+    item_registry: &ItemRegistry,
+    world_map: &WorldMap,
+    ctx: &WorldCtxRef,
+    (tile_x, tile_y): (i32, i32),
+    bg_modifier: bool,
+    can_break: bool,
+    can_place: bool,
+) -> bool {
+    [Hand::Left, Hand::Right].into_iter().any(|hand| {
+        let def = held_item_def(item_registry, hotbar.get_item_for_hand(hand == Hand::Left));
+        let action = resolve_hand_action(def, hand);
+        let Some(layer) = resolve_layer(action, hand, bg_modifier) else {
+            return false;
+        };
+        let occupied = match layer {
+            Layer::Fg => {
+                world_map
+                    .get_tile(tile_x, tile_y, Layer::Fg, ctx)
+                    .is_some_and(|t| ctx.tile_registry.is_solid(t))
+                    || get_object_at(world_map, tile_x, tile_y, ctx).is_some()
+            }
+            Layer::Bg => world_map
+                .get_tile(tile_x, tile_y, Layer::Bg, ctx)
+                .is_some_and(|t| t != TileId::AIR),
+        };
+        if action == ItemAction::Mine {
+            occupied && can_break
+        } else if action == ItemAction::ModifyTile {
+            can_break
+                && world_map
+                    .get_tile(tile_x, tile_y, layer, ctx)
+                    .zip(def)
+                    .is_some_and(|(t, d)| d.converted_tile(&ctx.tile_registry.get(t).id).is_some())
+        } else {
+            !occupied
+                && can_place
+                && def.is_some_and(|d| d.placeable.is_some() || d.placeable_object.is_some())
+        }
+    })
+}
+
+/// Keep the outline on the tile under the cursor while it is a reachable
+/// break or place target, and switch its style with the targeted layer.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_target_outline(
+    mut commands: Commands,
+    textures: Option<Res<OutlineTextures>>,
+    target: Res<TargetTile>,
+    theme: Option<Res<UiTheme>>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    item_registry: Res<ItemRegistry>,
+    player_query: Query<&Hotbar, With<Player>>,
+    mut outline: Query<
+        (&mut Sprite, &mut Transform, &mut Visibility),
+        (With<TargetOutline>, Without<Player>),
+    >,
+    mut gizmos: (
+        Gizmos<TargetOutlineGizmos>,
+        Gizmos<TargetOutlineFeatherGizmos>,
+        ResMut<GizmoConfigStore>,
+    ),
+) {
+    let Some(textures) = textures else {
+        return;
+    };
+    let ctx_ref = ctx.as_ref();
+    let tile_size = ctx_ref.config.tile_size;
+
+    let Ok((mut sprite, mut transform, mut visibility)) = outline.single_mut() else {
+        commands.spawn((
+            TargetOutline,
+            Sprite {
+                image: textures.solid.clone(),
+                custom_size: Some(Vec2::splat(tile_size)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 0.2),
+            Visibility::Hidden,
+        ));
+        return;
+    };
+    *visibility = Visibility::Hidden;
+
+    let Some(target) = target.0 else {
+        return;
+    };
+    let Ok(hotbar) = player_query.single() else {
+        return;
+    };
+    let bg_modifier = target.bg_modifier;
+    if !target.in_sight
+        || !has_block_target(
+            hotbar,
+            &item_registry,
+            &world_map,
+            &ctx_ref,
+            target.tile,
+            bg_modifier,
+            target.can_break,
+            target.can_place,
+        )
+    {
+        return;
+    }
+
+    let rect = tile_outline_rect(target.world_pos, tile_size);
+    let outline_config = theme.map(|t| t.tile_outline.clone()).unwrap_or_default();
+    match outline_config.style {
+        TileOutlineStyle::Sprite => {
+            transform.translation.x = rect.center().x;
+            transform.translation.y = rect.center().y;
+            *visibility = Visibility::Visible;
+
+            let wanted = if bg_modifier {
+                &textures.dashed
+            } else {
+                &textures.solid
+            };
+            if sprite.image != *wanted {
+                sprite.image = wanted.clone();
+            }
+        }
+        TileOutlineStyle::Gizmo => {
+            let (core, feather, config_store) = &mut gizmos;
+            let style = if bg_modifier {
+                GizmoLineStyle::Dashed {
+                    gap_scale: 2.0,
+                    line_scale: 2.0,
+                }
+            } else {
+                GizmoLineStyle::Solid
+            };
+            let (core_config, _) = config_store.config_mut::<TargetOutlineGizmos>();
+            core_config.line.width = outline_config.width;
+            core_config.line.style = style;
+            let (feather_config, _) = config_store.config_mut::<TargetOutlineFeatherGizmos>();
+            feather_config.line.width = outline_config.width + 2.0 * outline_config.feather;
+            feather_config.line.style = style;
+
+            let color: Color = outline_config.color.into();
+            if outline_config.feather > 0.0 {
+                let faded = color.with_alpha(color.alpha() * FEATHER_ALPHA);
+                feather.rect_2d(rect.center(), rect.size(), faded);
+            }
+            core.rect_2d(rect.center(), rect.size(), color);
+        }
+    }
+}
+
+/// Plugin registration helper — call from InteractionPlugin::build.
+pub fn register(app: &mut App) {
+    app.init_gizmo_group::<TargetOutlineGizmos>()
+        .init_gizmo_group::<TargetOutlineFeatherGizmos>()
+        .add_systems(OnEnter(AppState::InGame), init_outline_textures)
+        .add_systems(
+            Update,
+            update_target_outline
+                .in_set(GameSet::Input)
+                .after(super::target::target_resolution)
+                .run_if(in_state(AppState::InGame)),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alpha_at(image: &Image, x: usize, y: usize) -> u8 {
+        image.data.as_ref().unwrap()[(y * OUTLINE_SIZE + x) * 4 + 3]
+    }
+
+    #[test]
+    fn solid_outline_has_continuous_border() {
+        let img = generate_outline_image(false);
+        for i in 0..OUTLINE_SIZE {
+            assert_eq!(alpha_at(&img, i, 0), OUTLINE_ALPHA);
+            assert_eq!(alpha_at(&img, 0, i), OUTLINE_ALPHA);
+        }
+        assert_eq!(alpha_at(&img, OUTLINE_SIZE / 2, OUTLINE_SIZE / 2), 0);
+    }
+
+    #[test]
+    fn dashed_outline_has_gaps() {
+        let img = generate_outline_image(true);
+        assert_eq!(alpha_at(&img, 0, 0), OUTLINE_ALPHA);
+        assert_eq!(alpha_at(&img, DASH_LEN, 0), 0);
+        assert_eq!(alpha_at(&img, 2 * DASH_LEN, 0), OUTLINE_ALPHA);
+    }
+
+    #[test]
+    fn outline_rect_covers_the_tile_under_the_cursor() {
+        let rect = tile_outline_rect(Vec2::new(40.0, 70.0), 32.0);
+        assert_eq!(rect.min, Vec2::new(32.0, 64.0));
+        assert_eq!(rect.max, Vec2::new(64.0, 96.0));
+
+        // Left of the origin floors into the negative tile.
+        let rect = tile_outline_rect(Vec2::new(-1.0, -0.5), 32.0);
+        assert_eq!(rect.min, Vec2::new(-32.0, -32.0));
+        assert_eq!(rect.max, Vec2::ZERO);
+
+        // Past the seam of a 2048-tile world the rect stays under the cursor
+        // instead of jumping back to column 0.
+        let seam = 2048.0 * 32.0;
+        let rect = tile_outline_rect(Vec2::new(seam + 5.0, 16.0), 32.0);
+        assert_eq!(rect.min, Vec2::new(seam, 0.0));
+        assert_eq!(rect.size(), Vec2::splat(32.0));
+    }
+}
@@ -1,64 +1,252 @@
 use bevy::prelude::*;
 
-use crate::crafting::UnlockedRecipes;
-use crate::inventory::{Hotbar, Inventory};
-use crate::item::ItemRegistry;
-use crate::item::definition::ItemType;
-use crate::player::Player;
-
-/// Signals that `use_item_system` consumed an item this frame (e.g. a blueprint),
-/// so other right-click systems can skip their processing.
-#[derive(Resource, Default)]
-pub struct ItemUsedThisFrame(pub bool);
-
-/// Consumes blueprint items from the active hotbar slot on right-click.
-pub fn use_item_system(
-    mouse: Res<ButtonInput<MouseButton>>,
-    mut player_query: Query<(&Hotbar, &mut Inventory, &mut UnlockedRecipes), With<Player>>,
-    item_registry: Res<ItemRegistry>,
-    mut item_used: ResMut<ItemUsedThisFrame>,
-    chat_state: Res<crate::chat::ChatState>,
-) {
-    item_used.0 = false;
-
-    if chat_state.is_active {
-        return;
+use crate::combat::Health;
+use crate::crafting::KnownRecipes;
+use crate::inventory::Inventory;
+use crate::item::definition::{ConsumeEffect, ItemType};
+use crate::item::ItemDef;
+use crate::player::stats::SpeedBoost;
+
+/// Consume one `def` from the inventory and apply its effect: blueprints
+/// unlock their recipes, consumables apply their [`ConsumeEffect`]s and
+/// consumables with a `health_bonus` heal the player.
+///
+/// Returns false (and keeps the item) if it isn't in the inventory, isn't a
+/// consumable or has no effect.
+pub fn consume_item(
+    def: &ItemDef,
+    inventory: &mut Inventory,
+    known: &mut KnownRecipes,
+    mut health: Option<&mut Health>,
+    mut speed_boost: Option<&mut SpeedBoost>,
+) -> bool {
+    if inventory.count_item(&def.id) == 0 {
+        return false;
+    }
+
+    if def.item_type == ItemType::Blueprint {
+        let Some(ref item_id_to_unlock) = def.blueprint_item else {
+            return false;
+        };
+        // Unlock all recipes gated by Blueprint(item_id) for this item
+        known.blueprints.insert(item_id_to_unlock.clone());
+        info!("Blueprint used: unlocked item '{}'", item_id_to_unlock);
+    } else {
+        if def.item_type != ItemType::Consumable && def.effects.is_empty() {
+            return false;
+        }
+        let bonus = def
+            .stats
+            .as_ref()
+            .and_then(|s| s.health_bonus)
+            .map(|heal| ConsumeEffect::Heal(heal as f32));
+        let mut applied = false;
+        for effect in def.effects.iter().copied().chain(bonus) {
+            match effect {
+                ConsumeEffect::Heal(amount) => {
+                    if let Some(health) = health.as_deref_mut() {
+                        health.heal(amount);
+                        applied = true;
+                    }
+                }
+                ConsumeEffect::SpeedBoost { multiplier, secs } => {
+                    if let Some(boost) = speed_boost.as_deref_mut() {
+                        boost.apply(multiplier, secs);
+                        applied = true;
+                    }
+                }
+                ConsumeEffect::UnlockBagRows(rows) => {
+                    applied |= inventory.unlock_rows(rows);
+                }
+            }
+        }
+        if !applied {
+            return false;
+        }
     }
 
-    if !mouse.just_pressed(MouseButton::Right) {
-        return;
+    inventory.remove_item(&def.id, 1);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{BagTarget, BAG_COLUMNS, MAIN_BAG_ROWS, STARTING_MAIN_ROWS};
+    use crate::item::ItemStats;
+    use crate::test_helpers::fixtures::item_def;
+
+    #[test]
+    fn blueprint_unlocks_and_is_used_up() {
+        let blueprint = ItemDef {
+            blueprint_item: Some("wooden_sword".into()),
+            ..item_def("blueprint_wooden_sword", ItemType::Blueprint)
+        };
+        let mut inventory = Inventory::new();
+        inventory.try_add_item(&blueprint.id, 1, 1, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+
+        assert!(consume_item(
+            &blueprint,
+            &mut inventory,
+            &mut known,
+            None,
+            None
+        ));
+        assert!(known.blueprints.contains("wooden_sword"));
+        assert_eq!(inventory.count_item(&blueprint.id), 0);
+        assert!(!consume_item(
+            &blueprint,
+            &mut inventory,
+            &mut known,
+            None,
+            None
+        ));
     }
 
-    let Ok((hotbar, mut inventory, mut unlocked)) = player_query.single_mut() else {
-        return;
-    };
+    #[test]
+    fn healing_item_heals_and_inert_item_is_kept() {
+        let mut potion = item_def("potion", ItemType::Consumable);
+        potion.stats = Some(ItemStats {
+            damage: None,
+            defense: None,
+            speed_bonus: None,
+            health_bonus: Some(30),
+            mining_power: None,
+            attack_speed: None,
+            knockback: None,
+            durability: None,
+        });
+        let snack = item_def("snack", ItemType::Consumable);
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("potion", 1, 99, BagTarget::Main);
+        inventory.try_add_item("snack", 1, 99, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+        let mut health = Health::new(100.0);
+        health.take_damage(50.0);
+
+        assert!(consume_item(
+            &potion,
+            &mut inventory,
+            &mut known,
+            Some(&mut health),
+            None
+        ));
+        assert_eq!(health.current, 80.0);
+        assert!(!consume_item(
+            &snack,
+            &mut inventory,
+            &mut known,
+            Some(&mut health),
+            None
+        ));
+        assert_eq!(inventory.count_item("snack"), 1);
+    }
 
-    // Check left hand of active slot
-    let Some(item_id) = hotbar.slots[hotbar.active_slot].left_hand.as_deref() else {
-        return;
-    };
+    #[test]
+    fn healing_potion_heals_up_to_max_and_uses_one() {
+        let potion = ItemDef {
+            effects: vec![ConsumeEffect::Heal(40.0)],
+            ..item_def("healing_potion", ItemType::Consumable)
+        };
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("healing_potion", 3, 99, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+        let mut health = Health::new(100.0);
+        health.take_damage(20.0);
 
-    if inventory.count_item(item_id) == 0 {
-        return;
+        assert!(consume_item(
+            &potion,
+            &mut inventory,
+            &mut known,
+            Some(&mut health),
+            None
+        ));
+        assert_eq!(health.current, 100.0);
+        assert_eq!(inventory.count_item("healing_potion"), 2);
     }
 
-    let Some(def_id) = item_registry.by_name(item_id) else {
-        return;
-    };
-    let def = item_registry.get(def_id);
+    #[test]
+    fn speed_potion_starts_a_boost() {
+        let tonic = ItemDef {
+            effects: vec![ConsumeEffect::SpeedBoost {
+                multiplier: 1.5,
+                secs: 20.0,
+            }],
+            ..item_def("swiftness_tonic", ItemType::Consumable)
+        };
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("swiftness_tonic", 1, 99, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+        let mut boost = SpeedBoost::default();
 
-    if def.item_type != ItemType::Blueprint {
-        return;
+        assert!(consume_item(
+            &tonic,
+            &mut inventory,
+            &mut known,
+            None,
+            Some(&mut boost)
+        ));
+        assert_eq!(boost.factor(), 1.5);
+        assert_eq!(inventory.count_item("swiftness_tonic"), 0);
     }
 
-    let Some(ref item_id_to_unlock) = def.blueprint_item else {
-        return;
-    };
+    #[test]
+    fn bag_upgrade_unlocks_rows_until_the_bag_is_full() {
+        let upgrade = ItemDef {
+            effects: vec![ConsumeEffect::UnlockBagRows(1)],
+            ..item_def("bag_upgrade", ItemType::Consumable)
+        };
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("bag_upgrade", 5, 99, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+        let start = inventory.slot_count();
 
-    // Unlock all recipes gated by Blueprint(item_id) for this item
-    unlocked.blueprints.insert(item_id_to_unlock.clone());
-    inventory.remove_item(item_id, 1);
-    item_used.0 = true;
+        assert!(consume_item(
+            &upgrade,
+            &mut inventory,
+            &mut known,
+            None,
+            None
+        ));
+        assert_eq!(inventory.slot_count(), start + BAG_COLUMNS);
+        while consume_item(&upgrade, &mut inventory, &mut known, None, None) {}
+        // Once every row is open the rest are kept.
+        assert_eq!(inventory.slot_count(), inventory.main_bag.len());
+        assert_eq!(
+            inventory.count_item("bag_upgrade"),
+            5 - (MAIN_BAG_ROWS - STARTING_MAIN_ROWS) as u32
+        );
+    }
+
+    #[test]
+    fn non_consumable_does_nothing() {
+        // Armor's health bonus is an equipment stat, not something to eat.
+        let mut helmet = item_def("helmet", ItemType::Armor);
+        helmet.stats = Some(ItemStats {
+            damage: None,
+            defense: Some(2.0),
+            speed_bonus: None,
+            health_bonus: Some(10),
+            mining_power: None,
+            attack_speed: None,
+            knockback: None,
+            durability: None,
+        });
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("helmet", 1, 1, BagTarget::Main);
+        let mut known = KnownRecipes::default();
+        let mut health = Health::new(100.0);
+        health.take_damage(50.0);
 
-    info!("Blueprint used: unlocked item '{}'", item_id_to_unlock);
+        assert!(!consume_item(
+            &helmet,
+            &mut inventory,
+            &mut known,
+            Some(&mut health),
+            None
+        ));
+        assert_eq!(health.current, 50.0);
+        assert_eq!(inventory.count_item("helmet"), 1);
+    }
 }
@@ -22,27 +22,219 @@ pub enum BagTarget {
     Main,
 }
 
+/// A slot in one of the two bags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BagSlot {
+    Main(usize),
+    Material(usize),
+}
+
+/// Slots touched since changes were last taken, turned into UI messages by
+/// `emit_inventory_changes`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotChanges<T> {
+    /// Changed slots, each listed once, in the order they were first touched.
+    pub slots: Vec<T>,
+    /// A bulk operation touched everything; `slots` is then irrelevant.
+    pub all: bool,
+}
+
+impl<T> Default for SlotChanges<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            all: false,
+        }
+    }
+}
+
+impl<T: PartialEq> SlotChanges<T> {
+    pub fn mark(&mut self, slot: T) {
+        if !self.all && !self.slots.contains(&slot) {
+            self.slots.push(slot);
+        }
+    }
+
+    pub fn mark_all(&mut self) {
+        self.all = true;
+        self.slots.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.all && self.slots.is_empty()
+    }
+}
+
+/// Columns in each bag row.
+pub const BAG_COLUMNS: usize = 8;
+/// Rows of the main bag when fully unlocked.
+pub const MAIN_BAG_ROWS: usize = 5;
+/// Main-bag rows open on a new character.
+pub const STARTING_MAIN_ROWS: usize = 3;
+
+/// How many main-bag rows are open, saved with the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BagCapacity {
+    /// Rows unlocked for good by consumed upgrades (starting rows included).
+    pub unlocked_rows: usize,
+    /// Extra rows from the bag worn in the Back slot.
+    pub bag_rows: usize,
+}
+
+impl BagCapacity {
+    /// Every row open — what inventories saved before capacity existed had.
+    pub const FULL: Self = Self {
+        unlocked_rows: MAIN_BAG_ROWS,
+        bag_rows: 0,
+    };
+
+    /// Open main-bag rows, capped at [`MAIN_BAG_ROWS`].
+    pub fn rows(&self) -> usize {
+        (self.unlocked_rows + self.bag_rows).min(MAIN_BAG_ROWS)
+    }
+}
+
+impl Default for BagCapacity {
+    fn default() -> Self {
+        Self {
+            unlocked_rows: STARTING_MAIN_ROWS,
+            bag_rows: 0,
+        }
+    }
+}
+
 /// Player inventory component.
+///
+/// Mutate slots through the methods (or [`Inventory::slot_mut`]) rather than
+/// the bag vectors so the change is reported to the UI.
+///
+/// Only the first [`Inventory::slot_count`] main-bag slots are open; the
+/// rest stay empty until rows are unlocked (see [`BagCapacity`]).
 #[derive(Component, Debug)]
 pub struct Inventory {
     pub main_bag: Vec<Option<InventorySlot>>,
     pub material_bag: Vec<Option<InventorySlot>>,
-    pub max_slots_base: usize,
-    pub max_slots_bonus: usize,
+    capacity: BagCapacity,
+    changes: SlotChanges<BagSlot>,
 }
 
 impl Inventory {
     pub fn new() -> Self {
         Self {
-            main_bag: vec![None; 40],
+            main_bag: vec![None; BAG_COLUMNS * MAIN_BAG_ROWS],
             material_bag: vec![None; 40],
-            max_slots_base: 40,
-            max_slots_bonus: 0,
+            capacity: BagCapacity::default(),
+            changes: SlotChanges::default(),
+        }
+    }
+
+    pub fn slot(&self, slot: BagSlot) -> Option<&InventorySlot> {
+        match slot {
+            BagSlot::Main(idx) => self.main_bag.get(idx),
+            BagSlot::Material(idx) => self.material_bag.get(idx),
+        }
+        .and_then(|s| s.as_ref())
+    }
+
+    /// Mutable access to a bag slot; marks it changed. None for slots out of
+    /// range or in a locked row.
+    pub fn slot_mut(&mut self, slot: BagSlot) -> Option<&mut Option<InventorySlot>> {
+        if !self.is_unlocked(slot) {
+            return None;
         }
+        let entry = match slot {
+            BagSlot::Main(idx) => self.main_bag.get_mut(idx),
+            BagSlot::Material(idx) => self.material_bag.get_mut(idx),
+        }?;
+        self.changes.mark(slot);
+        Some(entry)
     }
 
-    pub fn total_slots(&self) -> usize {
-        self.max_slots_base + self.max_slots_bonus
+    pub fn capacity(&self) -> BagCapacity {
+        self.capacity
+    }
+
+    /// Open main-bag slots: the first `slot_count` of `main_bag`.
+    pub fn slot_count(&self) -> usize {
+        (self.capacity.rows() * BAG_COLUMNS).min(self.main_bag.len())
+    }
+
+    /// Whether items can go in `slot`.
+    pub fn is_unlocked(&self, slot: BagSlot) -> bool {
+        match slot {
+            BagSlot::Main(idx) => idx < self.slot_count(),
+            BagSlot::Material(idx) => idx < self.material_bag.len(),
+        }
+    }
+
+    /// Permanently open `rows` more main-bag rows. Returns false (changing
+    /// nothing) if every row is already unlocked for good.
+    pub fn unlock_rows(&mut self, rows: usize) -> bool {
+        if rows == 0 || self.capacity.unlocked_rows >= MAIN_BAG_ROWS {
+            return false;
+        }
+        let unlocked_rows = (self.capacity.unlocked_rows + rows).min(MAIN_BAG_ROWS);
+        self.set_capacity(BagCapacity {
+            unlocked_rows,
+            ..self.capacity
+        });
+        true
+    }
+
+    /// Whether the worn bag's rows can become `rows` without locking a slot
+    /// that holds items.
+    pub fn can_set_bag_rows(&self, rows: usize) -> bool {
+        let open = BagCapacity {
+            bag_rows: rows,
+            ..self.capacity
+        }
+        .rows()
+            * BAG_COLUMNS;
+        self.main_bag
+            .iter()
+            .take(self.slot_count())
+            .skip(open)
+            .all(|s| s.is_none())
+    }
+
+    /// Set the rows the worn bag adds. Refuses (returns false, changing
+    /// nothing) when that would lock slots holding items.
+    pub fn set_bag_rows(&mut self, rows: usize) -> bool {
+        if !self.can_set_bag_rows(rows) {
+            return false;
+        }
+        self.set_capacity(BagCapacity {
+            bag_rows: rows,
+            ..self.capacity
+        });
+        true
+    }
+
+    /// Put back a saved capacity as is, e.g. when restoring the player.
+    pub fn restore_capacity(&mut self, capacity: BagCapacity) {
+        self.set_capacity(capacity);
+    }
+
+    fn set_capacity(&mut self, capacity: BagCapacity) {
+        if capacity != self.capacity {
+            self.capacity = capacity;
+            // Rows changed lock state: redraw every slot.
+            self.changes.mark_all();
+        }
+    }
+
+    /// Report every slot as changed (bulk operations such as sorting).
+    pub fn mark_all_changed(&mut self) {
+        self.changes.mark_all();
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Slots changed since the last call.
+    pub fn take_changes(&mut self) -> SlotChanges<BagSlot> {
+        std::mem::take(&mut self.changes)
     }
 
     /// Try to add an item to the specified bag.
@@ -54,19 +246,15 @@ impl Inventory {
         max_stack: u16,
         target: BagTarget,
     ) -> u16 {
-        let bag = match target {
-            BagTarget::Material => &mut self.material_bag,
-            BagTarget::Main => &mut self.main_bag,
+        let (primary, overflow) = match target {
+            BagTarget::Material => (BagTarget::Material, BagTarget::Main),
+            BagTarget::Main => (BagTarget::Main, BagTarget::Material),
         };
-        let mut remaining = Self::try_stack_into(bag, item_id, count, max_stack);
+        let mut remaining = self.try_stack_into(primary, item_id, count, max_stack);
 
         // Overflow into the other bag if primary is full
         if remaining > 0 {
-            let overflow_bag = match target {
-                BagTarget::Material => &mut self.main_bag,
-                BagTarget::Main => &mut self.material_bag,
-            };
-            remaining = Self::try_stack_into(overflow_bag, item_id, remaining, max_stack);
+            remaining = self.try_stack_into(overflow, item_id, remaining, max_stack);
         }
 
         remaining
@@ -74,15 +262,21 @@ impl Inventory {
 
     /// Stack items into a specific bag. Returns remainder.
     fn try_stack_into(
-        bag: &mut [Option<InventorySlot>],
+        &mut self,
+        target: BagTarget,
         item_id: &str,
         count: u16,
         max_stack: u16,
     ) -> u16 {
+        let slot_count = self.slot_count();
+        let (bag, slot_ref): (_, fn(usize) -> BagSlot) = match target {
+            BagTarget::Material => (&mut self.material_bag[..], BagSlot::Material),
+            BagTarget::Main => (&mut self.main_bag[..slot_count], BagSlot::Main),
+        };
         let mut remaining = count;
 
         // First, stack into existing slots
-        for slot in bag.iter_mut() {
+        for (idx, slot) in bag.iter_mut().enumerate() {
             if remaining == 0 {
                 break;
             }
@@ -94,12 +288,13 @@ impl Inventory {
                 let to_add = remaining.min(can_add);
                 s.count += to_add;
                 remaining -= to_add;
+                self.changes.mark(slot_ref(idx));
             }
         }
 
         // Then, create new slots
         if remaining > 0 {
-            for slot in bag.iter_mut() {
+            for (idx, slot) in bag.iter_mut().enumerate() {
                 if remaining == 0 {
                     break;
                 }
@@ -111,6 +306,7 @@ impl Inventory {
                         durability: None,
                     });
                     remaining -= to_add;
+                    self.changes.mark(slot_ref(idx));
                 }
             }
         }
@@ -138,7 +334,17 @@ impl Inventory {
 
         let mut remaining = count;
 
-        for slot in self.main_bag.iter_mut().chain(self.material_bag.iter_mut()) {
+        let main = self
+            .main_bag
+            .iter_mut()
+            .enumerate()
+            .map(|(i, s)| (BagSlot::Main(i), s));
+        let material = self
+            .material_bag
+            .iter_mut()
+            .enumerate()
+            .map(|(i, s)| (BagSlot::Material(i), s));
+        for (slot_ref, slot) in main.chain(material) {
             if remaining == 0 {
                 break;
             }
@@ -149,6 +355,7 @@ impl Inventory {
                 let to_remove = remaining.min(s.count);
                 s.count -= to_remove;
                 remaining -= to_remove;
+                self.changes.mark(slot_ref);
 
                 if s.count == 0 {
                     *slot = None;
@@ -192,12 +399,77 @@ mod tests {
     }
 
     #[test]
-    fn inventory_total_slots_includes_bonus() {
+    fn inventory_starts_with_some_rows_locked() {
         let mut inv = Inventory::new();
-        assert_eq!(inv.total_slots(), 40);
+        assert_eq!(inv.slot_count(), STARTING_MAIN_ROWS * BAG_COLUMNS);
+        assert!(inv.is_unlocked(BagSlot::Main(inv.slot_count() - 1)));
+        assert!(!inv.is_unlocked(BagSlot::Main(inv.slot_count())));
+        assert!(inv.slot_mut(BagSlot::Main(inv.slot_count())).is_none());
+        assert!(inv.is_unlocked(BagSlot::Material(39)));
+
+        // Adding never spills into locked rows.
+        let open = inv.slot_count() as u16;
+        assert_eq!(inv.try_add_item("sword", open + 5, 1, BagTarget::Main), 0);
+        assert_eq!(inv.main_bag.iter().flatten().count(), open as usize);
+        assert_eq!(inv.material_bag.iter().flatten().count(), 5);
+    }
 
-        inv.max_slots_bonus = 10;
-        assert_eq!(inv.total_slots(), 50);
+    #[test]
+    fn unlocking_rows_is_permanent_and_capped() {
+        let mut inv = Inventory::new();
+        assert!(inv.unlock_rows(1));
+        assert_eq!(inv.slot_count(), (STARTING_MAIN_ROWS + 1) * BAG_COLUMNS);
+        assert!(
+            inv.take_changes().all,
+            "lock state change redraws every slot"
+        );
+
+        assert!(inv.unlock_rows(5));
+        assert_eq!(inv.capacity().unlocked_rows, MAIN_BAG_ROWS);
+        assert_eq!(inv.slot_count(), inv.main_bag.len());
+        // Nothing left to unlock: the upgrade isn't used up.
+        assert!(!inv.unlock_rows(1));
+
+        // A worn bag can't push past the full bag either.
+        assert!(inv.set_bag_rows(2));
+        assert_eq!(inv.slot_count(), inv.main_bag.len());
+    }
+
+    #[test]
+    fn bag_rows_only_lock_empty_slots() {
+        let mut inv = Inventory::new();
+        assert!(inv.set_bag_rows(2));
+        assert_eq!(inv.slot_count(), MAIN_BAG_ROWS * BAG_COLUMNS);
+
+        // An item in the bag's last row blocks taking the bag off...
+        let last = BagSlot::Main(MAIN_BAG_ROWS * BAG_COLUMNS - 1);
+        *inv.slot_mut(last).unwrap() = Some(Stack {
+            item_id: "torch".into(),
+            count: 1,
+            durability: None,
+        });
+        assert!(!inv.can_set_bag_rows(0));
+        assert!(!inv.set_bag_rows(1));
+        assert_eq!(inv.capacity().bag_rows, 2);
+
+        // ...until it moves into a row that stays open.
+        let torch = inv.slot_mut(last).unwrap().take();
+        *inv.slot_mut(BagSlot::Main(0)).unwrap() = torch;
+        assert!(inv.set_bag_rows(0));
+        assert_eq!(inv.slot_count(), STARTING_MAIN_ROWS * BAG_COLUMNS);
+        assert_eq!(inv.count_item("torch"), 1);
+
+        // Permanent rows keep slots open under a smaller bag.
+        inv.unlock_rows(1);
+        inv.set_bag_rows(1);
+        *inv.slot_mut(BagSlot::Main(4 * BAG_COLUMNS)).unwrap() = Some(Stack {
+            item_id: "torch".into(),
+            count: 1,
+            durability: None,
+        });
+        assert!(!inv.set_bag_rows(0));
+        inv.unlock_rows(1);
+        assert!(inv.set_bag_rows(0));
     }
 
     #[test]
@@ -430,4 +702,52 @@ mod tests {
         assert!(inv.main_bag[0].is_some());
         assert_eq!(inv.main_bag[0].as_ref().unwrap().item_id, "sword");
     }
+
+    #[test]
+    fn mutations_record_changed_slots() {
+        let mut inv = Inventory::new();
+        assert!(!inv.has_changes());
+
+        inv.try_add_item("dirt", 1500, 999, BagTarget::Material);
+        assert_eq!(
+            inv.take_changes().slots,
+            vec![BagSlot::Material(0), BagSlot::Material(1)]
+        );
+        assert!(!inv.has_changes());
+
+        // Topping up an existing stack reports only that slot.
+        inv.try_add_item("dirt", 10, 999, BagTarget::Material);
+        assert_eq!(inv.take_changes().slots, vec![BagSlot::Material(1)]);
+
+        inv.remove_item("dirt", 1000);
+        assert_eq!(
+            inv.take_changes().slots,
+            vec![BagSlot::Material(0), BagSlot::Material(1)]
+        );
+
+        // A failed removal touches nothing.
+        inv.remove_item("stone", 1);
+        assert!(!inv.has_changes());
+
+        *inv.slot_mut(BagSlot::Main(3)).unwrap() = Some(Stack {
+            item_id: "torch".into(),
+            count: 1,
+            durability: None,
+        });
+        assert_eq!(inv.slot(BagSlot::Main(3)).unwrap().item_id, "torch");
+        assert_eq!(inv.take_changes().slots, vec![BagSlot::Main(3)]);
+        assert!(inv.slot_mut(BagSlot::Main(99)).is_none());
+        assert!(!inv.has_changes());
+    }
+
+    #[test]
+    fn bulk_change_supersedes_slot_list() {
+        let mut inv = Inventory::new();
+        inv.try_add_item("dirt", 1, 999, BagTarget::Main);
+        inv.mark_all_changed();
+        inv.try_add_item("stone", 1, 999, BagTarget::Main);
+        let changes = inv.take_changes();
+        assert!(changes.all);
+        assert!(changes.slots.is_empty());
+    }
 }
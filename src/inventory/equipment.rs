@@ -1,10 +1,23 @@
 use std::collections::HashMap;
 
 use bevy::prelude::*;
+use thiserror::Error;
 
 use super::components::{BagTarget, Inventory};
 use crate::item::EquipmentSlot;
 
+/// Why a bag couldn't go on or come off the Back slot. The message is shown
+/// to the player.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum BagSwapError {
+    #[error("That bag isn't in your inventory")]
+    NotInInventory,
+    #[error("Empty the bag's rows before taking it off")]
+    ItemsInBagRows,
+    #[error("No room in your inventory")]
+    InventoryFull,
+}
+
 /// Player equipment component.
 #[derive(Component, Debug)]
 pub struct Equipment {
@@ -99,6 +112,55 @@ impl Equipment {
         }
         true
     }
+
+    /// Wear `item_id`, a bag adding `rows` main-bag rows, in the Back slot.
+    /// Any bag worn before goes back to the inventory; swapping to a smaller
+    /// bag is refused while the rows it would lose hold items.
+    pub fn equip_bag(
+        &mut self,
+        item_id: &str,
+        rows: usize,
+        inventory: &mut Inventory,
+    ) -> Result<(), BagSwapError> {
+        if inventory.count_item(item_id) == 0 {
+            return Err(BagSwapError::NotInInventory);
+        }
+        let old_rows = inventory.capacity().bag_rows;
+        inventory.remove_item(item_id, 1);
+        if !inventory.set_bag_rows(rows) {
+            inventory.try_add_item(item_id, 1, 1, BagTarget::Main);
+            return Err(BagSwapError::ItemsInBagRows);
+        }
+        if let Some(old_id) = self.get(EquipmentSlot::Back).cloned()
+            && inventory.try_add_item(&old_id, 1, 1, BagTarget::Main) > 0
+        {
+            // Nothing was added, so the old rows are free to restore.
+            inventory.set_bag_rows(old_rows);
+            inventory.try_add_item(item_id, 1, 1, BagTarget::Main);
+            return Err(BagSwapError::InventoryFull);
+        }
+        self.equip(EquipmentSlot::Back, item_id.to_string());
+        Ok(())
+    }
+
+    /// Take off the bag in the Back slot, returning it to the inventory.
+    /// Refused, leaving it worn, while its rows hold items or when there's
+    /// nowhere to put it.
+    pub fn unequip_bag(&mut self, inventory: &mut Inventory) -> Result<(), BagSwapError> {
+        let Some(item_id) = self.get(EquipmentSlot::Back).cloned() else {
+            return Ok(());
+        };
+        let old_rows = inventory.capacity().bag_rows;
+        if !inventory.set_bag_rows(0) {
+            return Err(BagSwapError::ItemsInBagRows);
+        }
+        if inventory.try_add_item(&item_id, 1, 1, BagTarget::Main) > 0 {
+            inventory.set_bag_rows(old_rows);
+            return Err(BagSwapError::InventoryFull);
+        }
+        self.unequip(EquipmentSlot::Back);
+        Ok(())
+    }
 }
 
 impl Default for Equipment {
@@ -110,6 +172,7 @@ impl Default for Equipment {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inventory::{BagSlot, Stack, BAG_COLUMNS, STARTING_MAIN_ROWS};
     use crate::item::EquipmentSlot;
 
     #[test]
@@ -193,4 +256,81 @@ mod tests {
 
         assert!(!equip.unequip_to_inventory(EquipmentSlot::Head, &mut inv));
     }
+
+    fn bag_inventory() -> Inventory {
+        let mut inv = Inventory::new();
+        inv.try_add_item("backpack", 1, 1, BagTarget::Main);
+        inv.try_add_item("satchel", 1, 1, BagTarget::Main);
+        inv
+    }
+
+    #[test]
+    fn wearing_a_bag_opens_its_rows() {
+        let mut equip = Equipment::new();
+        let mut inv = bag_inventory();
+        let base = inv.slot_count();
+
+        assert_eq!(equip.equip_bag("backpack", 2, &mut inv), Ok(()));
+        assert_eq!(equip.get(EquipmentSlot::Back), Some(&"backpack".into()));
+        assert_eq!(inv.count_item("backpack"), 0);
+        assert_eq!(inv.slot_count(), base + 2 * BAG_COLUMNS);
+
+        // Swapping bags returns the old one and applies the new size.
+        assert_eq!(equip.equip_bag("satchel", 1, &mut inv), Ok(()));
+        assert_eq!(inv.count_item("backpack"), 1);
+        assert_eq!(inv.slot_count(), base + BAG_COLUMNS);
+
+        assert_eq!(equip.unequip_bag(&mut inv), Ok(()));
+        assert!(equip.get(EquipmentSlot::Back).is_none());
+        assert_eq!(inv.count_item("satchel"), 1);
+        assert_eq!(inv.slot_count(), base);
+
+        assert_eq!(
+            equip.equip_bag("satchel", 1, &mut Inventory::new()),
+            Err(BagSwapError::NotInInventory)
+        );
+    }
+
+    #[test]
+    fn bag_with_items_in_its_rows_stays_on() {
+        let mut equip = Equipment::new();
+        let mut inv = bag_inventory();
+        equip.equip_bag("backpack", 2, &mut inv).unwrap();
+        let base = STARTING_MAIN_ROWS * BAG_COLUMNS;
+        *inv.slot_mut(BagSlot::Main(base + BAG_COLUMNS)).unwrap() = Some(Stack {
+            item_id: "torch".into(),
+            count: 3,
+            durability: None,
+        });
+
+        assert_eq!(
+            equip.unequip_bag(&mut inv),
+            Err(BagSwapError::ItemsInBagRows)
+        );
+        // A smaller bag would lose the row too.
+        assert_eq!(
+            equip.equip_bag("satchel", 1, &mut inv),
+            Err(BagSwapError::ItemsInBagRows)
+        );
+        assert_eq!(equip.get(EquipmentSlot::Back), Some(&"backpack".into()));
+        assert_eq!(inv.capacity().bag_rows, 2);
+        assert_eq!(inv.count_item("torch"), 3);
+        assert_eq!(inv.count_item("satchel"), 1);
+    }
+
+    #[test]
+    fn bag_stays_on_when_the_inventory_is_full() {
+        let mut equip = Equipment::new();
+        let mut inv = Inventory::new();
+        equip.equip(EquipmentSlot::Back, "backpack".into());
+        let open = inv.slot_count() as u16;
+        inv.try_add_item("stone", 40 * 999, 999, BagTarget::Material);
+        inv.try_add_item("sword", open, 1, BagTarget::Main);
+
+        assert_eq!(
+            equip.unequip_bag(&mut inv),
+            Err(BagSwapError::InventoryFull)
+        );
+        assert_eq!(equip.get(EquipmentSlot::Back), Some(&"backpack".into()));
+    }
 }
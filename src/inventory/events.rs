@@ -0,0 +1,250 @@
+//! UI-facing messages describing which inventory and hotbar slots changed.
+//!
+//! [`Inventory`] and [`Hotbar`] record the slots their mutation methods touch;
+//! [`emit_inventory_changes`] turns those records into messages once per
+//! frame, so slot widgets are rebuilt only when something actually changed.
+
+use bevy::prelude::*;
+
+use super::components::{BagSlot, Inventory};
+use super::hotbar::Hotbar;
+
+/// Bag slots of `entity`'s inventory changed this frame.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct InventoryChanged {
+    pub entity: Entity,
+    pub slots: Vec<BagSlot>,
+}
+
+/// Hotbar slots (by index) of `entity` changed this frame.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct HotbarChanged {
+    pub entity: Entity,
+    pub slots: Vec<usize>,
+}
+
+/// Everything about `entity`'s inventory and hotbar should be redrawn: sent
+/// for new components and bulk operations such as sorting.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct InventoryRefresh {
+    pub entity: Entity,
+}
+
+/// Drain the recorded slot changes into messages. An entity gets at most one
+/// [`InventoryRefresh`] per frame, which supersedes its per-slot messages.
+pub fn emit_inventory_changes(
+    mut inventories: Query<(Entity, &mut Inventory)>,
+    mut hotbars: Query<(Entity, &mut Hotbar)>,
+    mut inventory_changed: MessageWriter<InventoryChanged>,
+    mut hotbar_changed: MessageWriter<HotbarChanged>,
+    mut refresh: MessageWriter<InventoryRefresh>,
+) {
+    let mut refreshed: Vec<Entity> = Vec::new();
+
+    for (entity, mut inventory) in &mut inventories {
+        if inventory.is_added() {
+            refreshed.push(entity);
+        }
+        if !inventory.has_changes() {
+            continue;
+        }
+        // Draining the record is bookkeeping, not a content change.
+        let changes = inventory.bypass_change_detection().take_changes();
+        if refreshed.contains(&entity) {
+            continue;
+        }
+        if changes.all {
+            refreshed.push(entity);
+        } else {
+            inventory_changed.write(InventoryChanged {
+                entity,
+                slots: changes.slots,
+            });
+        }
+    }
+
+    for (entity, mut hotbar) in &mut hotbars {
+        if hotbar.is_added() && !refreshed.contains(&entity) {
+            refreshed.push(entity);
+        }
+        if !hotbar.has_changes() {
+            continue;
+        }
+        let changes = hotbar.bypass_change_detection().take_changes();
+        if refreshed.contains(&entity) {
+            continue;
+        }
+        if changes.all {
+            refreshed.push(entity);
+        } else {
+            hotbar_changed.write(HotbarChanged {
+                entity,
+                slots: changes.slots,
+            });
+        }
+    }
+
+    for entity in refreshed {
+        refresh.write(InventoryRefresh { entity });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::message::Messages;
+
+    use super::*;
+    use crate::inventory::systems::{item_pickup_system, ItemPickupEvent};
+    use crate::inventory::{BagTarget, Hand};
+    use crate::item::{DroppedItem, ItemDef, ItemRegistry, ItemType};
+    use crate::player::Player;
+    use crate::test_helpers::fixtures;
+
+    fn dirt() -> ItemDef {
+        ItemDef {
+            display_name: "Dirt".into(),
+            max_stack: 999,
+            ..fixtures::item_def("dirt", ItemType::Block)
+        }
+    }
+
+    /// App with a player whose spawn refresh has already been drained.
+    fn app_with_player() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(fixtures::test_player_config())
+            .insert_resource(fixtures::test_active_world())
+            .insert_resource(ItemRegistry::from_defs(vec![dirt()]))
+            .add_message::<ItemPickupEvent>()
+            .add_message::<InventoryChanged>()
+            .add_message::<HotbarChanged>()
+            .add_message::<InventoryRefresh>()
+            .add_systems(Update, item_pickup_system)
+            .add_systems(PostUpdate, emit_inventory_changes);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Inventory::new(),
+                Hotbar::new(),
+            ))
+            .id();
+        app.update();
+        assert_eq!(
+            drain::<InventoryRefresh>(&mut app),
+            vec![InventoryRefresh { entity: player }]
+        );
+        (app, player)
+    }
+
+    fn drain<M: Message>(app: &mut App) -> Vec<M> {
+        app.world_mut()
+            .resource_mut::<Messages<M>>()
+            .drain()
+            .collect()
+    }
+
+    fn assert_quiet(app: &mut App) {
+        assert!(drain::<InventoryChanged>(app).is_empty());
+        assert!(drain::<HotbarChanged>(app).is_empty());
+        assert!(drain::<InventoryRefresh>(app).is_empty());
+    }
+
+    #[test]
+    fn no_messages_without_mutation() {
+        let (mut app, _) = app_with_player();
+        app.update();
+        app.update();
+        assert_quiet(&mut app);
+    }
+
+    #[test]
+    fn pickup_reports_filled_slot() {
+        let (mut app, player) = app_with_player();
+        app.world_mut().spawn((
+            DroppedItem {
+                item_id: "dirt".into(),
+                count: 5,
+                lifetime: Timer::from_seconds(300.0, TimerMode::Once),
+            },
+            Transform::default(),
+        ));
+        app.update();
+
+        assert_eq!(
+            drain::<InventoryChanged>(&mut app),
+            vec![InventoryChanged {
+                entity: player,
+                slots: vec![BagSlot::Material(0)],
+            }]
+        );
+        assert!(drain::<HotbarChanged>(&mut app).is_empty());
+        app.update();
+        assert_quiet(&mut app);
+    }
+
+    #[test]
+    fn consuming_and_assigning_report_their_slots() {
+        let (mut app, player) = app_with_player();
+        {
+            let mut inventory = app.world_mut().get_mut::<Inventory>(player).unwrap();
+            inventory.try_add_item("dirt", 20, 999, BagTarget::Main);
+        }
+        app.update();
+        drain::<InventoryChanged>(&mut app);
+
+        // Crafting consumes ingredients through `remove_item`.
+        app.world_mut()
+            .get_mut::<Inventory>(player)
+            .unwrap()
+            .remove_item("dirt", 3);
+        app.world_mut().get_mut::<Hotbar>(player).unwrap().assign(
+            2,
+            Hand::Left,
+            "dirt".into(),
+            None,
+        );
+        app.update();
+
+        assert_eq!(
+            drain::<InventoryChanged>(&mut app),
+            vec![InventoryChanged {
+                entity: player,
+                slots: vec![BagSlot::Main(0)],
+            }]
+        );
+        assert_eq!(
+            drain::<HotbarChanged>(&mut app),
+            vec![HotbarChanged {
+                entity: player,
+                slots: vec![2],
+            }]
+        );
+    }
+
+    #[test]
+    fn bulk_change_sends_single_refresh() {
+        let (mut app, player) = app_with_player();
+        {
+            let mut inventory = app.world_mut().get_mut::<Inventory>(player).unwrap();
+            inventory.try_add_item("dirt", 1, 999, BagTarget::Main);
+            inventory.mark_all_changed();
+        }
+        app.world_mut().get_mut::<Hotbar>(player).unwrap().assign(
+            0,
+            Hand::Right,
+            "dirt".into(),
+            None,
+        );
+        app.update();
+
+        assert_eq!(
+            drain::<InventoryRefresh>(&mut app),
+            vec![InventoryRefresh { entity: player }]
+        );
+        // The refresh covers the hotbar too.
+        assert!(drain::<InventoryChanged>(&mut app).is_empty());
+        assert!(drain::<HotbarChanged>(&mut app).is_empty());
+    }
+}
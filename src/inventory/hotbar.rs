@@ -1,5 +1,24 @@
 use bevy::prelude::*;
 
+use super::components::SlotChanges;
+
+/// Which hand in a hotbar slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    /// Mouse button that uses the item held in this hand.
+    pub fn button(self) -> MouseButton {
+        match self {
+            Hand::Left => MouseButton::Left,
+            Hand::Right => MouseButton::Right,
+        }
+    }
+}
+
 /// A single hotbar slot with left/right hand item references.
 /// Stores only item_id — count is resolved from Inventory at runtime.
 #[derive(Clone, Debug, Default)]
@@ -29,12 +48,15 @@ impl HotbarSlot {
 }
 
 /// Player hotbar component (Starbound-style).
+///
+/// Change slot contents through [`Hotbar::slot_mut`] so the UI hears about it.
 #[derive(Component, Debug)]
 pub struct Hotbar {
     pub slots: [HotbarSlot; 6],
     pub active_slot: usize,
     pub active_set: usize,
     pub locked: bool,
+    changes: SlotChanges<usize>,
 }
 
 impl Hotbar {
@@ -44,14 +66,58 @@ impl Hotbar {
             active_slot: 0,
             active_set: 0,
             locked: false,
+            changes: SlotChanges::default(),
         }
     }
 
+    /// Mutable access to a slot's contents; marks it changed.
+    pub fn slot_mut(&mut self, index: usize) -> &mut HotbarSlot {
+        self.changes.mark(index);
+        &mut self.slots[index]
+    }
+
+    /// Put `item_id` (with its starting durability) into one hand of a slot.
+    pub fn assign(&mut self, index: usize, hand: Hand, item_id: String, durability: Option<u32>) {
+        let slot = self.slot_mut(index);
+        match hand {
+            Hand::Left => slot.left_hand = Some(item_id),
+            Hand::Right => slot.right_hand = Some(item_id),
+        }
+        slot.set_durability(hand == Hand::Left, durability);
+    }
+
+    /// Swap what the two hands of a slot hold, durability included.
+    pub fn swap_hands(&mut self, index: usize) {
+        let slot = self.slot_mut(index);
+        std::mem::swap(&mut slot.left_hand, &mut slot.right_hand);
+        std::mem::swap(&mut slot.left_durability, &mut slot.right_durability);
+    }
+
+    /// Report every slot as changed (bulk operations).
+    pub fn mark_all_changed(&mut self) {
+        self.changes.mark_all();
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Slot indices changed since the last call.
+    pub fn take_changes(&mut self) -> SlotChanges<usize> {
+        std::mem::take(&mut self.changes)
+    }
+
     pub fn select_slot(&mut self, slot: usize) {
         self.active_slot = slot % 6;
     }
 
-    /// Toggle between slot sets (X key).
+    /// Move the active slot by `steps`, wrapping around at either end.
+    pub fn cycle_slot(&mut self, steps: i32) {
+        let len = self.slots.len() as i32;
+        self.active_slot = (self.active_slot as i32 + steps).rem_euclid(len) as usize;
+    }
+
+    /// Toggle between slot sets.
     pub fn toggle_set(&mut self) {
         self.active_set = (self.active_set + 1) % 2;
     }
@@ -122,4 +188,41 @@ mod tests {
         assert_eq!(hotbar.get_item_for_hand(true), Some("sword"));
         assert_eq!(hotbar.get_item_for_hand(false), Some("shield"));
     }
+
+    #[test]
+    fn slot_mutations_record_changes() {
+        let mut hotbar = Hotbar::new();
+        hotbar.select_slot(2);
+        assert!(!hotbar.has_changes(), "selection is not a content change");
+
+        hotbar.assign(4, Hand::Right, "torch".into(), None);
+        hotbar.slot_mut(1).set_durability(true, Some(3));
+        hotbar.assign(4, Hand::Left, "sword".into(), Some(10));
+        assert_eq!(hotbar.slots[4].right_hand.as_deref(), Some("torch"));
+        assert_eq!(hotbar.slots[4].durability(true), Some(10));
+        assert_eq!(hotbar.take_changes().slots, vec![4, 1]);
+        assert!(!hotbar.has_changes());
+    }
+
+    #[test]
+    fn swap_hands_swaps_items_and_durability() {
+        let mut hotbar = Hotbar::new();
+        hotbar.assign(2, Hand::Left, "pickaxe".into(), Some(40));
+        hotbar.assign(2, Hand::Right, "torch".into(), None);
+        hotbar.take_changes();
+
+        hotbar.swap_hands(2);
+        let slot = &hotbar.slots[2];
+        assert_eq!(slot.left_hand.as_deref(), Some("torch"));
+        assert_eq!(slot.right_hand.as_deref(), Some("pickaxe"));
+        assert_eq!(slot.durability(true), None);
+        assert_eq!(slot.durability(false), Some(40));
+        assert_eq!(hotbar.take_changes().slots, vec![2]);
+
+        // An empty hand swaps too.
+        hotbar.slot_mut(2).left_hand = None;
+        hotbar.swap_hands(2);
+        assert_eq!(hotbar.slots[2].left_hand.as_deref(), Some("pickaxe"));
+        assert_eq!(hotbar.slots[2].right_hand, None);
+    }
 }
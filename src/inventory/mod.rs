@@ -1,11 +1,13 @@
 pub mod components;
 pub mod equipment;
+pub mod events;
 pub mod hotbar;
 pub mod plugin;
 pub mod systems;
 
 pub use components::*;
 pub use equipment::*;
+pub use events::*;
 pub use hotbar::*;
 pub use plugin::InventoryPlugin;
 pub use systems::*;
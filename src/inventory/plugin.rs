@@ -1,22 +1,37 @@
 use bevy::prelude::*;
 
+use super::events::{emit_inventory_changes, HotbarChanged, InventoryChanged, InventoryRefresh};
 use super::systems::{
-    hotbar_input_system, item_magnetism_system, item_pickup_system, ItemPickupEvent,
+    hotbar_input_system, hotbar_scroll_system, item_magnetism_system, item_pickup_system,
+    swap_hands_system, HotbarScroll, ItemPickupEvent, SwapHandsKeys,
 };
 use crate::registry::AppState;
 use crate::sets::GameSet;
+use crate::world::spatial_index::refresh_spatial_index;
 
 pub struct InventoryPlugin;
 
 impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<ItemPickupEvent>()
-            .add_systems(Update, hotbar_input_system.in_set(GameSet::Input))
+            .add_message::<InventoryChanged>()
+            .add_message::<HotbarChanged>()
+            .add_message::<InventoryRefresh>()
+            .init_resource::<HotbarScroll>()
+            .init_resource::<SwapHandsKeys>()
+            .add_systems(
+                Update,
+                (hotbar_input_system, hotbar_scroll_system, swap_hands_system)
+                    .in_set(GameSet::Input),
+            )
             .add_systems(
                 Update,
                 (item_magnetism_system, item_pickup_system)
                     .chain()
+                    .after(refresh_spatial_index)
                     .run_if(in_state(AppState::InGame)),
-            );
+            )
+            // After all gameplay mutations, before the slot UI reads them.
+            .add_systems(PostUpdate, emit_inventory_changes);
     }
 }
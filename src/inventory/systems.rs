@@ -1,12 +1,17 @@
+use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
 
 use super::components::{BagTarget, Inventory};
 use super::hotbar::Hotbar;
+use crate::interaction::layer_target::LayerModifierKeys;
 use crate::item::ItemRegistry;
-use crate::item::{DroppedItem, ItemType};
+use crate::item::{DroppedItem, ItemType, PickupDelay};
 use crate::physics::{Gravity, TileCollider, Velocity};
 use crate::player::Player;
 use crate::registry::player::PlayerConfig;
+use crate::registry::world::ActiveWorld;
+use crate::ui::input_capture::InputCapture;
+use crate::world::spatial_index::{wrapped_offset, SpatialIndex};
 
 /// Calculate magnet strength based on distance (pure function for testing).
 pub fn calculate_magnet_strength(distance: f32, config: &PlayerConfig) -> f32 {
@@ -30,13 +35,15 @@ pub struct ItemPickupEvent {
     pub count: u16,
 }
 
-/// System that detects and triggers item pickup.
+/// System that detects and triggers item pickup. Distances are measured the
+/// short way across the world seam, like the magnet's pull.
 #[allow(clippy::too_many_arguments)]
 pub fn item_pickup_system(
     config: Res<PlayerConfig>,
+    world: Res<ActiveWorld>,
     mut player_query: Query<(Entity, &Transform, &mut Inventory), With<Player>>,
     item_registry: Res<ItemRegistry>,
-    mut item_query: Query<(Entity, &Transform, &mut DroppedItem)>,
+    mut item_query: Query<(Entity, &Transform, &mut DroppedItem), Without<PickupDelay>>,
     mut commands: Commands,
     mut pickup_events: MessageWriter<ItemPickupEvent>,
 ) {
@@ -47,7 +54,7 @@ pub fn item_pickup_system(
 
     for (item_entity, item_tf, mut item) in &mut item_query {
         let item_pos = item_tf.translation.truncate();
-        let distance = player_pos.distance(item_pos);
+        let distance = wrapped_offset(item_pos, player_pos, &world).length();
 
         if should_pickup(distance, &config) {
             // Look up max_stack; skip unknown items instead of panicking
@@ -87,13 +94,26 @@ pub fn item_pickup_system(
 /// System that pulls dropped items toward the player when within magnet radius.
 /// Items within range fly directly toward the player, ignoring terrain collisions
 /// (TileCollider and Gravity are removed so physics doesn't fight the pull).
+/// Candidates come from the [`SpatialIndex`], so only drops near the player
+/// are checked.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn item_magnetism_system(
     config: Res<PlayerConfig>,
+    world: Res<ActiveWorld>,
+    index: Res<SpatialIndex>,
     time: Res<Time>,
     player_query: Query<&Transform, With<Player>>,
     mut item_query: Query<
-        (Entity, &mut Transform, &mut Velocity, Has<TileCollider>),
-        (With<DroppedItem>, Without<Player>),
+        (&mut Transform, &mut Velocity, Has<TileCollider>),
+        (With<DroppedItem>, Without<Player>, Without<PickupDelay>),
+    >,
+    magnetised: Query<
+        Entity,
+        (
+            With<DroppedItem>,
+            Without<TileCollider>,
+            Without<PickupDelay>,
+        ),
     >,
     mut commands: Commands,
 ) {
@@ -103,31 +123,41 @@ pub fn item_magnetism_system(
     let player_pos = player_tf.translation.truncate();
     let delta = time.delta_secs();
 
-    for (entity, mut item_tf, mut vel, has_collider) in &mut item_query {
-        let item_pos = item_tf.translation.truncate();
-        let distance = player_pos.distance(item_pos);
-
-        if distance < config.magnet_radius && distance > 0.0 {
-            // Strip physics so the item flies freely through terrain
-            if has_collider {
-                commands
-                    .entity(entity)
-                    .remove::<TileCollider>()
-                    .remove::<Gravity>();
-            }
+    let mut pulled = Vec::new();
+    for (entity, _) in index.query_radius(player_pos, config.magnet_radius, &world) {
+        let Ok((mut item_tf, mut vel, has_collider)) = item_query.get_mut(entity) else {
+            continue;
+        };
+        let to_player = wrapped_offset(item_tf.translation.truncate(), player_pos, &world);
+        let distance = to_player.length();
+        if distance >= config.magnet_radius || distance <= 0.0 {
+            continue;
+        }
+        pulled.push(entity);
 
-            // Move directly toward the player
-            let direction = (player_pos - item_pos).normalize();
-            let speed = config.magnet_strength * (1.0 - distance / config.magnet_radius) + 30.0;
+        // Strip physics so the item flies freely through terrain
+        if has_collider {
+            commands
+                .entity(entity)
+                .remove::<TileCollider>()
+                .remove::<Gravity>();
+        }
 
-            item_tf.translation.x += direction.x * speed * delta;
-            item_tf.translation.y += direction.y * speed * delta;
+        // Move directly toward the player
+        let direction = to_player / distance;
+        let speed = config.magnet_strength * (1.0 - distance / config.magnet_radius) + 30.0;
 
-            // Zero out residual velocity so physics doesn't interfere
-            vel.x = 0.0;
-            vel.y = 0.0;
-        } else if !has_collider {
-            // Left magnet radius — restore physics so item falls back down
+        item_tf.translation.x += direction.x * speed * delta;
+        item_tf.translation.y += direction.y * speed * delta;
+
+        // Zero out residual velocity so physics doesn't interfere
+        vel.x = 0.0;
+        vel.y = 0.0;
+    }
+
+    // Left magnet radius — restore physics so item falls back down
+    for entity in &magnetised {
+        if !pulled.contains(&entity) {
             commands.entity(entity).insert((
                 TileCollider {
                     width: 4.0,
@@ -149,6 +179,46 @@ const HOTBAR_KEYS: [KeyCode; 6] = [
     KeyCode::Digit6,
 ];
 
+/// Mouse-wheel binding for the hotbar.
+///
+/// The wheel also zooms the camera: while any `zoom_modifier` key is held (or
+/// when hotbar scrolling is disabled) it zooms, otherwise it cycles slots.
+#[derive(Resource, Debug, Clone)]
+pub struct HotbarScroll {
+    pub enabled: bool,
+    pub zoom_modifier: Vec<KeyCode>,
+}
+
+impl Default for HotbarScroll {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            zoom_modifier: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+        }
+    }
+}
+
+impl HotbarScroll {
+    /// Whether the wheel should zoom the camera this frame.
+    pub fn wheel_zooms(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        !self.enabled || keyboard.any_pressed(self.zoom_modifier.iter().copied())
+    }
+}
+
+/// Key binding for swapping the hands of the active hotbar slot.
+#[derive(Resource, Debug, Clone)]
+pub struct SwapHandsKeys {
+    pub swap: KeyCode,
+}
+
+impl Default for SwapHandsKeys {
+    fn default() -> Self {
+        Self {
+            swap: KeyCode::KeyX,
+        }
+    }
+}
+
 /// System that handles hotbar slot selection via number keys 1-6.
 pub fn hotbar_input_system(
     keyboard: Res<ButtonInput<KeyCode>>,
@@ -165,9 +235,50 @@ pub fn hotbar_input_system(
     }
 }
 
+/// System that swaps the left and right hand items of the active slot.
+pub fn swap_hands_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keys: Res<SwapHandsKeys>,
+    capture: Res<InputCapture>,
+    mut hotbar_query: Query<&mut Hotbar, With<Player>>,
+) {
+    if capture.keyboard || !keyboard.just_pressed(keys.swap) {
+        return;
+    }
+    let Ok(mut hotbar) = hotbar_query.single_mut() else {
+        return;
+    };
+    let active = hotbar.active_slot;
+    hotbar.swap_hands(active);
+}
+
+/// System that cycles the active hotbar slot with the mouse wheel: scrolling
+/// up advances, scrolling down goes back. With the layer modifier held the
+/// wheel switches the placement layer instead.
+pub fn hotbar_scroll_system(
+    mut scroll_events: MessageReader<MouseWheel>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    scroll: Res<HotbarScroll>,
+    capture: Res<InputCapture>,
+    layer_keys: Option<Res<LayerModifierKeys>>,
+    mut hotbar_query: Query<&mut Hotbar, With<Player>>,
+) {
+    let steps: i32 = scroll_events.read().map(|e| e.y.signum() as i32).sum();
+    let captured = capture.pointer || capture.keyboard;
+    let switches_layer = layer_keys.is_some_and(|keys| keys.held(&keyboard));
+    if steps == 0 || captured || switches_layer || scroll.wheel_zooms(&keyboard) {
+        return;
+    }
+    let Ok(mut hotbar) = hotbar_query.single_mut() else {
+        return;
+    };
+    hotbar.cycle_slot(steps);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inventory::Hand;
     use crate::test_helpers::fixtures;
 
     #[test]
@@ -191,6 +302,79 @@ mod tests {
         assert_eq!(strength, 0.0);
     }
 
+    #[test]
+    fn magnet_pulls_and_picks_up_drops_across_the_world_seam() {
+        use crate::world::spatial_index::{refresh_spatial_index, TrackedInIndex};
+
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_millis(20),
+        ))
+        .insert_resource(ItemRegistry::from_defs(vec![fixtures::item_def(
+            "dirt",
+            ItemType::Block,
+        )]))
+        .init_resource::<SpatialIndex>()
+        .add_message::<ItemPickupEvent>()
+        .add_systems(
+            Update,
+            (
+                refresh_spatial_index,
+                item_magnetism_system,
+                item_pickup_system,
+            )
+                .chain(),
+        );
+        let world_px = app.world().resource::<ActiveWorld>().world_pixel_width();
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(10.0, 500.0, 0.0),
+                Inventory::new(),
+            ))
+            .id();
+        let spawn_drop = |app: &mut App, x: f32| {
+            app.world_mut()
+                .spawn((
+                    DroppedItem {
+                        item_id: "dirt".into(),
+                        count: 1,
+                        lifetime: Timer::from_seconds(300.0, TimerMode::Once),
+                    },
+                    TrackedInIndex::default(),
+                    Transform::from_xyz(x, 500.0, 0.0),
+                    Velocity::default(),
+                    TileCollider {
+                        width: 4.0,
+                        height: 4.0,
+                    },
+                ))
+                .id()
+        };
+        let seam = spawn_drop(&mut app, world_px - 20.0);
+        let far = spawn_drop(&mut app, 2000.0);
+
+        app.update();
+        app.update();
+        let x = app.world().get::<Transform>(seam).unwrap().translation.x;
+        assert!(x > world_px - 20.0, "pulled the short way, over the seam");
+        assert!(!app.world().entity(seam).contains::<TileCollider>());
+        assert!(app.world().entity(far).contains::<TileCollider>());
+        assert_eq!(
+            app.world().get::<Transform>(far).unwrap().translation.x,
+            2000.0
+        );
+
+        // Pulled past the seam, the drop is picked up there too.
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get_entity(seam).is_err(), "never picked up");
+        let inventory = app.world().get::<Inventory>(player).unwrap();
+        assert_eq!(inventory.count_item("dirt"), 1);
+    }
+
     #[test]
     fn should_pickup_within_radius() {
         let config = fixtures::test_player_config();
@@ -199,4 +383,102 @@ mod tests {
         assert!(should_pickup(19.9, &config)); // Just under 20.0
         assert!(!should_pickup(25.0, &config));
     }
+
+    fn scroll_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<MouseWheel>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<HotbarScroll>()
+            .init_resource::<InputCapture>()
+            .add_systems(Update, hotbar_scroll_system);
+        app.world_mut().spawn((Player, Hotbar::new()));
+        app
+    }
+
+    fn scroll(app: &mut App, y: f32) -> usize {
+        app.world_mut().write_message(MouseWheel {
+            unit: bevy::input::mouse::MouseScrollUnit::Line,
+            x: 0.0,
+            y,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        let mut query = app.world_mut().query::<&Hotbar>();
+        query.single(app.world()).unwrap().active_slot
+    }
+
+    #[test]
+    fn scrolling_cycles_active_slot_with_wraparound() {
+        let mut app = scroll_app();
+        assert_eq!(scroll(&mut app, 1.0), 1);
+        assert_eq!(scroll(&mut app, -1.0), 0);
+        assert_eq!(scroll(&mut app, -1.0), 5);
+        assert_eq!(scroll(&mut app, 1.0), 0);
+    }
+
+    #[test]
+    fn scrolling_with_zoom_modifier_leaves_slot_alone() {
+        let mut app = scroll_app();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        assert_eq!(scroll(&mut app, 1.0), 0);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ControlLeft);
+        app.world_mut().resource_mut::<HotbarScroll>().enabled = false;
+        assert_eq!(scroll(&mut app, 1.0), 0);
+    }
+
+    #[test]
+    fn scrolling_with_layer_modifier_leaves_slot_alone() {
+        let mut app = scroll_app();
+        app.init_resource::<LayerModifierKeys>();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::AltLeft);
+        assert_eq!(scroll(&mut app, 1.0), 0);
+    }
+
+    #[test]
+    fn scrolling_over_a_ui_panel_leaves_slot_alone() {
+        let mut app = scroll_app();
+        app.world_mut().resource_mut::<InputCapture>().pointer = true;
+        assert_eq!(scroll(&mut app, 1.0), 0);
+    }
+
+    #[test]
+    fn swap_key_swaps_the_hands_of_the_active_slot() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<SwapHandsKeys>()
+            .init_resource::<InputCapture>()
+            .add_systems(Update, swap_hands_system);
+        let mut hotbar = Hotbar::new();
+        hotbar.select_slot(1);
+        hotbar.assign(1, Hand::Left, "pickaxe".into(), None);
+        let player = app.world_mut().spawn((Player, hotbar)).id();
+
+        let press = |app: &mut App| {
+            let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keys.release(KeyCode::KeyX);
+            keys.clear();
+            keys.press(KeyCode::KeyX);
+            app.update();
+        };
+        let right_hand = |app: &App| {
+            let hotbar = app.world().get::<Hotbar>(player).unwrap();
+            hotbar.slots[1].right_hand.clone()
+        };
+        press(&mut app);
+        assert_eq!(right_hand(&app).as_deref(), Some("pickaxe"));
+
+        // Typing into a focused text field doesn't swap.
+        app.world_mut().resource_mut::<InputCapture>().keyboard = true;
+        press(&mut app);
+        assert_eq!(right_hand(&app).as_deref(), Some("pickaxe"));
+    }
 }
@@ -1,5 +1,8 @@
 use serde::Deserialize;
 
+use crate::inventory::Hand;
+pub use crate::projectile::ProjectileSpec;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
 pub enum Rarity {
     #[default]
@@ -22,6 +25,50 @@ pub enum ItemType {
     Blueprint,
 }
 
+/// What using an item from a hotbar hand does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ItemAction {
+    /// Damage the targeted block until it breaks.
+    Mine,
+    /// Place the item's tile or object in the foreground.
+    PlaceFg,
+    /// Place the item's tile as a background wall.
+    PlaceBg,
+    /// Use up one item (blueprints, potions and food).
+    Consume,
+    /// Throw one item towards the cursor.
+    Throw,
+    /// Cast a fishing bobber towards the cursor, or reel in the one that is
+    /// out (see `crate::fishing`).
+    Fish,
+    /// Turn the targeted foreground tile into another one, per the item's
+    /// `tile_conversions` (hoes tilling dirt).
+    ModifyTile,
+}
+
+impl ItemAction {
+    /// Mining, placing and modifying tiles repeat while the button is held;
+    /// consuming, throwing and fishing fire once per click.
+    pub fn repeats_while_held(self) -> bool {
+        matches!(
+            self,
+            Self::Mine | Self::PlaceFg | Self::PlaceBg | Self::ModifyTile
+        )
+    }
+
+    /// Hand cooldown (seconds) after a use, unless the item overrides it.
+    pub fn default_cooldown(self) -> f32 {
+        match self {
+            Self::Mine => 0.15,
+            Self::PlaceFg | Self::PlaceBg => 0.0,
+            Self::Consume => 0.5,
+            Self::Throw => 0.4,
+            Self::Fish => 0.3,
+            Self::ModifyTile => 0.25,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum EquipmentSlot {
     Head,
@@ -57,6 +104,37 @@ fn default_max_stack() -> u16 {
     99
 }
 
+fn default_beam_half_angle() -> f32 {
+    25.0
+}
+
+/// Light an equipped item casts forward from its wearer (headlamps).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct WornLight {
+    pub emission: [u8; 3],
+    /// Half the beam's opening angle, in degrees.
+    #[serde(default = "default_beam_half_angle")]
+    pub half_angle: f32,
+}
+
+/// What using up a consumable does to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ConsumeEffect {
+    /// Restore this much health, up to the maximum.
+    Heal(f32),
+    /// Multiply movement speed for `secs` seconds.
+    SpeedBoost { multiplier: f32, secs: f32 },
+    /// Permanently open this many more rows of the main bag.
+    UnlockBagRows(usize),
+}
+
+/// A tile an item turns into another when used on it.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TileConversion {
+    pub from: String,
+    pub to: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ItemDef {
     pub id: String,
@@ -80,6 +158,80 @@ pub struct ItemDef {
     /// If set, using this item unlocks all recipes gated by `Blueprint(item_id)`.
     #[serde(default)]
     pub blueprint_item: Option<String>,
+    /// Explicit use action for both hands; `None` infers it from the item.
+    #[serde(default)]
+    pub action: Option<ItemAction>,
+    /// Hand cooldown after a use (seconds); `None` uses the action's default.
+    #[serde(default)]
+    pub use_cooldown: Option<f32>,
+    /// Cooldown (seconds) shared by every stack of this item after a use,
+    /// whichever slot or hand it is used from.
+    #[serde(default)]
+    pub item_cooldown: Option<f32>,
+    /// How the item flies when thrown; `None` drops it as an item.
+    #[serde(default)]
+    pub projectile: Option<ProjectileSpec>,
+    /// Light cast while the item is equipped.
+    #[serde(default)]
+    pub light: Option<WornLight>,
+    /// Main-bag rows opened while the item is worn in the Back slot.
+    #[serde(default)]
+    pub bag_rows: Option<usize>,
+    /// Effects applied when the item is used up; items with any are
+    /// consumed on use.
+    #[serde(default)]
+    pub effects: Vec<ConsumeEffect>,
+    /// Tiles the item turns into others when used on them. Any entry makes
+    /// the item's action [`ItemAction::ModifyTile`].
+    #[serde(default)]
+    pub tile_conversions: Vec<TileConversion>,
+}
+
+impl ItemDef {
+    /// Action when used from `hand`, or `None` for items with no use of
+    /// their own (resources, weapons).
+    ///
+    /// Tile items place in the foreground from the left hand and as walls
+    /// from the right hand; objects always go in the foreground.
+    pub fn action(&self, hand: Hand) -> Option<ItemAction> {
+        if let Some(action) = self.action {
+            return Some(action);
+        }
+        if !self.tile_conversions.is_empty() {
+            return Some(ItemAction::ModifyTile);
+        }
+        if self.placeable_object.is_some() {
+            return Some(ItemAction::PlaceFg);
+        }
+        if self.placeable.is_some() {
+            return Some(match hand {
+                Hand::Left => ItemAction::PlaceFg,
+                Hand::Right => ItemAction::PlaceBg,
+            });
+        }
+        if !self.effects.is_empty() {
+            return Some(ItemAction::Consume);
+        }
+        match self.item_type {
+            ItemType::Tool => Some(ItemAction::Mine),
+            ItemType::Consumable | ItemType::Blueprint => Some(ItemAction::Consume),
+            _ => None,
+        }
+    }
+
+    /// Hand cooldown (seconds) after using this item for `action`.
+    pub fn cooldown(&self, action: ItemAction) -> f32 {
+        self.use_cooldown
+            .unwrap_or_else(|| action.default_cooldown())
+    }
+
+    /// Tile this item turns tile `from` into, if it modifies that tile.
+    pub fn converted_tile(&self, from: &str) -> Option<&str> {
+        self.tile_conversions
+            .iter()
+            .find(|c| c.from == from)
+            .map(|c| c.to.as_str())
+    }
 }
 
 fn default_drop_min() -> u16 {
@@ -106,22 +258,17 @@ pub struct DropDef {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::fixtures;
 
     #[test]
     fn item_def_has_required_fields() {
         let item = ItemDef {
-            id: "dirt".into(),
             display_name: "Dirt Block".into(),
             description: "A block of dirt".into(),
             max_stack: 999,
-            rarity: Rarity::Common,
-            item_type: ItemType::Block,
             icon: Some("items/dirt.png".into()),
             placeable: Some("dirt".into()),
-            placeable_object: None,
-            equipment_slot: None,
-            stats: None,
-            blueprint_item: None,
+            ..fixtures::item_def("dirt", ItemType::Block)
         };
 
         assert_eq!(item.id, "dirt");
@@ -141,4 +288,70 @@ mod tests {
         assert!(drop.min <= drop.max);
         assert!(drop.chance <= 1.0);
     }
+
+    fn item(item_type: ItemType) -> ItemDef {
+        ItemDef {
+            display_name: "Test".into(),
+            ..fixtures::item_def("test", item_type)
+        }
+    }
+
+    #[test]
+    fn action_resolution_table() {
+        use ItemAction::*;
+
+        let tile = ItemDef {
+            placeable: Some("dirt".into()),
+            ..item(ItemType::Block)
+        };
+        let object = ItemDef {
+            placeable_object: Some("torch".into()),
+            ..item(ItemType::Block)
+        };
+        let thrown = ItemDef {
+            action: Some(Throw),
+            ..item(ItemType::Resource)
+        };
+        let hoe = ItemDef {
+            tile_conversions: vec![TileConversion {
+                from: "dirt".into(),
+                to: "tilled_soil".into(),
+            }],
+            ..item(ItemType::Tool)
+        };
+
+        // (item, left hand, right hand)
+        let cases = [
+            (tile, Some(PlaceFg), Some(PlaceBg)),
+            (object, Some(PlaceFg), Some(PlaceFg)),
+            (item(ItemType::Tool), Some(Mine), Some(Mine)),
+            (item(ItemType::Consumable), Some(Consume), Some(Consume)),
+            (item(ItemType::Blueprint), Some(Consume), Some(Consume)),
+            (thrown, Some(Throw), Some(Throw)),
+            (hoe, Some(ModifyTile), Some(ModifyTile)),
+            (item(ItemType::Weapon), None, None),
+            (item(ItemType::Resource), None, None),
+            (item(ItemType::Material), None, None),
+        ];
+        for (def, left, right) in cases {
+            assert_eq!(def.action(Hand::Left), left, "{:?} left", def.item_type);
+            assert_eq!(def.action(Hand::Right), right, "{:?} right", def.item_type);
+        }
+    }
+
+    #[test]
+    fn cooldown_uses_override_or_action_default() {
+        let tool = item(ItemType::Tool);
+        assert_eq!(
+            tool.cooldown(ItemAction::Mine),
+            ItemAction::Mine.default_cooldown()
+        );
+
+        let slow = ItemDef {
+            use_cooldown: Some(1.5),
+            projectile: None,
+            ..item(ItemType::Tool)
+        };
+        assert_eq!(slow.cooldown(ItemAction::Mine), 1.5);
+    }
 }
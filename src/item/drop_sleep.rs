@@ -0,0 +1,273 @@
+//! Sleeping for dropped items at rest.
+//!
+//! Without it every drop in a mined-out cavern keeps running gravity,
+//! collision and friction each frame. A drop that stays grounded, slower
+//! than [`SLEEP_SPEED`], on the same supporting tile for [`SLEEP_FRAMES`]
+//! frames gets [`Sleeping`], which the physics systems skip. It wakes when a
+//! foreground tile within [`WAKE_RADIUS`] tiles changes ([`TileChanged`]) or
+//! the player comes within magnet range, or when another stack merges into
+//! it (see [`merge_dropped_items`](super::merge_dropped_items)).
+
+use bevy::prelude::*;
+
+use super::DroppedItem;
+use crate::physics::{Grounded, Sleeping, TileCollider, Velocity};
+use crate::player::Player;
+use crate::registry::player::PlayerConfig;
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{world_to_tile, TileChanged};
+
+/// Speed (px/s) under which a grounded drop counts as still.
+pub const SLEEP_SPEED: f32 = 2.0;
+/// Consecutive still frames before a drop falls asleep.
+pub const SLEEP_FRAMES: u32 = 30;
+/// Chebyshev distance (tiles) within which a tile change wakes a drop.
+pub const WAKE_RADIUS: i32 = 2;
+
+/// How long a dropped item has been at rest, and on which tile.
+#[derive(Component, Debug, Default)]
+pub struct DropRest {
+    still_frames: u32,
+    /// Tile the drop is standing on, `None` while airborne.
+    support: Option<(i32, i32)>,
+}
+
+impl DropRest {
+    /// Record one physics frame. Returns true once the drop has been still
+    /// on the same support for [`SLEEP_FRAMES`] frames.
+    pub fn observe(&mut self, speed: f32, support: Option<(i32, i32)>) -> bool {
+        if speed >= SLEEP_SPEED || support.is_none() || support != self.support {
+            self.still_frames = 0;
+            self.support = support;
+            return false;
+        }
+        self.still_frames += 1;
+        self.still_frames >= SLEEP_FRAMES
+    }
+
+    /// Start counting again from scratch.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Whether a change at tile `changed` is close enough to wake a drop on
+/// tile `tile`. Wrap-aware on X.
+pub fn within_wake_radius(changed: (i32, i32), tile: (i32, i32), config: &ActiveWorld) -> bool {
+    let dx = config
+        .wrap_tile_x(changed.0)
+        .abs_diff(config.wrap_tile_x(tile.0)) as i32;
+    let dx = if config.wrap_x {
+        dx.min(config.width_tiles - dx)
+    } else {
+        dx
+    };
+    dx <= WAKE_RADIUS && (changed.1 - tile.1).abs() <= WAKE_RADIUS
+}
+
+/// Put drops that have come to rest to sleep.
+#[allow(clippy::type_complexity)]
+pub fn settle_dropped_items(
+    mut commands: Commands,
+    config: Res<ActiveWorld>,
+    mut drops: Query<
+        (
+            Entity,
+            &Transform,
+            &Velocity,
+            Option<&TileCollider>,
+            Option<&Grounded>,
+            Option<&mut DropRest>,
+        ),
+        (With<DroppedItem>, Without<Sleeping>),
+    >,
+) {
+    for (entity, tf, vel, collider, grounded, rest) in &mut drops {
+        let Some(mut rest) = rest else {
+            commands.entity(entity).insert(DropRest::default());
+            continue;
+        };
+        // Magnetised drops have no collider and are never at rest.
+        let support = collider.filter(|_| grounded.is_some_and(|g| g.0)).map(|c| {
+            let foot_y = tf.translation.y - c.height / 2.0 - 0.5;
+            world_to_tile(tf.translation.x, foot_y, config.tile_size)
+        });
+        let speed = Vec2::new(vel.x, vel.y).length();
+        if rest.observe(speed, support) {
+            commands.entity(entity).insert(Sleeping);
+        }
+    }
+}
+
+/// Wake sleeping drops near a tile change or within the player's magnet
+/// range.
+#[allow(clippy::type_complexity)]
+pub fn wake_dropped_items(
+    mut commands: Commands,
+    config: Res<ActiveWorld>,
+    player_config: Option<Res<PlayerConfig>>,
+    mut tile_changes: MessageReader<TileChanged>,
+    players: Query<&Transform, With<Player>>,
+    mut sleepers: Query<(Entity, &Transform, &mut DropRest), (With<DroppedItem>, With<Sleeping>)>,
+) {
+    let changes: Vec<(i32, i32)> = tile_changes.read().map(|c| (c.tile_x, c.tile_y)).collect();
+    let magnet_radius = player_config.map_or(0.0, |c| c.magnet_radius);
+    let player_pos = players.single().ok().map(|tf| tf.translation.truncate());
+
+    for (entity, tf, mut rest) in &mut sleepers {
+        let pos = tf.translation.truncate();
+        let tile = world_to_tile(pos.x, pos.y, config.tile_size);
+        let near_change = changes
+            .iter()
+            .any(|&changed| within_wake_radius(changed, tile, &config));
+        let near_player = player_pos.is_some_and(|p| p.distance(pos) < magnet_radius);
+        if near_change || near_player {
+            rest.reset();
+            commands.entity(entity).remove::<Sleeping>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    const FLOOR: Option<(i32, i32)> = Some((5, 9));
+
+    #[test]
+    fn rest_sleeps_after_still_frames_on_one_support() {
+        let mut rest = DropRest::default();
+        // The first frame only records the support.
+        assert!(!rest.observe(0.0, FLOOR));
+        for _ in 1..SLEEP_FRAMES {
+            assert!(!rest.observe(0.0, FLOOR));
+        }
+        assert!(rest.observe(0.0, FLOOR));
+    }
+
+    #[test]
+    fn motion_support_changes_and_airtime_reset_rest() {
+        let mut rest = DropRest::default();
+        for _ in 0..SLEEP_FRAMES {
+            rest.observe(0.0, FLOOR);
+        }
+        assert!(!rest.observe(SLEEP_SPEED, FLOOR), "moving");
+        for _ in 0..SLEEP_FRAMES - 1 {
+            rest.observe(0.0, FLOOR);
+        }
+        assert!(!rest.observe(0.0, Some((6, 9))), "slid onto a new tile");
+        assert!(!rest.observe(0.0, None), "airborne");
+        for _ in 0..SLEEP_FRAMES * 2 {
+            assert!(!rest.observe(0.0, None));
+        }
+    }
+
+    #[test]
+    fn wake_radius_is_chebyshev_and_wraps() {
+        let config = fixtures::test_active_world();
+        assert!(within_wake_radius((10, 10), (12, 8), &config));
+        assert!(!within_wake_radius((10, 10), (13, 10), &config));
+        assert!(!within_wake_radius((10, 10), (10, 13), &config));
+        // Across the world seam.
+        let last = config.width_tiles - 1;
+        assert!(within_wake_radius((0, 10), (last, 10), &config));
+        assert!(within_wake_radius((1, 10), (-1, 10), &config));
+    }
+
+    fn sleep_app() -> App {
+        let mut app = fixtures::test_app();
+        app.add_message::<TileChanged>()
+            .add_systems(Update, (wake_dropped_items, settle_dropped_items).chain());
+        app
+    }
+
+    fn spawn_drop(app: &mut App, tile: (i32, i32)) -> Entity {
+        let ts = app.world().resource::<ActiveWorld>().tile_size;
+        app.world_mut()
+            .spawn((
+                DroppedItem {
+                    item_id: "dirt".into(),
+                    count: 1,
+                    lifetime: Timer::from_seconds(300.0, TimerMode::Once),
+                },
+                Transform::from_xyz((tile.0 as f32 + 0.5) * ts, tile.1 as f32 * ts + 2.0, 0.0),
+                Velocity::default(),
+                Grounded(true),
+                TileCollider {
+                    width: 4.0,
+                    height: 4.0,
+                },
+            ))
+            .id()
+    }
+
+    fn asleep(app: &App, entity: Entity) -> bool {
+        app.world().entity(entity).contains::<Sleeping>()
+    }
+
+    #[test]
+    fn resting_drops_sleep_and_wake_on_nearby_tile_changes() {
+        let mut app = sleep_app();
+        let near = spawn_drop(&mut app, (100, 500));
+        let far = spawn_drop(&mut app, (103, 500));
+        let airborne = spawn_drop(&mut app, (200, 500));
+        app.world_mut().entity_mut(airborne).insert(Grounded(false));
+
+        // One frame to attach the rest tracker, one to record the support.
+        for _ in 0..SLEEP_FRAMES + 1 {
+            app.update();
+            assert!(!asleep(&app, near));
+        }
+        app.update();
+        assert!(asleep(&app, near));
+        assert!(asleep(&app, far));
+        assert!(!asleep(&app, airborne));
+
+        app.world_mut().write_message(TileChanged {
+            tile_x: 98,
+            tile_y: 498,
+        });
+        app.update();
+        assert!(!asleep(&app, near), "two tiles from the change");
+        assert!(asleep(&app, far), "five tiles from the change");
+
+        // Still at rest, so it settles again.
+        for _ in 0..SLEEP_FRAMES + 1 {
+            app.update();
+        }
+        assert!(asleep(&app, near));
+    }
+
+    #[test]
+    fn player_in_magnet_range_wakes_drops() {
+        let mut app = sleep_app();
+        let drop = spawn_drop(&mut app, (100, 500));
+        for _ in 0..SLEEP_FRAMES + 2 {
+            app.update();
+        }
+        assert!(asleep(&app, drop));
+
+        let drop_pos = app
+            .world()
+            .entity(drop)
+            .get::<Transform>()
+            .unwrap()
+            .translation;
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_translation(drop_pos + Vec3::X * 500.0),
+            ))
+            .id();
+        app.update();
+        assert!(asleep(&app, drop), "out of magnet range");
+
+        app.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_translation(drop_pos + Vec3::X * 50.0));
+        app.update();
+        assert!(!asleep(&app, drop));
+    }
+}
@@ -1,13 +1,87 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
+use super::drop_sleep::DropRest;
+use crate::cosmos::persistence::DROPPED_ITEM_LIFETIME_SECS;
+use crate::item::{ItemRegistry, Rarity};
+use crate::physics::{Sleeping, TileCollider};
+use crate::registry::world::ActiveWorld;
+use crate::ui::game_ui::theme::UiTheme;
+use crate::world::lit_sprite::LitSpriteMaterial;
+use crate::world::spatial_index::SpatialIndex;
+
 /// A dropped item entity in the world.
 #[derive(Component, Debug)]
 pub struct DroppedItem {
     pub item_id: String,
     pub count: u16,
+    /// Counts up from when the item dropped; the elapsed time is its age.
     pub lifetime: Timer,
 }
 
+/// Keeps a dropped item away from the player's magnet and pickup until it
+/// runs out, so an item the player drops on purpose isn't picked right back
+/// up.
+#[derive(Component, Debug)]
+pub struct PickupDelay(pub Timer);
+
+impl PickupDelay {
+    pub fn new(secs: f32) -> Self {
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
+}
+
+/// Lifetime timer for a dropped item that has `remaining_secs` left of a
+/// `lifetime_secs` lifetime, so its age carries over (e.g. across a save).
+pub fn lifetime_timer(lifetime_secs: f32, remaining_secs: f32) -> Timer {
+    let total = lifetime_secs.max(remaining_secs);
+    let mut timer = Timer::from_seconds(total, TimerMode::Once);
+    timer.set_elapsed(Duration::from_secs_f32((total - remaining_secs).max(0.0)));
+    timer
+}
+
+/// Limits on dropped items lying in the world.
+#[derive(Resource, Debug, Clone)]
+pub struct DroppedItemLimits {
+    /// Most dropped item entities alive at once. Past this, the oldest are
+    /// despawned first.
+    pub max_entities: usize,
+    /// Lifetime in seconds of common items.
+    pub common_lifetime: f32,
+    pub uncommon_lifetime: f32,
+    pub rare_lifetime: f32,
+    pub legendary_lifetime: f32,
+    /// Distance (px) within which drops of the same item merge into one
+    /// stack. 0 disables merging.
+    pub merge_radius: f32,
+}
+
+impl Default for DroppedItemLimits {
+    fn default() -> Self {
+        Self {
+            max_entities: 500,
+            common_lifetime: DROPPED_ITEM_LIFETIME_SECS,
+            uncommon_lifetime: DROPPED_ITEM_LIFETIME_SECS,
+            rare_lifetime: DROPPED_ITEM_LIFETIME_SECS * 2.0,
+            legendary_lifetime: DROPPED_ITEM_LIFETIME_SECS * 4.0,
+            merge_radius: 16.0,
+        }
+    }
+}
+
+impl DroppedItemLimits {
+    /// Lifetime in seconds of a dropped item of `rarity`.
+    pub fn lifetime(&self, rarity: Rarity) -> f32 {
+        match rarity {
+            Rarity::Common => self.common_lifetime,
+            Rarity::Uncommon => self.uncommon_lifetime,
+            Rarity::Rare => self.rare_lifetime,
+            Rarity::Legendary => self.legendary_lifetime,
+        }
+    }
+}
+
 /// Parameters for spawning a dropped item.
 pub struct SpawnParams {
     pub position: Vec2,
@@ -49,6 +123,149 @@ pub fn despawn_expired_drops(
     }
 }
 
+/// Lift the [`PickupDelay`] of items whose delay has run out.
+pub fn tick_pickup_delay(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PickupDelay)>,
+) {
+    for (entity, mut delay) in &mut query {
+        delay.0.tick(time.delta());
+        if delay.0.is_finished() {
+            commands.entity(entity).remove::<PickupDelay>();
+        }
+    }
+}
+
+/// Despawn the oldest dropped items while there are more than
+/// [`DroppedItemLimits::max_entities`].
+pub fn enforce_dropped_item_cap(
+    mut commands: Commands,
+    limits: Res<DroppedItemLimits>,
+    query: Query<(Entity, &DroppedItem)>,
+) {
+    let count = query.iter().len();
+    if count <= limits.max_entities {
+        return;
+    }
+
+    let mut by_age: Vec<(Duration, Entity)> = query
+        .iter()
+        .map(|(entity, item)| (item.lifetime.elapsed(), entity))
+        .collect();
+    // Oldest first; entity order breaks ties so the choice is deterministic.
+    by_age.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, entity) in &by_age[..count - limits.max_entities] {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Merge drops of the same item lying within
+/// [`DroppedItemLimits::merge_radius`] of each other, as long as the merged
+/// stack fits in the item's max stack.
+///
+/// Only awake drops look for partners, through the [`SpatialIndex`];
+/// magnetised and freshly thrown drops are left alone. The larger stack is
+/// kept (the lower entity on a tie) and woken so it settles again.
+#[allow(clippy::type_complexity)]
+pub fn merge_dropped_items(
+    mut commands: Commands,
+    limits: Res<DroppedItemLimits>,
+    config: Res<ActiveWorld>,
+    index: Res<SpatialIndex>,
+    registry: Option<Res<ItemRegistry>>,
+    awake: Query<
+        (Entity, &Transform),
+        (
+            With<DroppedItem>,
+            With<TileCollider>,
+            Without<Sleeping>,
+            Without<PickupDelay>,
+        ),
+    >,
+    mut drops: Query<
+        (&mut DroppedItem, Option<&mut DropRest>),
+        (With<TileCollider>, Without<PickupDelay>),
+    >,
+) {
+    let Some(registry) = registry.filter(|_| limits.merge_radius > 0.0) else {
+        return;
+    };
+    let mut absorbed: Vec<Entity> = Vec::new();
+    for (entity, tf) in &awake {
+        let pos = tf.translation.truncate();
+        for (other, _) in index.query_radius(pos, limits.merge_radius, &config) {
+            if absorbed.contains(&entity) {
+                break;
+            }
+            if other == entity || absorbed.contains(&other) {
+                continue;
+            }
+            let (Ok((a, _)), Ok((b, _))) = (drops.get(entity), drops.get(other)) else {
+                continue;
+            };
+            if a.item_id != b.item_id {
+                continue;
+            }
+            let Some(id) = registry.by_name(&a.item_id) else {
+                continue;
+            };
+            let total = a.count as u32 + b.count as u32;
+            if total > registry.get(id).max_stack as u32 {
+                continue;
+            }
+            let (keep, gone) = if (a.count, other) > (b.count, entity) {
+                (entity, other)
+            } else {
+                (other, entity)
+            };
+            let Ok([(mut kept, rest), _]) = drops.get_many_mut([keep, gone]) else {
+                continue;
+            };
+            kept.count = total as u16;
+            if let Some(mut rest) = rest {
+                rest.reset();
+            }
+            commands.entity(keep).remove::<Sleeping>();
+            commands.entity(gone).despawn();
+            absorbed.push(gone);
+        }
+    }
+}
+
+/// Outline highlight of a dropped item of `rarity`: the theme's rarity
+/// colour at [`UiTheme::drop_glow`] strength, none for common items.
+pub fn drop_glow(rarity: Rarity, theme: &UiTheme) -> Vec4 {
+    if rarity == Rarity::Common || theme.drop_glow <= 0.0 {
+        return Vec4::ZERO;
+    }
+    let color = LinearRgba::from(theme.colors.rarity(rarity));
+    Vec4::new(color.red, color.green, color.blue, theme.drop_glow)
+}
+
+/// Outline new dropped items in their rarity colour, and every dropped item
+/// again when the theme changes.
+pub fn glow_dropped_items(
+    theme: Res<UiTheme>,
+    item_registry: Res<ItemRegistry>,
+    drops: Query<(Ref<DroppedItem>, &MeshMaterial2d<LitSpriteMaterial>)>,
+    mut materials: ResMut<Assets<LitSpriteMaterial>>,
+) {
+    let restyle = theme.is_changed();
+    for (item, material) in &drops {
+        if !restyle && !item.is_added() {
+            continue;
+        }
+        let rarity = item_registry
+            .by_name(&item.item_id)
+            .map(|id| item_registry.get(id).rarity)
+            .unwrap_or_default();
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.highlight = drop_glow(rarity, &theme);
+        }
+    }
+}
+
 /// Calculate drops from a tile definition.
 pub fn calculate_drops(tile_drops: &[crate::item::DropDef]) -> Vec<(String, u16)> {
     use rand::Rng;
@@ -70,6 +287,23 @@ pub fn calculate_drops(tile_drops: &[crate::item::DropDef]) -> Vec<(String, u16)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn drops_above_common_glow_in_their_rarity_color() {
+        let mut theme = crate::test_helpers::fixtures::test_ui_theme();
+        assert_eq!(drop_glow(Rarity::Common, &theme), Vec4::ZERO);
+
+        let rare = LinearRgba::from(theme.colors.rarity(Rarity::Rare));
+        assert_eq!(
+            drop_glow(Rarity::Rare, &theme),
+            Vec4::new(rare.red, rare.green, rare.blue, theme.drop_glow)
+        );
+        assert!(theme.drop_glow > 0.0);
+
+        theme.drop_glow = 0.0;
+        assert_eq!(drop_glow(Rarity::Legendary, &theme), Vec4::ZERO);
+    }
 
     #[test]
     fn dropped_item_has_required_fields() {
@@ -95,4 +329,175 @@ mod tests {
         assert!(params.velocity().x.abs() < 0.1);
         assert!((params.velocity().y - 100.0).abs() < 0.1);
     }
+
+    fn drop_app(limits: DroppedItemLimits) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_secs(1),
+        ));
+        app.insert_resource(limits);
+        app.add_systems(
+            Update,
+            (despawn_expired_drops, enforce_dropped_item_cap).chain(),
+        );
+        app
+    }
+
+    fn spawn_drop(app: &mut App, lifetime_secs: f32, age_secs: f32) -> Entity {
+        app.world_mut()
+            .spawn(DroppedItem {
+                item_id: "dirt".into(),
+                count: 1,
+                lifetime: lifetime_timer(lifetime_secs, lifetime_secs - age_secs),
+            })
+            .id()
+    }
+
+    fn alive(app: &mut App) -> HashSet<Entity> {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, With<DroppedItem>>();
+        query.iter(app.world()).collect()
+    }
+
+    #[test]
+    fn lifetime_follows_rarity() {
+        let limits = DroppedItemLimits {
+            common_lifetime: 60.0,
+            legendary_lifetime: 600.0,
+            ..default()
+        };
+        assert_eq!(limits.lifetime(Rarity::Common), 60.0);
+        assert_eq!(limits.lifetime(Rarity::Legendary), 600.0);
+    }
+
+    #[test]
+    fn lifetime_timer_keeps_age() {
+        let timer = lifetime_timer(300.0, 120.0);
+        assert_eq!(timer.duration().as_secs_f32(), 300.0);
+        assert_eq!(timer.elapsed_secs(), 180.0);
+        // Saved with more time left than the current lifetime: never expired.
+        let timer = lifetime_timer(60.0, 90.0);
+        assert_eq!(timer.remaining_secs(), 90.0);
+    }
+
+    #[test]
+    fn expired_drops_are_despawned() {
+        let mut app = drop_app(DroppedItemLimits::default());
+        let short = spawn_drop(&mut app, 3.0, 0.0);
+        let long = spawn_drop(&mut app, 30.0, 0.0);
+
+        // One second of game time per update.
+        while alive(&mut app).contains(&short) {
+            app.update();
+            let elapsed = app.world().resource::<Time>().elapsed_secs();
+            assert!(elapsed <= 4.0, "3 s drop still alive after {elapsed} s");
+        }
+        let elapsed = app.world().resource::<Time>().elapsed_secs();
+        assert!(elapsed >= 3.0, "3 s drop despawned after {elapsed} s");
+        assert_eq!(alive(&mut app), HashSet::from([long]));
+    }
+
+    #[test]
+    fn exceeding_cap_despawns_oldest_first() {
+        let mut app = drop_app(DroppedItemLimits {
+            max_entities: 3,
+            ..default()
+        });
+        let ages = [50.0, 10.0, 40.0, 0.0, 20.0];
+        let drops: Vec<Entity> = ages
+            .iter()
+            .map(|&age| spawn_drop(&mut app, 300.0, age))
+            .collect();
+
+        app.update();
+        // The two oldest (50 s and 40 s) go; the rest stay.
+        assert_eq!(
+            alive(&mut app),
+            HashSet::from([drops[1], drops[3], drops[4]])
+        );
+
+        // Within the cap nothing else is touched.
+        app.update();
+        assert_eq!(alive(&mut app).len(), 3);
+    }
+
+    fn item(id: &str, max_stack: u16) -> crate::item::ItemDef {
+        crate::item::ItemDef {
+            max_stack,
+            ..crate::test_helpers::fixtures::item_def(id, crate::item::ItemType::Block)
+        }
+    }
+
+    fn merge_app() -> App {
+        use crate::world::spatial_index::refresh_spatial_index;
+
+        let mut app = crate::test_helpers::fixtures::test_app();
+        app.insert_resource(ItemRegistry::from_defs(vec![
+            item("dirt", 10),
+            item("stone", 10),
+        ]))
+        .init_resource::<DroppedItemLimits>()
+        .init_resource::<SpatialIndex>()
+        .add_systems(Update, (refresh_spatial_index, merge_dropped_items).chain());
+        app
+    }
+
+    fn spawn_stack(app: &mut App, id: &str, count: u16, x: f32) -> Entity {
+        use crate::world::spatial_index::TrackedInIndex;
+
+        app.world_mut()
+            .spawn((
+                DroppedItem {
+                    item_id: id.into(),
+                    count,
+                    lifetime: Timer::from_seconds(300.0, TimerMode::Once),
+                },
+                TrackedInIndex::default(),
+                Transform::from_xyz(x, 500.0, 0.0),
+                TileCollider {
+                    width: 4.0,
+                    height: 4.0,
+                },
+            ))
+            .id()
+    }
+
+    fn count(app: &App, entity: Entity) -> Option<u16> {
+        app.world().get::<DroppedItem>(entity).map(|d| d.count)
+    }
+
+    #[test]
+    fn nearby_stacks_of_the_same_item_merge_into_the_larger() {
+        let mut app = merge_app();
+        let small = spawn_stack(&mut app, "dirt", 3, 100.0);
+        let large = spawn_stack(&mut app, "dirt", 4, 108.0);
+        let other_item = spawn_stack(&mut app, "stone", 2, 104.0);
+        let far = spawn_stack(&mut app, "dirt", 1, 400.0);
+        app.update();
+
+        assert_eq!(count(&app, small), None);
+        assert_eq!(count(&app, large), Some(7));
+        assert_eq!(count(&app, other_item), Some(2));
+        assert_eq!(count(&app, far), Some(1));
+    }
+
+    #[test]
+    fn merging_respects_max_stack_and_wakes_the_kept_stack() {
+        let mut app = merge_app();
+        let full = spawn_stack(&mut app, "dirt", 8, 100.0);
+        let extra = spawn_stack(&mut app, "dirt", 3, 104.0);
+        app.update();
+        assert_eq!((count(&app, full), count(&app, extra)), (Some(8), Some(3)));
+
+        // A sleeping stack is merged into by an awake one, and woken.
+        let sleeper = spawn_stack(&mut app, "stone", 5, 2000.0);
+        app.world_mut().entity_mut(sleeper).insert(Sleeping);
+        let falling = spawn_stack(&mut app, "stone", 1, 2006.0);
+        app.update();
+        assert_eq!(count(&app, sleeper), Some(6));
+        assert_eq!(count(&app, falling), None);
+        assert!(!app.world().entity(sleeper).contains::<Sleeping>());
+    }
 }
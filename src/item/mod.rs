@@ -1,4 +1,5 @@
 pub mod definition;
+pub mod drop_sleep;
 pub mod dropped_item;
 pub mod plugin;
 pub mod registry;
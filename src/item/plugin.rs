@@ -1,6 +1,13 @@
 use bevy::prelude::*;
 
-use super::dropped_item::despawn_expired_drops;
+use super::drop_sleep::{settle_dropped_items, wake_dropped_items};
+use super::dropped_item::{
+    despawn_expired_drops, enforce_dropped_item_cap, glow_dropped_items, merge_dropped_items,
+    tick_pickup_delay, DroppedItemLimits,
+};
+use crate::physics::{apply_gravity, tile_collision};
+use crate::sets::{GameSet, WorldSet};
+use crate::world::spatial_index::refresh_spatial_index;
 
 pub struct ItemPlugin;
 
@@ -8,6 +15,30 @@ impl Plugin for ItemPlugin {
     fn build(&self, app: &mut App) {
         // ItemRegistry is now built from item.ron files during the registry
         // loading pipeline (see registry/loading.rs check_loading).
-        app.add_systems(Update, despawn_expired_drops);
+        app.init_resource::<DroppedItemLimits>()
+            .add_systems(
+                Update,
+                (
+                    despawn_expired_drops,
+                    enforce_dropped_item_cap,
+                    tick_pickup_delay,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    wake_dropped_items.before(apply_gravity),
+                    settle_dropped_items.after(tile_collision),
+                )
+                    .in_set(GameSet::Physics),
+            )
+            .add_systems(
+                Update,
+                merge_dropped_items
+                    .after(refresh_spatial_index)
+                    .in_set(WorldSet::Sim),
+            )
+            .add_systems(Update, glow_dropped_items.in_set(WorldSet::RenderPrep));
     }
 }
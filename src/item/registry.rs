@@ -56,37 +56,26 @@ impl ItemRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::item::{ItemType, Rarity};
+    use crate::item::ItemType;
+    use crate::test_helpers::fixtures;
 
     fn test_registry() -> ItemRegistry {
         ItemRegistry::from_defs(vec![
             ItemDef {
-                id: "dirt".into(),
                 display_name: "Dirt Block".into(),
                 description: "A block of dirt".into(),
                 max_stack: 999,
-                rarity: Rarity::Common,
-                item_type: ItemType::Block,
                 icon: Some("items/dirt.png".into()),
                 placeable: Some("dirt".into()),
-                placeable_object: None,
-                equipment_slot: None,
-                stats: None,
-                blueprint_item: None,
+                ..fixtures::item_def("dirt", ItemType::Block)
             },
             ItemDef {
-                id: "stone".into(),
                 display_name: "Stone".into(),
                 description: "A block of stone".into(),
                 max_stack: 999,
-                rarity: Rarity::Common,
-                item_type: ItemType::Block,
                 icon: Some("items/stone.png".into()),
                 placeable: Some("stone".into()),
-                placeable_object: None,
-                equipment_slot: None,
-                stats: None,
-                blueprint_item: None,
+                ..fixtures::item_def("stone", ItemType::Block)
             },
         ])
     }
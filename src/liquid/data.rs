@@ -13,7 +13,7 @@ impl LiquidId {
 }
 
 /// Per-tile liquid state.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct LiquidCell {
     pub liquid_type: LiquidId,
     pub level: f32,
@@ -16,7 +16,7 @@ pub use render::{
 pub use system::LiquidSimState;
 
 use crate::registry::AppState;
-use crate::sets::GameSet;
+use crate::sets::{GameSet, WorldSet};
 use bevy::prelude::*;
 use bevy_egui::EguiPrimaryContextPass;
 
@@ -33,29 +33,16 @@ impl Plugin for LiquidPlugin {
             .add_systems(OnEnter(AppState::InGame), render::init_liquid_material)
             .add_systems(
                 Update,
-                system::liquid_simulation_system.in_set(GameSet::WorldUpdate),
+                (system::liquid_simulation_system, debug::debug_liquid_keys)
+                    .in_set(WorldSet::Sim),
             )
             .add_systems(
                 Update,
-                render::rebuild_liquid_meshes
-                    .in_set(GameSet::WorldUpdate)
-                    .after(system::liquid_simulation_system),
-            )
-            .add_systems(
-                Update,
-                render::upload_liquid_field
-                    .in_set(GameSet::WorldUpdate)
-                    .after(system::liquid_simulation_system),
-            )
-            .add_systems(
-                Update,
-                render::update_liquid_field_quad
-                    .in_set(GameSet::WorldUpdate)
-                    .after(render::upload_liquid_field),
-            )
-            .add_systems(
-                Update,
-                debug::debug_liquid_keys.in_set(GameSet::WorldUpdate),
+                (
+                    render::rebuild_liquid_meshes,
+                    (render::upload_liquid_field, render::update_liquid_field_quad).chain(),
+                )
+                    .in_set(WorldSet::RenderPrep),
             )
             .add_systems(Update, debug::toggle_liquid_debug.in_set(GameSet::Ui))
             .add_systems(
@@ -435,14 +435,20 @@ pub fn rebuild_liquid_meshes(
     render_config: Res<LiquidRenderConfig>,
     liquid_query: Query<(Entity, &ChunkCoord, &Visibility), With<LiquidMeshEntity>>,
 ) {
-    let target_vis = if render_config.show_debug_meshes {
+    let debug_vis = if render_config.show_debug_meshes {
         Visibility::Visible
     } else {
         Visibility::Hidden
     };
 
     // Update visibility for all liquid mesh entities when toggle changes.
-    for (entity, _coord, vis) in &liquid_query {
+    // Hibernating chunks stay hidden whatever the toggle says.
+    for (entity, coord, vis) in &liquid_query {
+        let target_vis = if loaded_chunks.is_hibernating(coord.x, coord.y) {
+            Visibility::Hidden
+        } else {
+            debug_vis
+        };
         if *vis != target_vis {
             commands.entity(entity).insert(target_vis);
         }
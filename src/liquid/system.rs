@@ -50,12 +50,12 @@ pub fn liquid_simulation_system(
     dirty_resources: (
         ResMut<DirtyChunks>,
         ResMut<DirtyLiquidChunks>,
-        ResMut<crate::world::rc_lighting::RcGridDirty>,
+        ResMut<crate::world::rc_lighting::LiquidRelight>,
     ),
     _loaded_chunks: Res<chunk::LoadedChunks>,
     chunk_query: Query<(Entity, &chunk::ChunkCoord, &chunk::ChunkLayer)>,
 ) {
-    let (mut dirty_chunks, mut dirty_liquid, mut rc_dirty) = dirty_resources;
+    let (mut dirty_chunks, mut dirty_liquid, mut relight) = dirty_resources;
 
     if liquid_registry.defs.is_empty() {
         return;
@@ -80,10 +80,11 @@ pub fn liquid_simulation_system(
         all_produced.extend(produced);
     }
 
-    // Mark RC lighting grid dirty whenever liquid moved (density/opacity
-    // changes need to propagate to the lightmap for correct shadows).
+    // Request an RC lighting grid rebuild whenever liquid moved
+    // (density/opacity changes need to propagate to the lightmap for correct
+    // shadows). The request is throttled so fast flows don't rebuild every tick.
     if steps > 0 && !dirty_liquid.0.is_empty() {
-        rc_dirty.0 = true;
+        relight.request();
     }
 
     // Mark tile mesh entities dirty for any reaction-produced solid tiles.
@@ -4,6 +4,8 @@ pub mod combat;
 pub mod cosmos;
 pub mod crafting;
 pub mod enemy;
+pub mod fishing;
+mod game_mode;
 mod interaction;
 pub mod inventory;
 pub mod item;
@@ -15,11 +17,13 @@ pub mod particles;
 mod parallax;
 pub mod physics;
 mod player;
+pub mod projectile;
 mod registry;
 pub mod sets;
 pub mod trader;
 #[cfg(test)]
 mod test_helpers;
+mod tutorial;
 mod ui;
 pub mod weather;
 mod world;
@@ -28,9 +32,29 @@ use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::prelude::*;
 use bevy_egui::{EguiGlobalSettings, EguiPlugin};
 
-use sets::GameSet;
-
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(result) = world::worldgen_preview::run_from_args(&args) {
+        match result {
+            Ok(path) => println!("Wrote {}", path.display()),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(result) = world::world_hash::run_from_args(&args) {
+        match result {
+            Ok(digest) => println!("{digest}"),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     App::new()
         .add_plugins(
             DefaultPlugins
@@ -57,13 +81,17 @@ fn main() {
         .add_plugins(liquid::LiquidPlugin)
         .add_plugins(player::PlayerPlugin)
         .add_plugins(physics::PhysicsPlugin)
+        .add_plugins(projectile::ProjectilePlugin)
+        .add_plugins(fishing::FishingPlugin)
         .add_plugins(cosmos::pressurization::PressurizationPlugin)
+        .add_plugins(cosmos::autosave::AutosavePlugin)
         .add_plugins(particles::ParticlePlugin)
         .add_plugins(weather::WeatherPlugin)
         .add_plugins(camera::CameraPlugin)
         .add_plugins(parallax::ParallaxPlugin)
         .add_plugins(interaction::InteractionPlugin)
         .add_plugins(chat::ChatPlugin)
+        .add_plugins(game_mode::GameModePlugin)
         .add_plugins(ui::UiPlugin)
         .add_plugins(item::ItemPlugin)
         .add_plugins(object::ObjectPlugin)
@@ -72,18 +100,7 @@ fn main() {
         .add_plugins(combat::CombatPlugin)
         .add_plugins(enemy::EnemyPlugin)
         .add_plugins(trader::TraderPlugin)
-        .configure_sets(
-            Update,
-            (
-                GameSet::Input,
-                GameSet::Physics,
-                GameSet::WorldUpdate,
-                GameSet::Camera,
-                GameSet::Parallax,
-                GameSet::Ui,
-            )
-                .chain()
-                .run_if(in_state(registry::AppState::InGame)),
-        )
+        .add_plugins(tutorial::TutorialPlugin)
+        .add_plugins(sets::configure_sets)
         .run();
 }
@@ -1,3 +1,5 @@
+use bevy::math::{IVec2, Vec2};
+
 /// Axis-aligned bounding box for 2D collision detection.
 pub struct Aabb {
     pub min_x: f32,
@@ -81,9 +83,131 @@ pub fn tile_aabb(tx: i32, ty: i32, tile_size: f32) -> Aabb {
     }
 }
 
+/// Where a segment first enters a solid tile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentHit {
+    /// The solid tile.
+    pub tile: (i32, i32),
+    /// Fraction (0..=1) of the segment travelled before the hit.
+    pub t: f32,
+    /// Outward normal of the face that was hit; both components are set when
+    /// the segment runs into a corner.
+    pub normal: IVec2,
+}
+
+/// First solid tile entered by the segment from `from` to `to` (world px).
+/// The tile containing `from` is never reported.
+///
+/// Walks the tile grid with a DDA (Amanatides–Woo), so no tile on the path is
+/// skipped however long the segment is. Where the segment passes exactly
+/// through a tile corner, the two tiles beside the corner block it only if
+/// both are solid.
+pub fn sweep_segment(
+    from: Vec2,
+    to: Vec2,
+    tile_size: f32,
+    mut is_solid: impl FnMut(i32, i32) -> bool,
+) -> Option<SegmentHit> {
+    let dir = to - from;
+    let mut x = (from.x / tile_size).floor() as i32;
+    let mut y = (from.y / tile_size).floor() as i32;
+
+    // Per axis: tile step, segment parameter at the next tile boundary, and
+    // the parameter distance between boundaries.
+    let axis = |origin: f32, d: f32, tile: i32| -> (i32, f32, f32) {
+        if d == 0.0 {
+            return (0, f32::INFINITY, f32::INFINITY);
+        }
+        let step = if d > 0.0 { 1 } else { -1 };
+        let boundary = if d > 0.0 { tile + 1 } else { tile } as f32 * tile_size;
+        (step, (boundary - origin) / d, tile_size / d.abs())
+    };
+    let (step_x, mut t_max_x, t_delta_x) = axis(from.x, dir.x, x);
+    let (step_y, mut t_max_y, t_delta_y) = axis(from.y, dir.y, y);
+
+    loop {
+        let t = t_max_x.min(t_max_y);
+        if t > 1.0 {
+            return None;
+        }
+        let normal = if (t_max_x - t_max_y).abs() < 1e-6 {
+            let normal = IVec2::new(-step_x, -step_y);
+            let side = (x + step_x, y);
+            if is_solid(side.0, side.1) && is_solid(x, y + step_y) {
+                return Some(SegmentHit {
+                    tile: side,
+                    t,
+                    normal,
+                });
+            }
+            x += step_x;
+            y += step_y;
+            t_max_x += t_delta_x;
+            t_max_y += t_delta_y;
+            normal
+        } else if t_max_x < t_max_y {
+            x += step_x;
+            t_max_x += t_delta_x;
+            IVec2::new(-step_x, 0)
+        } else {
+            y += step_y;
+            t_max_y += t_delta_y;
+            IVec2::new(0, -step_y)
+        };
+        if is_solid(x, y) {
+            return Some(SegmentHit {
+                tile: (x, y),
+                t,
+                normal,
+            });
+        }
+    }
+}
+
+/// SplitMix64 RNG — deterministic, fast, non-cryptographic. Shared by
+/// everything that must roll the same way for the same seed (biome layout,
+/// fishing, plant growth, surface decorations).
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// An RNG for one independent stream of `seed`, e.g. one cast or one
+    /// roll; different salts give unrelated sequences.
+    pub fn salted(seed: u64, salt: u64) -> Self {
+        let mut mix = Self::new(seed ^ salt.rotate_left(32));
+        Self::new(mix.next_u64() ^ salt)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `[lo, hi]` (inclusive).
+    pub fn range(&mut self, lo: u32, hi: u32) -> u32 {
+        assert!(hi >= lo);
+        let span = (hi - lo) as u64 + 1;
+        (self.next_u64() % span) as u32 + lo
+    }
+
+    /// Returns a value in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     const TS: f32 = 32.0;
 
@@ -135,4 +259,54 @@ mod tests {
         assert_eq!(aabb.min_y, 160.0);
         assert_eq!(aabb.max_y, 192.0);
     }
+
+    fn sweep(solid: &[(i32, i32)], from: Vec2, to: Vec2) -> Option<SegmentHit> {
+        let solid: HashSet<(i32, i32)> = solid.iter().copied().collect();
+        sweep_segment(from, to, TS, |x, y| solid.contains(&(x, y)))
+    }
+
+    #[test]
+    fn sweep_stops_at_thin_wall_however_fast() {
+        // One-tile wall at x = 5; the segment spans 20 tiles in one step.
+        let wall = [(5, 0), (5, 1), (5, 2)];
+        let hit = sweep(&wall, Vec2::new(16.0, 48.0), Vec2::new(21.0 * TS, 48.0)).unwrap();
+        assert_eq!(hit.tile, (5, 1));
+        assert_eq!(hit.normal, IVec2::new(-1, 0));
+        let contact = 16.0 + (21.0 * TS - 16.0) * hit.t;
+        assert!((contact - 5.0 * TS).abs() < 1e-3, "contact at {contact}");
+    }
+
+    #[test]
+    fn sweep_reports_floor_normal_and_misses_clear_path() {
+        let floor = [(0, 0), (1, 0), (2, 0)];
+        let hit = sweep(&floor, Vec2::new(16.0, 80.0), Vec2::new(60.0, 8.0)).unwrap();
+        assert_eq!(hit.normal, IVec2::new(0, 1));
+        assert_eq!(hit.tile.1, 0);
+        assert!(sweep(&floor, Vec2::new(16.0, 80.0), Vec2::new(90.0, 40.0)).is_none());
+    }
+
+    #[test]
+    fn sweep_ignores_start_tile_and_zero_length() {
+        let solid = [(0, 0)];
+        assert!(sweep(&solid, Vec2::new(16.0, 16.0), Vec2::new(20.0, 20.0)).is_none());
+        assert!(sweep(&solid, Vec2::new(48.0, 16.0), Vec2::new(48.0, 16.0)).is_none());
+    }
+
+    #[test]
+    fn sweep_through_exact_corner() {
+        // Diagonal from the centre of (0, 0) through the corner at (32, 32).
+        let from = Vec2::new(16.0, 16.0);
+        let to = Vec2::new(80.0, 80.0);
+        // One tile beside the corner: the segment slips past it.
+        assert!(sweep(&[(1, 0)], from, to).is_none());
+        // Both tiles beside the corner close it.
+        let hit = sweep(&[(1, 0), (0, 1)], from, to).unwrap();
+        assert_eq!(hit.tile, (1, 0));
+        assert_eq!(hit.normal, IVec2::new(-1, -1));
+        // Tile diagonally across the corner is hit head-on.
+        let hit = sweep(&[(1, 1)], from, to).unwrap();
+        assert_eq!(hit.tile, (1, 1));
+        assert_eq!(hit.normal, IVec2::new(-1, -1));
+        assert!((hit.t - 0.25).abs() < 1e-5);
+    }
 }
@@ -0,0 +1,86 @@
+//! Screen shown when loading stops on a problem (see `AppState::LoadingFailed`).
+
+use bevy::prelude::*;
+
+use super::ui::{colors, ExitButton};
+use crate::registry::texture_check::LoadingFailure;
+
+/// Spawn the failure screen listing every problem that stopped loading.
+pub fn spawn_loading_failed_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    failure: Option<Res<LoadingFailure>>,
+) {
+    let font = asset_server.load("fonts/Silkscreen-Regular.ttf");
+    let font_bold = asset_server.load("fonts/Silkscreen-Bold.ttf");
+    let problems = failure.map(|f| f.0.clone()).unwrap_or_default();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(colors::BG_DEEP),
+            GlobalZIndex(1),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("LOADING FAILED"),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(colors::ACCENT_WARM),
+                Node {
+                    margin: UiRect::bottom(Val::Px(24.0)),
+                    ..default()
+                },
+            ));
+            for problem in problems {
+                parent.spawn((
+                    Text::new(problem),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_DIM),
+                ));
+            }
+
+            parent
+                .spawn((
+                    ExitButton,
+                    Button,
+                    Node {
+                        width: Val::Px(280.0),
+                        height: Val::Px(56.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(1.0)),
+                        margin: UiRect::top(Val::Px(36.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                    BorderColor::all(colors::BTN_SECONDARY_BORDER),
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new("EXIT"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(colors::TEXT),
+                    ));
+                });
+        });
+}
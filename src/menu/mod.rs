@@ -1,3 +1,4 @@
+pub mod loading_failed;
 pub mod starfield;
 pub mod ui;
 
@@ -26,12 +27,22 @@ impl Plugin for MenuPlugin {
                 (
                     starfield::update_starfield_time,
                     ui::handle_new_game_button,
+                    ui::handle_restore_button,
+                    ui::handle_game_mode_button,
                     ui::handle_exit_button,
                 )
                     .into_configs()
                     .run_if(in_state(AppState::MainMenu)),
             )
-            .add_systems(OnExit(AppState::MainMenu), despawn_menu_scene);
+            .add_systems(OnExit(AppState::MainMenu), despawn_menu_scene)
+            .add_systems(
+                OnEnter(AppState::LoadingFailed),
+                loading_failed::spawn_loading_failed_ui,
+            )
+            .add_systems(
+                Update,
+                ui::handle_exit_button.run_if(in_state(AppState::LoadingFailed)),
+            );
     }
 }
 
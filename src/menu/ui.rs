@@ -2,12 +2,33 @@ use bevy::ecs::message::MessageWriter;
 use bevy::prelude::*;
 
 use super::MenuEntity;
+use crate::cosmos::autosave::{self, AutosaveConfig};
+use crate::cosmos::persistence::Universe;
+use crate::game_mode::GameMode;
 use crate::registry::AppState;
 
 /// Marker for the "New Game" button.
 #[derive(Component)]
 pub struct NewGameButton;
 
+/// Marker for the "Restore Session" button, shown when an autosave newer
+/// than the main save is left over from a crash.
+#[derive(Component)]
+pub struct RestoreButton;
+
+/// Marker for the game mode toggle button.
+#[derive(Component)]
+pub struct GameModeButton;
+
+/// Marker for the game mode button's label.
+#[derive(Component)]
+pub struct GameModeButtonText;
+
+/// Label of the game mode button.
+fn game_mode_label(mode: GameMode) -> String {
+    format!("MODE: {}", mode.name().to_uppercase())
+}
+
 /// Marker for the "Exit" button.
 #[derive(Component)]
 pub struct ExitButton;
@@ -25,7 +46,6 @@ pub mod colors {
     // --text: #e8e8f0
     pub const TEXT: Color = Color::srgb(0.910, 0.910, 0.941);
     // --text-dim: #8888aa
-    #[allow(dead_code)]
     pub const TEXT_DIM: Color = Color::srgb(0.533, 0.533, 0.667);
 
     // Primary button: background = --accent, hover = brighter, pressed = darker
@@ -40,7 +60,13 @@ pub mod colors {
 }
 
 /// Spawn the complete menu UI layout.
-pub fn spawn_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_menu_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+    autosave_config: Res<AutosaveConfig>,
+) {
+    let can_restore = autosave::recoverable_autosave(&autosave_config);
     let font = asset_server.load("fonts/Silkscreen-Regular.ttf");
     let font_bold = asset_server.load("fonts/Silkscreen-Bold.ttf");
 
@@ -126,6 +152,63 @@ pub fn spawn_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                         ));
                     });
 
+                    // "RESTORE SESSION" — secondary outlined button, only after a crash
+                    if can_restore {
+                        col.spawn((
+                            RestoreButton,
+                            Button,
+                            Node {
+                                width: Val::Px(280.0),
+                                height: Val::Px(56.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::NONE),
+                            BorderColor::all(colors::BTN_SECONDARY_BORDER),
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                Text::new("RESTORE SESSION"),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(colors::TEXT),
+                            ));
+                        });
+                    }
+
+                    // "MODE: ..." — secondary outlined button, toggles survival/creative
+                    col.spawn((
+                        GameModeButton,
+                        Button,
+                        Node {
+                            width: Val::Px(280.0),
+                            height: Val::Px(56.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::NONE),
+                        BorderColor::all(colors::BTN_SECONDARY_BORDER),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            GameModeButtonText,
+                            Text::new(game_mode_label(*game_mode)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(colors::TEXT),
+                        ));
+                    });
+
                     // "EXIT" — secondary outlined button
                     // Website: bg transparent, border 1px rgba(255,255,255,0.15), color --text
                     col.spawn((
@@ -181,6 +264,75 @@ pub fn handle_new_game_button(
     }
 }
 
+/// Handle the Restore Session button: load the autosave journal and start
+/// the game from it.
+#[allow(clippy::type_complexity)]
+pub fn handle_restore_button(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RestoreButton>),
+    >,
+    autosave_config: Res<AutosaveConfig>,
+    mut universe: ResMut<Universe>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut bg, mut border) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match autosave::load_restore(&autosave_config.dir, &mut universe) {
+                    Ok(restore) => {
+                        *game_mode = restore.manifest.game_mode;
+                        commands.insert_resource(restore);
+                        next_state.set(AppState::Loading);
+                    }
+                    Err(err) => warn!("Can't restore the autosave: {err}"),
+                }
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(colors::BTN_SECONDARY_HOVER_BG);
+                *border = BorderColor::all(colors::BTN_SECONDARY_HOVER_BORDER);
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(Color::NONE);
+                *border = BorderColor::all(colors::BTN_SECONDARY_BORDER);
+            }
+        }
+    }
+}
+
+/// Handle the game mode button: each press switches the mode the next world
+/// starts in.
+#[allow(clippy::type_complexity)]
+pub fn handle_game_mode_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<GameModeButton>),
+    >,
+    mut label_query: Query<&mut Text, With<GameModeButtonText>>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    for (interaction, mut bg, mut border) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *game_mode = game_mode.toggled();
+                for mut text in &mut label_query {
+                    **text = game_mode_label(*game_mode);
+                }
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(colors::BTN_SECONDARY_HOVER_BG);
+                *border = BorderColor::all(colors::BTN_SECONDARY_HOVER_BORDER);
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(Color::NONE);
+                *border = BorderColor::all(colors::BTN_SECONDARY_BORDER);
+            }
+        }
+    }
+}
+
 /// Handle Exit button interaction.
 /// Website hover: border-color -> --accent, bg -> rgba(92,184,255,0.05)
 pub fn handle_exit_button(
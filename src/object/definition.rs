@@ -136,6 +136,14 @@ impl ObjectDef {
             equipment_slot: None,
             stats: None,
             blueprint_item: None,
+            action: None,
+            use_cooldown: None,
+            item_cooldown: None,
+            projectile: None,
+            light: None,
+            bag_rows: None,
+            effects: Vec::new(),
+            tile_conversions: Vec::new(),
         })
     }
 
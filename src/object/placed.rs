@@ -7,7 +7,7 @@ use super::definition::ObjectId;
 /// Reference from an occupancy grid cell to the object that occupies it.
 /// `data_chunk` stores the chunk coordinates where the PlacedObject lives,
 /// which may differ from the chunk this occupancy cell is in (for multi-tile objects).
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct OccupancyRef {
     pub object_index: u16,
     pub is_anchor: bool,
@@ -6,7 +6,7 @@ use bevy::sprite_render::MeshMaterial2d;
 use super::definition::ObjectId;
 use super::registry::ObjectRegistry;
 use crate::registry::AppState;
-use crate::sets::GameSet;
+use crate::sets::WorldSet;
 use crate::world::lit_sprite::{FallbackLightmap, LitSpriteMaterial};
 
 /// Per-type template materials and animation metadata for rendered objects.
@@ -142,7 +142,7 @@ impl Plugin for ObjectPlugin {
             Update,
             (
                 load_object_sprites.run_if(in_state(AppState::InGame)),
-                object_animation_system.in_set(GameSet::WorldUpdate),
+                object_animation_system.in_set(WorldSet::Sim),
             ),
         );
     }
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use crate::camera::aspect::{view_size, AspectLock};
+
 use super::spawn::{ParallaxLayerConfig, ParallaxLayerState, ParallaxTile};
 
 /// Scroll parallax layers based on camera position.
@@ -20,6 +22,7 @@ pub fn parallax_scroll(
     mut commands: Commands,
     camera_query: Query<(&Transform, &Projection), With<Camera2d>>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    aspect_lock: Option<Res<AspectLock>>,
     images: Res<Assets<Image>>,
     mut layer_query: Query<
         (
@@ -56,8 +59,9 @@ pub fn parallax_scroll(
 
     let cam_x = camera_tf.translation.x;
     let cam_y = camera_tf.translation.y;
-    let visible_w = window.width() * proj_scale;
-    let visible_h = window.height() * proj_scale;
+    let view = view_size(window, aspect_lock.as_deref());
+    let visible_w = view.x * proj_scale;
+    let visible_h = view.y * proj_scale;
 
     for (entity, config, mut state, mut transform, mut visibility, sprite) in &mut layer_query {
         // Resolve texture size on first frame the image is available
@@ -3,10 +3,9 @@ use bevy::prelude::*;
 use crate::cosmos::ship_location::GlobalBiome;
 use crate::player::Player;
 use crate::registry::biome::BiomeId;
-use crate::registry::world::ActiveWorld;
 use crate::registry::BiomeParallaxConfigs;
-use crate::world::biome_map::BiomeMap;
 use crate::world::chunk::world_to_tile;
+use crate::world::ctx::WorldCtx;
 
 use super::spawn::{ParallaxLayerConfig, ParallaxLayerState, ParallaxSkyLayer};
 
@@ -43,8 +42,7 @@ const TRANSITION_DURATION: f32 = 1.5;
 pub fn track_player_biome(
     mut commands: Commands,
     player_query: Query<&Transform, With<Player>>,
-    wc: Res<ActiveWorld>,
-    biome_map: Res<BiomeMap>,
+    world: WorldCtx,
     global_biome: Option<Res<GlobalBiome>>,
     current_biome: Option<Res<CurrentBiome>>,
     transition: Option<Res<ParallaxTransition>>,
@@ -61,13 +59,12 @@ pub fn track_player_biome(
     let new_biome = if let Some(ref global) = global_biome {
         global.biome_id
     } else {
-        let (tile_x, _) = world_to_tile(
+        let (tile_x, tile_y) = world_to_tile(
             player_tf.translation.x,
             player_tf.translation.y,
-            wc.tile_size,
+            world.config.tile_size,
         );
-        let wrapped_x = wc.wrap_tile_x(tile_x) as u32;
-        biome_map.biome_at(wrapped_x)
+        world.as_ref().biome_at_tile(tile_x, tile_y)
     };
 
     // Initialize on first frame
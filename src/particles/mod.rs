@@ -10,7 +10,7 @@ pub use pool::{ParticleConfig, ParticlePool};
 pub use render::ParticleMeshEntity;
 
 use crate::registry::AppState;
-use crate::sets::GameSet;
+use crate::sets::WorldSet;
 
 pub struct ParticlePlugin;
 
@@ -23,7 +23,7 @@ impl Plugin for ParticlePlugin {
                 Update,
                 (physics::particle_physics, render::rebuild_particle_mesh)
                     .chain()
-                    .in_set(GameSet::WorldUpdate)
+                    .in_set(WorldSet::Sim)
                     .run_if(in_state(AppState::InGame))
                     .run_if(resource_exists::<render::SharedParticleMaterial>),
             );
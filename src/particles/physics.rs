@@ -5,8 +5,14 @@ use crate::particles::ParticleConfig;
 use crate::registry::tile::TileRegistry;
 use crate::registry::world::ActiveWorld;
 use crate::world::chunk::WorldMap;
+use crate::world::culling::{ChunkBands, DistanceBand, Throttle};
 
 /// Update particle physics: move, age, apply gravity, kill on solid tile.
+///
+/// Particles are throttled by the [`DistanceBand`] of the chunk they are in,
+/// with their pool index as the phase. `Far` particles are never seen, so
+/// they are killed to free their slots.
+#[allow(clippy::too_many_arguments)]
 pub fn particle_physics(
     mut pool: ResMut<ParticlePool>,
     config: Res<ParticleConfig>,
@@ -14,15 +20,25 @@ pub fn particle_physics(
     world_map: Res<WorldMap>,
     tile_registry: Res<TileRegistry>,
     active_world: Res<ActiveWorld>,
+    bands: Option<Res<ChunkBands>>,
+    throttle: Throttle,
 ) {
-    let dt = time.delta_secs();
+    let frame_dt = time.delta_secs();
     let tile_size = active_world.tile_size;
     let chunk_size = active_world.chunk_size;
 
-    for p in &mut pool.particles {
+    for (index, p) in pool.particles.iter_mut().enumerate() {
         if p.is_dead() {
             continue;
         }
+        let band = bands
+            .as_ref()
+            .map_or(DistanceBand::Near, |b| b.band_at(p.position, &active_world));
+        let Some(frames) = throttle.frames(Some(&band), index as u32) else {
+            p.alive = false;
+            continue;
+        };
+        let dt = frame_dt * frames as f32;
 
         // Apply gravity scaled by particle's gravity_scale.
         // Negative scale = particle floats upward (e.g. bubbles).
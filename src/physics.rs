@@ -5,10 +5,12 @@ use crate::liquid::data::{LiquidCell, LiquidId};
 use crate::liquid::registry::LiquidRegistry;
 use crate::math::{tile_aabb, Aabb};
 use crate::object::registry::ObjectRegistry;
+use crate::registry::planet_physics::PlanetPhysics;
 use crate::registry::player::PlayerConfig;
 use crate::sets::GameSet;
-use crate::world::chunk::{self, WorldMap};
+use crate::world::chunk::{self, Layer, WorldMap};
 use crate::world::ctx::WorldCtx;
+use crate::world::culling::{entity_phase, DistanceBand, Throttle};
 
 /// Maximum delta time to prevent physics tunneling on lag spikes.
 pub const MAX_DELTA_SECS: f32 = 1.0 / 20.0;
@@ -19,6 +21,45 @@ const BOUNCE_THRESHOLD: f32 = 5.0;
 /// Horizontal velocity damping applied on each bounce.
 const BOUNCE_HORIZONTAL_DAMPING: f32 = 0.9;
 
+// ---------------------------------------------------------------------------
+// Resources
+// ---------------------------------------------------------------------------
+
+/// Collision sub-stepping for fast movers.
+///
+/// A tick that would move an entity further than `max_step` tiles along
+/// either axis is split into equal sub-steps, each moving and resolving
+/// collisions on its own, so fast entities can't skip over a wall or cut a
+/// corner. The split is capped at `max_substeps`.
+#[derive(Resource, Debug, Clone)]
+pub struct CollisionSubsteps {
+    /// Largest movement per sub-step along either axis, in tiles.
+    pub max_step: f32,
+    /// Upper bound on sub-steps per tick.
+    pub max_substeps: u32,
+}
+
+impl Default for CollisionSubsteps {
+    fn default() -> Self {
+        Self {
+            max_step: 0.5,
+            max_substeps: 8,
+        }
+    }
+}
+
+impl CollisionSubsteps {
+    /// Sub-steps needed to move at `vel` for `dt` seconds.
+    pub fn count(&self, vel: &Velocity, dt: f32, tile_size: f32) -> u32 {
+        let max_step = self.max_step * tile_size;
+        if max_step <= 0.0 {
+            return self.max_substeps.max(1);
+        }
+        let distance = vel.x.abs().max(vel.y.abs()) * dt;
+        ((distance / max_step).ceil() as u32).clamp(1, self.max_substeps.max(1))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Components
 // ---------------------------------------------------------------------------
@@ -34,6 +75,10 @@ pub struct Velocity {
 #[derive(Component, Debug)]
 pub struct Gravity(pub f32);
 
+/// Maximum downward speed in pixels per second.
+#[derive(Component, Debug)]
+pub struct TerminalVelocity(pub f32);
+
 /// Whether the entity is resting on a solid surface.
 #[derive(Component, Debug)]
 pub struct Grounded(pub bool);
@@ -55,6 +100,11 @@ pub struct Friction(pub f32);
 #[derive(Component, Debug)]
 pub struct Bounce(pub f32);
 
+/// A body at rest that gravity, collision and friction skip until something
+/// wakes it (see `item::drop_sleep`).
+#[derive(Component, Debug)]
+pub struct Sleeping;
+
 /// Gentle vertical oscillation while grounded (e.g. dropped items).
 #[derive(Component, Debug)]
 pub struct BobEffect {
@@ -85,6 +135,15 @@ impl Submerged {
     }
 }
 
+/// Holding on to a climbable tile (rope, ladder). Gravity skips the entity
+/// while `active`; `player::climbing` moves it instead.
+#[derive(Component, Debug, Default)]
+pub struct Climbing {
+    pub active: bool,
+    /// Seconds before the entity may grab on again after letting go.
+    pub regrab_cooldown: f32,
+}
+
 // ---------------------------------------------------------------------------
 // Plugin
 // ---------------------------------------------------------------------------
@@ -93,7 +152,7 @@ pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<CollisionSubsteps>().add_systems(
             Update,
             (
                 apply_gravity,
@@ -116,17 +175,44 @@ impl Plugin for PhysicsPlugin {
 /// If the entity has a `Submerged` component and is swimming, gravity is reduced
 /// by the configured `swim_gravity_factor`.
 /// If the entity has an `InVacuum` component and is in vacuum, gravity is zero.
+/// If the entity has a `TerminalVelocity`, falling speed is capped at it.
+/// Both are scaled by the planet's gravity scale.
+/// `Sleeping` bodies and entities holding on to a climbable tile are skipped.
+/// Entities with a [`DistanceBand`] are throttled by it.
+#[allow(clippy::type_complexity)]
 pub fn apply_gravity(
     time: Res<Time>,
     player_config: Option<Res<PlayerConfig>>,
-    mut query: Query<(&mut Velocity, &Gravity, Option<&Submerged>, Option<&InVacuum>)>,
+    planet: Option<Res<PlanetPhysics>>,
+    throttle: Throttle,
+    mut query: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &Gravity,
+            Option<&Submerged>,
+            Option<&InVacuum>,
+            Option<&TerminalVelocity>,
+            Option<&Climbing>,
+            Option<&DistanceBand>,
+        ),
+        Without<Sleeping>,
+    >,
 ) {
-    let dt = time.delta_secs().min(MAX_DELTA_SECS);
-    for (mut vel, gravity, submerged, in_vacuum) in &mut query {
+    let frame_dt = time.delta_secs().min(MAX_DELTA_SECS);
+    let scale = planet.map_or(1.0, |p| p.gravity_scale);
+    for (entity, mut vel, gravity, submerged, in_vacuum, terminal, climbing, band) in &mut query {
+        let Some(frames) = throttle.frames(band, entity_phase(entity)) else {
+            continue;
+        };
+        let dt = frame_dt * frames as f32;
         // Zero gravity in vacuum
         if in_vacuum.is_some_and(|v| v.0) {
             continue;
         }
+        if climbing.is_some_and(|c| c.active) {
+            continue;
+        }
 
         let gravity_factor = match submerged {
             Some(sub) if sub.is_swimming() => player_config
@@ -135,59 +221,42 @@ pub fn apply_gravity(
                 .unwrap_or(0.3),
             _ => 1.0,
         };
-        vel.y -= gravity.0 * gravity_factor * dt;
+        vel.y -= gravity.0 * gravity_factor * scale * dt;
+        if let Some(terminal) = terminal {
+            vel.y = vel.y.max(-terminal.0 * scale);
+        }
     }
 }
 
-/// Resolve tile collisions for all entities with `TileCollider`.
+/// Move an entity's collider at `pos` by `vel` for `dt` seconds in
+/// `substeps` equal steps, resolving tile collisions after each.
 ///
 /// Axes are resolved independently (X then Y) to prevent corner sticking.
-/// Optional `Grounded` is set when the entity lands on a solid tile.
-/// Optional `Bounce` causes the entity to bounce off the ground.
-/// Optional `BobEffect` is paused during physics and resumed after resolution.
-pub fn tile_collision(
-    time: Res<Time>,
-    ctx: WorldCtx,
-    world_map: Res<WorldMap>,
-    object_registry: Option<Res<ObjectRegistry>>,
-    mut query: Query<(
-        &mut Transform,
-        &mut Velocity,
-        &TileCollider,
-        Option<&mut Grounded>,
-        Option<&Bounce>,
-        Option<&mut BobEffect>,
-    )>,
-) {
-    let dt = time.delta_secs().min(MAX_DELTA_SECS);
-    let ts = ctx.config.tile_size;
-    let ctx_ref = ctx.as_ref();
-
-    let is_solid = |tx: i32, ty: i32| -> bool {
-        match &object_registry {
-            Some(reg) => world_map.is_solid_or_object(tx, ty, &ctx_ref, reg),
-            None => world_map.is_solid(tx, ty, &ctx_ref),
-        }
-    };
-
-    for (mut tf, mut vel, collider, mut grounded, bounce, mut bob) in &mut query {
-        let pos = &mut tf.translation;
-        let w = collider.width;
-        let h = collider.height;
-
-        // Remove bob offset before physics so collision uses the true rest position
-        if let Some(ref bob) = bob {
-            if grounded.as_ref().is_some_and(|g| g.0) {
-                pos.y = bob.rest_y;
-            }
-        }
-
+/// `bounce` is the coefficient of restitution for ground hits. Returns true
+/// if the entity landed on a solid tile and still stands on one after the
+/// last step.
+#[allow(clippy::too_many_arguments)]
+pub fn move_and_collide(
+    pos: &mut Vec3,
+    vel: &mut Velocity,
+    size: Vec2,
+    bounce: f32,
+    dt: f32,
+    substeps: u32,
+    tile_size: f32,
+    is_solid: impl Fn(i32, i32) -> bool,
+) -> bool {
+    let (w, h) = (size.x, size.y);
+    let step_dt = dt / substeps.max(1) as f32;
+    let mut landed = false;
+
+    for _ in 0..substeps.max(1) {
         // --- Resolve X axis ---
-        pos.x += vel.x * dt;
+        pos.x += vel.x * step_dt;
         let aabb = Aabb::from_center(pos.x, pos.y, w, h);
-        for (tx, ty) in aabb.overlapping_tiles(ts) {
+        for (tx, ty) in aabb.overlapping_tiles(tile_size) {
             if is_solid(tx, ty) {
-                let tile = tile_aabb(tx, ty, ts);
+                let tile = tile_aabb(tx, ty, tile_size);
                 let entity_aabb = Aabb::from_center(pos.x, pos.y, w, h);
                 if entity_aabb.overlaps(&tile) {
                     if vel.x > 0.0 {
@@ -201,28 +270,22 @@ pub fn tile_collision(
         }
 
         // --- Resolve Y axis ---
-        pos.y += vel.y * dt;
-        if let Some(ref mut g) = grounded {
-            g.0 = false;
-        }
+        pos.y += vel.y * step_dt;
         let aabb = Aabb::from_center(pos.x, pos.y, w, h);
-        for (tx, ty) in aabb.overlapping_tiles(ts) {
+        for (tx, ty) in aabb.overlapping_tiles(tile_size) {
             if is_solid(tx, ty) {
-                let tile = tile_aabb(tx, ty, ts);
+                let tile = tile_aabb(tx, ty, tile_size);
                 let entity_aabb = Aabb::from_center(pos.x, pos.y, w, h);
                 if entity_aabb.overlaps(&tile) {
                     if vel.y < 0.0 {
                         pos.y = tile.max_y + h / 2.0;
-                        let bounce_factor = bounce.map(|b| b.0).unwrap_or(0.0);
-                        let bounced = -vel.y * bounce_factor;
+                        let bounced = -vel.y * bounce;
                         if bounced > BOUNCE_THRESHOLD {
                             vel.y = bounced;
                             vel.x *= BOUNCE_HORIZONTAL_DAMPING;
                         } else {
                             vel.y = 0.0;
-                            if let Some(ref mut g) = grounded {
-                                g.0 = true;
-                            }
+                            landed = true;
                         }
                     } else if vel.y > 0.0 {
                         pos.y = tile.min_y - h / 2.0;
@@ -231,6 +294,168 @@ pub fn tile_collision(
                 }
             }
         }
+    }
+    // A landing in an early step doesn't count if later steps slid the
+    // entity off the ledge it landed on.
+    landed && stands_on_solid(*pos, size, tile_size, is_solid)
+}
+
+/// Whether a solid tile lies directly under the bottom edge of a collider
+/// of `size` centered at `pos`.
+fn stands_on_solid(
+    pos: Vec3,
+    size: Vec2,
+    tile_size: f32,
+    is_solid: impl Fn(i32, i32) -> bool,
+) -> bool {
+    let feet = pos.y - size.y / 2.0;
+    let below = Aabb {
+        min_x: pos.x - size.x / 2.0,
+        max_x: pos.x + size.x / 2.0,
+        min_y: feet - 1.0,
+        max_y: feet,
+    };
+    below
+        .overlapping_tiles(tile_size)
+        .any(|(tx, ty)| is_solid(tx, ty) && below.overlaps(&tile_aabb(tx, ty, tile_size)))
+}
+
+/// Movement multiplier for a body whose collider overlaps tiles with
+/// viscosity: `1 / (1 + v)` for the thickest overlapped tile, 1.0 when none
+/// is viscous.
+pub fn viscous_drag(aabb: &Aabb, tile_size: f32, viscosity_at: impl Fn(i32, i32) -> f32) -> f32 {
+    let thickest = aabb
+        .overlapping_tiles(tile_size)
+        .map(|(tx, ty)| viscosity_at(tx, ty))
+        .fold(0.0f32, f32::max);
+    1.0 / (1.0 + thickest)
+}
+
+/// Find the nearest position where an AABB of size `w`×`h` overlaps no solid tile.
+///
+/// Tries `center` first, then whole-tile offsets of increasing distance,
+/// preferring upward moves so an entity stuck in terrain pops out on top.
+/// Returns `None` if no free spot exists within `max_tiles`.
+pub fn find_air_pocket(
+    center: Vec2,
+    w: f32,
+    h: f32,
+    tile_size: f32,
+    max_tiles: i32,
+    is_solid: impl Fn(i32, i32) -> bool,
+) -> Option<Vec2> {
+    let fits = |pos: Vec2| {
+        !Aabb::from_center(pos.x, pos.y, w, h)
+            .overlapping_tiles(tile_size)
+            .any(|(tx, ty)| is_solid(tx, ty))
+    };
+    if fits(center) {
+        return Some(center);
+    }
+    for step in 1..=max_tiles {
+        let d = step as f32 * tile_size;
+        for offset in [
+            Vec2::new(0.0, d),
+            Vec2::new(-d, 0.0),
+            Vec2::new(d, 0.0),
+            Vec2::new(0.0, -d),
+        ] {
+            if fits(center + offset) {
+                return Some(center + offset);
+            }
+        }
+    }
+    None
+}
+
+/// Resolve tile collisions for all entities with `TileCollider`.
+///
+/// Movement is split into [`CollisionSubsteps`] for fast entities; see
+/// [`move_and_collide`].
+/// Non-solid fg tiles with a `viscosity` (tall grass, cobwebs) slow the
+/// movement of bodies overlapping them by [`viscous_drag`]; velocity itself
+/// is kept, so full speed returns as soon as the body is clear.
+/// Optional `Grounded` is set when the entity lands on a solid tile.
+/// Optional `Bounce` causes the entity to bounce off the ground.
+/// Optional `BobEffect` is paused during physics and resumed after resolution.
+/// `Sleeping` bodies are skipped and banded ones throttled.
+#[allow(clippy::type_complexity)]
+pub fn tile_collision(
+    time: Res<Time>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    object_registry: Option<Res<ObjectRegistry>>,
+    substeps: Option<Res<CollisionSubsteps>>,
+    throttle: Throttle,
+    mut query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &TileCollider,
+            Option<&mut Grounded>,
+            Option<&Bounce>,
+            Option<&mut BobEffect>,
+            Option<&DistanceBand>,
+        ),
+        Without<Sleeping>,
+    >,
+) {
+    let frame_dt = time.delta_secs().min(MAX_DELTA_SECS);
+    let ts = ctx.config.tile_size;
+    let ctx_ref = ctx.as_ref();
+    let substeps = substeps.map(|s| s.clone()).unwrap_or_default();
+
+    let is_solid = |tx: i32, ty: i32| -> bool {
+        match &object_registry {
+            Some(reg) => world_map.is_solid_or_object(tx, ty, &ctx_ref, reg),
+            None => world_map.is_solid(tx, ty, &ctx_ref),
+        }
+    };
+    let viscosity_at = |tx: i32, ty: i32| -> f32 {
+        world_map
+            .get_tile(tx, ty, Layer::Fg, &ctx_ref)
+            .map(|tile| ctx_ref.tile_registry.get(tile))
+            .filter(|def| !def.solid)
+            .map_or(0.0, |def| def.viscosity.max(0.0))
+    };
+
+    for (entity, mut tf, mut vel, collider, mut grounded, bounce, mut bob, band) in &mut query {
+        let Some(frames) = throttle.frames(band, entity_phase(entity)) else {
+            continue;
+        };
+        let dt = frame_dt * frames as f32;
+        let pos = &mut tf.translation;
+
+        // Remove bob offset before physics so collision uses the true rest position
+        if let Some(ref bob) = bob {
+            if grounded.as_ref().is_some_and(|g| g.0) {
+                pos.y = bob.rest_y;
+            }
+        }
+
+        let size = Vec2::new(collider.width, collider.height);
+        let drag = viscous_drag(
+            &Aabb::from_center(pos.x, pos.y, size.x, size.y),
+            ts,
+            viscosity_at,
+        );
+        let dt = dt * drag;
+
+        let steps = substeps.count(&vel, dt, ts);
+        let landed = move_and_collide(
+            pos,
+            &mut vel,
+            size,
+            bounce.map(|b| b.0).unwrap_or(0.0),
+            dt,
+            steps,
+            ts,
+            is_solid,
+        );
+        if let Some(ref mut g) = grounded {
+            g.0 = landed;
+        }
 
         // Store rest_y for bob after collision resolution
         if let Some(ref mut bob) = bob {
@@ -314,11 +539,28 @@ pub fn update_submersion(
     }
 }
 
-/// Damp horizontal velocity while grounded.
-pub fn apply_friction(mut query: Query<(&mut Velocity, &Grounded, &Friction)>) {
-    for (mut vel, grounded, friction) in &mut query {
+/// Damp horizontal velocity while grounded. `Sleeping` bodies are skipped and
+/// banded ones throttled.
+#[allow(clippy::type_complexity)]
+pub fn apply_friction(
+    throttle: Throttle,
+    mut query: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &Grounded,
+            &Friction,
+            Option<&DistanceBand>,
+        ),
+        Without<Sleeping>,
+    >,
+) {
+    for (entity, mut vel, grounded, friction, band) in &mut query {
+        let Some(frames) = throttle.frames(band, entity_phase(entity)) else {
+            continue;
+        };
         if grounded.0 {
-            vel.x *= friction.0;
+            vel.x *= friction.0.powi(frames as i32);
         }
     }
 }
@@ -341,6 +583,7 @@ pub fn apply_bob(time: Res<Time>, mut query: Query<(&mut Transform, &mut BobEffe
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::world::ActiveWorld;
     use crate::test_helpers::fixtures;
     use crate::world::chunk::WorldMap;
     use crate::world::terrain_gen;
@@ -388,6 +631,43 @@ mod tests {
         assert_eq!(vel.y, 5.0, "y should be unchanged without Gravity");
     }
 
+    /// Vertical velocity after a few 60 FPS frames of falling with gravity
+    /// 600 on a planet with `gravity_scale`.
+    fn fall_velocity(gravity_scale: f32, terminal: f32) -> f32 {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(1.0 / 60.0),
+        ))
+        .insert_resource(PlanetPhysics {
+            gravity_scale,
+            ..default()
+        })
+        .add_systems(Update, apply_gravity);
+        let entity = app
+            .world_mut()
+            .spawn((
+                Velocity::default(),
+                Gravity(600.0),
+                TerminalVelocity(terminal),
+            ))
+            .id();
+        for _ in 0..3 {
+            app.update();
+        }
+        app.world().get::<Velocity>(entity).unwrap().y
+    }
+
+    #[test]
+    fn planet_gravity_scale_scales_gravity_and_terminal_velocity() {
+        let full = fall_velocity(1.0, 1000.0);
+        let moon = fall_velocity(0.25, 1000.0);
+        assert!(full < 0.0);
+        assert!((moon - full * 0.25).abs() < 0.01, "{moon} vs {full}");
+
+        assert_eq!(fall_velocity(1.0, 5.0), -5.0);
+        assert_eq!(fall_velocity(0.25, 5.0), -1.25);
+    }
+
     // -----------------------------------------------------------------------
     // Tile collision tests
     // -----------------------------------------------------------------------
@@ -770,6 +1050,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn viscous_drag_follows_the_thickest_overlapped_tile() {
+        let aabb = Aabb::from_center(32.0, 16.0, 8.0, 8.0);
+        assert_eq!(viscous_drag(&aabb, 32.0, |_, _| 0.0), 1.0);
+        // Straddles tiles 0 and 1; only tile 1 is viscous.
+        let drag = viscous_drag(&aabb, 32.0, |tx, _| if tx == 1 { 3.0 } else { 0.0 });
+        assert_eq!(drag, 0.25);
+    }
+
+    #[test]
+    fn viscous_non_solid_tiles_slow_movement_until_exited() {
+        use crate::registry::tile::{TileId, TileRegistry};
+
+        let mut defs = fixtures::test_tile_registry().defs;
+        let mut cobweb = defs[0].clone();
+        cobweb.id = "cobweb".into();
+        cobweb.viscosity = 1.0;
+        defs.push(cobweb);
+        let cobweb = TileId(defs.len() as u16 - 1);
+        let tile_registry = TileRegistry::from_defs(defs);
+
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(MAX_DELTA_SECS),
+        ))
+        .add_systems(Update, tile_collision);
+
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tile_registry, &pc, &nc);
+        let ts = wc.tile_size;
+        let air_ty = wc.height_tiles - 10;
+        let mut world_map = WorldMap::default();
+        for tx in 10..13 {
+            world_map.set_tile(tx, air_ty, Layer::Fg, cobweb, &ctx);
+        }
+        *app.world_mut().resource_mut::<WorldMap>() = world_map;
+        app.insert_resource(tile_registry);
+
+        let y = (air_ty as f32 + 0.5) * ts;
+        let start = 10.5 * ts;
+        let entity = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(start, y, 0.0),
+                Velocity { x: 100.0, y: 0.0 },
+                TileCollider {
+                    width: 8.0,
+                    height: 8.0,
+                },
+            ))
+            .id();
+        let x = |app: &App| app.world().get::<Transform>(entity).unwrap().translation.x;
+
+        // The first update has no elapsed time.
+        app.update();
+        app.update();
+        let full_step = 100.0 * MAX_DELTA_SECS;
+        assert!(
+            (x(&app) - start - full_step / 2.0).abs() < 1e-3,
+            "halved in the web"
+        );
+        assert_eq!(app.world().get::<Velocity>(entity).unwrap().x, 100.0);
+
+        // Clear of the web, full speed again.
+        let clear = 20.5 * ts;
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Transform::from_xyz(clear, y, 0.0));
+        app.update();
+        assert!(
+            (x(&app) - clear - full_step).abs() < 1e-3,
+            "full speed outside"
+        );
+    }
+
+    /// Horizontal velocity left after an entity overlapping the left world
+    /// edge by 2px pushes further left.
+    fn push_into_left_edge(wrap_x: bool, border_tile: Option<&str>) -> f32 {
+        let mut app = fixtures::test_app();
+        app.add_systems(Update, tile_collision);
+        app.world_mut().resource_mut::<ActiveWorld>().wrap_x = wrap_x;
+        app.world_mut()
+            .resource_mut::<crate::registry::biome::PlanetConfig>()
+            .border_tile = border_tile.map(str::to_owned);
+
+        // High in the sky, where every in-world tile is air.
+        let ts = app.world().resource::<ActiveWorld>().tile_size;
+        let sky_y = (fixtures::test_active_world().height_tiles - 10) as f32 * ts;
+        app.world_mut().spawn((
+            Transform::from_xyz(2.0, sky_y, 0.0),
+            Velocity { x: -500.0, y: 0.0 },
+            TileCollider {
+                width: 8.0,
+                height: 8.0,
+            },
+        ));
+        app.update();
+
+        let mut query = app.world_mut().query::<&Velocity>();
+        query.iter(app.world()).next().unwrap().x
+    }
+
+    #[test]
+    fn walled_world_edge_stops_horizontal_movement() {
+        assert_eq!(push_into_left_edge(false, Some("stone")), 0.0);
+        // Open edges and wrapping worlds let the entity through.
+        assert_eq!(push_into_left_edge(false, None), -500.0);
+        assert_eq!(push_into_left_edge(true, Some("stone")), -500.0);
+    }
+
     #[test]
     fn multiple_entities_collide_independently() {
         let mut app = fixtures::test_app();
@@ -861,6 +1251,121 @@ mod tests {
         assert_eq!(vel.y, -50.0, "friction should only affect x, not y");
     }
 
+    // -----------------------------------------------------------------------
+    // Sub-stepping tests
+    // -----------------------------------------------------------------------
+
+    /// Inner corner: a wall at tile column 3 and a floor at tile row 0.
+    fn corner_solid(tx: i32, ty: i32) -> bool {
+        (tx == 3 && (0..=4).contains(&ty)) || (ty == 0 && (-2..=3).contains(&tx))
+    }
+
+    #[test]
+    fn substep_count_scales_with_speed_and_is_bounded() {
+        let substeps = CollisionSubsteps::default();
+        let ts = 32.0;
+        let dt = MAX_DELTA_SECS;
+        let slow = Velocity { x: 100.0, y: 0.0 };
+        assert_eq!(substeps.count(&slow, dt, ts), 1);
+        // 1500 px/s for 0.05 s = 75 px, in steps of at most 16 px.
+        let fast = Velocity {
+            x: 300.0,
+            y: -1500.0,
+        };
+        assert_eq!(substeps.count(&fast, dt, ts), 5);
+        let absurd = Velocity { x: 1.0e9, y: 0.0 };
+        assert_eq!(substeps.count(&absurd, dt, ts), substeps.max_substeps);
+        assert_eq!(substeps.count(&Velocity::default(), 0.0, ts), 1);
+    }
+
+    #[test]
+    fn fast_diagonal_move_into_corner_hits_both_walls() {
+        let ts = 32.0;
+        let size = Vec2::splat(8.0);
+        let dt = MAX_DELTA_SECS;
+        // 10 px from the wall and 10 px above the floor, moving 75 px per
+        // axis this tick: more than a whole tile.
+        let start = Vec3::new(3.0 * ts - 14.0, ts + 14.0, 0.0);
+        let launch = || Velocity {
+            x: 1500.0,
+            y: -1500.0,
+        };
+
+        // A single step jumps clean over both.
+        let mut pos = start;
+        let mut vel = launch();
+        let landed = move_and_collide(&mut pos, &mut vel, size, 0.0, dt, 1, ts, corner_solid);
+        assert!(!landed);
+        assert!(pos.x - size.x / 2.0 > 3.0 * ts, "clipped through the wall");
+        assert!(pos.y + size.y / 2.0 < 0.0, "clipped through the floor");
+
+        let mut pos = start;
+        let mut vel = launch();
+        let steps = CollisionSubsteps::default().count(&vel, dt, ts);
+        let landed = move_and_collide(&mut pos, &mut vel, size, 0.0, dt, steps, ts, corner_solid);
+        assert!(landed, "should land on the floor");
+        assert_eq!(pos.x + size.x / 2.0, 3.0 * ts, "flush against the wall");
+        assert_eq!(pos.y - size.y / 2.0, ts, "resting on the floor");
+        assert_eq!((vel.x, vel.y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn flung_dropped_item_does_not_tunnel_through_one_tile_floor() {
+        let ts = 32.0;
+        // Dropped item collider, 40 px above a one-tile floor at row 0,
+        // thrown down at 120 px per tick.
+        let size = Vec2::splat(4.0);
+        let dt = MAX_DELTA_SECS;
+        let floor = |_: i32, ty: i32| ty == 0;
+        let start = Vec3::new(ts / 2.0, ts + 42.0, 0.0);
+        let launch = || Velocity { x: 0.0, y: -2400.0 };
+
+        let mut pos = start;
+        let mut vel = launch();
+        move_and_collide(&mut pos, &mut vel, size, 0.3, dt, 1, ts, floor);
+        assert!(pos.y < 0.0, "one step should skip the floor");
+
+        let mut pos = start;
+        let mut vel = launch();
+        let steps = CollisionSubsteps::default().count(&vel, dt, ts);
+        move_and_collide(&mut pos, &mut vel, size, 0.3, dt, steps, ts, floor);
+        assert!(
+            pos.y - size.y / 2.0 >= ts,
+            "fell through the floor to y = {}",
+            pos.y
+        );
+        assert!(vel.y >= 0.0, "should bounce or rest, got {}", vel.y);
+    }
+
+    #[test]
+    fn landing_then_sliding_off_a_ledge_is_not_grounded() {
+        let ts = 32.0;
+        let size = Vec2::splat(8.0);
+        let dt = MAX_DELTA_SECS;
+        // A one-tile ledge at (0, 0). The body lands on it in the first of
+        // four steps, then slides 45 px right over the drop.
+        let ledge = |tx: i32, ty: i32| (tx, ty) == (0, 0);
+        let start = Vec3::new(ts / 2.0, ts + 7.0, 0.0);
+        let launch = || Velocity {
+            x: 1200.0,
+            y: -400.0,
+        };
+
+        let mut pos = start;
+        let mut vel = launch();
+        assert!(!move_and_collide(
+            &mut pos, &mut vel, size, 0.0, dt, 4, ts, ledge
+        ));
+        assert!(pos.x - size.x / 2.0 > ts, "should end past the ledge");
+
+        // Without the slide the landing stands.
+        let mut pos = start;
+        let mut vel = Velocity { x: 0.0, ..launch() };
+        assert!(move_and_collide(
+            &mut pos, &mut vel, size, 0.0, dt, 4, ts, ledge
+        ));
+    }
+
     // -----------------------------------------------------------------------
     // Bob tests (continued)
     // -----------------------------------------------------------------------
@@ -988,4 +1493,29 @@ mod tests {
             vel.y
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Air pocket search tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn air_pocket_keeps_position_when_already_free() {
+        let pos = Vec2::new(48.0, 80.0);
+        let found = find_air_pocket(pos, 16.0, 32.0, 32.0, 4, |_, _| false);
+        assert_eq!(found, Some(pos));
+    }
+
+    #[test]
+    fn air_pocket_prefers_moving_up_out_of_floor() {
+        // Solid ground at ty <= 2; entity bottom sunk into tile 2.
+        let pos = Vec2::new(48.0, 3.0 * 32.0 + 8.0);
+        let found = find_air_pocket(pos, 16.0, 32.0, 32.0, 4, |_, ty| ty <= 2);
+        assert_eq!(found, Some(pos + Vec2::new(0.0, 32.0)));
+    }
+
+    #[test]
+    fn air_pocket_gives_up_when_fully_enclosed() {
+        let found = find_air_pocket(Vec2::new(48.0, 48.0), 16.0, 32.0, 32.0, 3, |_, _| true);
+        assert_eq!(found, None);
+    }
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
+use crate::interaction::target::TargetTile;
 use crate::inventory::Hotbar;
 use crate::player::animation::AnimationState;
 use crate::player::parts::{ArmAiming, CharacterPart};
@@ -9,24 +9,14 @@ use crate::player::Player;
 /// Rotates arm children toward the mouse cursor when an item is in the active hotbar slot.
 /// Also overrides facing direction on all children based on cursor position.
 pub fn arm_aiming_system(
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    target: Res<TargetTile>,
     mut player_query: Query<
         (&GlobalTransform, &mut AnimationState, &Hotbar, &Children),
         With<Player>,
     >,
     mut arm_query: Query<(&CharacterPart, &mut ArmAiming, &mut Transform)>,
 ) {
-    let Ok(window) = window_query.single() else {
-        return;
-    };
-    let Some(cursor_pos) = window.cursor_position() else {
-        return;
-    };
-    let Ok((camera, camera_gt)) = camera_query.single() else {
-        return;
-    };
-    let Ok(world_pos) = camera.viewport_to_world_2d(camera_gt, cursor_pos) else {
+    let Some(world_pos) = target.0.map(|target| target.world_pos) else {
         return;
     };
 
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use bevy::asset::AssetEvent;
+use bevy::ecs::message::MessageReader;
 use bevy::prelude::*;
 use bevy::sprite_render::MeshMaterial2d;
 
@@ -39,6 +41,17 @@ impl CharacterAnimations {
             .unwrap_or(&[])
     }
 
+    /// Whether any loaded frame refers to the given image.
+    pub fn contains_image(&self, id: AssetId<Image>) -> bool {
+        self.parts.values().any(|p| {
+            p.idle
+                .iter()
+                .chain(&p.running)
+                .chain(&p.jumping)
+                .any(|h| h.id() == id)
+        })
+    }
+
     /// Max frame count across all parts for a given animation kind.
     /// Used as the master frame count for animation advancement.
     pub fn max_frame_count(&self, kind: AnimationKind) -> usize {
@@ -82,6 +95,14 @@ pub fn load_character_animations(
     asset_server: Res<AssetServer>,
     anim_config: Res<CharacterAnimConfig>,
 ) {
+    commands.insert_resource(build_character_animations(&asset_server, &anim_config));
+}
+
+/// Resolve every animation frame for every body part into image handles.
+fn build_character_animations(
+    asset_server: &AssetServer,
+    anim_config: &CharacterAnimConfig,
+) -> CharacterAnimations {
     let base = &anim_config.base_path;
     let mut parts_map = HashMap::new();
 
@@ -177,7 +198,82 @@ pub fn load_character_animations(
         );
     }
 
-    commands.insert_resource(CharacterAnimations { parts: parts_map });
+    CharacterAnimations { parts: parts_map }
+}
+
+/// Clamp a frame index into an animation of `frame_count` frames.
+pub fn clamp_frame(frame: usize, frame_count: usize) -> usize {
+    frame.min(frame_count.saturating_sub(1))
+}
+
+/// Pick up hot-reloaded character visuals without restarting.
+///
+/// Rebuilds [`CharacterAnimations`] when [`CharacterAnimConfig`] changes and
+/// refreshes part materials when any frame image is modified on disk. The
+/// player's [`AnimationState`] is preserved; its frame index is clamped when
+/// the reloaded animation is shorter.
+#[allow(clippy::type_complexity)]
+pub fn hot_reload_character_sprites(
+    asset_server: Res<AssetServer>,
+    anim_config: Res<CharacterAnimConfig>,
+    mut animations: ResMut<CharacterAnimations>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
+    mut materials: ResMut<Assets<LitSpriteMaterial>>,
+    mut player_query: Query<(&mut AnimationState, &Children), With<Player>>,
+    mut part_query: Query<(
+        &CharacterPart,
+        &MeshMaterial2d<LitSpriteMaterial>,
+        &mut Transform,
+        Option<&ArmAiming>,
+    )>,
+) {
+    let config_changed = anim_config.is_changed() && !anim_config.is_added();
+    if config_changed {
+        *animations = build_character_animations(&asset_server, &anim_config);
+    }
+
+    let images_changed = image_events.read().any(|event| {
+        matches!(event, AssetEvent::Modified { id } if animations.contains_image(*id))
+    });
+    if !config_changed && !images_changed {
+        return;
+    }
+
+    for (mut anim, children) in &mut player_query {
+        anim.frame = clamp_frame(anim.frame, animations.max_frame_count(anim.kind));
+
+        for child in children.iter() {
+            let Ok((part, mat_handle, mut transform, aim)) = part_query.get_mut(child) else {
+                continue;
+            };
+            let aiming = aim.is_some_and(|a| a.active);
+            let kind = if aiming { AnimationKind::Idle } else { anim.kind };
+            let frames = animations.frames_for(part.0, kind);
+            if !frames.is_empty() {
+                let idx = if aiming { 0 } else { clamp_frame(anim.frame, frames.len()) };
+                // Mutable access also re-prepares the material, which picks up
+                // modified image data for an unchanged handle.
+                if let Some(mat) = materials.get_mut(&mat_handle.0) {
+                    mat.sprite = frames[idx].clone();
+                }
+            }
+
+            if config_changed {
+                let part_cfg = anim_config.parts.as_ref().and_then(|p| p.config_for(part.0));
+                let (fw, fh) = part_cfg.map(|c| c.frame_size).unwrap_or(anim_config.sprite_size);
+                let scale = anim_config.render_scale;
+                transform.scale.x = (fw as f32 * scale).copysign(transform.scale.x);
+                transform.scale.y = fh as f32 * scale;
+                // Aiming arms position themselves; only move the static parts.
+                if aim.is_none() {
+                    let (ox, oy) = part_cfg.map(|c| c.offset).unwrap_or((0.0, 0.0));
+                    transform.translation.x = ox * scale;
+                    transform.translation.y = oy * scale;
+                }
+            }
+        }
+    }
+    info!("Hot-reloaded character sprites");
 }
 
 /// Advance animation frames and switch states based on velocity.
@@ -327,3 +423,19 @@ pub fn animate_player(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_frame_keeps_index_within_shorter_animation() {
+        assert_eq!(clamp_frame(5, 3), 2);
+        assert_eq!(clamp_frame(1, 3), 1);
+    }
+
+    #[test]
+    fn clamp_frame_handles_empty_animation() {
+        assert_eq!(clamp_frame(4, 0), 0);
+    }
+}
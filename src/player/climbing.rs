@@ -0,0 +1,255 @@
+//! Climbing ropes and ladders: pressing up or down while centred on a
+//! climbable tile grabs it. While holding on, gravity is off, up/down climb
+//! at a constant speed, and walking sideways carries the player off the
+//! tile. Jumping lets go with a small hop.
+
+use bevy::prelude::*;
+
+use super::Player;
+use crate::physics::{Climbing, TileCollider, Velocity, MAX_DELTA_SECS};
+use crate::ui::input_capture::InputCapture;
+use crate::world::chunk::{world_to_tile, Layer, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+
+/// Vertical climbing speed (px/s).
+const CLIMB_SPEED: f32 = 120.0;
+/// Upward speed (px/s) of the hop when jumping off.
+const CLIMB_HOP_SPEED: f32 = 250.0;
+/// Seconds after letting go before the player can grab on again, so
+/// jumping while holding up doesn't grab the same rope straight back.
+const REGRAB_SECS: f32 = 0.3;
+
+/// How much of a climbable tile a collider overlaps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClimbContact {
+    None,
+    /// Some of the collider overlaps a climbable tile.
+    Edge,
+    /// The collider's centre is on a climbable tile.
+    Centre,
+}
+
+/// How a collider of `size` centred at `pos` touches climbable tiles.
+pub fn climb_contact(
+    pos: Vec2,
+    size: Vec2,
+    ctx: &WorldCtxRef,
+    world_map: &WorldMap,
+) -> ClimbContact {
+    let ts = ctx.config.tile_size;
+    let climbable = |(tx, ty): (i32, i32)| {
+        world_map
+            .get_tile(tx, ty, Layer::Fg, ctx)
+            .is_some_and(|t| ctx.tile_registry.is_climbable(t))
+    };
+    if climbable(world_to_tile(pos.x, pos.y, ts)) {
+        return ClimbContact::Centre;
+    }
+    // Shrink by a pixel so merely touching a neighbouring tile doesn't count.
+    let (min_x, min_y) = world_to_tile(pos.x - size.x / 2.0 + 1.0, pos.y - size.y / 2.0 + 1.0, ts);
+    let (max_x, max_y) = world_to_tile(pos.x + size.x / 2.0 - 1.0, pos.y + size.y / 2.0 - 1.0, ts);
+    let touching = (min_y..=max_y).any(|ty| (min_x..=max_x).any(|tx| climbable((tx, ty))));
+    if touching {
+        ClimbContact::Edge
+    } else {
+        ClimbContact::None
+    }
+}
+
+/// Whether the player holds on next frame. Grabbing takes up/down while
+/// centred on the tile; once on, any overlap keeps holding, so the player
+/// doesn't flicker on and off at the tile's edge.
+pub fn next_climbing(
+    active: bool,
+    contact: ClimbContact,
+    wants_climb: bool,
+    regrab_ready: bool,
+) -> bool {
+    if active {
+        contact != ClimbContact::None
+    } else {
+        wants_climb && regrab_ready && contact == ClimbContact::Centre
+    }
+}
+
+/// Grab, climb and let go of climbable tiles. Runs after `player_input`,
+/// whose horizontal velocity it keeps.
+pub fn player_climb(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    capture: Res<InputCapture>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    mut query: Query<(&Transform, &TileCollider, &mut Velocity, &mut Climbing), With<Player>>,
+) {
+    let dt = time.delta_secs().min(MAX_DELTA_SECS);
+    let pressed = |codes: &[KeyCode]| !capture.keyboard && keys.any_pressed(codes.iter().copied());
+    let up = pressed(&[KeyCode::KeyW, KeyCode::ArrowUp]);
+    let down = pressed(&[KeyCode::KeyS, KeyCode::ArrowDown]);
+    let jump = !capture.keyboard && keys.just_pressed(KeyCode::Space);
+    let ctx_ref = ctx.as_ref();
+
+    for (tf, collider, mut vel, mut climbing) in &mut query {
+        climbing.regrab_cooldown = (climbing.regrab_cooldown - dt).max(0.0);
+        if climbing.active && jump {
+            climbing.active = false;
+            climbing.regrab_cooldown = REGRAB_SECS;
+            vel.y = CLIMB_HOP_SPEED;
+            continue;
+        }
+
+        let size = Vec2::new(collider.width, collider.height);
+        let contact = climb_contact(tf.translation.truncate(), size, &ctx_ref, &world_map);
+        let regrab_ready = climbing.regrab_cooldown <= 0.0;
+        climbing.active = next_climbing(climbing.active, contact, up || down, regrab_ready);
+        if climbing.active {
+            vel.y = CLIMB_SPEED * (up as i32 - down as i32) as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::tile::{TileDef, TileId, TileRegistry};
+    use crate::test_helpers::fixtures;
+
+    const TS: f32 = 32.0;
+    const ROPE_X: i32 = 100;
+    const Y: f32 = 505.0 * TS;
+
+    /// App with a climbable rope in column [`ROPE_X`] from row 500 to 510
+    /// in open air, and a player hanging in the air at `x`.
+    fn climb_app(x: f32) -> (App, Entity) {
+        let mut defs = fixtures::test_tile_registry().defs;
+        defs.push(TileDef {
+            id: "rope".into(),
+            climbable: true,
+            hanging: true,
+            ..defs[0].clone()
+        });
+        let tr = TileRegistry::from_defs(defs);
+
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let rope = tr.by_name("rope");
+        let mut map = WorldMap::default();
+        for tx in ROPE_X - 4..=ROPE_X + 4 {
+            for ty in 498..=512 {
+                let in_rope = tx == ROPE_X && (500..=510).contains(&ty);
+                let tile = if in_rope { rope } else { TileId::AIR };
+                map.set_tile(tx, ty, Layer::Fg, tile, &ctx);
+            }
+        }
+
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(1.0 / 60.0),
+        ))
+        .insert_resource(tr)
+        .insert_resource(map)
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<InputCapture>()
+        .add_systems(Update, player_climb);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(x, Y, 0.0),
+                TileCollider {
+                    width: 16.0,
+                    height: 32.0,
+                },
+                Velocity::default(),
+                Climbing::default(),
+            ))
+            .id();
+        (app, player)
+    }
+
+    fn move_to(app: &mut App, player: Entity, x: f32) {
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .x = x;
+    }
+
+    fn step(app: &mut App, keys: &[KeyCode]) {
+        {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.reset_all();
+            for &key in keys {
+                input.press(key);
+            }
+        }
+        app.update();
+    }
+
+    fn climbing(app: &App, player: Entity) -> bool {
+        app.world().get::<Climbing>(player).unwrap().active
+    }
+
+    #[test]
+    fn grabbing_needs_the_centre_but_holding_on_needs_only_an_edge() {
+        // Collider spans 3227..3243: overlaps the rope column (3200..3232)
+        // but its centre is in the next column.
+        let edge_x = (ROPE_X + 1) as f32 * TS + 3.0;
+        let (mut app, player) = climb_app(edge_x);
+        step(&mut app, &[KeyCode::KeyW]);
+        assert!(!climbing(&app, player), "grabbed with only an edge over it");
+
+        move_to(&mut app, player, (ROPE_X as f32 + 0.5) * TS);
+        step(&mut app, &[]);
+        assert!(!climbing(&app, player), "grabbed without pressing up/down");
+        step(&mut app, &[KeyCode::KeyW]);
+        assert!(climbing(&app, player));
+        assert_eq!(app.world().get::<Velocity>(player).unwrap().y, CLIMB_SPEED);
+        step(&mut app, &[KeyCode::KeyS]);
+        assert_eq!(app.world().get::<Velocity>(player).unwrap().y, -CLIMB_SPEED);
+        step(&mut app, &[]);
+        assert_eq!(app.world().get::<Velocity>(player).unwrap().y, 0.0);
+
+        // Sliding sideways keeps hold while any of the collider overlaps...
+        move_to(&mut app, player, edge_x);
+        step(&mut app, &[KeyCode::KeyD]);
+        assert!(climbing(&app, player), "let go while still overlapping");
+        // ...and lets go once clear of the rope.
+        move_to(&mut app, player, (ROPE_X + 2) as f32 * TS);
+        step(&mut app, &[KeyCode::KeyD]);
+        assert!(!climbing(&app, player));
+    }
+
+    #[test]
+    fn jumping_lets_go_with_a_hop_and_regrab_waits() {
+        let (mut app, player) = climb_app((ROPE_X as f32 + 0.5) * TS);
+        step(&mut app, &[KeyCode::KeyW]);
+        assert!(climbing(&app, player));
+
+        step(&mut app, &[KeyCode::KeyW, KeyCode::Space]);
+        assert!(!climbing(&app, player));
+        assert_eq!(
+            app.world().get::<Velocity>(player).unwrap().y,
+            CLIMB_HOP_SPEED
+        );
+
+        // Still holding up right after the jump doesn't grab straight back.
+        step(&mut app, &[KeyCode::KeyW]);
+        assert!(!climbing(&app, player));
+        for _ in 0..(REGRAB_SECS * 60.0) as usize + 2 {
+            step(&mut app, &[KeyCode::KeyW]);
+        }
+        assert!(climbing(&app, player));
+    }
+
+    #[test]
+    fn grab_and_release_thresholds_differ() {
+        use ClimbContact::*;
+        assert!(!next_climbing(false, Edge, true, true));
+        assert!(next_climbing(false, Centre, true, true));
+        assert!(!next_climbing(false, Centre, false, true));
+        assert!(!next_climbing(false, Centre, true, false));
+        assert!(next_climbing(true, Edge, false, false));
+        assert!(!next_climbing(true, None, true, true));
+    }
+}
@@ -0,0 +1,193 @@
+//! Footsteps: while the player walks on the ground, a [`Footstep`] goes out
+//! every stride carrying the material of the tile underfoot, for step sounds
+//! and dust.
+
+use bevy::prelude::*;
+
+use super::Player;
+use crate::physics::{Grounded, TileCollider, Velocity, MAX_DELTA_SECS};
+use crate::registry::tile::TileMaterial;
+use crate::world::chunk::{world_to_tile, Layer, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+
+/// Ground covered per footstep, in tiles.
+const STRIDE_TILES: f32 = 1.5;
+/// Slowest horizontal speed (px/s) that counts as walking.
+const MIN_WALK_SPEED: f32 = 5.0;
+
+/// A foot came down on a tile.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct Footstep {
+    pub entity: Entity,
+    /// Bottom centre of the walker's collider.
+    pub position: Vec2,
+    /// Tile stepped on, x wrapped.
+    pub tile: (i32, i32),
+    pub material: TileMaterial,
+}
+
+/// Solid tile a collider of `size` centred at `pos` stands on: the one under
+/// its centre, else the one under either edge.
+fn tile_underfoot(
+    pos: Vec2,
+    size: Vec2,
+    ctx: &WorldCtxRef,
+    world_map: &WorldMap,
+) -> Option<(i32, i32)> {
+    let feet_y = pos.y - size.y / 2.0 - 1.0;
+    let half_w = size.x / 2.0 - 1.0;
+    [pos.x, pos.x - half_w, pos.x + half_w]
+        .into_iter()
+        .map(|x| world_to_tile(x, feet_y, ctx.config.tile_size))
+        .find(|&(tx, ty)| world_map.is_solid(tx, ty, ctx))
+}
+
+/// Send a [`Footstep`] every stride the player walks along the ground.
+/// Standing still, jumping or falling sends none and starts the next stride
+/// afresh.
+pub fn emit_footsteps(
+    time: Res<Time>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    player: Query<(Entity, &Transform, &Velocity, &Grounded, &TileCollider), With<Player>>,
+    mut walked: Local<f32>,
+    mut footsteps: MessageWriter<Footstep>,
+) {
+    let Ok((entity, tf, vel, grounded, collider)) = player.single() else {
+        return;
+    };
+    if !grounded.0 || vel.x.abs() < MIN_WALK_SPEED {
+        *walked = 0.0;
+        return;
+    }
+    let ctx_ref = ctx.as_ref();
+    let stride = STRIDE_TILES * ctx_ref.config.tile_size;
+    *walked += vel.x.abs() * time.delta_secs().min(MAX_DELTA_SECS);
+    if *walked < stride {
+        return;
+    }
+    *walked %= stride;
+
+    let pos = tf.translation.truncate();
+    let size = Vec2::new(collider.width, collider.height);
+    let Some((tx, ty)) = tile_underfoot(pos, size, &ctx_ref, &world_map) else {
+        return;
+    };
+    let Some(tile) = world_map.get_tile(tx, ty, Layer::Fg, &ctx_ref) else {
+        return;
+    };
+    footsteps.write(Footstep {
+        entity,
+        position: Vec2::new(pos.x, pos.y - size.y / 2.0),
+        tile: (ctx_ref.config.wrap_tile_x(tx), ty),
+        material: ctx_ref.tile_registry.material(tile),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::tile::TileId;
+    use crate::test_helpers::fixtures;
+
+    const TS: f32 = 32.0;
+    const FLOOR: i32 = 500;
+    const SPEED: f32 = 200.0;
+
+    /// App with a floor at row [`FLOOR`]: grass on columns 100..110, stone
+    /// on 110..120, open air above. The player stands on it at column 100.
+    fn walk_app(grounded: bool, speed: f32) -> (App, Entity) {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(1.0 / 60.0),
+        ))
+        .add_message::<Footstep>()
+        .add_systems(Update, emit_footsteps);
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        for x in 96..124 {
+            let floor = if x < 110 {
+                tr.by_name("grass")
+            } else {
+                tr.by_name("stone")
+            };
+            map.set_tile(x, FLOOR, Layer::Fg, floor, &ctx);
+            for y in FLOOR + 1..FLOOR + 4 {
+                map.set_tile(x, y, Layer::Fg, TileId::AIR, &ctx);
+            }
+        }
+        *app.world_mut().resource_mut::<WorldMap>() = map;
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(100.5 * TS, (FLOOR + 1) as f32 * TS + 16.0, 0.0),
+                Velocity { x: speed, y: 0.0 },
+                Grounded(grounded),
+                TileCollider {
+                    width: 16.0,
+                    height: 32.0,
+                },
+            ))
+            .id();
+        (app, player)
+    }
+
+    /// Run `frames` frames, moving the player by its velocity after each,
+    /// and return the footsteps sent.
+    fn walk(app: &mut App, player: Entity, frames: usize) -> Vec<Footstep> {
+        let mut steps = Vec::new();
+        for _ in 0..frames {
+            app.update();
+            let dt = app.world().resource::<Time>().delta_secs();
+            let vx = app.world().get::<Velocity>(player).unwrap().x;
+            app.world_mut()
+                .get_mut::<Transform>(player)
+                .unwrap()
+                .translation
+                .x += vx * dt;
+            steps.extend(app.world_mut().resource_mut::<Messages<Footstep>>().drain());
+        }
+        steps
+    }
+
+    #[test]
+    fn walking_sends_footsteps_with_the_material_underfoot() {
+        let (mut app, player) = walk_app(true, SPEED);
+        // 400 px: from grass at column 100 onto stone at 110 and on to 113.
+        let steps = walk(&mut app, player, 120);
+
+        let distance = 120.0 * SPEED / 60.0;
+        let expected = (distance / (STRIDE_TILES * TS)) as usize;
+        assert!(
+            steps.len().abs_diff(expected) <= 1,
+            "{} steps, expected about {expected}",
+            steps.len()
+        );
+        assert!(steps
+            .iter()
+            .all(|s| s.entity == player && s.tile.1 == FLOOR));
+        for step in &steps {
+            let material = if step.tile.0 < 110 {
+                TileMaterial::Grass
+            } else {
+                TileMaterial::Stone
+            };
+            assert_eq!(step.material, material, "{step:?}");
+        }
+        assert_eq!(steps.first().unwrap().material, TileMaterial::Grass);
+        assert_eq!(steps.last().unwrap().material, TileMaterial::Stone);
+    }
+
+    #[test]
+    fn standing_still_or_airborne_sends_no_footsteps() {
+        let (mut app, player) = walk_app(true, 0.0);
+        assert!(walk(&mut app, player, 120).is_empty());
+
+        let (mut app, player) = walk_app(false, SPEED);
+        assert!(walk(&mut app, player, 120).is_empty());
+    }
+}
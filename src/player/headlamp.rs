@@ -0,0 +1,145 @@
+//! Worn lights: an item with a `light` in the Head slot (a headlamp) shines a
+//! cone forward from the player's head, the way the player faces.
+
+use bevy::prelude::*;
+
+use super::animation::AnimationState;
+use super::Player;
+use crate::inventory::Equipment;
+use crate::item::{EquipmentSlot, ItemRegistry, WornLight};
+use crate::physics::TileCollider;
+use crate::registry::tile::{LightCone, LightDirection};
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::world_to_tile;
+use crate::world::rc_lighting::{ConeLight, ConeLights};
+
+/// Height of the head above the player's centre, as a fraction of the
+/// collider's height.
+const HEAD_HEIGHT_FRACTION: f32 = 0.35;
+
+/// Cone a worn light casts from the tile at `head`.
+pub fn worn_cone(light: &WornLight, head: IVec2, facing_right: bool) -> ConeLight {
+    let direction = if facing_right {
+        LightDirection::Right
+    } else {
+        LightDirection::Left
+    };
+    ConeLight {
+        tile: head,
+        emission: light.emission,
+        cone: LightCone {
+            direction,
+            half_angle: light.half_angle,
+        },
+    }
+}
+
+/// Rebuild [`ConeLights`] from the light the player wears on their head.
+#[allow(clippy::type_complexity)]
+pub fn update_worn_lights(
+    player: Query<
+        (
+            &Transform,
+            &Equipment,
+            Option<&TileCollider>,
+            Option<&AnimationState>,
+        ),
+        With<Player>,
+    >,
+    item_registry: Res<ItemRegistry>,
+    world_config: Res<ActiveWorld>,
+    mut lights: ResMut<ConeLights>,
+) {
+    lights.0.clear();
+    let Ok((tf, equipment, collider, anim)) = player.single() else {
+        return;
+    };
+    let Some(light) = equipment
+        .get(EquipmentSlot::Head)
+        .and_then(|id| item_registry.by_name(id))
+        .and_then(|id| item_registry.get(id).light)
+    else {
+        return;
+    };
+    let head_y = tf.translation.y + collider.map_or(0.0, |c| c.height * HEAD_HEIGHT_FRACTION);
+    let (tx, ty) = world_to_tile(tf.translation.x, head_y, world_config.tile_size);
+    let facing_right = anim.is_none_or(|a| a.facing_right);
+    lights
+        .0
+        .push(worn_cone(&light, IVec2::new(tx, ty), facing_right));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{ItemDef, ItemType};
+    use crate::player::animation::AnimationKind;
+    use crate::test_helpers::fixtures;
+
+    fn headlamp() -> ItemDef {
+        ItemDef {
+            display_name: "Headlamp".into(),
+            max_stack: 1,
+            equipment_slot: Some(EquipmentSlot::Head),
+            light: Some(WornLight {
+                emission: [255, 240, 200],
+                half_angle: 25.0,
+            }),
+            ..fixtures::item_def("headlamp", ItemType::Armor)
+        }
+    }
+
+    fn headlamp_app(equipped: bool) -> (App, Entity) {
+        let mut app = fixtures::test_app();
+        app.insert_resource(ItemRegistry::from_defs(vec![headlamp()]))
+            .init_resource::<ConeLights>()
+            .add_systems(Update, update_worn_lights);
+        let mut equipment = Equipment::new();
+        if equipped {
+            equipment.equip(EquipmentSlot::Head, "headlamp".into());
+        }
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_xyz(100.0, 50.0, 0.0), equipment))
+            .id();
+        (app, player)
+    }
+
+    fn lights(app: &App) -> Vec<ConeLight> {
+        app.world().resource::<ConeLights>().0.clone()
+    }
+
+    #[test]
+    fn equipped_headlamp_shines_forward() {
+        let (mut app, _) = headlamp_app(true);
+        app.update();
+
+        let lights = lights(&app);
+        assert_eq!(lights.len(), 1);
+        assert_eq!(lights[0].tile, IVec2::new(3, 1));
+        assert_eq!(lights[0].cone.direction, LightDirection::Right);
+        assert_eq!(lights[0].emission, [255, 240, 200]);
+    }
+
+    #[test]
+    fn headlamp_follows_facing() {
+        let (mut app, player) = headlamp_app(true);
+        app.world_mut().entity_mut(player).insert(AnimationState {
+            kind: AnimationKind::Idle,
+            frame: 0,
+            timer: Timer::from_seconds(0.15, TimerMode::Repeating),
+            facing_right: false,
+            running_backwards: false,
+            facing_locked: false,
+        });
+        app.update();
+        assert_eq!(lights(&app)[0].cone.direction, LightDirection::Left);
+    }
+
+    #[test]
+    fn no_headlamp_no_light() {
+        let (mut app, _) = headlamp_app(false);
+        app.update();
+        assert!(lights(&app).is_empty());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod aiming;
 pub mod animation;
+pub mod climbing;
+pub mod footsteps;
+pub mod headlamp;
 pub mod movement;
 pub mod oxygen;
 pub mod parts;
+pub mod stats;
 
 use bevy::prelude::*;
 use bevy::sprite_render::MeshMaterial2d;
@@ -10,22 +14,29 @@ use bevy::sprite_render::MeshMaterial2d;
 use crate::cosmos::capsule::CapsuleLocation;
 use crate::cosmos::pressurization::InVacuum;
 use crate::cosmos::warp::NeedsRespawn;
-use crate::crafting::{HandCraftState, UnlockedRecipes};
+use crate::crafting::{HandCraftState, KnownRecipes};
 use crate::inventory::{Hotbar, Inventory};
 use crate::liquid::registry::LiquidRegistry;
-use crate::physics::{Gravity, Submerged, TileCollider};
-use crate::registry::biome::PlanetConfig;
+use crate::object::registry::ObjectRegistry;
+use crate::physics::{
+    find_air_pocket, Climbing, Gravity, Submerged, TerminalVelocity, TileCollider,
+};
 use crate::registry::loading::CharacterAnimConfig;
 use crate::registry::player::PlayerConfig;
 use crate::registry::world::ActiveWorld;
 use crate::registry::AppState;
-use crate::sets::GameSet;
+use crate::sets::{GameSet, WorldSet};
+use crate::world::chunk::WorldMap;
+use crate::world::ctx::WorldCtx;
 use crate::world::lit_sprite::{FallbackLightmap, LitSprite, LitSpriteMaterial, SharedLitQuad};
-use crate::world::terrain_gen;
-use crate::world::terrain_gen::TerrainNoiseCache;
+use crate::world::spawn_point::WorldSpawnPoint;
 
 pub use crate::physics::{Grounded, Velocity};
 
+/// How far (in tiles) to search for free space when a resized collider
+/// ends up inside terrain.
+const AIR_POCKET_SEARCH_TILES: i32 = 8;
+
 use animation::{AnimationKind, AnimationState, CharacterAnimations};
 use parts::{ArmAiming, CharacterPart, PartType};
 
@@ -40,6 +51,8 @@ impl Plugin for PlayerPlugin {
             OnEnter(AppState::InGame),
             (
                 animation::load_character_animations,
+                crate::world::spawn_point::choose_world_spawn,
+                crate::world::spawn_point::build_spawn_platform,
                 spawn_player.after(crate::world::lit_sprite::init_lit_sprite_resources),
                 respawn_player_on_warp,
             )
@@ -48,15 +61,36 @@ impl Plugin for PlayerPlugin {
         .add_systems(
             Update,
             (
+                sync_player_collider,
+                sync_player_gravity,
                 movement::player_input,
+                climbing::player_climb,
                 aiming::arm_aiming_system,
+                animation::hot_reload_character_sprites,
                 animation::animate_player,
             )
                 .chain()
                 .in_set(GameSet::Physics),
         )
         .add_systems(Update, update_submerge_tint.in_set(GameSet::Physics))
-        .add_systems(Update, oxygen::tick_oxygen.in_set(GameSet::Physics));
+        .add_systems(Update, oxygen::tick_oxygen.in_set(GameSet::Physics))
+        .add_systems(Update, stats::tick_speed_boost.in_set(GameSet::Physics))
+        .add_systems(
+            Update,
+            headlamp::update_worn_lights
+                .after(animation::animate_player)
+                .in_set(GameSet::Physics),
+        )
+        .init_resource::<stats::PlayerStatsSummary>()
+        .add_systems(
+            Update,
+            stats::update_player_stats_summary.in_set(WorldSet::Sim),
+        )
+        .add_message::<footsteps::Footstep>()
+        .add_systems(
+            Update,
+            footsteps::emit_footsteps.in_set(WorldSet::Sim),
+        );
     }
 }
 
@@ -64,9 +98,7 @@ impl Plugin for PlayerPlugin {
 fn spawn_player(
     mut commands: Commands,
     player_config: Res<PlayerConfig>,
-    world_config: Res<ActiveWorld>,
-    planet_config: Res<PlanetConfig>,
-    noise_cache: Res<TerrainNoiseCache>,
+    spawn_point: Res<WorldSpawnPoint>,
     animations: Res<CharacterAnimations>,
     anim_config: Res<CharacterAnimConfig>,
     quad: Option<Res<SharedLitQuad>>,
@@ -83,30 +115,8 @@ fn spawn_player(
         return;
     };
 
-    let is_ship = matches!(
-        world_config.address,
-        crate::cosmos::address::CelestialAddress::Ship { .. }
-    );
-
-    let (spawn_pixel_x, spawn_pixel_y) = if is_ship {
-        let cx = (world_config.width_tiles / 2) as f32 * world_config.tile_size
-            + world_config.tile_size / 2.0;
-        let cy = (world_config.height_tiles / 2 - 2) as f32 * world_config.tile_size
-            + player_config.height / 2.0;
-        (cx, cy)
-    } else {
-        let spawn_tile_x = 0;
-        let surface_y = terrain_gen::surface_height(
-            &noise_cache,
-            spawn_tile_x,
-            &world_config,
-            planet_config.layers.surface.terrain_frequency,
-            planet_config.layers.surface.terrain_amplitude,
-        );
-        let px = spawn_tile_x as f32 * world_config.tile_size + world_config.tile_size / 2.0;
-        let py = (surface_y + 5) as f32 * world_config.tile_size + player_config.height / 2.0;
-        (px, py)
-    };
+    let spawn_pos = spawn_point.position;
+    let collision = player_config.collision_size();
 
     // Determine which parts to spawn
     let parts_to_spawn: Vec<PartType> = if anim_config.parts.is_some() {
@@ -119,6 +129,9 @@ fn spawn_player(
         vec![PartType::Body]
     };
 
+    // A new player knows only the recipes that need nothing.
+    commands.insert_resource(KnownRecipes::default());
+
     // Spawn parent entity (physics + inventory, NO rendering components)
     let mut parent = commands.spawn((
         Player,
@@ -133,16 +146,16 @@ fn spawn_player(
         },
         Hotbar::new(),
         HandCraftState::default(),
-        UnlockedRecipes::default(),
         Velocity::default(),
         Gravity(player_config.gravity),
         Grounded(false),
         Submerged::default(),
+        Climbing::default(),
         InVacuum::default(),
         oxygen::Oxygen::default(),
         TileCollider {
-            width: player_config.width,
-            height: player_config.height,
+            width: collision.x,
+            height: collision.y,
         },
         AnimationState {
             kind: AnimationKind::Idle,
@@ -152,12 +165,16 @@ fn spawn_player(
             running_backwards: false,
             facing_locked: false,
         },
-        Transform::from_xyz(spawn_pixel_x, spawn_pixel_y, 1.0),
+        Transform::from_xyz(spawn_pos.x, spawn_pos.y, 1.0),
         Visibility::default(),
     ));
+    parent.insert(TerminalVelocity(player_config.max_fall_speed));
     parent.insert(crate::combat::Health::new(100.0));
     parent.insert(crate::combat::fall_damage::FallTracker::default());
     parent.insert(crate::combat::melee::MeleeAttack::default());
+    parent.insert(crate::interaction::hand_action::HandCooldowns::default());
+    parent.insert(crate::inventory::Equipment::new());
+    parent.insert(stats::SpeedBoost::default());
 
     // Spawn child entities for each body part
     parent.with_children(|builder| {
@@ -228,8 +245,7 @@ pub(crate) fn respawn_player_on_warp(
     mut commands: Commands,
     needs_respawn: Option<Res<NeedsRespawn>>,
     world_config: Res<ActiveWorld>,
-    planet_config: Res<PlanetConfig>,
-    noise_cache: Res<TerrainNoiseCache>,
+    spawn_point: Res<WorldSpawnPoint>,
     player_config: Res<PlayerConfig>,
     mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
     capsule_location: Option<Res<CapsuleLocation>>,
@@ -247,45 +263,19 @@ pub(crate) fn respawn_player_on_warp(
         .as_ref()
         .is_some_and(|loc| loc.planet_address == world_config.address);
 
-    let is_ship = matches!(
-        world_config.address,
-        crate::cosmos::address::CelestialAddress::Ship { .. }
-    );
-
-    let (spawn_pixel_x, spawn_pixel_y) = if is_ship {
-        // Spawn at the center of the ship hull
-        let cx = (world_config.width_tiles / 2) as f32 * world_config.tile_size
-            + world_config.tile_size / 2.0;
-        let cy = (world_config.height_tiles / 2 - 2) as f32 * world_config.tile_size
-            + player_config.height / 2.0;
-        info!(
-            "Player spawning at ship hull center tile ({}, {})",
-            world_config.width_tiles / 2,
-            world_config.height_tiles / 2 - 2
-        );
-        (cx, cy)
-    } else if use_capsule_spawn {
+    let (spawn_pixel_x, spawn_pixel_y) = if use_capsule_spawn {
         let loc = capsule_location.as_ref().unwrap();
         let px = loc.tile_x as f32 * world_config.tile_size + world_config.tile_size / 2.0;
         // Spawn a few tiles above the capsule so the player doesn't clip into it
-        let py = (loc.tile_y + 3) as f32 * world_config.tile_size + player_config.height / 2.0;
+        let py = (loc.tile_y + 3) as f32 * world_config.tile_size
+            + player_config.collision_size().y / 2.0;
         info!(
             "Player respawning near capsule at tile ({}, {})",
             loc.tile_x, loc.tile_y
         );
         (px, py)
     } else {
-        let spawn_tile_x = 0;
-        let surface_y = terrain_gen::surface_height(
-            &noise_cache,
-            spawn_tile_x,
-            &world_config,
-            planet_config.layers.surface.terrain_frequency,
-            planet_config.layers.surface.terrain_amplitude,
-        );
-        let px = spawn_tile_x as f32 * world_config.tile_size + world_config.tile_size / 2.0;
-        let py = (surface_y + 5) as f32 * world_config.tile_size + player_config.height / 2.0;
-        (px, py)
+        (spawn_point.position.x, spawn_point.position.y)
     };
 
     transform.translation.x = spawn_pixel_x;
@@ -300,6 +290,57 @@ pub(crate) fn respawn_player_on_warp(
     );
 }
 
+/// Apply hot-reloaded player dimensions to the live collider.
+///
+/// If the new size overlaps solid terrain, the player is nudged into the
+/// nearest air pocket so they don't get stuck.
+fn sync_player_collider(
+    player_config: Res<PlayerConfig>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    object_registry: Option<Res<ObjectRegistry>>,
+    mut query: Query<(&mut Transform, &mut TileCollider), With<Player>>,
+) {
+    if !player_config.is_changed() {
+        return;
+    }
+    let ctx_ref = ctx.as_ref();
+    let is_solid = |tx: i32, ty: i32| -> bool {
+        match &object_registry {
+            Some(reg) => world_map.is_solid_or_object(tx, ty, &ctx_ref, reg),
+            None => world_map.is_solid(tx, ty, &ctx_ref),
+        }
+    };
+
+    let size = player_config.collision_size();
+    for (mut transform, mut collider) in &mut query {
+        if collider.width == size.x && collider.height == size.y {
+            continue;
+        }
+        collider.width = size.x;
+        collider.height = size.y;
+
+        let pos = transform.translation.truncate();
+        match find_air_pocket(
+            pos,
+            collider.width,
+            collider.height,
+            ctx_ref.config.tile_size,
+            AIR_POCKET_SEARCH_TILES,
+            is_solid,
+        ) {
+            Some(free) => {
+                transform.translation.x = free.x;
+                transform.translation.y = free.y;
+            }
+            None => warn!(
+                "Resized player collider ({}x{}) overlaps terrain with no free space nearby",
+                collider.width, collider.height
+            ),
+        }
+    }
+}
+
 /// Update the player sprite's submersion tint based on the `Submerged` component.
 /// Applies a multiplicative hue shift simulating view through liquid.
 fn update_submerge_tint(
@@ -334,3 +375,209 @@ fn update_submerge_tint(
         }
     }
 }
+
+/// Keep the player's gravity and terminal velocity in step with
+/// [`PlayerConfig`] after a hot-reload. The planet's gravity scale is applied
+/// on top by `apply_gravity`.
+fn sync_player_gravity(
+    player_config: Res<PlayerConfig>,
+    mut query: Query<(&mut Gravity, &mut TerminalVelocity), With<Player>>,
+) {
+    if !player_config.is_changed() {
+        return;
+    }
+    for (mut g, mut terminal) in &mut query {
+        g.0 = player_config.gravity;
+        terminal.0 = player_config.max_fall_speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::move_and_collide;
+    use crate::registry::biome::PlanetConfig;
+    use crate::registry::planet_physics::{sync_planet_physics, PlanetPhysics};
+    use crate::registry::tile::TileId;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::Layer;
+
+    #[test]
+    fn narrow_collision_box_fits_through_a_gap_the_sprite_would_not() {
+        let ts = 32.0;
+        // A floor at row 10 with a one-tile hole at x = 5.
+        let is_solid = |tx: i32, ty: i32| ty == 10 && tx != 5;
+        let fall = |size: Vec2| {
+            let mut pos = Vec3::new(5.5 * ts, 12.0 * ts, 1.0);
+            let mut vel = Velocity { x: 0.0, y: -300.0 };
+            for _ in 0..60 {
+                move_and_collide(&mut pos, &mut vel, size, 0.0, 1.0 / 60.0, 4, ts, is_solid);
+            }
+            pos.y
+        };
+
+        let mut config = fixtures::test_player_config();
+        config.width = 40.0;
+        assert_eq!(config.collision_size(), Vec2::new(40.0, 40.0));
+        // Wider than the hole: lands on the floor.
+        assert_eq!(fall(config.collision_size()), 11.0 * ts + 20.0);
+
+        config.collision_width = Some(28.0);
+        assert_eq!(config.collision_size(), Vec2::new(28.0, 40.0));
+        assert!(fall(config.collision_size()) < 10.0 * ts);
+    }
+
+    #[test]
+    fn resized_player_is_nudged_out_of_terrain() {
+        let mut app = fixtures::test_app();
+        app.add_systems(Update, sync_player_collider);
+
+        // Solid stone everywhere in chunk (0, 10) except an air shaft at x = 5
+        // spanning tiles 322..=330.
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let stone = tr.by_name("stone");
+        let mut world_map = WorldMap::default();
+        for ty in 320..352 {
+            for tx in 0..32 {
+                let tile = if tx == 5 && (322..=330).contains(&ty) {
+                    TileId::AIR
+                } else {
+                    stone
+                };
+                world_map.set_tile(tx, ty, Layer::Fg, tile, &ctx);
+            }
+        }
+        *app.world_mut().resource_mut::<WorldMap>() = world_map;
+
+        {
+            let mut config = app.world_mut().resource_mut::<PlayerConfig>();
+            config.width = 16.0;
+            config.height = 96.0;
+        }
+
+        // Standing on the shaft floor with the old 16x32 collider.
+        let ts = wc.tile_size;
+        let start = Vec3::new(5.5 * ts, 322.0 * ts + 16.0, 1.0);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_translation(start),
+                TileCollider {
+                    width: 16.0,
+                    height: 32.0,
+                },
+            ))
+            .id();
+
+        app.update();
+
+        let collider = app.world().get::<TileCollider>(player).unwrap();
+        assert_eq!((collider.width, collider.height), (16.0, 96.0));
+
+        // The taller collider sinks into the floor, so the player moves up a tile.
+        let pos = app.world().get::<Transform>(player).unwrap().translation;
+        assert_eq!(pos.x, start.x);
+        assert_eq!(pos.y, start.y + ts);
+    }
+
+    /// Jump straight up on a planet with the given gravity scale and return
+    /// (apex height, vertical velocity after 5 s).
+    fn simulate_jump(gravity_scale: f32) -> (f32, f32) {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(1.0 / 60.0),
+        ));
+        app.init_resource::<PlanetPhysics>();
+        app.world_mut().resource_mut::<PlanetConfig>().gravity_scale = gravity_scale;
+        app.add_systems(
+            Update,
+            (
+                sync_planet_physics,
+                sync_player_gravity,
+                crate::physics::apply_gravity,
+            )
+                .chain(),
+        );
+
+        let jump_velocity = app.world().resource::<PlayerConfig>().jump_velocity;
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Velocity {
+                    x: 0.0,
+                    y: jump_velocity,
+                },
+                Gravity(0.0),
+                TerminalVelocity(0.0),
+            ))
+            .id();
+
+        let (mut y, mut apex) = (0.0_f32, 0.0_f32);
+        for _ in 0..300 {
+            app.update();
+            let dt = app.world().resource::<Time>().delta_secs();
+            y += app.world().get::<Velocity>(player).unwrap().y * dt;
+            apex = apex.max(y);
+        }
+        (apex, app.world().get::<Velocity>(player).unwrap().y)
+    }
+
+    #[test]
+    fn low_gravity_planet_jumps_higher_and_falls_slower() {
+        let (default_apex, default_fall) = simulate_jump(1.0);
+        let (moon_apex, moon_fall) = simulate_jump(0.5);
+
+        assert!(default_apex > 0.0);
+        assert!(
+            moon_apex > default_apex * 1.5,
+            "apex {moon_apex} vs default {default_apex}"
+        );
+        assert!(
+            moon_fall > default_fall,
+            "fall speed {moon_fall} vs default {default_fall}"
+        );
+        // Both capped at their planet's terminal velocity.
+        let config = fixtures::test_player_config();
+        assert_eq!(default_fall, -config.max_fall_speed);
+        assert_eq!(moon_fall, -config.max_fall_speed * 0.5);
+    }
+
+    #[test]
+    fn player_gravity_follows_hot_reloads() {
+        let mut app = fixtures::test_app();
+        app.init_resource::<PlanetPhysics>();
+        app.add_systems(Update, (sync_planet_physics, sync_player_gravity).chain());
+        let player = app
+            .world_mut()
+            .spawn((Player, Gravity(0.0), TerminalVelocity(0.0)))
+            .id();
+        app.update();
+        let base = fixtures::test_player_config();
+        assert_eq!(app.world().get::<Gravity>(player).unwrap().0, base.gravity);
+
+        // The planet's scale goes to PlanetPhysics, not into the component,
+        // so apply_gravity doesn't scale it twice.
+        app.world_mut().resource_mut::<PlanetConfig>().gravity_scale = 2.0;
+        app.update();
+        assert_eq!(app.world().resource::<PlanetPhysics>().gravity_scale, 2.0);
+        assert_eq!(app.world().get::<Gravity>(player).unwrap().0, base.gravity);
+
+        {
+            let mut config = app.world_mut().resource_mut::<PlayerConfig>();
+            config.gravity *= 2.0;
+            config.max_fall_speed *= 2.0;
+        }
+        app.update();
+        assert_eq!(
+            app.world().get::<Gravity>(player).unwrap().0,
+            base.gravity * 2.0
+        );
+        assert_eq!(
+            app.world().get::<TerminalVelocity>(player).unwrap().0,
+            base.max_fall_speed * 2.0
+        );
+    }
+}
@@ -2,8 +2,12 @@ use bevy::prelude::*;
 
 use crate::cosmos::pressurization::InVacuum;
 use crate::physics::{Grounded, Submerged, Velocity, MAX_DELTA_SECS};
+use crate::player::stats::{movement_modifiers, movement_stats, MovementStats, SpeedBoost};
 use crate::player::Player;
+use crate::registry::biome::PlanetConfig;
 use crate::registry::player::PlayerConfig;
+use crate::registry::world::ActiveWorld;
+use crate::ui::input_capture::InputCapture;
 
 /// EVA jetpack impulse (px/s^2) when pressing movement keys in vacuum.
 const EVA_IMPULSE: f32 = 200.0;
@@ -12,85 +16,156 @@ const EVA_IMPULSE: f32 = 200.0;
 /// Slightly higher than swim drag for a floaty feel.
 const EVA_DRAG: f32 = 0.25;
 
+#[allow(clippy::type_complexity)]
 pub fn player_input(
     time: Res<Time>,
     keys: Res<ButtonInput<KeyCode>>,
     player_config: Res<PlayerConfig>,
-    mut query: Query<(&mut Velocity, &Grounded, &Submerged, Option<&InVacuum>), With<Player>>,
-    chat_state: Res<crate::chat::ChatState>,
+    planet: Option<Res<PlanetConfig>>,
+    world: Option<Res<ActiveWorld>>,
+    mut query: Query<
+        (
+            &Transform,
+            &mut Velocity,
+            &Grounded,
+            &Submerged,
+            Option<&InVacuum>,
+            Option<&SpeedBoost>,
+        ),
+        With<Player>,
+    >,
+    capture: Res<InputCapture>,
 ) {
-    if chat_state.is_active {
+    if capture.keyboard {
         return;
     }
 
     let dt = time.delta_secs().min(MAX_DELTA_SECS);
 
-    for (mut vel, grounded, submerged, in_vacuum) in &mut query {
-        let is_in_vacuum = in_vacuum.is_some_and(|v| v.0);
+    for (tf, mut vel, grounded, submerged, in_vacuum, boost) in &mut query {
+        let modifiers =
+            movement_modifiers(boost, tf.translation.y, planet.as_deref(), world.as_deref());
+        let stats = movement_stats(
+            &player_config,
+            submerged,
+            in_vacuum.is_some_and(|v| v.0),
+            modifiers,
+        );
 
-        if is_in_vacuum {
-            // --- EVA jetpack mode (zero-g in vacuum) ---
-            // WASD gives impulse in all 4 directions
-            if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
-                vel.x -= EVA_IMPULSE * dt;
-            }
-            if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
-                vel.x += EVA_IMPULSE * dt;
-            }
-            if keys.pressed(KeyCode::KeyW)
-                || keys.pressed(KeyCode::ArrowUp)
-                || keys.pressed(KeyCode::Space)
-            {
-                vel.y += EVA_IMPULSE * dt;
-            }
-            if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
-                vel.y -= EVA_IMPULSE * dt;
-            }
+        match stats {
+            MovementStats::Eva => {
+                // --- EVA jetpack mode (zero-g in vacuum) ---
+                // WASD gives impulse in all 4 directions
+                if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
+                    vel.x -= EVA_IMPULSE * dt;
+                }
+                if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
+                    vel.x += EVA_IMPULSE * dt;
+                }
+                if keys.pressed(KeyCode::KeyW)
+                    || keys.pressed(KeyCode::ArrowUp)
+                    || keys.pressed(KeyCode::Space)
+                {
+                    vel.y += EVA_IMPULSE * dt;
+                }
+                if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
+                    vel.y -= EVA_IMPULSE * dt;
+                }
 
-            // FPS-independent drag for playability
-            let drag = EVA_DRAG.powf(dt);
-            vel.x *= drag;
-            vel.y *= drag;
-        } else if submerged.is_swimming() {
-            // --- Swimming mode ---
-            let swim_speed = player_config.speed * submerged.swim_speed_factor;
-
-            // Horizontal movement
-            vel.x = 0.0;
-            if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
-                vel.x -= swim_speed;
-            }
-            if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
-                vel.x += swim_speed;
+                // FPS-independent drag for playability
+                let drag = EVA_DRAG.powf(dt);
+                vel.x *= drag;
+                vel.y *= drag;
             }
+            MovementStats::Swimming { speed } => {
+                // --- Swimming mode ---
+                let swim_speed = speed.current;
 
-            // Vertical swimming: W/Space = up, S = down
-            if keys.pressed(KeyCode::Space)
-                || keys.pressed(KeyCode::KeyW)
-                || keys.pressed(KeyCode::ArrowUp)
-            {
-                vel.y += player_config.swim_impulse * dt;
-            }
-            if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
-                vel.y -= player_config.swim_impulse * dt;
-            }
+                // Horizontal movement
+                vel.x = 0.0;
+                if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
+                    vel.x -= swim_speed;
+                }
+                if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
+                    vel.x += swim_speed;
+                }
 
-            // FPS-independent drag (exponential decay)
-            let drag = player_config.swim_drag.powf(dt);
-            vel.x *= drag;
-            vel.y *= drag;
-        } else {
-            // --- Normal ground/air mode ---
-            vel.x = 0.0;
-            if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
-                vel.x -= player_config.speed;
-            }
-            if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
-                vel.x += player_config.speed;
+                // Vertical swimming: W/Space = up, S = down
+                if keys.pressed(KeyCode::Space)
+                    || keys.pressed(KeyCode::KeyW)
+                    || keys.pressed(KeyCode::ArrowUp)
+                {
+                    vel.y += player_config.swim_impulse * dt;
+                }
+                if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
+                    vel.y -= player_config.swim_impulse * dt;
+                }
+
+                // FPS-independent drag (exponential decay)
+                let drag = player_config.swim_drag.powf(dt);
+                vel.x *= drag;
+                vel.y *= drag;
             }
-            if keys.just_pressed(KeyCode::Space) && grounded.0 {
-                vel.y = player_config.jump_velocity;
+            MovementStats::Walking {
+                speed,
+                jump_velocity,
+            } => {
+                // --- Normal ground/air mode ---
+                vel.x = 0.0;
+                if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
+                    vel.x -= speed.current;
+                }
+                if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
+                    vel.x += speed.current;
+                }
+                if keys.just_pressed(KeyCode::Space) && grounded.0 {
+                    vel.y = jump_velocity.current;
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn walk_right(capture: InputCapture) -> f32 {
+        let mut app = fixtures::test_app();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(capture)
+            .add_systems(Update, player_input);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Velocity::default(),
+                Grounded(true),
+                Submerged::default(),
+            ))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyD);
+        app.update();
+        app.world().entity(player).get::<Velocity>().unwrap().x
+    }
+
+    #[test]
+    fn movement_keys_are_ignored_only_under_keyboard_capture() {
+        let speed = fixtures::test_player_config().speed;
+        assert_eq!(walk_right(InputCapture::default()), speed);
+        let panel_hovered = InputCapture {
+            pointer: true,
+            keyboard: false,
+        };
+        assert_eq!(walk_right(panel_hovered), speed);
+        let typing = InputCapture {
+            pointer: false,
+            keyboard: true,
+        };
+        assert_eq!(walk_right(typing), 0.0);
+    }
+}
@@ -0,0 +1,341 @@
+//! The player's current numbers after every modifier that applies.
+//!
+//! [`movement_stats`] is what [`player_input`](super::movement::player_input)
+//! moves the player with, and [`PlayerStatsSummary`] is rebuilt from it and
+//! the player's components, so the inventory stats column can never show a
+//! value gameplay is not using.
+
+use bevy::prelude::*;
+
+use super::oxygen::Oxygen;
+use super::Player;
+use crate::combat::Health;
+use crate::cosmos::pressurization::InVacuum;
+use crate::physics::Submerged;
+use crate::registry::biome::PlanetConfig;
+use crate::registry::player::PlayerConfig;
+use crate::registry::world::ActiveWorld;
+
+/// A stat's configured value and its value after modifiers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stat {
+    pub base: f32,
+    pub current: f32,
+}
+
+impl Stat {
+    /// Whether a modifier is currently changing this stat.
+    pub fn is_modified(&self) -> bool {
+        (self.current - self.base).abs() > f32::EPSILON
+    }
+}
+
+/// A resource that fills and drains, such as health or oxygen.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Meter {
+    pub current: f32,
+    pub max: f32,
+}
+
+/// A timed movement speed multiplier, from consumables.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SpeedBoost {
+    pub multiplier: f32,
+    /// Seconds left; the boost does nothing once this runs out.
+    pub remaining: f32,
+}
+
+impl Default for SpeedBoost {
+    fn default() -> Self {
+        Self {
+            multiplier: 1.0,
+            remaining: 0.0,
+        }
+    }
+}
+
+impl SpeedBoost {
+    /// Start a boost, replacing any that is running.
+    pub fn apply(&mut self, multiplier: f32, secs: f32) {
+        self.multiplier = multiplier;
+        self.remaining = secs;
+    }
+
+    /// Factor movement speed is scaled by right now.
+    pub fn factor(&self) -> f32 {
+        if self.remaining > 0.0 {
+            self.multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Multipliers on walk/swim speed and jump strength.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementModifiers {
+    pub speed: f32,
+    pub jump: f32,
+}
+
+impl Default for MovementModifiers {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            jump: 1.0,
+        }
+    }
+}
+
+/// Modifiers for a player with `boost` standing at height `y` (px): the
+/// boost scales speed and thin air near the top of the world weakens jumps.
+pub fn movement_modifiers(
+    boost: Option<&SpeedBoost>,
+    y: f32,
+    planet: Option<&PlanetConfig>,
+    world: Option<&ActiveWorld>,
+) -> MovementModifiers {
+    let jump = match (planet.and_then(|p| p.thin_air), world) {
+        (Some(thin_air), Some(world)) => {
+            thin_air.jump_factor(y / world.world_pixel_height().max(1.0))
+        }
+        _ => 1.0,
+    };
+    MovementModifiers {
+        speed: boost.map_or(1.0, SpeedBoost::factor),
+        jump,
+    }
+}
+
+/// Run down the player's speed boost.
+pub fn tick_speed_boost(time: Res<Time>, mut query: Query<&mut SpeedBoost>) {
+    for mut boost in &mut query {
+        if boost.remaining > 0.0 {
+            boost.remaining = (boost.remaining - time.delta_secs()).max(0.0);
+        }
+    }
+}
+
+/// How the player moves right now, with the values that movement uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovementStats {
+    /// On the ground or in the air: walk speed and jump.
+    Walking { speed: Stat, jump_velocity: Stat },
+    /// In liquid: the liquid scales swim speed, and jumping becomes
+    /// swimming upward.
+    Swimming { speed: Stat },
+    /// In vacuum: the jetpack drives movement instead of walking.
+    Eva,
+}
+
+/// Movement values for the player's current surroundings, scaled by
+/// `modifiers` (see [`movement_modifiers`]).
+pub fn movement_stats(
+    config: &PlayerConfig,
+    submerged: &Submerged,
+    in_vacuum: bool,
+    modifiers: MovementModifiers,
+) -> MovementStats {
+    if in_vacuum {
+        MovementStats::Eva
+    } else if submerged.is_swimming() {
+        MovementStats::Swimming {
+            speed: Stat {
+                base: config.speed,
+                current: config.speed * submerged.swim_speed_factor * modifiers.speed,
+            },
+        }
+    } else {
+        MovementStats::Walking {
+            speed: Stat {
+                base: config.speed,
+                current: config.speed * modifiers.speed,
+            },
+            jump_velocity: Stat {
+                base: config.jump_velocity,
+                current: config.jump_velocity * modifiers.jump,
+            },
+        }
+    }
+}
+
+/// Snapshot of the player's stats for the UI. It only counts as changed
+/// when one of the numbers does.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct PlayerStatsSummary {
+    pub health: Meter,
+    pub oxygen: Option<Meter>,
+    /// `None` until the player spawns.
+    pub movement: Option<MovementStats>,
+}
+
+/// Rebuild [`PlayerStatsSummary`] from the player's components.
+#[allow(clippy::type_complexity)]
+pub fn update_player_stats_summary(
+    player_config: Res<PlayerConfig>,
+    planet: Option<Res<PlanetConfig>>,
+    world: Option<Res<ActiveWorld>>,
+    query: Query<
+        (
+            &Transform,
+            &Health,
+            Option<&Oxygen>,
+            &Submerged,
+            Option<&InVacuum>,
+            Option<&SpeedBoost>,
+        ),
+        With<Player>,
+    >,
+    mut summary: ResMut<PlayerStatsSummary>,
+) {
+    let Ok((tf, health, oxygen, submerged, in_vacuum, boost)) = query.single() else {
+        return;
+    };
+    summary.set_if_neq(PlayerStatsSummary {
+        health: Meter {
+            current: health.current,
+            max: health.max,
+        },
+        oxygen: oxygen.map(|o| Meter {
+            current: o.current,
+            max: o.max,
+        }),
+        movement: Some(movement_stats(
+            &player_config,
+            submerged,
+            in_vacuum.is_some_and(|v| v.0),
+            movement_modifiers(boost, tf.translation.y, planet.as_deref(), world.as_deref()),
+        )),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{process_damage, DamageEvent};
+    use crate::game_mode::GameMode;
+    use crate::physics::{Grounded, Velocity};
+    use crate::player::movement::player_input;
+    use crate::test_helpers::fixtures;
+    use crate::ui::input_capture::InputCapture;
+
+    fn stats_app(submerged: Submerged, in_vacuum: bool) -> (App, Entity) {
+        let mut app = fixtures::test_app();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<InputCapture>()
+            .init_resource::<PlayerStatsSummary>()
+            .init_resource::<GameMode>()
+            .add_message::<DamageEvent>()
+            .add_systems(
+                Update,
+                (player_input, process_damage, update_player_stats_summary).chain(),
+            );
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Velocity::default(),
+                Grounded(true),
+                submerged,
+                InVacuum(in_vacuum),
+                Health::new(100.0),
+                Oxygen::default(),
+            ))
+            .id();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::KeyD);
+        keys.press(KeyCode::Space);
+        (app, player)
+    }
+
+    fn swimming_in(factor: f32) -> Submerged {
+        Submerged {
+            ratio: 1.0,
+            swim_speed_factor: factor,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn walking_summary_matches_movement() {
+        let (mut app, player) = stats_app(Submerged::default(), false);
+        app.update();
+        let vel = app.world().entity(player).get::<Velocity>().unwrap();
+        let summary = app.world().resource::<PlayerStatsSummary>();
+        let Some(MovementStats::Walking {
+            speed,
+            jump_velocity,
+        }) = summary.movement
+        else {
+            panic!("expected walking, got {:?}", summary.movement);
+        };
+        assert_eq!(vel.x, speed.current);
+        assert_eq!(vel.y, jump_velocity.current);
+        assert!(!speed.is_modified());
+        assert!(!jump_velocity.is_modified());
+    }
+
+    #[test]
+    fn liquid_slowdown_shows_as_modified_swim_speed() {
+        for factor in [0.5, 0.25] {
+            let (mut app, player) = stats_app(swimming_in(factor), false);
+            app.update();
+            let vel = app.world().entity(player).get::<Velocity>().unwrap();
+            let summary = app.world().resource::<PlayerStatsSummary>();
+            let Some(MovementStats::Swimming { speed }) = summary.movement else {
+                panic!("expected swimming, got {:?}", summary.movement);
+            };
+            assert_eq!(vel.x, speed.current);
+            assert_eq!(speed.base, fixtures::test_player_config().speed);
+            assert!(speed.is_modified());
+        }
+    }
+
+    #[test]
+    fn vacuum_overrides_swimming() {
+        let (mut app, _) = stats_app(swimming_in(0.5), true);
+        app.update();
+        let summary = app.world().resource::<PlayerStatsSummary>();
+        assert_eq!(summary.movement, Some(MovementStats::Eva));
+    }
+
+    #[test]
+    fn summary_tracks_health_after_damage() {
+        let (mut app, player) = stats_app(Submerged::default(), false);
+        app.world_mut().write_message(DamageEvent {
+            target: player,
+            amount: 30.0,
+            knockback: Vec2::ZERO,
+        });
+        app.update();
+        let health = app.world().entity(player).get::<Health>().unwrap().current;
+        let summary = app.world().resource::<PlayerStatsSummary>();
+        assert_eq!(health, 70.0);
+        assert_eq!(
+            summary.health,
+            Meter {
+                current: 70.0,
+                max: 100.0
+            }
+        );
+        assert_eq!(summary.oxygen.map(|o| o.max), Some(100.0));
+    }
+
+    #[test]
+    fn unchanged_stats_do_not_mark_the_summary_changed() {
+        let (mut app, _) = stats_app(Submerged::default(), false);
+        app.update();
+        let tick = app
+            .world()
+            .resource_ref::<PlayerStatsSummary>()
+            .last_changed();
+        app.update();
+        assert_eq!(
+            app.world()
+                .resource_ref::<PlayerStatsSummary>()
+                .last_changed(),
+            tick
+        );
+    }
+}
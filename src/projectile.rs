@@ -0,0 +1,591 @@
+//! Thrown and fired projectiles (arrows, thrown items, bombs, grappling
+//! hooks).
+//!
+//! A [`Projectile`] moves itself rather than going through
+//! `physics::tile_collision`: each frame [`move_projectiles`] sweeps it along
+//! its path against the tile grid, so fast projectiles can't tunnel through
+//! thin walls. It bounces off walls while it has bounces left; otherwise the
+//! hit applies its [`OnHit`] behaviour and is reported as a [`ProjectileHit`]
+//! for consumers (combat damage, explosions, grapples) to act on.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::combat::Health;
+use crate::math::{sweep_segment, SegmentHit};
+use crate::object::registry::ObjectRegistry;
+use crate::physics::{TileCollider, MAX_DELTA_SECS};
+use crate::registry::planet_physics::PlanetPhysics;
+use crate::sets::GameSet;
+use crate::world::chunk::WorldMap;
+use crate::world::ctx::WorldCtx;
+
+/// Downward acceleration (px/s²) of a projectile with a gravity scale of 1,
+/// matching dropped items.
+pub const PROJECTILE_GRAVITY: f32 = 400.0;
+/// Lifetime (seconds) of projectiles that don't set their own.
+pub const DEFAULT_PROJECTILE_LIFETIME: f32 = 5.0;
+/// Bounces slower than this (px/s into the wall) end the flight instead.
+const MIN_BOUNCE_SPEED: f32 = 20.0;
+/// Distance (px) a projectile is kept off the face it hit.
+const CONTACT_OFFSET: f32 = 0.01;
+/// Sweeps per frame; each bounce uses one.
+const MAX_SWEEPS: u32 = 4;
+/// Sprite size in pixels.
+const PROJECTILE_SPRITE_SIZE: f32 = 12.0;
+/// Projectiles draw above tiles and dropped items.
+const PROJECTILE_Z: f32 = 2.0;
+
+/// What a projectile does when its flight ends against a tile or entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum OnHit {
+    /// Report the hit and despawn; consumers spawn the blast. Also fires
+    /// when the lifetime runs out (fuse).
+    Explode,
+    /// Stop and stay until a consumer despawns it (grappling hook anchor).
+    /// Its lifetime no longer counts down.
+    Anchor,
+    /// Stop and stay for the rest of its lifetime (arrow in a wall).
+    Stick,
+    /// Report the hit and despawn.
+    #[default]
+    Despawn,
+}
+
+/// What a projectile hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTarget {
+    /// A solid tile, in wrapped world tile coordinates.
+    Tile(i32, i32),
+    Entity(Entity),
+    /// Nothing: the lifetime of an [`OnHit::Explode`] projectile ran out.
+    Expired,
+}
+
+/// A flying projectile.
+#[derive(Component, Debug, Clone)]
+pub struct Projectile {
+    /// Velocity in px/s.
+    pub velocity: Vec2,
+    /// Multiplier on [`PROJECTILE_GRAVITY`]; 0 flies in a straight line.
+    pub gravity_scale: f32,
+    /// Seconds left before it expires.
+    pub lifetime: f32,
+    /// Fraction of the speed into a wall kept when bouncing off it.
+    pub restitution: f32,
+    /// Wall bounces left before a wall hit ends the flight.
+    pub bounces_left: u32,
+    pub on_hit: OnHit,
+    /// Half-size (px) of the box used for entity hits.
+    pub radius: f32,
+    pub damage: f32,
+    pub knockback: f32,
+    /// Entity that fired it; never hit by its own projectile.
+    pub owner: Option<Entity>,
+    /// Stopped by an `Anchor` or `Stick` hit.
+    pub stuck: bool,
+}
+
+impl Projectile {
+    /// A projectile with no gravity, bounces or damage.
+    pub fn new(velocity: Vec2, on_hit: OnHit) -> Self {
+        Self {
+            velocity,
+            gravity_scale: 0.0,
+            lifetime: DEFAULT_PROJECTILE_LIFETIME,
+            restitution: 0.0,
+            bounces_left: 0,
+            on_hit,
+            radius: 4.0,
+            damage: 0.0,
+            knockback: 0.0,
+            owner: None,
+            stuck: false,
+        }
+    }
+
+    /// Launch an item's projectile towards `direction`.
+    pub fn from_spec(spec: &ProjectileSpec, direction: Vec2, owner: Option<Entity>) -> Self {
+        let direction = direction.try_normalize().unwrap_or(Vec2::X);
+        Self {
+            gravity_scale: spec.gravity,
+            lifetime: spec.lifetime,
+            restitution: spec.restitution,
+            bounces_left: spec.bounces,
+            owner,
+            ..Self::new(direction * spec.speed, spec.on_hit)
+        }
+    }
+
+    /// Count down the lifetime; true once it has run out. Anchored
+    /// projectiles never expire.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        if self.stuck && self.on_hit == OnHit::Anchor {
+            return false;
+        }
+        self.lifetime -= dt;
+        self.lifetime <= 0.0
+    }
+}
+
+/// How an item flies when thrown (`ItemAction::Throw`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectileSpec {
+    /// Launch speed in px/s.
+    pub speed: f32,
+    /// Multiplier on [`PROJECTILE_GRAVITY`].
+    #[serde(default = "default_gravity")]
+    pub gravity: f32,
+    /// Sprite path; `None` uses the item's icon.
+    #[serde(default)]
+    pub sprite: Option<String>,
+    #[serde(default = "default_lifetime")]
+    pub lifetime: f32,
+    /// Fraction of the speed into a wall kept on a bounce.
+    #[serde(default)]
+    pub restitution: f32,
+    /// Wall bounces before a wall hit ends the flight.
+    #[serde(default)]
+    pub bounces: u32,
+    #[serde(default)]
+    pub on_hit: OnHit,
+}
+
+fn default_gravity() -> f32 {
+    1.0
+}
+
+fn default_lifetime() -> f32 {
+    DEFAULT_PROJECTILE_LIFETIME
+}
+
+/// A projectile's flight ended.
+#[derive(Message, Debug, Clone)]
+pub struct ProjectileHit {
+    pub projectile: Entity,
+    pub owner: Option<Entity>,
+    /// World position of the projectile at the hit.
+    pub position: Vec2,
+    /// Velocity just before the hit.
+    pub velocity: Vec2,
+    pub target: HitTarget,
+    pub on_hit: OnHit,
+    pub damage: f32,
+    pub knockback: f32,
+}
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ProjectileHit>().add_systems(
+            Update,
+            (move_projectiles, expire_projectiles)
+                .chain()
+                .in_set(GameSet::Physics),
+        );
+    }
+}
+
+/// Spawn `projectile` at `position`, drawn with `sprite` if given.
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    projectile: Projectile,
+    position: Vec2,
+    sprite: Option<Handle<Image>>,
+) -> Entity {
+    let rotation = Quat::from_rotation_z(projectile.velocity.to_angle());
+    let mut entity = commands.spawn((
+        projectile,
+        Transform::from_xyz(position.x, position.y, PROJECTILE_Z).with_rotation(rotation),
+        Visibility::default(),
+    ));
+    if let Some(image) = sprite {
+        entity.insert(Sprite {
+            image,
+            custom_size: Some(Vec2::splat(PROJECTILE_SPRITE_SIZE)),
+            ..default()
+        });
+    }
+    entity.id()
+}
+
+/// Move `projectile` from `pos` for `dt` seconds: apply gravity (scaled by
+/// the planet's `gravity_scale`), sweep the path against solid tiles and
+/// bounce off walls while it may.
+///
+/// Returns the new position and, if its flight ended against a tile, that
+/// hit. The position is then at the contact point, just off the wall.
+pub fn step_projectile(
+    pos: Vec2,
+    projectile: &mut Projectile,
+    dt: f32,
+    gravity_scale: f32,
+    tile_size: f32,
+    mut is_solid: impl FnMut(i32, i32) -> bool,
+) -> (Vec2, Option<SegmentHit>) {
+    projectile.velocity.y -= PROJECTILE_GRAVITY * projectile.gravity_scale * gravity_scale * dt;
+
+    let mut pos = pos;
+    let mut remaining = dt;
+    for _ in 0..MAX_SWEEPS {
+        let to = pos + projectile.velocity * remaining;
+        let Some(hit) = sweep_segment(pos, to, tile_size, &mut is_solid) else {
+            return (to, None);
+        };
+        let normal = hit.normal.as_vec2();
+        pos = pos.lerp(to, hit.t) + normal * CONTACT_OFFSET;
+        remaining *= 1.0 - hit.t;
+
+        let into_wall = -projectile.velocity.dot(normal.normalize());
+        if projectile.bounces_left == 0 || into_wall * projectile.restitution < MIN_BOUNCE_SPEED {
+            return (pos, Some(hit));
+        }
+        projectile.bounces_left -= 1;
+        // Reflect the velocity off each face that was hit, keeping only
+        // `restitution` of the speed into it.
+        if hit.normal.x != 0 {
+            projectile.velocity.x *= -projectile.restitution;
+        }
+        if hit.normal.y != 0 {
+            projectile.velocity.y *= -projectile.restitution;
+        }
+    }
+    (pos, None)
+}
+
+/// Horizontal offset `a - b` taking the shorter way around a wrapping world.
+fn wrapped_dx(a: f32, b: f32, world_width: f32, wrap: bool) -> f32 {
+    let dx = a - b;
+    if wrap {
+        dx - world_width * (dx / world_width).round()
+    } else {
+        dx
+    }
+}
+
+/// Report a hit and apply the projectile's `on_hit`. Returns true if the
+/// projectile was despawned.
+fn finish_flight(
+    commands: &mut Commands,
+    hits: &mut MessageWriter<ProjectileHit>,
+    entity: Entity,
+    projectile: &mut Projectile,
+    position: Vec2,
+    velocity: Vec2,
+    target: HitTarget,
+) -> bool {
+    hits.write(ProjectileHit {
+        projectile: entity,
+        owner: projectile.owner,
+        position,
+        velocity,
+        target,
+        on_hit: projectile.on_hit,
+        damage: projectile.damage,
+        knockback: projectile.knockback,
+    });
+    match projectile.on_hit {
+        OnHit::Explode | OnHit::Despawn => {
+            commands.entity(entity).despawn();
+            true
+        }
+        OnHit::Anchor | OnHit::Stick => {
+            projectile.stuck = true;
+            projectile.velocity = Vec2::ZERO;
+            false
+        }
+    }
+}
+
+/// Move flying projectiles and end their flight against tiles and
+/// creatures.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    object_registry: Option<Res<ObjectRegistry>>,
+    planet: Option<Res<PlanetPhysics>>,
+    mut projectiles: Query<(Entity, &mut Transform, &mut Projectile)>,
+    targets: Query<(Entity, &Transform, &TileCollider), (With<Health>, Without<Projectile>)>,
+    mut hits: MessageWriter<ProjectileHit>,
+) {
+    let dt = time.delta_secs().min(MAX_DELTA_SECS);
+    let gravity_scale = planet.map_or(1.0, |p| p.gravity_scale);
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let is_solid = |tx: i32, ty: i32| -> bool {
+        match &object_registry {
+            Some(reg) => world_map.is_solid_or_object(tx, ty, &ctx_ref, reg),
+            None => world_map.is_solid(tx, ty, &ctx_ref),
+        }
+    };
+
+    for (entity, mut tf, mut projectile) in &mut projectiles {
+        if projectile.stuck {
+            continue;
+        }
+        let start = tf.translation.truncate();
+        let velocity = projectile.velocity;
+        let (pos, tile_hit) = step_projectile(
+            start,
+            &mut projectile,
+            dt,
+            gravity_scale,
+            config.tile_size,
+            is_solid,
+        );
+        tf.translation.x = pos.x;
+        tf.translation.y = pos.y;
+        if projectile.velocity != Vec2::ZERO {
+            tf.rotation = Quat::from_rotation_z(projectile.velocity.to_angle());
+        }
+
+        if let Some(hit) = tile_hit {
+            let tile = HitTarget::Tile(config.wrap_tile_x(hit.tile.0), hit.tile.1);
+            if finish_flight(
+                &mut commands,
+                &mut hits,
+                entity,
+                &mut projectile,
+                pos,
+                velocity,
+                tile,
+            ) || projectile.stuck
+            {
+                continue;
+            }
+        }
+
+        let target = targets.iter().find(|&(target, target_tf, collider)| {
+            if projectile.owner == Some(target) {
+                return false;
+            }
+            let offset = target_tf.translation.truncate();
+            let dx = wrapped_dx(pos.x, offset.x, config.world_pixel_width(), config.wrap_x);
+            dx.abs() < projectile.radius + collider.width / 2.0
+                && (pos.y - offset.y).abs() < projectile.radius + collider.height / 2.0
+        });
+        if let Some((target, _, _)) = target {
+            finish_flight(
+                &mut commands,
+                &mut hits,
+                entity,
+                &mut projectile,
+                pos,
+                velocity,
+                HitTarget::Entity(target),
+            );
+        }
+    }
+}
+
+/// Despawn projectiles whose lifetime ran out; `Explode` ones go off first.
+pub fn expire_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<(Entity, &Transform, &mut Projectile)>,
+    mut hits: MessageWriter<ProjectileHit>,
+) {
+    let dt = time.delta_secs();
+    for (entity, tf, mut projectile) in &mut projectiles {
+        if !projectile.tick(dt) {
+            continue;
+        }
+        if projectile.on_hit == OnHit::Explode {
+            let velocity = projectile.velocity;
+            finish_flight(
+                &mut commands,
+                &mut hits,
+                entity,
+                &mut projectile,
+                tf.translation.truncate(),
+                velocity,
+                HitTarget::Expired,
+            );
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::message::Messages;
+
+    use super::*;
+    use crate::registry::tile::TileId;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::Layer;
+    use crate::world::terrain_gen;
+
+    const TS: f32 = 32.0;
+
+    fn bouncy(velocity: Vec2, restitution: f32, bounces: u32) -> Projectile {
+        Projectile {
+            restitution,
+            bounces_left: bounces,
+            ..Projectile::new(velocity, OnHit::Despawn)
+        }
+    }
+
+    #[test]
+    fn bounce_loses_energy() {
+        let mut proj = bouncy(Vec2::new(0.0, -200.0), 0.5, 1);
+        let (pos, hit) =
+            step_projectile(Vec2::new(16.0, 8.0), &mut proj, 0.1, 1.0, TS, |_, y| y < 0);
+
+        assert!(hit.is_none());
+        assert!(
+            (proj.velocity.y - 100.0).abs() < 1e-3,
+            "{}",
+            proj.velocity.y
+        );
+        assert_eq!(proj.bounces_left, 0);
+        // 8px down to the floor, then 0.06s back up at half speed.
+        assert!((pos.y - 6.0).abs() < 0.1, "{}", pos.y);
+    }
+
+    #[test]
+    fn no_bounce_without_restitution() {
+        let mut proj = bouncy(Vec2::new(0.0, -200.0), 0.0, 3);
+        let (pos, hit) =
+            step_projectile(Vec2::new(16.0, 8.0), &mut proj, 0.1, 1.0, TS, |_, y| y < 0);
+
+        let hit = hit.expect("should hit the floor");
+        assert_eq!(hit.tile, (0, -1));
+        assert_eq!(hit.normal, IVec2::Y);
+        assert!(pos.y > 0.0 && pos.y < 0.1, "{}", pos.y);
+        assert_eq!(proj.bounces_left, 3);
+    }
+
+    #[test]
+    fn fast_projectile_does_not_tunnel_thin_wall() {
+        let mut proj = Projectile::new(Vec2::new(100_000.0, 0.0), OnHit::Despawn);
+        let (pos, hit) =
+            step_projectile(Vec2::new(16.0, 16.0), &mut proj, 0.05, 1.0, TS, |x, _| {
+                x == 5
+            });
+
+        assert_eq!(hit.map(|h| h.tile), Some((5, 0)));
+        assert!(pos.x < 5.0 * TS, "{}", pos.x);
+    }
+
+    #[test]
+    fn lifetime_expires_unless_anchored() {
+        let mut proj = Projectile::new(Vec2::X, OnHit::Stick);
+        assert!(!proj.tick(1.0));
+        assert!(proj.tick(DEFAULT_PROJECTILE_LIFETIME));
+
+        let mut hook = Projectile::new(Vec2::X, OnHit::Anchor);
+        hook.stuck = true;
+        assert!(!hook.tick(DEFAULT_PROJECTILE_LIFETIME * 2.0));
+    }
+
+    fn projectile_app() -> App {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(50),
+        ));
+        app.add_message::<ProjectileHit>()
+            .add_systems(Update, (move_projectiles, expire_projectiles).chain());
+        app
+    }
+
+    /// A tile row well above the terrain, where everything is air.
+    fn sky_row() -> i32 {
+        let (wc, _, _, _, pc, nc) = fixtures::test_world_ctx();
+        terrain_gen::surface_height(
+            &nc,
+            0,
+            &wc,
+            pc.layers.surface.terrain_frequency,
+            pc.layers.surface.terrain_amplitude,
+        ) + 20
+    }
+
+    fn run_until_hit(app: &mut App) -> ProjectileHit {
+        for _ in 0..20 {
+            app.update();
+            let hits: Vec<_> = app
+                .world_mut()
+                .resource_mut::<Messages<ProjectileHit>>()
+                .drain()
+                .collect();
+            if let Some(hit) = hits.into_iter().next() {
+                return hit;
+            }
+        }
+        panic!("projectile never hit anything");
+    }
+
+    #[test]
+    fn hit_across_world_seam_reports_wrapped_tile() {
+        let mut app = projectile_app();
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let row = sky_row();
+        app.world_mut()
+            .resource_mut::<WorldMap>()
+            .set_tile(0, row, Layer::Fg, TileId(1), &ctx);
+
+        // Fired rightwards from the last tile column towards tile 0.
+        let start = Vec2::new(wc.world_pixel_width() - TS * 1.5, (row as f32 + 0.5) * TS);
+        let projectile = app
+            .world_mut()
+            .spawn((
+                Projectile::new(Vec2::new(600.0, 0.0), OnHit::Despawn),
+                Transform::from_translation(start.extend(0.0)),
+            ))
+            .id();
+
+        let hit = run_until_hit(&mut app);
+        assert_eq!(hit.projectile, projectile);
+        assert_eq!(hit.target, HitTarget::Tile(0, row));
+        app.update();
+        assert!(app.world().get_entity(projectile).is_err());
+    }
+
+    #[test]
+    fn hits_creature_but_not_owner() {
+        let mut app = projectile_app();
+        let row = sky_row();
+        let y = (row as f32 + 0.5) * TS;
+        let collider = || TileCollider {
+            width: 16.0,
+            height: 16.0,
+        };
+        let owner = app
+            .world_mut()
+            .spawn((
+                Health::new(10.0),
+                collider(),
+                Transform::from_xyz(0.0, y, 0.0),
+            ))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((
+                Health::new(10.0),
+                collider(),
+                Transform::from_xyz(100.0, y, 0.0),
+            ))
+            .id();
+        app.world_mut().spawn((
+            Projectile {
+                owner: Some(owner),
+                damage: 3.0,
+                ..Projectile::new(Vec2::new(600.0, 0.0), OnHit::Stick)
+            },
+            Transform::from_xyz(0.0, y, 0.0),
+        ));
+
+        let hit = run_until_hit(&mut app);
+        assert_eq!(hit.target, HitTarget::Entity(target));
+        assert_eq!(hit.damage, 3.0);
+    }
+}
@@ -4,7 +4,7 @@ use bevy::prelude::*;
 use bevy::reflect::TypePath;
 use serde::Deserialize;
 
-use super::tile::TileDef;
+use super::tile::{TileDef, TileOrientation};
 use crate::crafting::Recipe;
 use crate::item::definition::ItemDef;
 use crate::object::definition::ObjectDef;
@@ -44,6 +44,10 @@ pub struct CharacterDefAsset {
     pub gravity: f32,
     pub width: f32,
     pub height: f32,
+    #[serde(default)]
+    pub collision_width: Option<f32>,
+    #[serde(default)]
+    pub collision_height: Option<f32>,
     #[serde(default = "default_magnet_radius")]
     pub magnet_radius: f32,
     #[serde(default = "default_magnet_strength")]
@@ -56,6 +60,14 @@ pub struct CharacterDefAsset {
     pub swim_gravity_factor: f32,
     #[serde(default = "default_swim_drag")]
     pub swim_drag: f32,
+    #[serde(default = "default_reach")]
+    pub break_reach: f32,
+    #[serde(default = "default_reach")]
+    pub place_reach: f32,
+    #[serde(default = "default_max_fall_speed")]
+    pub max_fall_speed: f32,
+    #[serde(default = "default_spawn_search_radius")]
+    pub spawn_search_radius: i32,
     pub sprite_size: (u32, u32),
     #[serde(default = "default_render_scale")]
     pub render_scale: f32,
@@ -65,7 +77,7 @@ pub struct CharacterDefAsset {
 }
 
 /// A single animation within a CharacterDefAsset.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 #[allow(dead_code)]
 pub struct AnimationDef {
     #[serde(default)]
@@ -74,7 +86,7 @@ pub struct AnimationDef {
 }
 
 /// Per-part sprite configuration within a character.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PartDef {
     /// Relative path to the sprite directory (within the character folder).
     /// Contains animation subdirectories matching the character's animation names.
@@ -94,7 +106,7 @@ pub struct PartDef {
 }
 
 /// All body parts for a modular character.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct CharacterPartsDef {
     pub body: PartDef,
     #[serde(default)]
@@ -146,11 +158,27 @@ pub struct ItemDefAsset {
     pub stats: Option<crate::item::definition::ItemStats>,
     #[serde(default)]
     pub blueprint_item: Option<String>,
+    #[serde(default)]
+    pub action: Option<crate::item::definition::ItemAction>,
+    #[serde(default)]
+    pub use_cooldown: Option<f32>,
+    #[serde(default)]
+    pub item_cooldown: Option<f32>,
+    #[serde(default)]
+    pub projectile: Option<crate::item::definition::ProjectileSpec>,
+    #[serde(default)]
+    pub light: Option<crate::item::definition::WornLight>,
+    #[serde(default)]
+    pub bag_rows: Option<usize>,
+    #[serde(default)]
+    pub effects: Vec<crate::item::definition::ConsumeEffect>,
+    #[serde(default)]
+    pub tile_conversions: Vec<crate::item::definition::TileConversion>,
 }
 
 impl ItemDefAsset {
-    /// Convert to an `ItemDef`, resolving the icon path relative to the
-    /// item.ron file's directory.
+    /// Convert to an `ItemDef`, resolving the icon and projectile sprite paths
+    /// relative to the item.ron file's directory.
     pub fn to_item_def(&self, base_path: &str) -> ItemDef {
         ItemDef {
             id: self.id.clone(),
@@ -165,6 +193,17 @@ impl ItemDefAsset {
             equipment_slot: self.equipment_slot,
             stats: self.stats.clone(),
             blueprint_item: self.blueprint_item.clone(),
+            action: self.action,
+            use_cooldown: self.use_cooldown,
+            item_cooldown: self.item_cooldown,
+            projectile: self.projectile.clone().map(|mut spec| {
+                spec.sprite = spec.sprite.map(|s| format!("{}{}", base_path, s));
+                spec
+            }),
+            light: self.light,
+            bag_rows: self.bag_rows,
+            effects: self.effects.clone(),
+            tile_conversions: self.tile_conversions.clone(),
         }
     }
 }
@@ -191,6 +230,15 @@ fn default_swim_gravity_factor() -> f32 {
 fn default_swim_drag() -> f32 {
     0.15
 }
+fn default_reach() -> f32 {
+    5.0
+}
+fn default_max_fall_speed() -> f32 {
+    900.0
+}
+fn default_spawn_search_radius() -> i32 {
+    64
+}
 fn default_render_scale() -> f32 {
     1.0
 }
@@ -230,6 +278,66 @@ pub struct AutotileAsset {
     pub atlas_columns: u32,
     pub atlas_rows: u32,
     pub tiles: HashMap<u8, BitmaskMapping>,
+    /// What to draw for a bitmask that has no entry in `tiles`.
+    #[serde(default)]
+    pub fallback: AutotileFallback,
+    /// Texels trimmed from each sprite edge when sampling the combined atlas,
+    /// against seams between neighbouring cells. Read from the first loaded
+    /// autotile, like `tile_size`.
+    #[serde(default = "default_uv_inset")]
+    pub uv_inset: f32,
+    /// Variants drawn for orientable tiles instead of their bitmask's, per
+    /// orientation. Orientations missing here keep the bitmask variants.
+    #[serde(default)]
+    pub orientations: HashMap<TileOrientation, Vec<SpriteVariant>>,
+    /// Cycles the sprite through extra sheet columns over time (water or
+    /// lava surfaces). Unset draws the first column only.
+    #[serde(default)]
+    pub animation: Option<TileAnimation>,
+}
+
+fn default_uv_inset() -> f32 {
+    0.5
+}
+
+/// Frame sequence of an animated autotile. Frame `n` is column `n` of the
+/// sheet, drawn with the same rows as column 0; the sequence loops.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct TileAnimation {
+    /// Number of frames, i.e. sheet columns.
+    pub frames: u32,
+    /// Seconds each frame stays on screen.
+    pub frame_secs: f32,
+}
+
+impl TileAnimation {
+    /// Whether the sequence actually changes frames; a single frame or a
+    /// non-positive duration draws column 0 only.
+    pub fn is_animated(&self) -> bool {
+        self.frames > 1 && self.frame_secs > 0.0
+    }
+
+    /// Frame shown `elapsed` seconds in. Matches the tile shader, which
+    /// computes the same offset from `globals.time`.
+    pub fn frame_at(&self, elapsed: f32) -> u32 {
+        if !self.is_animated() {
+            return 0;
+        }
+        (elapsed.max(0.0) / self.frame_secs).floor() as u32 % self.frames
+    }
+}
+
+/// Sprite used for bitmasks missing from an autotile's `tiles` map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum AutotileFallback {
+    /// The mapped mask sharing the most neighbours with the requested one
+    /// (a subset of its bits), preferring masks that keep cardinal edges.
+    #[default]
+    NearestSimpler,
+    /// The fully surrounded sprite (mask 255).
+    Full,
+    /// A specific mask.
+    Mask(u8),
 }
 
 /// Layer configuration within a planet type.
@@ -241,6 +349,13 @@ pub struct LayerConfigAsset {
     /// Fraction of world height this layer occupies (0.0–1.0).
     #[serde(default)]
     pub depth_ratio: f64,
+    /// Multiplier on the biome cave threshold at the (top, bottom) of the layer.
+    #[serde(default = "default_cave_threshold_scale")]
+    pub cave_threshold_scale: (f64, f64),
+}
+
+fn default_cave_threshold_scale() -> (f64, f64) {
+    (1.0, 1.0)
 }
 
 /// All 4 vertical layers.
@@ -278,6 +393,40 @@ pub struct WeatherConfig {
     pub types: Vec<WeatherTypeEntry>,
 }
 
+/// How region difficulty ([`WorldCtxRef::difficulty_at`]) grows away from
+/// the spawn point and with depth.
+///
+/// [`WorldCtxRef::difficulty_at`]: crate::world::ctx::WorldCtxRef::difficulty_at
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct DifficultyCurve {
+    /// Fraction of the way from spawn to the far side of the world
+    /// (0.0–1.0) at which the distance term reaches 0.5. Below 0.5 the
+    /// difficulty climbs quickly near spawn; 0.5 is linear.
+    #[serde(default = "default_half_distance")]
+    pub half_distance: f32,
+    /// Weight of the depth layer against the distance term (0 = depth
+    /// doesn't matter, 1 = both count equally).
+    #[serde(default = "default_depth_weight")]
+    pub depth_weight: f32,
+}
+
+fn default_half_distance() -> f32 {
+    0.5
+}
+
+fn default_depth_weight() -> f32 {
+    0.5
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        Self {
+            half_distance: default_half_distance(),
+            depth_weight: default_depth_weight(),
+        }
+    }
+}
+
 /// Asset loaded from *.planet.ron
 #[derive(Asset, TypePath, Debug, Deserialize)]
 pub struct PlanetTypeAsset {
@@ -314,12 +463,88 @@ pub struct PlanetTypeAsset {
     pub temperature_celsius_offsets: Option<[f32; 4]>,
     #[serde(default)]
     pub wrap_x: Option<bool>,
+    /// Tile walling off the edges when `wrap_x` is false (None = open edges).
+    #[serde(default)]
+    pub border_tile: Option<String>,
+    /// Unbreakable tile lining the bottom of the world (None = no band).
+    #[serde(default)]
+    pub bedrock_tile: Option<String>,
+    /// Rows of `bedrock_tile` at the bottom (None = 4).
+    #[serde(default)]
+    pub bedrock_depth: Option<i32>,
+    /// Rows on either side of each layer boundary over which cave frequency
+    /// and size blend between the layers (None = 16, 0 = hard edges).
+    #[serde(default)]
+    pub layer_blend: Option<i32>,
+    /// `(start, min_jump)`: jumps weaken above `start` (fraction of world
+    /// height) down to `min_jump` times their strength at the top
+    /// (None = no thinning).
+    #[serde(default)]
+    pub thin_air: Option<(f32, f32)>,
+    /// Multiplier on the gravity and terminal velocity of everything that
+    /// falls: the player, dropped items, creatures and projectiles
+    /// (None = 1.0). Must be above zero.
+    #[serde(default)]
+    pub gravity_scale: Option<f32>,
+    /// Air thickness relative to an Earth-like planet: thinner air lets a
+    /// harsher sun through and darkens the sky towards space (None = 1.0).
+    #[serde(default)]
+    pub atmosphere_density: Option<f32>,
+    /// Seconds per day/night cycle, replacing `cycle_duration_range`
+    /// (None = generated).
+    #[serde(default)]
+    pub day_length: Option<f32>,
+    /// Biome forced onto the region at x = 0, where the player spawns.
+    /// Must be the primary or one of the secondary biomes (None = random).
+    #[serde(default)]
+    pub starter_biome: Option<String>,
+    /// Fewest regions between two regions of the same biome (None = 1, which
+    /// only keeps neighbours apart).
+    #[serde(default)]
+    pub biome_separation: Option<u32>,
+    /// Difficulty curve away from spawn; omitted fields take defaults.
+    #[serde(default)]
+    pub difficulty: DifficultyCurve,
+    /// Border styles between biome pairs, e.g. a cliff where desert meets
+    /// forest (unlisted pairs blend).
+    #[serde(default)]
+    pub biome_borders: Vec<crate::registry::biome::BiomeBorder>,
     #[serde(default)]
     pub base_temperature: Option<f32>,
     #[serde(default)]
     pub weather: Option<WeatherConfig>,
 }
 
+impl PlanetTypeAsset {
+    /// Values that load but can't be used; each falls back to its default.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(scale) = self.gravity_scale.filter(|&s| s <= 0.0) {
+            problems.push(format!("gravity_scale {scale} must be above zero"));
+        }
+        if let Some(density) = self.atmosphere_density.filter(|&d| d < 0.0) {
+            problems.push(format!("atmosphere_density {density} must not be negative"));
+        }
+        if let Some(length) = self.day_length.filter(|&l| l <= 0.0) {
+            problems.push(format!("day_length {length} must be above zero"));
+        }
+        for border in &self.biome_borders {
+            let (a, b) = &border.between;
+            if let crate::registry::biome::BorderStyle::Cliff { height } = border.style
+                && height <= 0
+            {
+                problems.push(format!("cliff between {a} and {b} must be above zero"));
+            }
+            for biome in [a, b] {
+                if *biome != self.primary_biome && !self.secondary_biomes.contains(biome) {
+                    problems.push(format!("biome border names unknown biome '{biome}'"));
+                }
+            }
+        }
+        problems
+    }
+}
+
 /// Asset loaded from *.biome.ron
 #[derive(Asset, TypePath, Debug, Deserialize)]
 pub struct BiomeAsset {
@@ -332,6 +557,9 @@ pub struct BiomeAsset {
     pub parallax: Option<String>,
     #[serde(default)]
     pub temperature_offset: f32,
+    /// Tiles scattered on the surface, tried in order per column.
+    #[serde(default)]
+    pub decorations: Vec<DecorationAsset>,
     // Future fields — not implemented in MVP, kept for RON schema forward-compatibility
     #[allow(dead_code)]
     #[serde(default)]
@@ -353,6 +581,23 @@ pub struct BiomeAsset {
     pub status_effects: Option<Vec<String>>,
 }
 
+/// A surface decoration of a biome, as written in *.biome.ron.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecorationAsset {
+    /// Tile placed on top of the surface block.
+    pub tile: String,
+    /// Chance that a column inside a cluster gets the decoration.
+    pub density: f32,
+    /// Frequency (per tile) of the cluster noise; 0 = no clusters, every
+    /// column rolls `density` on its own.
+    #[serde(default)]
+    pub cluster_scale: f64,
+    /// Cluster noise value (-1.0–1.0) a column must exceed to be inside a
+    /// cluster. Higher = fewer, smaller clusters.
+    #[serde(default)]
+    pub cluster_threshold: f64,
+}
+
 /// Asset loaded from *.recipes.ron — a list of crafting recipes.
 #[derive(Asset, TypePath, Debug, Deserialize)]
 #[serde(transparent)]
@@ -417,4 +662,50 @@ mod tests {
         assert_eq!(asset.max_stack, 999);
         assert!(asset.placeable.is_some());
     }
+
+    #[test]
+    fn hoe_converts_dirt_to_tilled_soil() {
+        let ron_str = std::fs::read_to_string("assets/content/items/hoe/hoe.item.ron")
+            .expect("hoe.item.ron should exist");
+        let asset: ItemDefAsset = ron::from_str(&ron_str).expect("hoe.item.ron should parse");
+        let hoe = asset.to_item_def("content/items/hoe/");
+        assert_eq!(
+            hoe.action(crate::inventory::Hand::Left),
+            Some(crate::item::ItemAction::ModifyTile)
+        );
+        assert_eq!(hoe.converted_tile("dirt"), Some("tilled_soil"));
+        assert_eq!(hoe.converted_tile("stone"), None);
+    }
+
+    fn read_planet(name: &str) -> PlanetTypeAsset {
+        let path = format!("assets/worlds/planet_types/{name}/{name}.planet.ron");
+        let ron_str = std::fs::read_to_string(&path).expect("planet type should exist");
+        ron::from_str(&ron_str).expect("planet type should parse")
+    }
+
+    #[test]
+    fn moon_has_low_gravity_and_thin_air() {
+        let moon = read_planet("moon");
+        let garden = read_planet("garden");
+        assert!(moon.validate().is_empty(), "{:?}", moon.validate());
+        assert!(moon.gravity_scale.unwrap() < garden.gravity_scale.unwrap_or(1.0));
+        assert!(moon.atmosphere_density.unwrap() < garden.atmosphere_density.unwrap_or(1.0));
+        assert!(moon.day_length.is_some());
+    }
+
+    #[test]
+    fn gravity_scale_must_be_positive() {
+        let mut planet = read_planet("barren");
+        assert!(planet.validate().is_empty());
+
+        for bad in [0.0, -1.0] {
+            planet.gravity_scale = Some(bad);
+            let problems = planet.validate();
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].contains("gravity_scale"), "{problems:?}");
+            // Loading falls back to normal gravity.
+            let config = crate::registry::loading::planet_config_from_asset(&planet, 1024);
+            assert_eq!(config.gravity_scale, 1.0);
+        }
+    }
 }
@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
+use crate::registry::assets::DifficultyCurve;
 use crate::registry::tile::TileId;
+use crate::world::biome_map::BorderCliffs;
 
 /// Type-safe biome identifier backed by a `u16`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -28,6 +30,19 @@ pub struct BiomeDef {
     // stored for hot-reload; parallax loaded separately via BiomeParallaxConfigs
     pub parallax_path: Option<String>,
     pub temperature_offset: f32,
+    /// Tiles scattered on the surface, tried in order per column.
+    pub decorations: Vec<SurfaceDecoration>,
+}
+
+/// A surface decoration resolved against the tile registry. See
+/// [`DecorationAsset`](crate::registry::assets::DecorationAsset) for the
+/// fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceDecoration {
+    pub tile: TileId,
+    pub density: f32,
+    pub cluster_scale: f64,
+    pub cluster_threshold: f64,
 }
 
 /// All loaded biome definitions keyed by BiomeId.
@@ -73,6 +88,11 @@ impl BiomeRegistry {
             .unwrap_or_else(|| panic!("Unknown biome name: {name}"))
     }
 
+    /// Like [`Self::id_by_name`], but `None` for unknown names.
+    pub fn try_id_by_name(&self, name: &str) -> Option<BiomeId> {
+        self.name_to_id.get(name).copied()
+    }
+
     pub fn name_of(&self, id: BiomeId) -> &str {
         self.id_to_name
             .get(&id)
@@ -107,6 +127,40 @@ impl LayerBoundaries {
     }
 }
 
+/// Rows of bedrock a planet with a `bedrock_tile` gets unless it says.
+pub const DEFAULT_BEDROCK_DEPTH: i32 = 4;
+/// Rows on either side of a layer boundary over which terrain parameters
+/// blend, unless the planet says.
+pub const DEFAULT_LAYER_BLEND: i32 = 16;
+
+/// How the surface meets at the border between two biome regions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum BorderStyle {
+    /// The surface runs on across the border.
+    #[default]
+    Blend,
+    /// The surface steps by `height` tiles at the border, a seeded side up.
+    Cliff { height: i32 },
+}
+
+/// Border style between regions of two biomes, given in either order.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct BiomeBorder {
+    pub between: (String, String),
+    pub style: BorderStyle,
+}
+
+/// Style of the border between biomes `a` and `b`; pairs not listed blend.
+pub fn border_style(borders: &[BiomeBorder], a: &str, b: &str) -> BorderStyle {
+    borders
+        .iter()
+        .find(|border| {
+            let (x, y) = (&border.between.0, &border.between.1);
+            (x == a && y == b) || (x == b && y == a)
+        })
+        .map_or(BorderStyle::Blend, |border| border.style)
+}
+
 /// Runtime planet type data, built from PlanetTypeAsset.
 #[derive(Resource, Debug, Clone)]
 pub struct PlanetConfig {
@@ -117,9 +171,58 @@ pub struct PlanetConfig {
     pub layers: LayerConfigs,
     /// Computed Y boundaries for each layer.
     pub layer_boundaries: LayerBoundaries,
+    /// Rows on either side of each layer boundary over which the terrain
+    /// parameters of the two layers blend (0 = hard edges).
+    pub layer_blend: i32,
     pub region_width_min: u32,
     pub region_width_max: u32,
     pub primary_region_ratio: f64,
+    /// Scales gravity and terminal velocity (0.5 = low-gravity moon).
+    pub gravity_scale: f32,
+    /// Air thickness relative to an Earth-like planet (1.0).
+    pub atmosphere_density: f32,
+    /// Seconds per day/night cycle (None = the generated cycle).
+    pub day_length: Option<f32>,
+    /// Biome forced onto the spawn region at x = 0 (None = random).
+    pub starter_biome: Option<String>,
+    /// Fewest regions between two regions of the same biome.
+    pub biome_separation: u32,
+    /// Tile filling the columns past the edges of a non-wrapping world
+    /// (None = open edges).
+    pub border_tile: Option<String>,
+    /// Unbreakable tile filling the bottom `bedrock_depth` rows (None = no
+    /// band, and stone below the world).
+    pub bedrock_tile: Option<String>,
+    pub bedrock_depth: i32,
+    /// Air thinning out near the top of the world (None = same air all the
+    /// way up).
+    pub thin_air: Option<ThinAir>,
+    /// How difficulty grows away from spawn and with depth.
+    pub difficulty: DifficultyCurve,
+    /// Border styles between biome pairs.
+    pub biome_borders: Vec<BiomeBorder>,
+    /// Cliffs at this world's region borders, resolved from `biome_borders`
+    /// once the biome map exists (see [`BorderCliffs::new`]).
+    pub border_cliffs: BorderCliffs,
+}
+
+/// Jumps weaken above `start` (fraction of world height), down to
+/// `min_jump` times their strength at the very top.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThinAir {
+    pub start: f32,
+    pub min_jump: f32,
+}
+
+impl ThinAir {
+    /// Jump multiplier at `height_ratio` (0.0 bottom, 1.0 top of the world).
+    pub fn jump_factor(&self, height_ratio: f32) -> f32 {
+        if height_ratio <= self.start || self.start >= 1.0 {
+            return 1.0;
+        }
+        let t = ((height_ratio - self.start) / (1.0 - self.start)).min(1.0);
+        1.0 + (self.min_jump - 1.0) * t
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +232,9 @@ pub struct LayerConfig {
     pub terrain_amplitude: f64,
     /// Fraction of world height this layer occupies (0.0–1.0).
     pub depth_ratio: f64,
+    /// Multiplier on the biome cave threshold at the (top, bottom) of the layer,
+    /// interpolated linearly with depth. `(1.0, 1.0)` keeps caves uniform.
+    pub cave_threshold_scale: (f64, f64),
 }
 
 #[derive(Debug, Clone)]
@@ -139,8 +245,19 @@ pub struct LayerConfigs {
     pub core: LayerConfig,
 }
 
+impl LayerConfigs {
+    pub fn get(&self, layer: WorldLayer) -> &LayerConfig {
+        match layer {
+            WorldLayer::Surface => &self.surface,
+            WorldLayer::Underground => &self.underground,
+            WorldLayer::DeepUnderground => &self.deep_underground,
+            WorldLayer::Core => &self.core,
+        }
+    }
+}
+
 /// Determines which vertical layer a tile_y coordinate belongs to.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
 pub enum WorldLayer {
     Core,
     DeepUnderground,
@@ -162,6 +279,17 @@ impl WorldLayer {
             WorldLayer::Surface
         }
     }
+
+    /// Tile-y range `[bottom, top)` covered by this layer.
+    pub fn y_range(self, planet_config: &PlanetConfig, world_height: i32) -> (i32, i32) {
+        let b = &planet_config.layer_boundaries;
+        match self {
+            WorldLayer::Core => (0, b.core_top),
+            WorldLayer::DeepUnderground => (b.core_top, b.deep_underground_top),
+            WorldLayer::Underground => (b.deep_underground_top, b.underground_top),
+            WorldLayer::Surface => (b.underground_top, world_height),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -187,6 +315,31 @@ mod tests {
         assert_eq!(WorldLayer::from_tile_y(1023, &pc), WorldLayer::Surface);
     }
 
+    #[test]
+    fn thin_air_weakens_jumps_above_its_start() {
+        let thin_air = ThinAir {
+            start: 0.8,
+            min_jump: 0.5,
+        };
+        assert_eq!(thin_air.jump_factor(0.5), 1.0);
+        assert_eq!(thin_air.jump_factor(0.8), 1.0);
+        assert!((thin_air.jump_factor(0.9) - 0.75).abs() < 1e-6);
+        assert_eq!(thin_air.jump_factor(1.0), 0.5);
+        assert_eq!(thin_air.jump_factor(1.5), 0.5);
+    }
+
+    #[test]
+    fn border_style_matches_pairs_in_either_order() {
+        let borders = vec![BiomeBorder {
+            between: ("meadow".into(), "forest".into()),
+            style: BorderStyle::Cliff { height: 12 },
+        }];
+        let cliff = BorderStyle::Cliff { height: 12 };
+        assert_eq!(border_style(&borders, "meadow", "forest"), cliff);
+        assert_eq!(border_style(&borders, "forest", "meadow"), cliff);
+        assert_eq!(border_style(&borders, "forest", "rocky"), BorderStyle::Blend);
+    }
+
     #[test]
     fn biome_registry_insert_and_get() {
         let mut reg = BiomeRegistry::default();
@@ -201,6 +354,7 @@ mod tests {
                 cave_threshold: 0.3,
                 parallax_path: Some("biomes/meadow/parallax.ron".into()),
                 temperature_offset: 0.0,
+                decorations: vec![],
             },
         );
         let def = reg.get(id);
@@ -224,6 +378,7 @@ mod tests {
                 cave_threshold: 0.3,
                 parallax_path: None,
                 temperature_offset: 0.0,
+                decorations: vec![],
             },
         );
         let id2 = reg.insert(
@@ -237,6 +392,7 @@ mod tests {
                 cave_threshold: 0.3,
                 parallax_path: None,
                 temperature_offset: 0.0,
+                decorations: vec![],
             },
         );
         assert_eq!(id1, id2, "re-insert must return same BiomeId");
@@ -8,17 +8,24 @@ use super::assets::{
     BiomeAsset, CharacterDefAsset, ItemDefAsset, LiquidRegistryAsset, ObjectDefAsset,
     ParallaxConfigAsset, PlanetTypeAsset, RecipeListAsset, TileRegistryAsset,
 };
-use super::biome::{
-    BiomeDef, BiomeId, BiomeRegistry, LayerBoundaries, LayerConfig, LayerConfigs, PlanetConfig,
-};
+use super::biome::{BiomeId, BiomeRegistry, PlanetConfig};
+use super::loading::{biome_def_from_asset, planet_config_from_asset, CharacterAnimConfig};
 use super::player::PlayerConfig;
+use super::texture_check::z_order_collisions;
 use super::tile::TileRegistry;
 use super::world::ActiveWorld;
 use super::{BiomeParallaxConfigs, RegistryHandles};
+use crate::fishing::FishingTable;
+use crate::tutorial::Tutorial;
+use crate::world::reactions::ReactionRules;
 use crate::object::registry::ObjectRegistry;
 
+use crate::cosmos::address::CelestialAddress;
+use crate::cosmos::assets::GenerationConfigAsset;
+use crate::cosmos::current::CurrentSystem;
 use crate::parallax::config::ParallaxConfig;
 use crate::world::biome_map::BiomeMap;
+use crate::world::regenerate::{rebuild_biome_map, RegenerateWorld, WorldChange};
 
 /// Keeps biome-related asset handles alive for hot-reload detection.
 #[derive(Resource)]
@@ -26,6 +33,9 @@ pub(crate) struct BiomeHandles {
     pub(crate) planet_type: Handle<PlanetTypeAsset>,
     pub(crate) biomes: Vec<(BiomeId, Handle<BiomeAsset>)>,
     pub(crate) parallax_configs: Vec<(BiomeId, Handle<ParallaxConfigAsset>)>,
+    /// Checked (possibly downscaled) parallax images; held so they aren't
+    /// reloaded from disk at full size.
+    pub(crate) _parallax_images: Vec<Handle<Image>>,
 }
 
 pub(crate) fn hot_reload_character(
@@ -33,6 +43,7 @@ pub(crate) fn hot_reload_character(
     handles: Res<RegistryHandles>,
     assets: Res<Assets<CharacterDefAsset>>,
     mut config: ResMut<PlayerConfig>,
+    mut anim_config: ResMut<CharacterAnimConfig>,
 ) {
     for event in events.read() {
         if let AssetEvent::Modified { id } = event
@@ -44,12 +55,32 @@ pub(crate) fn hot_reload_character(
             config.gravity = asset.gravity;
             config.width = asset.width;
             config.height = asset.height;
+            config.collision_width = asset.collision_width;
+            config.collision_height = asset.collision_height;
             config.magnet_radius = asset.magnet_radius;
             config.magnet_strength = asset.magnet_strength;
             config.pickup_radius = asset.pickup_radius;
             config.swim_impulse = asset.swim_impulse;
             config.swim_gravity_factor = asset.swim_gravity_factor;
             config.swim_drag = asset.swim_drag;
+            config.break_reach = asset.break_reach;
+            config.place_reach = asset.place_reach;
+            config.max_fall_speed = asset.max_fall_speed;
+            config.spawn_search_radius = asset.spawn_search_radius;
+
+            // Only touch the animation config when visual fields actually
+            // changed, so numeric tweaks don't rebuild sprite handles.
+            if anim_config.sprite_size != asset.sprite_size
+                || anim_config.render_scale != asset.render_scale
+                || anim_config.animations != asset.animations
+                || anim_config.parts != asset.parts
+            {
+                anim_config.sprite_size = asset.sprite_size;
+                anim_config.render_scale = asset.render_scale;
+                anim_config.animations = asset.animations.clone();
+                anim_config.parts = asset.parts.clone();
+                info!("Hot-reloaded CharacterAnimConfig ({} animations)", asset.animations.len());
+            }
             info!(
                 "Hot-reloaded PlayerConfig: speed={}, jump={}, gravity={}, magnet_r={}, magnet_s={}",
                 asset.speed, asset.jump_velocity, asset.gravity,
@@ -71,6 +102,9 @@ pub(crate) fn hot_reload_tiles(
             && let Some(asset) = assets.get(&handles.tiles)
         {
             *registry = TileRegistry::from_defs(asset.tiles.clone());
+            for problem in registry.validate() {
+                warn!("Tile data: {problem}");
+            }
             info!("Hot-reloaded TileRegistry ({} tiles)", asset.tiles.len());
         }
     }
@@ -148,19 +182,7 @@ pub(crate) fn hot_reload_biomes(
                     && let Some(asset) = biome_assets.get(handle)
                 {
                     let name = biome_registry.name_of(*biome_id).to_string();
-                    biome_registry.insert(
-                        &name,
-                        BiomeDef {
-                            id: asset.id.clone(),
-                            surface_block: tile_registry.by_name(&asset.surface_block),
-                            subsurface_block: tile_registry.by_name(&asset.subsurface_block),
-                            subsurface_depth: asset.subsurface_depth,
-                            fill_block: tile_registry.by_name(&asset.fill_block),
-                            cave_threshold: asset.cave_threshold,
-                            parallax_path: asset.parallax.clone(),
-                            temperature_offset: asset.temperature_offset,
-                        },
-                    );
+                    biome_registry.insert(&name, biome_def_from_asset(asset, &tile_registry));
                     info!("Hot-reloaded biome: {name}");
                     break;
                 }
@@ -183,59 +205,13 @@ pub(crate) fn hot_reload_planet_type(
             && *id == handles.planet_type.id()
             && let Some(asset) = planet_assets.get(&handles.planet_type)
         {
-            planet_config.id = asset.id.clone();
-            planet_config.primary_biome = asset.primary_biome.clone();
-            planet_config.secondary_biomes = asset.secondary_biomes.clone();
-            planet_config.layers = LayerConfigs {
-                surface: LayerConfig {
-                    primary_biome: asset.layers.surface.primary_biome.clone(),
-                    terrain_frequency: asset.layers.surface.terrain_frequency,
-                    terrain_amplitude: asset.layers.surface.terrain_amplitude,
-                    depth_ratio: asset.layers.surface.depth_ratio,
-                },
-                underground: LayerConfig {
-                    primary_biome: asset.layers.underground.primary_biome.clone(),
-                    terrain_frequency: asset.layers.underground.terrain_frequency,
-                    terrain_amplitude: asset.layers.underground.terrain_amplitude,
-                    depth_ratio: asset.layers.underground.depth_ratio,
-                },
-                deep_underground: LayerConfig {
-                    primary_biome: asset.layers.deep_underground.primary_biome.clone(),
-                    terrain_frequency: asset.layers.deep_underground.terrain_frequency,
-                    terrain_amplitude: asset.layers.deep_underground.terrain_amplitude,
-                    depth_ratio: asset.layers.deep_underground.depth_ratio,
-                },
-                core: LayerConfig {
-                    primary_biome: asset.layers.core.primary_biome.clone(),
-                    terrain_frequency: asset.layers.core.terrain_frequency,
-                    terrain_amplitude: asset.layers.core.terrain_amplitude,
-                    depth_ratio: asset.layers.core.depth_ratio,
-                },
-            };
-            planet_config.layer_boundaries = LayerBoundaries::from_layers(
-                &planet_config.layers,
-                world_config.height_tiles,
-            );
-            planet_config.region_width_min = asset.region_width_min;
-            planet_config.region_width_max = asset.region_width_max;
-            planet_config.primary_region_ratio = asset.primary_region_ratio;
+            for problem in asset.validate() {
+                warn!("Planet type '{}': {problem}", asset.id);
+            }
+            *planet_config = planet_config_from_asset(asset, world_config.height_tiles);
 
             // Rebuild BiomeMap with updated planet config
-            let secondaries: Vec<&str> = planet_config
-                .secondary_biomes
-                .iter()
-                .map(|s| s.as_str())
-                .collect();
-            *biome_map = BiomeMap::generate(
-                &planet_config.primary_biome,
-                &secondaries,
-                world_config.seed as u64,
-                world_config.width_tiles as u32,
-                planet_config.region_width_min,
-                planet_config.region_width_max,
-                planet_config.primary_region_ratio,
-                &biome_registry,
-            );
+            *biome_map = rebuild_biome_map(&mut planet_config, &world_config, &biome_registry);
             info!(
                 "Hot-reloaded PlanetConfig + BiomeMap ({} regions)",
                 biome_map.regions.len()
@@ -244,6 +220,80 @@ pub(crate) fn hot_reload_planet_type(
     }
 }
 
+/// Apply an edited `generation.ron` to the running world. Settings that fit
+/// the live world, like the chunk load radius, are swapped in place; a new
+/// chunk size, tile size or default planet size regenerates the world.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn hot_reload_generation_config(
+    mut events: MessageReader<AssetEvent<GenerationConfigAsset>>,
+    handles: Res<RegistryHandles>,
+    gen_config_assets: Res<Assets<GenerationConfigAsset>>,
+    biome_handles: Res<BiomeHandles>,
+    planet_assets: Res<Assets<PlanetTypeAsset>>,
+    mut current_system: ResMut<CurrentSystem>,
+    mut world_config: ResMut<ActiveWorld>,
+    planet_config: Res<PlanetConfig>,
+    mut regenerate: MessageWriter<RegenerateWorld>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && *id == handles.generation_config.id()
+            && let Some(asset) = gen_config_assets.get(&handles.generation_config)
+        {
+            // Later warps build their worlds from these.
+            current_system.chunk_size = asset.chunk_size;
+            current_system.tile_size = asset.tile_size;
+            current_system.chunk_load_radius = asset.chunk_load_radius;
+
+            let mut next = world_config.clone();
+            next.chunk_size = asset.chunk_size;
+            next.tile_size = asset.tile_size;
+            next.chunk_load_radius = asset.chunk_load_radius;
+
+            // Planets whose type sets no size of its own use the default size.
+            let planet = planet_assets.get(&biome_handles.planet_type);
+            let is_ship = matches!(next.address, CelestialAddress::Ship { .. });
+            if !is_ship && planet.is_some_and(|p| p.size.is_none()) {
+                next.width_tiles = asset.default_planet_size.width;
+                next.height_tiles = asset.default_planet_size.height;
+                if let Some(body) = current_system
+                    .system
+                    .bodies
+                    .iter_mut()
+                    .find(|b| b.address == next.address)
+                {
+                    body.width_tiles = next.width_tiles;
+                    body.height_tiles = next.height_tiles;
+                }
+            }
+
+            match WorldChange::between(&world_config, &next) {
+                WorldChange::Unchanged => {}
+                WorldChange::InPlace => {
+                    *world_config = next;
+                    info!("Hot-reloaded generation config in place");
+                }
+                // The hull is built once on arrival, so the ship keeps its
+                // layout until the player warps back to it.
+                WorldChange::Identity if is_ship => {
+                    warn!("Generation config change applies to the ship after the next warp");
+                }
+                WorldChange::Identity => {
+                    let planet_config = planet.map_or_else(
+                        || planet_config.clone(),
+                        |asset| planet_config_from_asset(asset, next.height_tiles),
+                    );
+                    info!("Hot-reloaded generation config; regenerating the world");
+                    regenerate.write(RegenerateWorld {
+                        world: next,
+                        planet_config,
+                    });
+                }
+            }
+        }
+    }
+}
+
 pub(crate) fn hot_reload_biome_parallax(
     mut events: MessageReader<AssetEvent<ParallaxConfigAsset>>,
     handles: Res<BiomeHandles>,
@@ -256,6 +306,9 @@ pub(crate) fn hot_reload_biome_parallax(
                 if *id == handle.id()
                     && let Some(asset) = parallax_assets.get(handle)
                 {
+                    for problem in z_order_collisions(&asset.layers) {
+                        warn!("biome {biome_id}: {problem}");
+                    }
                     biome_parallax.configs.insert(
                         *biome_id,
                         ParallaxConfig {
@@ -371,3 +424,54 @@ pub(crate) fn hot_reload_ui_theme(
         }
     }
 }
+
+pub(crate) fn hot_reload_fishing(
+    mut events: MessageReader<AssetEvent<FishingTable>>,
+    handles: Res<RegistryHandles>,
+    assets: Res<Assets<FishingTable>>,
+    mut table: ResMut<FishingTable>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && *id == handles.fishing.id()
+            && let Some(asset) = assets.get(&handles.fishing)
+        {
+            *table = asset.clone();
+            info!("Hot-reloaded FishingTable ({} catches)", table.loot.len());
+        }
+    }
+}
+
+pub(crate) fn hot_reload_reactions(
+    mut events: MessageReader<AssetEvent<ReactionRules>>,
+    handles: Res<RegistryHandles>,
+    assets: Res<Assets<ReactionRules>>,
+    mut rules: ResMut<ReactionRules>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && *id == handles.reactions.id()
+            && let Some(asset) = assets.get(&handles.reactions)
+        {
+            *rules = asset.clone();
+            info!("Hot-reloaded ReactionRules ({} rules)", rules.rules.len());
+        }
+    }
+}
+
+pub(crate) fn hot_reload_tutorial(
+    mut events: MessageReader<AssetEvent<Tutorial>>,
+    handles: Res<RegistryHandles>,
+    assets: Res<Assets<Tutorial>>,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && *id == handles.tutorial.id()
+            && let Some(asset) = assets.get(&handles.tutorial)
+        {
+            *tutorial = asset.clone();
+            info!("Hot-reloaded Tutorial ({} objectives)", tutorial.objectives.len());
+        }
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashSet;
 use bevy::asset::RenderAssetUsages;
 use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::renderer::RenderDevice;
 
 use super::assets::{
     AnimationDef, AutotileAsset, BiomeAsset, CharacterDefAsset, CharacterPartsDef, ItemDefAsset,
@@ -13,9 +14,14 @@ use super::assets::{
 };
 use super::biome::{
     BiomeDef, BiomeId, BiomeRegistry, LayerBoundaries, LayerConfig, LayerConfigs, PlanetConfig,
+    SurfaceDecoration, ThinAir, DEFAULT_BEDROCK_DEPTH, DEFAULT_LAYER_BLEND,
 };
 use super::hot_reload::BiomeHandles;
 use super::player::PlayerConfig;
+use super::texture_check::{
+    animation_sheet_problem, autotile_sheet_problem, enforce_texture_limit, max_texture_side,
+    repeat_width_problem, z_order_collisions, LoadingFailure, TextureValidation,
+};
 use super::tile::TileRegistry;
 use super::world::ActiveWorld;
 use super::{AppState, BiomeParallaxConfigs, RegistryHandles};
@@ -27,16 +33,19 @@ use crate::cosmos::pressurization::PressureMap;
 use crate::cosmos::ship_location::{GlobalBiome, ShipManifest};
 use crate::item::definition::ItemDef;
 use crate::item::registry::ItemRegistry;
+use crate::fishing::FishingTable;
 use crate::object::definition::ObjectDef;
 use crate::object::registry::ObjectRegistry;
+use crate::tutorial::Tutorial;
 use crate::world::day_night::WorldTime;
+use crate::world::reactions::ReactionRules;
 
 use crate::parallax::config::ParallaxConfig;
 use crate::world::atlas::{build_combined_atlas, AtlasParams, TileAtlas};
 use crate::world::autotile::{AutotileEntry, AutotileRegistry};
-use crate::world::biome_map::BiomeMap;
+use crate::world::biome_map::{BiomeMap, BorderCliffs};
 use crate::world::terrain_gen::TerrainNoiseCache;
-use crate::world::tile_renderer::{SharedTileMaterial, TileMaterial};
+use crate::world::tile_renderer::{SharedTileMaterial, TileMaterial, DEFAULT_TILE_SWAY};
 
 /// Handles for assets being loaded.
 #[derive(Resource)]
@@ -51,6 +60,9 @@ pub(crate) struct LoadingAssets {
     recipes: Vec<(String, Handle<RecipeListAsset>)>,
     liquids: Handle<LiquidRegistryAsset>,
     ui_theme: Handle<crate::ui::game_ui::theme::UiTheme>,
+    fishing: Handle<FishingTable>,
+    reactions: Handle<ReactionRules>,
+    tutorial: Handle<Tutorial>,
 }
 
 /// Intermediate resource holding autotile asset handles during loading.
@@ -66,6 +78,8 @@ pub struct LoadingBiomeAssets {
     pub(crate) planet_type: Handle<PlanetTypeAsset>,
     pub(crate) biomes: Vec<(String, Handle<BiomeAsset>)>,
     pub(crate) parallax_configs: Vec<(String, Handle<ParallaxConfigAsset>)>,
+    /// Parallax layer images, loaded up front so they can be validated.
+    pub(crate) parallax_images: Vec<(String, Handle<Image>)>,
 }
 
 /// Character animation configuration built from CharacterDefAsset.
@@ -102,6 +116,11 @@ pub(crate) fn start_loading(mut commands: Commands, asset_server: Res<AssetServe
             asset_server
                 .load::<PlanetTypeAsset>("worlds/planet_types/barren/barren.planet.ron"),
         ),
+        (
+            "moon".to_string(),
+            asset_server
+                .load::<PlanetTypeAsset>("worlds/planet_types/moon/moon.planet.ron"),
+        ),
         (
             "ship".to_string(),
             asset_server
@@ -223,6 +242,65 @@ pub(crate) fn start_loading(mut commands: Commands, asset_server: Res<AssetServe
             "content/items/rare_ore/".to_string(),
             asset_server.load::<ItemDefAsset>("content/items/rare_ore/rare_ore.item.ron"),
         ),
+        (
+            "content/items/sign/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/sign/sign.item.ron"),
+        ),
+        (
+            "content/items/rope/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/rope/rope.item.ron"),
+        ),
+        (
+            "content/items/ladder/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/ladder/ladder.item.ron"),
+        ),
+        (
+            "content/items/fishing_rod/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/fishing_rod/fishing_rod.item.ron"),
+        ),
+        (
+            "content/items/hoe/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/hoe/hoe.item.ron"),
+        ),
+        (
+            "content/items/raw_fish/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/raw_fish/raw_fish.item.ron"),
+        ),
+        (
+            "content/items/healing_potion/".to_string(),
+            asset_server
+                .load::<ItemDefAsset>("content/items/healing_potion/healing_potion.item.ron"),
+        ),
+        (
+            "content/items/swiftness_tonic/".to_string(),
+            asset_server
+                .load::<ItemDefAsset>("content/items/swiftness_tonic/swiftness_tonic.item.ron"),
+        ),
+        (
+            "content/items/sapling/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/sapling/sapling.item.ron"),
+        ),
+        (
+            "content/items/wheat_seeds/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/wheat_seeds/wheat_seeds.item.ron"),
+        ),
+        (
+            "content/items/wheat/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/wheat/wheat.item.ron"),
+        ),
+        (
+            "content/items/headlamp/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/headlamp/headlamp.item.ron"),
+        ),
+        (
+            "content/items/backpack/".to_string(),
+            asset_server.load::<ItemDefAsset>("content/items/backpack/backpack.item.ron"),
+        ),
+        (
+            "content/items/bag_expansion/".to_string(),
+            asset_server
+                .load::<ItemDefAsset>("content/items/bag_expansion/bag_expansion.item.ron"),
+        ),
     ];
 
     let recipes = vec![
@@ -240,6 +318,9 @@ pub(crate) fn start_loading(mut commands: Commands, asset_server: Res<AssetServe
         asset_server.load::<LiquidRegistryAsset>("worlds/liquids.liquid.ron");
     let ui_theme =
         asset_server.load::<crate::ui::game_ui::theme::UiTheme>("ui.theme.ron");
+    let fishing = asset_server.load::<FishingTable>("content/fishing.ron");
+    let reactions = asset_server.load::<ReactionRules>("content/reactions.ron");
+    let tutorial = asset_server.load::<Tutorial>("content/tutorial.ron");
 
     commands.insert_resource(LoadingAssets {
         tiles,
@@ -252,9 +333,13 @@ pub(crate) fn start_loading(mut commands: Commands, asset_server: Res<AssetServe
         recipes,
         liquids,
         ui_theme,
+        fishing,
+        reactions,
+        tutorial,
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn check_loading(
     mut commands: Commands,
     loading: Res<LoadingAssets>,
@@ -268,6 +353,9 @@ pub(crate) fn check_loading(
     recipe_assets: Res<Assets<RecipeListAsset>>,
     liquid_assets: Res<Assets<LiquidRegistryAsset>>,
     ui_theme_assets: Res<Assets<crate::ui::game_ui::theme::UiTheme>>,
+    fishing_assets: Res<Assets<FishingTable>>,
+    reaction_assets: Res<Assets<ReactionRules>>,
+    tutorial_assets: Res<Assets<Tutorial>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
     let (Some(tiles), Some(character)) = (
@@ -332,6 +420,21 @@ pub(crate) fn check_loading(
         return;
     }
 
+    // Wait for the fishing table
+    if !fishing_assets.contains(&loading.fishing) {
+        return;
+    }
+
+    // Wait for the tile reaction rules
+    if !reaction_assets.contains(&loading.reactions) {
+        return;
+    }
+
+    // Wait for the tutorial objectives
+    if !tutorial_assets.contains(&loading.tutorial) {
+        return;
+    }
+
     // Build ObjectRegistry from loaded object.ron files (order preserved from start_loading)
     let object_defs: Vec<ObjectDef> = loading
         .objects
@@ -381,6 +484,9 @@ pub(crate) fn check_loading(
 
     // Build resources from loaded assets
     let registry_ref = TileRegistry::from_defs(tiles.tiles.clone());
+    for problem in registry_ref.validate() {
+        warn!("Tile data: {problem}");
+    }
 
     // Build liquid registry from loaded asset
     let liquid_asset = liquid_assets.get(&loading.liquids).unwrap();
@@ -393,6 +499,18 @@ pub(crate) fn check_loading(
     let ui_theme = ui_theme_assets.get(&loading.ui_theme).unwrap().clone();
     commands.insert_resource(ui_theme);
 
+    let fishing = fishing_assets.get(&loading.fishing).unwrap().clone();
+    bevy::log::info!("Fishing table loaded: {} catches", fishing.loot.len());
+    commands.insert_resource(fishing);
+
+    let reactions = reaction_assets.get(&loading.reactions).unwrap().clone();
+    bevy::log::info!("Tile reactions loaded: {} rules", reactions.rules.len());
+    commands.insert_resource(reactions);
+
+    let tutorial = tutorial_assets.get(&loading.tutorial).unwrap().clone();
+    bevy::log::info!("Tutorial loaded: {} objectives", tutorial.objectives.len());
+    commands.insert_resource(tutorial);
+
     commands.insert_resource(registry_ref);
     commands.insert_resource(ObjectRegistry::from_defs(object_defs));
     commands.insert_resource(PlayerConfig {
@@ -401,12 +519,18 @@ pub(crate) fn check_loading(
         gravity: character.gravity,
         width: character.width,
         height: character.height,
+        collision_width: character.collision_width,
+        collision_height: character.collision_height,
         magnet_radius: character.magnet_radius,
         magnet_strength: character.magnet_strength,
         pickup_radius: character.pickup_radius,
         swim_impulse: character.swim_impulse,
         swim_gravity_factor: character.swim_gravity_factor,
         swim_drag: character.swim_drag,
+        break_reach: character.break_reach,
+        place_reach: character.place_reach,
+        max_fall_speed: character.max_fall_speed,
+        spawn_search_radius: character.spawn_search_radius,
     });
 
     // Store character animation data for the animation system
@@ -509,6 +633,10 @@ pub(crate) fn check_loading(
         recipes: loading.recipes.clone(),
         liquids: loading.liquids.clone(),
         ui_theme: loading.ui_theme.clone(),
+        fishing: loading.fishing.clone(),
+        reactions: loading.reactions.clone(),
+        tutorial: loading.tutorial.clone(),
+        generation_config: loading.generation_config.clone(),
     });
 
     // Load the "ship" planet type for the biome pipeline
@@ -522,6 +650,7 @@ pub(crate) fn check_loading(
         planet_type: planet_handle,
         biomes: Vec::new(),
         parallax_configs: Vec::new(),
+        parallax_images: Vec::new(),
     });
 
     // Store system for star-map UI and planet warping
@@ -545,8 +674,148 @@ pub(crate) fn check_loading(
     info!("Base registry assets loaded, loading biome assets...");
 }
 
-/// Multi-phase system that loads planet type → biome assets → parallax configs,
+/// Stop loading and show `problems` on the failure screen.
+fn fail_loading(
+    commands: &mut Commands,
+    next_state: &mut NextState<AppState>,
+    problems: Vec<String>,
+) {
+    for problem in &problems {
+        error!("Texture check: {problem}");
+    }
+    commands.insert_resource(LoadingFailure(problems));
+    next_state.set(AppState::LoadingFailed);
+}
+
+/// Multi-phase system that loads planet type → biome assets → parallax configs →
+/// parallax images,
 /// then builds BiomeRegistry, BiomeMap, PlanetConfig, and BiomeParallaxConfigs.
+/// Every biome a planet type references: primary, secondaries and the
+/// per-layer overrides.
+pub(crate) fn planet_biome_ids(planet_asset: &PlanetTypeAsset) -> HashSet<String> {
+    let mut biome_ids = HashSet::new();
+    biome_ids.insert(planet_asset.primary_biome.clone());
+    for id in &planet_asset.secondary_biomes {
+        biome_ids.insert(id.clone());
+    }
+    let layers = &planet_asset.layers;
+    for layer in [
+        &layers.surface,
+        &layers.underground,
+        &layers.deep_underground,
+        &layers.core,
+    ] {
+        if let Some(ref b) = layer.primary_biome {
+            biome_ids.insert(b.clone());
+        }
+    }
+    biome_ids
+}
+
+/// Resolve a planet type asset into the runtime [`PlanetConfig`] for a world
+/// `height_tiles` tall.
+pub(crate) fn planet_config_from_asset(
+    planet_asset: &PlanetTypeAsset,
+    height_tiles: i32,
+) -> PlanetConfig {
+    let layers = LayerConfigs {
+        surface: LayerConfig {
+            primary_biome: planet_asset.layers.surface.primary_biome.clone(),
+            terrain_frequency: planet_asset.layers.surface.terrain_frequency,
+            terrain_amplitude: planet_asset.layers.surface.terrain_amplitude,
+            depth_ratio: planet_asset.layers.surface.depth_ratio,
+            cave_threshold_scale: planet_asset.layers.surface.cave_threshold_scale,
+        },
+        underground: LayerConfig {
+            primary_biome: planet_asset.layers.underground.primary_biome.clone(),
+            terrain_frequency: planet_asset.layers.underground.terrain_frequency,
+            terrain_amplitude: planet_asset.layers.underground.terrain_amplitude,
+            depth_ratio: planet_asset.layers.underground.depth_ratio,
+            cave_threshold_scale: planet_asset.layers.underground.cave_threshold_scale,
+        },
+        deep_underground: LayerConfig {
+            primary_biome: planet_asset.layers.deep_underground.primary_biome.clone(),
+            terrain_frequency: planet_asset.layers.deep_underground.terrain_frequency,
+            terrain_amplitude: planet_asset.layers.deep_underground.terrain_amplitude,
+            depth_ratio: planet_asset.layers.deep_underground.depth_ratio,
+            cave_threshold_scale: planet_asset.layers.deep_underground.cave_threshold_scale,
+        },
+        core: LayerConfig {
+            primary_biome: planet_asset.layers.core.primary_biome.clone(),
+            terrain_frequency: planet_asset.layers.core.terrain_frequency,
+            terrain_amplitude: planet_asset.layers.core.terrain_amplitude,
+            depth_ratio: planet_asset.layers.core.depth_ratio,
+            cave_threshold_scale: planet_asset.layers.core.cave_threshold_scale,
+        },
+    };
+    let layer_boundaries = LayerBoundaries::from_layers(&layers, height_tiles);
+    PlanetConfig {
+        id: planet_asset.id.clone(),
+        primary_biome: planet_asset.primary_biome.clone(),
+        secondary_biomes: planet_asset.secondary_biomes.clone(),
+        layers,
+        layer_boundaries,
+        layer_blend: planet_asset
+            .layer_blend
+            .unwrap_or(DEFAULT_LAYER_BLEND)
+            .max(0),
+        region_width_min: planet_asset.region_width_min,
+        region_width_max: planet_asset.region_width_max,
+        primary_region_ratio: planet_asset.primary_region_ratio,
+        gravity_scale: planet_asset
+            .gravity_scale
+            .filter(|&s| s > 0.0)
+            .unwrap_or(1.0),
+        atmosphere_density: planet_asset
+            .atmosphere_density
+            .filter(|&d| d >= 0.0)
+            .unwrap_or(1.0),
+        day_length: planet_asset.day_length.filter(|&l| l > 0.0),
+        starter_biome: planet_asset.starter_biome.clone(),
+        biome_separation: planet_asset.biome_separation.unwrap_or(1),
+        border_tile: planet_asset.border_tile.clone(),
+        bedrock_tile: planet_asset.bedrock_tile.clone(),
+        bedrock_depth: planet_asset.bedrock_depth.unwrap_or(DEFAULT_BEDROCK_DEPTH),
+        thin_air: planet_asset
+            .thin_air
+            .map(|(start, min_jump)| ThinAir { start, min_jump }),
+        difficulty: planet_asset.difficulty,
+        biome_borders: planet_asset.biome_borders.clone(),
+        // Resolved against the biome map once it is generated.
+        border_cliffs: BorderCliffs::default(),
+    }
+}
+
+/// Resolve a biome asset's block names against the tile registry.
+pub(crate) fn biome_def_from_asset(asset: &BiomeAsset, tile_registry: &TileRegistry) -> BiomeDef {
+    BiomeDef {
+        id: asset.id.clone(),
+        surface_block: tile_registry.by_name(&asset.surface_block),
+        subsurface_block: tile_registry.by_name(&asset.subsurface_block),
+        subsurface_depth: asset.subsurface_depth,
+        fill_block: tile_registry.by_name(&asset.fill_block),
+        cave_threshold: asset.cave_threshold,
+        parallax_path: asset.parallax.clone(),
+        temperature_offset: asset.temperature_offset,
+        decorations: asset
+            .decorations
+            .iter()
+            .filter_map(|deco| {
+                let Some(tile) = tile_registry.try_by_name(&deco.tile) else {
+                    warn!("Biome '{}': unknown decoration tile '{}'", asset.id, deco.tile);
+                    return None;
+                };
+                Some(SurfaceDecoration {
+                    tile,
+                    density: deco.density,
+                    cluster_scale: deco.cluster_scale,
+                    cluster_threshold: deco.cluster_threshold,
+                })
+            })
+            .collect(),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn check_biomes_loaded(
     mut commands: Commands,
@@ -557,6 +826,9 @@ pub(crate) fn check_biomes_loaded(
     parallax_assets: Res<Assets<ParallaxConfigAsset>>,
     tile_registry: Res<TileRegistry>,
     mut world_config: ResMut<ActiveWorld>,
+    mut image_assets: ResMut<Assets<Image>>,
+    validation: Res<TextureValidation>,
+    render_device: Option<Res<RenderDevice>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
     // Check for planet type load failure
@@ -573,28 +845,8 @@ pub(crate) fn check_biomes_loaded(
     };
 
     if loading.biomes.is_empty() {
-        // Collect all unique biome IDs from the planet type
-        let mut biome_ids = HashSet::new();
-        biome_ids.insert(planet_asset.primary_biome.clone());
-        for id in &planet_asset.secondary_biomes {
-            biome_ids.insert(id.clone());
-        }
-        // Also collect biomes referenced in layer configs
-        if let Some(ref b) = planet_asset.layers.surface.primary_biome {
-            biome_ids.insert(b.clone());
-        }
-        if let Some(ref b) = planet_asset.layers.underground.primary_biome {
-            biome_ids.insert(b.clone());
-        }
-        if let Some(ref b) = planet_asset.layers.deep_underground.primary_biome {
-            biome_ids.insert(b.clone());
-        }
-        if let Some(ref b) = planet_asset.layers.core.primary_biome {
-            biome_ids.insert(b.clone());
-        }
-
         // Load each biome asset
-        for id in &biome_ids {
+        for id in &planet_biome_ids(planet_asset) {
             let handle =
                 asset_server.load::<BiomeAsset>(format!("content/biomes/{id}/{id}.biome.ron"));
             loading.biomes.push((id.clone(), handle));
@@ -657,7 +909,7 @@ pub(crate) fn check_biomes_loaded(
         }
     }
 
-    // Phase 3: Wait for all parallax configs, then build resources
+    // Phase 3: Wait for all parallax configs, then load their layer images
     if !loading.parallax_configs.is_empty() {
         let all_parallax_loaded = loading
             .parallax_configs
@@ -668,44 +920,82 @@ pub(crate) fn check_biomes_loaded(
         }
     }
 
+    if loading.parallax_images.is_empty() {
+        let mut seen = HashSet::new();
+        let images: Vec<String> = loading
+            .parallax_configs
+            .iter()
+            .filter_map(|(_, h)| parallax_assets.get(h))
+            .flat_map(|asset| asset.layers.iter().map(|layer| layer.image.clone()))
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+        if !images.is_empty() {
+            for path in images {
+                let handle = asset_server.load::<Image>(path.clone());
+                loading.parallax_images.push((path, handle));
+            }
+            return; // wait for next frame
+        }
+    }
+
+    for (name, handle) in &loading.parallax_images {
+        if let bevy::asset::LoadState::Failed(_) = asset_server.load_state(handle) {
+            error!("Failed to load parallax image: {name} — check file exists");
+        }
+    }
+
+    // Phase 4: Wait for all parallax images, then check them against the GPU
+    let all_images_loaded = loading
+        .parallax_images
+        .iter()
+        .all(|(_, h)| image_assets.contains(h));
+    if !all_images_loaded {
+        return;
+    }
+
+    let max_side = max_texture_side(render_device.as_deref());
+    let mut failures = Vec::new();
+    for (biome_name, handle) in &loading.parallax_configs {
+        let asset = parallax_assets.get(handle).unwrap();
+        for problem in z_order_collisions(&asset.layers) {
+            let problem = format!("biome '{biome_name}': {problem}");
+            if validation.strict {
+                failures.push(problem);
+            } else {
+                warn!("{problem}");
+            }
+        }
+        for layer in &asset.layers {
+            let width = loading
+                .parallax_images
+                .iter()
+                .find(|(path, _)| *path == layer.image)
+                .and_then(|(_, h)| image_assets.get(h))
+                .map(|image| image.width());
+            if let Some(problem) = width.and_then(|w| repeat_width_problem(layer, w)) {
+                warn!("biome '{biome_name}': {problem}");
+            }
+        }
+    }
+    for (name, handle) in &loading.parallax_images {
+        failures.extend(enforce_texture_limit(
+            name,
+            handle.id(),
+            &mut image_assets,
+            max_side,
+            validation.strict,
+        ));
+    }
+    if !failures.is_empty() {
+        fail_loading(&mut commands, &mut next_state, failures);
+        return;
+    }
+
     // --- Build PlanetConfig ---
-    let layers = LayerConfigs {
-        surface: LayerConfig {
-            primary_biome: planet_asset.layers.surface.primary_biome.clone(),
-            terrain_frequency: planet_asset.layers.surface.terrain_frequency,
-            terrain_amplitude: planet_asset.layers.surface.terrain_amplitude,
-            depth_ratio: planet_asset.layers.surface.depth_ratio,
-        },
-        underground: LayerConfig {
-            primary_biome: planet_asset.layers.underground.primary_biome.clone(),
-            terrain_frequency: planet_asset.layers.underground.terrain_frequency,
-            terrain_amplitude: planet_asset.layers.underground.terrain_amplitude,
-            depth_ratio: planet_asset.layers.underground.depth_ratio,
-        },
-        deep_underground: LayerConfig {
-            primary_biome: planet_asset.layers.deep_underground.primary_biome.clone(),
-            terrain_frequency: planet_asset.layers.deep_underground.terrain_frequency,
-            terrain_amplitude: planet_asset.layers.deep_underground.terrain_amplitude,
-            depth_ratio: planet_asset.layers.deep_underground.depth_ratio,
-        },
-        core: LayerConfig {
-            primary_biome: planet_asset.layers.core.primary_biome.clone(),
-            terrain_frequency: planet_asset.layers.core.terrain_frequency,
-            terrain_amplitude: planet_asset.layers.core.terrain_amplitude,
-            depth_ratio: planet_asset.layers.core.depth_ratio,
-        },
-    };
-    let layer_boundaries = LayerBoundaries::from_layers(&layers, world_config.height_tiles);
-    let planet_config = PlanetConfig {
-        id: planet_asset.id.clone(),
-        primary_biome: planet_asset.primary_biome.clone(),
-        secondary_biomes: planet_asset.secondary_biomes.clone(),
-        layers,
-        layer_boundaries,
-        region_width_min: planet_asset.region_width_min,
-        region_width_max: planet_asset.region_width_max,
-        primary_region_ratio: planet_asset.primary_region_ratio,
-    };
+    for problem in planet_asset.validate() {
+        warn!("Planet type '{}': {problem}", planet_asset.id);
+    }
+    let mut planet_config = planet_config_from_asset(planet_asset, world_config.height_tiles);
 
     // --- Update ActiveWorld with planet type weather data ---
     world_config.base_temperature = planet_asset.base_temperature.unwrap_or(15.0);
@@ -715,19 +1005,7 @@ pub(crate) fn check_biomes_loaded(
     let mut biome_registry = BiomeRegistry::default();
     for (name, handle) in &loading.biomes {
         let asset = biome_assets.get(handle).unwrap();
-        biome_registry.insert(
-            name,
-            BiomeDef {
-                id: asset.id.clone(),
-                surface_block: tile_registry.by_name(&asset.surface_block),
-                subsurface_block: tile_registry.by_name(&asset.subsurface_block),
-                subsurface_depth: asset.subsurface_depth,
-                fill_block: tile_registry.by_name(&asset.fill_block),
-                cave_threshold: asset.cave_threshold,
-                parallax_path: asset.parallax.clone(),
-                temperature_offset: asset.temperature_offset,
-            },
-        );
+        biome_registry.insert(name, biome_def_from_asset(asset, &tile_registry));
     }
 
     // --- Build BiomeMap ---
@@ -744,8 +1022,17 @@ pub(crate) fn check_biomes_loaded(
         planet_config.region_width_min,
         planet_config.region_width_max,
         planet_config.primary_region_ratio,
+        planet_config.biome_separation,
+        planet_config.starter_biome.as_deref(),
         &biome_registry,
     );
+    planet_config.border_cliffs = BorderCliffs::new(
+        &biome_map,
+        &planet_config.biome_borders,
+        &biome_registry,
+        world_config.seed,
+        world_config.wrap_x,
+    );
     let region_count = biome_map.regions.len();
 
     // --- Build BiomeParallaxConfigs ---
@@ -795,6 +1082,11 @@ pub(crate) fn check_biomes_loaded(
         planet_type: loading.planet_type.clone(),
         biomes: biome_handles,
         parallax_configs: parallax_handles,
+        _parallax_images: loading
+            .parallax_images
+            .iter()
+            .map(|(_, h)| h.clone())
+            .collect(),
     });
 
     commands.remove_resource::<LoadingBiomeAssets>();
@@ -831,6 +1123,7 @@ pub(crate) fn start_autotile_loading(
     commands.insert_resource(LoadingAutotileAssets { rons, images: imgs });
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn check_autotile_loading(
     mut commands: Commands,
     loading: Res<LoadingAutotileAssets>,
@@ -838,7 +1131,9 @@ pub(crate) fn check_autotile_loading(
     mut image_assets: ResMut<Assets<Image>>,
     mut tile_materials: ResMut<Assets<TileMaterial>>,
     asset_server: Res<AssetServer>,
+    validation: Res<TextureValidation>,
     mut next_state: ResMut<NextState<AppState>>,
+    tile_registry: Option<Res<TileRegistry>>,
 ) {
     // Check for load failures before waiting
     for (name, handle) in &loading.rons {
@@ -866,15 +1161,54 @@ pub(crate) fn check_autotile_loading(
     let tile_size = first_ron.tile_size;
     let rows = first_ron.atlas_rows;
 
+    // Atlas columns per autotile: one per animation frame.
+    let frames: std::collections::HashMap<&str, u32> = loading
+        .rons
+        .iter()
+        .filter_map(|(name, handle)| {
+            let animation = autotile_assets.get(handle)?.animation?;
+            animation
+                .is_animated()
+                .then_some((name.as_str(), animation.frames))
+        })
+        .collect();
+    let frames_of = |name: &str| frames.get(name).copied().unwrap_or(1);
+
+    let mut failures = Vec::new();
+    for (name, handle) in &loading.images {
+        let Some(image) = image_assets.get(handle) else {
+            continue;
+        };
+        let problems = [
+            autotile_sheet_problem(name, image.size(), tile_size),
+            animation_sheet_problem(name, image.width(), tile_size, frames_of(name)),
+        ];
+        for problem in problems.into_iter().flatten() {
+            if validation.strict {
+                failures.push(problem);
+            } else {
+                warn!("{problem}");
+            }
+        }
+    }
+    if !failures.is_empty() {
+        fail_loading(&mut commands, &mut next_state, failures);
+        return;
+    }
+
     // Build combined atlas from per-type spritesheet images
-    let sources: Vec<(&str, &Image)> = loading
+    let sources: Vec<(&str, &Image, u32)> = loading
         .images
         .iter()
         .filter_map(|(name, handle)| {
-            image_assets.get(handle).map(|img| (name.as_str(), img)).or_else(|| {
-                error!("Failed to load autotile image: {name}");
-                None
-            })
+            let name = name.as_str();
+            image_assets
+                .get(handle)
+                .map(|img| (name, img, frames_of(name)))
+                .or_else(|| {
+                    error!("Failed to load autotile image: {name}");
+                    None
+                })
         })
         .collect();
 
@@ -884,12 +1218,13 @@ pub(crate) fn check_autotile_loading(
     }
 
     let (atlas_image, column_map) = build_combined_atlas(&sources, tile_size, rows);
-    let num_types = sources.len() as u32;
+    let num_types = sources.len();
     let params = AtlasParams {
         tile_size,
         rows,
-        atlas_width: num_types * tile_size,
+        atlas_width: atlas_image.width(),
         atlas_height: rows * tile_size,
+        uv_inset: first_ron.uv_inset,
     };
     let atlas_handle = image_assets.add(atlas_image);
 
@@ -903,6 +1238,11 @@ pub(crate) fn check_autotile_loading(
         let col_idx = column_map[name.as_str()];
         autotile_reg.insert(name.clone(), AutotileEntry::from_asset(asset, col_idx));
     }
+    if let Some(tile_registry) = tile_registry {
+        for problem in autotile_reg.validate(&tile_registry) {
+            warn!("Autotile data: {problem}; affected tiles draw a placeholder sprite");
+        }
+    }
 
     // Create 1×1 white fallback lightmap (replaced by RC pipeline each frame).
     // Uses Rgba16Float to match the RC pipeline's lightmap format exactly.
@@ -925,18 +1265,21 @@ pub(crate) fn check_autotile_loading(
         dim: 1.0,
         lightmap: white_lightmap.clone(),
         lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0), // No scaling/offset
+        sway: DEFAULT_TILE_SWAY,
     });
     let bg_material = tile_materials.add(TileMaterial {
         atlas: atlas_handle.clone(),
         dim: 0.6,
         lightmap: white_lightmap,
         lightmap_uv_rect: Vec4::new(1.0, 1.0, 0.0, 0.0), // No scaling/offset
+        sway: DEFAULT_TILE_SWAY,
     });
 
     // Insert all autotile resources
     commands.insert_resource(TileAtlas {
         image: atlas_handle,
         params,
+        column_map,
     });
     commands.insert_resource(autotile_reg);
     commands.insert_resource(SharedTileMaterial {
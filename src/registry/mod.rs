@@ -3,7 +3,9 @@ pub mod biome;
 pub mod hot_reload;
 pub mod loader;
 pub mod loading;
+pub mod planet_physics;
 pub mod player;
+pub mod texture_check;
 pub mod tile;
 pub mod world;
 
@@ -16,20 +18,26 @@ use assets::{
     ObjectDefAsset, ParallaxConfigAsset, PlanetTypeAsset, RecipeListAsset, TileRegistryAsset,
 };
 use crate::cosmos::assets::{GenerationConfigAsset, StarTypeAsset};
+use crate::fishing::FishingTable;
+use crate::tutorial::Tutorial;
+use crate::world::reactions::ReactionRules;
 use crate::ui::game_ui::theme::UiTheme;
 use biome::BiomeId;
 use hot_reload::{
-    hot_reload_biome_parallax, hot_reload_biomes, hot_reload_character, hot_reload_items,
-    hot_reload_liquids, hot_reload_objects, hot_reload_planet_type, hot_reload_recipes,
-    hot_reload_tiles, hot_reload_ui_theme,
+    hot_reload_biome_parallax, hot_reload_biomes, hot_reload_fishing, hot_reload_character, hot_reload_items,
+    hot_reload_generation_config, hot_reload_liquids, hot_reload_objects, hot_reload_planet_type,
+    hot_reload_reactions, hot_reload_recipes, hot_reload_tiles, hot_reload_tutorial,
+    hot_reload_ui_theme,
 };
 use loader::RonLoader;
 use loading::{
     check_autotile_loading, check_biomes_loaded, check_loading, start_autotile_loading,
     start_loading,
 };
+use planet_physics::{sync_planet_physics, PlanetPhysics};
 
 use crate::parallax::config::ParallaxConfig;
+use crate::sets::GameSet;
 
 /// Keeps asset handles alive for hot-reload detection.
 #[derive(Resource)]
@@ -42,6 +50,10 @@ pub struct RegistryHandles {
     pub recipes: Vec<(String, Handle<RecipeListAsset>)>,
     pub liquids: Handle<LiquidRegistryAsset>,
     pub ui_theme: Handle<UiTheme>,
+    pub fishing: Handle<FishingTable>,
+    pub reactions: Handle<ReactionRules>,
+    pub tutorial: Handle<Tutorial>,
+    pub generation_config: Handle<GenerationConfigAsset>,
 }
 
 /// Application state: MainMenu shows title screen, Loading waits for assets, InGame runs gameplay.
@@ -52,6 +64,8 @@ pub enum AppState {
     Loading,
     LoadingBiomes,
     LoadingAutotile,
+    /// Loading stopped on a problem; the failure screen lists what went wrong.
+    LoadingFailed,
     InGame,
 }
 
@@ -66,6 +80,8 @@ pub struct RegistryPlugin;
 impl Plugin for RegistryPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<AppState>()
+            .init_resource::<texture_check::TextureValidation>()
+            .init_resource::<PlanetPhysics>()
             .init_asset::<TileRegistryAsset>()
             .init_asset::<ObjectDefAsset>()
             .init_asset::<CharacterDefAsset>()
@@ -82,6 +98,12 @@ impl Plugin for RegistryPlugin {
             .register_asset_loader(RonLoader::<AutotileAsset>::new(&["autotile.ron"]))
             .register_asset_loader(RonLoader::<LiquidRegistryAsset>::new(&["liquid.ron"]))
             .register_asset_loader(RonLoader::<UiTheme>::new(&["theme.ron"]))
+            .init_asset::<FishingTable>()
+            .register_asset_loader(RonLoader::<FishingTable>::new(&["fishing.ron"]))
+            .init_asset::<ReactionRules>()
+            .register_asset_loader(RonLoader::<ReactionRules>::new(&["reactions.ron"]))
+            .init_asset::<Tutorial>()
+            .register_asset_loader(RonLoader::<Tutorial>::new(&["tutorial.ron"]))
             .init_asset::<RecipeListAsset>()
             .register_asset_loader(RonLoader::<RecipeListAsset>::new(&["recipes.ron"]))
             .init_asset::<PlanetTypeAsset>()
@@ -116,8 +138,19 @@ impl Plugin for RegistryPlugin {
                     hot_reload_recipes,
                     hot_reload_liquids,
                     hot_reload_ui_theme,
+                    hot_reload_fishing,
+                    hot_reload_reactions,
+                    hot_reload_tutorial,
+                    hot_reload_generation_config,
                 )
                     .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                sync_planet_physics
+                    .after(hot_reload_planet_type)
+                    .before(GameSet::Input)
+                    .run_if(in_state(AppState::InGame)),
             );
     }
 }
@@ -0,0 +1,49 @@
+//! Planet-wide physics and ambience: gravity, atmosphere and day length.
+//!
+//! These come from the planet type and live in [`PlanetConfig`], but gravity,
+//! lighting and the day/night cycle read them from [`PlanetPhysics`], which
+//! [`sync_planet_physics`] refreshes whenever the config is replaced by a warp
+//! or changed by a hot-reload.
+
+use bevy::prelude::*;
+
+use super::biome::PlanetConfig;
+
+/// Physics and ambience knobs of the current planet.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct PlanetPhysics {
+    /// Multiplier on the gravity and terminal velocity of everything that
+    /// falls.
+    pub gravity_scale: f32,
+    /// Air thickness relative to an Earth-like planet (1.0).
+    pub atmosphere_density: f32,
+    /// Seconds per day/night cycle, replacing the generated one.
+    pub day_length: Option<f32>,
+}
+
+impl Default for PlanetPhysics {
+    fn default() -> Self {
+        Self {
+            gravity_scale: 1.0,
+            atmosphere_density: 1.0,
+            day_length: None,
+        }
+    }
+}
+
+impl PlanetPhysics {
+    pub fn from_config(planet: &PlanetConfig) -> Self {
+        Self {
+            gravity_scale: planet.gravity_scale,
+            atmosphere_density: planet.atmosphere_density,
+            day_length: planet.day_length,
+        }
+    }
+}
+
+/// Copy the planet's physics out of [`PlanetConfig`] when it changes.
+pub fn sync_planet_physics(planet: Option<Res<PlanetConfig>>, mut physics: ResMut<PlanetPhysics>) {
+    if let Some(planet) = planet.filter(|p| p.is_changed()) {
+        physics.set_if_neq(PlanetPhysics::from_config(&planet));
+    }
+}
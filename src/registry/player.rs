@@ -7,8 +7,15 @@ pub struct PlayerConfig {
     pub speed: f32,
     pub jump_velocity: f32,
     pub gravity: f32,
+    /// Sprite size (px); also the collision box unless overridden below.
     pub width: f32,
     pub height: f32,
+    /// Collision box width (px); `None` uses `width`.
+    #[serde(default)]
+    pub collision_width: Option<f32>,
+    /// Collision box height (px); `None` uses `height`.
+    #[serde(default)]
+    pub collision_height: Option<f32>,
     /// Radius (px) within which dropped items are pulled toward the player.
     #[serde(default = "default_magnet_radius")]
     pub magnet_radius: f32,
@@ -27,6 +34,28 @@ pub struct PlayerConfig {
     /// Per-second velocity retention in liquid (0.0 = instant stop, 1.0 = no drag).
     #[serde(default = "default_swim_drag")]
     pub swim_drag: f32,
+    /// Maximum per-axis distance (tiles) at which blocks can be broken.
+    #[serde(default = "default_reach")]
+    pub break_reach: f32,
+    /// Maximum per-axis distance (tiles) at which blocks can be placed.
+    #[serde(default = "default_reach")]
+    pub place_reach: f32,
+    /// Terminal fall speed (px/s) at 1.0 planet gravity.
+    #[serde(default = "default_max_fall_speed")]
+    pub max_fall_speed: f32,
+    /// Columns (tiles) searched on each side of x = 0 for a safe spawn.
+    #[serde(default = "default_spawn_search_radius")]
+    pub spawn_search_radius: i32,
+}
+
+impl PlayerConfig {
+    /// Size of the player's [`TileCollider`](crate::physics::TileCollider).
+    pub fn collision_size(&self) -> Vec2 {
+        Vec2::new(
+            self.collision_width.unwrap_or(self.width),
+            self.collision_height.unwrap_or(self.height),
+        )
+    }
 }
 
 fn default_magnet_radius() -> f32 {
@@ -47,3 +76,12 @@ fn default_swim_gravity_factor() -> f32 {
 fn default_swim_drag() -> f32 {
     0.15
 }
+fn default_reach() -> f32 {
+    5.0
+}
+fn default_max_fall_speed() -> f32 {
+    900.0
+}
+fn default_spawn_search_radius() -> i32 {
+    64
+}
@@ -0,0 +1,292 @@
+//! Sanity checks for textures that go straight to the GPU: parallax layers
+//! and autotile sheets.
+//!
+//! An image larger than the device's texture limit fails to upload (silently
+//! or with a crash, depending on the backend). In strict mode oversized
+//! images, colliding parallax `z_order`s and autotile sheets that aren't a
+//! whole number of cells stop loading on the
+//! [`AppState::LoadingFailed`](super::AppState) screen; in lenient mode
+//! oversized images are downscaled on the CPU and the rest is logged.
+//! Repeating layers with awkward widths are only ever a warning.
+
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension};
+use bevy::render::renderer::RenderDevice;
+
+use crate::parallax::config::ParallaxLayerDef;
+
+/// Texture side limit (px) assumed when no render device is available.
+pub const DEFAULT_MAX_TEXTURE_SIDE: u32 = 8192;
+/// Width (px) a repeating parallax layer should be a multiple of so its
+/// copies line up on whole texels at any zoom.
+pub const REPEAT_WIDTH_STEP: u32 = 64;
+
+/// How texture problems found while loading are handled.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct TextureValidation {
+    /// Stop loading on the failure screen instead of working around problems.
+    pub strict: bool,
+}
+
+/// Problems that stopped loading, shown on the failure screen.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LoadingFailure(pub Vec<String>);
+
+/// Largest texture side the GPU accepts.
+pub fn max_texture_side(device: Option<&RenderDevice>) -> u32 {
+    device
+        .map(|d| d.limits().max_texture_dimension_2d)
+        .unwrap_or(DEFAULT_MAX_TEXTURE_SIDE)
+}
+
+/// Problem when an image is larger than `max_side` on either axis.
+pub fn oversized(name: &str, size: UVec2, max_side: u32) -> Option<String> {
+    (size.x > max_side || size.y > max_side).then(|| {
+        format!(
+            "{name}: {}×{} exceeds the {max_side} px texture limit",
+            size.x, size.y
+        )
+    })
+}
+
+/// Warning when a horizontally repeating layer's width doesn't tile cleanly.
+pub fn repeat_width_problem(layer: &ParallaxLayerDef, width: u32) -> Option<String> {
+    (layer.repeat_x && !width.is_multiple_of(REPEAT_WIDTH_STEP)).then(|| {
+        format!(
+            "parallax layer '{}': repeating width {width} px is not a multiple of {REPEAT_WIDTH_STEP}",
+            layer.name
+        )
+    })
+}
+
+/// Layers that share a `z_order` and so draw in arbitrary order.
+pub fn z_order_collisions(layers: &[ParallaxLayerDef]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (i, layer) in layers.iter().enumerate() {
+        if let Some(other) = layers[..i]
+            .iter()
+            .find(|other| other.z_order == layer.z_order)
+        {
+            problems.push(format!(
+                "parallax layers '{}' and '{}' share z_order {}",
+                other.name, layer.name, layer.z_order
+            ));
+        }
+    }
+    problems
+}
+
+/// Problem when an autotile sheet isn't a whole number of `tile_size` cells.
+pub fn autotile_sheet_problem(name: &str, size: UVec2, tile_size: u32) -> Option<String> {
+    let fits =
+        tile_size > 0 && size.x.is_multiple_of(tile_size) && size.y.is_multiple_of(tile_size);
+    (!fits).then(|| {
+        format!(
+            "autotile sheet '{name}': {}×{} is not divisible by tile_size {tile_size}",
+            size.x, size.y
+        )
+    })
+}
+
+/// Problem when an animated autotile sheet is too narrow for its frames,
+/// one `tile_size` column each. Missing frames draw transparent.
+pub fn animation_sheet_problem(
+    name: &str,
+    width: u32,
+    tile_size: u32,
+    frames: u32,
+) -> Option<String> {
+    (width < frames.saturating_mul(tile_size)).then(|| {
+        format!(
+            "autotile sheet '{name}': {width}px is too narrow for {frames} animation frames \
+             of {tile_size}px"
+        )
+    })
+}
+
+/// Size of an image scaled down so neither side exceeds `max_side`, keeping
+/// its aspect ratio.
+pub fn downscaled_size(size: UVec2, max_side: u32) -> UVec2 {
+    let longest = size.x.max(size.y);
+    if longest <= max_side {
+        return size;
+    }
+    let scale = max_side as f64 / longest as f64;
+    UVec2::new(
+        ((size.x as f64 * scale).round() as u32).clamp(1, max_side),
+        ((size.y as f64 * scale).round() as u32).clamp(1, max_side),
+    )
+}
+
+/// Nearest-neighbour copy of `image` scaled down to fit `max_side`, or `None`
+/// if it already fits or its format can't be resampled (compressed, no data).
+pub fn downscale_nearest(image: &Image, max_side: u32) -> Option<Image> {
+    let src = image.size();
+    let dst = downscaled_size(src, max_side);
+    if dst == src {
+        return None;
+    }
+    let format = image.texture_descriptor.format;
+    if format.block_dimensions() != (1, 1) {
+        return None;
+    }
+    let bpp = format.block_copy_size(None)? as usize;
+    let data = image.data.as_ref()?;
+
+    let mut out = vec![0u8; dst.x as usize * dst.y as usize * bpp];
+    for y in 0..dst.y {
+        let sy = (y as u64 * src.y as u64 / dst.y as u64) as usize;
+        for x in 0..dst.x {
+            let sx = (x as u64 * src.x as u64 / dst.x as u64) as usize;
+            let from = (sy * src.x as usize + sx) * bpp;
+            let to = (y as usize * dst.x as usize + x as usize) * bpp;
+            out[to..to + bpp].copy_from_slice(data.get(from..from + bpp)?);
+        }
+    }
+
+    let mut scaled = Image::new(
+        Extent3d {
+            width: dst.x,
+            height: dst.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        out,
+        format,
+        image.asset_usage,
+    );
+    scaled.sampler = image.sampler.clone();
+    Some(scaled)
+}
+
+/// Hold a loaded image to the texture limit: report it in strict mode,
+/// otherwise swap in a downscaled copy.
+pub fn enforce_texture_limit(
+    name: &str,
+    id: AssetId<Image>,
+    images: &mut Assets<Image>,
+    max_side: u32,
+    strict: bool,
+) -> Option<String> {
+    let image = images.get_mut(id)?;
+    let problem = oversized(name, image.size(), max_side)?;
+    if strict {
+        return Some(problem);
+    }
+    match downscale_nearest(image, max_side) {
+        Some(scaled) => {
+            warn!(
+                "{problem}; downscaled to {}×{}",
+                scaled.width(),
+                scaled.height()
+            );
+            *image = scaled;
+        }
+        None => warn!("{problem}; its format can't be downscaled"),
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_resource::TextureFormat;
+
+    use super::*;
+
+    fn layer(name: &str, repeat_x: bool, z_order: f32) -> ParallaxLayerDef {
+        ParallaxLayerDef {
+            name: name.into(),
+            image: format!("{name}.png"),
+            speed_x: 0.1,
+            speed_y: 0.0,
+            repeat_x,
+            repeat_y: false,
+            z_order,
+        }
+    }
+
+    #[test]
+    fn oversized_checks_both_axes() {
+        assert_eq!(oversized("sky", UVec2::new(2048, 1024), 2048), None);
+        assert!(oversized("sky", UVec2::new(2049, 16), 2048).is_some());
+        assert!(oversized("sky", UVec2::new(16, 8192), 2048).is_some());
+    }
+
+    #[test]
+    fn repeat_width_only_matters_for_repeating_layers() {
+        assert_eq!(repeat_width_problem(&layer("hills", true, 0.0), 640), None);
+        assert!(repeat_width_problem(&layer("hills", true, 0.0), 650).is_some());
+        assert_eq!(repeat_width_problem(&layer("sky", false, 0.0), 650), None);
+    }
+
+    #[test]
+    fn z_order_collisions_name_both_layers() {
+        let layers = [
+            layer("sky", false, -100.0),
+            layer("far", true, -90.0),
+            layer("near", true, -90.0),
+        ];
+        let problems = z_order_collisions(&layers);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("'far'") && problems[0].contains("'near'"));
+
+        assert!(z_order_collisions(&layers[..2]).is_empty());
+    }
+
+    #[test]
+    fn autotile_sheet_must_be_whole_cells() {
+        assert_eq!(
+            autotile_sheet_problem("dirt", UVec2::new(16, 752), 16),
+            None
+        );
+        assert!(autotile_sheet_problem("dirt", UVec2::new(16, 750), 16).is_some());
+        assert!(autotile_sheet_problem("dirt", UVec2::new(18, 752), 16).is_some());
+        assert!(autotile_sheet_problem("dirt", UVec2::new(16, 752), 0).is_some());
+    }
+
+    #[test]
+    fn animated_sheet_needs_a_column_per_frame() {
+        assert_eq!(animation_sheet_problem("water", 64, 16, 4), None);
+        assert_eq!(animation_sheet_problem("water", 80, 16, 4), None);
+        assert!(animation_sheet_problem("water", 48, 16, 4).is_some());
+    }
+
+    #[test]
+    fn downscaled_size_keeps_aspect() {
+        assert_eq!(
+            downscaled_size(UVec2::new(1280, 720), 2048),
+            UVec2::new(1280, 720)
+        );
+        assert_eq!(
+            downscaled_size(UVec2::new(8192, 2048), 2048),
+            UVec2::new(2048, 512)
+        );
+        assert_eq!(
+            downscaled_size(UVec2::new(1000, 4000), 1000),
+            UVec2::new(250, 1000)
+        );
+    }
+
+    #[test]
+    fn downscale_nearest_samples_source_pixels() {
+        // 4×2 image, each pixel's red channel is its index.
+        let data: Vec<u8> = (0..8u8).flat_map(|i| [i, 0, 0, 255]).collect();
+        let image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            default(),
+        );
+
+        assert!(downscale_nearest(&image, 4).is_none());
+        let scaled = downscale_nearest(&image, 2).unwrap();
+        assert_eq!(scaled.size(), UVec2::new(2, 1));
+        let reds: Vec<u8> = scaled.data.unwrap().chunks(4).map(|px| px[0]).collect();
+        assert_eq!(reds, vec![0, 2]);
+    }
+}
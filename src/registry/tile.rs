@@ -13,18 +13,127 @@ impl TileId {
     pub const AIR: TileId = TileId(0);
 }
 
+/// Bit in a foreground tile's state byte marking a switchable tile as off.
+pub const TILE_STATE_OFF: u8 = 0b0000_0001;
+/// Bits in a growing tile's state byte holding its growth stage.
+pub const TILE_STATE_GROWTH: u8 = 0b1111_0000;
+/// Shift of the growth stage within the state byte.
+pub const TILE_STATE_GROWTH_SHIFT: u8 = 4;
+/// Bits in an orientable tile's state byte holding its [`TileOrientation`].
+pub const TILE_STATE_ORIENTATION: u8 = 0b0000_0110;
+/// Shift of the orientation within the state byte.
+pub const TILE_STATE_ORIENTATION_SHIFT: u8 = 1;
+
 fn default_light_opacity() -> u8 {
     15
 }
 
+/// Light filter of a tile that does not tint light passing through it.
+pub const NO_LIGHT_FILTER: [u8; 3] = [255, 255, 255];
+/// Light opacity from which a tile blocks too much light for its filter to
+/// matter.
+pub const FILTER_OPACITY_WARN: u8 = 8;
+
 fn default_albedo() -> [u8; 3] {
     [128, 128, 128]
 }
 
+fn default_light_filter() -> [u8; 3] {
+    NO_LIGHT_FILTER
+}
+
 fn default_flicker_min() -> f32 {
     1.0
 }
 
+fn default_cone_half_angle() -> f32 {
+    30.0
+}
+
+/// Which way a directional light points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum LightDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl LightDirection {
+    /// Unit step in tile coordinates (y up).
+    pub fn step(self) -> IVec2 {
+        match self {
+            Self::Up => IVec2::Y,
+            Self::Down => IVec2::NEG_Y,
+            Self::Left => IVec2::NEG_X,
+            Self::Right => IVec2::X,
+        }
+    }
+}
+
+/// Cone a directional emitter aims its light into (spotlights, headlamps).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct LightCone {
+    pub direction: LightDirection,
+    /// Half the cone's opening angle, in degrees.
+    #[serde(default = "default_cone_half_angle")]
+    pub half_angle: f32,
+}
+
+/// Side an orientable tile attaches to (torches) or faces (stairs, pipes),
+/// kept in its state byte. `Floor` is zero, so a tile placed before it had an
+/// orientation stands on the floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum TileOrientation {
+    #[default]
+    Floor,
+    LeftWall,
+    RightWall,
+    Ceiling,
+}
+
+impl TileOrientation {
+    /// All orientations in state-bit order, which is also the order automatic
+    /// placement tries them in.
+    pub const ALL: [Self; 4] = [Self::Floor, Self::LeftWall, Self::RightWall, Self::Ceiling];
+
+    /// Orientation stored in a tile's state byte.
+    pub fn from_state(state: u8) -> Self {
+        Self::ALL[((state & TILE_STATE_ORIENTATION) >> TILE_STATE_ORIENTATION_SHIFT) as usize]
+    }
+
+    /// `state` with its orientation bits replaced by this orientation.
+    pub fn apply(self, state: u8) -> u8 {
+        (state & !TILE_STATE_ORIENTATION) | ((self as u8) << TILE_STATE_ORIENTATION_SHIFT)
+    }
+
+    /// Tile offset of the neighbour this orientation attaches to.
+    pub fn support_offset(self) -> IVec2 {
+        match self {
+            Self::Floor => IVec2::NEG_Y,
+            Self::LeftWall => IVec2::NEG_X,
+            Self::RightWall => IVec2::X,
+            Self::Ceiling => IVec2::Y,
+        }
+    }
+}
+
+/// What a tile is made of, as far as walking on it goes (footstep sounds).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum TileMaterial {
+    #[default]
+    Stone,
+    Dirt,
+    Grass,
+    Sand,
+    Snow,
+    Wood,
+    Metal,
+    Glass,
+    /// Leaves, crops and other soft plant matter.
+    Plant,
+}
+
 /// Properties of a single tile type, deserialized from RON.
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)] // Fields reserved for future gameplay systems
@@ -32,6 +141,8 @@ pub struct TileDef {
     pub id: String,
     pub autotile: Option<String>,
     pub solid: bool,
+    /// Mining damage needed to break the tile; negative means it can't be
+    /// broken at all (bedrock).
     pub hardness: f32,
     pub friction: f32,
     pub viscosity: f32,
@@ -40,10 +151,18 @@ pub struct TileDef {
     pub effects: Vec<String>,
     #[serde(default)]
     pub light_emission: [u8; 3],
+    /// Aims `light_emission` into a cone instead of all around.
+    #[serde(default)]
+    pub light_cone: Option<LightCone>,
     #[serde(default = "default_light_opacity")]
     pub light_opacity: u8,
     #[serde(default = "default_albedo")]
     pub albedo: [u8; 3],
+    /// Per-channel tint (0–255) multiplied into light passing through the
+    /// tile, after `light_opacity` has been taken off. Colored glass pairs
+    /// a filter with zero opacity.
+    #[serde(default = "default_light_filter")]
+    pub light_filter: [u8; 3],
     /// Flicker oscillation speed in Hz. 0 = no flicker.
     #[serde(default)]
     pub flicker_speed: f32,
@@ -53,8 +172,71 @@ pub struct TileDef {
     /// Minimum brightness multiplier during flicker (floor).
     #[serde(default = "default_flicker_min")]
     pub flicker_min: f32,
+    /// Tile has an on/off state (lamps). Emits `light_emission` only while on.
+    #[serde(default)]
+    pub switchable: bool,
+    /// Decorative tile (tall grass, leaves) whose top edge sways in the wind.
+    #[serde(default)]
+    pub sway: bool,
+    /// Tile holds a short player-written text (see `ChunkData::signs`),
+    /// edited by right-clicking it and shown as a label above it.
+    #[serde(default)]
+    pub sign: bool,
+    /// Strength of the per-tile brightness/hue variation baked into chunk
+    /// meshes (1.0 = full, 0.0 disables). Breaks up large uniform areas.
+    #[serde(default)]
+    pub variation: f32,
+    /// Light from this tile adds to touching emitters of the same kind instead
+    /// of the brightest one winning, so clusters glow brighter (see
+    /// `PointLightMerge`).
+    #[serde(default)]
+    pub additive_light: bool,
     #[serde(default)]
     pub drops: Vec<DropDef>,
+    /// Plant that grows in stages over time (saplings, crops).
+    #[serde(default)]
+    pub growth: Option<GrowthDef>,
+    /// What footsteps on the tile sound like.
+    #[serde(default)]
+    pub material: TileMaterial,
+    /// The player can climb it (ropes, ladders) while overlapping it.
+    #[serde(default)]
+    pub climbable: bool,
+    /// Held up by the tile above (ropes): placed at the bottom of its run and
+    /// breaks along with whatever it hangs from.
+    #[serde(default)]
+    pub hanging: bool,
+    /// Attaches to a side (see [`TileOrientation`]): picked from the
+    /// neighbours when placed, or chosen by the player, and drawn with the
+    /// autotile's variants for that orientation.
+    #[serde(default)]
+    pub orientable: bool,
+}
+
+/// How a growing tile matures. The current stage lives in the tile's state
+/// byte (see [`TILE_STATE_GROWTH`]).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrowthDef {
+    /// Stages to grow through; the plant matures when the last one ends.
+    pub stages: u8,
+    /// Average seconds per stage while the plant has light and soil.
+    pub stage_secs: f32,
+    /// Light level (0.0–1.0) the plant needs to grow.
+    pub min_light: f32,
+    /// Tiles the plant must stand on; empty = any solid tile.
+    #[serde(default)]
+    pub soil: Vec<String>,
+    /// What the plant turns into once mature.
+    pub matures_into: Maturity,
+}
+
+/// Result of a plant maturing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Maturity {
+    /// Replaced by another tile (a ripe crop).
+    Tile(String),
+    /// Replaced by an object centered on the plant (a tree).
+    Object(String),
 }
 
 /// Registry of all tile definitions. Inserted as a Resource after asset loading.
@@ -83,6 +265,11 @@ impl TileRegistry {
         self.defs[id.0 as usize].solid
     }
 
+    /// Whether nothing (mining, blasts) can break the tile.
+    pub fn is_unbreakable(&self, id: TileId) -> bool {
+        self.defs[id.0 as usize].hardness < 0.0
+    }
+
     pub fn autotile_name(&self, id: TileId) -> Option<&str> {
         self.defs[id.0 as usize].autotile.as_deref()
     }
@@ -92,6 +279,17 @@ impl TileRegistry {
         self.defs[id.0 as usize].light_emission
     }
 
+    /// Light emission for a tile given its per-tile state byte.
+    /// Switchable tiles in the off state emit nothing.
+    pub fn light_emission_in_state(&self, id: TileId, state: u8) -> [u8; 3] {
+        let def = &self.defs[id.0 as usize];
+        if def.switchable && state & TILE_STATE_OFF != 0 {
+            [0, 0, 0]
+        } else {
+            def.light_emission
+        }
+    }
+
     #[allow(dead_code)] // Used by lighting propagation system (Task 5)
     pub fn light_opacity(&self, id: TileId) -> u8 {
         self.defs[id.0 as usize].light_opacity
@@ -102,6 +300,42 @@ impl TileRegistry {
         self.defs[id.0 as usize].albedo
     }
 
+    pub fn light_filter(&self, id: TileId) -> [u8; 3] {
+        self.defs[id.0 as usize].light_filter
+    }
+
+    pub fn material(&self, id: TileId) -> TileMaterial {
+        self.defs[id.0 as usize].material
+    }
+
+    pub fn is_climbable(&self, id: TileId) -> bool {
+        self.defs[id.0 as usize].climbable
+    }
+
+    pub fn is_hanging(&self, id: TileId) -> bool {
+        self.defs[id.0 as usize].hanging
+    }
+
+    pub fn is_orientable(&self, id: TileId) -> bool {
+        self.defs[id.0 as usize].orientable
+    }
+
+    /// Tile definitions that load but cannot behave as written.
+    pub fn validate(&self) -> Vec<String> {
+        self.defs
+            .iter()
+            .filter(|def| {
+                def.light_filter != NO_LIGHT_FILTER && def.light_opacity >= FILTER_OPACITY_WARN
+            })
+            .map(|def| {
+                format!(
+                    "tile '{}' has a light filter but light_opacity {} blocks most of the light",
+                    def.id, def.light_opacity
+                )
+            })
+            .collect()
+    }
+
     pub fn by_name(&self, name: &str) -> TileId {
         *self
             .name_to_id
@@ -135,7 +369,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "grass".into(),
@@ -152,7 +398,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Grass,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "dirt".into(),
@@ -169,7 +427,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Dirt,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "stone".into(),
@@ -186,7 +456,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
         ])
     }
@@ -214,6 +496,20 @@ mod tests {
         assert!(reg.is_solid(TileId(3)));
     }
 
+    #[test]
+    fn negative_hardness_is_unbreakable() {
+        let reg = test_registry();
+        let mut defs: Vec<TileDef> = (0..4).map(|i| reg.get(TileId(i)).clone()).collect();
+        let mut bedrock = defs[3].clone();
+        bedrock.id = "bedrock".into();
+        bedrock.hardness = -1.0;
+        defs.push(bedrock);
+        let reg = TileRegistry::from_defs(defs);
+        assert!(reg.is_unbreakable(reg.by_name("bedrock")));
+        assert!(!reg.is_unbreakable(reg.by_name("stone")));
+        assert!(!reg.is_unbreakable(TileId::AIR));
+    }
+
     #[test]
     fn autotile_name() {
         let reg = test_registry();
@@ -240,6 +536,43 @@ mod tests {
         assert_eq!(reg.light_opacity(TileId(3)), 15); // stone
     }
 
+    #[test]
+    fn switchable_tile_emits_only_when_on() {
+        let mut defs = test_registry().defs;
+        let mut lamp = defs[3].clone();
+        lamp.id = "lamp".into();
+        lamp.light_emission = [255, 220, 150];
+        lamp.switchable = true;
+        defs.push(lamp);
+        let reg = TileRegistry::from_defs(defs);
+        let lamp = reg.by_name("lamp");
+        assert_eq!(reg.light_emission_in_state(lamp, 0), [255, 220, 150]);
+        assert_eq!(reg.light_emission_in_state(lamp, TILE_STATE_OFF), [0, 0, 0]);
+    }
+
+    #[test]
+    fn non_switchable_tile_ignores_state() {
+        let reg = test_registry();
+        assert_eq!(
+            reg.light_emission_in_state(TileId(3), TILE_STATE_OFF),
+            reg.light_emission(TileId(3))
+        );
+    }
+
+    #[test]
+    fn orientation_round_trips_through_state_bits() {
+        for orientation in TileOrientation::ALL {
+            let state = orientation.apply(TILE_STATE_OFF | TILE_STATE_GROWTH);
+            assert_eq!(TileOrientation::from_state(state), orientation);
+            // The other state bits are left alone.
+            assert_eq!(
+                state & !TILE_STATE_ORIENTATION,
+                TILE_STATE_OFF | TILE_STATE_GROWTH
+            );
+        }
+        assert_eq!(TileOrientation::from_state(0), TileOrientation::Floor);
+    }
+
     #[test]
     fn albedo_properties() {
         let reg = test_registry();
@@ -247,6 +580,34 @@ mod tests {
         assert_eq!(reg.albedo(TileId(3)), [128, 128, 128]); // stone
     }
 
+    #[test]
+    fn material_properties() {
+        let reg = test_registry();
+        assert_eq!(reg.material(TileId(1)), TileMaterial::Grass);
+        assert_eq!(reg.material(TileId(3)), TileMaterial::Stone);
+        let def: TileDef = ron::from_str(
+            r#"(id: "plank", autotile: None, solid: true, hardness: 1.0, friction: 0.8,
+                viscosity: 0.0, damage_on_contact: 0.0, effects: [], material: Wood)"#,
+        )
+        .unwrap();
+        assert_eq!(def.material, TileMaterial::Wood);
+    }
+
+    #[test]
+    fn validate_warns_about_filters_on_opaque_tiles() {
+        let mut defs = test_registry().defs;
+        assert!(TileRegistry::from_defs(defs.clone()).validate().is_empty());
+        let mut glass = defs[0].clone();
+        glass.id = "red_glass".into();
+        glass.light_filter = [255, 40, 40];
+        defs.push(glass);
+        assert!(TileRegistry::from_defs(defs.clone()).validate().is_empty());
+        defs[3].light_filter = [200, 200, 255];
+        let problems = TileRegistry::from_defs(defs).validate();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("'stone'"));
+    }
+
     #[test]
     #[should_panic]
     fn by_name_panics_on_unknown() {
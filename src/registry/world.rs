@@ -46,6 +46,12 @@ impl ActiveWorld {
         }
     }
 
+    /// Whether `tile_x` lies past the left or right edge of a non-wrapping
+    /// world.
+    pub fn outside_x(&self, tile_x: i32) -> bool {
+        !self.wrap_x && (tile_x < 0 || tile_x >= self.width_tiles)
+    }
+
     pub fn world_pixel_width(&self) -> f32 {
         self.width_tiles as f32 * self.tile_size
     }
@@ -142,4 +148,16 @@ mod tests {
         assert_eq!(c.wrap_chunk_x(-1), -1);
         assert_eq!(c.wrap_chunk_x(64), 64);
     }
+
+    #[test]
+    fn outside_x_only_past_non_wrapping_edges() {
+        let mut c = test_config();
+        assert!(!c.outside_x(-1));
+        assert!(!c.outside_x(2048));
+        c.wrap_x = false;
+        assert!(c.outside_x(-1));
+        assert!(c.outside_x(2048));
+        assert!(!c.outside_x(0));
+        assert!(!c.outside_x(2047));
+    }
 }
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::registry::AppState;
+
 /// Top-level system ordering sets for the game loop.
 ///
 /// Configured as a chain: Input → Physics → WorldUpdate → Camera → Parallax → Ui.
@@ -13,3 +15,142 @@ pub enum GameSet {
     Parallax,
     Ui,
 }
+
+/// Sub-sets of [`GameSet::WorldUpdate`], chained Gen → Sim → Light → RenderPrep.
+///
+/// World systems go into exactly one of these instead of ordering themselves
+/// against each other's systems. Invariants:
+/// - chunks are loaded (and restored) before anything reads or edits them;
+/// - all tile mutations happen before `Light`: player edits run earlier still,
+///   in [`GameSet::Input`], and world ticks run in `Sim`;
+/// - `Light` only marks what to relight. RC extraction itself runs after
+///   [`GameSet::Camera`] so the lightmap lines up with the current frame;
+/// - `RenderPrep` only turns the final tiles of the frame into meshes and
+///   overlays, it never edits the world.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldSet {
+    /// Chunk loading and generation, restoring saved state into new chunks.
+    Gen,
+    /// Tile ticks, liquids, tile-entity updates, spawns and world commands.
+    Sim,
+    /// Relight requests for the tiles and lights changed this frame.
+    Light,
+    /// Dirty chunk mesh rebuilds and other render-side mirrors of the world.
+    RenderPrep,
+}
+
+/// Configure the [`GameSet`] chain and the [`WorldSet`] chain inside it.
+pub fn configure_sets(app: &mut App) {
+    app.configure_sets(
+        Update,
+        (
+            GameSet::Input,
+            GameSet::Physics,
+            GameSet::WorldUpdate,
+            GameSet::Camera,
+            GameSet::Parallax,
+            GameSet::Ui,
+        )
+            .chain()
+            .run_if(in_state(AppState::InGame)),
+    )
+    .configure_sets(
+        Update,
+        (
+            WorldSet::Gen,
+            WorldSet::Sim,
+            WorldSet::Light,
+            WorldSet::RenderPrep,
+        )
+            .chain()
+            .in_set(GameSet::WorldUpdate),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::schedule::{NodeId, ScheduleGraph};
+
+    /// App with the sets configured and the world systems registered, without
+    /// the render plugins the world plugin also brings in.
+    fn world_app() -> App {
+        let mut app = App::new();
+        configure_sets(&mut app);
+        crate::world::add_update_systems(&mut app);
+        crate::world::rc_lighting::add_relight_systems(&mut app);
+        app.add_plugins(crate::liquid::LiquidPlugin);
+        app
+    }
+
+    fn set_node(graph: &ScheduleGraph, set: impl SystemSet) -> NodeId {
+        let name = format!("{set:?}");
+        graph
+            .system_sets
+            .iter()
+            .find(|(_, s, _)| format!("{s:?}") == name)
+            .map(|(key, ..)| NodeId::Set(key))
+            .unwrap_or_else(|| panic!("set {name} is not configured"))
+    }
+
+    fn system_node(graph: &ScheduleGraph, name: &str) -> NodeId {
+        let suffix = format!("::{name}");
+        graph
+            .systems
+            .iter()
+            .find(|(_, system, _)| system.name().to_string().ends_with(&suffix))
+            .map(|(key, ..)| NodeId::System(key))
+            .unwrap_or_else(|| panic!("system {name} is not registered"))
+    }
+
+    #[test]
+    fn world_sets_are_chained_inside_world_update() {
+        let app = world_app();
+        let graph = app.get_schedule(Update).unwrap().graph();
+        let update = set_node(graph, GameSet::WorldUpdate);
+        let sets = [
+            WorldSet::Gen,
+            WorldSet::Sim,
+            WorldSet::Light,
+            WorldSet::RenderPrep,
+        ]
+        .map(|set| set_node(graph, set));
+        for set in sets {
+            assert!(graph.hierarchy().graph().contains_edge(update, set));
+        }
+        for pair in sets.windows(2) {
+            assert!(
+                graph.dependency().graph().contains_edge(pair[0], pair[1]),
+                "{:?} does not run before {:?}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn world_systems_are_in_their_sets() {
+        let app = world_app();
+        let graph = app.get_schedule(Update).unwrap().graph();
+        let expected = [
+            ("chunk_loading_system", WorldSet::Gen),
+            ("restore_loaded_dropped_items", WorldSet::Gen),
+            ("tick_growth", WorldSet::Sim),
+            ("tick_grass_spread", WorldSet::Sim),
+            ("liquid_simulation_system", WorldSet::Sim),
+            ("refresh_spatial_index", WorldSet::Sim),
+            ("flush_liquid_relight", WorldSet::Light),
+            ("relight_overridden_chunks", WorldSet::Light),
+            ("rebuild_dirty_chunks", WorldSet::RenderPrep),
+            ("rebuild_liquid_meshes", WorldSet::RenderPrep),
+        ];
+        for (system, set) in expected {
+            let system_id = system_node(graph, system);
+            let set_id = set_node(graph, set);
+            assert!(
+                graph.hierarchy().graph().contains_edge(set_id, system_id),
+                "{system} is not in {set:?}"
+            );
+        }
+    }
+}
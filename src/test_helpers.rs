@@ -3,13 +3,16 @@ pub mod fixtures {
     use bevy::prelude::*;
 
     use crate::cosmos::address::{CelestialAddress, CelestialSeeds};
+    use crate::item::{ItemDef, ItemType, Rarity};
+    use crate::registry::assets::DifficultyCurve;
     use crate::registry::biome::{
         BiomeDef, BiomeRegistry, LayerBoundaries, LayerConfig, LayerConfigs, PlanetConfig,
     };
     use crate::registry::player::PlayerConfig;
-    use crate::registry::tile::{TileDef, TileId, TileRegistry};
+    use crate::registry::tile::{TileDef, TileId, TileMaterial, TileRegistry};
     use crate::registry::world::ActiveWorld;
-    use crate::world::biome_map::BiomeMap;
+    use crate::ui::game_ui::theme::UiTheme;
+    use crate::world::biome_map::{BiomeMap, BorderCliffs};
     use crate::world::chunk::WorldMap;
     use crate::world::ctx::WorldCtxRef;
     use crate::world::terrain_gen::TerrainNoiseCache;
@@ -47,6 +50,8 @@ pub mod fixtures {
             300,
             600,
             0.6,
+            1,
+            None,
             biome_registry,
         )
     }
@@ -72,6 +77,7 @@ pub mod fixtures {
                     cave_threshold: threshold,
                     parallax_path: None,
                     temperature_offset: 0.0,
+                    decorations: vec![],
                 },
             );
         }
@@ -95,7 +101,19 @@ pub mod fixtures {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "grass".into(),
@@ -112,7 +130,19 @@ pub mod fixtures {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Grass,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "dirt".into(),
@@ -129,7 +159,19 @@ pub mod fixtures {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Dirt,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "stone".into(),
@@ -146,7 +188,19 @@ pub mod fixtures {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
         ])
     }
@@ -158,24 +212,28 @@ pub mod fixtures {
                 terrain_frequency: 0.02,
                 terrain_amplitude: 40.0,
                 depth_ratio: 0.30,
+                cave_threshold_scale: (1.0, 1.0),
             },
             underground: LayerConfig {
                 primary_biome: Some("underground_dirt".into()),
                 terrain_frequency: 0.07,
                 terrain_amplitude: 1.0,
                 depth_ratio: 0.25,
+                cave_threshold_scale: (1.0, 1.0),
             },
             deep_underground: LayerConfig {
                 primary_biome: Some("underground_rock".into()),
                 terrain_frequency: 0.05,
                 terrain_amplitude: 1.0,
                 depth_ratio: 0.33,
+                cave_threshold_scale: (1.0, 1.0),
             },
             core: LayerConfig {
                 primary_biome: Some("core_magma".into()),
                 terrain_frequency: 0.04,
                 terrain_amplitude: 1.0,
                 depth_ratio: 0.12,
+                cave_threshold_scale: (1.0, 1.0),
             },
         };
         let layer_boundaries = LayerBoundaries::from_layers(&layers, 1024);
@@ -185,9 +243,22 @@ pub mod fixtures {
             secondary_biomes: vec!["forest".into(), "rocky".into()],
             layers,
             layer_boundaries,
+            layer_blend: 0,
             region_width_min: 300,
             region_width_max: 600,
             primary_region_ratio: 0.6,
+            gravity_scale: 1.0,
+            atmosphere_density: 1.0,
+            day_length: None,
+            starter_biome: None,
+            biome_separation: 1,
+            border_tile: None,
+            bedrock_tile: None,
+            bedrock_depth: 0,
+            thin_air: None,
+            difficulty: DifficultyCurve::default(),
+            biome_borders: Vec::new(),
+            border_cliffs: BorderCliffs::default(),
         }
     }
 
@@ -232,9 +303,45 @@ pub mod fixtures {
             tile_registry: tr,
             planet_config: pc,
             noise_cache: nc,
+            biome_overrides: None,
         }
     }
 
+    /// A common item named after its id that places nothing and has no use,
+    /// stats or effects of its own. Tests build their items from it with
+    /// struct-update syntax.
+    pub fn item_def(id: &str, item_type: ItemType) -> ItemDef {
+        ItemDef {
+            id: id.into(),
+            display_name: id.into(),
+            description: String::new(),
+            max_stack: 99,
+            rarity: Rarity::Common,
+            item_type,
+            icon: None,
+            placeable: None,
+            placeable_object: None,
+            equipment_slot: None,
+            stats: None,
+            blueprint_item: None,
+            action: None,
+            use_cooldown: None,
+            item_cooldown: None,
+            projectile: None,
+            light: None,
+            bag_rows: None,
+            effects: Vec::new(),
+            tile_conversions: Vec::new(),
+        }
+    }
+
+    /// The shipped UI theme.
+    pub fn test_ui_theme() -> UiTheme {
+        let ron_str =
+            std::fs::read_to_string("assets/ui.theme.ron").expect("ui.theme.ron should exist");
+        ron::from_str(&ron_str).expect("ui.theme.ron should parse")
+    }
+
     pub fn test_player_config() -> PlayerConfig {
         PlayerConfig {
             speed: 200.0,
@@ -242,12 +349,18 @@ pub mod fixtures {
             gravity: 980.0,
             width: 24.0,
             height: 40.0,
+            collision_width: None,
+            collision_height: None,
             magnet_radius: 96.0,
             magnet_strength: 400.0,
             pickup_radius: 20.0,
             swim_impulse: 180.0,
             swim_gravity_factor: 0.3,
             swim_drag: 0.15,
+            break_reach: 5.0,
+            place_reach: 5.0,
+            max_fall_speed: 900.0,
+            spawn_search_radius: 64,
         }
     }
 
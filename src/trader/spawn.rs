@@ -12,6 +12,22 @@ use super::{TradeOffer, TradeOffers, Trader};
 #[derive(Component)]
 pub struct TraderSpawned;
 
+/// Ground tile the trader stands on: the surface at the centre column of the
+/// world. `None` on ships, which get no trader.
+pub fn trader_post(
+    active_world: &ActiveWorld,
+    noise_cache: &TerrainNoiseCache,
+    planet_config: &PlanetConfig,
+) -> Option<IVec2> {
+    // Ship worlds have zero amplitude — skip trader spawn on ships.
+    if planet_config.layers.surface.terrain_amplitude == 0.0 {
+        return None;
+    }
+    let tile_x = active_world.width_tiles / 2;
+    let surface_y = noise_cache.surface_height_at(tile_x, active_world, planet_config);
+    Some(IVec2::new(tile_x, surface_y))
+}
+
 /// Spawn a trader at the approximate center of the world, on the surface.
 pub fn spawn_trader(
     mut commands: Commands,
@@ -26,22 +42,13 @@ pub fn spawn_trader(
         return;
     }
 
-    // Ship worlds have zero amplitude — skip trader spawn on ships.
-    if planet_config.layers.surface.terrain_amplitude == 0.0 {
+    let Some(post) = trader_post(&active_world, &noise_cache, &planet_config) else {
         return;
-    }
-
-    let tile_x = active_world.width_tiles / 2;
-    let surface_y = crate::world::terrain_gen::surface_height(
-        &noise_cache,
-        tile_x,
-        &active_world,
-        planet_config.layers.surface.terrain_frequency,
-        planet_config.layers.surface.terrain_amplitude,
-    );
+    };
 
     // Place 2 tiles above the surface
-    let tile_y = surface_y + 2;
+    let tile_x = post.x;
+    let tile_y = post.y + 2;
     let tile_size = active_world.tile_size;
 
     let world_x = tile_x as f32 * tile_size + tile_size / 2.0;
@@ -0,0 +1,139 @@
+//! Starter objectives guiding new players: open the inventory, dig, craft
+//! and place torches, head underground.
+//!
+//! `tutorial.ron` lists the objectives in order, each with a
+//! [`Trigger`](objective::Trigger).
+//! [`track_tutorial_progress`] turns this frame's game events (tiles broken
+//! and placed, crafts, the inventory screen opening, the player's depth)
+//! into [`TutorialAction`]s and records them in the [`TutorialProgress`],
+//! which is saved with the player. The [`panel`] shows the first objective
+//! not completed yet. [`TutorialSettings`] turns the whole thing off.
+
+pub mod objective;
+pub mod panel;
+
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub use objective::{Tutorial, TutorialAction, TutorialProgress};
+pub use panel::TutorialPanel;
+
+use crate::crafting::ItemCrafted;
+use crate::object::placement::get_object_at;
+use crate::object::registry::ObjectRegistry;
+use crate::player::Player;
+use crate::registry::tile::TileId;
+use crate::registry::AppState;
+use crate::sets::GameSet;
+use crate::ui::game_ui::InventoryScreenState;
+use crate::world::chunk::{world_to_tile, Layer, TileBroken, TileChanged, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+
+/// Tutorial settings. On by default; toggled from the debug panel.
+#[derive(Resource, Debug, Clone)]
+pub struct TutorialSettings {
+    pub enabled: bool,
+}
+
+impl Default for TutorialSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Name of the object, or else the foreground tile, at a tile that just
+/// changed. `None` where it was cleared.
+fn placed_at<'a>(
+    world_map: &WorldMap,
+    tile_x: i32,
+    tile_y: i32,
+    ctx: &'a WorldCtxRef,
+    object_registry: Option<&'a ObjectRegistry>,
+) -> Option<&'a str> {
+    if let Some((.., object)) = get_object_at(world_map, tile_x, tile_y, ctx) {
+        return object_registry.map(|registry| registry.get(object).id.as_str());
+    }
+    let tile = world_map.get_tile(tile_x, tile_y, Layer::Fg, ctx)?;
+    (tile != TileId::AIR).then(|| ctx.tile_registry.get(tile).id.as_str())
+}
+
+/// Record this frame's tutorial-relevant events in the progress.
+#[allow(clippy::too_many_arguments)]
+pub fn track_tutorial_progress(
+    settings: Res<TutorialSettings>,
+    tutorial: Option<Res<Tutorial>>,
+    mut progress: ResMut<TutorialProgress>,
+    mut events: (
+        MessageReader<TileBroken>,
+        MessageReader<TileChanged>,
+        MessageReader<ItemCrafted>,
+    ),
+    inventory_screen: Option<Res<InventoryScreenState>>,
+    player: Query<&Transform, (With<Player>, Changed<Transform>)>,
+    world_map: Res<WorldMap>,
+    ctx: WorldCtx,
+    object_registry: Option<Res<ObjectRegistry>>,
+) {
+    let (broken, changed, crafted) = &mut events;
+    let Some(tutorial) = tutorial.filter(|_| settings.enabled) else {
+        return;
+    };
+    let ctx = ctx.as_ref();
+    let mut actions = Vec::new();
+    for broken in broken.read() {
+        let name = &ctx.tile_registry.get(broken.tile).id;
+        actions.push(TutorialAction::BrokeTile(name));
+    }
+    for changed in changed.read() {
+        let placed = placed_at(
+            &world_map,
+            changed.tile_x,
+            changed.tile_y,
+            &ctx,
+            object_registry.as_deref(),
+        );
+        actions.extend(placed.map(TutorialAction::Placed));
+    }
+    for crafted in crafted.read() {
+        actions.push(TutorialAction::Crafted(&crafted.item_id));
+    }
+    if inventory_screen.is_some_and(|screen| screen.is_changed() && screen.visible) {
+        actions.push(TutorialAction::OpenedInventory);
+    }
+    if let Ok(tf) = player.single() {
+        let (tile_x, tile_y) =
+            world_to_tile(tf.translation.x, tf.translation.y, ctx.config.tile_size);
+        let surface = ctx
+            .noise_cache
+            .surface_height_at(tile_x, ctx.config, ctx.planet_config);
+        actions.push(TutorialAction::Depth(surface - tile_y));
+    }
+
+    for action in &actions {
+        for id in progress.record(&tutorial, action) {
+            info!("Tutorial objective completed: {id}");
+        }
+    }
+}
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TutorialSettings>()
+            .init_resource::<TutorialProgress>()
+            .init_resource::<TutorialPanel>()
+            .add_systems(
+                Update,
+                (track_tutorial_progress, panel::update_tutorial_panel)
+                    .chain()
+                    .in_set(GameSet::Ui)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                EguiPrimaryContextPass,
+                panel::draw_tutorial_panel.run_if(in_state(AppState::InGame)),
+            );
+    }
+}
@@ -0,0 +1,254 @@
+//! Objective definitions from `tutorial.ron` and the player's progress
+//! through them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What completes an objective.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Trigger {
+    /// Break `count` foreground or background tiles named `tile`.
+    BreakTiles { tile: String, count: u32 },
+    /// Finish crafting `item`.
+    Craft { item: String },
+    /// Stand at least `depth` tiles below the surface of the current column.
+    ReachDepth { depth: i32 },
+    /// Place a tile or an object named `what`.
+    Place { what: String },
+    /// Open the inventory screen.
+    OpenInventory,
+}
+
+/// Something the player did that may advance an objective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialAction<'a> {
+    /// Broke one tile with this name.
+    BrokeTile(&'a str),
+    /// Crafted this item.
+    Crafted(&'a str),
+    /// Is this many tiles below the surface (negative above it).
+    Depth(i32),
+    /// Placed a tile or object with this name.
+    Placed(&'a str),
+    OpenedInventory,
+}
+
+impl Trigger {
+    /// Units of progress `action` makes toward this trigger.
+    fn progress(&self, action: &TutorialAction) -> u32 {
+        let hit = match (self, action) {
+            (Self::BreakTiles { tile, .. }, TutorialAction::BrokeTile(name)) => tile == name,
+            (Self::Craft { item }, TutorialAction::Crafted(name)) => item == name,
+            (Self::ReachDepth { depth }, TutorialAction::Depth(reached)) => reached >= depth,
+            (Self::Place { what }, TutorialAction::Placed(name)) => what == name,
+            (Self::OpenInventory, TutorialAction::OpenedInventory) => true,
+            _ => false,
+        };
+        hit as u32
+    }
+
+    /// Units of progress needed to complete the trigger.
+    pub fn required(&self) -> u32 {
+        match self {
+            Self::BreakTiles { count, .. } => (*count).max(1),
+            _ => 1,
+        }
+    }
+}
+
+/// One step of the tutorial.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Objective {
+    /// Stable key progress is saved under.
+    pub id: String,
+    /// Shown to the player while the objective is current.
+    pub text: String,
+    pub trigger: Trigger,
+}
+
+/// Asset loaded from `tutorial.ron`: the objectives, in the order they are
+/// shown. Inserted as a resource once loaded.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Default, Deserialize)]
+pub struct Tutorial {
+    pub objectives: Vec<Objective>,
+}
+
+/// Which objectives the player has completed, saved with the player.
+///
+/// Every unfinished objective listens for its trigger, not only the current
+/// one, so an objective satisfied early is skipped once it comes up.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TutorialProgress {
+    /// Ids of completed objectives.
+    pub completed: BTreeSet<String>,
+    /// Progress toward unfinished multi-step objectives, by id.
+    pub counts: BTreeMap<String, u32>,
+}
+
+impl TutorialProgress {
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    /// First objective of `tutorial` not completed yet.
+    pub fn current<'a>(&self, tutorial: &'a Tutorial) -> Option<&'a Objective> {
+        tutorial
+            .objectives
+            .iter()
+            .find(|objective| !self.is_completed(&objective.id))
+    }
+
+    /// Progress made toward objective `id`.
+    pub fn count(&self, id: &str) -> u32 {
+        self.counts.get(id).copied().unwrap_or_default()
+    }
+
+    /// Count `action` toward every unfinished objective of `tutorial`.
+    /// Returns the ids it completed, in tutorial order.
+    pub fn record<'a>(&mut self, tutorial: &'a Tutorial, action: &TutorialAction) -> Vec<&'a str> {
+        let mut done = Vec::new();
+        for objective in &tutorial.objectives {
+            let step = objective.trigger.progress(action);
+            if step == 0 || self.is_completed(&objective.id) {
+                continue;
+            }
+            let count = self.count(&objective.id) + step;
+            if count >= objective.trigger.required() {
+                self.counts.remove(&objective.id);
+                self.completed.insert(objective.id.clone());
+                done.push(objective.id.as_str());
+            } else {
+                self.counts.insert(objective.id.clone(), count);
+            }
+        }
+        done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objective(id: &str, trigger: Trigger) -> Objective {
+        Objective {
+            id: id.into(),
+            text: id.into(),
+            trigger,
+        }
+    }
+
+    fn tutorial() -> Tutorial {
+        Tutorial {
+            objectives: vec![
+                objective("inventory", Trigger::OpenInventory),
+                objective(
+                    "dig",
+                    Trigger::BreakTiles {
+                        tile: "dirt".into(),
+                        count: 3,
+                    },
+                ),
+                objective(
+                    "torches",
+                    Trigger::Craft {
+                        item: "torch".into(),
+                    },
+                ),
+                objective(
+                    "light",
+                    Trigger::Place {
+                        what: "torch".into(),
+                    },
+                ),
+                objective("deep", Trigger::ReachDepth { depth: 20 }),
+            ],
+        }
+    }
+
+    #[test]
+    fn triggers_match_only_their_own_actions() {
+        use TutorialAction::*;
+        let tutorial = tutorial();
+        let cases: [(&str, &[TutorialAction]); 5] = [
+            ("inventory", &[OpenedInventory]),
+            (
+                "dig",
+                &[BrokeTile("dirt"), BrokeTile("dirt"), BrokeTile("dirt")],
+            ),
+            ("torches", &[Crafted("torch")]),
+            ("light", &[Placed("torch")]),
+            ("deep", &[Depth(25)]),
+        ];
+        let misses = [
+            BrokeTile("stone"),
+            Crafted("sign"),
+            Placed("sign"),
+            Depth(19),
+            Depth(-5),
+        ];
+        for (id, actions) in cases {
+            let mut progress = TutorialProgress::default();
+            for action in &misses {
+                progress.record(&tutorial, action);
+            }
+            assert!(progress.completed.is_empty(), "{:?}", progress.completed);
+            for action in actions {
+                progress.record(&tutorial, action);
+            }
+            assert_eq!(
+                progress.completed,
+                BTreeSet::from([id.to_string()]),
+                "{actions:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn tile_counts_build_up_to_completion() {
+        let tutorial = tutorial();
+        let mut progress = TutorialProgress::default();
+        let dirt = TutorialAction::BrokeTile("dirt");
+        assert!(progress.record(&tutorial, &dirt).is_empty());
+        assert!(progress.record(&tutorial, &dirt).is_empty());
+        assert_eq!(progress.count("dig"), 2);
+        assert_eq!(progress.record(&tutorial, &dirt), ["dig"]);
+        assert_eq!(progress.count("dig"), 0);
+        // Further breaks don't count once it is done.
+        progress.record(&tutorial, &dirt);
+        assert!(progress.counts.is_empty());
+    }
+
+    #[test]
+    fn objectives_satisfied_early_are_skipped() {
+        let tutorial = tutorial();
+        let mut progress = TutorialProgress::default();
+        assert_eq!(progress.current(&tutorial).unwrap().id, "inventory");
+
+        // Torches crafted and placed before the first objectives.
+        progress.record(&tutorial, &TutorialAction::Crafted("torch"));
+        progress.record(&tutorial, &TutorialAction::Placed("torch"));
+        assert_eq!(progress.current(&tutorial).unwrap().id, "inventory");
+
+        progress.record(&tutorial, &TutorialAction::OpenedInventory);
+        assert_eq!(progress.current(&tutorial).unwrap().id, "dig");
+        for _ in 0..3 {
+            progress.record(&tutorial, &TutorialAction::BrokeTile("dirt"));
+        }
+        // Straight past the torch objectives.
+        assert_eq!(progress.current(&tutorial).unwrap().id, "deep");
+        progress.record(&tutorial, &TutorialAction::Depth(20));
+        assert_eq!(progress.current(&tutorial), None);
+    }
+
+    #[test]
+    fn tutorial_file_parses() {
+        let text = std::fs::read_to_string("assets/content/tutorial.ron").unwrap();
+        let tutorial: Tutorial = ron::de::from_str(&text).unwrap();
+        assert!(!tutorial.objectives.is_empty());
+        let ids: BTreeSet<_> = tutorial.objectives.iter().map(|o| &o.id).collect();
+        assert_eq!(ids.len(), tutorial.objectives.len(), "duplicate ids");
+    }
+}
@@ -0,0 +1,186 @@
+//! On-screen box showing the current tutorial objective.
+//!
+//! When the objective on screen is completed it stays a moment with a
+//! checkmark popping in, then the next objective slides in from the right.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use super::objective::{Tutorial, TutorialProgress};
+use super::TutorialSettings;
+
+/// Width (px) of the objective box.
+const PANEL_WIDTH: f32 = 260.0;
+/// Seconds a new objective takes to slide in.
+const SLIDE_SECS: f32 = 0.3;
+/// Seconds a completed objective stays up with its checkmark.
+const CHECK_SECS: f32 = 1.0;
+
+/// Objective on screen and where its animation is.
+#[derive(Resource, Debug, Default)]
+pub struct TutorialPanel {
+    /// Id of the objective shown.
+    shown: Option<String>,
+    /// Seconds since it slid in.
+    age: f32,
+    /// Seconds since it was completed, while the checkmark plays.
+    completed_for: Option<f32>,
+}
+
+impl TutorialPanel {
+    /// Advance the animation toward showing `current`, the objective that
+    /// should be up now.
+    pub fn tick(&mut self, current: Option<&str>, dt: f32) {
+        self.age += dt;
+        if let Some(t) = self.completed_for.as_mut() {
+            *t += dt;
+        }
+        if self.shown.as_deref() == current {
+            return;
+        }
+        match self.completed_for {
+            // The shown objective was just completed: play its checkmark.
+            None if self.shown.is_some() => self.completed_for = Some(0.0),
+            Some(t) if t < CHECK_SECS => {}
+            _ => {
+                self.shown = current.map(String::from);
+                self.age = 0.0;
+                self.completed_for = None;
+            }
+        }
+    }
+
+    pub fn shown(&self) -> Option<&str> {
+        self.shown.as_deref()
+    }
+
+    /// How far in (0.0 off to the side, 1.0 in place) the objective has slid.
+    pub fn slide(&self) -> f32 {
+        (self.age / SLIDE_SECS).clamp(0.0, 1.0)
+    }
+
+    /// How far the checkmark has played (0.0 to 1.0), while it plays.
+    pub fn check(&self) -> Option<f32> {
+        self.completed_for.map(|t| (t / CHECK_SECS).clamp(0.0, 1.0))
+    }
+}
+
+/// Move the panel toward the current objective.
+pub fn update_tutorial_panel(
+    time: Res<Time>,
+    settings: Res<TutorialSettings>,
+    tutorial: Option<Res<Tutorial>>,
+    progress: Res<TutorialProgress>,
+    mut panel: ResMut<TutorialPanel>,
+) {
+    if !settings.enabled {
+        if panel.shown.is_some() {
+            *panel = TutorialPanel::default();
+        }
+        return;
+    }
+    let current = tutorial
+        .as_deref()
+        .and_then(|tutorial| progress.current(tutorial))
+        .map(|objective| objective.id.as_str());
+    panel.tick(current, time.delta_secs());
+}
+
+/// Draw the objective box on the right edge of the screen.
+pub fn draw_tutorial_panel(
+    mut contexts: EguiContexts,
+    tutorial: Option<Res<Tutorial>>,
+    panel: Res<TutorialPanel>,
+) -> Result {
+    let Some(objective) = panel.shown().and_then(|id| {
+        tutorial
+            .as_deref()?
+            .objectives
+            .iter()
+            .find(|objective| objective.id == id)
+    }) else {
+        return Ok(());
+    };
+    let ctx = contexts.ctx_mut()?;
+    let green = egui::Color32::from_rgb(50, 200, 70);
+    let check = panel.check();
+    let offset_x = (1.0 - panel.slide()) * (PANEL_WIDTH + 10.0);
+
+    egui::Area::new(egui::Id::new("tutorial_objective"))
+        .anchor(
+            egui::Align2::RIGHT_CENTER,
+            egui::vec2(-10.0 + offset_x, 0.0),
+        )
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::new()
+                .fill(egui::Color32::from_rgba_unmultiplied(20, 20, 30, 200))
+                .stroke(egui::Stroke::new(
+                    1.0,
+                    if check.is_some() {
+                        green
+                    } else {
+                        egui::Color32::from_rgb(230, 200, 50)
+                    },
+                ))
+                .corner_radius(3.0)
+                .inner_margin(egui::Margin::symmetric(8, 6))
+                .show(ui, |ui| {
+                    ui.set_width(PANEL_WIDTH);
+                    ui.label(
+                        egui::RichText::new("Objective")
+                            .color(egui::Color32::GRAY)
+                            .size(11.0),
+                    );
+                    ui.horizontal(|ui| {
+                        let text = egui::RichText::new(&objective.text).size(13.0);
+                        match check {
+                            Some(t) => {
+                                // The checkmark pops in, overshooting a little.
+                                let pop = (t * 4.0).min(1.0);
+                                let size = 14.0 * (pop + 0.3 * (pop * std::f32::consts::PI).sin());
+                                ui.label(egui::RichText::new("✔").color(green).size(size.max(1.0)));
+                                ui.label(text.color(green).strikethrough());
+                            }
+                            None => {
+                                ui.label(text.color(egui::Color32::WHITE));
+                            }
+                        }
+                    });
+                });
+        });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completed_objective_checks_off_before_the_next_slides_in() {
+        let mut panel = TutorialPanel::default();
+        panel.tick(Some("dig"), 0.0);
+        assert_eq!(panel.shown(), Some("dig"));
+        assert_eq!((panel.slide(), panel.check()), (0.0, None));
+        panel.tick(Some("dig"), SLIDE_SECS);
+        assert_eq!(panel.slide(), 1.0);
+
+        // "dig" is done: it stays up with its checkmark first.
+        panel.tick(Some("craft"), 0.1);
+        assert_eq!(panel.shown(), Some("dig"));
+        assert_eq!(panel.check(), Some(0.0));
+        panel.tick(Some("craft"), CHECK_SECS * 0.5);
+        assert_eq!(panel.shown(), Some("dig"));
+        assert!((panel.check().unwrap() - 0.5).abs() < 1e-4);
+
+        panel.tick(Some("craft"), CHECK_SECS * 0.5);
+        assert_eq!(panel.shown(), Some("craft"));
+        assert_eq!((panel.slide(), panel.check()), (0.0, None));
+
+        // After the last objective the panel empties.
+        panel.tick(None, 0.0);
+        panel.tick(None, CHECK_SECS);
+        assert_eq!(panel.shown(), None);
+    }
+}
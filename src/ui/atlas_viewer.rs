@@ -0,0 +1,492 @@
+//! Debug viewer for the combined tile atlas (F9, cheats only).
+//!
+//! Draws the atlas with a grid over its cells and each column labelled with
+//! its autotile. Clicking a cell lists the bitmasks that draw it. Cells that
+//! no bitmask maps (dead art) are tinted red; Blob47 masks that an autotile
+//! leaves to a fallback or maps past the last atlas row (missing art) are
+//! listed per column.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiTextureHandle};
+
+use crate::game_mode::CheatsEnabled;
+use crate::world::atlas::TileAtlas;
+use crate::world::autotile::{is_blob47_mask, AutotileRegistry};
+
+/// Height (px) of the column label band above the atlas.
+const LABEL_HEIGHT: f32 = 14.0;
+
+/// Atlas viewer visibility and selection.
+#[derive(Resource)]
+pub struct AtlasViewerState {
+    pub visible: bool,
+    /// Selected cell as (column, row).
+    pub selected: Option<(u32, u32)>,
+    /// Screen pixels per atlas texel.
+    pub zoom: f32,
+}
+
+impl Default for AtlasViewerState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            selected: None,
+            zoom: 2.0,
+        }
+    }
+}
+
+/// A bitmask drawing an atlas cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellUse {
+    pub mask: u8,
+    /// Weight of the cell among the mask's variants.
+    pub weight: f32,
+}
+
+/// Everything that draws one atlas cell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AtlasCell {
+    /// Masks that map the cell themselves.
+    pub mapped: Vec<CellUse>,
+    /// Unmapped Blob47 masks that borrow the cell through the fallback.
+    pub fallback_for: Vec<u8>,
+}
+
+/// A bitmask without art of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingArt {
+    /// A Blob47 mask the autotile doesn't map; drawn with a fallback.
+    Unmapped(u8),
+    /// A mapped mask whose variant points past the last atlas row.
+    OutOfAtlas { mask: u8, row: u32 },
+}
+
+/// Reverse lookup from atlas cells to the bitmasks that draw them.
+#[derive(Debug, Default)]
+pub struct AtlasReport {
+    pub cells: HashMap<(u32, u32), AtlasCell>,
+    /// Cells no mask maps, sorted by (column, row).
+    pub dead: Vec<(u32, u32)>,
+    /// Masks without art, by atlas column.
+    pub missing: BTreeMap<u32, Vec<MissingArt>>,
+}
+
+impl AtlasReport {
+    /// Walk every bitmask of every autotile in an atlas of `columns` ×
+    /// `rows` cells.
+    pub fn build(registry: &AutotileRegistry, columns: u32, rows: u32) -> Self {
+        let mut report = Self::default();
+        for entry in registry.entries.values() {
+            // Animation frames draw the rows of the first column; missing
+            // art is listed once, under that column.
+            for frame in 0..entry.frame_count() {
+                let column = entry.column_index + frame;
+                for mask in 0..=255u8 {
+                    let variants = entry.variants_for(mask);
+                    if entry.is_mapped(mask) {
+                        for variant in variants {
+                            if variant.row >= rows {
+                                if frame == 0 {
+                                    report.missing.entry(column).or_default().push(
+                                        MissingArt::OutOfAtlas {
+                                            mask,
+                                            row: variant.row,
+                                        },
+                                    );
+                                }
+                                continue;
+                            }
+                            report
+                                .cells
+                                .entry((column, variant.row))
+                                .or_default()
+                                .mapped
+                                .push(CellUse {
+                                    mask,
+                                    weight: variant.weight,
+                                });
+                        }
+                    } else if is_blob47_mask(mask) {
+                        if frame == 0 {
+                            report
+                                .missing
+                                .entry(column)
+                                .or_default()
+                                .push(MissingArt::Unmapped(mask));
+                        }
+                        for variant in variants.iter().filter(|v| v.row < rows) {
+                            let cell = report.cells.entry((column, variant.row)).or_default();
+                            if !cell.fallback_for.contains(&mask) {
+                                cell.fallback_for.push(mask);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        report.dead = (0..columns)
+            .flat_map(|column| (0..rows).map(move |row| (column, row)))
+            .filter(|cell| report.cells.get(cell).is_none_or(|c| c.mapped.is_empty()))
+            .collect();
+        report
+    }
+
+    pub fn is_dead(&self, column: u32, row: u32) -> bool {
+        self.dead.binary_search(&(column, row)).is_ok()
+    }
+}
+
+/// Toggles the atlas viewer on F9 while cheats are enabled.
+pub fn toggle_atlas_viewer(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cheats: Res<CheatsEnabled>,
+    mut state: ResMut<AtlasViewerState>,
+) {
+    if keyboard.just_pressed(KeyCode::F9) && cheats.0 {
+        state.visible = !state.visible;
+    }
+}
+
+/// Draws the atlas viewer window.
+pub fn draw_atlas_viewer(
+    mut contexts: EguiContexts,
+    mut state: ResMut<AtlasViewerState>,
+    atlas: Option<Res<TileAtlas>>,
+    autotiles: Option<Res<AutotileRegistry>>,
+) -> Result {
+    if !state.visible {
+        return Ok(());
+    }
+    let (Some(atlas), Some(autotiles)) = (atlas, autotiles) else {
+        return Ok(());
+    };
+    let texture = contexts.add_image(EguiTextureHandle::Weak(atlas.image.id()));
+    let ctx = contexts.ctx_mut()?;
+
+    let params = &atlas.params;
+    let columns = params.atlas_width / params.tile_size.max(1);
+    let report = AtlasReport::build(&autotiles, columns, params.rows);
+    let mut labels = vec![""; columns as usize];
+    for (name, &column) in &atlas.column_map {
+        if let Some(label) = labels.get_mut(column as usize) {
+            *label = name;
+        }
+    }
+    let missing_count: usize = report.missing.values().map(Vec::len).sum();
+
+    let mut open = true;
+    egui::Window::new("Tile Atlas")
+        .open(&mut open)
+        .default_size([560.0, 640.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Zoom");
+                ui.add(egui::Slider::new(&mut state.zoom, 1.0..=6.0).step_by(1.0));
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("{} dead cells", report.dead.len()),
+                );
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("{missing_count} masks without art"),
+                );
+            });
+            ui.separator();
+
+            ui.horizontal_top(|ui| {
+                egui::ScrollArea::both()
+                    .id_salt("atlas_scroll")
+                    .max_width(ui.available_width() * 0.6)
+                    .show(ui, |ui| {
+                        let cell = params.tile_size as f32 * state.zoom;
+                        let size = egui::vec2(
+                            columns as f32 * cell,
+                            LABEL_HEIGHT + params.rows as f32 * cell,
+                        );
+                        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+                        let image_rect = egui::Rect::from_min_max(
+                            rect.min + egui::vec2(0.0, LABEL_HEIGHT),
+                            rect.max,
+                        );
+                        let cell_rect = |column: u32, row: u32| {
+                            egui::Rect::from_min_size(
+                                image_rect.min + egui::vec2(column as f32, row as f32) * cell,
+                                egui::vec2(cell, cell),
+                            )
+                        };
+
+                        let painter = ui.painter_at(rect);
+                        painter.image(
+                            texture,
+                            image_rect,
+                            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                            egui::Color32::WHITE,
+                        );
+                        for &(column, row) in &report.dead {
+                            painter.rect_filled(
+                                cell_rect(column, row),
+                                0.0,
+                                egui::Color32::from_rgba_unmultiplied(200, 40, 40, 110),
+                            );
+                        }
+                        let grid = egui::Stroke::new(1.0, egui::Color32::from_gray(70));
+                        for column in 0..=columns {
+                            let x = image_rect.left() + column as f32 * cell;
+                            painter.line_segment(
+                                [
+                                    egui::pos2(x, image_rect.top()),
+                                    egui::pos2(x, image_rect.bottom()),
+                                ],
+                                grid,
+                            );
+                        }
+                        for row in 0..=params.rows {
+                            let y = image_rect.top() + row as f32 * cell;
+                            painter.line_segment(
+                                [
+                                    egui::pos2(image_rect.left(), y),
+                                    egui::pos2(image_rect.right(), y),
+                                ],
+                                grid,
+                            );
+                        }
+                        for (column, label) in labels.iter().enumerate() {
+                            painter.text(
+                                egui::pos2(
+                                    rect.left() + (column as f32 + 0.5) * cell,
+                                    rect.top() + LABEL_HEIGHT / 2.0,
+                                ),
+                                egui::Align2::CENTER_CENTER,
+                                label,
+                                egui::FontId::proportional(10.0),
+                                egui::Color32::LIGHT_GRAY,
+                            );
+                        }
+                        if let Some((column, row)) = state.selected {
+                            painter.rect_stroke(
+                                cell_rect(column, row),
+                                0.0,
+                                egui::Stroke::new(2.0, egui::Color32::YELLOW),
+                                egui::StrokeKind::Inside,
+                            );
+                        }
+
+                        if response.clicked()
+                            && let Some(pos) = response.interact_pointer_pos()
+                            && image_rect.contains(pos)
+                        {
+                            let offset = (pos - image_rect.min) / cell;
+                            let picked = (offset.x as u32, offset.y as u32);
+                            if picked.0 < columns && picked.1 < params.rows {
+                                state.selected = Some(picked);
+                            }
+                        }
+                    });
+
+                ui.vertical(|ui| {
+                    draw_cell_details(ui, &report, &labels, state.selected);
+                    ui.separator();
+                    draw_missing_art(ui, &report, &labels);
+                });
+            });
+        });
+    if !open {
+        state.visible = false;
+    }
+    Ok(())
+}
+
+/// Masks that draw the selected cell.
+fn draw_cell_details(
+    ui: &mut egui::Ui,
+    report: &AtlasReport,
+    labels: &[&str],
+    selected: Option<(u32, u32)>,
+) {
+    let Some((column, row)) = selected else {
+        ui.label("Click a cell to see which bitmasks draw it.");
+        return;
+    };
+    let name = labels.get(column as usize).copied().unwrap_or("");
+    ui.label(egui::RichText::new(format!("{name} — column {column}, row {row}")).strong());
+    if report.is_dead(column, row) {
+        ui.colored_label(egui::Color32::LIGHT_RED, "No bitmask maps this cell.");
+    }
+    let Some(cell) = report.cells.get(&(column, row)) else {
+        return;
+    };
+    for usage in &cell.mapped {
+        ui.monospace(format!(
+            "{:3} ({:08b})  weight {:.2}",
+            usage.mask, usage.mask, usage.weight
+        ));
+    }
+    if !cell.fallback_for.is_empty() {
+        ui.label("Fallback for:");
+        for mask in &cell.fallback_for {
+            ui.monospace(format!("{mask:3} ({mask:08b})"));
+        }
+    }
+}
+
+/// Masks without art, per column.
+fn draw_missing_art(ui: &mut egui::Ui, report: &AtlasReport, labels: &[&str]) {
+    if report.missing.is_empty() {
+        ui.label("Every Blob47 mask has art.");
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .id_salt("missing_art_scroll")
+        .show(ui, |ui| {
+            for (&column, missing) in &report.missing {
+                let name = labels.get(column as usize).copied().unwrap_or("");
+                egui::CollapsingHeader::new(format!("{name}: {} masks", missing.len()))
+                    .id_salt(("missing_art", column))
+                    .show(ui, |ui| {
+                        for art in missing {
+                            match *art {
+                                MissingArt::Unmapped(mask) => {
+                                    ui.monospace(format!("{mask:3} ({mask:08b})  fallback"));
+                                }
+                                MissingArt::OutOfAtlas { mask, row } => {
+                                    ui.colored_label(
+                                        egui::Color32::LIGHT_RED,
+                                        format!("{mask:3} ({mask:08b})  row {row} past atlas"),
+                                    );
+                                }
+                            }
+                        }
+                    });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::assets::{
+        AutotileAsset, AutotileFallback, BitmaskMapping, SpriteVariant, TileAnimation,
+    };
+    use crate::world::autotile::AutotileEntry;
+
+    fn variant(row: u32, weight: f32) -> SpriteVariant {
+        SpriteVariant {
+            row,
+            weight,
+            col: 0,
+            index: 0,
+        }
+    }
+
+    fn asset(tiles: &[(u8, Vec<SpriteVariant>)]) -> AutotileAsset {
+        AutotileAsset {
+            tile_size: 16,
+            atlas_columns: 1,
+            atlas_rows: 4,
+            tiles: tiles
+                .iter()
+                .map(|(mask, variants)| {
+                    let mapping = BitmaskMapping {
+                        description: String::new(),
+                        variants: variants.clone(),
+                    };
+                    (*mask, mapping)
+                })
+                .collect(),
+            fallback: AutotileFallback::Full,
+            uv_inset: 0.5,
+            orientations: HashMap::new(),
+            animation: None,
+        }
+    }
+
+    fn registry(assets: &[(&str, AutotileAsset)]) -> AutotileRegistry {
+        let mut registry = AutotileRegistry::default();
+        for (column, (name, asset)) in assets.iter().enumerate() {
+            registry.insert(
+                name.to_string(),
+                AutotileEntry::from_asset(asset, column as u32),
+            );
+        }
+        registry
+    }
+
+    #[test]
+    fn cells_list_the_masks_that_draw_them() {
+        let registry = registry(&[
+            (
+                "dirt",
+                asset(&[
+                    (0, vec![variant(0, 1.0)]),
+                    (255, vec![variant(1, 3.0), variant(2, 1.0)]),
+                    (17, vec![variant(1, 1.0)]),
+                ]),
+            ),
+            ("stone", asset(&[(0, vec![variant(0, 1.0)])])),
+        ]);
+        let report = AtlasReport::build(&registry, 2, 4);
+
+        let full = &report.cells[&(0, 1)];
+        let mut masks: Vec<u8> = full.mapped.iter().map(|u| u.mask).collect();
+        masks.sort();
+        assert_eq!(masks, vec![17, 255]);
+        assert!(full.mapped.contains(&CellUse {
+            mask: 255,
+            weight: 3.0
+        }));
+        // Unmapped Blob47 masks borrow the `Full` fallback's variants.
+        assert!(full.fallback_for.contains(&1));
+        assert!(report.cells[&(0, 2)].fallback_for.contains(&1));
+        // Impossible masks are never drawn, so they borrow nothing.
+        assert!(!full.fallback_for.contains(&2));
+        assert_eq!(report.cells[&(1, 0)].mapped[0].mask, 0);
+    }
+
+    #[test]
+    fn classifies_dead_cells_and_missing_masks() {
+        let registry = registry(&[(
+            "dirt",
+            asset(&[(0, vec![variant(0, 1.0)]), (255, vec![variant(9, 1.0)])]),
+        )]);
+        let report = AtlasReport::build(&registry, 2, 4);
+
+        // Row 0 is mapped; rows 1–3 and the column without an autotile are
+        // dead.
+        assert!(!report.is_dead(0, 0));
+        assert_eq!(
+            report.dead,
+            vec![(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2), (1, 3)]
+        );
+
+        let missing = &report.missing[&0];
+        assert!(missing.contains(&MissingArt::OutOfAtlas { mask: 255, row: 9 }));
+        let unmapped = missing
+            .iter()
+            .filter(|m| matches!(m, MissingArt::Unmapped(_)))
+            .count();
+        // All 47 Blob47 masks but the two that are mapped.
+        assert_eq!(unmapped, 45);
+        assert!(!missing.contains(&MissingArt::Unmapped(2)));
+        assert!(!report.missing.contains_key(&1));
+    }
+
+    #[test]
+    fn animation_frames_are_live_columns() {
+        let mut water = asset(&[(0, vec![variant(0, 1.0)])]);
+        water.animation = Some(TileAnimation {
+            frames: 3,
+            frame_secs: 0.2,
+        });
+        let registry = registry(&[("water", water)]);
+        let report = AtlasReport::build(&registry, 3, 4);
+
+        for column in 0..3 {
+            assert!(!report.is_dead(column, 0), "frame column {column}");
+        }
+        // Missing art is listed once, not per frame.
+        assert_eq!(report.missing.keys().copied().collect::<Vec<_>>(), vec![0]);
+    }
+}
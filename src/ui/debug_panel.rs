@@ -3,16 +3,25 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_egui::{egui, EguiContexts};
 
+use crate::camera::aspect::AspectLock;
+use crate::camera::color_grading::ColorGrading;
+use crate::interaction::block_action::DirectDrops;
+use crate::interaction::smart_torch::SmartTorch;
+use crate::item::DroppedItem;
 use crate::parallax::transition::CurrentBiome;
+use crate::physics::Sleeping;
 use crate::player::{Grounded, Player, Velocity};
-use crate::registry::biome::BiomeRegistry;
 use crate::registry::tile::TileId;
-use crate::registry::tile::TileRegistry;
-use crate::registry::world::ActiveWorld;
 use crate::registry::BiomeParallaxConfigs;
+use crate::tutorial::TutorialSettings;
 use crate::world::chunk::{tile_to_chunk, tile_to_local, world_to_tile, LoadedChunks, WorldMap};
+use crate::world::chunk_reveal::LightReveal;
+use crate::world::ctx::WorldCtx;
 use crate::world::day_night::WorldTime;
-use crate::world::rc_lighting::RcLightingConfig;
+use crate::world::exploration::{FogMode, FogOfWar};
+use crate::world::mesh_builder::{AmbientOcclusion, MeshDiagnostics};
+use crate::world::rc_lighting::{RcLightingConfig, RcResolutionScale};
+use crate::world::shader_reload::ShaderErrors;
 
 /// Tracks debug panel visibility.
 #[derive(Resource, Default)]
@@ -28,29 +37,42 @@ pub fn toggle_debug_panel(keyboard: Res<ButtonInput<KeyCode>>, mut state: ResMut
 }
 
 /// Draws the debug inspector panel using egui.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn draw_debug_panel(
     mut contexts: EguiContexts,
     state: Res<DebugUiState>,
     // Player
-    player_query: Query<(&Transform, &Velocity, &Grounded), With<Player>>,
+    player: (
+        Query<(&Transform, &Velocity, &Grounded), With<Player>>,
+        Option<ResMut<SmartTorch>>,
+        Option<ResMut<TutorialSettings>>,
+        Option<ResMut<DirectDrops>>,
+    ),
     // Cursor
     windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
     // World
-    world_map: Res<WorldMap>,
-    world_config: Res<ActiveWorld>,
-    tile_registry: Res<TileRegistry>,
+    map_view: (Res<WorldMap>, ResMut<FogOfWar>),
+    world: WorldCtx,
     loaded_chunks: Res<LoadedChunks>,
     // Performance
     diagnostics: Res<DiagnosticsStore>,
     entities: Query<Entity>,
+    mesh_diagnostics: Query<&MeshDiagnostics>,
+    dropped_items: Query<Has<Sleeping>, With<DroppedItem>>,
     // Lighting
-    mut rc_config: ResMut<RcLightingConfig>,
+    lighting: (
+        ResMut<RcLightingConfig>,
+        Option<ResMut<RcResolutionScale>>,
+        Option<ResMut<AspectLock>>,
+        Option<ResMut<ColorGrading>>,
+        Option<ResMut<LightReveal>>,
+        Option<ResMut<AmbientOcclusion>>,
+        Option<Res<ShaderErrors>>,
+    ),
     // Day/Night
     mut world_time: Option<ResMut<WorldTime>>,
     // Parallax
-    biome_registry: Res<BiomeRegistry>,
     biome_parallax: Option<Res<BiomeParallaxConfigs>>,
     current_biome: Option<Res<CurrentBiome>>,
 ) -> Result {
@@ -58,7 +80,25 @@ pub fn draw_debug_panel(
         return Ok(());
     }
 
+    let (player_query, mut smart_torch, mut tutorial, mut direct_drops) = player;
+    let (world_map, mut fog) = map_view;
+    let (
+        mut rc_config,
+        mut resolution,
+        mut aspect_lock,
+        mut color_grading,
+        mut light_reveal,
+        mut occlusion,
+        shader_errors,
+    ) = lighting;
+    let shader_errors = shader_errors
+        .map(|errors| errors.snapshot())
+        .unwrap_or_default();
     let ctx = contexts.ctx_mut()?;
+    let world_info = world.as_ref();
+    let world_config = world_info.config;
+    let tile_registry = world_info.tile_registry;
+    let biome_registry = world_info.biome_registry;
 
     let panel_frame = egui::Frame::NONE
         .fill(egui::Color32::from_rgba_unmultiplied(20, 20, 30, 200))
@@ -73,6 +113,15 @@ pub fn draw_debug_panel(
             ui.heading("Debug Panel");
             ui.separator();
 
+            // --- Shader errors (hot reload keeps the last good pipeline) ---
+            if !shader_errors.is_empty() {
+                for (shader, error) in &shader_errors {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("{shader} failed:"));
+                    ui.monospace(error);
+                }
+                ui.separator();
+            }
+
             // --- Performance ---
             egui::CollapsingHeader::new(egui::RichText::new("Performance").strong())
                 .default_open(true)
@@ -102,6 +151,30 @@ pub fn draw_debug_panel(
                             ui.label("Entities:");
                             ui.label(format!("{}", entities.iter().count()));
                             ui.end_row();
+
+                            // Tiles drawn with placeholder sprites, and how
+                            // many chunk layers they are spread over.
+                            let (fallbacks, layers) = mesh_diagnostics
+                                .iter()
+                                .map(MeshDiagnostics::total)
+                                .filter(|&n| n > 0)
+                                .fold((0, 0), |(total, layers), n| (total + n, layers + 1));
+                            ui.label("Mesh fallbacks:");
+                            if fallbacks == 0 {
+                                ui.label("0");
+                            } else {
+                                ui.colored_label(
+                                    egui::Color32::YELLOW,
+                                    format!("{fallbacks} tiles / {layers} layers"),
+                                );
+                            }
+                            ui.end_row();
+
+                            let asleep = dropped_items.iter().filter(|&s| s).count();
+                            let awake = dropped_items.iter().len() - asleep;
+                            ui.label("Dropped items:");
+                            ui.label(format!("{awake} awake / {asleep} asleep"));
+                            ui.end_row();
                         });
                 });
 
@@ -114,6 +187,16 @@ pub fn draw_debug_panel(
                         let py = transform.translation.y;
                         let (tx, ty) = world_to_tile(px, py, world_config.tile_size);
                         let (cx, cy) = tile_to_chunk(tx, ty, world_config.chunk_size);
+                        let biome = biome_registry.name_of(world_info.biome_at_tile(tx, ty));
+                        let layer = world_info.layer_at(ty);
+                        let depth = world_info.depth_fraction(ty);
+                        let difficulty = world_info.difficulty_at(tx, ty);
+                        let data_cx = world_config.wrap_chunk_x(cx);
+                        let chunk_biome = biome_registry.name_of(
+                            world_map
+                                .chunk_info(data_cx, cy, &world_info)
+                                .dominant_biome,
+                        );
 
                         egui::Grid::new("player_grid")
                             .num_columns(2)
@@ -138,10 +221,40 @@ pub fn draw_debug_panel(
                                 ui.label("Chunk:");
                                 ui.monospace(format!("{cx}, {cy}"));
                                 ui.end_row();
+
+                                ui.label("Biome:");
+                                ui.label(biome);
+                                ui.end_row();
+
+                                ui.label("Chunk biome:");
+                                ui.label(chunk_biome);
+                                ui.end_row();
+
+                                ui.label("Layer:");
+                                ui.label(format!("{layer:?}"));
+                                ui.end_row();
+
+                                ui.label("Depth:");
+                                ui.monospace(format!("{:.0}%", depth * 100.0));
+                                ui.end_row();
+
+                                ui.label("Difficulty:");
+                                ui.monospace(format!("{difficulty:.2}"));
+                                ui.end_row();
                             });
                     } else {
                         ui.label("No player entity");
                     }
+                    if let Some(smart_torch) = smart_torch.as_mut() {
+                        ui.checkbox(&mut smart_torch.enabled, "Smart torches");
+                    }
+                    if let Some(tutorial) = tutorial.as_mut() {
+                        ui.checkbox(&mut tutorial.enabled, "Tutorial objectives");
+                    }
+                    if let Some(direct_drops) = direct_drops.as_mut() {
+                        ui.checkbox(&mut direct_drops.survival, "Drops to inventory (survival)");
+                        ui.checkbox(&mut direct_drops.creative, "Drops to inventory (creative)");
+                    }
                 });
 
             // --- Cursor ---
@@ -260,7 +373,28 @@ pub fn draw_debug_panel(
                             ui.label("Loaded chunks:");
                             ui.label(format!("{}", loaded_chunks.map.len()));
                             ui.end_row();
+
+                            ui.label("Explored tiles:");
+                            ui.label(format!(
+                                "{}",
+                                world_map.explored.values().map(|e| e.count()).sum::<u32>()
+                            ));
+                            ui.end_row();
                         });
+
+                    ui.separator();
+                    ui.label("Fog of war:");
+                    ui.horizontal(|ui| {
+                        for (mode, label) in [
+                            (FogMode::Off, "Off"),
+                            (FogMode::Darken, "Darken"),
+                            (FogMode::Hide, "Hide"),
+                        ] {
+                            ui.selectable_value(&mut fog.world, mode, label);
+                        }
+                    });
+                    ui.label("Reveal radius:");
+                    ui.add(egui::Slider::new(&mut fog.reveal_radius, 1..=64).suffix(" tiles"));
                 });
 
             // --- Lighting (RC) ---
@@ -280,7 +414,7 @@ pub fn draw_debug_panel(
 
                             ui.label("Viewport:");
                             ui.monospace(format!(
-                                "{} × {} tiles",
+                                "{} × {} texels",
                                 rc_config.viewport_size.x, rc_config.viewport_size.y
                             ));
                             ui.end_row();
@@ -291,7 +425,7 @@ pub fn draw_debug_panel(
 
                             ui.label("Lightmap:");
                             ui.monospace(format!(
-                                "{} × {} tiles",
+                                "{} × {} texels",
                                 rc_config.lightmap_size.x, rc_config.lightmap_size.y
                             ));
                             ui.end_row();
@@ -306,8 +440,94 @@ pub fn draw_debug_panel(
                     ui.add(
                         egui::Slider::new(&mut rc_config.bounce_damping, 0.0..=1.0).step_by(0.05),
                     );
+                    if let Some(ref mut resolution) = resolution {
+                        ui.label("Texels per tile:");
+                        ui.horizontal(|ui| {
+                            for scale in [0.5, 1.0, 2.0] {
+                                ui.selectable_value(&mut resolution.0, scale, format!("{scale}"));
+                            }
+                        });
+                    }
+                    if let Some(ref mut light_reveal) = light_reveal {
+                        ui.label("New chunks:");
+                        ui.horizontal(|ui| {
+                            let progressive = LightReveal::Progressive {
+                                frames: LightReveal::DEFAULT_FRAMES,
+                            };
+                            for (mode, label) in
+                                [(LightReveal::Instant, "Instant"), (progressive, "Fade in")]
+                            {
+                                ui.selectable_value(&mut **light_reveal, mode, label);
+                            }
+                        });
+                    }
+                    if let Some(ref mut occlusion) = occlusion {
+                        // Edit a copy: every change rebuilds all chunk meshes.
+                        let mut strength = occlusion.strength;
+                        ui.label("Ambient occlusion:");
+                        ui.add(egui::Slider::new(&mut strength, 0.0..=1.0).step_by(0.05));
+                        if strength != occlusion.strength {
+                            occlusion.strength = strength;
+                        }
+                    }
+                    if let Some(ref mut aspect_lock) = aspect_lock {
+                        ui.label("Aspect lock:");
+                        ui.horizontal(|ui| {
+                            for (aspect, label) in [
+                                (None, "Off"),
+                                (Some(16.0 / 9.0), "16:9"),
+                                (Some(21.0 / 9.0), "21:9"),
+                                (Some(4.0 / 3.0), "4:3"),
+                            ] {
+                                ui.selectable_value(&mut aspect_lock.0, aspect, label);
+                            }
+                        });
+                    }
                 });
 
+            // --- Color grading ---
+            if let Some(ref mut grading) = color_grading {
+                egui::CollapsingHeader::new(egui::RichText::new("Color grading").strong())
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut grading.enabled, "Enabled");
+                        ui.add_enabled_ui(grading.enabled, |ui| {
+                            ui.label("Exposure:");
+                            ui.add(
+                                egui::Slider::new(&mut grading.exposure, -2.0..=2.0)
+                                    .step_by(0.05)
+                                    .suffix(" stops"),
+                            );
+                            ui.label("Contrast:");
+                            ui.add(
+                                egui::Slider::new(&mut grading.contrast, 0.5..=1.5).step_by(0.05),
+                            );
+                            ui.label("Saturation:");
+                            ui.add(
+                                egui::Slider::new(&mut grading.saturation, 0.0..=2.0).step_by(0.05),
+                            );
+                            ui.label("Vignette (surface / deep):");
+                            ui.add(
+                                egui::Slider::new(&mut grading.vignette_surface, 0.0..=1.0)
+                                    .step_by(0.05),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut grading.vignette_deep, 0.0..=1.0)
+                                    .step_by(0.05),
+                            );
+                            ui.label("Night shift:");
+                            ui.add(
+                                egui::Slider::new(&mut grading.night_shift, 0.0..=1.0)
+                                    .step_by(0.05),
+                            );
+                            ui.monospace(format!(
+                                "Vignette now: {:.2}",
+                                grading.depth_vignette(grading.depth)
+                            ));
+                        });
+                    });
+            }
+
             // --- Day/Night ---
             if let Some(ref mut wt) = world_time {
                 egui::CollapsingHeader::new(egui::RichText::new("Day/Night").strong())
@@ -0,0 +1,126 @@
+//! Window-size handling for the game UI: shrinking the UI to fit small
+//! windows and the Alt+Enter fullscreen toggle.
+//!
+//! The hotbar and inventory screen are laid out in fixed pixel sizes from the
+//! theme. When the window is smaller than that layout, [`fit_ui_scale`] lowers
+//! [`UiScale`] so the inventory fits on screen and the hotbar never runs off
+//! the sides. Systems that place UI nodes at the cursor divide by the scale.
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+
+use super::game_ui::theme::UiTheme;
+use super::game_ui::{hotbar, inventory};
+
+/// Space kept free between the nominal UI and the window edge (logical px).
+const EDGE_MARGIN: f32 = 16.0;
+
+/// Largest UI scale (at most 1.0) at which every box in `nominal` fits inside
+/// `window` with [`EDGE_MARGIN`] to spare on each side.
+pub fn fit_scale(window: Vec2, nominal: &[Vec2]) -> f32 {
+    let available = window - 2.0 * EDGE_MARGIN;
+    nominal
+        .iter()
+        .fold(1.0f32, |scale, size| {
+            scale
+                .min(available.x / size.x.max(1.0))
+                .min(available.y / size.y.max(1.0))
+        })
+        .max(f32::EPSILON)
+}
+
+/// Lower [`UiScale`] when the window is too small for the hotbar or the
+/// inventory screen; back to 1.0 once it is large enough again.
+pub fn fit_ui_scale(
+    window: Query<&Window, With<PrimaryWindow>>,
+    theme: Res<UiTheme>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window.single() else {
+        return;
+    };
+    let size = window.size();
+    // Minimized: keep the current scale rather than collapsing the UI.
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return;
+    }
+
+    let hotbar = &theme.hotbar;
+    let nominal = [
+        Vec2::new(
+            hotbar::hotbar_width(hotbar),
+            hotbar.slot_size + hotbar.margin_bottom,
+        ),
+        inventory::inventory_window_size(&theme.inventory_screen),
+    ];
+    let scale = fit_scale(size, &nominal);
+    if (ui_scale.0 - scale).abs() > 1e-4 {
+        ui_scale.0 = scale;
+    }
+}
+
+/// Window mode Alt+Enter switches to from `mode`.
+pub fn toggled_window_mode(mode: WindowMode) -> WindowMode {
+    match mode {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        _ => WindowMode::Windowed,
+    }
+}
+
+/// Toggle borderless fullscreen on Alt+Enter.
+pub fn toggle_fullscreen(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let alt = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    if !alt || !keyboard.just_pressed(KeyCode::Enter) {
+        return;
+    }
+    if let Ok(mut window) = window.single_mut() {
+        window.mode = toggled_window_mode(window.mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_window_keeps_full_scale() {
+        let nominal = [Vec2::new(600.0, 60.0), Vec2::new(500.0, 400.0)];
+        assert_eq!(fit_scale(Vec2::new(1280.0, 720.0), &nominal), 1.0);
+        // Exactly the nominal size plus margins still fits.
+        assert_eq!(fit_scale(Vec2::new(632.0, 432.0), &nominal), 1.0);
+    }
+
+    #[test]
+    fn small_window_scales_to_tightest_axis() {
+        let hotbar = Vec2::new(768.0, 60.0);
+        let inventory = Vec2::new(500.0, 400.0);
+
+        // Width-bound by the hotbar: (640 - 32) / 768.
+        let scale = fit_scale(Vec2::new(640.0, 720.0), &[hotbar, inventory]);
+        assert!((scale - 608.0 / 768.0).abs() < 1e-6);
+        assert!(hotbar.x * scale <= 640.0 - 2.0 * EDGE_MARGIN + 1e-3);
+
+        // Height-bound by the inventory: (332 - 32) / 400.
+        let scale = fit_scale(Vec2::new(1280.0, 332.0), &[hotbar, inventory]);
+        assert!((scale - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn degenerate_window_stays_positive() {
+        let scale = fit_scale(Vec2::new(10.0, 10.0), &[Vec2::new(500.0, 400.0)]);
+        assert!(scale > 0.0);
+    }
+
+    #[test]
+    fn alt_enter_toggles_between_windowed_and_borderless() {
+        let full = toggled_window_mode(WindowMode::Windowed);
+        assert_eq!(
+            full,
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        );
+        assert_eq!(toggled_window_mode(full), WindowMode::Windowed);
+    }
+}
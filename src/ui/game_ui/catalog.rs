@@ -0,0 +1,494 @@
+//! Creative item catalog — every registered item, searchable, shown beside the
+//! inventory in creative mode.
+//!
+//! Spawned/despawned reactively with the inventory screen while
+//! [`GameMode::Creative`] is active. Dragging an entry onto a bag slot creates
+//! a full stack there; dragging it onto the hotbar assigns it like any other
+//! item.
+
+use bevy::ecs::message::MessageReader;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::picking::events::DragStart;
+use bevy::picking::prelude::*;
+use bevy::prelude::*;
+use bevy::ui::widget::ImageNode;
+
+use crate::chat::ChatState;
+use crate::game_mode::GameMode;
+use crate::inventory::InventorySlot;
+use crate::item::{ItemId, ItemRegistry};
+use crate::registry::AppState;
+
+use super::components::{on_slot_hover, on_slot_unhover};
+use super::components::{DragInfo, DragState, InventoryScreenState, SlotType, UiSlot};
+use super::drag_drop::{on_drag_end, spawn_drag_icon};
+use super::icon_registry::ItemIconRegistry;
+use super::theme::UiTheme;
+
+// ── Marker components ──
+
+/// Root entity of the catalog panel.
+#[derive(Component)]
+pub struct CatalogPanelRoot;
+
+/// Clickable search box; focusing it routes typing to the search query.
+#[derive(Component)]
+pub struct CatalogSearchField;
+
+/// Text inside the search box.
+#[derive(Component)]
+pub struct CatalogSearchText;
+
+/// Grid holding one entry per matching item.
+#[derive(Component)]
+pub struct CatalogEntryList;
+
+// ── State ──
+
+/// Search query and the items currently listed.
+#[derive(Resource, Default, Debug)]
+pub struct CatalogState {
+    pub query: String,
+    /// The search box has keyboard focus.
+    pub focused: bool,
+    /// Item ids listed in the panel; indexed by [`SlotType::Catalog`].
+    pub entries: Vec<String>,
+}
+
+// ── Plugin ──
+
+pub struct CatalogUiPlugin;
+
+impl Plugin for CatalogUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CatalogState>().add_systems(
+            Update,
+            (
+                manage_catalog_panel,
+                ApplyDeferred,
+                (
+                    focus_catalog_search,
+                    catalog_search_input,
+                    update_catalog_entries,
+                ),
+            )
+                .chain()
+                // Run after the systems that check `ChatState::keyboard_captured`
+                // so Enter/Escape that end a search don't also open chat or
+                // close the inventory.
+                .after(super::chat::chat_input_system)
+                .after(super::window::close_topmost_on_esc)
+                .after(super::toggle_inventory)
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+// ── Constants ──
+
+const PANEL_WIDTH: f32 = 232.0;
+const PANEL_HEIGHT: f32 = 360.0;
+const PANEL_PADDING: f32 = 8.0;
+const ENTRY_SIZE: f32 = 36.0;
+const ENTRY_GAP: f32 = 4.0;
+const ENTRY_COLUMNS: usize = 5;
+
+// ── Helpers ──
+
+/// Ids of items whose id or display name contains `query` (case-insensitive),
+/// in registry order. An empty query matches everything.
+pub fn catalog_matches(registry: &ItemRegistry, query: &str) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    (0..registry.len())
+        .map(|i| registry.get(ItemId(i as u16)))
+        .filter(|def| {
+            query.is_empty()
+                || def.id.to_lowercase().contains(&query)
+                || def.display_name.to_lowercase().contains(&query)
+        })
+        .map(|def| def.id.clone())
+        .collect()
+}
+
+/// Drop `count` of `item_id` from the catalog into a bag slot: an empty slot
+/// gets a new stack, a stack of the same item is topped up to `count`, and a
+/// slot holding another item is left alone.
+pub fn fill_from_catalog(slot: &mut Option<InventorySlot>, item_id: &str, count: u16) {
+    match slot {
+        None => {
+            *slot = Some(InventorySlot {
+                item_id: item_id.to_string(),
+                count,
+                durability: None,
+            });
+        }
+        Some(stack) if stack.item_id == item_id => {
+            stack.count = stack.count.max(count);
+        }
+        Some(_) => {}
+    }
+}
+
+// ── Systems ──
+
+/// Show the catalog alongside the inventory screen in creative mode.
+fn manage_catalog_panel(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    inv_state: Res<InventoryScreenState>,
+    panel_query: Query<Entity, With<CatalogPanelRoot>>,
+    theme: Res<UiTheme>,
+    mut catalog: ResMut<CatalogState>,
+    mut chat_state: Option<ResMut<ChatState>>,
+) {
+    let should_be_open = game_mode.is_creative() && inv_state.visible;
+    let panel_exists = !panel_query.is_empty();
+
+    if should_be_open && !panel_exists {
+        spawn_catalog_panel(&mut commands, &theme);
+    } else if !should_be_open && panel_exists {
+        for entity in &panel_query {
+            commands.entity(entity).despawn();
+        }
+        catalog.focused = false;
+        catalog.entries.clear();
+        if let Some(chat) = chat_state.as_mut() {
+            chat.text_field_focused = false;
+        }
+    }
+}
+
+/// Focus the search box when it is clicked; any other click unfocuses it.
+fn focus_catalog_search(
+    mouse: Res<ButtonInput<MouseButton>>,
+    field_query: Query<&Interaction, With<CatalogSearchField>>,
+    mut catalog: ResMut<CatalogState>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let on_field = field_query.iter().any(|i| *i == Interaction::Pressed);
+    if catalog.focused != on_field {
+        catalog.focused = on_field;
+    }
+}
+
+/// Type into the focused search box. Enter or Escape ends the search.
+fn catalog_search_input(
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    mut catalog: ResMut<CatalogState>,
+    mut chat_state: Option<ResMut<ChatState>>,
+    mut text_query: Query<&mut Text, With<CatalogSearchText>>,
+) {
+    for event in keyboard_events.read() {
+        if !catalog.focused || event.state != ButtonState::Pressed {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::Enter | KeyCode::Escape => catalog.focused = false,
+            KeyCode::Backspace => {
+                catalog.query.pop();
+            }
+            KeyCode::Space => catalog.query.push(' '),
+            _ => {
+                if let Key::Character(ref ch) = event.logical_key {
+                    catalog.query.push_str(ch.as_str());
+                }
+            }
+        }
+    }
+
+    if !catalog.is_changed() {
+        return;
+    }
+    if let Some(chat) = chat_state.as_mut()
+        && chat.text_field_focused != catalog.focused
+    {
+        chat.text_field_focused = catalog.focused;
+    }
+    let label = if catalog.focused {
+        format!("{}_", catalog.query)
+    } else if catalog.query.is_empty() {
+        "Search...".to_string()
+    } else {
+        catalog.query.clone()
+    };
+    for mut text in &mut text_query {
+        **text = label.clone();
+    }
+}
+
+/// Rebuild the entry grid when the panel opens or the query changes.
+fn update_catalog_entries(
+    mut commands: Commands,
+    mut catalog: ResMut<CatalogState>,
+    mut shown_query: Local<String>,
+    list_query: Query<(Entity, Option<&Children>, Ref<CatalogEntryList>)>,
+    item_registry: Res<ItemRegistry>,
+    icon_registry: Res<ItemIconRegistry>,
+    theme: Res<UiTheme>,
+) {
+    let Ok((list_entity, children, list)) = list_query.single() else {
+        return;
+    };
+    if !list.is_added() && *shown_query == catalog.query {
+        return;
+    }
+    *shown_query = catalog.query.clone();
+
+    let colors = &theme.colors;
+    let bg_medium = Color::from(colors.bg_medium.clone());
+    let border_color = Color::from(colors.border.clone());
+    let text_color = Color::from(colors.text.clone());
+
+    if let Some(children) = children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    catalog.entries = catalog_matches(&item_registry, &catalog.query);
+
+    commands.entity(list_entity).with_children(|grid| {
+        for (idx, item_id) in catalog.entries.iter().enumerate() {
+            let icon = item_registry
+                .by_name(item_id)
+                .and_then(|id| icon_registry.get(id))
+                .cloned();
+            grid.spawn((
+                UiSlot {
+                    slot_type: SlotType::Catalog(idx),
+                },
+                Node {
+                    width: Val::Px(ENTRY_SIZE),
+                    height: Val::Px(ENTRY_SIZE),
+                    border: UiRect::all(Val::Px(1.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                BackgroundColor(bg_medium),
+                BorderColor::all(border_color),
+                Pickable {
+                    should_block_lower: false,
+                    is_hoverable: true,
+                },
+            ))
+            .with_children(|entry| {
+                if let Some(icon) = icon {
+                    entry.spawn((
+                        ImageNode::new(icon),
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    ));
+                } else {
+                    entry.spawn((
+                        Text::new(item_id.chars().take(4).collect::<String>()),
+                        TextFont {
+                            font_size: 9.0,
+                            ..default()
+                        },
+                        TextColor(text_color),
+                        Pickable::IGNORE,
+                    ));
+                }
+            })
+            .observe(on_slot_hover)
+            .observe(on_slot_unhover)
+            .observe(on_catalog_drag_start)
+            .observe(on_drag_end);
+        }
+    });
+}
+
+/// Observer: start dragging a full stack of the catalog entry's item.
+fn on_catalog_drag_start(
+    trigger: On<Pointer<DragStart>>,
+    mut drag_state: ResMut<DragState>,
+    slot_query: Query<&UiSlot>,
+    catalog: Res<CatalogState>,
+    item_registry: Res<ItemRegistry>,
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+) {
+    let Ok(slot) = slot_query.get(trigger.event_target()) else {
+        return;
+    };
+    let SlotType::Catalog(idx) = slot.slot_type else {
+        return;
+    };
+    let Some(item_id) = catalog.entries.get(idx) else {
+        return;
+    };
+    let Some(id) = item_registry.by_name(item_id) else {
+        return;
+    };
+    let count = item_registry.max_stack(id).max(1);
+
+    let drag_icon = spawn_drag_icon(&mut commands, item_id, count, &theme);
+    drag_state.dragging = Some(DragInfo {
+        item_id: item_id.clone(),
+        count,
+        source_slot: slot.slot_type,
+        drag_icon,
+    });
+}
+
+// ── Spawn helpers ──
+
+/// Spawn the catalog panel anchored to the right edge of the screen.
+fn spawn_catalog_panel(commands: &mut Commands, theme: &UiTheme) {
+    let colors = &theme.colors;
+    let bg_dark = Color::from(colors.bg_dark.clone());
+    let bg_medium = Color::from(colors.bg_medium.clone());
+    let border_color = Color::from(colors.border.clone());
+    let text_color = Color::from(colors.text.clone());
+    let text_dim = Color::from(colors.text_dim.clone());
+
+    commands
+        .spawn((
+            CatalogPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                top: Val::Percent(50.0),
+                margin: UiRect::top(Val::Px(-PANEL_HEIGHT / 2.0)),
+                width: Val::Px(PANEL_WIDTH),
+                height: Val::Px(PANEL_HEIGHT),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                border: UiRect::all(Val::Px(2.0)),
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(bg_dark),
+            BorderColor::all(border_color),
+            Pickable {
+                should_block_lower: true,
+                is_hoverable: true,
+            },
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("Catalog"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(text_color),
+                Pickable::IGNORE,
+            ));
+
+            panel
+                .spawn((
+                    CatalogSearchField,
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(22.0),
+                        padding: UiRect::horizontal(Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(bg_medium),
+                    BorderColor::all(border_color),
+                ))
+                .with_children(|field| {
+                    field.spawn((
+                        CatalogSearchText,
+                        Text::new("Search..."),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(text_dim),
+                        Pickable::IGNORE,
+                    ));
+                });
+
+            panel.spawn((
+                CatalogEntryList,
+                Node {
+                    display: Display::Grid,
+                    grid_template_columns: vec![GridTrack::px(ENTRY_SIZE); ENTRY_COLUMNS],
+                    grid_auto_rows: vec![GridTrack::px(ENTRY_SIZE)],
+                    column_gap: Val::Px(ENTRY_GAP),
+                    row_gap: Val::Px(ENTRY_GAP),
+                    flex_grow: 1.0,
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{ItemDef, ItemType};
+    use crate::test_helpers::fixtures;
+
+    fn item(id: &str, display_name: &str) -> ItemDef {
+        ItemDef {
+            display_name: display_name.into(),
+            max_stack: 999,
+            ..fixtures::item_def(id, ItemType::Block)
+        }
+    }
+
+    #[test]
+    fn search_matches_id_or_name_case_insensitively() {
+        let registry = ItemRegistry::from_defs(vec![
+            item("dirt", "Dirt"),
+            item("stone", "Stone"),
+            item("torch", "Wall Torch"),
+            item("iron_ore", "Iron Ore"),
+        ]);
+
+        assert_eq!(catalog_matches(&registry, "").len(), 4);
+        assert_eq!(catalog_matches(&registry, "WALL"), vec!["torch"]);
+        assert_eq!(catalog_matches(&registry, "ore"), vec!["iron_ore"]);
+        assert_eq!(
+            catalog_matches(&registry, " t "),
+            vec!["dirt", "stone", "torch"]
+        );
+        assert!(catalog_matches(&registry, "diamond").is_empty());
+    }
+
+    #[test]
+    fn catalog_drop_fills_empty_or_matching_slots_only() {
+        let mut empty = None;
+        fill_from_catalog(&mut empty, "dirt", 999);
+        assert_eq!(
+            empty.as_ref().map(|s| (s.item_id.as_str(), s.count)),
+            Some(("dirt", 999))
+        );
+
+        let mut partial = Some(InventorySlot {
+            item_id: "dirt".into(),
+            count: 12,
+            durability: None,
+        });
+        fill_from_catalog(&mut partial, "dirt", 999);
+        assert_eq!(partial.as_ref().unwrap().count, 999);
+
+        let mut other = Some(InventorySlot {
+            item_id: "stone".into(),
+            count: 5,
+            durability: None,
+        });
+        fill_from_catalog(&mut other, "dirt", 999);
+        assert_eq!(
+            other.as_ref().map(|s| (s.item_id.as_str(), s.count)),
+            Some(("stone", 5))
+        );
+    }
+}
@@ -127,7 +127,7 @@ pub fn chat_input_system(
 
         if !chat_state.is_active {
             // Enter opens chat
-            if event.key_code == KeyCode::Enter {
+            if event.key_code == KeyCode::Enter && !chat_state.text_field_focused {
                 chat_state.is_active = true;
 
                 for (mut _text, mut vis, mut bg) in &mut input_query {
@@ -1,12 +1,9 @@
 use bevy::picking::prelude::*;
 use bevy::prelude::*;
 
-/// Which hand in a hotbar slot.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Hand {
-    Left,
-    Right,
-}
+use crate::inventory::BagSlot;
+pub use crate::inventory::Hand;
+use crate::item::EquipmentSlot;
 
 /// Equipment slot type.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -21,17 +18,46 @@ pub enum EquipSlot {
     BackCosmetic,
 }
 
+impl EquipSlot {
+    /// The [`Equipment`](crate::inventory::Equipment) slot this UI slot shows.
+    pub fn equipment_slot(self) -> EquipmentSlot {
+        match self {
+            EquipSlot::Head => EquipmentSlot::Head,
+            EquipSlot::Chest => EquipmentSlot::Chest,
+            EquipSlot::Legs => EquipmentSlot::Legs,
+            EquipSlot::Back => EquipmentSlot::Back,
+            EquipSlot::HeadCosmetic => EquipmentSlot::CosmeticHead,
+            EquipSlot::ChestCosmetic => EquipmentSlot::CosmeticChest,
+            EquipSlot::LegsCosmetic => EquipmentSlot::CosmeticLegs,
+            EquipSlot::BackCosmetic => EquipmentSlot::CosmeticBack,
+        }
+    }
+}
+
 /// Type of UI slot — maps to inventory positions.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SlotType {
     /// Hotbar slot with hand designation (index 0-5, hand L/R)
     Hotbar { index: usize, hand: Hand },
-    /// Main inventory bag (index 0-39)
+    /// Main inventory bag (index 0-39; only the unlocked rows take items)
     MainBag(usize),
     /// Material bag (index 0-15)
     MaterialBag(usize),
     /// Equipment slot
     Equipment(EquipSlot),
+    /// Creative catalog entry (index into `CatalogState::entries`)
+    Catalog(usize),
+}
+
+impl SlotType {
+    /// The inventory slot this UI slot shows, for bag slots.
+    pub fn bag_slot(self) -> Option<BagSlot> {
+        match self {
+            SlotType::MainBag(idx) => Some(BagSlot::Main(idx)),
+            SlotType::MaterialBag(idx) => Some(BagSlot::Material(idx)),
+            _ => None,
+        }
+    }
 }
 
 /// Marker component for a UI slot entity.
@@ -94,6 +120,27 @@ pub struct ItemCount;
 #[derive(Component)]
 pub struct DurabilityBar;
 
+/// Frame holding both hands of a hotbar slot. Drops on the frame itself
+/// (its border or divider) go to the hand on that side.
+#[derive(Component)]
+pub struct HotbarSlotFrame {
+    pub index: usize,
+}
+
+/// Shade over a hotbar hand that shrinks as the hand's use cooldown runs out.
+#[derive(Component)]
+pub struct CooldownOverlay {
+    pub index: usize,
+    pub hand: Hand,
+}
+
+/// Radial shade over a hotbar hand whose item is on its own cooldown.
+#[derive(Component)]
+pub struct ItemCooldownOverlay {
+    pub index: usize,
+    pub hand: Hand,
+}
+
 /// Inventory screen visibility state.
 #[derive(Resource, Default)]
 pub struct InventoryScreenState {
@@ -0,0 +1,42 @@
+//! Radial "clock" shade drawn over hotbar hands while the held item's own
+//! cooldown runs (see `crate::interaction::hand_action::Cooldowns`).
+
+use bevy::prelude::*;
+use bevy::render::render_resource::AsBindGroup;
+use bevy::shader::ShaderRef;
+use bevy::ui_render::prelude::UiMaterial;
+
+/// Shade covering the part of a slot whose cooldown is still to run,
+/// sweeping clockwise from twelve o'clock as it clears.
+#[derive(Asset, AsBindGroup, Clone, TypePath)]
+pub struct CooldownSweepMaterial {
+    #[uniform(0)]
+    pub color: LinearRgba,
+    /// Fraction of the cooldown still to run (1.0 covers the whole slot).
+    #[uniform(0)]
+    pub fraction: f32,
+    #[uniform(0)]
+    pub _pad0: f32,
+    #[uniform(0)]
+    pub _pad1: f32,
+    #[uniform(0)]
+    pub _pad2: f32,
+}
+
+impl CooldownSweepMaterial {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color: color.to_linear(),
+            fraction: 0.0,
+            _pad0: 0.0,
+            _pad1: 0.0,
+            _pad2: 0.0,
+        }
+    }
+}
+
+impl UiMaterial for CooldownSweepMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "engine/shaders/cooldown_sweep.wgsl".into()
+    }
+}
@@ -8,7 +8,7 @@ use bevy::picking::prelude::*;
 use bevy::prelude::*;
 
 use crate::crafting::{
-    ActiveCraft, CraftingStation, HandCraftState, RecipeRegistry, UnlockedRecipes,
+    ActiveCraft, CraftingStation, HandCraftState, KnownRecipes, RecipeDiscovery, RecipeRegistry,
 };
 use crate::interaction::interactable::{HandCraftOpen, OpenStation};
 use crate::inventory::Inventory;
@@ -98,6 +98,8 @@ const PANEL_HEIGHT: f32 = 350.0;
 const PANEL_PADDING: f32 = 12.0;
 const RECIPE_LIST_WIDTH: f32 = 180.0;
 const PROGRESS_BAR_HEIGHT: f32 = 16.0;
+/// Shown in place of the name of a recipe the player hasn't unlocked.
+const LOCKED_RECIPE_LABEL: &str = "???";
 
 // ── Systems ──
 
@@ -137,13 +139,18 @@ fn manage_crafting_panel(
     }
 }
 
-/// Update the recipe list when the panel is visible.
+/// Update the recipe list when the panel is visible. Recipes the player
+/// doesn't know are left out, or listed as locked silhouettes under
+/// [`RecipeDiscovery::Silhouette`].
+#[allow(clippy::too_many_arguments)]
 fn update_recipe_list(
     mut commands: Commands,
     open_station: Res<OpenStation>,
     hand_craft_open: Res<HandCraftOpen>,
     recipe_registry: Res<RecipeRegistry>,
-    player_query: Query<(Ref<Inventory>, &UnlockedRecipes), With<Player>>,
+    known: Res<KnownRecipes>,
+    discovery: Res<RecipeDiscovery>,
+    player_query: Query<Ref<Inventory>, With<Player>>,
     station_query: Query<&CraftingStation>,
     list_query: Query<(Entity, Option<&Children>), With<RecipeListContainer>>,
     ui_state: Res<CraftingUiState>,
@@ -161,7 +168,7 @@ fn update_recipe_list(
         return;
     };
 
-    let Ok((inventory_ref, unlocked)) = player_query.single() else {
+    let Ok(inventory_ref) = player_query.single() else {
         return;
     };
 
@@ -172,6 +179,8 @@ fn update_recipe_list(
         && !hand_craft_open.is_changed()
         && !ui_state.is_changed()
         && !inventory_ref.is_changed()
+        && !known.is_changed()
+        && !discovery.is_changed()
     {
         return;
     }
@@ -190,13 +199,18 @@ fn update_recipe_list(
         return;
     };
 
-    let recipes: Vec<&crate::crafting::Recipe> = recipe_registry
+    let knows = |r: &crate::crafting::Recipe| discovery.knows(&known, r);
+    let (recipes, locked): (Vec<&crate::crafting::Recipe>, Vec<_>) = recipe_registry
         .for_station(station_id.as_deref())
         .into_iter()
-        .filter(|r| r.unlocked_by.is_unlocked(&unlocked.blueprints))
-        .collect();
+        .partition(|r| knows(r));
+    let silhouettes = if *discovery == RecipeDiscovery::Silhouette {
+        locked.len()
+    } else {
+        0
+    };
     let craftable: Vec<&str> = recipe_registry
-        .craftable_recipes(station_id.as_deref(), inventory, &unlocked.blueprints)
+        .craftable_recipes(station_id.as_deref(), inventory, knows)
         .iter()
         .map(|r| r.id.as_str())
         .collect();
@@ -271,6 +285,35 @@ fn update_recipe_list(
                     ));
                 });
         }
+
+        // Locked recipes: a row the size of a button, without the name and
+        // not selectable.
+        for _ in 0..silhouettes {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(28.0),
+                        padding: UiRect::horizontal(Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BorderColor::all(border_color),
+                    Pickable::IGNORE,
+                ))
+                .with_children(|row| {
+                    row.spawn((
+                        Text::new(LOCKED_RECIPE_LABEL),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(text_dim),
+                        Pickable::IGNORE,
+                    ));
+                });
+        }
     });
 }
 
@@ -527,10 +570,13 @@ fn handle_recipe_button_click(
 }
 
 /// Handle craft button click — consume ingredients and start crafting.
+#[allow(clippy::too_many_arguments)]
 fn handle_craft_button_click(
     craft_btn_query: Query<&Interaction, (Changed<Interaction>, With<CraftButton>)>,
     ui_state: Res<CraftingUiState>,
     recipe_registry: Res<RecipeRegistry>,
+    known: Res<KnownRecipes>,
+    discovery: Res<RecipeDiscovery>,
     open_station: Res<OpenStation>,
     mut player_query: Query<(&mut Inventory, &mut HandCraftState), With<Player>>,
     mut station_query: Query<&mut CraftingStation>,
@@ -550,6 +596,9 @@ fn handle_craft_button_click(
     let Some(recipe) = recipe_registry.get(recipe_id) else {
         return;
     };
+    if !discovery.knows(&known, recipe) {
+        return;
+    }
 
     let Ok((mut inventory, mut hand_craft)) = player_query.single_mut() else {
         return;
@@ -4,18 +4,25 @@
 //! - Spawning visual drag icons that follow the cursor
 //! - Updating drag icon position during drag operations
 //! - Canceling drags and returning items to source slots
-//! - Dropping items onto target slots (move/swap)
-//! - Assigning items to hotbar via drag-drop
+//! - Dropping items onto target slots (move/swap); locked bag rows refuse drops
+//! - Assigning items to one hand of a hotbar slot via drag-drop; items worn
+//!   in an equipment slot bounce back
+//! - Taking stacks from the creative catalog
+//! - Putting bags on and taking them off the Back equipment slot
 
 use bevy::picking::events::{DragDrop, DragEnd, DragStart};
 use bevy::picking::prelude::*;
 use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
 use bevy::window::PrimaryWindow;
 
-use super::components::{DragInfo, DragState, Hand, SlotType, UiSlot};
+use super::catalog::fill_from_catalog;
+use super::components::{DragInfo, DragState, EquipSlot, HotbarSlotFrame, SlotType, UiSlot};
+use super::hotbar::hand_at;
+use super::notifications::{NotificationKind, Notify};
 use super::theme::UiTheme;
-use crate::inventory::{Hotbar, Inventory};
-use crate::item::ItemRegistry;
+use crate::inventory::{BagSlot, Equipment, Hotbar, Inventory};
+use crate::item::{EquipmentSlot, ItemRegistry};
 use crate::player::Player;
 
 /// Marker component for the visual drag icon entity.
@@ -51,6 +58,7 @@ pub fn spawn_drag_icon(
 pub fn update_drag_position(
     drag_state: Res<DragState>,
     window: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
     mut query: Query<&mut Node, With<DragIcon>>,
 ) {
     let Some(drag) = drag_state.dragging.as_ref() else {
@@ -65,6 +73,8 @@ pub fn update_drag_position(
         return;
     };
 
+    // Node offsets are in UI pixels, which `UiScale` enlarges or shrinks.
+    let cursor = cursor / ui_scale.0;
     if let Ok(mut node) = query.get_mut(drag.drag_icon) {
         node.left = Val::Px(cursor.x - 16.0);
         node.top = Val::Px(cursor.y - 16.0);
@@ -79,38 +89,50 @@ pub fn cancel_drag(mut drag_state: ResMut<DragState>, mut commands: Commands) {
     }
 }
 
-/// Handle drag start on inventory bag slots (MainBag and MaterialBag).
+/// Handle drag start on inventory bag slots (MainBag and MaterialBag) and
+/// equipment slots.
 pub fn on_bag_slot_drag_start(
     trigger: On<Pointer<DragStart>>,
     mut drag_state: ResMut<DragState>,
     slot_query: Query<&UiSlot>,
-    inventory_query: Query<&Inventory, With<Player>>,
+    inventory_query: Query<(&Inventory, Option<&Equipment>), With<Player>>,
     mut commands: Commands,
     theme: Res<UiTheme>,
 ) {
     let Ok(slot) = slot_query.get(trigger.event_target()) else {
         return;
     };
-    let Ok(inv) = inventory_query.single() else {
+    let Ok((inv, equipment)) = inventory_query.single() else {
         return;
     };
 
     // Get item from slot based on slot type
     let item_opt = match slot.slot_type {
-        SlotType::MainBag(idx) => inv.main_bag.get(idx).and_then(|s| s.as_ref()),
-        SlotType::MaterialBag(idx) => inv.material_bag.get(idx).and_then(|s| s.as_ref()),
-        _ => return, // Only handle bag slots here
+        SlotType::MainBag(idx) => inv
+            .main_bag
+            .get(idx)
+            .and_then(|s| s.as_ref())
+            .map(|s| (&s.item_id, s.count)),
+        SlotType::MaterialBag(idx) => inv
+            .material_bag
+            .get(idx)
+            .and_then(|s| s.as_ref())
+            .map(|s| (&s.item_id, s.count)),
+        SlotType::Equipment(equip_slot) => equipment
+            .and_then(|e| e.get(equip_slot.equipment_slot()))
+            .map(|id| (id, 1)),
+        _ => return, // Only handle bag and equipment slots here
     };
 
-    let Some(item) = item_opt else {
+    let Some((item_id, count)) = item_opt else {
         return; // Empty slot, don't start drag
     };
 
-    let drag_icon = spawn_drag_icon(&mut commands, &item.item_id, item.count, &theme);
+    let drag_icon = spawn_drag_icon(&mut commands, item_id, count, &theme);
 
     drag_state.dragging = Some(DragInfo {
-        item_id: item.item_id.clone(),
-        count: item.count,
+        item_id: item_id.clone(),
+        count,
         source_slot: slot.slot_type,
         drag_icon,
     });
@@ -127,18 +149,57 @@ pub fn on_drag_end(
     }
 }
 
+/// Slot a drop lands in: the [`UiSlot`] under the pointer, or for a hotbar
+/// slot frame the hand on the side of the pointer (`cursor_x` normalized,
+/// see [`hand_at`]).
+pub fn drop_target(
+    slot: Option<&UiSlot>,
+    frame: Option<&HotbarSlotFrame>,
+    cursor_x: Option<f32>,
+) -> Option<SlotType> {
+    if let Some(slot) = slot {
+        return Some(slot.slot_type);
+    }
+    let (frame, x) = (frame?, cursor_x?);
+    Some(SlotType::Hotbar {
+        index: frame.index,
+        hand: hand_at(x),
+    })
+}
+
+/// Whether an item can go in a hotbar hand: anything but what is worn in a
+/// non-weapon equipment slot (armor, bags).
+pub fn hand_accepts(item_registry: &ItemRegistry, item_id: &str) -> bool {
+    item_registry.by_name(item_id).is_some_and(|id| {
+        matches!(
+            item_registry.get(id).equipment_slot,
+            None | Some(EquipmentSlot::Weapon1 | EquipmentSlot::Weapon2)
+        )
+    })
+}
+
 /// Handle drop onto a target slot — move/swap items between inventory slots,
-/// or assign an item to a hotbar slot.
+/// assign an item to a hotbar hand, or put on / take off a bag.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_drop(
     trigger: On<Pointer<DragDrop>>,
     mut drag_state: ResMut<DragState>,
-    slot_query: Query<&UiSlot>,
-    mut inventory_query: Query<&mut Inventory, With<Player>>,
+    slot_query: Query<(
+        Option<&UiSlot>,
+        Option<&HotbarSlotFrame>,
+        Option<&RelativeCursorPosition>,
+    )>,
+    mut inventory_query: Query<(&mut Inventory, Option<&mut Equipment>), With<Player>>,
     mut hotbar_query: Query<&mut Hotbar, With<Player>>,
     item_registry: Res<ItemRegistry>,
+    mut notify: MessageWriter<Notify>,
     mut commands: Commands,
 ) {
-    let Ok(target) = slot_query.get(trigger.event_target()) else {
+    let Ok((slot, frame, cursor)) = slot_query.get(trigger.event_target()) else {
+        return;
+    };
+    let cursor_x = cursor.and_then(|c| c.normalized).map(|p| p.x);
+    let Some(target_type) = drop_target(slot, frame, cursor_x) else {
         return;
     };
 
@@ -149,78 +210,219 @@ pub fn handle_drop(
     // Despawn the drag icon
     commands.entity(drag.drag_icon).despawn();
 
-    let target_type = target.slot_type;
-
     // Same slot — no-op
     if drag.source_slot == target_type {
         return;
     }
 
-    // Hotbar target — assign item reference (id only) without moving from inventory
+    // Hotbar target — assign item reference (id only) to that hand without
+    // moving it from the inventory. Refused items just stay where they were.
     if let SlotType::Hotbar { index, hand } = target_type {
+        if !hand_accepts(&item_registry, &drag.item_id) {
+            let name = item_registry
+                .by_name(&drag.item_id)
+                .map_or(drag.item_id.as_str(), |id| {
+                    item_registry.get(id).display_name.as_str()
+                });
+            notify.write(Notify {
+                kind: NotificationKind::Warning,
+                text: format!("{name} can't be held in a hand"),
+                icon: None,
+            });
+            return;
+        }
         if let Ok(mut hotbar) = hotbar_query.single_mut() {
             let durability = item_registry
                 .by_name(&drag.item_id)
                 .and_then(|id| item_registry.get(id).stats.as_ref())
                 .and_then(|s| s.durability);
-            match hand {
-                Hand::Left => {
-                    hotbar.slots[index].left_hand = Some(drag.item_id.clone());
-                    hotbar.slots[index].left_durability = durability;
-                }
-                Hand::Right => {
-                    hotbar.slots[index].right_hand = Some(drag.item_id.clone());
-                    hotbar.slots[index].right_durability = durability;
-                }
-            }
+            hotbar.assign(index, hand, drag.item_id.clone(), durability);
         }
         return;
     }
 
-    let Ok(mut inventory) = inventory_query.single_mut() else {
+    let Ok((mut inventory, equipment)) = inventory_query.single_mut() else {
         return;
     };
 
-    // Remove item from source slot
-    let source_item = match drag.source_slot {
-        SlotType::MainBag(idx) => inventory.main_bag.get_mut(idx).and_then(|s| s.take()),
-        SlotType::MaterialBag(idx) => inventory.material_bag.get_mut(idx).and_then(|s| s.take()),
-        _ => None,
-    };
+    // Catalog source — conjure a stack into the target bag slot
+    if let SlotType::Catalog(_) = drag.source_slot {
+        let target_slot = target_type
+            .bag_slot()
+            .and_then(|slot| inventory.slot_mut(slot));
+        if let Some(slot) = target_slot {
+            fill_from_catalog(slot, &drag.item_id, drag.count);
+        }
+        return;
+    }
 
-    let Some(source_item) = source_item else {
+    // Bags on and off the Back slot
+    let back = SlotType::Equipment(EquipSlot::Back);
+    if target_type == back || drag.source_slot == back {
+        let Some(mut equipment) = equipment else {
+            return;
+        };
+        let result = if target_type == back {
+            let rows = item_registry
+                .by_name(&drag.item_id)
+                .and_then(|id| item_registry.get(id).bag_rows);
+            match (rows, drag.source_slot.bag_slot()) {
+                (Some(rows), Some(_)) => equipment.equip_bag(&drag.item_id, rows, &mut inventory),
+                _ => return,
+            }
+        } else if target_type.bag_slot().is_some() {
+            equipment.unequip_bag(&mut inventory)
+        } else {
+            return;
+        };
+        match result {
+            // Redraw the equipment slot along with the bags.
+            Ok(()) => inventory.mark_all_changed(),
+            Err(e) => {
+                notify.write(Notify {
+                    kind: NotificationKind::Warning,
+                    text: e.to_string(),
+                    icon: None,
+                });
+            }
+        }
+        return;
+    }
+
+    let (Some(source), Some(target)) = (drag.source_slot.bag_slot(), target_type.bag_slot()) else {
         return;
     };
+    move_stack(&mut inventory, source, target);
+}
 
-    // Place in target, taking any existing item
-    let displaced = match target_type {
-        SlotType::MainBag(idx) => inventory.main_bag.get_mut(idx).and_then(|slot| {
-            let old = slot.take();
-            *slot = Some(source_item);
-            old
-        }),
-        SlotType::MaterialBag(idx) => inventory.material_bag.get_mut(idx).and_then(|slot| {
-            let old = slot.take();
-            *slot = Some(source_item);
-            old
-        }),
-        _ => None,
+/// Move the stack in `source` to `target`, swapping with whatever is there.
+/// Returns false, moving nothing, if `source` is empty or either slot is
+/// locked.
+pub fn move_stack(inventory: &mut Inventory, source: BagSlot, target: BagSlot) -> bool {
+    // Check first: a locked target would otherwise swallow the stack.
+    if !inventory.is_unlocked(source) || !inventory.is_unlocked(target) {
+        return false;
+    }
+
+    // Remove item from source slot
+    let Some(source_item) = inventory.slot_mut(source).and_then(|s| s.take()) else {
+        return false;
     };
 
+    // Place in target, taking any existing item
+    let displaced = inventory
+        .slot_mut(target)
+        .and_then(|slot| slot.replace(source_item));
+
     // Put displaced item back in source slot (swap)
-    if let Some(displaced_item) = displaced {
-        match drag.source_slot {
-            SlotType::MainBag(idx) => {
-                if let Some(slot) = inventory.main_bag.get_mut(idx) {
-                    *slot = Some(displaced_item);
-                }
-            }
-            SlotType::MaterialBag(idx) => {
-                if let Some(slot) = inventory.material_bag.get_mut(idx) {
-                    *slot = Some(displaced_item);
-                }
-            }
-            _ => {}
+    if let Some(displaced_item) = displaced
+        && let Some(slot) = inventory.slot_mut(source)
+    {
+        *slot = Some(displaced_item);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{BagTarget, Hand, Stack};
+    use crate::item::{ItemDef, ItemType};
+    use crate::test_helpers::fixtures;
+
+    fn stack(item_id: &str, count: u16) -> Option<Stack> {
+        Some(Stack {
+            item_id: item_id.into(),
+            count,
+            durability: None,
+        })
+    }
+
+    #[test]
+    fn drops_into_locked_rows_are_refused() {
+        let mut inv = Inventory::new();
+        inv.try_add_item("torch", 5, 99, BagTarget::Main);
+        let locked = inv.slot_count();
+
+        assert!(!move_stack(
+            &mut inv,
+            BagSlot::Main(0),
+            BagSlot::Main(locked)
+        ));
+        assert_eq!(inv.main_bag[0], stack("torch", 5));
+        assert_eq!(inv.main_bag[locked], None);
+
+        // The same drop works once the row is open.
+        inv.unlock_rows(1);
+        assert!(move_stack(
+            &mut inv,
+            BagSlot::Main(0),
+            BagSlot::Main(locked)
+        ));
+        assert_eq!(inv.main_bag[0], None);
+        assert_eq!(inv.main_bag[locked], stack("torch", 5));
+    }
+
+    #[test]
+    fn drops_swap_stacks() {
+        let mut inv = Inventory::new();
+        *inv.slot_mut(BagSlot::Main(0)).unwrap() = stack("torch", 5);
+        *inv.slot_mut(BagSlot::Material(3)).unwrap() = stack("dirt", 40);
+
+        assert!(move_stack(&mut inv, BagSlot::Main(0), BagSlot::Material(3)));
+        assert_eq!(inv.main_bag[0], stack("dirt", 40));
+        assert_eq!(inv.material_bag[3], stack("torch", 5));
+        assert!(!move_stack(&mut inv, BagSlot::Main(1), BagSlot::Main(2)));
+    }
+
+    fn item(id: &str, item_type: ItemType, equipment_slot: Option<EquipmentSlot>) -> ItemDef {
+        ItemDef {
+            max_stack: 1,
+            equipment_slot,
+            ..fixtures::item_def(id, item_type)
         }
     }
+
+    #[test]
+    fn drops_on_a_hotbar_frame_go_to_the_hand_on_that_side() {
+        let frame = HotbarSlotFrame { index: 3 };
+        let hotbar = |hand| Some(SlotType::Hotbar { index: 3, hand });
+        let on_frame = |x| drop_target(None, Some(&frame), x);
+        assert_eq!(on_frame(Some(-0.4)), hotbar(Hand::Left));
+        assert_eq!(on_frame(Some(-0.01)), hotbar(Hand::Left));
+        assert_eq!(on_frame(Some(0.0)), hotbar(Hand::Right));
+        assert_eq!(on_frame(Some(0.4)), hotbar(Hand::Right));
+        // Without a cursor position there is no telling which hand.
+        assert_eq!(on_frame(None), None);
+        assert_eq!(drop_target(None, None, Some(0.2)), None);
+
+        // A hand's own slot wins over the frame around it.
+        let left = UiSlot {
+            slot_type: SlotType::Hotbar {
+                index: 3,
+                hand: Hand::Left,
+            },
+        };
+        assert_eq!(
+            drop_target(Some(&left), Some(&frame), Some(0.4)),
+            hotbar(Hand::Left)
+        );
+    }
+
+    #[test]
+    fn hands_refuse_equipment() {
+        let registry = ItemRegistry::from_defs(vec![
+            item("pickaxe", ItemType::Tool, None),
+            item("dirt", ItemType::Block, None),
+            item("sword", ItemType::Weapon, Some(EquipmentSlot::Weapon1)),
+            item("helmet", ItemType::Armor, Some(EquipmentSlot::Head)),
+            item("backpack", ItemType::Armor, Some(EquipmentSlot::Back)),
+        ]);
+        assert!(hand_accepts(&registry, "pickaxe"));
+        assert!(hand_accepts(&registry, "dirt"));
+        assert!(hand_accepts(&registry, "sword"));
+        assert!(!hand_accepts(&registry, "helmet"));
+        assert!(!hand_accepts(&registry, "backpack"));
+        assert!(!hand_accepts(&registry, "unknown"));
+    }
 }
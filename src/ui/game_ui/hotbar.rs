@@ -1,24 +1,51 @@
 use bevy::picking::prelude::*;
 use bevy::prelude::*;
 use bevy::ui::widget::ImageNode;
+use bevy::ui::RelativeCursorPosition;
+use bevy::ui_render::prelude::MaterialNode;
 
 use super::components::*;
 use super::components::{on_slot_hover, on_slot_unhover};
+use super::cooldown_sweep::CooldownSweepMaterial;
 use super::drag_drop::handle_drop;
+use super::slot_sync::SlotChangeReader;
 use super::spawn_slot_icon_children;
-use super::theme::UiTheme;
+use super::theme::{HotbarConfig, UiTheme};
+use crate::interaction::hand_action::{Cooldowns, HandCooldowns};
 use crate::inventory::Hotbar;
 use crate::player::Player;
 
+/// Width of the hotbar in UI pixels: two hands per slot plus the gaps.
+pub fn hotbar_width(config: &HotbarConfig) -> f32 {
+    let pair_width = config.slot_size * 2.0;
+    config.slots as f32 * pair_width + config.slots.saturating_sub(1) as f32 * config.gap
+}
+
+/// Colour of the radial shade over items on their own cooldown.
+const ITEM_COOLDOWN_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// Hand under a point of a hotbar slot frame, `normalized_x` running from
+/// -0.5 at its left edge to 0.5 at its right edge.
+pub fn hand_at(normalized_x: f32) -> Hand {
+    if normalized_x < 0.0 {
+        Hand::Left
+    } else {
+        Hand::Right
+    }
+}
+
 /// Spawn the hotbar UI at the bottom of the screen.
-pub fn spawn_hotbar(commands: &mut Commands, theme: &UiTheme, asset_server: &AssetServer) {
+pub fn spawn_hotbar(
+    commands: &mut Commands,
+    theme: &UiTheme,
+    asset_server: &AssetServer,
+    sweeps: &mut Assets<CooldownSweepMaterial>,
+) {
     let config = &theme.hotbar;
     let colors = &theme.colors;
 
     // Hotbar container
-    let pair_width = config.slot_size * 2.0;
-    let total_width =
-        config.slots as f32 * pair_width + (config.slots - 1) as f32 * config.gap;
+    let total_width = hotbar_width(config);
 
     commands
         .spawn((
@@ -60,10 +87,13 @@ pub fn spawn_hotbar(commands: &mut Commands, theme: &UiTheme, asset_server: &Ass
                 let border_color = colors.border.clone();
                 let text_dim = colors.text_dim.clone();
 
-                // Slot container (no UiSlot — only hand children have it)
+                // Slot container (no UiSlot — only hand children have it;
+                // drops on the frame itself go to the hand on that side)
                 // Width = 2× slot_size so each hand half is a square.
                 let mut slot_cmd = parent
                     .spawn((
+                        HotbarSlotFrame { index: i },
+                        RelativeCursorPosition::default(),
                         Node {
                             width: Val::Px(slot_size * 2.0),
                             height: Val::Px(slot_size),
@@ -120,7 +150,11 @@ pub fn spawn_hotbar(commands: &mut Commands, theme: &UiTheme, asset_server: &Ass
                             .observe(on_slot_hover)
                             .observe(on_slot_unhover)
                             .observe(handle_drop)
-                            .with_children(spawn_slot_icon_children);
+                            .with_children(|hand_parent| {
+                                spawn_slot_icon_children(hand_parent);
+                                spawn_cooldown_overlay(hand_parent, i, Hand::Left);
+                                hand_parent.spawn(item_cooldown_overlay(i, Hand::Left, sweeps));
+                            });
                         // Right hand half
                         slot_parent
                             .spawn((
@@ -144,7 +178,25 @@ pub fn spawn_hotbar(commands: &mut Commands, theme: &UiTheme, asset_server: &Ass
                             .observe(on_slot_hover)
                             .observe(on_slot_unhover)
                             .observe(handle_drop)
-                            .with_children(spawn_slot_icon_children);
+                            .with_children(|hand_parent| {
+                                spawn_slot_icon_children(hand_parent);
+                                spawn_cooldown_overlay(hand_parent, i, Hand::Right);
+                                hand_parent.spawn(item_cooldown_overlay(i, Hand::Right, sweeps));
+                            });
+                        // Divider between the hands
+                        slot_parent.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(50.0),
+                                top: Val::Percent(15.0),
+                                width: Val::Px(border_width.max(1.0)),
+                                height: Val::Percent(70.0),
+                                margin: UiRect::left(Val::Px(-border_width.max(1.0) / 2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::from(colors.border.clone())),
+                            Pickable::IGNORE,
+                        ));
                         // Slot number label
                         slot_parent.spawn((
                             Text::new(format!("{}", i + 1)),
@@ -166,20 +218,125 @@ pub fn spawn_hotbar(commands: &mut Commands, theme: &UiTheme, asset_server: &Ass
         });
 }
 
-/// Sync hotbar UI slots with Hotbar component data.
+/// Spawn the cooldown shade for one hand of a hotbar slot (hidden at 0% height).
+fn spawn_cooldown_overlay(parent: &mut ChildSpawnerCommands, index: usize, hand: Hand) {
+    parent.spawn((
+        CooldownOverlay { index, hand },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(0.0),
+            left: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            height: Val::Percent(0.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.55)),
+        Pickable::IGNORE,
+    ));
+}
+
+/// Radial shade for the own cooldown of the item in one hand of a hotbar
+/// slot (hidden until the item is used).
+fn item_cooldown_overlay(
+    index: usize,
+    hand: Hand,
+    sweeps: &mut Assets<CooldownSweepMaterial>,
+) -> impl Bundle {
+    (
+        ItemCooldownOverlay { index, hand },
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        MaterialNode(sweeps.add(CooldownSweepMaterial::new(ITEM_COOLDOWN_COLOR))),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+    )
+}
+
+/// Size each hand's cooldown shade to the remaining cooldown of the active
+/// slot; other slots show none.
+pub fn update_cooldown_overlays(
+    player_query: Query<(&Hotbar, &HandCooldowns), With<Player>>,
+    mut overlay_query: Query<(&CooldownOverlay, &mut Node)>,
+) {
+    let Ok((hotbar, cooldowns)) = player_query.single() else {
+        return;
+    };
+
+    for (overlay, mut node) in &mut overlay_query {
+        let fraction = if overlay.index == hotbar.active_slot {
+            cooldowns.fraction(overlay.hand)
+        } else {
+            0.0
+        };
+        let height = Val::Percent(fraction * 100.0);
+        if node.height != height {
+            node.height = height;
+        }
+    }
+}
+
+/// Sweep the radial shade of every hotbar hand holding an item on its own
+/// cooldown, in any slot.
+pub fn update_item_cooldown_overlays(
+    cooldowns: Res<Cooldowns>,
+    player_query: Query<&Hotbar, With<Player>>,
+    mut overlay_query: Query<(
+        &ItemCooldownOverlay,
+        &MaterialNode<CooldownSweepMaterial>,
+        &mut Visibility,
+    )>,
+    mut sweeps: ResMut<Assets<CooldownSweepMaterial>>,
+) {
+    let Ok(hotbar) = player_query.single() else {
+        return;
+    };
+
+    for (overlay, material, mut visibility) in &mut overlay_query {
+        let Some(slot) = hotbar.slots.get(overlay.index) else {
+            continue;
+        };
+        let item = match overlay.hand {
+            Hand::Left => slot.left_hand.as_deref(),
+            Hand::Right => slot.right_hand.as_deref(),
+        };
+        let fraction = item.map_or(0.0, |id| cooldowns.fraction(id));
+        let shown = if fraction > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(shown);
+        if fraction > 0.0
+            && let Some(sweep) = sweeps.get_mut(&material.0)
+        {
+            sweep.fraction = fraction;
+        }
+    }
+}
+
+/// Sync hotbar UI slots with Hotbar component data when they change.
 pub fn update_hotbar_slots(
-    hotbar_query: Query<&Hotbar, With<Player>>,
-    mut slot_query: Query<(&UiSlot, &mut BackgroundColor, Option<&Children>)>,
+    hotbar_query: Query<(Entity, &Hotbar), With<Player>>,
+    mut changes: SlotChangeReader,
+    mut slot_query: Query<(Ref<UiSlot>, &mut BackgroundColor, Option<&Children>)>,
     _child_slots: Query<&UiSlot>,
 ) {
-    let Ok(hotbar) = hotbar_query.single() else {
+    let Ok((player, hotbar)) = hotbar_query.single() else {
         return;
     };
+    let dirty = changes.dirty_slots(player);
 
     for (slot, mut bg_color, children) in &mut slot_query {
         let SlotType::Hotbar { index, hand } = slot.slot_type else {
             continue;
         };
+        if !slot.is_added() && !dirty.contains(slot.slot_type) {
+            continue;
+        }
 
         let Some(_children) = children else {
             continue;
@@ -1,4 +1,5 @@
-//! Inventory screen UI — equipment panel (left) + bag grids (right).
+//! Inventory screen UI — equipment panel (left), bag grids (middle) and
+//! player stats (right).
 //!
 //! Spawned hidden; toggled by I key via `toggle_inventory` in mod.rs.
 //! Uses the unified window system for dragging and ESC-close.
@@ -10,17 +11,12 @@ use super::components::*;
 use super::components::{on_slot_hover, on_slot_unhover};
 use super::drag_drop::{handle_drop, on_bag_slot_drag_start, on_drag_end};
 use super::spawn_slot_icon_children;
-use super::theme::UiTheme;
+use super::stats_panel;
+use super::theme::{InventoryScreenConfig, UiTheme};
 use super::window::{self, GameWindow, WindowConfig};
 
-/// Spawn the inventory screen (hidden by default).
-pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_server: &AssetServer) {
-    let config = &theme.inventory_screen;
-    let colors = &theme.colors;
-
-    let bg_medium = Color::from(colors.bg_medium.clone());
-    let border_color = Color::from(colors.border.clone());
-
+/// Nominal size of the inventory window in UI pixels.
+pub fn inventory_window_size(config: &InventoryScreenConfig) -> Vec2 {
     // Compute the window height from actual content dimensions so the layout
     // never overflows the window border regardless of theme values.
     //
@@ -40,6 +36,17 @@ pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_se
     //   padding top(cfg) + border top(2) + header(28) + header-margin(4)
     //   + padding bottom(cfg) + border bottom(2)  = cfg.padding×2 + 36
     let window_h = body_h + config.padding * 2.0 + 36.0;
+    Vec2::new(config.width, window_h)
+}
+
+/// Spawn the inventory screen (hidden by default).
+pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_server: &AssetServer) {
+    let config = &theme.inventory_screen;
+    let colors = &theme.colors;
+
+    let bg_medium = Color::from(colors.bg_medium.clone());
+    let border_color = Color::from(colors.border.clone());
+    let window_size = inventory_window_size(config);
 
     // Spawn unified window frame.
     let entities = window::spawn_window_frame(
@@ -47,8 +54,8 @@ pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_se
         theme,
         &WindowConfig {
             title: "Inventory",
-            width: config.width,
-            height: window_h,
+            width: window_size.x,
+            height: window_size.y,
             padding: config.padding,
         },
         GameWindow::Inventory,
@@ -117,12 +124,16 @@ pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_se
                                 is_hoverable: true,
                             },
                         ))
+                        .with_children(spawn_slot_icon_children)
                         .observe(on_slot_hover)
-                        .observe(on_slot_unhover);
+                        .observe(on_slot_unhover)
+                        .observe(on_bag_slot_drag_start)
+                        .observe(on_drag_end)
+                        .observe(handle_drop);
                 }
             });
 
-        // ── Right column: Bags ──
+        // ── Middle column: Bags ──
         parent
             .spawn((
                 Node {
@@ -233,5 +244,8 @@ pub fn spawn_inventory_screen(commands: &mut Commands, theme: &UiTheme, asset_se
                         }
                     });
             });
+
+        // ── Right column: Stats ──
+        stats_panel::spawn_stats_column(parent, theme);
     });
 }
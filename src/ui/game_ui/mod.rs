@@ -1,13 +1,19 @@
+pub mod catalog;
 pub mod chat;
 pub mod components;
+pub mod cooldown_sweep;
 pub mod crafting_panel;
 pub mod drag_drop;
 pub mod hotbar;
 pub mod icon_registry;
 pub mod inventory;
 pub mod health_hud;
+pub mod mode_hint;
+pub mod notifications;
 pub mod oxygen_hud;
+pub mod sign_editor;
 pub mod slot_sync;
+pub mod stats_panel;
 pub mod theme;
 pub mod tooltip;
 pub mod trade_panel;
@@ -18,6 +24,7 @@ use bevy::picking::prelude::*;
 use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use bevy::ui::widget::ImageNode;
+use bevy::ui_render::prelude::UiMaterialPlugin;
 
 use crate::registry::AppState;
 
@@ -101,10 +108,15 @@ impl Plugin for GameUiPlugin {
         // and hot-reloaded in real-time by hot_reload_ui_theme.
         app.add_plugins(crafting_panel::CraftingUiPlugin)
             .add_plugins(trade_panel::TradeUiPlugin)
+            .add_plugins(catalog::CatalogUiPlugin)
+            .add_plugins(UiMaterialPlugin::<cooldown_sweep::CooldownSweepMaterial>::default())
             .init_resource::<DragState>()
             .init_resource::<HoveredSlot>()
             .init_resource::<InventoryScreenState>()
             .init_resource::<FocusedWindow>()
+            .init_resource::<sign_editor::SignEditor>()
+            .init_resource::<notifications::NotificationQueue>()
+            .add_message::<notifications::Notify>()
             .add_systems(
                 OnEnter(AppState::InGame),
                 (
@@ -118,9 +130,8 @@ impl Plugin for GameUiPlugin {
             .add_systems(
                 Update,
                 (
-                    hotbar::update_hotbar_slots,
-                    slot_sync::sync_slot_contents,
-                    slot_sync::update_slot_icons,
+                    hotbar::update_cooldown_overlays,
+                    hotbar::update_item_cooldown_overlays,
                     toggle_inventory,
                     drag_drop::update_drag_position,
                     tooltip::update_tooltip,
@@ -130,6 +141,18 @@ impl Plugin for GameUiPlugin {
                 )
                     .run_if(in_state(AppState::InGame)),
             )
+            // Slot widgets redraw from the change messages sent in PostUpdate.
+            .add_systems(
+                PostUpdate,
+                (
+                    hotbar::update_hotbar_slots,
+                    slot_sync::sync_slot_contents,
+                    slot_sync::update_slot_icons,
+                )
+                    .after(crate::inventory::emit_inventory_changes)
+                    .before(bevy::ui::UiSystems::Prepare)
+                    .run_if(in_state(AppState::InGame)),
+            )
             .add_systems(
                 Update,
                 (
@@ -139,6 +162,19 @@ impl Plugin for GameUiPlugin {
                 )
                     .in_set(crate::sets::GameSet::Ui)
                     .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                notifications::update_notifications
+                    .in_set(crate::sets::GameSet::Ui)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                stats_panel::update_stats_column
+                    .in_set(crate::sets::GameSet::Ui)
+                    .run_if(resource_changed::<crate::player::stats::PlayerStatsSummary>)
+                    .run_if(in_state(AppState::InGame)),
             );
     }
 }
@@ -150,7 +186,7 @@ fn toggle_inventory(
     mut query: Query<&mut Visibility, With<InventoryScreen>>,
     chat_state: Res<crate::chat::ChatState>,
 ) {
-    if chat_state.is_active {
+    if chat_state.keyboard_captured() {
         return;
     }
 
@@ -174,11 +210,12 @@ fn spawn_game_ui(
     theme: Res<UiTheme>,
     existing: Query<Entity, With<InventoryScreen>>,
     asset_server: Res<AssetServer>,
+    mut sweeps: ResMut<Assets<cooldown_sweep::CooldownSweepMaterial>>,
 ) {
     if !existing.is_empty() {
         return;
     }
-    hotbar::spawn_hotbar(&mut commands, &theme, &asset_server);
+    hotbar::spawn_hotbar(&mut commands, &theme, &asset_server, &mut sweeps);
     inventory::spawn_inventory_screen(&mut commands, &theme, &asset_server);
     chat::spawn_chat(&mut commands, &theme, &asset_server);
 }
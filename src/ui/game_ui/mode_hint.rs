@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::interaction::mode::InteractionMode;
+use crate::ui::game_ui::theme::UiTheme;
+
+/// Gap (px) between the hint and the top of the hotbar.
+const GAP: f32 = 4.0;
+
+/// Draw what each mouse button would do as two small lines just above the
+/// hotbar.
+pub fn draw_mode_hint(
+    mut contexts: EguiContexts,
+    mode: Res<InteractionMode>,
+    theme: Option<Res<UiTheme>>,
+    ui_scale: Res<UiScale>,
+) -> Result {
+    if mode.left.is_empty() && mode.right.is_empty() {
+        return Ok(());
+    }
+    let ctx = contexts.ctx_mut()?;
+    // The hotbar is laid out in UI pixels, which `UiScale` scales.
+    let hotbar_top = theme
+        .as_ref()
+        .map_or(0.0, |t| t.hotbar.margin_bottom + t.hotbar.slot_size)
+        * ui_scale.0;
+
+    egui::Area::new(egui::Id::new("interaction_mode_hint"))
+        .anchor(
+            egui::Align2::CENTER_BOTTOM,
+            egui::vec2(0.0, -(hotbar_top + GAP)),
+        )
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::new()
+                .fill(egui::Color32::from_rgba_unmultiplied(20, 20, 30, 160))
+                .corner_radius(3.0)
+                .inner_margin(egui::Margin::symmetric(6, 2))
+                .show(ui, |ui| {
+                    for line in [&mode.left, &mode.right] {
+                        ui.label(
+                            egui::RichText::new(line)
+                                .color(egui::Color32::from_gray(220))
+                                .size(11.0),
+                        );
+                    }
+                });
+        });
+
+    Ok(())
+}
@@ -0,0 +1,432 @@
+//! Transient on-screen notifications (toasts) in the top-right corner.
+//!
+//! Any system can raise one by writing a [`Notify`] message; item pickups
+//! raise their own from [`ItemPickupEvent`]. [`update_notifications`] feeds
+//! them into the [`NotificationQueue`], which shows up to `max_visible`
+//! toasts at a time. Each slides in, holds, then fades out. A toast
+//! identical to the one just raised bumps that one's `×count` instead, and
+//! pickups of the same item within `pickup_window_secs` add up ("+5 Copper
+//! Ore"). When every slot is taken, the oldest toast makes room.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiTextureHandle};
+
+use super::theme::{NotificationColors, UiTheme};
+use super::ItemIconRegistry;
+use crate::inventory::ItemPickupEvent;
+use crate::item::ItemRegistry;
+
+/// Width (px) of a toast.
+const TOAST_WIDTH: f32 = 240.0;
+/// Height (px) of a toast slot, including the gap below it.
+const TOAST_SLOT_HEIGHT: f32 = 34.0;
+/// Side (px) of a toast's icon.
+const ICON_SIZE: f32 = 16.0;
+
+/// What a notification is about, which sets its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationKind {
+    /// Accent colour of toasts of this kind in the theme's palette.
+    pub fn color(self, colors: &NotificationColors) -> egui::Color32 {
+        let hex = match self {
+            Self::Info => &colors.info,
+            Self::Success => &colors.success,
+            Self::Warning => &colors.warning,
+            Self::Error => &colors.error,
+        };
+        let [r, g, b, a] = Color::from(hex.clone()).to_srgba().to_u8_array();
+        egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
+
+/// Raise a notification from any system.
+#[derive(Message, Debug, Clone)]
+pub struct Notify {
+    pub kind: NotificationKind,
+    pub text: String,
+    pub icon: Option<Handle<Image>>,
+}
+
+/// Timing and capacity of toasts.
+#[derive(Debug, Clone)]
+pub struct NotificationSettings {
+    /// Most toasts on screen at once.
+    pub max_visible: usize,
+    /// Seconds a toast takes to slide in.
+    pub slide_secs: f32,
+    /// Seconds a toast stays fully shown.
+    pub hold_secs: f32,
+    /// Seconds a toast takes to fade out.
+    pub fade_secs: f32,
+    /// Pickups of one item this close together (seconds) share a toast.
+    pub pickup_window_secs: f32,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            max_visible: 4,
+            slide_secs: 0.2,
+            hold_secs: 3.0,
+            fade_secs: 0.6,
+            pickup_window_secs: 1.5,
+        }
+    }
+}
+
+/// One notification, waiting or on screen.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub kind: NotificationKind,
+    pub text: String,
+    pub icon: Option<Handle<Image>>,
+    /// How many identical notifications this toast stands for.
+    pub count: u32,
+    /// Item and running total, for pickup toasts.
+    pickup: Option<(String, u32)>,
+    /// Seconds on screen; rewound to the end of the slide-in when bumped.
+    age: f32,
+    /// Seconds since it was raised or last bumped.
+    since_update: f32,
+}
+
+impl Toast {
+    fn new(kind: NotificationKind, text: String, icon: Option<Handle<Image>>) -> Self {
+        Self {
+            kind,
+            text,
+            icon,
+            count: 1,
+            pickup: None,
+            age: 0.0,
+            since_update: 0.0,
+        }
+    }
+
+    /// Text to show, with the `×count` suffix when coalesced.
+    pub fn label(&self) -> String {
+        if self.count > 1 {
+            format!("{} ×{}", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
+
+    /// Restart the hold without sliding in again.
+    fn bump(&mut self, settings: &NotificationSettings) {
+        self.age = self.age.min(settings.slide_secs);
+        self.since_update = 0.0;
+    }
+
+    fn expired(&self, settings: &NotificationSettings) -> bool {
+        self.age >= settings.slide_secs + settings.hold_secs + settings.fade_secs
+    }
+
+    /// How far in (0.0 off to the side, 1.0 in place) it has slid.
+    pub fn slide(&self, settings: &NotificationSettings) -> f32 {
+        if settings.slide_secs <= 0.0 {
+            return 1.0;
+        }
+        (self.age / settings.slide_secs).clamp(0.0, 1.0)
+    }
+
+    /// Opacity while fading out.
+    pub fn alpha(&self, settings: &NotificationSettings) -> f32 {
+        let fade_start = settings.slide_secs + settings.hold_secs;
+        if settings.fade_secs <= 0.0 {
+            return 1.0;
+        }
+        (1.0 - (self.age - fade_start) / settings.fade_secs).clamp(0.0, 1.0)
+    }
+}
+
+/// Toasts on screen, oldest first, and those waiting for a slot.
+#[derive(Resource, Debug, Default)]
+pub struct NotificationQueue {
+    pub settings: NotificationSettings,
+    visible: VecDeque<Toast>,
+    pending: VecDeque<Toast>,
+}
+
+impl NotificationQueue {
+    /// Raise a notification, or bump the last one if it says the same.
+    pub fn push(
+        &mut self,
+        kind: NotificationKind,
+        text: impl Into<String>,
+        icon: Option<Handle<Image>>,
+    ) {
+        let text = text.into();
+        let last = match self.pending.back_mut() {
+            Some(toast) => Some(toast),
+            None => self.visible.back_mut(),
+        };
+        if let Some(last) = last
+            && last.pickup.is_none()
+            && last.kind == kind
+            && last.text == text
+        {
+            last.count += 1;
+            last.bump(&self.settings);
+            return;
+        }
+        self.pending.push_back(Toast::new(kind, text, icon));
+    }
+
+    /// Raise "+count name" for a pickup, adding to a recent toast for the
+    /// same item if there is one.
+    pub fn push_pickup(
+        &mut self,
+        item_id: &str,
+        name: &str,
+        count: u32,
+        icon: Option<Handle<Image>>,
+    ) {
+        let window = self.settings.pickup_window_secs;
+        let recent = self
+            .visible
+            .iter_mut()
+            .chain(self.pending.iter_mut())
+            .find(|toast| {
+                toast.since_update < window
+                    && toast.pickup.as_ref().is_some_and(|(id, _)| id == item_id)
+            });
+        if let Some(toast) = recent {
+            let total = toast.pickup.as_mut().map_or(count, |(_, total)| {
+                *total += count;
+                *total
+            });
+            toast.text = format!("+{total} {name}");
+            toast.bump(&self.settings);
+            return;
+        }
+        let mut toast = Toast::new(NotificationKind::Info, format!("+{count} {name}"), icon);
+        toast.pickup = Some((item_id.to_string(), count));
+        self.pending.push_back(toast);
+    }
+
+    /// Age the toasts on screen, drop expired ones and move waiting ones
+    /// into free slots, evicting the oldest when all are taken.
+    pub fn tick(&mut self, dt: f32) {
+        let settings = &self.settings;
+        for toast in &mut self.visible {
+            toast.age += dt;
+            toast.since_update += dt;
+        }
+        for toast in &mut self.pending {
+            toast.since_update += dt;
+        }
+        self.visible.retain(|toast| !toast.expired(settings));
+        while let Some(toast) = self.pending.pop_front() {
+            if self.visible.len() >= settings.max_visible.max(1) {
+                self.visible.pop_front();
+            }
+            self.visible.push_back(toast);
+        }
+    }
+
+    /// Toasts on screen, oldest first.
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.visible.iter()
+    }
+}
+
+/// Feed this frame's notifications and pickups into the queue and age it.
+pub fn update_notifications(
+    time: Res<Time>,
+    mut notes: MessageReader<Notify>,
+    mut pickups: MessageReader<ItemPickupEvent>,
+    items: Option<Res<ItemRegistry>>,
+    icons: Option<Res<ItemIconRegistry>>,
+    mut queue: ResMut<NotificationQueue>,
+) {
+    for note in notes.read() {
+        queue.push(note.kind, note.text.clone(), note.icon.clone());
+    }
+    for pickup in pickups.read() {
+        let id = items
+            .as_ref()
+            .and_then(|items| items.by_name(&pickup.item_id));
+        let name = match (&items, id) {
+            (Some(items), Some(id)) => items.get(id).display_name.clone(),
+            _ => pickup.item_id.clone(),
+        };
+        let icon = id.and_then(|id| icons.as_ref()?.get(id).cloned());
+        queue.push_pickup(&pickup.item_id, &name, pickup.count as u32, icon);
+    }
+    queue.tick(time.delta_secs());
+}
+
+/// Draw the toasts stacked down from the top-right corner.
+pub fn draw_notifications(
+    mut contexts: EguiContexts,
+    queue: Res<NotificationQueue>,
+    theme: Option<Res<UiTheme>>,
+) -> Result {
+    if queue.visible.is_empty() {
+        return Ok(());
+    }
+    let fallback = NotificationColors::default();
+    let colors = theme.as_ref().map_or(&fallback, |t| &t.notifications);
+    let textures: Vec<_> = queue
+        .visible()
+        .map(|toast| {
+            toast
+                .icon
+                .as_ref()
+                .map(|icon| contexts.add_image(EguiTextureHandle::Weak(icon.id())))
+        })
+        .collect();
+    let ctx = contexts.ctx_mut()?;
+    let settings = &queue.settings;
+
+    for (i, (toast, texture)) in queue.visible().zip(textures).enumerate() {
+        let alpha = toast.alpha(settings);
+        let offset_x = (1.0 - toast.slide(settings)) * (TOAST_WIDTH + 10.0);
+        egui::Area::new(egui::Id::new(("notification", i)))
+            .anchor(
+                egui::Align2::RIGHT_TOP,
+                egui::vec2(-10.0 + offset_x, 10.0 + i as f32 * TOAST_SLOT_HEIGHT),
+            )
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(
+                        egui::Color32::from_rgba_unmultiplied(20, 20, 30, 200)
+                            .gamma_multiply(alpha),
+                    )
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        toast.kind.color(colors).gamma_multiply(alpha),
+                    ))
+                    .corner_radius(3.0)
+                    .inner_margin(egui::Margin::symmetric(8, 5))
+                    .show(ui, |ui| {
+                        ui.set_width(TOAST_WIDTH);
+                        ui.horizontal(|ui| {
+                            if let Some(texture) = texture {
+                                ui.add(
+                                    egui::Image::new(egui::load::SizedTexture::new(
+                                        texture,
+                                        [ICON_SIZE, ICON_SIZE],
+                                    ))
+                                    .tint(egui::Color32::WHITE.gamma_multiply(alpha)),
+                                );
+                            }
+                            ui.label(
+                                egui::RichText::new(toast.label())
+                                    .color(egui::Color32::WHITE.gamma_multiply(alpha))
+                                    .size(13.0),
+                            );
+                        });
+                    });
+            });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::game_ui::theme::HexColor;
+
+    fn labels(queue: &NotificationQueue) -> Vec<String> {
+        queue.visible().map(Toast::label).collect()
+    }
+
+    #[test]
+    fn identical_consecutive_notifications_coalesce() {
+        let mut queue = NotificationQueue::default();
+        queue.push(NotificationKind::Success, "Game saved", None);
+        queue.push(NotificationKind::Success, "Game saved", None);
+        queue.tick(0.1);
+        queue.push(NotificationKind::Success, "Game saved", None);
+        // Same text but a different kind is a new toast, and so is a repeat
+        // that isn't consecutive.
+        queue.push(NotificationKind::Error, "Game saved", None);
+        queue.push(NotificationKind::Success, "Game saved", None);
+        queue.tick(0.1);
+
+        assert_eq!(
+            labels(&queue),
+            ["Game saved ×3", "Game saved", "Game saved"]
+        );
+    }
+
+    #[test]
+    fn kind_colours_come_from_the_theme() {
+        let mut colors = crate::test_helpers::fixtures::test_ui_theme().notifications;
+        assert_eq!(
+            NotificationKind::Error.color(&colors),
+            egui::Color32::from_rgb(220, 50, 50)
+        );
+        colors.warning = HexColor("#10203040".into());
+        assert_eq!(
+            NotificationKind::Warning.color(&colors),
+            egui::Color32::from_rgba_unmultiplied(16, 32, 48, 64)
+        );
+    }
+
+    #[test]
+    fn pickups_add_up_within_the_window() {
+        let mut queue = NotificationQueue::default();
+        let window = queue.settings.pickup_window_secs;
+        queue.push_pickup("copper_ore", "Copper Ore", 2, None);
+        queue.tick(window * 0.5);
+        queue.push_pickup("copper_ore", "Copper Ore", 3, None);
+        queue.push_pickup("dirt", "Dirt", 1, None);
+        queue.tick(0.1);
+        assert_eq!(labels(&queue), ["+5 Copper Ore", "+1 Dirt"]);
+
+        // Past the window a pickup starts a new toast.
+        queue.tick(window);
+        queue.push_pickup("copper_ore", "Copper Ore", 4, None);
+        queue.tick(0.1);
+        assert_eq!(
+            labels(&queue),
+            ["+5 Copper Ore", "+1 Dirt", "+4 Copper Ore"]
+        );
+    }
+
+    #[test]
+    fn full_slots_evict_the_oldest_toast() {
+        let mut queue = NotificationQueue::default();
+        queue.settings.max_visible = 3;
+        for text in ["a", "b", "c"] {
+            queue.push(NotificationKind::Info, text, None);
+            queue.tick(0.1);
+        }
+        queue.push(NotificationKind::Info, "d", None);
+        queue.push(NotificationKind::Info, "e", None);
+        queue.tick(0.1);
+        assert_eq!(labels(&queue), ["c", "d", "e"]);
+    }
+
+    #[test]
+    fn toasts_slide_in_hold_and_fade_out() {
+        let mut queue = NotificationQueue::default();
+        let settings = queue.settings.clone();
+        queue.push(NotificationKind::Warning, "Tool broke", None);
+        queue.tick(0.0);
+        let toast = queue.visible().next().unwrap();
+        assert_eq!((toast.slide(&settings), toast.alpha(&settings)), (0.0, 1.0));
+
+        queue.tick(settings.slide_secs + settings.hold_secs + settings.fade_secs * 0.5);
+        let toast = queue.visible().next().unwrap();
+        assert_eq!(toast.slide(&settings), 1.0);
+        assert!((toast.alpha(&settings) - 0.5).abs() < 1e-4);
+
+        queue.tick(settings.fade_secs);
+        assert_eq!(queue.visible().count(), 0);
+    }
+}
@@ -0,0 +1,127 @@
+//! Text prompt for writing on a sign tile.
+//!
+//! Opened by right-clicking a sign (see `block_interaction_system`). Enter or
+//! "Save" writes the text, "Clear" erases it, Escape or "Cancel" leaves it
+//! unchanged. While open, gameplay keys and clicks are ignored via
+//! [`ChatState::text_field_focused`].
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::chat::ChatState;
+use crate::cosmos::persistence::DirtyChunks;
+use crate::world::chunk::{Layer, WorldMap};
+use crate::world::ctx::WorldCtx;
+use crate::world::sign::{write_sign, SIGN_TEXT_MAX_CHARS};
+
+/// Sign currently being edited, if any.
+#[derive(Resource, Default, Debug)]
+pub struct SignEditor {
+    /// Tile of the sign being edited.
+    pub target: Option<(i32, i32)>,
+    /// Text in the prompt.
+    pub buffer: String,
+    /// Set on open so the text field grabs keyboard focus once.
+    focus_pending: bool,
+}
+
+impl SignEditor {
+    /// Start editing the sign at `tile`, prefilled with its current text.
+    pub fn open(&mut self, tile: (i32, i32), current: Option<&str>) {
+        self.target = Some(tile);
+        self.buffer = current.unwrap_or_default().to_string();
+        self.focus_pending = true;
+    }
+
+    pub fn close(&mut self) {
+        self.target = None;
+        self.buffer.clear();
+        self.focus_pending = false;
+    }
+}
+
+/// Draw the sign prompt and apply the player's choice.
+pub fn draw_sign_editor(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<SignEditor>,
+    mut world_map: ResMut<WorldMap>,
+    mut dirty_chunks: ResMut<DirtyChunks>,
+    mut chat_state: Option<ResMut<ChatState>>,
+    ctx: WorldCtx,
+) -> Result {
+    let ctx_ref = ctx.as_ref();
+    // The sign may have been broken while the prompt was open.
+    let target = editor.target.filter(|&(x, y)| {
+        world_map
+            .get_tile(x, y, Layer::Fg, &ctx_ref)
+            .is_some_and(|tile| ctx_ref.tile_registry.get(tile).sign)
+    });
+    let Some((tile_x, tile_y)) = target else {
+        if editor.target.is_some() {
+            editor.close();
+            if let Some(chat) = chat_state.as_mut() {
+                chat.text_field_focused = false;
+            }
+        }
+        return Ok(());
+    };
+    if let Some(chat) = chat_state.as_mut()
+        && !chat.text_field_focused
+    {
+        chat.text_field_focused = true;
+    }
+
+    let egui_ctx = contexts.ctx_mut()?;
+    let mut save = false;
+    let mut clear = false;
+    let mut cancel = false;
+
+    egui::Window::new("Sign")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(egui_ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut editor.buffer)
+                    .char_limit(SIGN_TEXT_MAX_CHARS)
+                    .desired_width(240.0)
+                    .hint_text("Write something..."),
+            );
+            if editor.focus_pending {
+                response.request_focus();
+                editor.focus_pending = false;
+            }
+            let (enter, escape) = ui.input(|i| {
+                (
+                    i.key_pressed(egui::Key::Enter),
+                    i.key_pressed(egui::Key::Escape),
+                )
+            });
+            save |= enter;
+            cancel |= escape;
+            ui.horizontal(|ui| {
+                save |= ui.button("Save").clicked();
+                clear |= ui.button("Clear").clicked();
+                cancel |= ui.button("Cancel").clicked();
+            });
+        });
+
+    if save || clear {
+        let text = if clear { "" } else { editor.buffer.as_str() };
+        write_sign(
+            &mut world_map,
+            &mut dirty_chunks,
+            tile_x,
+            tile_y,
+            text,
+            &ctx_ref,
+        );
+    }
+    if save || clear || cancel {
+        editor.close();
+        if let Some(chat) = chat_state.as_mut() {
+            chat.text_field_focused = false;
+        }
+    }
+    Ok(())
+}
@@ -1,31 +1,104 @@
 //! Sync UI slot visuals with backing Inventory data.
+//!
+//! Slots are redrawn only when the player's inventory messages say they
+//! changed (or the slot widget is new), not every frame. Main-bag slots in
+//! locked rows are greyed out; equipment slots show what is worn. Slot
+//! frames take the theme colour of the held item's rarity, so every slot is
+//! redrawn when the theme changes.
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::ui::widget::ImageNode;
 
 use super::components::{DurabilityBar, Hand, ItemCount, ItemIcon, SlotFrame, SlotType, UiSlot};
 use super::icon_registry::ItemIconRegistry;
+use super::theme::UiTheme;
 use super::SlotFrames;
-use crate::inventory::Hotbar;
 use crate::inventory::Inventory;
+use crate::inventory::{BagSlot, HotbarChanged, InventoryChanged, InventoryRefresh};
+use crate::inventory::{Equipment, Hotbar};
 use crate::item::ItemRegistry;
 use crate::player::Player;
 
-/// Sync inventory bag slot backgrounds (tinted when occupied).
+/// Slot widgets that need redrawing this frame.
+#[derive(Debug, Default)]
+pub struct DirtySlots {
+    all: bool,
+    bag: Vec<BagSlot>,
+    /// Hotbar slots show inventory totals, so any bag change dirties them all.
+    all_hotbar: bool,
+    hotbar: Vec<usize>,
+}
+
+impl DirtySlots {
+    pub fn contains(&self, slot: SlotType) -> bool {
+        if self.all {
+            return true;
+        }
+        match slot {
+            SlotType::MainBag(idx) => self.bag.contains(&BagSlot::Main(idx)),
+            SlotType::MaterialBag(idx) => self.bag.contains(&BagSlot::Material(idx)),
+            SlotType::Hotbar { index, .. } => self.all_hotbar || self.hotbar.contains(&index),
+            SlotType::Equipment(_) | SlotType::Catalog(_) => false,
+        }
+    }
+}
+
+/// Readers for the inventory change messages, one cursor per system.
+#[derive(SystemParam)]
+pub struct SlotChangeReader<'w, 's> {
+    inventory: MessageReader<'w, 's, InventoryChanged>,
+    hotbar: MessageReader<'w, 's, HotbarChanged>,
+    refresh: MessageReader<'w, 's, InventoryRefresh>,
+}
+
+impl SlotChangeReader<'_, '_> {
+    /// Consume this frame's messages and return the slots of `player` they
+    /// touch.
+    pub fn dirty_slots(&mut self, player: Entity) -> DirtySlots {
+        let mut dirty = DirtySlots {
+            all: self.refresh.read().any(|msg| msg.entity == player),
+            ..default()
+        };
+        for msg in self.inventory.read().filter(|msg| msg.entity == player) {
+            dirty.bag.extend_from_slice(&msg.slots);
+            dirty.all_hotbar = true;
+        }
+        for msg in self.hotbar.read().filter(|msg| msg.entity == player) {
+            dirty.hotbar.extend_from_slice(&msg.slots);
+        }
+        dirty
+    }
+}
+
+/// Background of main-bag slots in locked rows.
+const LOCKED_SLOT_COLOR: Color = Color::srgba(0.05, 0.05, 0.05, 0.8);
+
+/// Sync inventory bag slot backgrounds (tinted when occupied, dark when
+/// locked).
 pub fn sync_slot_contents(
-    inventory_query: Query<&Inventory, With<Player>>,
-    mut slot_query: Query<(&UiSlot, &mut BackgroundColor)>,
+    inventory_query: Query<(Entity, &Inventory), With<Player>>,
+    mut changes: SlotChangeReader,
+    mut slot_query: Query<(Ref<UiSlot>, &mut BackgroundColor)>,
 ) {
-    let Ok(inventory) = inventory_query.single() else {
+    let Ok((player, inventory)) = inventory_query.single() else {
         return;
     };
+    let dirty = changes.dirty_slots(player);
 
     for (slot, mut bg_color) in &mut slot_query {
+        if !slot.is_added() && !dirty.contains(slot.slot_type) {
+            continue;
+        }
         let item_opt = match slot.slot_type {
+            SlotType::MainBag(idx) if !inventory.is_unlocked(BagSlot::Main(idx)) => {
+                *bg_color = BackgroundColor(LOCKED_SLOT_COLOR);
+                continue;
+            }
             SlotType::MainBag(idx) => inventory.main_bag.get(idx).and_then(|s| s.as_ref()),
             SlotType::MaterialBag(idx) => inventory.material_bag.get(idx).and_then(|s| s.as_ref()),
             SlotType::Hotbar { .. } => continue,
-            SlotType::Equipment(_) => continue,
+            SlotType::Equipment(_) | SlotType::Catalog(_) => continue,
         };
 
         if item_opt.is_some() {
@@ -37,16 +110,20 @@ pub fn sync_slot_contents(
 }
 
 /// Update slot icons, frames, and counts from inventory/hotbar data.
-/// Only runs when Inventory or Hotbar components have changed.
+/// Only touches slots named by this frame's change messages, or every slot
+/// after a theme change.
+#[allow(clippy::too_many_arguments)]
 pub fn update_slot_icons(
-    inventory_query: Query<Ref<Inventory>, With<Player>>,
-    hotbar_query: Query<Ref<Hotbar>, With<Player>>,
+    inventory_query: Query<(Entity, &Inventory, Option<&Equipment>), With<Player>>,
+    hotbar_query: Query<&Hotbar, With<Player>>,
+    mut changes: SlotChangeReader,
     item_registry: Res<ItemRegistry>,
     icon_registry: Res<ItemIconRegistry>,
     slot_frames: Res<SlotFrames>,
+    theme: Res<UiTheme>,
 
     // Query for slots with children
-    slot_query: Query<(Entity, &UiSlot), With<Children>>,
+    slot_query: Query<(Entity, Ref<UiSlot>), With<Children>>,
     // Single query for ImageNode children — Has<T> used to distinguish icon vs frame
     mut image_query: Query<(&mut ImageNode, Has<ItemIcon>, Has<SlotFrame>)>,
     mut count_query: Query<&mut Text, With<ItemCount>>,
@@ -54,19 +131,19 @@ pub fn update_slot_icons(
     mut durability_query: Query<(&mut Node, &mut BackgroundColor, &mut Visibility), With<DurabilityBar>>,
     children_query: Query<&Children>,
 ) {
-    let Ok(inventory) = inventory_query.single() else {
+    let Ok((player, inventory, equipment)) = inventory_query.single() else {
         return;
     };
     let Ok(hotbar) = hotbar_query.single() else {
         return;
     };
-
-    // Skip if neither changed
-    if !inventory.is_changed() && !hotbar.is_changed() {
-        return;
-    }
+    let dirty = changes.dirty_slots(player);
+    let restyle = theme.is_changed();
 
     for (entity, slot) in &slot_query {
+        if !slot.is_added() && !restyle && !dirty.contains(slot.slot_type) {
+            continue;
+        }
         // Get item data for this slot
         let item_data: Option<(&str, u16)> = match slot.slot_type {
             SlotType::MainBag(idx) => inventory
@@ -92,7 +169,10 @@ pub fn update_slot_icons(
                     (id, count.min(u16::MAX as u32) as u16)
                 })
             }
-            SlotType::Equipment(_) => continue,
+            SlotType::Equipment(equip_slot) => equipment
+                .and_then(|e| e.get(equip_slot.equipment_slot()))
+                .map(|id| (id.as_str(), 1)),
+            SlotType::Catalog(_) => continue,
         };
 
         // Get children of this slot
@@ -107,6 +187,7 @@ pub fn update_slot_icons(
             };
 
             let depleted = count == 0;
+            let rarity = theme.colors.rarity(item_registry.get(item_id_typed).rarity);
 
             for child in children.iter() {
                 // Update icon or frame image
@@ -123,6 +204,7 @@ pub fn update_slot_icons(
                         };
                     } else if is_frame {
                         image_node.image = slot_frames.common.clone();
+                        image_node.color = rarity;
                     }
                 }
                 // Update count
@@ -217,3 +299,167 @@ fn resolve_stack_durability(
         .and_then(|stats| stats.durability)?;
     Some((current, max))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{emit_inventory_changes, BagTarget};
+    use crate::item::{ItemDef, ItemType, Rarity};
+    use crate::test_helpers::fixtures;
+    use crate::ui::game_ui::theme::HexColor;
+
+    const SENTINEL: Color = Color::srgb(1.0, 0.0, 1.0);
+
+    fn slot_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<InventoryChanged>()
+            .add_message::<HotbarChanged>()
+            .add_message::<InventoryRefresh>()
+            .add_systems(
+                PostUpdate,
+                (emit_inventory_changes, sync_slot_contents).chain(),
+            );
+        let player = app
+            .world_mut()
+            .spawn((Player, Inventory::new(), Hotbar::new()))
+            .id();
+        let slot = app
+            .world_mut()
+            .spawn((
+                UiSlot {
+                    slot_type: SlotType::MainBag(0),
+                },
+                BackgroundColor(SENTINEL),
+            ))
+            .id();
+        (app, player, slot)
+    }
+
+    fn background(app: &App, slot: Entity) -> Color {
+        app.world().get::<BackgroundColor>(slot).unwrap().0
+    }
+
+    fn paint(app: &mut App, slot: Entity) {
+        app.world_mut().get_mut::<BackgroundColor>(slot).unwrap().0 = SENTINEL;
+    }
+
+    #[test]
+    fn slot_redraws_only_when_its_slot_changes() {
+        let (mut app, player, slot) = slot_app();
+
+        // New slot widgets are drawn once.
+        app.update();
+        assert_ne!(background(&app, slot), SENTINEL);
+
+        // Nothing changed: the system leaves the widget alone.
+        paint(&mut app, slot);
+        app.update();
+        assert_eq!(background(&app, slot), SENTINEL);
+
+        // A change to another slot does not touch this one.
+        app.world_mut()
+            .get_mut::<Inventory>(player)
+            .unwrap()
+            .slot_mut(BagSlot::Main(5));
+        app.update();
+        assert_eq!(background(&app, slot), SENTINEL);
+
+        app.world_mut()
+            .get_mut::<Inventory>(player)
+            .unwrap()
+            .try_add_item("dirt", 1, 999, BagTarget::Main);
+        app.update();
+        assert_eq!(background(&app, slot), Color::srgb(0.2, 0.4, 0.2));
+    }
+
+    #[test]
+    fn locked_slots_grey_out_until_unlocked() {
+        let (mut app, player, _) = slot_app();
+        let first_locked = app.world().get::<Inventory>(player).unwrap().slot_count();
+        let locked = app
+            .world_mut()
+            .spawn((
+                UiSlot {
+                    slot_type: SlotType::MainBag(first_locked),
+                },
+                BackgroundColor(SENTINEL),
+            ))
+            .id();
+        app.update();
+        assert_eq!(background(&app, locked), LOCKED_SLOT_COLOR);
+
+        app.world_mut()
+            .get_mut::<Inventory>(player)
+            .unwrap()
+            .unlock_rows(1);
+        app.update();
+        assert_eq!(background(&app, locked), Color::srgba(0.0, 0.0, 0.0, 0.0));
+    }
+
+    fn gem(rarity: Rarity) -> ItemDef {
+        ItemDef {
+            display_name: "Gem".into(),
+            rarity,
+            ..fixtures::item_def("gem", ItemType::Resource)
+        }
+    }
+
+    #[test]
+    fn slot_frame_takes_the_rarity_color_of_its_item() {
+        let theme = crate::test_helpers::fixtures::test_ui_theme();
+        let rare = theme.colors.rarity(Rarity::Rare);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<InventoryChanged>()
+            .add_message::<HotbarChanged>()
+            .add_message::<InventoryRefresh>()
+            .insert_resource(ItemRegistry::from_defs(vec![gem(Rarity::Rare)]))
+            .insert_resource(ItemIconRegistry::new())
+            .insert_resource(SlotFrames {
+                common: Handle::default(),
+            })
+            .insert_resource(theme)
+            .add_systems(
+                PostUpdate,
+                (emit_inventory_changes, update_slot_icons).chain(),
+            );
+        let mut inventory = Inventory::new();
+        inventory.try_add_item("gem", 1, 99, BagTarget::Main);
+        app.world_mut().spawn((Player, inventory, Hotbar::new()));
+        let frame = app.world_mut().spawn((ImageNode::default(), SlotFrame)).id();
+        app.world_mut()
+            .spawn(UiSlot {
+                slot_type: SlotType::MainBag(0),
+            })
+            .add_child(frame);
+        app.update();
+        assert_eq!(app.world().get::<ImageNode>(frame).unwrap().color, rare);
+
+        // A reloaded theme restyles slots that did not change.
+        let pink = HexColor("#ff0080".into());
+        app.world_mut().resource_mut::<UiTheme>().colors.rarity_rare = pink.clone();
+        app.update();
+        assert_eq!(
+            app.world().get::<ImageNode>(frame).unwrap().color,
+            Color::from(pink)
+        );
+    }
+
+    #[test]
+    fn dirty_slots_cover_hotbar_after_bag_change() {
+        let dirty = DirtySlots {
+            bag: vec![BagSlot::Material(2)],
+            all_hotbar: true,
+            ..default()
+        };
+        assert!(dirty.contains(SlotType::MaterialBag(2)));
+        assert!(!dirty.contains(SlotType::MainBag(2)));
+        assert!(dirty.contains(SlotType::Hotbar {
+            index: 5,
+            hand: Hand::Right
+        }));
+        assert!(!dirty.contains(SlotType::Catalog(0)));
+        assert!(!DirtySlots::default().contains(SlotType::MainBag(0)));
+    }
+}
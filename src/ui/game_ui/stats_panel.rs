@@ -0,0 +1,185 @@
+//! Stats column of the inventory screen.
+//!
+//! Shows [`PlayerStatsSummary`], the same numbers gameplay uses. Values a
+//! modifier is changing are drawn in the theme's `stat_modified` color with
+//! the base value in parentheses.
+
+use bevy::picking::prelude::*;
+use bevy::prelude::*;
+
+use super::theme::UiTheme;
+use crate::player::stats::{MovementStats, PlayerStatsSummary, Stat};
+
+/// Width of the stats column in UI pixels.
+pub const STATS_COLUMN_WIDTH: f32 = 140.0;
+
+/// One line of the stats column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatRow {
+    Health,
+    Oxygen,
+    Movement,
+    Speed,
+    Jump,
+}
+
+impl StatRow {
+    const ALL: [StatRow; 5] = [
+        StatRow::Health,
+        StatRow::Oxygen,
+        StatRow::Movement,
+        StatRow::Speed,
+        StatRow::Jump,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            StatRow::Health => "Health",
+            StatRow::Oxygen => "Oxygen",
+            StatRow::Movement => "Moving",
+            StatRow::Speed => "Speed",
+            StatRow::Jump => "Jump",
+        }
+    }
+}
+
+/// Marks the value text of a stats row.
+#[derive(Component)]
+pub struct StatValueText(pub StatRow);
+
+/// Spawn the stats column as a child of the inventory body.
+pub fn spawn_stats_column(parent: &mut ChildSpawnerCommands, theme: &UiTheme) {
+    let text_dim = Color::from(theme.colors.text_dim.clone());
+    let text_color = Color::from(theme.colors.text.clone());
+    let font_size = theme.font_size;
+
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(STATS_COLUMN_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .with_children(|column| {
+            for row in StatRow::ALL {
+                column
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            justify_content: JustifyContent::SpaceBetween,
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    ))
+                    .with_children(|line| {
+                        line.spawn((
+                            Text::new(row.label()),
+                            TextFont {
+                                font_size,
+                                ..default()
+                            },
+                            TextColor(text_dim),
+                            Pickable::IGNORE,
+                        ));
+                        line.spawn((
+                            StatValueText(row),
+                            Text::new("—"),
+                            TextFont {
+                                font_size,
+                                ..default()
+                            },
+                            TextColor(text_color),
+                            Pickable::IGNORE,
+                        ));
+                    });
+            }
+        });
+}
+
+/// Text for a row, and whether a modifier is changing it.
+pub fn stat_row_value(summary: &PlayerStatsSummary, row: StatRow) -> (String, bool) {
+    let stat = |stat: Stat| {
+        if stat.is_modified() {
+            (format!("{:.0} ({:.0})", stat.current, stat.base), true)
+        } else {
+            (format!("{:.0}", stat.current), false)
+        }
+    };
+    let none = || ("—".to_string(), false);
+    match row {
+        StatRow::Health => (
+            format!("{:.0} / {:.0}", summary.health.current, summary.health.max),
+            false,
+        ),
+        StatRow::Oxygen => summary.oxygen.map_or_else(none, |o| {
+            (format!("{:.0} / {:.0}", o.current, o.max), false)
+        }),
+        StatRow::Movement => match summary.movement {
+            Some(MovementStats::Walking { .. }) => ("Walking".to_string(), false),
+            Some(MovementStats::Swimming { .. }) => ("Swimming".to_string(), true),
+            Some(MovementStats::Eva) => ("EVA".to_string(), true),
+            None => none(),
+        },
+        StatRow::Speed => match summary.movement {
+            Some(MovementStats::Walking { speed, .. } | MovementStats::Swimming { speed }) => {
+                stat(speed)
+            }
+            _ => none(),
+        },
+        StatRow::Jump => match summary.movement {
+            Some(MovementStats::Walking { jump_velocity, .. }) => stat(jump_velocity),
+            _ => none(),
+        },
+    }
+}
+
+/// Refresh the stats column when the summary changes.
+pub fn update_stats_column(
+    summary: Res<PlayerStatsSummary>,
+    theme: Res<UiTheme>,
+    mut texts: Query<(&StatValueText, &mut Text, &mut TextColor)>,
+) {
+    let text_color = Color::from(theme.colors.text.clone());
+    let modified_color = Color::from(theme.colors.stat_modified.clone());
+    for (row, mut text, mut color) in &mut texts {
+        let (value, modified) = stat_row_value(&summary, row.0);
+        text.0 = value;
+        color.0 = if modified { modified_color } else { text_color };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::stats::Meter;
+
+    #[test]
+    fn modified_stats_show_their_base_value() {
+        let summary = PlayerStatsSummary {
+            health: Meter {
+                current: 70.0,
+                max: 100.0,
+            },
+            oxygen: None,
+            movement: Some(MovementStats::Swimming {
+                speed: Stat {
+                    base: 200.0,
+                    current: 100.0,
+                },
+            }),
+        };
+        assert_eq!(
+            stat_row_value(&summary, StatRow::Health),
+            ("70 / 100".to_string(), false)
+        );
+        assert_eq!(
+            stat_row_value(&summary, StatRow::Speed),
+            ("100 (200)".to_string(), true)
+        );
+        assert_eq!(stat_row_value(&summary, StatRow::Jump).0, "—");
+        assert_eq!(stat_row_value(&summary, StatRow::Oxygen).0, "—");
+    }
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy::reflect::TypePath;
 use serde::Deserialize;
 
+use crate::item::Rarity;
+
 /// Parsed hex color wrapper for RON deserialization.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(transparent)]
@@ -37,6 +39,26 @@ pub struct UiColors {
     pub rarity_uncommon: HexColor,
     pub rarity_rare: HexColor,
     pub rarity_legendary: HexColor,
+    /// Player stats that a modifier is currently changing.
+    #[serde(default = "default_stat_modified")]
+    pub stat_modified: HexColor,
+}
+
+fn default_stat_modified() -> HexColor {
+    HexColor("#66ccff".into())
+}
+
+impl UiColors {
+    /// Colour of item names, slot frames and drop glows for `rarity`.
+    pub fn rarity(&self, rarity: Rarity) -> Color {
+        let hex = match rarity {
+            Rarity::Common => &self.rarity_common,
+            Rarity::Uncommon => &self.rarity_uncommon,
+            Rarity::Rare => &self.rarity_rare,
+            Rarity::Legendary => &self.rarity_legendary,
+        };
+        Color::from(hex.clone())
+    }
 }
 
 /// 9-slice texture configuration.
@@ -122,6 +144,61 @@ pub struct ChatConfig {
     pub active_bg_color: HexColor,
 }
 
+/// How the outline of the tile under the cursor is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum TileOutlineStyle {
+    /// Textured sprite border.
+    #[default]
+    Sprite,
+    /// Gizmo lines with a soft feathered edge.
+    Gizmo,
+}
+
+/// Targeted-tile outline configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TileOutlineConfig {
+    pub style: TileOutlineStyle,
+    pub color: HexColor,
+    /// Gizmo line width in screen pixels.
+    pub width: f32,
+    /// Width in pixels of the faded edge drawn around gizmo lines to
+    /// anti-alias them; 0 disables it.
+    pub feather: f32,
+}
+
+impl Default for TileOutlineConfig {
+    fn default() -> Self {
+        Self {
+            style: TileOutlineStyle::default(),
+            color: HexColor("#ffffffaa".into()),
+            width: 1.5,
+            feather: 1.0,
+        }
+    }
+}
+
+/// Accent colours of notification toasts, one per kind.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NotificationColors {
+    pub info: HexColor,
+    pub success: HexColor,
+    pub warning: HexColor,
+    pub error: HexColor,
+}
+
+impl Default for NotificationColors {
+    fn default() -> Self {
+        Self {
+            info: HexColor("#3c96ff".into()),
+            success: HexColor("#32c846".into()),
+            warning: HexColor("#e6c832".into()),
+            error: HexColor("#dc3232".into()),
+        }
+    }
+}
+
 /// Root UI theme loaded from RON.
 #[derive(Asset, TypePath, Debug, Clone, Deserialize, Resource)]
 #[allow(dead_code)]
@@ -134,6 +211,18 @@ pub struct UiTheme {
     pub tooltip: TooltipConfig,
     pub panel_texture: Option<SliceConfig>,
     pub chat: ChatConfig,
+    #[serde(default)]
+    pub tile_outline: TileOutlineConfig,
+    #[serde(default)]
+    pub notifications: NotificationColors,
+    /// Strength of the rarity-coloured outline on dropped items above
+    /// common; 0 disables it.
+    #[serde(default = "default_drop_glow")]
+    pub drop_glow: f32,
+}
+
+fn default_drop_glow() -> f32 {
+    0.6
 }
 
 #[cfg(test)]
@@ -161,4 +250,21 @@ mod tests {
         assert!((c.red - 1.0).abs() < 0.01);
         assert!((c.alpha - 0.502).abs() < 0.01);
     }
+
+    #[test]
+    fn rarity_resolves_to_its_theme_color() {
+        let theme = crate::test_helpers::fixtures::test_ui_theme();
+        assert_eq!(
+            theme.colors.rarity(Rarity::Common),
+            Color::from(HexColor("#aaaaaa".into()))
+        );
+        assert_eq!(
+            theme.colors.rarity(Rarity::Rare),
+            Color::from(theme.colors.rarity_rare.clone())
+        );
+        assert_eq!(
+            theme.colors.rarity(Rarity::Legendary),
+            Color::srgb(1.0, 170.0 / 255.0, 0.0)
+        );
+    }
 }
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy::ui::widget::ImageNode;
 use bevy::window::PrimaryWindow;
 
+use super::catalog::CatalogState;
 use super::components::*;
 use super::icon_registry::ItemIconRegistry;
 use super::theme::UiTheme;
@@ -132,13 +133,16 @@ pub fn spawn_tooltip(
 }
 
 /// Resolve hovered item, update UiTooltip data, position, and visibility.
+#[allow(clippy::too_many_arguments)]
 pub fn update_tooltip(
     mut tooltip_query: Query<(&mut Node, &mut Visibility, &mut UiTooltip)>,
     hovered: Res<HoveredSlot>,
     inventory_query: Query<&Inventory, With<Player>>,
     hotbar_query: Query<&Hotbar, With<Player>>,
+    catalog: Res<CatalogState>,
     window: Query<&Window, With<PrimaryWindow>>,
     theme: Res<UiTheme>,
+    ui_scale: Res<UiScale>,
 ) {
     let Ok((mut node, mut vis, mut tooltip)) = tooltip_query.single_mut() else {
         return;
@@ -178,6 +182,7 @@ pub fn update_tooltip(
             }
         }
         SlotType::Equipment(_) => None,
+        SlotType::Catalog(idx) => catalog.entries.get(idx).map(String::as_str),
     };
 
     let Some(item_id_str) = item_id_str else {
@@ -196,10 +201,12 @@ pub fn update_tooltip(
         return;
     };
     if let Some(cursor_pos) = window.cursor_position() {
+        // Work in UI pixels so the clamp below matches the scaled layout.
+        let cursor_pos = cursor_pos / ui_scale.0;
         let offset = theme.tooltip.padding;
         let max_w = theme.tooltip.max_width;
-        let win_w = window.width();
-        let win_h = window.height();
+        let win_w = window.width() / ui_scale.0;
+        let win_h = window.height() / ui_scale.0;
         let tip_x = if cursor_pos.x + offset + max_w > win_w {
             (cursor_pos.x - offset - max_w).max(0.0)
         } else {
@@ -262,7 +269,7 @@ pub(super) fn render_tooltip_content(
                 String::new()
             };
             *text = Text::new(format!("{}{}", def.display_name, suffix));
-            *color = TextColor(rarity_color(&def.rarity, &theme));
+            *color = TextColor(theme.colors.rarity(def.rarity));
         }
         if let Ok(mut text) = type_q.get_mut(descendant) {
             *text = Text::new(format!(
@@ -295,15 +302,6 @@ pub(super) fn render_tooltip_content(
     }
 }
 
-fn rarity_color(rarity: &Rarity, theme: &UiTheme) -> Color {
-    match rarity {
-        Rarity::Common => Color::from(theme.colors.rarity_common.clone()),
-        Rarity::Uncommon => Color::from(theme.colors.rarity_uncommon.clone()),
-        Rarity::Rare => Color::from(theme.colors.rarity_rare.clone()),
-        Rarity::Legendary => Color::from(theme.colors.rarity_legendary.clone()),
-    }
-}
-
 fn rarity_label(rarity: &Rarity) -> &'static str {
     match rarity {
         Rarity::Common => "Common",
@@ -345,3 +343,47 @@ fn build_stats_text(def: &crate::item::definition::ItemDef) -> String {
     }
     parts.join(" · ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::definition::ItemDef;
+    use crate::test_helpers::fixtures;
+
+    fn relic() -> ItemDef {
+        ItemDef {
+            display_name: "Relic".into(),
+            max_stack: 1,
+            rarity: Rarity::Legendary,
+            ..fixtures::item_def("relic", ItemType::Resource)
+        }
+    }
+
+    #[test]
+    fn tooltip_name_takes_the_rarity_color() {
+        let theme = crate::test_helpers::fixtures::test_ui_theme();
+        let legendary = theme.colors.rarity(Rarity::Legendary);
+        let mut app = App::new();
+        app.insert_resource(ItemRegistry::from_defs(vec![relic()]))
+            .insert_resource(ItemIconRegistry::new())
+            .insert_resource(theme)
+            .add_systems(Update, render_tooltip_content);
+        let name = app
+            .world_mut()
+            .spawn((Text::default(), TextColor::default(), TooltipName))
+            .id();
+        app.world_mut()
+            .spawn((
+                UiTooltip {
+                    item_id: "relic".into(),
+                    count: 1,
+                },
+                Visibility::Visible,
+            ))
+            .add_child(name);
+        app.update();
+
+        assert_eq!(app.world().get::<TextColor>(name).unwrap().0, legendary);
+        assert_eq!(app.world().get::<Text>(name).unwrap().0, "Relic");
+    }
+}
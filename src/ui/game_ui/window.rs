@@ -231,6 +231,7 @@ fn on_window_drag(
     trigger: On<Pointer<Drag>>,
     mut query: Query<&mut Node, With<GameWindow>>,
     drag_state: Res<DragState>,
+    ui_scale: Res<UiScale>,
 ) {
     // Don't move the window while dragging an inventory item.
     if drag_state.dragging.is_some() {
@@ -241,7 +242,8 @@ fn on_window_drag(
         return;
     };
 
-    let delta = trigger.event().delta;
+    // Pointer delta is in logical pixels; margins are scaled by `UiScale`.
+    let delta = trigger.event().delta / ui_scale.0;
     if let Val::Px(ref mut left) = node.margin.left {
         *left += delta.x;
     }
@@ -288,7 +290,7 @@ pub fn close_topmost_on_esc(
     focused: Res<FocusedWindow>,
     chat_state: Res<crate::chat::ChatState>,
 ) {
-    if chat_state.is_active {
+    if chat_state.keyboard_captured() {
         return;
     }
 
@@ -0,0 +1,76 @@
+//! Which UI, if any, owns the mouse and keyboard this frame.
+//!
+//! Clicking inside an egui panel used to fall through to block interaction
+//! and break the blocks behind it. [`update_input_capture`] runs before
+//! [`GameSet::Input`](crate::sets::GameSet::Input) and gathers egui's input
+//! wishes and the game UI state into [`InputCapture`], which gameplay input
+//! systems check before acting.
+
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+
+use super::game_ui::{HoveredSlot, InventoryScreenState};
+use crate::chat::ChatState;
+
+/// Input devices claimed by UI for the current frame.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InputCapture {
+    /// The pointer is over an egui panel or an open inventory slot: clicks
+    /// and the mouse wheel must not reach the world.
+    pub pointer: bool,
+    /// A text field (chat console, egui text edit) has focus: gameplay key
+    /// bindings, movement included, are ignored. A merely visible debug panel
+    /// does not set it.
+    pub keyboard: bool,
+}
+
+/// Refresh [`InputCapture`] from egui and the game UI.
+pub fn update_input_capture(
+    egui_input: Option<Res<EguiWantsInput>>,
+    chat_state: Res<ChatState>,
+    inventory: Res<InventoryScreenState>,
+    hovered: Res<HoveredSlot>,
+    mut capture: ResMut<InputCapture>,
+) {
+    let egui_pointer = egui_input
+        .as_ref()
+        .is_some_and(|input| input.wants_pointer_input());
+    let egui_keyboard = egui_input.is_some_and(|input| input.wants_keyboard_input());
+    capture.pointer = egui_pointer || (inventory.visible && hovered.slot.is_some());
+    capture.keyboard = egui_keyboard || chat_state.keyboard_captured();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::game_ui::SlotType;
+
+    #[test]
+    fn capture_follows_chat_and_hovered_inventory_slots() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ChatState::new(10))
+            .init_resource::<InventoryScreenState>()
+            .init_resource::<HoveredSlot>()
+            .init_resource::<InputCapture>()
+            .add_systems(Update, update_input_capture);
+        app.update();
+        assert_eq!(
+            *app.world().resource::<InputCapture>(),
+            InputCapture::default()
+        );
+
+        app.world_mut().resource_mut::<ChatState>().is_active = true;
+        app.world_mut().resource_mut::<HoveredSlot>().slot = Some(SlotType::MainBag(0));
+        app.update();
+        let capture = *app.world().resource::<InputCapture>();
+        assert!(capture.keyboard);
+        assert!(!capture.pointer, "inventory closed");
+
+        app.world_mut()
+            .resource_mut::<InventoryScreenState>()
+            .visible = true;
+        app.update();
+        assert!(app.world().resource::<InputCapture>().pointer);
+    }
+}
@@ -1,5 +1,8 @@
+pub mod atlas_viewer;
 pub mod debug_panel;
+pub mod display;
 pub mod game_ui;
+pub mod input_capture;
 pub mod star_map;
 
 use bevy::prelude::*;
@@ -16,20 +19,42 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<debug_panel::DebugUiState>()
+            .init_resource::<atlas_viewer::AtlasViewerState>()
             .init_resource::<star_map::StarMapState>()
             .init_resource::<star_map::AutopilotMode>()
+            .init_resource::<input_capture::InputCapture>()
             .add_message::<WarpToBody>()
             .add_message::<WarpToShip>()
             .add_message::<star_map::NavigateToBody>()
             .add_plugins(GameUiPlugin)
             .add_systems(
                 Update,
-                (debug_panel::toggle_debug_panel, star_map::toggle_star_map).in_set(GameSet::Ui),
+                (
+                    debug_panel::toggle_debug_panel,
+                    atlas_viewer::toggle_atlas_viewer,
+                    star_map::toggle_star_map,
+                )
+                    .in_set(GameSet::Ui),
+            )
+            .add_systems(
+                Update,
+                input_capture::update_input_capture
+                    .before(GameSet::Input)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, display::toggle_fullscreen)
+            .add_systems(
+                Update,
+                display::fit_ui_scale.run_if(in_state(AppState::InGame)),
             )
             .add_systems(
                 EguiPrimaryContextPass,
                 debug_panel::draw_debug_panel.run_if(in_state(AppState::InGame)),
             )
+            .add_systems(
+                EguiPrimaryContextPass,
+                atlas_viewer::draw_atlas_viewer.run_if(in_state(AppState::InGame)),
+            )
             .add_systems(
                 EguiPrimaryContextPass,
                 star_map::draw_star_map.run_if(in_state(AppState::InGame)),
@@ -42,6 +67,18 @@ impl Plugin for UiPlugin {
                 EguiPrimaryContextPass,
                 game_ui::health_hud::draw_health_hud.run_if(in_state(AppState::InGame)),
             )
+            .add_systems(
+                EguiPrimaryContextPass,
+                game_ui::mode_hint::draw_mode_hint.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                EguiPrimaryContextPass,
+                game_ui::sign_editor::draw_sign_editor.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                EguiPrimaryContextPass,
+                game_ui::notifications::draw_notifications.run_if(in_state(AppState::InGame)),
+            )
             .add_systems(Update, handle_warp.run_if(in_state(AppState::InGame)))
             .add_systems(Update, handle_warp_to_ship.run_if(in_state(AppState::InGame)))
             .add_systems(Update, handle_navigate.run_if(in_state(AppState::InGame)))
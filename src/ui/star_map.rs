@@ -183,6 +183,7 @@ pub fn draw_star_map(
                 let type_color = match body.planet_type_id.as_str() {
                     "garden" => egui::Color32::from_rgb(100, 200, 100),
                     "barren" => egui::Color32::from_rgb(180, 150, 120),
+                    "moon" => egui::Color32::from_rgb(190, 190, 195),
                     _ => egui::Color32::from_rgb(160, 160, 200),
                 };
 
@@ -9,7 +9,7 @@ pub mod wind;
 use bevy::prelude::*;
 
 use crate::registry::AppState;
-use crate::sets::GameSet;
+use crate::sets::WorldSet;
 
 pub use weather_state::WeatherState;
 pub use wind::Wind;
@@ -32,21 +32,25 @@ impl Plugin for WeatherPlugin {
             )
             .add_systems(
                 Update,
-                (wind::update_wind, weather_state::update_weather)
-                    .in_set(GameSet::WorldUpdate)
+                (
+                    wind::update_wind,
+                    wind::sync_tile_sway.after(wind::update_wind),
+                    weather_state::update_weather,
+                )
+                    .in_set(WorldSet::Sim)
                     .run_if(in_state(AppState::InGame)),
             )
             .add_systems(
                 Update,
                 precipitation::resolve_weather_type_system
-                    .in_set(GameSet::WorldUpdate)
+                    .in_set(WorldSet::Sim)
                     .run_if(in_state(AppState::InGame))
                     .after(weather_state::update_weather),
             )
             .add_systems(
                 Update,
                 (fog::update_fog_overlay, fog::update_fog_clouds)
-                    .in_set(GameSet::WorldUpdate)
+                    .in_set(WorldSet::Sim)
                     .run_if(in_state(AppState::InGame))
                     .after(precipitation::resolve_weather_type_system),
             )
@@ -58,7 +62,7 @@ impl Plugin for WeatherPlugin {
                     particles::rebuild_weather_mesh,
                 )
                     .chain()
-                    .in_set(GameSet::WorldUpdate)
+                    .in_set(WorldSet::Sim)
                     .run_if(in_state(AppState::InGame))
                     .run_if(resource_exists::<particles::WeatherParticleMaterial>)
                     .after(precipitation::resolve_weather_type_system),
@@ -71,7 +75,7 @@ impl Plugin for WeatherPlugin {
                     snow_overlay::update_tree_snow,
                     snow_overlay::cleanup_tree_snow,
                 )
-                    .in_set(GameSet::WorldUpdate)
+                    .in_set(WorldSet::RenderPrep)
                     .run_if(in_state(AppState::InGame)),
             );
     }
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use rand::Rng;
 
+use crate::world::tile_renderer::{SharedTileMaterial, TileMaterial};
+
 /// Maximum wind speed in pixels per second.
 pub const MAX_WIND_SPEED: f32 = 60.0;
 
@@ -70,3 +72,24 @@ pub fn update_wind(mut wind: ResMut<Wind>, time: Res<Time>) {
     diff = (diff + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
     wind.direction += diff * coeff;
 }
+
+/// System that scales foliage sway in the tile shader by the current wind
+/// strength. Only touches the material uniform; chunk meshes are untouched.
+pub fn sync_tile_sway(
+    wind: Res<Wind>,
+    shared_material: Option<Res<SharedTileMaterial>>,
+    mut tile_materials: ResMut<Assets<TileMaterial>>,
+) {
+    let Some(shared_material) = shared_material else {
+        return;
+    };
+    for handle in [&shared_material.fg, &shared_material.bg] {
+        // Read first so an unchanged uniform doesn't mark the asset modified.
+        let stale = tile_materials
+            .get(handle)
+            .is_some_and(|mat| (mat.sway.w - wind.strength).abs() > 0.01);
+        if stale && let Some(mat) = tile_materials.get_mut(handle) {
+            mat.sway.w = wind.strength;
+        }
+    }
+}
@@ -10,6 +10,9 @@ pub struct AtlasParams {
     pub rows: u32,         // 47
     pub atlas_width: u32,  // N_types * tile_size
     pub atlas_height: u32, // rows * tile_size = 752
+    /// Texels trimmed from each edge of a cell's UV rect so nearest sampling
+    /// never reads the neighbouring cell through floating-point error.
+    pub uv_inset: f32,
 }
 
 /// Combined atlas texture handle + layout parameters.
@@ -18,32 +21,38 @@ pub struct AtlasParams {
 pub struct TileAtlas {
     pub image: Handle<Image>,
     pub params: AtlasParams,
+    /// Atlas column of each autotile, by name (for the atlas viewer).
+    pub column_map: std::collections::HashMap<String, u32>,
 }
 
 /// Build a combined horizontal atlas from individual per-type spritesheet images.
-/// Each source image is a single column of `rows` sprites (`tile_size` × `rows*tile_size` px).
+/// Each source image is a single column of `rows` sprites (`tile_size` × `rows*tile_size` px),
+/// or one column per frame for animated autotiles.
 /// Returns the combined Image + column index mapping.
 ///
-/// `sources` is an ordered list of (name, Image) pairs.
-/// Returns (combined Image, HashMap<name, column_index>).
+/// `sources` is an ordered list of (name, Image, frames) triples; a source
+/// takes `frames` adjacent atlas columns, copied from the left of its image.
+/// Returns (combined Image, HashMap<name, first column_index>).
 pub fn build_combined_atlas(
-    sources: &[(&str, &Image)],
+    sources: &[(&str, &Image, u32)],
     tile_size: u32,
     rows: u32,
 ) -> (Image, std::collections::HashMap<String, u32>) {
     use std::collections::HashMap;
 
-    let num_types = sources.len() as u32;
-    let atlas_width = num_types * tile_size;
+    let num_columns: u32 = sources.iter().map(|&(_, _, frames)| frames.max(1)).sum();
+    let atlas_width = num_columns * tile_size;
     let atlas_height = rows * tile_size;
 
     // Create RGBA8 image buffer (zeroed = fully transparent)
     let mut data = vec![0u8; (atlas_width * atlas_height * 4) as usize];
 
     let mut column_map = HashMap::new();
+    let mut first_column = 0;
 
-    for (col_idx, (name, src_image)) in sources.iter().enumerate() {
-        column_map.insert(name.to_string(), col_idx as u32);
+    for &(name, src_image, frames) in sources {
+        let frames = frames.max(1);
+        column_map.insert(name.to_string(), first_column);
 
         let src_data = src_image
             .data
@@ -52,14 +61,15 @@ pub fn build_combined_atlas(
         let src_width = src_image.width();
         let src_height = src_image.height();
 
-        // Copy pixel by pixel from source into atlas column
+        // Copy pixel by pixel from source into the atlas columns; frames
+        // missing from a narrow sheet stay transparent.
         let copy_h = src_height.min(atlas_height);
-        let copy_w = src_width.min(tile_size);
+        let copy_w = src_width.min(frames * tile_size);
 
         for y in 0..copy_h {
             for x in 0..copy_w {
                 let src_idx = ((y * src_width + x) * 4) as usize;
-                let dst_x = col_idx as u32 * tile_size + x;
+                let dst_x = first_column * tile_size + x;
                 let dst_idx = ((y * atlas_width + dst_x) * 4) as usize;
 
                 if src_idx + 3 < src_data.len() && dst_idx + 3 < data.len() {
@@ -70,6 +80,7 @@ pub fn build_combined_atlas(
                 }
             }
         }
+        first_column += frames;
     }
 
     let mut image = Image::new(
@@ -98,15 +109,18 @@ pub fn build_combined_atlas(
 }
 
 /// Compute UV coordinates for a tile sprite in the combined atlas.
-/// Returns (u_min, u_max, v_min, v_max) with half-pixel inset to prevent texture bleeding.
+/// Returns (u_min, u_max, v_min, v_max), inset by `params.uv_inset` texels on
+/// every side to prevent texture bleeding. The inset is in texels, so its UV
+/// size follows the atlas dimensions; it is capped so at least one texel
+/// of the cell stays visible.
 pub fn atlas_uv(column: u32, row: u32, params: &AtlasParams) -> (f32, f32, f32, f32) {
     let ts = params.tile_size as f32;
-    let half = 0.5;
+    let inset = params.uv_inset.clamp(0.0, (ts - 1.0).max(0.0) * 0.5);
 
-    let u_min = (column as f32 * ts + half) / params.atlas_width as f32;
-    let u_max = (column as f32 * ts + ts - half) / params.atlas_width as f32;
-    let v_min = (row as f32 * ts + half) / params.atlas_height as f32;
-    let v_max = (row as f32 * ts + ts - half) / params.atlas_height as f32;
+    let u_min = (column as f32 * ts + inset) / params.atlas_width as f32;
+    let u_max = (column as f32 * ts + ts - inset) / params.atlas_width as f32;
+    let v_min = (row as f32 * ts + inset) / params.atlas_height as f32;
+    let v_max = (row as f32 * ts + ts - inset) / params.atlas_height as f32;
 
     (u_min, u_max, v_min, v_max)
 }
@@ -122,6 +136,7 @@ mod tests {
             rows: 47,
             atlas_width: 48,   // 3 types
             atlas_height: 752, // 47 * 16
+            uv_inset: 0.5,
         };
         let (u_min, u_max, v_min, v_max) = atlas_uv(0, 0, &params);
         assert!(u_min > 0.0, "half-pixel inset");
@@ -137,6 +152,7 @@ mod tests {
             rows: 47,
             atlas_width: 48,
             atlas_height: 752,
+            uv_inset: 0.5,
         };
         let (u_min, _, _, _) = atlas_uv(1, 0, &params);
         let expected = (16.0 + 0.5) / 48.0;
@@ -150,9 +166,105 @@ mod tests {
             rows: 47,
             atlas_width: 48,
             atlas_height: 752,
+            uv_inset: 0.5,
         };
         let (_, _, v_min, v_max) = atlas_uv(0, 46, &params);
         assert!(v_min > 46.0 * 16.0 / 752.0);
         assert!(v_max < 47.0 * 16.0 / 752.0);
     }
+
+    #[test]
+    fn atlas_uv_inset_is_half_a_texel_inside_the_cell() {
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 48,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let (u_min, u_max, v_min, v_max) = atlas_uv(2, 3, &params);
+        let (texel_u, texel_v) = (1.0 / 48.0, 1.0 / 752.0);
+        assert!((u_min - (32.0 / 48.0 + 0.5 * texel_u)).abs() < 1e-6);
+        assert!((u_max - (48.0 / 48.0 - 0.5 * texel_u)).abs() < 1e-6);
+        assert!((v_min - (48.0 / 752.0 + 0.5 * texel_v)).abs() < 1e-6);
+        assert!((v_max - (64.0 / 752.0 - 0.5 * texel_v)).abs() < 1e-6);
+        // Still inside the cell it addresses.
+        assert!(u_min > 32.0 / 48.0 && u_max < 1.0);
+        assert!(v_min > 48.0 / 752.0 && v_max < 64.0 / 752.0);
+    }
+
+    #[test]
+    fn atlas_uv_inset_scales_with_atlas_size() {
+        let narrow = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 32,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let wide = AtlasParams {
+            atlas_width: 64,
+            ..narrow.clone()
+        };
+        let (narrow_min, ..) = atlas_uv(0, 0, &narrow);
+        let (wide_min, ..) = atlas_uv(0, 0, &wide);
+        assert!((narrow_min - 2.0 * wide_min).abs() < 1e-6);
+    }
+
+    #[test]
+    fn atlas_uv_inset_is_configurable_and_capped() {
+        let mut params = AtlasParams {
+            tile_size: 16,
+            rows: 1,
+            atlas_width: 16,
+            atlas_height: 16,
+            uv_inset: 0.0,
+        };
+        assert_eq!(atlas_uv(0, 0, &params), (0.0, 1.0, 0.0, 1.0));
+
+        // An oversized inset never flips the rect inside out.
+        params.uv_inset = 100.0;
+        let (u_min, u_max, v_min, v_max) = atlas_uv(0, 0, &params);
+        assert!(u_min < u_max && v_min < v_max);
+    }
+
+    fn sheet(width: u32, height: u32, fill: u8) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[fill, fill, fill, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            default(),
+        )
+    }
+
+    #[test]
+    fn animated_sources_take_one_column_per_frame() {
+        let dirt = sheet(16, 32, 10);
+        let water = sheet(48, 32, 20);
+        let stone = sheet(16, 32, 30);
+        let (image, columns) = build_combined_atlas(
+            &[
+                ("dirt", &dirt, 1),
+                ("water", &water, 3),
+                ("stone", &stone, 1),
+            ],
+            16,
+            2,
+        );
+        assert_eq!(image.width(), 5 * 16);
+        assert_eq!(columns["dirt"], 0);
+        assert_eq!(columns["water"], 1);
+        assert_eq!(columns["stone"], 4);
+
+        let data = image.data.as_ref().unwrap();
+        let texel = |column: u32| data[(column * 16 * 4) as usize];
+        assert_eq!(texel(0), 10);
+        assert_eq!([texel(1), texel(2), texel(3)], [20; 3]);
+        assert_eq!(texel(4), 30);
+    }
 }
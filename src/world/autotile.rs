@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
-use crate::registry::assets::{AutotileAsset, SpriteVariant};
+use crate::registry::assets::{AutotileAsset, AutotileFallback, SpriteVariant, TileAnimation};
+use crate::registry::tile::{TileOrientation, TileRegistry};
 
 /// Chunk dimensions in tiles. Must match `chunk_size` in `generation.ron`.
 /// Used only for buffer pre-allocation capacity; actual chunk iteration uses
@@ -12,14 +13,14 @@ pub const CHUNK_SIZE: u32 = 32;
 pub const CHUNK_TILE_COUNT: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
 
 // Neighbor bit layout for 8-bit bitmask (Blob47 scheme).
-const BIT_N: u8 = 1;
-const BIT_NE: u8 = 2;
-const BIT_E: u8 = 4;
-const BIT_SE: u8 = 8;
-const BIT_S: u8 = 16;
-const BIT_SW: u8 = 32;
-const BIT_W: u8 = 64;
-const BIT_NW: u8 = 128;
+pub(crate) const BIT_N: u8 = 1;
+pub(crate) const BIT_NE: u8 = 2;
+pub(crate) const BIT_E: u8 = 4;
+pub(crate) const BIT_SE: u8 = 8;
+pub(crate) const BIT_S: u8 = 16;
+pub(crate) const BIT_SW: u8 = 32;
+pub(crate) const BIT_W: u8 = 64;
+pub(crate) const BIT_NW: u8 = 128;
 
 /// Runtime entry for one autotile type, built from an AutotileAsset.
 /// Provides fast bitmask-to-variant lookup.
@@ -27,8 +28,16 @@ pub struct AutotileEntry {
     /// Position of this tile type's column in the combined atlas.
     pub column_index: u32,
     /// Length-256 lookup table indexed by bitmask value.
-    /// Each entry holds the list of sprite variants for that bitmask.
+    /// Each entry holds the list of sprite variants for that bitmask; masks
+    /// missing from the asset hold the variants of their fallback mask.
     bitmask_map: Vec<Vec<SpriteVariant>>,
+    /// Whether the asset maps each bitmask with variants of its own.
+    mapped: Vec<bool>,
+    /// Variants replacing the bitmask's for orientable tiles.
+    orientations: HashMap<TileOrientation, Vec<SpriteVariant>>,
+    /// Frame cycle, only kept when it actually animates. Frames occupy the
+    /// atlas columns following `column_index`.
+    pub animation: Option<TileAnimation>,
 }
 
 impl AutotileEntry {
@@ -38,24 +47,99 @@ impl AutotileEntry {
         for (&bitmask, mapping) in &asset.tiles {
             bitmask_map[bitmask as usize] = mapping.variants.clone();
         }
+        let mapped: Vec<bool> = bitmask_map.iter().map(|v| !v.is_empty()).collect();
+        let mapped_masks: Vec<u8> = (0..=255u8).filter(|&m| mapped[m as usize]).collect();
+        for mask in 0..=255u8 {
+            if bitmask_map[mask as usize].is_empty()
+                && let Some(fallback) = fallback_mask(mask, asset.fallback, &mapped_masks)
+            {
+                bitmask_map[mask as usize] = bitmask_map[fallback as usize].clone();
+            }
+        }
+        let orientations = asset
+            .orientations
+            .iter()
+            .filter(|(_, variants)| !variants.is_empty())
+            .map(|(&orientation, variants)| (orientation, variants.clone()))
+            .collect();
         Self {
             column_index,
             bitmask_map,
+            mapped,
+            orientations,
+            animation: asset.animation.filter(TileAnimation::is_animated),
         }
     }
 
-    /// Returns the variants for a given bitmask value.
-    /// Falls back to bitmask 0 (isolated) if the requested bitmask has no entries.
+    /// Atlas columns this autotile occupies: one per animation frame.
+    pub fn frame_count(&self) -> u32 {
+        self.animation.map_or(1, |animation| animation.frames)
+    }
+
+    /// Frame drawn `elapsed` seconds in; always 0 for static autotiles.
+    #[allow(dead_code)] // the tile shader picks frames itself; kept for tools and tests
+    pub fn frame_at(&self, elapsed: f32) -> u32 {
+        self.animation
+            .map_or(0, |animation| animation.frame_at(elapsed))
+    }
+
+    /// Returns the variants for a given bitmask value. Unmapped bitmasks
+    /// resolve to the autotile's [`AutotileFallback`]; empty only if the
+    /// asset maps no bitmask at all.
     pub fn variants_for(&self, bitmask: u8) -> &[SpriteVariant] {
-        let variants = &self.bitmask_map[bitmask as usize];
-        if variants.is_empty() {
-            &self.bitmask_map[0]
-        } else {
-            variants
-        }
+        &self.bitmask_map[bitmask as usize]
+    }
+
+    /// Variants for an orientable tile: those of its orientation when the
+    /// asset lists any, otherwise the bitmask's.
+    pub fn oriented_variants(&self, bitmask: u8, orientation: TileOrientation) -> &[SpriteVariant] {
+        self.orientations
+            .get(&orientation)
+            .map_or_else(|| self.variants_for(bitmask), Vec::as_slice)
+    }
+
+    /// Whether the asset maps `bitmask` itself rather than drawing it with a
+    /// fallback mask's variants.
+    pub fn is_mapped(&self, bitmask: u8) -> bool {
+        self.mapped[bitmask as usize]
+    }
+
+    /// True if no bitmask has any variant, so every tile falls back to row 0.
+    pub fn is_empty(&self) -> bool {
+        !self.mapped.contains(&true)
     }
 }
 
+/// Mapped bitmask to draw in place of the unmapped `mask`.
+///
+/// An explicit fallback (`Full`, `Mask`) that is itself unmapped degrades to
+/// `NearestSimpler`. Returns `None` only when `mapped` is empty.
+fn fallback_mask(mask: u8, fallback: AutotileFallback, mapped: &[u8]) -> Option<u8> {
+    let explicit = match fallback {
+        AutotileFallback::NearestSimpler => None,
+        AutotileFallback::Full => Some(255),
+        AutotileFallback::Mask(m) => Some(m),
+    };
+    if let Some(m) = explicit.filter(|m| mapped.contains(m)) {
+        return Some(m);
+    }
+    const CARDINALS: u8 = BIT_N | BIT_E | BIT_S | BIT_W;
+    let nearest = mapped
+        .iter()
+        .copied()
+        .filter(|&m| m & !mask == 0)
+        .max_by_key(|&m| {
+            (
+                m.count_ones(),
+                (m & CARDINALS).count_ones(),
+                std::cmp::Reverse(m),
+            )
+        });
+    // Every mapped mask has bits the requested one lacks; an isolated sprite
+    // is the least wrong of those.
+    nearest.or_else(|| mapped.iter().copied().min_by_key(|m| m.count_ones()))
+}
+
 /// Registry of all autotile entries, keyed by tile type name (e.g. "dirt", "stone").
 #[derive(Resource, Default)]
 pub struct AutotileRegistry {
@@ -70,6 +154,42 @@ impl AutotileRegistry {
     pub fn insert(&mut self, name: String, entry: AutotileEntry) {
         self.entries.insert(name, entry);
     }
+
+    /// Problems that would make chunk meshes fall back to placeholder
+    /// sprites: tiles naming an autotile that isn't loaded, and autotiles
+    /// without a single variant. Sorted for stable logs.
+    pub fn validate(&self, tile_registry: &TileRegistry) -> Vec<String> {
+        let mut problems: Vec<String> = tile_registry
+            .defs
+            .iter()
+            .filter_map(|def| {
+                let name = def.autotile.as_deref()?;
+                (!self.entries.contains_key(name))
+                    .then(|| format!("tile '{}' uses missing autotile '{name}'", def.id))
+            })
+            .collect();
+        problems.extend(
+            self.entries
+                .iter()
+                .filter(|(_, entry)| entry.is_empty())
+                .map(|(name, _)| format!("autotile '{name}' maps no bitmask with variants")),
+        );
+        problems.sort();
+        problems
+    }
+}
+
+/// Whether [`compute_bitmask`] can produce `mask`: every corner bit needs
+/// both of its adjacent cardinal bits. There are 47 such masks.
+pub fn is_blob47_mask(mask: u8) -> bool {
+    [
+        (BIT_NE, BIT_N | BIT_E),
+        (BIT_SE, BIT_S | BIT_E),
+        (BIT_SW, BIT_S | BIT_W),
+        (BIT_NW, BIT_N | BIT_W),
+    ]
+    .into_iter()
+    .all(|(corner, cardinals)| mask & corner == 0 || mask & cardinals == cardinals)
 }
 
 /// Compute the 8-bit bitmask for a tile at (x, y) based on its neighbors.
@@ -299,4 +419,185 @@ mod tests {
         let r2 = select_variant(&variants, 10, 20, 42, 0);
         assert_eq!(r1, r2);
     }
+
+    #[test]
+    fn blob47_masks_are_exactly_the_computable_ones() {
+        assert_eq!((0..=255u8).filter(|&m| is_blob47_mask(m)).count(), 47);
+        // Every neighbourhood of the 3×3 block yields a Blob47 mask.
+        for bits in 0..=255u32 {
+            let solid = |x: i32, y: i32| {
+                let i = [
+                    (0, 1),
+                    (1, 1),
+                    (1, 0),
+                    (1, -1),
+                    (0, -1),
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                ]
+                .iter()
+                .position(|&p| p == (x, y));
+                i.is_some_and(|i| bits & (1 << i) != 0)
+            };
+            assert!(is_blob47_mask(compute_bitmask(solid, 0, 0)));
+        }
+        assert!(!is_blob47_mask(BIT_NE | BIT_N));
+    }
+
+    fn asset_with(masks: &[u8], fallback: AutotileFallback) -> AutotileAsset {
+        use crate::registry::assets::BitmaskMapping;
+        // Each mapped mask draws the atlas row equal to its own value.
+        let tiles = masks
+            .iter()
+            .map(|&m| {
+                let mapping = BitmaskMapping {
+                    description: String::new(),
+                    variants: vec![SpriteVariant {
+                        row: m as u32,
+                        weight: 1.0,
+                        col: 0,
+                        index: 0,
+                    }],
+                };
+                (m, mapping)
+            })
+            .collect();
+        AutotileAsset {
+            tile_size: 16,
+            atlas_columns: 1,
+            atlas_rows: 47,
+            tiles,
+            fallback,
+            uv_inset: 0.5,
+            orientations: HashMap::new(),
+            animation: None,
+        }
+    }
+
+    fn row_for(entry: &AutotileEntry, mask: u8) -> Option<u32> {
+        entry.variants_for(mask).first().map(|v| v.row)
+    }
+
+    #[test]
+    fn mapped_bitmask_ignores_fallback() {
+        let entry = AutotileEntry::from_asset(&asset_with(&[0, 5, 255], AutotileFallback::Full), 0);
+        assert_eq!(row_for(&entry, 5), Some(5));
+        assert_eq!(row_for(&entry, 0), Some(0));
+    }
+
+    #[test]
+    fn unmapped_bitmask_uses_nearest_simpler_mask() {
+        // N+E mapped, N+NE+E (7) is not: drop the corner.
+        let entry = AutotileEntry::from_asset(
+            &asset_with(&[0, 1, 4, 5, 85], AutotileFallback::NearestSimpler),
+            0,
+        );
+        assert_eq!(row_for(&entry, BIT_N | BIT_NE | BIT_E), Some(5));
+        // All neighbours with corners falls back to the four cardinals.
+        assert_eq!(row_for(&entry, 255), Some(85));
+        // N+S has no mapping and only N is a mapped subset.
+        assert_eq!(row_for(&entry, BIT_N | BIT_S), Some(1));
+        // W alone has nothing but the isolated sprite below it.
+        assert_eq!(row_for(&entry, BIT_W), Some(0));
+    }
+
+    #[test]
+    fn unmapped_bitmask_uses_configured_fallback() {
+        let full = AutotileEntry::from_asset(&asset_with(&[0, 1, 255], AutotileFallback::Full), 0);
+        assert_eq!(row_for(&full, BIT_N | BIT_E), Some(255));
+        assert_eq!(row_for(&full, BIT_N), Some(1));
+
+        let mask = AutotileEntry::from_asset(&asset_with(&[0, 85], AutotileFallback::Mask(85)), 0);
+        assert_eq!(row_for(&mask, BIT_E), Some(85));
+    }
+
+    #[test]
+    fn unmapped_explicit_fallback_degrades_to_nearest_simpler() {
+        // Full is requested but 255 is missing from the asset.
+        let entry = AutotileEntry::from_asset(&asset_with(&[0, 4], AutotileFallback::Full), 0);
+        assert_eq!(row_for(&entry, BIT_E | BIT_S), Some(4));
+
+        // No isolated sprite and no subset mapped: the simplest mapped mask.
+        let entry = AutotileEntry::from_asset(&asset_with(&[85, 5], AutotileFallback::Full), 0);
+        assert_eq!(row_for(&entry, BIT_W), Some(5));
+
+        let empty = AutotileEntry::from_asset(&asset_with(&[], AutotileFallback::Full), 0);
+        assert!(empty.variants_for(0).is_empty());
+    }
+
+    #[test]
+    fn validate_reports_missing_and_empty_autotiles() {
+        use crate::registry::assets::{AutotileAsset, BitmaskMapping};
+
+        let empty = AutotileAsset {
+            tile_size: 16,
+            atlas_columns: 1,
+            atlas_rows: 47,
+            tiles: HashMap::from([(
+                0u8,
+                BitmaskMapping {
+                    description: "isolated".into(),
+                    variants: vec![],
+                },
+            )]),
+            fallback: Default::default(),
+            uv_inset: 0.5,
+            orientations: HashMap::new(),
+            animation: None,
+        };
+        let mut reg = AutotileRegistry::default();
+        reg.insert("dirt".into(), AutotileEntry::from_asset(&empty, 0));
+
+        let tiles = crate::test_helpers::fixtures::test_tile_registry();
+        let problems = reg.validate(&tiles);
+        assert!(problems.contains(&"autotile 'dirt' maps no bitmask with variants".to_string()));
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.contains("uses missing autotile 'stone'")),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn animated_entry_cycles_frames_and_loops() {
+        let mut asset = asset_with(&[0], AutotileFallback::Full);
+        asset.animation = Some(TileAnimation {
+            frames: 4,
+            frame_secs: 0.25,
+        });
+        let entry = AutotileEntry::from_asset(&asset, 3);
+        assert_eq!(entry.frame_count(), 4);
+        assert_eq!(entry.frame_at(0.0), 0);
+        assert_eq!(entry.frame_at(0.24), 0);
+        assert_eq!(entry.frame_at(0.25), 1);
+        assert_eq!(entry.frame_at(0.8), 3);
+        // One full cycle is 1s: wraps back to the first frame.
+        assert_eq!(entry.frame_at(1.0), 0);
+        assert_eq!(entry.frame_at(10.6), 2);
+    }
+
+    #[test]
+    fn degenerate_animation_draws_a_static_tile() {
+        for animation in [
+            TileAnimation {
+                frames: 1,
+                frame_secs: 0.25,
+            },
+            TileAnimation {
+                frames: 4,
+                frame_secs: 0.0,
+            },
+        ] {
+            let mut asset = asset_with(&[0], AutotileFallback::Full);
+            asset.animation = Some(animation);
+            let entry = AutotileEntry::from_asset(&asset, 0);
+            assert!(entry.animation.is_none());
+            assert_eq!(entry.frame_count(), 1);
+            assert_eq!(entry.frame_at(5.0), 0);
+        }
+        let entry = AutotileEntry::from_asset(&asset_with(&[0], AutotileFallback::Full), 0);
+        assert_eq!(entry.frame_at(5.0), 0);
+    }
 }
@@ -1,41 +1,17 @@
 //! Deterministic biome region generation for horizontal world layout.
 //!
 //! Distributes biomes as contiguous horizontal regions across the world width,
-//! ensuring no two adjacent regions share the same biome (including cylindrical wrap).
+//! ensuring no two adjacent regions share the same biome (including cylindrical wrap),
+//! optionally keeping repeats of a biome a minimum number of regions apart.
+//! Region borders whose biome pair is styled as a cliff get a step in the
+//! surface height ([`BorderCliffs`]).
 
-use bevy::prelude::Resource;
+use std::hash::{Hash, Hasher};
 
-use crate::registry::biome::{BiomeId, BiomeRegistry};
+use bevy::prelude::{warn, Resource};
 
-// ---------------------------------------------------------------------------
-// Minimal splitmix64 RNG — no external crate needed
-// ---------------------------------------------------------------------------
-
-/// SplitMix64 RNG — deterministic, fast, non-cryptographic.
-struct SplitMix64 {
-    state: u64,
-}
-
-impl SplitMix64 {
-    fn new(seed: u64) -> Self {
-        Self { state: seed }
-    }
-
-    fn next(&mut self) -> u64 {
-        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
-        let mut z = self.state;
-        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
-        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
-        z ^ (z >> 31)
-    }
-
-    /// Returns a value in `[lo, hi]` (inclusive).
-    fn range(&mut self, lo: u32, hi: u32) -> u32 {
-        assert!(hi >= lo);
-        let span = (hi - lo) as u64 + 1;
-        (self.next() % span) as u32 + lo
-    }
-}
+use crate::math::SplitMix64;
+use crate::registry::biome::{border_style, BiomeBorder, BiomeId, BiomeRegistry, BorderStyle};
 
 // ---------------------------------------------------------------------------
 // Public data structures
@@ -65,6 +41,10 @@ impl BiomeMap {
     /// * `region_min`      – minimum region width in tiles
     /// * `region_max`      – maximum region width in tiles
     /// * `primary_ratio`   – target fraction of regions assigned to the primary biome
+    /// * `separation`      – fewest regions between two regions of the same biome
+    ///   (1 = only neighbours differ); relaxed per region where the palette is too small
+    /// * `starter`         – biome forced onto the region at x = 0 (the spawn);
+    ///   must be `primary` or one of `secondaries`, otherwise it is ignored
     /// * `biome_registry`  – used to resolve biome names to BiomeId
     #[allow(clippy::too_many_arguments)]
     pub fn generate(
@@ -75,6 +55,8 @@ impl BiomeMap {
         region_min: u32,
         region_max: u32,
         primary_ratio: f64,
+        separation: u32,
+        starter: Option<&str>,
         biome_registry: &BiomeRegistry,
     ) -> Self {
         assert!(region_min > 0, "region_min must be > 0");
@@ -124,12 +106,27 @@ impl BiomeMap {
             biome_names.swap(i, j);
         }
 
+        // --- Pin the starter biome to the spawn region ---
+        // The fix-ups below never move slot 0, so it survives them.
+        if let Some(starter) = starter {
+            if !all_biomes.iter().any(|b| b == starter) {
+                warn!("Starter biome '{starter}' is not in the planet's biome palette; ignoring");
+            } else if let Some(j) = biome_names.iter().position(|n| n == starter) {
+                biome_names.swap(0, j);
+            } else {
+                biome_names[0] = starter.to_string();
+            }
+        }
+
         // --- Fix adjacent duplicates ---
         fix_adjacent_duplicates(&mut biome_names, &all_biomes, &mut rng);
 
         // --- Fix wrap-around (first != last for cylindrical world) ---
         fix_wrap(&mut biome_names, &all_biomes, &mut rng);
 
+        // --- Spread out repeats of the same biome ---
+        enforce_separation(&mut biome_names, &all_biomes, separation as usize, &mut rng);
+
         // --- Assign widths ---
         let mut widths: Vec<u32> = (0..region_count)
             .map(|_| rng.range(region_min, region_max))
@@ -188,6 +185,116 @@ impl BiomeMap {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Border cliffs
+// ---------------------------------------------------------------------------
+
+/// Salt for the per-border roll of which side of a cliff is up.
+const CLIFF_SIDE_SALT: u64 = 0xc1f5_51de;
+
+/// A step in the surface at a region border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BorderCliff {
+    /// First column of the region to the right of the border.
+    pub x: i32,
+    /// Height of the step; positive raises the left side.
+    pub step: i32,
+    /// Columns on each side over which the step fades back to the noise
+    /// surface (half the narrower region).
+    pub reach: i32,
+}
+
+/// Surface height offsets making cliffs at region borders. Each cliff
+/// raises one side by half its step and lowers the other by the rest, both
+/// fading out away from the border, so blended borders and the middle of
+/// regions keep the plain noise surface.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BorderCliffs {
+    cliffs: Vec<BorderCliff>,
+    world_width: i32,
+    wrap_x: bool,
+    /// Hash of the cliffs, for caches of the surface built on them.
+    fingerprint: u64,
+}
+
+impl BorderCliffs {
+    /// Cliffs at the borders of `map` whose biome pair `borders` styles as
+    /// one. Which side is up is rolled from `seed` per border. On
+    /// non-wrapping worlds the world edge at x = 0 is not a border.
+    pub fn new(
+        map: &BiomeMap,
+        borders: &[BiomeBorder],
+        registry: &BiomeRegistry,
+        seed: u32,
+        wrap_x: bool,
+    ) -> Self {
+        let count = map.regions.len();
+        let mut cliffs = Vec::new();
+        if count >= 2 && !borders.is_empty() {
+            for (i, right) in map.regions.iter().enumerate() {
+                if i == 0 && !wrap_x {
+                    continue;
+                }
+                let left = &map.regions[(i + count - 1) % count];
+                let style = border_style(
+                    borders,
+                    registry.name_of(left.biome_id),
+                    registry.name_of(right.biome_id),
+                );
+                let BorderStyle::Cliff { height } = style else {
+                    continue;
+                };
+                let mut rng = SplitMix64::salted(seed as u64 ^ CLIFF_SIDE_SALT, i as u64);
+                let step = if rng.next_u64() & 1 == 0 {
+                    height
+                } else {
+                    -height
+                };
+                cliffs.push(BorderCliff {
+                    x: right.start_x as i32,
+                    step,
+                    reach: (left.width.min(right.width) as i32 / 2).max(1),
+                });
+            }
+        }
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        cliffs.hash(&mut hasher);
+        Self {
+            cliffs,
+            world_width: map.world_width as i32,
+            wrap_x,
+            fingerprint: hasher.finish(),
+        }
+    }
+
+    pub fn cliffs(&self) -> &[BorderCliff] {
+        &self.cliffs
+    }
+
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Surface height offset at (wrapped) column `tile_x`.
+    pub fn offset_at(&self, tile_x: i32) -> i32 {
+        let mut offset = 0.0;
+        for cliff in &self.cliffs {
+            let mut d = tile_x - cliff.x;
+            if self.wrap_x {
+                let half = self.world_width / 2;
+                d = (d + half).rem_euclid(self.world_width) - half;
+            }
+            // Columns from the border on this side: 0 right next to it.
+            let (side, from_border) = if d >= 0 { (-1.0, d) } else { (1.0, -d - 1) };
+            if from_border < cliff.reach {
+                let fade = 1.0 - from_border as f64 / cliff.reach as f64;
+                offset += side * cliff.step as f64 / 2.0 * fade;
+            }
+        }
+        offset.round() as i32
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -236,7 +343,7 @@ fn fix_adjacent_duplicates(ids: &mut [String], all_biomes: &[String], rng: &mut
                     .collect();
                 if !candidates.is_empty() {
                     if let Some(&replacement) =
-                        candidates.get(rng.next() as usize % candidates.len())
+                        candidates.get(rng.next_u64() as usize % candidates.len())
                     {
                         ids[i] = replacement.clone();
                     }
@@ -278,13 +385,59 @@ fn fix_wrap(ids: &mut [String], all_biomes: &[String], rng: &mut SplitMix64) {
             .filter(|b| *b != first && *b != second_to_last)
             .collect();
         if !candidates.is_empty() {
-            if let Some(&replacement) = candidates.get(rng.next() as usize % candidates.len()) {
+            if let Some(&replacement) = candidates.get(rng.next_u64() as usize % candidates.len()) {
                 ids[len - 1] = replacement.clone();
             }
         }
     }
 }
 
+/// Keep regions of the same biome at least `separation` regions apart,
+/// counting across the wrap.
+///
+/// Runs after the adjacency and wrap fix-ups and never touches slot 0 (the
+/// spawn). Each slot first tries a swap with a later slot, which keeps the
+/// biome mix, then a replacement from `all_biomes`; when neither satisfies
+/// the full separation, the largest separation that can be met is used.
+fn enforce_separation(
+    ids: &mut [String],
+    all_biomes: &[String],
+    separation: usize,
+    rng: &mut SplitMix64,
+) {
+    let len = ids.len();
+    if separation < 2 || len < 3 {
+        return;
+    }
+
+    for i in 1..len {
+        for sep in (1..=separation).rev() {
+            if !repeats_within(ids, i, &ids[i], sep) {
+                break;
+            }
+            if let Some(k) = (i + 1..len).find(|&k| !repeats_within(ids, i, &ids[k], sep)) {
+                ids.swap(i, k);
+                break;
+            }
+            let candidates: Vec<&String> = all_biomes
+                .iter()
+                .filter(|b| !repeats_within(ids, i, b, sep))
+                .collect();
+            if !candidates.is_empty() {
+                ids[i] = candidates[rng.next_u64() as usize % candidates.len()].clone();
+                break;
+            }
+        }
+    }
+}
+
+/// Whether `biome` sits within `sep` regions of slot `i` among the slots
+/// before it, directly or across the wrap.
+fn repeats_within(ids: &[String], i: usize, biome: &str, sep: usize) -> bool {
+    let len = ids.len();
+    (0..i).any(|j| (i - j).min(len - i + j) <= sep && ids[j] == biome)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -302,8 +455,12 @@ mod tests {
     const PRIMARY_RATIO: f64 = 0.6;
 
     fn test_registry() -> BiomeRegistry {
+        registry_with(&["meadow", "forest", "rocky"])
+    }
+
+    fn registry_with(names: &[&str]) -> BiomeRegistry {
         let mut reg = BiomeRegistry::default();
-        for name in ["meadow", "forest", "rocky"] {
+        for &name in names {
             reg.insert(
                 name,
                 BiomeDef {
@@ -315,6 +472,7 @@ mod tests {
                     cave_threshold: 0.3,
                     parallax_path: None,
                     temperature_offset: 0.0,
+                    decorations: vec![],
                 },
             );
         }
@@ -331,6 +489,8 @@ mod tests {
             REGION_MIN,
             REGION_MAX,
             PRIMARY_RATIO,
+            1,
+            None,
             &reg,
         );
         (map, reg)
@@ -453,6 +613,8 @@ mod tests {
             REGION_MIN,
             REGION_MAX,
             PRIMARY_RATIO,
+            1,
+            None,
             &reg,
         );
         // At least one region should differ in biome_id or start_x
@@ -464,6 +626,38 @@ mod tests {
         assert!(differs, "different seeds must produce different maps");
     }
 
+    #[test]
+    fn starter_biome_is_pinned_to_spawn_region() {
+        let reg = test_registry();
+        for starter in ["meadow", "forest", "rocky"] {
+            for seed in 0..32 {
+                let map = BiomeMap::generate(
+                    "meadow",
+                    &["forest", "rocky"],
+                    seed,
+                    WORLD_WIDTH,
+                    REGION_MIN,
+                    REGION_MAX,
+                    PRIMARY_RATIO,
+                    1,
+                    Some(starter),
+                    &reg,
+                );
+                assert_eq!(map.biome_at(0), reg.id_by_name(starter), "seed {seed}");
+                for pair in map.regions.windows(2) {
+                    assert_ne!(pair[0].biome_id, pair[1].biome_id, "seed {seed}");
+                }
+                assert_ne!(
+                    map.regions.first().unwrap().biome_id,
+                    map.regions.last().unwrap().biome_id,
+                    "seed {seed}"
+                );
+                let total: u32 = map.regions.iter().map(|r| r.width).sum();
+                assert_eq!(total, WORLD_WIDTH);
+            }
+        }
+    }
+
     #[test]
     fn region_index_at_returns_index() {
         let (map, _) = test_map();
@@ -476,4 +670,89 @@ mod tests {
         let last_start = map.regions[last_idx].start_x;
         assert_eq!(map.region_index_at(last_start), last_idx);
     }
+
+    /// Smallest distance (in regions, across the wrap) between two regions of
+    /// the same biome.
+    fn min_repeat_distance(map: &BiomeMap) -> usize {
+        let len = map.regions.len();
+        let mut min = usize::MAX;
+        for i in 0..len {
+            for j in i + 1..len {
+                if map.regions[i].biome_id == map.regions[j].biome_id {
+                    min = min.min((j - i).min(len - j + i));
+                }
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn separation_keeps_repeats_apart() {
+        let names = ["meadow", "forest", "rocky", "tundra", "desert"];
+        let reg = registry_with(&names);
+        for seed in 0..32 {
+            let map = BiomeMap::generate(
+                "meadow",
+                &names[1..],
+                seed,
+                8192,
+                REGION_MIN,
+                REGION_MAX,
+                0.3,
+                2,
+                Some("forest"),
+                &reg,
+            );
+            assert!(min_repeat_distance(&map) > 2, "seed {seed}");
+            assert_eq!(map.biome_at(0), reg.id_by_name("forest"), "seed {seed}");
+            let total: u32 = map.regions.iter().map(|r| r.width).sum();
+            assert_eq!(total, 8192);
+        }
+    }
+
+    #[test]
+    fn separation_relaxes_with_small_palette() {
+        let reg = test_registry();
+        for seed in 0..32 {
+            let map = BiomeMap::generate(
+                "meadow",
+                &["forest", "rocky"],
+                seed,
+                7700,
+                REGION_MIN,
+                REGION_MAX,
+                PRIMARY_RATIO,
+                4,
+                None,
+                &reg,
+            );
+            // Three biomes can't keep repeats four regions apart, but
+            // neighbours still differ.
+            assert!(min_repeat_distance(&map) >= 2, "seed {seed}");
+            let total: u32 = map.regions.iter().map(|r| r.width).sum();
+            assert_eq!(total, 7700);
+        }
+    }
+
+    #[test]
+    fn separation_is_deterministic() {
+        let reg = test_registry();
+        let generate = || {
+            BiomeMap::generate(
+                "meadow",
+                &["forest", "rocky"],
+                TEST_SEED,
+                8192,
+                REGION_MIN,
+                REGION_MAX,
+                PRIMARY_RATIO,
+                2,
+                None,
+                &reg,
+            )
+        };
+        let (a, b) = (generate(), generate());
+        let ids = |m: &BiomeMap| m.regions.iter().map(|r| r.biome_id).collect::<Vec<_>>();
+        assert_eq!(ids(&a), ids(&b));
+    }
 }
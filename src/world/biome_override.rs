@@ -0,0 +1,251 @@
+//! Forced biomes for looking at a biome's generation without finding it in
+//! the world, e.g. for screenshots and tests.
+//!
+//! [`BiomeOverrides`] maps chunks and column ranges to a biome id. Terrain
+//! generation asks [`WorldCtxRef::forced_biome`] before the biome map, so a
+//! covered tile generates (surface, subsurface, fill and all) as if the forced
+//! biome were there. Only chunks generated after an override is set change;
+//! saved and already loaded chunks keep their tiles. Cheats-gated `/biome`
+//! commands set and clear overrides in game.
+//!
+//! [`WorldCtxRef::forced_biome`]: crate::world::ctx::WorldCtxRef::forced_biome
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+
+use crate::chat::{ChatCommandEvent, ChatState};
+use crate::game_mode::CheatsEnabled;
+use crate::registry::biome::{BiomeId, BiomeRegistry};
+use crate::registry::world::ActiveWorld;
+
+/// Biomes forced onto chunks or column ranges, in wrapped coordinates.
+/// Chunk overrides win over column overrides; later column ranges win over
+/// earlier ones.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BiomeOverrides {
+    chunks: HashMap<(i32, i32), BiomeId>,
+    columns: Vec<(Range<i32>, BiomeId)>,
+}
+
+impl BiomeOverrides {
+    /// Generate every tile of chunk (`chunk_x`, `chunk_y`) as `biome`.
+    pub fn force_chunk(&mut self, chunk_x: i32, chunk_y: i32, biome: BiomeId) {
+        self.chunks.insert((chunk_x, chunk_y), biome);
+    }
+
+    /// Generate every tile of the columns in `columns` as `biome`.
+    pub fn force_columns(&mut self, columns: Range<i32>, biome: BiomeId) {
+        self.columns.push((columns, biome));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty() && self.columns.is_empty()
+    }
+
+    /// Biome forced onto tile (`tile_x`, `tile_y`), `tile_x` already wrapped.
+    pub fn biome_at(&self, tile_x: i32, tile_y: i32, chunk_size: u32) -> Option<BiomeId> {
+        let size = chunk_size as i32;
+        let chunk = (tile_x.div_euclid(size), tile_y.div_euclid(size));
+        self.chunks.get(&chunk).copied().or_else(|| {
+            self.columns
+                .iter()
+                .rev()
+                .find(|(columns, _)| columns.contains(&tile_x))
+                .map(|(_, biome)| *biome)
+        })
+    }
+}
+
+/// Overrides describe the world being left.
+pub fn clear_biome_overrides(mut overrides: ResMut<BiomeOverrides>) {
+    if !overrides.is_empty() {
+        *overrides = BiomeOverrides::default();
+    }
+}
+
+/// Handle `/biome` commands.
+pub fn handle_biome_command(
+    mut commands_in: MessageReader<ChatCommandEvent>,
+    cheats: Res<CheatsEnabled>,
+    config: Res<ActiveWorld>,
+    biome_registry: Res<BiomeRegistry>,
+    mut overrides: ResMut<BiomeOverrides>,
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    for cmd in commands_in.read() {
+        if cmd.command != "biome" {
+            continue;
+        }
+        let reply = if !cheats.0 {
+            "Cheats are disabled.".to_string()
+        } else {
+            run_biome_command(&mut overrides, &cmd.args, &biome_registry, &config)
+        };
+        if let Some(chat) = chat.as_mut() {
+            chat.send_system(&reply, time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Apply one `/biome` command and describe the result.
+fn run_biome_command(
+    overrides: &mut BiomeOverrides,
+    args: &[String],
+    biome_registry: &BiomeRegistry,
+    config: &ActiveWorld,
+) -> String {
+    let usage = "Usage: /biome <name> columns <x0> <x1> | <name> chunk <cx> <cy> | clear";
+    let numbers = || -> Option<(i32, i32)> {
+        let a = args.get(2)?.parse().ok()?;
+        let b = args.get(3)?.parse().ok()?;
+        Some((a, b))
+    };
+    match args.first().map(String::as_str) {
+        Some("clear") => {
+            *overrides = BiomeOverrides::default();
+            "Biome overrides cleared.".to_string()
+        }
+        Some(name) => {
+            let Some(biome) = biome_registry.try_id_by_name(name) else {
+                return format!("Unknown biome '{name}'.");
+            };
+            let (kind, Some((a, b))) = (args.get(1).map(String::as_str), numbers()) else {
+                return usage.to_string();
+            };
+            match kind {
+                Some("columns") if a < b => {
+                    let start = config.wrap_tile_x(a);
+                    let end = start + (b - a).min(config.width_tiles);
+                    overrides.force_columns(start..end, biome);
+                    // A range crossing the seam continues from column 0.
+                    if end > config.width_tiles && config.wrap_x {
+                        overrides.force_columns(0..end - config.width_tiles, biome);
+                    }
+                    format!("Columns {a}..{b} generate as {name} from now on.")
+                }
+                Some("chunk") => {
+                    overrides.force_chunk(config.wrap_chunk_x(a), b, biome);
+                    format!("Chunk ({a}, {b}) generates as {name} from now on.")
+                }
+                _ => usage.to_string(),
+            }
+        }
+        None => usage.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::biome::WorldLayer;
+    use crate::registry::tile::TileId;
+    use crate::test_helpers::fixtures::{self, make_ctx};
+    use crate::world::ctx::WorldCtxRef;
+    use crate::world::terrain_gen::generate_tile;
+
+    /// First column whose map biome has grass on top, and its surface row.
+    fn grassy_column(ctx: &WorldCtxRef) -> (i32, i32) {
+        (0..ctx.config.width_tiles)
+            .find(|&x| {
+                let biome = ctx.layer_biome(WorldLayer::Surface, x);
+                ctx.biome_registry.get(biome).surface_block == TileId(1)
+            })
+            .map(|x| (x, surface_at(ctx, x)))
+            .expect("the test biome map has grassy biomes")
+    }
+
+    fn surface_at(ctx: &WorldCtxRef, x: i32) -> i32 {
+        ctx.noise_cache
+            .surface_height_at(x, ctx.config, ctx.planet_config)
+    }
+
+    #[test]
+    fn forced_columns_generate_the_forced_surface_block() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let plain = make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let (x, surface) = grassy_column(&plain);
+        assert_eq!(generate_tile(x, surface, &plain), TileId(1));
+
+        let rocky = br.id_by_name("rocky");
+        let mut overrides = BiomeOverrides::default();
+        overrides.force_columns(x..x + 1, rocky);
+        let forced = WorldCtxRef {
+            biome_overrides: Some(&overrides),
+            ..make_ctx(&wc, &bm, &br, &tr, &pc, &nc)
+        };
+        // Rocky's surface block, although the biome map says otherwise.
+        assert_eq!(generate_tile(x, surface, &forced), TileId(3));
+        assert_eq!(forced.biome_at_tile(x, surface), rocky);
+        // Neighbouring columns are untouched.
+        let next = x + 1;
+        let next_surface = surface_at(&plain, next);
+        assert_eq!(
+            generate_tile(next, next_surface, &forced),
+            generate_tile(next, next_surface, &plain)
+        );
+        assert_eq!(
+            forced.biome_at_tile(next, next_surface),
+            plain.biome_at_tile(next, next_surface)
+        );
+    }
+
+    #[test]
+    fn forced_chunks_cover_only_their_own_tiles() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let plain = make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let (x, surface) = grassy_column(&plain);
+        let size = wc.chunk_size as i32;
+        let (cx, cy) = (x.div_euclid(size), surface.div_euclid(size));
+
+        let rocky = br.id_by_name("rocky");
+        let mut overrides = BiomeOverrides::default();
+        overrides.force_chunk(cx, cy, rocky);
+        let forced = WorldCtxRef {
+            biome_overrides: Some(&overrides),
+            ..make_ctx(&wc, &bm, &br, &tr, &pc, &nc)
+        };
+        assert_eq!(generate_tile(x, surface, &forced), TileId(3));
+        // Same column, chunk above: the biome map decides again.
+        let above = (cy + 1) * size;
+        assert_eq!(
+            forced.biome_at_tile(x, above),
+            plain.biome_at_tile(x, above)
+        );
+    }
+
+    #[test]
+    fn chunk_overrides_win_over_column_overrides() {
+        let (forest, rocky) = (BiomeId(1), BiomeId(2));
+        let mut overrides = BiomeOverrides::default();
+        overrides.force_columns(0..100, forest);
+        overrides.force_chunk(1, 0, rocky);
+        assert_eq!(overrides.biome_at(10, 5, 32), Some(forest));
+        assert_eq!(overrides.biome_at(40, 5, 32), Some(rocky));
+        assert_eq!(overrides.biome_at(40, 40, 32), Some(forest));
+        assert_eq!(overrides.biome_at(100, 5, 32), None);
+    }
+
+    #[test]
+    fn biome_command_forces_columns_across_the_seam() {
+        let wc = fixtures::test_world_config();
+        let br = fixtures::test_biome_registry();
+        let rocky = br.id_by_name("rocky");
+        let mut overrides = BiomeOverrides::default();
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+
+        let reply = run_biome_command(&mut overrides, &args("rocky columns -10 10"), &br, &wc);
+        assert!(reply.contains("rocky"), "{reply}");
+        assert_eq!(overrides.biome_at(wc.width_tiles - 5, 0, 32), Some(rocky));
+        assert_eq!(overrides.biome_at(5, 0, 32), Some(rocky));
+        assert_eq!(overrides.biome_at(10, 0, 32), None);
+
+        let reply = run_biome_command(&mut overrides, &args("lava columns 0 10"), &br, &wc);
+        assert!(reply.starts_with("Unknown biome"), "{reply}");
+        run_biome_command(&mut overrides, &args("clear"), &br, &wc);
+        assert!(overrides.is_empty());
+    }
+}
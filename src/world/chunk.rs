@@ -16,16 +16,24 @@ use crate::object::registry::ObjectRegistry;
 use crate::object::spawn::{
     despawn_objects_for_chunk, spawn_objects_for_chunk, ObjectDisplayChunk, PlacedObjectEntity,
 };
+use crate::physics::Velocity;
+use crate::player::Player;
 use crate::registry::tile::{TileId, TileRegistry};
 use crate::registry::world::ActiveWorld;
 use crate::world::atlas::TileAtlas;
 use crate::world::autotile::{compute_bitmask, AutotileRegistry};
+use crate::world::chunk_reveal::{ChunkReveal, LightReveal};
 use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::exploration::ExploredTiles;
 use crate::world::lit_sprite::{LitSpriteMaterial, SharedLitQuad};
-use crate::world::mesh_builder::{build_chunk_mesh, MeshBuildBuffers};
+use crate::world::mesh_builder::{
+    build_chunk_mesh, scale_tints, AmbientOcclusion, MeshBuildBuffers,
+};
 use crate::world::surface_objects;
 use crate::world::terrain_gen;
 use crate::world::tile_renderer::SharedTileMaterial;
+use crate::world::world_hash::StableHasher;
+use crate::world::world_info::ChunkSummary;
 
 /// Marker component on tilemap entities to identify which chunk they represent.
 #[derive(Component)]
@@ -35,9 +43,35 @@ pub struct ChunkCoord {
 }
 
 /// Marker component indicating a chunk's mesh needs rebuilding.
+///
+/// Inserting it again before the rebuild is a no-op, so any number of edits
+/// in a frame cost one remesh.
 #[derive(Component)]
 pub struct ChunkDirty;
 
+/// When (`Time::elapsed_secs`) the chunk layer's mesh was last rebuilt.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LastRemesh(pub f32);
+
+/// A foreground tile or object was placed or removed at runtime, in world
+/// tile coordinates (for the anchor tile, for objects).
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileChanged {
+    pub tile_x: i32,
+    pub tile_y: i32,
+}
+
+/// The player broke `tile` on `layer`, in world tile coordinates. Sent
+/// alongside [`TileChanged`] for readers that need to know what was there.
+#[allow(dead_code)] // only the tile is read so far, by the tutorial
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileBroken {
+    pub tile_x: i32,
+    pub tile_y: i32,
+    pub layer: Layer,
+    pub tile: TileId,
+}
+
 /// Marker component identifying whether a chunk entity is foreground or background.
 #[derive(Component)]
 pub struct ChunkLayer(pub Layer);
@@ -47,10 +81,29 @@ pub struct ChunkEntities {
     pub fg: Entity,
     pub bg: Entity,
     pub liquid: Entity,
+    pub state: ChunkState,
+}
+
+/// Lifecycle of a display chunk's entities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChunkState {
+    /// In the desired set and rendered.
+    Visible,
+    /// Left the desired set at `since` (elapsed seconds); entities are hidden
+    /// and despawn once the grace period runs out.
+    Hibernating { since: f32 },
+}
+
+/// What `chunk_loading_system` does to an already-loaded chunk this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkTransition {
+    Hide,
+    Wake,
+    Despawn,
 }
 
 /// Identifies which tile layer to operate on.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum Layer {
     Fg,
     Bg,
@@ -92,6 +145,13 @@ pub struct ChunkData {
     pub occupancy: Vec<Option<OccupancyRef>>,
     #[allow(dead_code)] // Reserved for future block-damage system
     pub damage: Vec<u8>,
+    /// Per-tile runtime state bits for the foreground layer (see `TILE_STATE_*`).
+    /// Empty for chunks saved before tile state existed; treated as all zero.
+    #[serde(default)]
+    pub tile_state: Vec<u8>,
+    /// Player-written text of sign tiles, keyed by tile index.
+    #[serde(default)]
+    pub signs: HashMap<usize, String>,
 }
 
 impl ChunkData {
@@ -108,12 +168,56 @@ impl ChunkData {
             Layer::Bg => &mut self.bg,
         }
     }
+
+    /// State byte of the foreground tile at `idx` (0 if never set).
+    pub fn tile_state(&self, idx: usize) -> u8 {
+        self.tile_state.get(idx).copied().unwrap_or(0)
+    }
+
+    /// Set the state byte of the foreground tile at `idx`.
+    pub fn set_tile_state(&mut self, idx: usize, state: u8) {
+        if self.tile_state.len() != self.fg.tiles.len() {
+            self.tile_state.resize(self.fg.tiles.len(), 0);
+        }
+        self.tile_state[idx] = state;
+    }
+
+    /// Text of the sign at `idx`, if one has been written.
+    pub fn sign_text(&self, idx: usize) -> Option<&str> {
+        self.signs.get(&idx).map(String::as_str)
+    }
+
+    /// Set the text of the sign at `idx`; empty text clears it.
+    pub fn set_sign_text(&mut self, idx: usize, text: String) {
+        if text.is_empty() {
+            self.signs.remove(&idx);
+        } else {
+            self.signs.insert(idx, text);
+        }
+    }
+
+    /// Platform-stable hash of the fg and bg tile arrays.
+    ///
+    /// FNV-1a over each `TileId` encoded little-endian, fg first then bg, so
+    /// the value can be checked into worldgen regression snapshots.
+    #[allow(dead_code)] // used by worldgen snapshot tests
+    pub fn tile_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        for tile in self.fg.tiles.iter().chain(&self.bg.tiles) {
+            hasher.write_u16(tile.0);
+        }
+        hasher.finish()
+    }
 }
 
 /// Authoritative world tile data. Chunks are lazily generated and cached.
 #[derive(Resource, Default)]
 pub struct WorldMap {
     pub(crate) chunks: HashMap<(i32, i32), ChunkData>,
+    /// Biome/layer summary per chunk, recorded when the chunk generates.
+    pub(crate) summaries: HashMap<(i32, i32), ChunkSummary>,
+    /// Tiles the player has seen, per chunk; kept when chunk data unloads.
+    pub(crate) explored: HashMap<(i32, i32), ExploredTiles>,
 }
 
 impl WorldMap {
@@ -122,6 +226,29 @@ impl WorldMap {
         self.chunks.get(&(cx, cy))
     }
 
+    /// Biome/layer summary stored when the chunk was generated.
+    pub fn chunk_summary(&self, cx: i32, cy: i32) -> Option<&ChunkSummary> {
+        self.summaries.get(&(cx, cy))
+    }
+
+    /// Biome/layer summary for a chunk: a map lookup for generated chunks,
+    /// computed on the spot for anything else (e.g. chunks restored from a save).
+    pub fn chunk_info(&self, cx: i32, cy: i32, ctx: &WorldCtxRef) -> ChunkSummary {
+        self.chunk_summary(cx, cy)
+            .copied()
+            .unwrap_or_else(|| ctx.chunk_summary(cx, cy))
+    }
+
+    /// Coordinates of all loaded chunks, sorted by (y, x).
+    ///
+    /// `chunks` is a `HashMap`, so use this wherever iteration order matters.
+    #[allow(dead_code)] // used by worldgen snapshot tests
+    pub fn sorted_chunk_coords(&self) -> Vec<(i32, i32)> {
+        let mut coords: Vec<(i32, i32)> = self.chunks.keys().copied().collect();
+        coords.sort_unstable_by_key(|&(x, y)| (y, x));
+        coords
+    }
+
     /// Mutable access to a chunk by coordinates.
     #[allow(dead_code)] // public API for future use
     pub fn chunk_mut(&mut self, cx: i32, cy: i32) -> Option<&mut ChunkData> {
@@ -137,6 +264,8 @@ impl WorldMap {
         ctx: &WorldCtxRef,
     ) -> &ChunkData {
         self.chunks.entry((chunk_x, chunk_y)).or_insert_with(|| {
+            self.summaries
+                .insert((chunk_x, chunk_y), ctx.chunk_summary(chunk_x, chunk_y));
             let chunk_tiles = terrain_gen::generate_chunk_tiles(chunk_x, chunk_y, ctx);
             let len = chunk_tiles.fg.len();
             ChunkData {
@@ -154,6 +283,8 @@ impl WorldMap {
                 objects: Vec::new(),
                 occupancy: vec![None; len],
                 damage: vec![0; len],
+                tile_state: vec![0; len],
+                signs: HashMap::new(),
             }
         })
     }
@@ -168,11 +299,17 @@ impl WorldMap {
         ctx: &WorldCtxRef,
     ) -> Option<TileId> {
         if tile_y < 0 {
-            return Some(ctx.tile_registry.by_name("stone"));
+            return Some(terrain_gen::bedrock_tile(ctx));
         }
         if tile_y >= ctx.config.height_tiles {
             return Some(TileId::AIR);
         }
+        if ctx.config.outside_x(tile_x) {
+            return Some(match layer {
+                Layer::Fg => terrain_gen::border_tile(ctx),
+                Layer::Bg => TileId::AIR,
+            });
+        }
         let wrapped_x = ctx.config.wrap_tile_x(tile_x);
         let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
         let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
@@ -191,11 +328,17 @@ impl WorldMap {
         ctx: &WorldCtxRef,
     ) -> TileId {
         if tile_y < 0 {
-            return ctx.tile_registry.by_name("stone"); // bedrock
+            return terrain_gen::bedrock_tile(ctx);
         }
         if tile_y >= ctx.config.height_tiles {
             return TileId::AIR; // sky
         }
+        if ctx.config.outside_x(tile_x) {
+            return match layer {
+                Layer::Fg => terrain_gen::border_tile(ctx),
+                Layer::Bg => TileId::AIR,
+            };
+        }
         let wrapped_x = ctx.config.wrap_tile_x(tile_x);
         let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
         let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
@@ -204,6 +347,42 @@ impl WorldMap {
             .get(lx, ly, ctx.config.chunk_size)
     }
 
+    /// Read-only: `(tile_x, tile_y, tile)` for every tile in the rectangle
+    /// between two corners (inclusive, in any order), row by row from the
+    /// bottom. Tiles in unloaded chunks are skipped.
+    ///
+    /// Coordinates are reported as given, so a region crossing the wrap seam
+    /// yields x past the world's edges. Rows below the world yield bedrock
+    /// (stone) and rows above it sky (air), like [`Self::get_tile`].
+    #[allow(dead_code)] // public API for region tools and queries
+    pub fn iter_region(
+        &self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        layer: Layer,
+        ctx: &WorldCtxRef,
+    ) -> impl Iterator<Item = (i32, i32, TileId)> {
+        region_coords(x0, y0, x1, y1)
+            .filter_map(move |(x, y)| Some((x, y, self.get_tile(x, y, layer, ctx)?)))
+    }
+
+    /// Like [`Self::iter_region`], but generates unloaded chunks on the way
+    /// so every tile in the rectangle is yielded.
+    #[allow(dead_code)] // public API for region tools and queries
+    pub fn iter_region_mut(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        layer: Layer,
+        ctx: &WorldCtxRef,
+    ) -> impl Iterator<Item = (i32, i32, TileId)> {
+        region_coords(x0, y0, x1, y1).map(move |(x, y)| (x, y, self.get_tile_mut(x, y, layer, ctx)))
+    }
+
     pub fn set_tile(
         &mut self,
         tile_x: i32,
@@ -212,18 +391,77 @@ impl WorldMap {
         tile: TileId,
         ctx: &WorldCtxRef,
     ) {
-        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+        // The border past the edges of a non-wrapping world can't be edited.
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles || ctx.config.outside_x(tile_x) {
             return;
         }
         let wrapped_x = ctx.config.wrap_tile_x(tile_x);
         let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
         let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
         self.get_or_generate_chunk(cx, cy, ctx);
+        let chunk = self.chunks.get_mut(&(cx, cy)).unwrap();
+        chunk.layer_mut(layer).set(lx, ly, tile, ctx.config.chunk_size);
+        // A new foreground tile starts in its default state, without text.
+        if layer == Layer::Fg {
+            let idx = (ly * ctx.config.chunk_size + lx) as usize;
+            if chunk.tile_state(idx) != 0 {
+                chunk.set_tile_state(idx, 0);
+            }
+            chunk.signs.remove(&idx);
+        }
+    }
+
+    /// Read-only: state byte of the foreground tile (0 for unloaded chunks).
+    pub fn get_tile_state(&self, tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> u8 {
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+            return 0;
+        }
+        let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+        let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
         self.chunks
-            .get_mut(&(cx, cy))
-            .unwrap()
-            .layer_mut(layer)
-            .set(lx, ly, tile, ctx.config.chunk_size);
+            .get(&(cx, cy))
+            .map_or(0, |chunk| chunk.tile_state((ly * ctx.config.chunk_size + lx) as usize))
+    }
+
+    /// Read-only: text of the sign at the tile (None if unwritten or unloaded).
+    pub fn get_sign_text(&self, tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> Option<&str> {
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+            return None;
+        }
+        let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+        let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
+        self.chunks
+            .get(&(cx, cy))
+            .and_then(|chunk| chunk.sign_text((ly * ctx.config.chunk_size + lx) as usize))
+    }
+
+    /// Set the text of the sign at the tile; empty text clears it. No-op for
+    /// unloaded chunks.
+    pub fn set_sign_text(&mut self, tile_x: i32, tile_y: i32, text: String, ctx: &WorldCtxRef) {
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+            return;
+        }
+        let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+        let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
+        if let Some(chunk) = self.chunk_mut(cx, cy) {
+            chunk.set_sign_text((ly * ctx.config.chunk_size + lx) as usize, text);
+        }
+    }
+
+    /// Set the state byte of the foreground tile. No-op for unloaded chunks.
+    pub fn set_tile_state(&mut self, tile_x: i32, tile_y: i32, state: u8, ctx: &WorldCtxRef) {
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+            return;
+        }
+        let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+        let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(wrapped_x, tile_y, ctx.config.chunk_size);
+        if let Some(chunk) = self.chunk_mut(cx, cy) {
+            chunk.set_tile_state((ly * ctx.config.chunk_size + lx) as usize, state);
+        }
     }
 
     pub fn get_liquid(&self, tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> LiquidCell {
@@ -241,7 +479,7 @@ impl WorldMap {
 
     pub fn set_liquid(&mut self, tile_x: i32, tile_y: i32, cell: LiquidCell, ctx: &WorldCtxRef) {
         let tx = ctx.config.wrap_tile_x(tile_x);
-        if tile_y < 0 || tile_y >= ctx.config.height_tiles {
+        if tile_y < 0 || tile_y >= ctx.config.height_tiles || ctx.config.outside_x(tile_x) {
             return;
         }
         let (cx, cy) = tile_to_chunk(tx, tile_y, ctx.config.chunk_size);
@@ -311,6 +549,121 @@ pub struct LoadedChunks {
     pub(crate) map: HashMap<(i32, i32), ChunkEntities>,
 }
 
+impl LoadedChunks {
+    /// True if the display chunk is loaded and not hibernating.
+    pub fn is_visible(&self, chunk_x: i32, chunk_y: i32) -> bool {
+        self.map
+            .get(&(chunk_x, chunk_y))
+            .is_some_and(|e| e.state == ChunkState::Visible)
+    }
+
+    pub fn is_hibernating(&self, chunk_x: i32, chunk_y: i32) -> bool {
+        self.map
+            .get(&(chunk_x, chunk_y))
+            .is_some_and(|e| matches!(e.state, ChunkState::Hibernating { .. }))
+    }
+
+    /// Advance the Visible/Hibernating state of every loaded chunk against the
+    /// `desired` set and return the transitions to apply, sorted by coords.
+    ///
+    /// Chunks marked [`ChunkTransition::Despawn`] are left in the map;
+    /// [`despawn_chunk`] removes them.
+    pub fn update_states(
+        &mut self,
+        desired: &HashSet<(i32, i32)>,
+        now: f32,
+        grace_secs: f32,
+    ) -> Vec<((i32, i32), ChunkTransition)> {
+        let mut transitions = Vec::new();
+        for (&coords, entities) in &mut self.map {
+            let wanted = desired.contains(&coords);
+            match entities.state {
+                ChunkState::Visible if !wanted => {
+                    if grace_secs <= 0.0 {
+                        transitions.push((coords, ChunkTransition::Despawn));
+                    } else {
+                        entities.state = ChunkState::Hibernating { since: now };
+                        transitions.push((coords, ChunkTransition::Hide));
+                    }
+                }
+                ChunkState::Hibernating { .. } if wanted => {
+                    entities.state = ChunkState::Visible;
+                    transitions.push((coords, ChunkTransition::Wake));
+                }
+                ChunkState::Hibernating { since } if now - since >= grace_secs => {
+                    transitions.push((coords, ChunkTransition::Despawn));
+                }
+                _ => {}
+            }
+        }
+        transitions.sort_by_key(|&(coords, _)| coords);
+        transitions
+    }
+}
+
+/// How long chunks that left the load radius stay hidden before despawning.
+///
+/// Walking back within the grace period shows the existing entities again
+/// instead of regenerating bitmasks and meshes.
+#[derive(Resource, Debug, Clone)]
+pub struct ChunkHibernation {
+    /// Seconds a hidden chunk is kept; 0 despawns immediately.
+    pub grace_secs: f32,
+}
+
+impl Default for ChunkHibernation {
+    fn default() -> Self {
+        Self { grace_secs: 5.0 }
+    }
+}
+
+/// Velocity-biased chunk preloading, so a fast-falling player doesn't outrun
+/// chunk generation and land on ungenerated (non-solid) tiles.
+#[derive(Resource, Debug, Clone)]
+pub struct ChunkPreload {
+    /// How far ahead (seconds of travel) to load in the direction of motion.
+    pub lookahead_secs: f32,
+    /// Upper bound on extra chunks per side beyond `chunk_load_radius`.
+    pub max_extra_chunks: i32,
+}
+
+impl Default for ChunkPreload {
+    fn default() -> Self {
+        Self {
+            lookahead_secs: 1.0,
+            max_extra_chunks: 4,
+        }
+    }
+}
+
+/// Caps how often a chunk under continuous edits (drag-placing, blasts)
+/// is remeshed.
+#[derive(Resource, Debug, Clone)]
+pub struct RemeshDebounce {
+    /// Minimum seconds between rebuilds of one chunk layer; `None` rebuilds
+    /// every frame it is dirty. The first edit after a quiet spell still
+    /// rebuilds at once; later ones wait, dirty, for the interval to pass.
+    pub min_interval_secs: Option<f32>,
+}
+
+impl Default for RemeshDebounce {
+    fn default() -> Self {
+        Self {
+            min_interval_secs: Some(0.05),
+        }
+    }
+}
+
+impl RemeshDebounce {
+    /// Whether a chunk last rebuilt at `last` may rebuild at `now`.
+    pub fn ready(&self, last: Option<f32>, now: f32) -> bool {
+        match (self.min_interval_secs, last) {
+            (Some(interval), Some(last)) => now - last >= interval,
+            _ => true,
+        }
+    }
+}
+
 // --- Coordinate conversion helpers ---
 
 pub fn tile_to_chunk(tile_x: i32, tile_y: i32, chunk_size: u32) -> (i32, i32) {
@@ -327,6 +680,14 @@ pub fn tile_to_local(tile_x: i32, tile_y: i32, chunk_size: u32) -> (u32, u32) {
     )
 }
 
+/// Tile coordinates of the rectangle between two corners (inclusive, in any
+/// order), row by row from the bottom.
+fn region_coords(x0: i32, y0: i32, x1: i32, y1: i32) -> impl Iterator<Item = (i32, i32)> {
+    let (min_x, max_x) = (x0.min(x1), x0.max(x1));
+    let (min_y, max_y) = (y0.min(y1), y0.max(y1));
+    (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+}
+
 pub fn world_to_tile(world_x: f32, world_y: f32, tile_size: f32) -> (i32, i32) {
     (
         (world_x / tile_size).floor() as i32,
@@ -378,6 +739,56 @@ pub fn update_bitmasks_around(
     dirty_chunks
 }
 
+/// One tile write of a bulk edit, in world tile coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileEdit {
+    pub tile_x: i32,
+    pub tile_y: i32,
+    pub layer: Layer,
+    pub tile: TileId,
+}
+
+/// Apply many tile writes at once (pasted builds, region fills). Bitmasks are
+/// recomputed once for every edited tile and its neighbours instead of once
+/// per write. Returns the data chunks that need a mesh rebuild.
+pub fn apply_tile_edits(
+    world_map: &mut WorldMap,
+    edits: &[TileEdit],
+    ctx: &WorldCtxRef,
+) -> HashSet<(i32, i32)> {
+    let mut touched = HashSet::new();
+    for edit in edits {
+        world_map.set_tile(edit.tile_x, edit.tile_y, edit.layer, edit.tile, ctx);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let y = edit.tile_y + dy;
+                if y >= 0 && y < ctx.config.height_tiles {
+                    touched.insert((ctx.config.wrap_tile_x(edit.tile_x + dx), y, edit.layer));
+                }
+            }
+        }
+    }
+
+    let mut dirty_chunks = HashSet::new();
+    for (x, y, layer) in touched {
+        let mask = compute_bitmask(
+            |bx, by| {
+                let tile = world_map.get_tile_mut(bx, by, layer, ctx);
+                ctx.tile_registry.is_solid(tile)
+            },
+            x,
+            y,
+        );
+        let (cx, cy) = tile_to_chunk(x, y, ctx.config.chunk_size);
+        let (lx, ly) = tile_to_local(x, y, ctx.config.chunk_size);
+        if let Some(chunk) = world_map.chunks.get_mut(&(cx, cy)) {
+            chunk.layer_mut(layer).bitmasks[(ly * ctx.config.chunk_size + lx) as usize] = mask;
+            dirty_chunks.insert((cx, cy));
+        }
+    }
+    dirty_chunks
+}
+
 /// Compute bitmasks for all tiles in a chunk using neighbor solidity checks.
 pub fn init_chunk_bitmasks(
     world_map: &mut WorldMap,
@@ -421,6 +832,7 @@ pub fn spawn_chunk(
     atlas: &TileAtlas,
     material: &SharedTileMaterial,
     buffers: &mut MeshBuildBuffers,
+    occlusion: f32,
     liquid_registry: &LiquidRegistry,
     liquid_material: Option<&SharedLiquidMaterial>,
     display_chunk_x: i32,
@@ -446,35 +858,41 @@ pub fn spawn_chunk(
     let bg_mesh = build_chunk_mesh(
         &chunk_data.bg.tiles,
         &chunk_data.bg.bitmasks,
+        &[],
         display_chunk_x,
         chunk_y,
         ctx.config.chunk_size,
         ctx.config.tile_size,
         ctx.config.seed,
         Layer::Bg,
+        occlusion,
         ctx.tile_registry,
         autotile_registry,
         &atlas.params,
         buffers,
     );
     let bg_handle = meshes.add(bg_mesh);
+    let bg_diagnostics = buffers.diagnostics;
 
     // Build fg mesh
     let fg_mesh = build_chunk_mesh(
         &chunk_data.fg.tiles,
         &chunk_data.fg.bitmasks,
+        &chunk_data.tile_state,
         display_chunk_x,
         chunk_y,
         ctx.config.chunk_size,
         ctx.config.tile_size,
         ctx.config.seed,
         Layer::Fg,
+        occlusion,
         ctx.tile_registry,
         autotile_registry,
         &atlas.params,
         buffers,
     );
     let fg_handle = meshes.add(fg_mesh);
+    let fg_diagnostics = buffers.diagnostics;
 
     // Spawn bg entity (z=-1.0, behind foreground)
     let bg_entity = commands
@@ -485,6 +903,7 @@ pub fn spawn_chunk(
             },
             ChunkLayer(Layer::Bg),
             Mesh2d(bg_handle),
+            bg_diagnostics,
             MeshMaterial2d(material.bg.clone()),
             Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
             Visibility::default(),
@@ -500,6 +919,7 @@ pub fn spawn_chunk(
             },
             ChunkLayer(Layer::Fg),
             Mesh2d(fg_handle),
+            fg_diagnostics,
             MeshMaterial2d(material.fg.clone()),
             Transform::from_translation(Vec3::ZERO),
             Visibility::default(),
@@ -553,6 +973,7 @@ pub fn spawn_chunk(
             fg: fg_entity,
             bg: bg_entity,
             liquid: liquid_entity,
+            state: ChunkState::Visible,
         },
     );
 }
@@ -584,6 +1005,9 @@ pub fn despawn_chunk(
 /// Registered on `OnEnter(LoadingBiomes)` — at this point deferred commands
 /// have been applied, so the new `ActiveWorld` is in effect and anything left
 /// in `world_map` / `loaded_chunks` is guaranteed stale.
+///
+/// A regenerated world (see [`regenerate`](super::regenerate)) is torn down
+/// by the same system, right after the new `ActiveWorld` is swapped in.
 pub fn clear_stale_chunks(
     mut commands: Commands,
     mut world_map: ResMut<WorldMap>,
@@ -596,6 +1020,8 @@ pub fn clear_stale_chunks(
     mut dirty_chunks: ResMut<DirtyChunks>,
 ) {
     world_map.chunks.clear();
+    world_map.summaries.clear();
+    world_map.explored.clear();
     loaded_chunks.map.clear();
     dirty_chunks.0.clear();
 
@@ -616,14 +1042,21 @@ pub fn clear_stale_chunks(
                 world_map.chunks.insert(coords, chunk_data.clone());
                 dirty_chunks.0.insert(coords);
             }
+            world_map.explored = save.explored.clone();
         }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn chunk_loading_system(
     mut commands: Commands,
-    camera_query: Query<&Transform, With<Camera2d>>,
+    streaming: (
+        Query<&Transform, With<Camera2d>>,
+        Query<&Velocity, With<Player>>,
+        Res<ChunkPreload>,
+        Res<ChunkHibernation>,
+        Res<Time>,
+    ),
     ctx: WorldCtx,
     mut world_map: ResMut<WorldMap>,
     mut loaded_chunks: ResMut<LoadedChunks>,
@@ -631,7 +1064,7 @@ pub fn chunk_loading_system(
     autotile_registry: Res<AutotileRegistry>,
     atlas: Res<TileAtlas>,
     material: Res<SharedTileMaterial>,
-    mut buffers: ResMut<MeshBuildBuffers>,
+    meshing: (ResMut<MeshBuildBuffers>, Option<Res<AmbientOcclusion>>),
     liquid_params: (Res<LiquidRegistry>, Option<Res<SharedLiquidMaterial>>),
     object_registry: Option<Res<ObjectRegistry>>,
     object_sprites: Option<Res<ObjectSpriteMaterials>>,
@@ -639,7 +1072,10 @@ pub fn chunk_loading_system(
     mut lit_materials: ResMut<Assets<LitSpriteMaterial>>,
     object_entities: Query<(Entity, &ObjectDisplayChunk)>,
 ) {
+    let (camera_query, player_velocity, preload, hibernation, time) = streaming;
     let (liquid_registry, liquid_material) = liquid_params;
+    let (mut buffers, occlusion) = meshing;
+    let occlusion = occlusion.map(|o| *o).unwrap_or_default().strength;
     let Ok(camera_transform) = camera_query.single() else {
         return;
     };
@@ -648,36 +1084,12 @@ pub fn chunk_loading_system(
 
     let (cam_tile_x, cam_tile_y) =
         world_to_tile(camera_pos.x, camera_pos.y, ctx_ref.config.tile_size);
-    let (cam_chunk_x, cam_chunk_y) =
-        tile_to_chunk(cam_tile_x, cam_tile_y, ctx_ref.config.chunk_size);
-
-    let mut desired: HashSet<(i32, i32)> = HashSet::new();
-    let load_radius = ctx_ref.config.chunk_load_radius;
-    let world_chunks = ctx_ref.config.width_chunks();
-
-    let mut add_chunks_around = |center_cx: i32| {
-        for display_cx in (center_cx - load_radius)..=(center_cx + load_radius) {
-            for cy in (cam_chunk_y - load_radius)..=(cam_chunk_y + load_radius) {
-                if cy >= 0 && cy < ctx_ref.config.height_chunks() {
-                    desired.insert((display_cx, cy));
-                }
-            }
-        }
-    };
-
-    add_chunks_around(cam_chunk_x);
-
-    if ctx_ref.config.wrap_x {
-        // For wrapping worlds, load duplicate chunks on the other side of the seam
-        if cam_chunk_x < load_radius {
-            add_chunks_around(cam_chunk_x + world_chunks);
-        } else if cam_chunk_x >= world_chunks - load_radius {
-            add_chunks_around(cam_chunk_x - world_chunks);
-        }
-    } else {
-        // For non-wrapping worlds, discard chunks outside [0, world_chunks)
-        desired.retain(|&(cx, _)| cx >= 0 && cx < world_chunks);
-    }
+    let cam_chunk = tile_to_chunk(cam_tile_x, cam_tile_y, ctx_ref.config.chunk_size);
+    let velocity = player_velocity
+        .single()
+        .map(|v| Vec2::new(v.x, v.y))
+        .unwrap_or(Vec2::ZERO);
+    let desired = desired_chunks(cam_chunk, velocity, ctx_ref.config, &preload);
 
     for &(display_cx, cy) in &desired {
         if !loaded_chunks.map.contains_key(&(display_cx, cy)) {
@@ -691,6 +1103,7 @@ pub fn chunk_loading_system(
                 &atlas,
                 &material,
                 &mut buffers,
+                occlusion,
                 &liquid_registry,
                 liquid_material.as_deref(),
                 display_cx,
@@ -727,23 +1140,117 @@ pub fn chunk_loading_system(
         }
     }
 
-    let to_remove: Vec<(i32, i32)> = loaded_chunks
-        .map
-        .keys()
-        .filter(|k| !desired.contains(k))
-        .copied()
-        .collect();
-    for (cx, cy) in to_remove {
-        despawn_objects_for_chunk(&mut commands, &object_entities, cx, cy);
-        despawn_chunk(&mut commands, &mut loaded_chunks, cx, cy);
+    let transitions =
+        loaded_chunks.update_states(&desired, time.elapsed_secs(), hibernation.grace_secs);
+    for ((cx, cy), transition) in transitions {
+        match transition {
+            ChunkTransition::Hide | ChunkTransition::Wake => {
+                let visibility = if transition == ChunkTransition::Hide {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                };
+                let entities = &loaded_chunks.map[&(cx, cy)];
+                commands.entity(entities.fg).insert(visibility);
+                commands.entity(entities.bg).insert(visibility);
+                commands.entity(entities.liquid).insert(visibility);
+                for (entity, display) in &object_entities {
+                    if display.display_chunk == (cx, cy) {
+                        commands.entity(entity).insert(visibility);
+                    }
+                }
+            }
+            ChunkTransition::Despawn => {
+                despawn_objects_for_chunk(&mut commands, &object_entities, cx, cy);
+                despawn_chunk(&mut commands, &mut loaded_chunks, cx, cy);
+            }
+        }
     }
 }
 
-/// Rebuild meshes for chunks marked as dirty (e.g. after tile modification).
-#[allow(clippy::too_many_arguments)]
+/// Chunks that should be loaded for a camera in chunk `cam_chunk`.
+///
+/// A square of `chunk_load_radius` around the camera, extended towards the
+/// direction of `velocity` (px/s) by [`preload_extents`]. On wrapping worlds,
+/// display chunks on the far side of the seam are included as duplicates.
+pub fn desired_chunks(
+    cam_chunk: (i32, i32),
+    velocity: Vec2,
+    config: &ActiveWorld,
+    preload: &ChunkPreload,
+) -> HashSet<(i32, i32)> {
+    let (cam_chunk_x, cam_chunk_y) = cam_chunk;
+    let mut desired: HashSet<(i32, i32)> = HashSet::new();
+    let load_radius = config.chunk_load_radius;
+    let world_chunks = config.width_chunks();
+    let chunk_px = config.chunk_size as f32 * config.tile_size;
+    let (left, right, down, up) = preload_extents(velocity, preload, chunk_px);
+
+    let mut add_chunks_around = |center_cx: i32| {
+        for display_cx in (center_cx - load_radius - left)..=(center_cx + load_radius + right) {
+            for cy in (cam_chunk_y - load_radius - down)..=(cam_chunk_y + load_radius + up) {
+                if cy >= 0 && cy < config.height_chunks() {
+                    desired.insert((display_cx, cy));
+                }
+            }
+        }
+    };
+
+    add_chunks_around(cam_chunk_x);
+
+    if config.wrap_x {
+        // For wrapping worlds, load duplicate chunks on the other side of the seam
+        if cam_chunk_x < load_radius + left {
+            add_chunks_around(cam_chunk_x + world_chunks);
+        } else if cam_chunk_x >= world_chunks - load_radius - right {
+            add_chunks_around(cam_chunk_x - world_chunks);
+        }
+    } else {
+        // For non-wrapping worlds, discard chunks outside [0, world_chunks)
+        // except one on each side, which shows the world border.
+        desired.retain(|&(cx, _)| cx >= -1 && cx <= world_chunks);
+    }
+
+    desired
+}
+
+/// Extra chunks to load beyond the base radius as `(left, right, down, up)`.
+///
+/// Each axis extends only in the direction of motion, by the number of chunks
+/// covered in `lookahead_secs`, capped at `max_extra_chunks`.
+pub fn preload_extents(
+    velocity: Vec2,
+    preload: &ChunkPreload,
+    chunk_px: f32,
+) -> (i32, i32, i32, i32) {
+    let extra = |speed: f32| {
+        ((speed * preload.lookahead_secs / chunk_px).ceil() as i32)
+            .clamp(0, preload.max_extra_chunks)
+    };
+    (
+        extra(-velocity.x),
+        extra(velocity.x),
+        extra(-velocity.y),
+        extra(velocity.y),
+    )
+}
+
+/// Rebuild meshes for chunks marked as dirty (e.g. after tile modification),
+/// each at most once per frame and no more often than [`RemeshDebounce`]
+/// allows.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn rebuild_dirty_chunks(
     mut commands: Commands,
-    query: Query<(Entity, &ChunkCoord, &ChunkLayer), With<ChunkDirty>>,
+    query: Query<
+        (
+            Entity,
+            &ChunkCoord,
+            &ChunkLayer,
+            Option<&ChunkReveal>,
+            Option<&LastRemesh>,
+        ),
+        With<ChunkDirty>,
+    >,
     mut meshes: ResMut<Assets<Mesh>>,
     world_map: Res<WorldMap>,
     wc: Res<ActiveWorld>,
@@ -751,45 +1258,73 @@ pub fn rebuild_dirty_chunks(
     autotile_registry: Res<AutotileRegistry>,
     atlas: Res<TileAtlas>,
     mut buffers: ResMut<MeshBuildBuffers>,
+    loaded_chunks: Res<LoadedChunks>,
+    light_reveal: Option<Res<LightReveal>>,
+    occlusion: Option<Res<AmbientOcclusion>>,
+    time: Res<Time>,
+    debounce: Option<Res<RemeshDebounce>>,
 ) {
-    for (entity, coord, chunk_layer) in &query {
+    let reveal_mode = light_reveal.map(|r| *r).unwrap_or_default();
+    let occlusion = occlusion.map(|o| *o).unwrap_or_default().strength;
+    let now = time.elapsed_secs();
+    for (entity, coord, chunk_layer, reveal, last) in &query {
+        // Hibernating chunks keep their ChunkDirty marker and rebuild on wake.
+        if loaded_chunks.is_hibernating(coord.x, coord.y) {
+            continue;
+        }
+        // Chunks fading in remesh every frame for their tint; anything else
+        // edited again too soon stays dirty until the interval passes.
+        if reveal.is_none()
+            && debounce
+                .as_ref()
+                .is_some_and(|d| !d.ready(last.map(|l| l.0), now))
+        {
+            continue;
+        }
         let data_chunk_x = wc.wrap_chunk_x(coord.x);
         let Some(chunk_data) = world_map.chunks.get(&(data_chunk_x, coord.y)) else {
             continue;
         };
 
-        let (tiles, bitmasks, layer) = match chunk_layer.0 {
+        let (tiles, bitmasks, states, layer) = match chunk_layer.0 {
             Layer::Fg => (
                 chunk_data.fg.tiles.as_slice(),
                 chunk_data.fg.bitmasks.as_slice(),
+                chunk_data.tile_state.as_slice(),
                 Layer::Fg,
             ),
             Layer::Bg => (
                 chunk_data.bg.tiles.as_slice(),
                 chunk_data.bg.bitmasks.as_slice(),
+                &[][..],
                 Layer::Bg,
             ),
         };
 
-        let mesh = build_chunk_mesh(
+        let mut mesh = build_chunk_mesh(
             tiles,
             bitmasks,
+            states,
             coord.x,
             coord.y,
             wc.chunk_size,
             wc.tile_size,
             wc.seed,
             layer,
+            occlusion,
             &registry,
             &autotile_registry,
             &atlas.params,
             &mut buffers,
         );
+        if let Some(reveal) = reveal {
+            scale_tints(&mut mesh, reveal.brightness(reveal_mode));
+        }
 
         let mesh_handle = meshes.add(mesh);
         commands
             .entity(entity)
-            .insert(Mesh2d(mesh_handle))
+            .insert((Mesh2d(mesh_handle), buffers.diagnostics, LastRemesh(now)))
             .remove::<ChunkDirty>();
     }
 }
@@ -799,6 +1334,7 @@ mod tests {
     use super::*;
     use crate::object::definition::{ObjectDef, ObjectType, PlacementRule};
     use crate::object::registry::ObjectRegistry;
+    use crate::registry::tile::TILE_STATE_OFF;
     use crate::test_helpers::fixtures;
 
     #[test]
@@ -911,6 +1447,67 @@ mod tests {
         assert_eq!(t3, t4);
     }
 
+    #[test]
+    fn iter_region_skips_unloaded_chunks() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+
+        // x 94..=97 straddles chunks 2 and 3; only chunk 3 is loaded.
+        let tiles: Vec<_> = map.iter_region(97, 481, 94, 480, Layer::Fg, &ctx).collect();
+        let coords: Vec<_> = tiles.iter().map(|&(x, y, _)| (x, y)).collect();
+        assert_eq!(coords, [(96, 480), (97, 480), (96, 481), (97, 481)]);
+        for (x, y, tile) in tiles {
+            assert_eq!(map.get_tile(x, y, Layer::Fg, &ctx), Some(tile));
+        }
+    }
+
+    #[test]
+    fn iter_region_mut_covers_the_wrap_seam() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let w = wc.width_tiles;
+
+        let tiles: Vec<_> = map
+            .iter_region_mut(w - 2, 500, w + 1, 501, Layer::Bg, &ctx)
+            .collect();
+        let coords: Vec<_> = tiles.iter().map(|&(x, y, _)| (x, y)).collect();
+        let expected: Vec<_> = (500..=501)
+            .flat_map(|y| (w - 2..=w + 1).map(move |x| (x, y)))
+            .collect();
+        assert_eq!(coords, expected);
+
+        // Both sides of the seam are loaded now, and past the edge reads
+        // wrapped tiles.
+        for (x, y, tile) in tiles {
+            assert_eq!(map.get_tile(x, y, Layer::Bg, &ctx), Some(tile));
+            assert_eq!(
+                map.get_tile(x.rem_euclid(w), y, Layer::Bg, &ctx),
+                Some(tile)
+            );
+        }
+        assert_eq!(map.iter_region(-2, 500, 1, 501, Layer::Bg, &ctx).count(), 8);
+    }
+
+    #[test]
+    fn iter_region_out_of_bounds_rows_are_bedrock_and_sky() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let map = WorldMap::default();
+        let stone = tr.by_name("stone");
+
+        let below: Vec<_> = map.iter_region(0, -2, 2, -1, Layer::Fg, &ctx).collect();
+        assert_eq!(below.len(), 6);
+        assert!(below.iter().all(|&(_, _, t)| t == stone));
+
+        let h = wc.height_tiles;
+        let above: Vec<_> = map.iter_region(0, h, 2, h + 1, Layer::Fg, &ctx).collect();
+        assert_eq!(above.len(), 6);
+        assert!(above.iter().all(|&(_, _, t)| t == TileId::AIR));
+    }
+
     #[test]
     fn worldmap_set_tile_wraps() {
         let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
@@ -949,6 +1546,74 @@ mod tests {
         assert_eq!(map.get_tile(100, 500, Layer::Bg, &ctx), Some(stone));
     }
 
+    #[test]
+    fn worldmap_tile_state_roundtrip() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+        assert_eq!(map.get_tile_state(100, 500, &ctx), 0);
+        map.set_tile_state(100, 500, TILE_STATE_OFF, &ctx);
+        assert_eq!(map.get_tile_state(100, 500, &ctx), TILE_STATE_OFF);
+        // Neighbouring tiles are untouched
+        assert_eq!(map.get_tile_state(101, 500, &ctx), 0);
+    }
+
+    #[test]
+    fn set_tile_resets_fg_tile_state() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+        map.set_tile_state(100, 500, TILE_STATE_OFF, &ctx);
+        map.set_tile(100, 500, Layer::Bg, TileId::AIR, &ctx);
+        assert_eq!(map.get_tile_state(100, 500, &ctx), TILE_STATE_OFF);
+        map.set_tile(100, 500, Layer::Fg, TileId::AIR, &ctx);
+        assert_eq!(map.get_tile_state(100, 500, &ctx), 0);
+    }
+
+    #[test]
+    fn worldmap_sign_text_set_and_clear() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), None);
+        map.set_sign_text(100, 500, "Home".into(), &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), Some("Home"));
+        assert_eq!(map.get_sign_text(101, 500, &ctx), None);
+        map.set_sign_text(100, 500, "Mine ->".into(), &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), Some("Mine ->"));
+        map.set_sign_text(100, 500, String::new(), &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), None);
+        assert!(map.chunk(3, 15).unwrap().signs.is_empty());
+    }
+
+    #[test]
+    fn replacing_fg_tile_erases_sign_text() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+        map.set_sign_text(100, 500, "Home".into(), &ctx);
+        map.set_tile(100, 500, Layer::Bg, TileId::AIR, &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), Some("Home"));
+        map.set_tile(100, 500, Layer::Fg, TileId::AIR, &ctx);
+        assert_eq!(map.get_sign_text(100, 500, &ctx), None);
+    }
+
+    #[test]
+    fn chunk_without_saved_state_reads_zero() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(3, 15, &ctx);
+        map.chunk_mut(3, 15).unwrap().tile_state.clear();
+        assert_eq!(map.get_tile_state(100, 500, &ctx), 0);
+        map.set_tile_state(100, 500, TILE_STATE_OFF, &ctx);
+        assert_eq!(map.chunk(3, 15).unwrap().tile_state.len(), 32 * 32);
+    }
+
     fn test_object_registry() -> ObjectRegistry {
         ObjectRegistry::from_defs(vec![
             ObjectDef {
@@ -1080,4 +1745,380 @@ mod tests {
         assert!(!map.is_solid(test_x, test_y, &ctx));
         assert!(!map.is_solid_or_object(test_x, test_y, &ctx, &obj_reg));
     }
+
+    #[test]
+    fn tile_hash_encodes_tile_ids_little_endian() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(0, 0, &ctx);
+        let chunk = map.chunk_mut(0, 0).unwrap();
+        chunk.fg.tiles = vec![TileId(1)];
+        chunk.bg.tiles = vec![TileId(0x0203)];
+        // FNV-1a over bytes [01 00 03 02]; pinned so snapshot hashes stay valid.
+        assert_eq!(chunk.tile_hash(), 0xad34_fe77_47a1_0445);
+    }
+
+    #[test]
+    fn tile_hash_distinguishes_fg_from_bg() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(0, 0, &ctx);
+        let chunk = map.chunk_mut(0, 0).unwrap();
+        chunk.fg.tiles = vec![TileId(1), TileId(2)];
+        chunk.bg.tiles = vec![TileId(2), TileId(1)];
+        let before = chunk.tile_hash();
+        std::mem::swap(&mut chunk.fg.tiles, &mut chunk.bg.tiles);
+        assert_ne!(chunk.tile_hash(), before);
+    }
+
+    #[test]
+    fn sorted_chunk_coords_orders_by_row_then_column() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        for (cx, cy) in [(3, 1), (0, 2), (1, 1), (2, 0)] {
+            map.get_or_generate_chunk(cx, cy, &ctx);
+        }
+        assert_eq!(
+            map.sorted_chunk_coords(),
+            vec![(2, 0), (1, 1), (3, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn walled_world_edges_load_and_render_as_the_border_tile() {
+        let (mut wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        wc.wrap_x = false;
+        pc.border_tile = Some("stone".into());
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let stone = tr.by_name("stone");
+        let last_chunk = wc.width_chunks() - 1;
+
+        // One chunk past each edge is loaded so the wall is on screen.
+        let preload = ChunkPreload::default();
+        let left = desired_chunks((0, 10), Vec2::ZERO, &wc, &preload);
+        assert!(left.contains(&(-1, 10)));
+        assert!(!left.contains(&(-2, 10)));
+        let right = desired_chunks((last_chunk, 10), Vec2::ZERO, &wc, &preload);
+        assert!(right.contains(&(last_chunk + 1, 10)));
+        assert!(!right.contains(&(last_chunk + 2, 10)));
+
+        // Those chunks generate as solid border tiles, sky rows included.
+        let mut map = WorldMap::default();
+        let top = wc.height_chunks() - 1;
+        for (cx, cy) in [(-1, 10), (last_chunk + 1, top)] {
+            let chunk = map.get_or_generate_chunk(cx, cy, &ctx);
+            assert!(chunk.fg.tiles.iter().all(|&t| t == stone));
+            assert!(chunk.bg.tiles.iter().all(|&t| t == TileId::AIR));
+        }
+
+        // Unloaded edge columns still read as the border, and can't be edited.
+        let y = wc.height_tiles - 10;
+        assert_eq!(map.get_tile(-40, y, Layer::Fg, &ctx), Some(stone));
+        assert!(map.is_solid(wc.width_tiles, y, &ctx));
+        map.set_tile(-1, 400, Layer::Fg, TileId::AIR, &ctx);
+        assert_eq!(map.get_tile(-1, 400, Layer::Fg, &ctx), Some(stone));
+        assert_eq!(map.get_tile_mut(-1, 400, Layer::Fg, &ctx), stone);
+
+        // Open edges stay air.
+        pc.border_tile = None;
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        assert!(!map.is_solid(-1, y, &ctx));
+    }
+
+    #[test]
+    fn stationary_desired_chunks_are_symmetric() {
+        let wc = fixtures::test_world_config();
+        let desired = desired_chunks((10, 10), Vec2::ZERO, &wc, &ChunkPreload::default());
+        let r = wc.chunk_load_radius;
+        let side = (2 * r + 1) as usize;
+        assert_eq!(desired.len(), side * side);
+        assert!(desired.contains(&(10 - r, 10 - r)));
+        assert!(desired.contains(&(10 + r, 10 + r)));
+    }
+
+    #[test]
+    fn falling_preloads_further_below_than_above() {
+        let wc = fixtures::test_world_config();
+        let preload = ChunkPreload::default();
+        let desired = desired_chunks((10, 20), Vec2::new(0.0, -1500.0), &wc, &preload);
+        let min_y = desired.iter().map(|&(_, y)| y).min().unwrap();
+        let max_y = desired.iter().map(|&(_, y)| y).max().unwrap();
+        let below = 20 - min_y;
+        let above = max_y - 20;
+        assert_eq!(above, wc.chunk_load_radius);
+        assert!(below > above, "below={below} above={above}");
+    }
+
+    #[test]
+    fn preload_extents_are_capped() {
+        let preload = ChunkPreload::default();
+        let (left, right, down, up) = preload_extents(Vec2::new(1e6, -1e6), &preload, 1024.0);
+        assert_eq!((left, right, up), (0, preload.max_extra_chunks, 0));
+        assert_eq!(down, preload.max_extra_chunks);
+    }
+
+    fn loaded_entry(state: ChunkState) -> ChunkEntities {
+        ChunkEntities {
+            fg: Entity::PLACEHOLDER,
+            bg: Entity::PLACEHOLDER,
+            liquid: Entity::PLACEHOLDER,
+            state,
+        }
+    }
+
+    /// Replay `chunk_loading_system`'s bookkeeping for a camera that sits in
+    /// each chunk of `path` for one frame of `dt` seconds.
+    /// Returns the final loaded set plus total spawns and despawns.
+    fn simulate_camera(
+        path: &[(i32, i32)],
+        dt: f32,
+        grace_secs: f32,
+    ) -> (LoadedChunks, usize, usize) {
+        let wc = fixtures::test_world_config();
+        let preload = ChunkPreload::default();
+        let mut loaded = LoadedChunks::default();
+        let (mut spawns, mut despawns) = (0, 0);
+        for (frame, &cam) in path.iter().enumerate() {
+            let desired = desired_chunks(cam, Vec2::ZERO, &wc, &preload);
+            for &coords in &desired {
+                loaded.map.entry(coords).or_insert_with(|| {
+                    spawns += 1;
+                    loaded_entry(ChunkState::Visible)
+                });
+            }
+            let now = frame as f32 * dt;
+            for (coords, transition) in loaded.update_states(&desired, now, grace_secs) {
+                if transition == ChunkTransition::Despawn {
+                    loaded.map.remove(&coords);
+                    despawns += 1;
+                }
+            }
+        }
+        (loaded, spawns, despawns)
+    }
+
+    #[test]
+    fn chunk_state_transitions() {
+        let mut loaded = LoadedChunks::default();
+        loaded.map.insert((0, 0), loaded_entry(ChunkState::Visible));
+        let here: HashSet<(i32, i32)> = [(0, 0)].into();
+        let away = HashSet::new();
+
+        assert!(loaded.update_states(&here, 0.0, 5.0).is_empty());
+        assert_eq!(
+            loaded.update_states(&away, 1.0, 5.0),
+            vec![((0, 0), ChunkTransition::Hide)]
+        );
+        assert!(loaded.is_hibernating(0, 0));
+        assert!(loaded.update_states(&away, 5.9, 5.0).is_empty());
+        assert_eq!(
+            loaded.update_states(&here, 6.0, 5.0),
+            vec![((0, 0), ChunkTransition::Wake)]
+        );
+        assert!(loaded.is_visible(0, 0));
+
+        // The grace period restarts each time the chunk is hidden.
+        loaded.update_states(&away, 10.0, 5.0);
+        assert!(loaded.update_states(&away, 14.0, 5.0).is_empty());
+        assert_eq!(
+            loaded.update_states(&away, 15.0, 5.0),
+            vec![((0, 0), ChunkTransition::Despawn)]
+        );
+    }
+
+    #[test]
+    fn zero_grace_despawns_immediately() {
+        let mut loaded = LoadedChunks::default();
+        loaded.map.insert((3, 4), loaded_entry(ChunkState::Visible));
+        assert_eq!(
+            loaded.update_states(&HashSet::new(), 0.0, 0.0),
+            vec![((3, 4), ChunkTransition::Despawn)]
+        );
+    }
+
+    #[test]
+    fn pacing_across_chunk_boundary_never_regenerates() {
+        let wc = fixtures::test_world_config();
+        let preload = ChunkPreload::default();
+        let a = desired_chunks((10, 10), Vec2::ZERO, &wc, &preload);
+        let b = desired_chunks((11, 10), Vec2::ZERO, &wc, &preload);
+
+        // Cross back and forth every half second for ten seconds.
+        let path: Vec<(i32, i32)> = (0..20)
+            .flat_map(|i| std::iter::repeat_n((10 + i % 2, 10), 30))
+            .collect();
+        let (loaded, spawns, despawns) = simulate_camera(&path, 1.0 / 60.0, 5.0);
+
+        assert_eq!(spawns, a.union(&b).count());
+        assert_eq!(despawns, 0);
+        // Ended in chunk 11: the column only chunk 10 wanted is hibernating.
+        for coords in a.difference(&b) {
+            assert!(loaded.is_hibernating(coords.0, coords.1), "{coords:?}");
+        }
+        for coords in &b {
+            assert!(loaded.is_visible(coords.0, coords.1), "{coords:?}");
+        }
+    }
+
+    #[test]
+    fn walking_away_despawns_after_grace_period() {
+        let wc = fixtures::test_world_config();
+        let preload = ChunkPreload::default();
+        let start = desired_chunks((10, 10), Vec2::ZERO, &wc, &preload);
+        let end = desired_chunks((30, 10), Vec2::ZERO, &wc, &preload);
+        assert!(start.is_disjoint(&end));
+
+        // 4 s after leaving: everything from the start is still hibernating.
+        let mut path = vec![(10, 10)];
+        path.extend(std::iter::repeat_n((30, 10), 4 * 60));
+        let (loaded, _, despawns) = simulate_camera(&path, 1.0 / 60.0, 5.0);
+        assert_eq!(despawns, 0);
+        assert_eq!(loaded.map.len(), start.len() + end.len());
+
+        // 6 s after leaving: the start area has been freed.
+        path.extend(std::iter::repeat_n((30, 10), 2 * 60));
+        let (loaded, _, despawns) = simulate_camera(&path, 1.0 / 60.0, 5.0);
+        assert_eq!(despawns, start.len());
+        assert_eq!(loaded.map.len(), end.len());
+    }
+
+    /// App running only [`rebuild_dirty_chunks`], with chunk (2, 20)
+    /// generated.
+    fn remesh_app() -> App {
+        use crate::world::atlas::AtlasParams;
+
+        let mut app = fixtures::test_app();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<AutotileRegistry>()
+            .init_resource::<MeshBuildBuffers>()
+            .init_resource::<LoadedChunks>()
+            .insert_resource(TileAtlas {
+                image: Handle::default(),
+                params: AtlasParams {
+                    tile_size: 16,
+                    rows: 47,
+                    atlas_width: 16,
+                    atlas_height: 752,
+                    uv_inset: 0.5,
+                },
+                column_map: Default::default(),
+            })
+            .add_systems(Update, rebuild_dirty_chunks);
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world_mut()
+            .resource_mut::<WorldMap>()
+            .get_or_generate_chunk(2, 20, &ctx);
+        app
+    }
+
+    #[test]
+    fn hibernating_chunks_rebuild_on_wake() {
+        let mut app = remesh_app();
+        let entity = app
+            .world_mut()
+            .spawn((
+                ChunkCoord { x: 2, y: 20 },
+                ChunkLayer(Layer::Fg),
+                ChunkDirty,
+            ))
+            .id();
+        let mut entry = loaded_entry(ChunkState::Hibernating { since: 0.0 });
+        entry.fg = entity;
+        app.world_mut()
+            .resource_mut::<LoadedChunks>()
+            .map
+            .insert((2, 20), entry);
+
+        app.update();
+        let e = app.world().entity(entity);
+        assert!(e.contains::<ChunkDirty>());
+        assert!(!e.contains::<Mesh2d>());
+
+        app.world_mut()
+            .resource_mut::<LoadedChunks>()
+            .map
+            .get_mut(&(2, 20))
+            .unwrap()
+            .state = ChunkState::Visible;
+        app.update();
+        let e = app.world().entity(entity);
+        assert!(!e.contains::<ChunkDirty>());
+        assert!(e.contains::<Mesh2d>());
+    }
+
+    #[test]
+    fn many_edits_in_a_frame_remesh_once() {
+        let mut app = remesh_app();
+        app.add_systems(
+            Update,
+            (|mut commands: Commands, chunks: Query<Entity, With<ChunkLayer>>| {
+                // Three edits landing in the same chunk this frame.
+                for entity in &chunks {
+                    for _ in 0..3 {
+                        commands.entity(entity).insert(ChunkDirty);
+                    }
+                }
+            })
+            .run_if(|mut ran: Local<bool>| !std::mem::replace(&mut *ran, true))
+            .before(rebuild_dirty_chunks),
+        );
+        let entity = app
+            .world_mut()
+            .spawn((ChunkCoord { x: 2, y: 20 }, ChunkLayer(Layer::Fg)))
+            .id();
+
+        app.update();
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), 1);
+        assert!(!app.world().entity(entity).contains::<ChunkDirty>());
+        app.update();
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), 1);
+    }
+
+    #[test]
+    fn debounce_caps_the_remesh_rate_of_a_chunk_under_edits() {
+        let mut app = remesh_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_millis(20),
+        ))
+        .insert_resource(RemeshDebounce {
+            min_interval_secs: Some(0.05),
+        });
+        let entity = app
+            .world_mut()
+            .spawn((
+                ChunkCoord { x: 2, y: 20 },
+                ChunkLayer(Layer::Fg),
+                ChunkDirty,
+            ))
+            .id();
+        let meshes = |app: &App| app.world().resource::<Assets<Mesh>>().len();
+
+        // The first edit rebuilds straight away.
+        app.update();
+        assert_eq!(meshes(&app), 1);
+
+        // Edited every frame: rebuilt again only once the interval has passed.
+        let mut rebuilt_at = Vec::new();
+        for frame in 1..=6 {
+            app.world_mut().entity_mut(entity).insert(ChunkDirty);
+            app.update();
+            if meshes(&app) > rebuilt_at.len() + 1 {
+                rebuilt_at.push(frame);
+            }
+        }
+        assert_eq!(rebuilt_at, [3, 6]);
+
+        // Without a debounce every dirty frame rebuilds.
+        app.world_mut()
+            .resource_mut::<RemeshDebounce>()
+            .min_interval_secs = None;
+        app.world_mut().entity_mut(entity).insert(ChunkDirty);
+        app.update();
+        assert_eq!(meshes(&app), 4);
+    }
 }
@@ -0,0 +1,240 @@
+//! How newly loaded chunks light up.
+//!
+//! Lighting itself is global (the radiance-cascade lightmap covers every
+//! loaded tile at once), so a chunk is lit fully the frame it spawns. With
+//! [`LightReveal::Progressive`] each new chunk instead starts dark and
+//! brightens over a few frames: it carries a [`ChunkReveal`] and goes back
+//! through the `ChunkDirty` rebuild queue every frame, with its mesh tint
+//! scaled by [`reveal_brightness`], until the ramp completes.
+
+use bevy::prelude::*;
+
+use super::chunk::{ChunkDirty, ChunkLayer};
+
+/// How chunks light up when they load.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LightReveal {
+    /// Fully lit the frame they spawn.
+    #[default]
+    Instant,
+    /// Start dark and brighten over `frames` frames.
+    Progressive { frames: u32 },
+}
+
+impl LightReveal {
+    /// Frames the progressive ramp takes by default.
+    pub const DEFAULT_FRAMES: u32 = 20;
+}
+
+/// A chunk mesh part-way through its reveal, `frame` frames in.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkReveal {
+    pub frame: u32,
+}
+
+/// Mesh brightness `frame` frames into a reveal of `frames`: 0.0 on the
+/// first frame, easing up to 1.0 on frame `frames`.
+pub fn reveal_brightness(frame: u32, frames: u32) -> f32 {
+    if frames == 0 {
+        return 1.0;
+    }
+    let t = (frame as f32 / frames as f32).min(1.0);
+    t * (2.0 - t)
+}
+
+impl ChunkReveal {
+    /// Brightness its mesh is built with under `mode`.
+    pub fn brightness(&self, mode: LightReveal) -> f32 {
+        match mode {
+            LightReveal::Instant => 1.0,
+            LightReveal::Progressive { frames } => reveal_brightness(self.frame, frames),
+        }
+    }
+}
+
+/// Start newly spawned chunk meshes dark and step the ones revealing, queueing
+/// a rebuild of each. Runs between chunk loading and `rebuild_dirty_chunks`.
+pub fn reveal_chunks(
+    mut commands: Commands,
+    mode: Res<LightReveal>,
+    spawned: Query<Entity, Added<ChunkLayer>>,
+    mut revealing: Query<(Entity, &mut ChunkReveal)>,
+) {
+    let frames = match *mode {
+        LightReveal::Instant => 0,
+        LightReveal::Progressive { frames } => frames,
+    };
+    for (entity, mut reveal) in &mut revealing {
+        reveal.frame += 1;
+        if reveal.frame >= frames {
+            commands
+                .entity(entity)
+                .remove::<ChunkReveal>()
+                .insert(ChunkDirty);
+        } else {
+            commands.entity(entity).insert(ChunkDirty);
+        }
+    }
+    if frames == 0 {
+        return;
+    }
+    for entity in &spawned {
+        commands
+            .entity(entity)
+            .insert((ChunkReveal { frame: 0 }, ChunkDirty));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+    use crate::world::atlas::{AtlasParams, TileAtlas};
+    use crate::world::autotile::AutotileRegistry;
+    use crate::world::chunk::{
+        rebuild_dirty_chunks, ChunkCoord, ChunkEntities, ChunkState, Layer, LoadedChunks, WorldMap,
+    };
+    use crate::world::mesh_builder::{build_chunk_mesh, AmbientOcclusion, MeshBuildBuffers};
+    use crate::world::tile_renderer::ATTRIBUTE_TINT;
+    use bevy::mesh::VertexAttributeValues;
+
+    const CHUNK: (i32, i32) = (2, 20);
+
+    /// App running the reveal and the rebuild queue over one generated
+    /// underground chunk; returns it with the chunk's fg entity.
+    fn reveal_app(mode: LightReveal) -> (App, Entity) {
+        let mut app = fixtures::test_app();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<AutotileRegistry>()
+            .init_resource::<MeshBuildBuffers>()
+            .init_resource::<LoadedChunks>()
+            .insert_resource(mode)
+            .insert_resource(TileAtlas {
+                image: Handle::default(),
+                params: test_atlas_params(),
+                column_map: Default::default(),
+            })
+            .add_systems(Update, (reveal_chunks, rebuild_dirty_chunks).chain());
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        app.world_mut()
+            .resource_mut::<WorldMap>()
+            .get_or_generate_chunk(CHUNK.0, CHUNK.1, &ctx);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                ChunkCoord {
+                    x: CHUNK.0,
+                    y: CHUNK.1,
+                },
+                ChunkLayer(Layer::Fg),
+            ))
+            .id();
+        app.world_mut().resource_mut::<LoadedChunks>().map.insert(
+            CHUNK,
+            ChunkEntities {
+                fg: entity,
+                bg: Entity::PLACEHOLDER,
+                liquid: Entity::PLACEHOLDER,
+                state: ChunkState::Visible,
+            },
+        );
+        (app, entity)
+    }
+
+    fn test_atlas_params() -> AtlasParams {
+        AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        }
+    }
+
+    /// Tints of the chunk built straight from its data, as spawning does.
+    fn full_tints(app: &App) -> Vec<[f32; 3]> {
+        let (wc, _, _, tr, _, _) = fixtures::test_world_ctx();
+        let chunk = &app.world().resource::<WorldMap>().chunks[&CHUNK];
+        let mesh = build_chunk_mesh(
+            &chunk.fg.tiles,
+            &chunk.fg.bitmasks,
+            &chunk.tile_state,
+            CHUNK.0,
+            CHUNK.1,
+            wc.chunk_size,
+            wc.tile_size,
+            wc.seed,
+            Layer::Fg,
+            AmbientOcclusion::default().strength,
+            &tr,
+            &AutotileRegistry::default(),
+            &test_atlas_params(),
+            &mut MeshBuildBuffers::default(),
+        );
+        tints(&mesh)
+    }
+
+    fn tints(mesh: &Mesh) -> Vec<[f32; 3]> {
+        match mesh.attribute(ATTRIBUTE_TINT) {
+            Some(VertexAttributeValues::Float32x3(values)) => values.clone(),
+            other => panic!("unexpected tint attribute {other:?}"),
+        }
+    }
+
+    fn mesh_tints(app: &App, entity: Entity) -> Vec<[f32; 3]> {
+        let handle = &app.world().get::<Mesh2d>(entity).expect("no mesh").0;
+        tints(app.world().resource::<Assets<Mesh>>().get(handle).unwrap())
+    }
+
+    fn mean(tints: &[[f32; 3]]) -> f32 {
+        tints.iter().map(|t| t[1]).sum::<f32>() / tints.len() as f32
+    }
+
+    #[test]
+    fn brightness_ramps_from_dark_to_full() {
+        assert_eq!(reveal_brightness(0, 10), 0.0);
+        assert_eq!(reveal_brightness(10, 10), 1.0);
+        assert_eq!(reveal_brightness(15, 10), 1.0);
+        assert_eq!(reveal_brightness(0, 0), 1.0);
+        let ramp: Vec<f32> = (0..=10).map(|f| reveal_brightness(f, 10)).collect();
+        assert!(ramp.windows(2).all(|w| w[0] < w[1]), "{ramp:?}");
+    }
+
+    #[test]
+    fn progressive_chunks_start_dark_and_converge() {
+        let frames = 5;
+        let (mut app, entity) = reveal_app(LightReveal::Progressive { frames });
+        let full = full_tints(&app);
+        assert!(!full.is_empty());
+
+        app.update();
+        let mut previous = mean(&mesh_tints(&app, entity));
+        assert_eq!(previous, 0.0, "a new chunk starts dark");
+        for _ in 1..frames {
+            app.update();
+            let now = mean(&mesh_tints(&app, entity));
+            assert!(previous < now && now < mean(&full), "{previous} -> {now}");
+            previous = now;
+        }
+
+        app.update();
+        assert_eq!(mesh_tints(&app, entity), full);
+        let e = app.world().entity(entity);
+        assert!(!e.contains::<ChunkReveal>() && !e.contains::<ChunkDirty>());
+        // Nothing more to do once revealed.
+        app.update();
+        assert!(!app.world().entity(entity).contains::<ChunkDirty>());
+    }
+
+    #[test]
+    fn instant_chunks_keep_their_full_light() {
+        let (mut app, entity) = reveal_app(LightReveal::Instant);
+        app.world_mut().entity_mut(entity).insert(ChunkDirty);
+        app.update();
+        assert!(!app.world().entity(entity).contains::<ChunkReveal>());
+        assert_eq!(mesh_tints(&app, entity), full_tints(&app));
+    }
+}
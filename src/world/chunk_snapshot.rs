@@ -0,0 +1,704 @@
+//! Compact byte encoding of a chunk's tiles, for sending chunks between
+//! peers (groundwork for multiplayer; nothing goes over a network yet).
+//!
+//! A [`ChunkSnapshot`] holds a chunk's foreground and background tiles.
+//! Chunks rarely use more than a handful of tile types, so each layer is
+//! written as a palette of the tile ids it uses followed by bit-packed
+//! palette indices: a chunk of stone packs into a few bytes and a surface
+//! chunk into a few hundred. A [`ChunkDelta`] lists only the cells that
+//! changed between two snapshots.
+//!
+//! Snapshot layout, little-endian:
+//!
+//! ```text
+//! u8 version, u8 b'S', u16 chunk size
+//! per layer (fg, then bg):
+//!     u16 palette length, palette length × u16 tile id,
+//!     chunk size² indices of ceil(log2(palette length)) bits, LSB first,
+//!     padded to a whole byte
+//! ```
+//!
+//! Delta layout: `u8 version, u8 b'D', u16 chunk size, u32 change count`,
+//! then per change `u8 layer (0 fg, 1 bg), u16 cell index, u16 tile id`.
+//!
+//! Both decoders reject other versions: peers have to run the same format.
+//! [`WorldMap::apply_snapshot`] and [`WorldMap::apply_delta`] write the
+//! received tiles through [`apply_tile_edits`], like a schematic paste.
+
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::registry::tile::TileId;
+use crate::world::chunk::{apply_tile_edits, ChunkData, Layer, TileEdit, WorldMap};
+use crate::world::ctx::WorldCtxRef;
+
+/// Version written into snapshots and deltas. Bump it when the layout
+/// changes.
+pub const SNAPSHOT_VERSION: u8 = 1;
+/// Largest chunk side a snapshot describes, so cell indices fit a `u16`.
+const MAX_CHUNK_SIZE: u32 = 256;
+const SNAPSHOT_TAG: u8 = b'S';
+const DELTA_TAG: u8 = b'D';
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SnapshotError {
+    #[error("snapshot data ends early")]
+    Truncated,
+    #[error("snapshot format version {found}, this build reads version {supported}")]
+    UnsupportedVersion { found: u8, supported: u8 },
+    #[error("expected a chunk {expected}, found tag {found:#04x}")]
+    WrongKind { expected: &'static str, found: u8 },
+    #[error("malformed snapshot: {0}")]
+    Malformed(&'static str),
+    #[error(
+        "snapshot of {found}×{found} tile chunks, this world's chunks are \
+         {expected}×{expected}"
+    )]
+    SizeMismatch { found: u32, expected: u32 },
+}
+
+/// Foreground and background tiles of one chunk, row-major like
+/// [`ChunkData`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSnapshot {
+    /// Tiles per chunk side.
+    pub size: u32,
+    pub fg: Vec<TileId>,
+    pub bg: Vec<TileId>,
+}
+
+impl ChunkSnapshot {
+    /// Tiles of `chunk`, a chunk of `size`×`size` tiles.
+    pub fn of(chunk: &ChunkData, size: u32) -> Self {
+        Self {
+            size,
+            fg: chunk.fg.tiles.clone(),
+            bg: chunk.bg.tiles.clone(),
+        }
+    }
+
+    pub fn layer(&self, layer: Layer) -> &[TileId] {
+        match layer {
+            Layer::Fg => &self.fg,
+            Layer::Bg => &self.bg,
+        }
+    }
+
+    fn layer_mut(&mut self, layer: Layer) -> &mut [TileId] {
+        match layer {
+            Layer::Fg => &mut self.fg,
+            Layer::Bg => &mut self.bg,
+        }
+    }
+
+    /// Tiles per layer.
+    pub fn cells(&self) -> usize {
+        (self.size * self.size) as usize
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![SNAPSHOT_VERSION, SNAPSHOT_TAG];
+        out.extend_from_slice(&(self.size as u16).to_le_bytes());
+        for tiles in [&self.fg, &self.bg] {
+            encode_layer(tiles, &mut out);
+        }
+        out
+    }
+
+    /// Read a snapshot written by [`ChunkSnapshot::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader::new(bytes);
+        let size = reader.header(SNAPSHOT_TAG, "snapshot")?;
+        let cells = (size * size) as usize;
+        let fg = decode_layer(&mut reader, cells)?;
+        let bg = decode_layer(&mut reader, cells)?;
+        reader.finish()?;
+        Ok(Self { size, fg, bg })
+    }
+}
+
+/// One cell that differs between two snapshots, and its new tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub layer: Layer,
+    /// Row-major index within the chunk.
+    pub index: u16,
+    pub tile: TileId,
+}
+
+/// The cells that changed between two snapshots of a chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkDelta {
+    pub size: u32,
+    pub changes: Vec<CellChange>,
+}
+
+impl ChunkDelta {
+    /// Changes turning `old` into `new`, foreground first, in cell order.
+    ///
+    /// # Panics
+    /// If the snapshots are of differently sized chunks.
+    pub fn between(old: &ChunkSnapshot, new: &ChunkSnapshot) -> Self {
+        assert_eq!(old.size, new.size, "delta between different chunk sizes");
+        let changes = [Layer::Fg, Layer::Bg]
+            .into_iter()
+            .flat_map(move |layer| {
+                old.layer(layer)
+                    .iter()
+                    .zip(new.layer(layer))
+                    .enumerate()
+                    .filter(|(_, (a, b))| a != b)
+                    .map(move |(index, (_, &tile))| CellChange {
+                        layer,
+                        index: index as u16,
+                        tile,
+                    })
+            })
+            .collect();
+        Self {
+            size: old.size,
+            changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Check the delta is for chunks of `size` and stays inside them.
+    fn check(&self, size: u32) -> Result<(), SnapshotError> {
+        check_size(self.size, size)?;
+        if self.changes.iter().any(|c| c.index as u32 >= size * size) {
+            return Err(SnapshotError::Malformed("cell index outside the chunk"));
+        }
+        Ok(())
+    }
+
+    /// Write the changes into `snapshot`.
+    pub fn apply_to(&self, snapshot: &mut ChunkSnapshot) -> Result<(), SnapshotError> {
+        self.check(snapshot.size)?;
+        for change in &self.changes {
+            snapshot.layer_mut(change.layer)[change.index as usize] = change.tile;
+        }
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![SNAPSHOT_VERSION, DELTA_TAG];
+        out.extend_from_slice(&(self.size as u16).to_le_bytes());
+        out.extend_from_slice(&(self.changes.len() as u32).to_le_bytes());
+        for change in &self.changes {
+            out.push(match change.layer {
+                Layer::Fg => 0,
+                Layer::Bg => 1,
+            });
+            out.extend_from_slice(&change.index.to_le_bytes());
+            out.extend_from_slice(&change.tile.0.to_le_bytes());
+        }
+        out
+    }
+
+    /// Read a delta written by [`ChunkDelta::encode`] or [`encode_delta`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader::new(bytes);
+        let size = reader.header(DELTA_TAG, "delta")?;
+        let count = reader.u32()? as usize;
+        // Five bytes per change: don't trust a count the data can't hold.
+        if count > reader.remaining() / 5 {
+            return Err(SnapshotError::Truncated);
+        }
+        let mut changes = Vec::with_capacity(count);
+        for _ in 0..count {
+            let layer = match reader.u8()? {
+                0 => Layer::Fg,
+                1 => Layer::Bg,
+                _ => return Err(SnapshotError::Malformed("unknown layer")),
+            };
+            let index = reader.u16()?;
+            if index as u32 >= size * size {
+                return Err(SnapshotError::Malformed("cell index outside the chunk"));
+            }
+            let tile = TileId(reader.u16()?);
+            changes.push(CellChange { layer, index, tile });
+        }
+        reader.finish()?;
+        Ok(Self { size, changes })
+    }
+}
+
+/// Encoded [`ChunkDelta`] turning `old` into `new`.
+pub fn encode_delta(old: &ChunkSnapshot, new: &ChunkSnapshot) -> Vec<u8> {
+    ChunkDelta::between(old, new).encode()
+}
+
+/// Tile writes made by applying a snapshot or delta to the world, and the
+/// data chunks whose bitmasks they touched.
+///
+/// Like a schematic paste, the caller marks `dirty_chunks` for a mesh
+/// rebuild and for saving, sends a [`TileChanged`] for each foreground edit
+/// and flags the lighting grids for a rebuild.
+///
+/// [`TileChanged`]: crate::world::chunk::TileChanged
+#[derive(Debug, Default)]
+pub struct AppliedSnapshot {
+    pub edits: Vec<TileEdit>,
+    pub dirty_chunks: HashSet<(i32, i32)>,
+}
+
+impl WorldMap {
+    /// Snapshot of loaded chunk (`cx`, `cy`), in data chunk coordinates.
+    pub fn snapshot_chunk(&self, cx: i32, cy: i32) -> Option<ChunkSnapshot> {
+        let chunk = self.chunk(cx, cy)?;
+        Some(ChunkSnapshot::of(
+            chunk,
+            (chunk.fg.tiles.len() as u32).isqrt(),
+        ))
+    }
+
+    /// Make chunk (`cx`, `cy`) hold the snapshot's tiles, generating it
+    /// first if needed. Only cells that differ are written.
+    pub fn apply_snapshot(
+        &mut self,
+        cx: i32,
+        cy: i32,
+        snapshot: &ChunkSnapshot,
+        ctx: &WorldCtxRef,
+    ) -> Result<AppliedSnapshot, SnapshotError> {
+        check_size(snapshot.size, ctx.config.chunk_size)?;
+        let cx = ctx.config.wrap_chunk_x(cx);
+        let current = ChunkSnapshot::of(
+            self.get_or_generate_chunk(cx, cy, ctx),
+            ctx.config.chunk_size,
+        );
+        let delta = ChunkDelta::between(&current, snapshot);
+        Ok(self.write_changes(cx, cy, &delta.changes, ctx))
+    }
+
+    /// Apply the delta's changes to chunk (`cx`, `cy`), generating it first
+    /// if needed. Changes that match the chunk already are skipped.
+    pub fn apply_delta(
+        &mut self,
+        cx: i32,
+        cy: i32,
+        delta: &ChunkDelta,
+        ctx: &WorldCtxRef,
+    ) -> Result<AppliedSnapshot, SnapshotError> {
+        delta.check(ctx.config.chunk_size)?;
+        let cx = ctx.config.wrap_chunk_x(cx);
+        let chunk = self.get_or_generate_chunk(cx, cy, ctx);
+        let changes: Vec<CellChange> = delta
+            .changes
+            .iter()
+            .filter(|c| chunk.layer(c.layer).tiles[c.index as usize] != c.tile)
+            .copied()
+            .collect();
+        Ok(self.write_changes(cx, cy, &changes, ctx))
+    }
+
+    fn write_changes(
+        &mut self,
+        cx: i32,
+        cy: i32,
+        changes: &[CellChange],
+        ctx: &WorldCtxRef,
+    ) -> AppliedSnapshot {
+        let size = ctx.config.chunk_size;
+        let edits: Vec<TileEdit> = changes
+            .iter()
+            .map(|change| {
+                let index = change.index as u32;
+                TileEdit {
+                    tile_x: cx * size as i32 + (index % size) as i32,
+                    tile_y: cy * size as i32 + (index / size) as i32,
+                    layer: change.layer,
+                    tile: change.tile,
+                }
+            })
+            .collect();
+        let dirty_chunks = apply_tile_edits(self, &edits, ctx);
+        AppliedSnapshot {
+            edits,
+            dirty_chunks,
+        }
+    }
+}
+
+fn check_size(found: u32, expected: u32) -> Result<(), SnapshotError> {
+    if found == expected {
+        Ok(())
+    } else {
+        Err(SnapshotError::SizeMismatch { found, expected })
+    }
+}
+
+/// Bits per palette index for a palette of `len` tiles.
+fn index_bits(len: usize) -> u32 {
+    if len <= 1 {
+        0
+    } else {
+        usize::BITS - (len - 1).leading_zeros()
+    }
+}
+
+fn encode_layer(tiles: &[TileId], out: &mut Vec<u8>) {
+    let mut palette: Vec<TileId> = Vec::new();
+    let mut slots: HashMap<TileId, u16> = HashMap::new();
+    let indices: Vec<u16> = tiles
+        .iter()
+        .map(|&tile| {
+            *slots.entry(tile).or_insert_with(|| {
+                palette.push(tile);
+                (palette.len() - 1) as u16
+            })
+        })
+        .collect();
+    out.extend_from_slice(&(palette.len() as u16).to_le_bytes());
+    for tile in &palette {
+        out.extend_from_slice(&tile.0.to_le_bytes());
+    }
+
+    let bits = index_bits(palette.len());
+    if bits == 0 {
+        return;
+    }
+    let (mut acc, mut filled) = (0u32, 0u32);
+    for index in indices {
+        acc |= (index as u32) << filled;
+        filled += bits;
+        while filled >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            filled -= 8;
+        }
+    }
+    if filled > 0 {
+        out.push(acc as u8);
+    }
+}
+
+fn decode_layer(reader: &mut Reader, cells: usize) -> Result<Vec<TileId>, SnapshotError> {
+    let len = reader.u16()? as usize;
+    if len == 0 || len > cells {
+        return Err(SnapshotError::Malformed("palette size"));
+    }
+    let palette = (0..len)
+        .map(|_| reader.u16().map(TileId))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bits = index_bits(len);
+    if bits == 0 {
+        return Ok(vec![palette[0]; cells]);
+    }
+    let packed = reader.take((cells * bits as usize).div_ceil(8))?;
+    let mask = (1u32 << bits) - 1;
+    let mut bytes = packed.iter();
+    let (mut acc, mut filled) = (0u32, 0u32);
+    let mut tiles = Vec::with_capacity(cells);
+    for _ in 0..cells {
+        while filled < bits {
+            // `take` returned exactly enough bytes for every index.
+            acc |= (*bytes.next().unwrap() as u32) << filled;
+            filled += 8;
+        }
+        let index = (acc & mask) as usize;
+        acc >>= bits;
+        filled -= bits;
+        let tile = palette
+            .get(index)
+            .ok_or(SnapshotError::Malformed("palette index out of range"))?;
+        tiles.push(*tile);
+    }
+    Ok(tiles)
+}
+
+/// Little-endian reads off the front of a byte slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Check the version and kind, and return the chunk size.
+    fn header(&mut self, tag: u8, kind: &'static str) -> Result<u32, SnapshotError> {
+        let version = self.u8()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion {
+                found: version,
+                supported: SNAPSHOT_VERSION,
+            });
+        }
+        let found = self.u8()?;
+        if found != tag {
+            return Err(SnapshotError::WrongKind {
+                expected: kind,
+                found,
+            });
+        }
+        let size = self.u16()? as u32;
+        if size == 0 || size > MAX_CHUNK_SIZE {
+            return Err(SnapshotError::Malformed("chunk size"));
+        }
+        Ok(size)
+    }
+
+    fn finish(&self) -> Result<(), SnapshotError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(SnapshotError::Malformed("trailing bytes"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    /// Chunk of `size`² cells per layer drawn from `kinds` tile types.
+    fn random_snapshot(rng: &mut StdRng, size: u32, kinds: u16) -> ChunkSnapshot {
+        let cells = (size * size) as usize;
+        let mut layer = || -> Vec<TileId> {
+            (0..cells)
+                .map(|_| TileId(rng.gen_range(0..kinds) * 7))
+                .collect()
+        };
+        ChunkSnapshot {
+            size,
+            fg: layer(),
+            bg: layer(),
+        }
+    }
+
+    #[test]
+    fn random_chunks_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..200 {
+            let size = [1, 8, 16, 32, 64][rng.gen_range(0..5)];
+            let kinds = rng.gen_range(1..=300);
+            let snapshot = random_snapshot(&mut rng, size, kinds);
+            let bytes = snapshot.encode();
+            assert_eq!(ChunkSnapshot::decode(&bytes), Ok(snapshot));
+        }
+    }
+
+    #[test]
+    fn random_deltas_turn_old_into_new() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..200 {
+            let size = [8, 16, 32][rng.gen_range(0..3)];
+            let kinds = rng.gen_range(1..=12);
+            let old = random_snapshot(&mut rng, size, kinds);
+            let mut new = old.clone();
+            for _ in 0..rng.gen_range(0..64) {
+                let cell = rng.gen_range(0..new.cells());
+                let layer = if rng.gen_bool(0.5) {
+                    &mut new.fg
+                } else {
+                    &mut new.bg
+                };
+                layer[cell] = TileId(rng.gen_range(0..40));
+            }
+
+            let delta = ChunkDelta::decode(&encode_delta(&old, &new)).unwrap();
+            let differing = [Layer::Fg, Layer::Bg]
+                .into_iter()
+                .map(|layer| {
+                    let pairs = old.layer(layer).iter().zip(new.layer(layer));
+                    pairs.filter(|(a, b)| a != b).count()
+                })
+                .sum::<usize>();
+            assert_eq!(delta.changes.len(), differing);
+
+            let mut patched = old.clone();
+            delta.apply_to(&mut patched).unwrap();
+            assert_eq!(patched, new);
+        }
+    }
+
+    #[test]
+    fn unchanged_chunk_has_an_empty_delta() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let snapshot = random_snapshot(&mut rng, 32, 5);
+        let bytes = encode_delta(&snapshot, &snapshot);
+        assert_eq!(bytes.len(), 8);
+        assert!(ChunkDelta::decode(&bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn terrain_chunks_encode_well_under_a_kilobyte() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let size = wc.chunk_size as i32;
+        let surface = nc.surface_height_at(0, &wc, &pc);
+        let mut map = WorldMap::default();
+        for cy in [surface.div_euclid(size), 2] {
+            map.get_or_generate_chunk(0, cy, &ctx);
+            let snapshot = map.snapshot_chunk(0, cy).unwrap();
+            let bytes = snapshot.encode();
+            assert!(
+                bytes.len() < 1024,
+                "chunk (0, {cy}) encodes to {} bytes",
+                bytes.len()
+            );
+            assert_eq!(ChunkSnapshot::decode(&bytes).unwrap(), snapshot);
+        }
+    }
+
+    #[test]
+    fn applying_a_snapshot_writes_only_changed_cells() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let size = wc.chunk_size as i32;
+        let (cx, cy) = (1, nc.surface_height_at(size, &wc, &pc).div_euclid(size));
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(cx, cy, &ctx);
+        let mut target = map.snapshot_chunk(cx, cy).unwrap();
+        let (stone, dirt) = (tr.by_name("stone"), tr.by_name("dirt"));
+        let swap = |tile: TileId| if tile == stone { dirt } else { stone };
+        target.fg[100] = swap(target.fg[100]);
+        target.bg[200] = swap(target.bg[200]);
+
+        let mut peer = WorldMap::default();
+        let received = ChunkSnapshot::decode(&target.encode()).unwrap();
+        let applied = peer.apply_snapshot(cx, cy, &received, &ctx).unwrap();
+        assert_eq!(peer.snapshot_chunk(cx, cy).unwrap(), target);
+        assert_eq!(applied.edits.len(), 2);
+        assert!(applied.edits.contains(&TileEdit {
+            tile_x: cx * size + 100 % size,
+            tile_y: cy * size + 100 / size,
+            layer: Layer::Fg,
+            tile: target.fg[100],
+        }));
+        assert!(applied.dirty_chunks.contains(&(cx, cy)));
+
+        // Again: nothing left to write.
+        let again = peer.apply_snapshot(cx, cy, &target, &ctx).unwrap();
+        assert!(again.edits.is_empty());
+    }
+
+    #[test]
+    fn applying_a_delta_updates_bitmasks() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        map.get_or_generate_chunk(0, 2, &ctx);
+        let old = map.snapshot_chunk(0, 2).unwrap();
+        let mut new = old.clone();
+        let index = 5 * old.size as usize + 5;
+        new.fg[index] = TileId::AIR;
+        for neighbour in [index - 1, index + 1] {
+            new.fg[neighbour] = tr.by_name("stone");
+        }
+
+        let delta = ChunkDelta::decode(&encode_delta(&old, &new)).unwrap();
+        map.apply_delta(0, 2, &delta, &ctx).unwrap();
+        assert_eq!(map.snapshot_chunk(0, 2).unwrap(), new);
+        // The cell right of the cleared one has lost its west neighbour.
+        let (x, y) = (6, 2 * wc.chunk_size as i32 + 5);
+        let expected = fresh_bitmask(&mut map, x, y, &ctx);
+        assert_eq!(map.chunk(0, 2).unwrap().fg.bitmasks[index + 1], expected);
+
+        // Cells that already match are skipped.
+        let again = map.apply_delta(0, 2, &delta, &ctx).unwrap();
+        assert!(again.edits.is_empty());
+        let outside = ChunkDelta {
+            size: old.size,
+            changes: vec![CellChange {
+                layer: Layer::Fg,
+                index: old.cells() as u16,
+                tile: TileId::AIR,
+            }],
+        };
+        assert!(map.apply_delta(0, 2, &outside, &ctx).is_err());
+    }
+
+    fn fresh_bitmask(map: &mut WorldMap, x: i32, y: i32, ctx: &WorldCtxRef) -> u8 {
+        crate::world::autotile::compute_bitmask(
+            |bx, by| {
+                let tile = map.get_tile_mut(bx, by, Layer::Fg, ctx);
+                ctx.tile_registry.is_solid(tile)
+            },
+            x,
+            y,
+        )
+    }
+
+    #[test]
+    fn mismatched_and_corrupt_data_is_rejected() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut rng = StdRng::seed_from_u64(1);
+        let small = random_snapshot(&mut rng, 8, 3);
+        let mut map = WorldMap::default();
+        assert_eq!(
+            map.apply_snapshot(0, 2, &small, &ctx).unwrap_err(),
+            SnapshotError::SizeMismatch {
+                found: 8,
+                expected: wc.chunk_size,
+            }
+        );
+
+        let bytes = small.encode();
+        assert_eq!(
+            ChunkSnapshot::decode(&bytes[..bytes.len() - 1]),
+            Err(SnapshotError::Truncated)
+        );
+        assert!(matches!(
+            ChunkDelta::decode(&bytes),
+            Err(SnapshotError::WrongKind {
+                expected: "delta",
+                ..
+            })
+        ));
+        let mut newer = bytes.clone();
+        newer[0] = SNAPSHOT_VERSION + 1;
+        assert!(matches!(
+            ChunkSnapshot::decode(&newer),
+            Err(SnapshotError::UnsupportedVersion { .. })
+        ));
+
+        // Three tile types pack into two bits; index 3 has no palette entry.
+        let mut bad_index = bytes.clone();
+        let fg_indices = 4 + 2 + 3 * 2;
+        bad_index[fg_indices] = 0xff;
+        assert_eq!(
+            ChunkSnapshot::decode(&bad_index),
+            Err(SnapshotError::Malformed("palette index out of range"))
+        );
+    }
+}
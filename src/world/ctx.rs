@@ -5,6 +5,7 @@ use crate::registry::biome::{BiomeRegistry, PlanetConfig};
 use crate::registry::tile::TileRegistry;
 use crate::registry::world::ActiveWorld;
 use crate::world::biome_map::BiomeMap;
+use crate::world::biome_override::BiomeOverrides;
 use crate::world::terrain_gen::TerrainNoiseCache;
 
 /// Bevy SystemParam bundling the 5 read-only world resources that most
@@ -18,6 +19,7 @@ pub struct WorldCtx<'w> {
     pub tile_registry: Res<'w, TileRegistry>,
     pub planet_config: Res<'w, PlanetConfig>,
     pub noise_cache: Res<'w, TerrainNoiseCache>,
+    pub biome_overrides: Option<Res<'w, BiomeOverrides>>,
 }
 
 impl WorldCtx<'_> {
@@ -30,6 +32,7 @@ impl WorldCtx<'_> {
             tile_registry: &self.tile_registry,
             planet_config: &self.planet_config,
             noise_cache: &self.noise_cache,
+            biome_overrides: self.biome_overrides.as_deref(),
         }
     }
 }
@@ -43,4 +46,6 @@ pub struct WorldCtxRef<'a> {
     pub tile_registry: &'a TileRegistry,
     pub planet_config: &'a PlanetConfig,
     pub noise_cache: &'a TerrainNoiseCache,
+    /// Biomes forced onto parts of the world for testing, if any.
+    pub biome_overrides: Option<&'a BiomeOverrides>,
 }
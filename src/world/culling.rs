@@ -0,0 +1,326 @@
+//! Distance culling and update throttling for per-entity systems.
+//!
+//! Every [`CullingConfig::refresh_frames`] frames, [`refresh_distance_bands`]
+//! gives each loaded chunk a [`DistanceBand`] from its wrap-aware chunk
+//! distance to the camera ([`ChunkBands`]). It then copies the band of the
+//! chunk an entity stands in onto that entity's [`DistanceBand`] component.
+//! Entities in chunks that aren't loaded are `Far`. That is one hash lookup
+//! per entity, and none at all on the frames in between.
+//!
+//! Systems ask [`Throttle`] how many frames' worth of time to step an entity:
+//! - `Near` entities step every frame.
+//! - `Mid` entities step every [`CullingConfig::mid_every`] frames, by that
+//!   many frames at once. Each entity's turn is offset by a stable phase, so
+//!   the updates spread across frames instead of bunching up.
+//! - `Far` entities are skipped.
+
+use std::collections::HashMap;
+
+use bevy::diagnostic::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{tile_to_chunk, world_to_tile, LoadedChunks};
+
+/// How far an entity is from the camera, in coarse steps.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceBand {
+    /// On or just off screen: updated every frame.
+    #[default]
+    Near,
+    /// Further out: updated at a reduced rate.
+    Mid,
+    /// Far from the camera or in an unloaded chunk: not updated.
+    Far,
+}
+
+/// Band radii and update rates.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CullingConfig {
+    /// Chunks (Chebyshev, from the camera's chunk) that count as `Near`.
+    pub near_chunks: i32,
+    /// Chunks that count as `Mid`; anything further is `Far`.
+    pub mid_chunks: i32,
+    /// `Mid` entities update once every this many frames.
+    pub mid_every: u32,
+    /// Frames between band refreshes.
+    pub refresh_frames: u32,
+}
+
+impl Default for CullingConfig {
+    fn default() -> Self {
+        Self {
+            near_chunks: 1,
+            mid_chunks: 2,
+            mid_every: 4,
+            refresh_frames: 10,
+        }
+    }
+}
+
+/// Band of a chunk at `chunk` with the camera in `camera`, both as chunk
+/// coordinates. Wrap-aware on X.
+pub fn chunk_band(
+    camera: (i32, i32),
+    chunk: (i32, i32),
+    world: &ActiveWorld,
+    config: &CullingConfig,
+) -> DistanceBand {
+    let mut dx = (chunk.0 - camera.0).abs();
+    if world.wrap_x {
+        let width = world.width_chunks();
+        dx = dx.rem_euclid(width);
+        dx = dx.min(width - dx);
+    }
+    let distance = dx.max((chunk.1 - camera.1).abs());
+    if distance <= config.near_chunks {
+        DistanceBand::Near
+    } else if distance <= config.mid_chunks {
+        DistanceBand::Mid
+    } else {
+        DistanceBand::Far
+    }
+}
+
+/// Band of every loaded chunk as of the last refresh, keyed by data chunk
+/// coordinates.
+#[derive(Resource, Debug, Default)]
+pub struct ChunkBands {
+    bands: HashMap<(i32, i32), DistanceBand>,
+}
+
+impl ChunkBands {
+    /// Recompute the bands of `loaded` (display chunk coordinates).
+    pub fn rebuild(
+        &mut self,
+        camera: (i32, i32),
+        loaded: impl IntoIterator<Item = (i32, i32)>,
+        world: &ActiveWorld,
+        config: &CullingConfig,
+    ) {
+        self.bands.clear();
+        for (cx, cy) in loaded {
+            let band = chunk_band(camera, (cx, cy), world, config);
+            self.bands.insert((world.wrap_chunk_x(cx), cy), band);
+        }
+    }
+
+    /// Band at world position `pos`; `Far` outside the loaded chunks.
+    /// Everything is `Near` until the first refresh.
+    pub fn band_at(&self, pos: Vec2, world: &ActiveWorld) -> DistanceBand {
+        if self.bands.is_empty() {
+            return DistanceBand::Near;
+        }
+        let (tx, ty) = world_to_tile(pos.x, pos.y, world.tile_size);
+        let (cx, cy) = tile_to_chunk(world.wrap_tile_x(tx), ty, world.chunk_size);
+        self.bands
+            .get(&(cx, cy))
+            .copied()
+            .unwrap_or(DistanceBand::Far)
+    }
+}
+
+/// Frames' worth of time to step something in `band` on frame `frame`, or
+/// `None` to skip it this frame. `phase` offsets a `Mid` entity's turn.
+pub fn throttle_frames(band: DistanceBand, phase: u32, frame: u32, mid_every: u32) -> Option<u32> {
+    let every = mid_every.max(1);
+    match band {
+        DistanceBand::Near => Some(1),
+        DistanceBand::Mid => frame
+            .wrapping_add(phase)
+            .is_multiple_of(every)
+            .then_some(every),
+        DistanceBand::Far => None,
+    }
+}
+
+/// Stable phase for an entity, spreading consecutively spawned entities
+/// across frames.
+pub fn entity_phase(entity: Entity) -> u32 {
+    entity.index_u32()
+}
+
+/// Throttling decisions for the current frame. Without a frame counter or
+/// [`CullingConfig`] nothing is throttled.
+#[derive(SystemParam)]
+pub struct Throttle<'w> {
+    frame: Option<Res<'w, FrameCount>>,
+    config: Option<Res<'w, CullingConfig>>,
+}
+
+impl Throttle<'_> {
+    /// See [`throttle_frames`]. Entities without a band count as `Near`.
+    pub fn frames(&self, band: Option<&DistanceBand>, phase: u32) -> Option<u32> {
+        let (Some(frame), Some(config)) = (&self.frame, &self.config) else {
+            return Some(1);
+        };
+        let band = band.copied().unwrap_or_default();
+        throttle_frames(band, phase, frame.0, config.mid_every)
+    }
+}
+
+/// Periodically recompute chunk bands around the camera and copy them onto
+/// entities with a [`DistanceBand`].
+pub fn refresh_distance_bands(
+    frame: Res<FrameCount>,
+    config: Res<CullingConfig>,
+    world: Res<ActiveWorld>,
+    loaded_chunks: Res<LoadedChunks>,
+    camera: Query<&Transform, With<Camera2d>>,
+    mut bands: ResMut<ChunkBands>,
+    mut entities: Query<(&Transform, &mut DistanceBand)>,
+) {
+    if !frame.0.is_multiple_of(config.refresh_frames.max(1)) {
+        return;
+    }
+    let Ok(camera_tf) = camera.single() else {
+        return;
+    };
+    let pos = camera_tf.translation;
+    let (tx, ty) = world_to_tile(pos.x, pos.y, world.tile_size);
+    let camera_chunk = tile_to_chunk(tx, ty, world.chunk_size);
+    bands.rebuild(
+        camera_chunk,
+        loaded_chunks.map.keys().copied(),
+        &world,
+        &config,
+    );
+    for (tf, mut band) in &mut entities {
+        band.set_if_neq(bands.band_at(tf.translation.truncate(), &world));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    #[test]
+    fn bands_grow_outward_and_wrap_across_the_seam() {
+        let world = fixtures::test_active_world();
+        let config = CullingConfig::default();
+        let last = world.width_chunks() - 1;
+        let band = |camera, chunk| chunk_band(camera, chunk, &world, &config);
+
+        assert_eq!(band((0, 10), (1, 11)), DistanceBand::Near);
+        assert_eq!(band((0, 10), (2, 10)), DistanceBand::Mid);
+        assert_eq!(band((0, 10), (0, 13)), DistanceBand::Far);
+        // The chunk just across the seam is a neighbour, not a world away.
+        assert_eq!(band((0, 10), (last, 10)), DistanceBand::Near);
+        assert_eq!(band((0, 10), (last - 1, 10)), DistanceBand::Mid);
+        assert_eq!(band((last, 10), (1, 10)), DistanceBand::Mid);
+        // Display coordinates past the seam wrap the same way.
+        assert_eq!(band((-1, 10), (last, 10)), DistanceBand::Near);
+    }
+
+    #[test]
+    fn entities_take_their_chunks_band() {
+        let world = fixtures::test_active_world();
+        let config = CullingConfig::default();
+        let last = world.width_chunks() - 1;
+        let mut bands = ChunkBands::default();
+        assert_eq!(
+            bands.band_at(Vec2::ZERO, &world),
+            DistanceBand::Near,
+            "not refreshed yet"
+        );
+        // Camera just left of the seam, in display coordinates.
+        bands.rebuild(
+            (-1, 10),
+            [(-2, 10), (-1, 10), (0, 10), (1, 10)],
+            &world,
+            &config,
+        );
+
+        let chunk_px = world.chunk_size as f32 * world.tile_size;
+        let centre = |cx: i32| Vec2::new((cx as f32 + 0.5) * chunk_px, 10.5 * chunk_px);
+        assert_eq!(bands.band_at(centre(last), &world), DistanceBand::Near);
+        assert_eq!(bands.band_at(centre(-1), &world), DistanceBand::Near);
+        assert_eq!(bands.band_at(centre(1), &world), DistanceBand::Mid);
+        assert_eq!(
+            bands.band_at(centre(5), &world),
+            DistanceBand::Far,
+            "unloaded"
+        );
+    }
+
+    #[test]
+    fn near_is_never_throttled_and_far_never_runs() {
+        for frame in 0..16 {
+            for phase in 0..16 {
+                assert_eq!(
+                    throttle_frames(DistanceBand::Near, phase, frame, 4),
+                    Some(1)
+                );
+                assert_eq!(throttle_frames(DistanceBand::Far, phase, frame, 4), None);
+            }
+        }
+    }
+
+    #[test]
+    fn mid_updates_are_spread_by_phase() {
+        let every = 4;
+        // Each entity runs once per window, making up for the skipped frames.
+        for phase in 0..8 {
+            let runs: Vec<u32> = (0..every)
+                .filter_map(|frame| throttle_frames(DistanceBand::Mid, phase, frame, every))
+                .collect();
+            assert_eq!(runs, vec![every]);
+        }
+        // Consecutive phases spread evenly over the frames of a window.
+        let per_frame: Vec<usize> = (0..every)
+            .map(|frame| {
+                (0..8)
+                    .filter(|&phase| {
+                        throttle_frames(DistanceBand::Mid, phase, frame, every).is_some()
+                    })
+                    .count()
+            })
+            .collect();
+        assert_eq!(per_frame, vec![2; every as usize]);
+    }
+
+    #[test]
+    fn refresh_bands_entities_around_the_camera() {
+        let mut app = fixtures::test_app();
+        app.init_resource::<CullingConfig>()
+            .init_resource::<ChunkBands>()
+            .init_resource::<LoadedChunks>()
+            .add_systems(Update, refresh_distance_bands);
+        let world = app.world().resource::<ActiveWorld>().clone();
+        let chunk_px = world.chunk_size as f32 * world.tile_size;
+        for cx in -1..=3 {
+            app.world_mut().resource_mut::<LoadedChunks>().map.insert(
+                (cx, 10),
+                crate::world::chunk::ChunkEntities {
+                    fg: Entity::PLACEHOLDER,
+                    bg: Entity::PLACEHOLDER,
+                    liquid: Entity::PLACEHOLDER,
+                    state: crate::world::chunk::ChunkState::Visible,
+                },
+            );
+        }
+        let at = |cx: f32| Transform::from_xyz(cx * chunk_px, 10.5 * chunk_px, 0.0);
+        app.world_mut().spawn((Camera2d, at(0.5)));
+        let near = app
+            .world_mut()
+            .spawn((DistanceBand::default(), at(1.5)))
+            .id();
+        let mid = app
+            .world_mut()
+            .spawn((DistanceBand::default(), at(2.5)))
+            .id();
+        let unloaded = app
+            .world_mut()
+            .spawn((DistanceBand::default(), at(9.5)))
+            .id();
+
+        // The first frame is a refresh frame.
+        app.update();
+        let band = |app: &App, e| *app.world().get::<DistanceBand>(e).unwrap();
+        assert_eq!(band(&app, near), DistanceBand::Near);
+        assert_eq!(band(&app, mid), DistanceBand::Mid);
+        assert_eq!(band(&app, unloaded), DistanceBand::Far);
+    }
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use serde::Deserialize;
 
 use crate::parallax::spawn::{ParallaxLayerConfig, ParallaxSkyLayer};
+use crate::registry::planet_physics::PlanetPhysics;
+use crate::registry::world::ActiveWorld;
 
 /// Day phase indices into the config arrays.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -111,6 +113,88 @@ impl Default for WorldTime {
     }
 }
 
+/// Sky colours lighting the world, top of the sky vs horizon. Arrays are
+/// ordered [dawn, day, sunset, night], like [`DayNightConfig`].
+///
+/// The blended tint colours the sun emitters of the RC lighting pipeline and
+/// an ambient sky fill added when the lightmap is finalized, so dawn and
+/// sunset paint the scene rather than only the parallax sky.
+#[derive(Resource, Debug, Clone)]
+pub struct SkyGradient {
+    pub zenith_colors: [[f32; 3]; 4],
+    pub horizon_colors: [[f32; 3]; 4],
+    /// How far the tint leans from the zenith colour toward the horizon one.
+    pub horizon_weight: f32,
+    /// Brightness of the ambient sky fill per phase.
+    pub ambient_strengths: [f32; 4],
+    /// Share of the sky light lost at full precipitation intensity.
+    pub weather_dimming: f32,
+}
+
+impl Default for SkyGradient {
+    fn default() -> Self {
+        Self {
+            zenith_colors: [
+                [0.55, 0.60, 0.85],
+                [0.92, 0.96, 1.0],
+                [0.50, 0.45, 0.75],
+                [0.35, 0.40, 0.70],
+            ],
+            horizon_colors: [
+                [1.0, 0.62, 0.40],
+                [1.0, 1.0, 1.0],
+                [1.0, 0.50, 0.30],
+                [0.45, 0.50, 0.75],
+            ],
+            horizon_weight: 0.5,
+            ambient_strengths: [0.06, 0.08, 0.05, 0.02],
+            weather_dimming: 0.4,
+        }
+    }
+}
+
+/// Light the sky casts at one moment, see [`SkyGradient::sky_light`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyLight {
+    /// Colour of the sun emitters in the sky and open air.
+    pub emitter: Vec3,
+    /// Ambient fill added to the lit parts of the lightmap.
+    pub ambient: Vec3,
+}
+
+impl Default for SkyLight {
+    /// Warm-white sun and no ambient, for worlds without a day/night cycle.
+    fn default() -> Self {
+        Self {
+            emitter: Vec3::new(1.0, 0.98, 0.9),
+            ambient: Vec3::ZERO,
+        }
+    }
+}
+
+impl SkyGradient {
+    /// Sky tint at `progress` through `phase`.
+    pub fn tint(&self, phase: DayPhase, progress: f32) -> Vec3 {
+        let zenith = lerp_phase_color(&self.zenith_colors, phase, progress);
+        let horizon = lerp_phase_color(&self.horizon_colors, phase, progress);
+        zenith.lerp(horizon, self.horizon_weight)
+    }
+
+    /// Sky light for `world_time` under precipitation of `weather` intensity
+    /// (0.0 = clear). The emitter is the sun tinted by the sky, never below
+    /// the phase's `ambient_min` per channel.
+    pub fn sky_light(&self, world_time: &WorldTime, weather: f32) -> SkyLight {
+        let (phase, progress) = (world_time.phase, world_time.phase_progress);
+        let tint = self.tint(phase, progress);
+        let clear = 1.0 - self.weather_dimming * weather.clamp(0.0, 1.0);
+        let sun = world_time.sun_color * tint * world_time.sun_intensity * clear;
+        SkyLight {
+            emitter: sun.max(Vec3::splat(world_time.ambient_min)),
+            ambient: tint * lerp_phase_value(&self.ambient_strengths, phase, progress) * clear,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helper functions
 // ---------------------------------------------------------------------------
@@ -164,16 +248,39 @@ fn lerp_phase_color4(colors: &[[f32; 4]; 4], phase: DayPhase, progress: f32) ->
 // Systems
 // ---------------------------------------------------------------------------
 
-/// Advances world time and recomputes all derived values each frame.
+/// How much brighter the sun gets per unit of atmosphere density below 1.0
+/// (and dimmer above it).
+const ATMOSPHERE_SUN_FACTOR: f32 = 0.25;
+/// Dimmest the sun gets under a thick atmosphere.
+const MIN_ATMOSPHERE_SUN_SCALE: f32 = 0.25;
+
+/// Sun intensity multiplier through an atmosphere of `density` (1.0 =
+/// Earth-like): thin air lets a harsher sun through, thick air scatters it.
+pub fn atmosphere_sun_scale(density: f32) -> f32 {
+    (1.0 + ATMOSPHERE_SUN_FACTOR * (1.0 - density)).max(MIN_ATMOSPHERE_SUN_SCALE)
+}
+
+/// Sky colour through an atmosphere of `density`: the thinner the air, the
+/// closer to the black of space.
+pub fn atmosphere_sky_tint(sky: Color, density: f32) -> Color {
+    sky.mix(&SPACE_COLOR, (1.0 - density).clamp(0.0, 1.0))
+}
+
+/// Advances world time and recomputes all derived values each frame. The
+/// planet's atmosphere scales the sun and tints the sky, and its day length
+/// replaces the configured cycle.
 pub fn tick_world_time(
     time: Res<Time>,
     config: Res<DayNightConfig>,
+    planet: Option<Res<PlanetPhysics>>,
     mut world_time: ResMut<WorldTime>,
     mut phase_events: MessageWriter<DayPhaseChanged>,
 ) {
+    let planet = planet.as_deref().copied().unwrap_or_default();
     if !world_time.paused {
         let dt = time.delta_secs();
-        world_time.time_of_day += dt / config.cycle_duration_secs;
+        let cycle = planet.day_length.unwrap_or(config.cycle_duration_secs);
+        world_time.time_of_day += dt / cycle;
         world_time.time_of_day = world_time.time_of_day.rem_euclid(1.0);
     }
 
@@ -191,9 +298,13 @@ pub fn tick_world_time(
     world_time.phase = phase;
     world_time.phase_progress = progress;
     world_time.sun_color = lerp_phase_color(&config.sun_colors, phase, progress);
-    world_time.sun_intensity = lerp_phase_value(&config.sun_intensities, phase, progress);
+    world_time.sun_intensity = lerp_phase_value(&config.sun_intensities, phase, progress)
+        * atmosphere_sun_scale(planet.atmosphere_density);
     world_time.ambient_min = lerp_phase_value(&config.ambient_mins, phase, progress);
-    world_time.sky_color = lerp_phase_color4(&config.sky_colors, phase, progress);
+    world_time.sky_color = atmosphere_sky_tint(
+        lerp_phase_color4(&config.sky_colors, phase, progress),
+        planet.atmosphere_density,
+    );
     world_time.danger_multiplier = lerp_phase_value(&config.danger_multipliers, phase, progress);
     world_time.temperature_modifier =
         lerp_phase_value(&config.temperature_modifiers, phase, progress);
@@ -201,14 +312,37 @@ pub fn tick_world_time(
         lerp_phase_value(&config.temperature_celsius_offsets, phase, progress);
 }
 
-/// Tint parallax layers based on time of day.
-/// Sky layers get full tint; background layers get 50% blend.
+/// Altitude (fraction of world height) where the sky starts fading to space.
+const SPACE_FADE_START: f32 = 0.8;
+/// Sky colour at the very top of the world.
+const SPACE_COLOR: Color = Color::srgb(0.02, 0.02, 0.06);
+
+/// Sky tint at `altitude` (0.0 at the bottom of the world, 1.0 at the top):
+/// the time-of-day tint, fading to space above [`SPACE_FADE_START`].
+pub fn altitude_sky_tint(sky_tint: Color, altitude: f32) -> Color {
+    let t = ((altitude - SPACE_FADE_START) / (1.0 - SPACE_FADE_START)).clamp(0.0, 1.0);
+    sky_tint.mix(&SPACE_COLOR, t)
+}
+
+/// Tint parallax layers based on time of day and the camera's altitude.
+/// Sky layers get full tint; background layers get 50% blend. The clear
+/// colour fades to space with altitude too, so gaps between layers match.
 pub fn tint_parallax_layers(
     world_time: Res<WorldTime>,
+    world_config: Option<Res<ActiveWorld>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    clear_color: Option<ResMut<ClearColor>>,
     mut sky_query: Query<&mut Sprite, With<ParallaxSkyLayer>>,
     mut layer_query: Query<&mut Sprite, (With<ParallaxLayerConfig>, Without<ParallaxSkyLayer>)>,
 ) {
-    let sky_tint = world_time.sky_color;
+    let altitude = match (world_config, camera_query.single()) {
+        (Some(world), Ok(camera)) => camera.translation.y / world.world_pixel_height().max(1.0),
+        _ => 0.0,
+    };
+    let sky_tint = altitude_sky_tint(world_time.sky_color, altitude);
+    if let Some(mut clear_color) = clear_color {
+        clear_color.0 = altitude_sky_tint(ClearColor::default().0, altitude);
+    }
 
     // Sky: full RGB tint, preserve alpha (biome transition controls alpha)
     for mut sprite in &mut sky_query {
@@ -346,4 +480,124 @@ mod tests {
         // Sun intensity should be interpolated from config
         assert!(wt.sun_intensity > 0.0);
     }
+
+    #[test]
+    fn sky_fades_to_space_near_the_top() {
+        let day = Color::srgb(0.6, 0.8, 1.0);
+        assert_eq!(altitude_sky_tint(day, 0.5), day);
+        assert_eq!(altitude_sky_tint(day, 1.0), SPACE_COLOR);
+        let high = altitude_sky_tint(day, 0.9).to_srgba();
+        assert!(high.blue < 1.0 && high.blue > SPACE_COLOR.to_srgba().blue);
+    }
+
+    #[test]
+    fn sky_gradient_warms_the_dawn_sun_emitter() {
+        let gradient = SkyGradient::default();
+        let config = test_config();
+        let dawn = WorldTime::from_config(&config);
+        assert_eq!(dawn.phase, DayPhase::Dawn);
+
+        let light = gradient.sky_light(&dawn, 0.0);
+        // Half way between the dawn zenith and horizon, times the 0.6 dawn sun.
+        let tint = Vec3::new(0.775, 0.61, 0.625);
+        let expected = Vec3::new(1.0, 0.65, 0.35) * tint * 0.6;
+        assert!(
+            light.emitter.abs_diff_eq(expected, 1e-5),
+            "{} vs {expected}",
+            light.emitter
+        );
+        assert!(light.emitter.x > light.emitter.z);
+        assert!(light.ambient.abs_diff_eq(tint * 0.06, 1e-5));
+    }
+
+    #[test]
+    fn sky_gradient_blends_between_phases() {
+        let gradient = SkyGradient::default();
+        let day = gradient.tint(DayPhase::Day, 0.0);
+        let sunset = gradient.tint(DayPhase::Sunset, 0.0);
+        let between = gradient.tint(DayPhase::Day, 0.5);
+        assert!(between.abs_diff_eq((day + sunset) / 2.0, 1e-5));
+        // Night wraps around to dawn.
+        let dawn = gradient.tint(DayPhase::Dawn, 0.0);
+        assert!(gradient.tint(DayPhase::Night, 1.0).abs_diff_eq(dawn, 1e-5));
+    }
+
+    #[test]
+    fn weather_dims_the_sky_down_to_ambient_min() {
+        let gradient = SkyGradient::default();
+        let noon = WorldTime {
+            phase: DayPhase::Day,
+            sun_color: Vec3::ONE,
+            ambient_min: 0.1,
+            ..default()
+        };
+        let clear = gradient.sky_light(&noon, 0.0);
+        let storm = gradient.sky_light(&noon, 1.0);
+        assert!(storm.emitter.abs_diff_eq(clear.emitter * 0.6, 1e-5));
+        assert!(storm.ambient.abs_diff_eq(clear.ambient * 0.6, 1e-5));
+
+        let night = WorldTime {
+            phase: DayPhase::Night,
+            sun_intensity: 0.0,
+            ..noon
+        };
+        assert_eq!(gradient.sky_light(&night, 1.0).emitter, Vec3::splat(0.1));
+    }
+
+    /// World time after one second of ticking on a planet with `physics`.
+    fn ticked(physics: PlanetPhysics) -> WorldTime {
+        let config = test_config();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs(1),
+            ))
+            .add_message::<DayPhaseChanged>()
+            .insert_resource(WorldTime::from_config(&config))
+            .insert_resource(config)
+            .insert_resource(physics)
+            .add_systems(Update, tick_world_time);
+        app.update();
+        app.update();
+        app.world_mut().remove_resource::<WorldTime>().unwrap()
+    }
+
+    #[test]
+    fn atmosphere_scales_the_sun_and_darkens_the_sky() {
+        let earth = ticked(PlanetPhysics::default());
+        let moon = ticked(PlanetPhysics {
+            atmosphere_density: 0.2,
+            ..default()
+        });
+        let thick = ticked(PlanetPhysics {
+            atmosphere_density: 2.0,
+            ..default()
+        });
+
+        assert_eq!(moon.time_of_day, earth.time_of_day);
+        assert!(earth.sun_intensity > 0.0);
+        assert!((moon.sun_intensity - earth.sun_intensity * 1.2).abs() < 1e-5);
+        assert!((thick.sun_intensity - earth.sun_intensity * 0.75).abs() < 1e-5);
+
+        let (earth_sky, moon_sky) = (earth.sky_color.to_srgba(), moon.sky_color.to_srgba());
+        assert!(moon_sky.blue < earth_sky.blue && moon_sky.red < earth_sky.red);
+        assert_eq!(thick.sky_color, earth.sky_color);
+    }
+
+    #[test]
+    fn planet_day_length_replaces_the_cycle_duration() {
+        let start = WorldTime::default().time_of_day;
+        let configured = ticked(PlanetPhysics::default());
+        let short = ticked(PlanetPhysics {
+            day_length: Some(10.0),
+            ..default()
+        });
+        // The test config's cycle is 100 s.
+        let (configured, short) = (configured.time_of_day - start, short.time_of_day - start);
+        assert!(configured > 0.0);
+        assert!(
+            (short - configured * 10.0).abs() < 1e-4,
+            "{short} vs {configured}"
+        );
+    }
 }
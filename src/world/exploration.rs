@@ -0,0 +1,371 @@
+//! Fog of war: which tiles the player has seen.
+//!
+//! Every frame the tiles within [`FogOfWar::reveal_radius`] of the player are
+//! marked in a per-chunk [`ExploredTiles`] bitset in [`WorldMap`]. The
+//! bitsets live next to the chunk data rather than in it, so they survive
+//! chunks unloading and regenerating. They are saved with the world on warp
+//! (see [`WorldSave::explored`](crate::cosmos::persistence::WorldSave)).
+//! [`FogOfWar::world`] picks how unexplored tiles are drawn: an overlay mesh
+//! over the visible tiles darkens or hides them. There is no minimap yet; it
+//! should read the same bitsets.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy::sprite_render::AlphaMode2d;
+use serde::{Deserialize, Serialize};
+
+use crate::player::Player;
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{tile_to_chunk, tile_to_local, world_to_tile, WorldMap};
+
+/// Overlay z-layer: above tiles, objects, drops and particles.
+const FOG_Z: f32 = 2.0;
+/// Extra tiles covered past each viewport edge.
+const FOG_PADDING: i32 = 2;
+
+/// Explored flags of one chunk's tiles, one bit per tile, row-major like
+/// the chunk's tile arrays.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExploredTiles {
+    bits: Vec<u64>,
+}
+
+impl ExploredTiles {
+    pub fn is_explored(&self, idx: usize) -> bool {
+        self.bits
+            .get(idx / 64)
+            .is_some_and(|word| word & (1 << (idx % 64)) != 0)
+    }
+
+    /// Mark a tile explored. Returns true if it wasn't yet.
+    pub fn mark(&mut self, idx: usize) -> bool {
+        if self.bits.len() <= idx / 64 {
+            self.bits.resize(idx / 64 + 1, 0);
+        }
+        let word = &mut self.bits[idx / 64];
+        let bit = 1 << (idx % 64);
+        let new = *word & bit == 0;
+        *word |= bit;
+        new
+    }
+
+    /// Number of explored tiles.
+    pub fn count(&self) -> u32 {
+        self.bits.iter().map(|word| word.count_ones()).sum()
+    }
+}
+
+/// How unexplored tiles are drawn in the world view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FogMode {
+    /// Drawn as usual; exploration is still tracked.
+    #[default]
+    Off,
+    /// Dimmed.
+    Darken,
+    /// Covered completely.
+    Hide,
+}
+
+impl FogMode {
+    /// Opacity of the overlay on unexplored tiles.
+    pub fn alpha(self) -> f32 {
+        match self {
+            FogMode::Off => 0.0,
+            FogMode::Darken => 0.6,
+            FogMode::Hide => 1.0,
+        }
+    }
+}
+
+/// Fog-of-war settings.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct FogOfWar {
+    /// Tiles within this distance (in tiles) of the player become explored.
+    pub reveal_radius: u32,
+    pub world: FogMode,
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self {
+            reveal_radius: 12,
+            world: FogMode::Off,
+        }
+    }
+}
+
+impl WorldMap {
+    /// Whether the tile has been explored. Wrap-aware on X; tiles outside
+    /// the world are never explored.
+    pub fn is_explored(&self, tile_x: i32, tile_y: i32, config: &ActiveWorld) -> bool {
+        if tile_y < 0 || tile_y >= config.height_tiles || config.outside_x(tile_x) {
+            return false;
+        }
+        let tile_x = config.wrap_tile_x(tile_x);
+        let chunk = tile_to_chunk(tile_x, tile_y, config.chunk_size);
+        let (lx, ly) = tile_to_local(tile_x, tile_y, config.chunk_size);
+        self.explored
+            .get(&chunk)
+            .is_some_and(|tiles| tiles.is_explored((ly * config.chunk_size + lx) as usize))
+    }
+
+    /// Mark every tile within `radius` tiles of `center` explored. Returns
+    /// how many tiles were newly explored.
+    pub fn reveal_around(&mut self, center: (i32, i32), radius: u32, config: &ActiveWorld) -> u32 {
+        let r = radius as i32;
+        let mut revealed = 0;
+        for dy in -r..=r {
+            let tile_y = center.1 + dy;
+            if tile_y < 0 || tile_y >= config.height_tiles {
+                continue;
+            }
+            for dx in -r..=r {
+                if dx * dx + dy * dy > r * r || config.outside_x(center.0 + dx) {
+                    continue;
+                }
+                let tile_x = config.wrap_tile_x(center.0 + dx);
+                let chunk = tile_to_chunk(tile_x, tile_y, config.chunk_size);
+                let (lx, ly) = tile_to_local(tile_x, tile_y, config.chunk_size);
+                let idx = (ly * config.chunk_size + lx) as usize;
+                revealed += self.explored.entry(chunk).or_default().mark(idx) as u32;
+            }
+        }
+        revealed
+    }
+}
+
+/// Explore the tiles around the player.
+pub fn reveal_around_player(
+    fog: Res<FogOfWar>,
+    config: Res<ActiveWorld>,
+    mut world_map: ResMut<WorldMap>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let Ok(tf) = player.single() else {
+        return;
+    };
+    let tile = world_to_tile(tf.translation.x, tf.translation.y, config.tile_size);
+    world_map.reveal_around(tile, fog.reveal_radius, &config);
+}
+
+/// Marker for the mesh covering unexplored tiles.
+#[derive(Component)]
+pub struct UnexploredOverlay;
+
+/// Cover the unexplored tiles in view according to [`FogOfWar::world`].
+#[allow(clippy::too_many_arguments)]
+pub fn update_unexplored_overlay(
+    mut commands: Commands,
+    fog: Res<FogOfWar>,
+    config: Res<ActiveWorld>,
+    world_map: Res<WorldMap>,
+    camera_query: Query<(&Camera, &Transform, &Projection), With<Camera2d>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut overlay: Query<(&Mesh2d, &mut Visibility), With<UnexploredOverlay>>,
+) {
+    let Ok((mesh, mut visibility)) = overlay.single_mut() else {
+        let material = materials.add(ColorMaterial {
+            color: Color::WHITE,
+            alpha_mode: AlphaMode2d::Blend,
+            ..default()
+        });
+        commands.spawn((
+            UnexploredOverlay,
+            Mesh2d(meshes.add(Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+            ))),
+            MeshMaterial2d(material),
+            Transform::from_xyz(0.0, 0.0, FOG_Z),
+            Visibility::Hidden,
+        ));
+        return;
+    };
+    if fog.world == FogMode::Off {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    let Ok((camera, camera_tf, projection)) = camera_query.single() else {
+        return;
+    };
+    let viewport = camera
+        .physical_viewport_size()
+        .unwrap_or(UVec2::new(1280, 720));
+    let scale = match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    };
+    let tile_size = config.tile_size;
+    let half_w = (viewport.x as f32 * scale / tile_size / 2.0).ceil() as i32 + FOG_PADDING;
+    let half_h = (viewport.y as f32 * scale / tile_size / 2.0).ceil() as i32 + FOG_PADDING;
+    let (cam_x, cam_y) = world_to_tile(camera_tf.translation.x, camera_tf.translation.y, tile_size);
+
+    let color = [0.0, 0.0, 0.0, fog.world.alpha()];
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut colors: Vec<[f32; 4]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    for y in (cam_y - half_h).max(0)..=(cam_y + half_h).min(config.height_tiles - 1) {
+        // One quad per run of unexplored tiles in the row.
+        let mut run_start = None;
+        for x in cam_x - half_w..=cam_x + half_w + 1 {
+            let unexplored = x <= cam_x + half_w
+                && !world_map.is_explored(x, y, &config)
+                && !config.outside_x(x);
+            match (unexplored, run_start) {
+                (true, None) => run_start = Some(x),
+                (false, Some(start)) => {
+                    let base = positions.len() as u32;
+                    let (l, r) = (start as f32 * tile_size, x as f32 * tile_size);
+                    let (b, t) = (y as f32 * tile_size, (y + 1) as f32 * tile_size);
+                    positions.extend([[l, b, 0.0], [r, b, 0.0], [r, t, 0.0], [l, t, 0.0]]);
+                    colors.extend([color; 4]);
+                    indices.extend_from_slice(&[
+                        base,
+                        base + 1,
+                        base + 2,
+                        base,
+                        base + 2,
+                        base + 3,
+                    ]);
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut new_mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+    );
+    new_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    new_mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    new_mesh.insert_indices(Indices::U32(indices));
+    if let Some(existing) = meshes.get_mut(&mesh.0) {
+        *existing = new_mesh;
+    }
+    *visibility = Visibility::Inherited;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::address::CelestialAddress;
+    use crate::cosmos::persistence::{load_world_save, save_current_world, DirtyChunks, Universe};
+    use crate::test_helpers::fixtures;
+
+    #[test]
+    fn explored_bits_mark_once() {
+        let mut tiles = ExploredTiles::default();
+        assert!(!tiles.is_explored(700));
+        assert!(tiles.mark(700));
+        assert!(!tiles.mark(700));
+        assert!(tiles.is_explored(700));
+        assert!(!tiles.is_explored(701));
+        assert_eq!(tiles.count(), 1);
+    }
+
+    #[test]
+    fn reveal_marks_tiles_within_the_radius() {
+        let config = fixtures::test_active_world();
+        let mut map = WorldMap::default();
+        let revealed = map.reveal_around((100, 500), 5, &config);
+        assert_eq!(map.reveal_around((100, 500), 5, &config), 0);
+
+        let mut inside = 0;
+        for y in 490..=510 {
+            for x in 90..=110 {
+                let (dx, dy) = (x - 100, y - 500);
+                let expected = dx * dx + dy * dy <= 25;
+                assert_eq!(map.is_explored(x, y, &config), expected, "({x}, {y})");
+                inside += expected as u32;
+            }
+        }
+        assert_eq!(revealed, inside);
+    }
+
+    #[test]
+    fn reveal_wraps_and_stays_inside_the_world() {
+        let mut config = fixtures::test_active_world();
+        let mut map = WorldMap::default();
+        map.reveal_around((0, 0), 3, &config);
+        let last = config.width_tiles - 1;
+        assert!(map.is_explored(last, 0, &config));
+        assert!(map.is_explored(-1, 2, &config));
+        assert!(!map.is_explored(0, -1, &config));
+
+        config.wrap_x = false;
+        let mut walled = WorldMap::default();
+        walled.reveal_around((0, 10), 3, &config);
+        assert!(!walled.is_explored(-1, 10, &config));
+        assert!(!walled.is_explored(last, 10, &config));
+        assert!(walled.is_explored(3, 10, &config));
+    }
+
+    fn explore_app() -> App {
+        let mut app = fixtures::test_app();
+        app.init_resource::<FogOfWar>()
+            .add_systems(Update, reveal_around_player);
+        app
+    }
+
+    #[test]
+    fn player_explores_and_exploration_survives_chunk_reload() {
+        let mut app = explore_app();
+        let config = app.world().resource::<ActiveWorld>().clone();
+        let at = |tile: (i32, i32)| {
+            Vec3::new(
+                (tile.0 as f32 + 0.5) * config.tile_size,
+                (tile.1 as f32 + 0.5) * config.tile_size,
+                0.0,
+            )
+        };
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_translation(at((300, 600)))))
+            .id();
+        app.update();
+        app.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_translation(at((400, 600))));
+        app.update();
+
+        let map = app.world().resource::<WorldMap>();
+        let radius = FogOfWar::default().reveal_radius as i32;
+        assert!(map.is_explored(300 + radius, 600, &config));
+        assert!(map.is_explored(400, 600 - radius, &config));
+        assert!(
+            !map.is_explored(350, 600, &config),
+            "walked past, not through"
+        );
+        assert!(!map.is_explored(300, 600 + radius + 1, &config));
+
+        // Chunks unloading and regenerating keep their exploration.
+        let mut map = app.world_mut().resource_mut::<WorldMap>();
+        let chunk = tile_to_chunk(300, 600, config.chunk_size);
+        map.chunks.remove(&chunk);
+        assert!(map.is_explored(300, 600, &config));
+    }
+
+    #[test]
+    fn exploration_is_saved_with_the_world() {
+        let config = fixtures::test_active_world();
+        let addr = CelestialAddress::planet(IVec2::ZERO, IVec2::ZERO, 1);
+        let mut universe = Universe::default();
+        let mut map = WorldMap::default();
+        let mut dirty = DirtyChunks::default();
+        map.reveal_around((50, 700), 4, &config);
+        save_current_world(&mut universe, &addr, &map, &dirty, vec![], 10.0);
+
+        let mut fresh = WorldMap::default();
+        assert!(!fresh.is_explored(50, 700, &config));
+        load_world_save(&universe, &addr, &mut fresh, &mut dirty, 20.0);
+        assert!(fresh.is_explored(50, 700, &config));
+        assert!(fresh.is_explored(54, 700, &config));
+        assert!(!fresh.is_explored(55, 700, &config));
+        assert!(dirty.0.is_empty(), "exploring doesn't modify chunks");
+    }
+}
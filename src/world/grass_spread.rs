@@ -0,0 +1,314 @@
+//! Grass creeping over exposed dirt, and dying back when covered.
+//!
+//! Every [`GrassSpread::interval_secs`] each grass tile in a visible loaded
+//! chunk rolls to turn a neighbouring dirt tile into grass, if that dirt has
+//! open space above it and gets at least `min_light` (the same CPU estimate
+//! plants use, see [`growth`](super::growth)). Grass with a solid tile on top
+//! turns back into dirt on the next pass. Changes are tile swaps, capped at
+//! `max_changes` per pass.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::DirtyChunks;
+use crate::math::SplitMix64;
+use crate::object::registry::ObjectRegistry;
+use crate::registry::tile::TileId;
+use crate::world::chunk::{update_bitmasks_around, Layer, LoadedChunks, TileChanged, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::day_night::WorldTime;
+use crate::world::growth::{light_at, mark_meshes_dirty, sunlight_at};
+use crate::world::rc_lighting::RcGridDirty;
+
+/// The 8 neighbours grass can spread to.
+const NEIGHBOURS_8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// How grass spreads and dies back.
+#[derive(Resource, Debug, Clone)]
+pub struct GrassSpread {
+    /// Tile that spreads.
+    pub grass: String,
+    /// Tile it spreads onto, and turns back into.
+    pub dirt: String,
+    /// Seconds between passes.
+    pub interval_secs: f32,
+    /// Chance per pass that a grass tile spreads to one exposed neighbour.
+    pub chance: f32,
+    /// Light (0.0–1.0) the dirt needs above it.
+    pub min_light: f32,
+    /// Most tiles changed in one pass.
+    pub max_changes: usize,
+}
+
+impl Default for GrassSpread {
+    fn default() -> Self {
+        Self {
+            grass: "grass".into(),
+            dirt: "dirt".into(),
+            interval_secs: 2.0,
+            chance: 0.05,
+            min_light: 0.5,
+            max_changes: 64,
+        }
+    }
+}
+
+/// Time since the last pass, and how many passes have happened.
+#[derive(Resource, Debug, Default)]
+pub struct GrassSpreadClock {
+    pub elapsed: f32,
+    pub ticks: u64,
+}
+
+/// Whether the tile at `(tile_x, tile_y)` has a solid foreground tile on top.
+fn covered(world_map: &WorldMap, tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> bool {
+    world_map
+        .get_tile(tile_x, tile_y + 1, Layer::Fg, ctx)
+        .is_some_and(|above| ctx.tile_registry.is_solid(above))
+}
+
+/// Spread grass onto exposed dirt and turn covered grass back into dirt, in
+/// the visible loaded chunks.
+#[allow(clippy::too_many_arguments)]
+pub fn tick_grass_spread(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<GrassSpread>,
+    mut clock: ResMut<GrassSpreadClock>,
+    ctx: WorldCtx,
+    mut world_map: ResMut<WorldMap>,
+    loaded_chunks: Res<LoadedChunks>,
+    world_time: Option<Res<WorldTime>>,
+    object_registry: Option<Res<ObjectRegistry>>,
+    mut dirty_chunks: ResMut<DirtyChunks>,
+    mut tile_changes: MessageWriter<TileChanged>,
+    mut rc_dirty: Option<ResMut<RcGridDirty>>,
+) {
+    clock.elapsed += time.delta_secs();
+    if clock.elapsed < settings.interval_secs {
+        return;
+    }
+    clock.elapsed = 0.0;
+    clock.ticks += 1;
+
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let tiles = ctx_ref.tile_registry;
+    let (Some(grass), Some(dirt)) = (
+        tiles.try_by_name(&settings.grass),
+        tiles.try_by_name(&settings.dirt),
+    ) else {
+        return;
+    };
+    let chunk_size = config.chunk_size as i32;
+    let sun = world_time.map_or(1.0, |wt| wt.sun_intensity);
+
+    let data_chunks: HashSet<(i32, i32)> = loaded_chunks
+        .map
+        .keys()
+        .filter(|&&(cx, cy)| loaded_chunks.is_visible(cx, cy))
+        .map(|&(cx, cy)| (config.wrap_chunk_x(cx), cy))
+        .collect();
+    let mut data_chunks: Vec<_> = data_chunks.into_iter().collect();
+    data_chunks.sort_unstable();
+
+    // Decide every change against the tiles as they were, so fresh grass
+    // doesn't spread again in the same pass.
+    let mut changes: Vec<(i32, i32, TileId)> = Vec::new();
+    let mut claimed = HashSet::new();
+    'chunks: for &(cx, cy) in &data_chunks {
+        let Some(chunk) = world_map.chunk(cx, cy) else {
+            continue;
+        };
+        for (idx, &tile) in chunk.fg.tiles.iter().enumerate() {
+            if changes.len() >= settings.max_changes {
+                break 'chunks;
+            }
+            if tile != grass {
+                continue;
+            }
+            let tile_x = cx * chunk_size + idx as i32 % chunk_size;
+            let tile_y = cy * chunk_size + idx as i32 / chunk_size;
+            if covered(&world_map, tile_x, tile_y, &ctx_ref) {
+                changes.push((tile_x, tile_y, dirt));
+                continue;
+            }
+
+            let salt =
+                ((tile_x as u32 as u64) << 32 | tile_y as u32 as u64) ^ clock.ticks.rotate_left(23);
+            let mut rng = SplitMix64::salted(config.seed as u64, salt);
+            if rng.next_f32() >= settings.chance {
+                continue;
+            }
+            let (dx, dy) = NEIGHBOURS_8[(rng.next_u64() % 8) as usize];
+            let (x, y) = (config.wrap_tile_x(tile_x + dx), tile_y + dy);
+            if world_map.get_tile(x, y, Layer::Fg, &ctx_ref) != Some(dirt)
+                || covered(&world_map, x, y, &ctx_ref)
+                || claimed.contains(&(x, y))
+            {
+                continue;
+            }
+            // Grass grows under any colour of light, so the brightest
+            // channel counts.
+            let sky = sunlight_at(&world_map, x, y + 1, [sun; 3], &ctx_ref)
+                .into_iter()
+                .fold(0.0, f32::max);
+            let light = light_at(
+                &world_map,
+                x,
+                y + 1,
+                sky,
+                object_registry.as_deref(),
+                &ctx_ref,
+            );
+            if light >= settings.min_light {
+                claimed.insert((x, y));
+                changes.push((x, y, grass));
+            }
+        }
+    }
+
+    for (tile_x, tile_y, tile) in changes {
+        world_map.set_tile(tile_x, tile_y, Layer::Fg, tile, &ctx_ref);
+        for changed in update_bitmasks_around(&mut world_map, tile_x, tile_y, Layer::Fg, &ctx_ref) {
+            dirty_chunks.0.insert(changed);
+            mark_meshes_dirty(&mut commands, &loaded_chunks, changed, &ctx_ref);
+        }
+        tile_changes.write(TileChanged { tile_x, tile_y });
+        if let Some(rc_dirty) = rc_dirty.as_deref_mut() {
+            rc_dirty.0 = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::{tile_to_chunk, ChunkEntities, ChunkState};
+
+    const LIT: (i32, i32) = (100, 1000);
+    const DARK: (i32, i32) = (200, 1000);
+
+    fn spread_app() -> App {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(250),
+        ))
+        .insert_resource(GrassSpread {
+            chance: 1.0,
+            interval_secs: 1.0,
+            ..default()
+        })
+        .init_resource::<GrassSpreadClock>()
+        .init_resource::<DirtyChunks>()
+        .init_resource::<LoadedChunks>()
+        .add_message::<TileChanged>()
+        .add_systems(Update, tick_grass_spread);
+        app
+    }
+
+    /// Set `(x, y)` to the tile named `name` and mark its chunk loaded.
+    fn put(app: &mut App, (x, y): (i32, i32), name: &'static str) {
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands,
+                      ctx: WorldCtx,
+                      mut map: ResMut<WorldMap>,
+                      mut loaded: ResMut<LoadedChunks>| {
+                    let ctx = ctx.as_ref();
+                    map.set_tile(x, y, Layer::Fg, ctx.tile_registry.by_name(name), &ctx);
+                    let chunk = tile_to_chunk(x, y, ctx.config.chunk_size);
+                    loaded.map.entry(chunk).or_insert_with(|| ChunkEntities {
+                        fg: commands.spawn_empty().id(),
+                        bg: commands.spawn_empty().id(),
+                        liquid: Entity::PLACEHOLDER,
+                        state: ChunkState::Visible,
+                    });
+                },
+            )
+            .unwrap();
+    }
+
+    fn tile_at(app: &mut App, (x, y): (i32, i32)) -> String {
+        app.world_mut()
+            .run_system_once(move |ctx: WorldCtx, map: Res<WorldMap>| {
+                let ctx = ctx.as_ref();
+                let tile = map.get_tile(x, y, Layer::Fg, &ctx).unwrap();
+                ctx.tile_registry.get(tile).id.clone()
+            })
+            .unwrap()
+    }
+
+    /// Update until the next pass has run.
+    fn pass(app: &mut App) {
+        let ticks = app.world().resource::<GrassSpreadClock>().ticks;
+        while app.world().resource::<GrassSpreadClock>().ticks == ticks {
+            app.update();
+        }
+    }
+
+    /// A patch of grass surrounded by dirt at `(x, y)`, roofed with stone a
+    /// few tiles up if `roofed`.
+    fn patch(app: &mut App, (x, y): (i32, i32), roofed: bool) {
+        for dx in -1..=1 {
+            for dy in -1..=0 {
+                put(app, (x + dx, y + dy), "dirt");
+            }
+        }
+        put(app, (x, y), "grass");
+        if roofed {
+            for dx in -2..=2 {
+                put(app, (x + dx, y + 4), "stone");
+            }
+        }
+    }
+
+    #[test]
+    fn exposed_dirt_next_to_lit_grass_turns_to_grass() {
+        let mut app = spread_app();
+        patch(&mut app, LIT, false);
+        patch(&mut app, DARK, true);
+
+        let spread = |app: &mut App, (x, y): (i32, i32)| {
+            [(x - 1, y), (x + 1, y)]
+                .into_iter()
+                .filter(|&pos| tile_at(app, pos) == "grass")
+                .count()
+        };
+        for _ in 0..40 {
+            pass(&mut app);
+        }
+        assert_eq!(spread(&mut app, LIT), 2);
+        assert_eq!(spread(&mut app, DARK), 0);
+        assert!(!app.world().resource::<DirtyChunks>().0.is_empty());
+        // Dirt under other dirt has no open space above it.
+        assert_eq!(tile_at(&mut app, (LIT.0, LIT.1 - 1)), "dirt");
+    }
+
+    #[test]
+    fn covered_grass_dies_back_to_dirt() {
+        let mut app = spread_app();
+        put(&mut app, LIT, "grass");
+        pass(&mut app);
+        assert_eq!(tile_at(&mut app, LIT), "grass");
+
+        put(&mut app, (LIT.0, LIT.1 + 1), "stone");
+        pass(&mut app);
+        assert_eq!(tile_at(&mut app, LIT), "dirt");
+    }
+}
@@ -0,0 +1,608 @@
+//! Time-based growth of plant tiles (saplings, crops).
+//!
+//! A tile with a [`GrowthDef`] keeps its stage in the high bits of its
+//! foreground state byte, which is saved with the chunk. Every
+//! [`GROWTH_TICK_SECS`] each plant in a visible loaded chunk rolls to advance
+//! a stage, as long as it stands on its soil and gets at least `min_light`.
+//! Lighting only exists on the GPU, so the light here is a CPU estimate from
+//! sunlight falling straight down through each column, tinted per channel
+//! by light filters ([`compute_chunk_sunlight`]), and nearby emitters. A
+//! plant past its last stage turns into its [`Maturity`] tile or object; a
+//! tree that has no room waits.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::DirtyChunks;
+use crate::math::SplitMix64;
+use crate::object::placement::{can_place_object, get_object_at, place_object};
+use crate::object::plugin::ObjectSpriteMaterials;
+use crate::object::registry::ObjectRegistry;
+use crate::object::spawn::spawn_object_entity;
+use crate::registry::tile::{
+    GrowthDef, Maturity, TileId, TILE_STATE_GROWTH, TILE_STATE_GROWTH_SHIFT,
+};
+use crate::world::chunk::{
+    tile_to_chunk, update_bitmasks_around, ChunkDirty, Layer, LoadedChunks, TileChanged, WorldMap,
+};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::day_night::WorldTime;
+use crate::world::lit_sprite::{LitSpriteMaterial, SharedLitQuad};
+use crate::world::rc_lighting::RcGridDirty;
+
+/// Seconds between growth rolls.
+pub const GROWTH_TICK_SECS: f32 = 1.0;
+/// Tiles above a plant checked for something blocking the sky.
+const SKY_SCAN_TILES: i32 = 48;
+/// Reach (tiles) of tile and object light sources.
+const EMITTER_RADIUS: i32 = 6;
+
+/// Time since the last growth roll, and how many rolls have happened.
+#[derive(Resource, Debug, Default)]
+pub struct GrowthClock {
+    pub elapsed: f32,
+    pub ticks: u64,
+}
+
+/// Growth stage stored in a tile's state byte.
+pub fn growth_stage(state: u8) -> u8 {
+    (state & TILE_STATE_GROWTH) >> TILE_STATE_GROWTH_SHIFT
+}
+
+/// `state` with its growth stage replaced by `stage` (at most 15).
+pub fn with_growth_stage(state: u8, stage: u8) -> u8 {
+    (state & !TILE_STATE_GROWTH) | ((stage << TILE_STATE_GROWTH_SHIFT) & TILE_STATE_GROWTH)
+}
+
+/// What one growth roll did to a plant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthStep {
+    /// Too dark or off its soil.
+    Stalled,
+    /// Could grow but the roll missed.
+    Waiting,
+    /// Advanced to this stage.
+    Grew(u8),
+    /// Finished its last stage.
+    Matured,
+}
+
+/// Roll one growth step for a plant at `stage`. `roll` is uniform in
+/// `[0, 1)`; over `dt` seconds the plant advances with chance
+/// `dt / stage_secs`, so a stage takes `stage_secs` on average.
+pub fn step_growth(
+    def: &GrowthDef,
+    stage: u8,
+    light: f32,
+    on_soil: bool,
+    roll: f32,
+    dt: f32,
+) -> GrowthStep {
+    if !on_soil || light < def.min_light {
+        return GrowthStep::Stalled;
+    }
+    let chance = if def.stage_secs > 0.0 {
+        dt / def.stage_secs
+    } else {
+        1.0
+    };
+    if roll >= chance {
+        return GrowthStep::Waiting;
+    }
+    let next = stage.saturating_add(1);
+    if next >= def.stages.min(16) {
+        GrowthStep::Matured
+    } else {
+        GrowthStep::Grew(next)
+    }
+}
+
+/// Light left after passing through one tile: `opacity` (0–15) takes that
+/// many fifteenths off every channel, then `filter` scales each channel.
+pub fn attenuate(light: [f32; 3], opacity: u8, filter: [u8; 3]) -> [f32; 3] {
+    let blocked = opacity as f32 / 15.0;
+    std::array::from_fn(|c| (light[c] - blocked).max(0.0) * filter[c] as f32 / 255.0)
+}
+
+/// Sunlight reaching each tile of chunk `(cx, cy)` straight from above,
+/// indexed like the chunk's tiles. Each column starts at `sun`
+/// [`SKY_SCAN_TILES`] above the chunk and is attenuated by every
+/// foreground tile on the way down; a tile's entry is the light arriving
+/// at it. Unloaded tiles count as open sky.
+pub fn compute_chunk_sunlight(
+    world_map: &WorldMap,
+    (cx, cy): (i32, i32),
+    sun: [f32; 3],
+    ctx: &WorldCtxRef,
+) -> Vec<[f32; 3]> {
+    let tiles = ctx.tile_registry;
+    let chunk_size = ctx.config.chunk_size as i32;
+    let top = (cy + 1) * chunk_size - 1;
+    let mut sunlight = vec![[0.0; 3]; (chunk_size * chunk_size) as usize];
+    for local_x in 0..chunk_size {
+        let tile_x = cx * chunk_size + local_x;
+        let mut light = sun;
+        for tile_y in (cy * chunk_size..=top + SKY_SCAN_TILES).rev() {
+            if tile_y <= top {
+                let local_y = tile_y - cy * chunk_size;
+                sunlight[(local_y * chunk_size + local_x) as usize] = light;
+            }
+            if let Some(tile) = world_map.get_tile(tile_x, tile_y, Layer::Fg, ctx) {
+                light = attenuate(light, tiles.light_opacity(tile), tiles.light_filter(tile));
+            }
+        }
+    }
+    sunlight
+}
+
+/// Sunlight reaching the single tile `(tile_x, tile_y)` straight from above,
+/// attenuated by the [`SKY_SCAN_TILES`] foreground tiles over it.
+pub fn sunlight_at(
+    world_map: &WorldMap,
+    tile_x: i32,
+    tile_y: i32,
+    sun: [f32; 3],
+    ctx: &WorldCtxRef,
+) -> [f32; 3] {
+    let tiles = ctx.tile_registry;
+    let mut light = sun;
+    for y in (tile_y + 1..=tile_y + SKY_SCAN_TILES).rev() {
+        if let Some(tile) = world_map.get_tile(tile_x, y, Layer::Fg, ctx) {
+            light = attenuate(light, tiles.light_opacity(tile), tiles.light_filter(tile));
+        }
+    }
+    light
+}
+
+/// Estimated light level (0.0–1.0) at a tile: `sky`, the sunlight reaching
+/// it (see [`compute_chunk_sunlight`]), or the brightest tile or object
+/// emitter in reach, fading with distance.
+pub fn light_at(
+    world_map: &WorldMap,
+    tile_x: i32,
+    tile_y: i32,
+    sky: f32,
+    object_registry: Option<&ObjectRegistry>,
+    ctx: &WorldCtxRef,
+) -> f32 {
+    let tiles = ctx.tile_registry;
+    let mut light = sky;
+
+    for dy in -EMITTER_RADIUS..=EMITTER_RADIUS {
+        for dx in -EMITTER_RADIUS..=EMITTER_RADIUS {
+            let falloff =
+                1.0 - Vec2::new(dx as f32, dy as f32).length() / (EMITTER_RADIUS + 1) as f32;
+            if falloff <= light {
+                continue;
+            }
+            let (x, y) = (tile_x + dx, tile_y + dy);
+            let mut emission = world_map
+                .get_tile(x, y, Layer::Fg, ctx)
+                .map_or([0; 3], |tile| {
+                    tiles.light_emission_in_state(tile, world_map.get_tile_state(x, y, ctx))
+                });
+            if let Some(objects) = object_registry
+                && let Some((_, _, _, object_id)) = get_object_at(world_map, x, y, ctx)
+            {
+                let object_emission = objects.get(object_id).light_emission;
+                for (e, o) in emission.iter_mut().zip(object_emission) {
+                    *e = (*e).max(o);
+                }
+            }
+            let strength = emission.into_iter().max().unwrap_or(0) as f32 / 255.0;
+            light = light.max(strength * falloff);
+        }
+    }
+    light.min(1.0)
+}
+
+/// Whether a plant at `(tile_x, tile_y)` stands on one of its soil tiles.
+fn on_soil(
+    world_map: &WorldMap,
+    tile_x: i32,
+    tile_y: i32,
+    def: &GrowthDef,
+    ctx: &WorldCtxRef,
+) -> bool {
+    let Some(below) = world_map.get_tile(tile_x, tile_y - 1, Layer::Fg, ctx) else {
+        return false;
+    };
+    if def.soil.is_empty() {
+        return ctx.tile_registry.is_solid(below);
+    }
+    let name = &ctx.tile_registry.get(below).id;
+    def.soil.iter().any(|soil| soil == name)
+}
+
+/// Mark the meshes of every display chunk showing data chunk `(cx, cy)`
+/// for rebuilding.
+pub(crate) fn mark_meshes_dirty(
+    commands: &mut Commands,
+    loaded_chunks: &LoadedChunks,
+    (cx, cy): (i32, i32),
+    ctx: &WorldCtxRef,
+) {
+    for (&(display_cx, display_cy), entities) in &loaded_chunks.map {
+        if ctx.config.wrap_chunk_x(display_cx) == cx && display_cy == cy {
+            commands.entity(entities.fg).insert(ChunkDirty);
+            commands.entity(entities.bg).insert(ChunkDirty);
+        }
+    }
+}
+
+/// Roll growth for every plant in the visible loaded chunks.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn tick_growth(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut clock: ResMut<GrowthClock>,
+    ctx: WorldCtx,
+    mut world_map: ResMut<WorldMap>,
+    loaded_chunks: Res<LoadedChunks>,
+    world_time: Option<Res<WorldTime>>,
+    mut dirty_chunks: ResMut<DirtyChunks>,
+    mut tile_changes: MessageWriter<TileChanged>,
+    mut rc_dirty: Option<ResMut<RcGridDirty>>,
+    objects: (
+        Option<Res<ObjectRegistry>>,
+        Option<Res<ObjectSpriteMaterials>>,
+        Option<Res<SharedLitQuad>>,
+        Option<ResMut<Assets<LitSpriteMaterial>>>,
+    ),
+) {
+    clock.elapsed += time.delta_secs();
+    if clock.elapsed < GROWTH_TICK_SECS {
+        return;
+    }
+    let dt = std::mem::take(&mut clock.elapsed);
+    clock.ticks += 1;
+
+    let (object_registry, object_sprites, quad, mut lit_materials) = objects;
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let chunk_size = config.chunk_size as i32;
+    let sun = world_time.map_or(1.0, |wt| wt.sun_intensity);
+
+    let data_chunks: HashSet<(i32, i32)> = loaded_chunks
+        .map
+        .keys()
+        .filter(|&&(cx, cy)| loaded_chunks.is_visible(cx, cy))
+        .map(|&(cx, cy)| (config.wrap_chunk_x(cx), cy))
+        .collect();
+    let mut data_chunks: Vec<_> = data_chunks.into_iter().collect();
+    data_chunks.sort_unstable();
+
+    let mut plants = Vec::new();
+    for &(cx, cy) in &data_chunks {
+        let Some(chunk) = world_map.chunk(cx, cy) else {
+            continue;
+        };
+        let mut sunlight = None;
+        for (idx, &tile) in chunk.fg.tiles.iter().enumerate() {
+            if ctx_ref.tile_registry.get(tile).growth.is_some() {
+                let tile_x = cx * chunk_size + idx as i32 % chunk_size;
+                let tile_y = cy * chunk_size + idx as i32 / chunk_size;
+                // Plants grow under any colour of light, so the brightest
+                // channel counts.
+                let sky = sunlight.get_or_insert_with(|| {
+                    compute_chunk_sunlight(&world_map, (cx, cy), [sun; 3], &ctx_ref)
+                })[idx]
+                    .into_iter()
+                    .fold(0.0, f32::max);
+                plants.push((tile_x, tile_y, tile, chunk.tile_state(idx), sky));
+            }
+        }
+    }
+
+    for (tile_x, tile_y, tile, state, sky) in plants {
+        let Some(def) = &ctx_ref.tile_registry.get(tile).growth else {
+            continue;
+        };
+        let stage = growth_stage(state);
+        let light = light_at(
+            &world_map,
+            tile_x,
+            tile_y,
+            sky,
+            object_registry.as_deref(),
+            &ctx_ref,
+        );
+        let soil = on_soil(&world_map, tile_x, tile_y, def, &ctx_ref);
+        let salt =
+            ((tile_x as u32 as u64) << 32 | tile_y as u32 as u64) ^ clock.ticks.rotate_left(17);
+        let roll = SplitMix64::salted(config.seed as u64, salt).next_f32();
+        let chunk = tile_to_chunk(tile_x, tile_y, config.chunk_size);
+
+        match step_growth(def, stage, light, soil, roll, dt) {
+            GrowthStep::Stalled | GrowthStep::Waiting => continue,
+            GrowthStep::Grew(next) => {
+                world_map.set_tile_state(tile_x, tile_y, with_growth_stage(state, next), &ctx_ref);
+                dirty_chunks.0.insert(chunk);
+                continue;
+            }
+            GrowthStep::Matured => {}
+        }
+
+        match &def.matures_into {
+            Maturity::Tile(name) => {
+                let Some(ripe) = ctx_ref.tile_registry.try_by_name(name) else {
+                    continue;
+                };
+                world_map.set_tile(tile_x, tile_y, Layer::Fg, ripe, &ctx_ref);
+                for changed in
+                    update_bitmasks_around(&mut world_map, tile_x, tile_y, Layer::Fg, &ctx_ref)
+                {
+                    dirty_chunks.0.insert(changed);
+                    mark_meshes_dirty(&mut commands, &loaded_chunks, changed, &ctx_ref);
+                }
+            }
+            Maturity::Object(name) => {
+                let Some(registry) = object_registry.as_deref() else {
+                    continue;
+                };
+                let Some(object_id) = registry.by_name(name) else {
+                    continue;
+                };
+                // Centre the object's footprint on the plant.
+                let anchor_x = tile_x - (registry.get(object_id).size.0 as i32 - 1) / 2;
+                world_map.set_tile(tile_x, tile_y, Layer::Fg, TileId::AIR, &ctx_ref);
+                if !can_place_object(&world_map, registry, object_id, anchor_x, tile_y, &ctx_ref)
+                    || !place_object(
+                        &mut world_map,
+                        registry,
+                        object_id,
+                        anchor_x,
+                        tile_y,
+                        &ctx_ref,
+                    )
+                {
+                    // No room yet: stay fully grown and try again next roll.
+                    world_map.set_tile(tile_x, tile_y, Layer::Fg, tile, &ctx_ref);
+                    world_map.set_tile_state(tile_x, tile_y, state, &ctx_ref);
+                    continue;
+                }
+                let anchor_chunk =
+                    tile_to_chunk(config.wrap_tile_x(anchor_x), tile_y, config.chunk_size);
+                dirty_chunks.0.insert(chunk);
+                dirty_chunks.0.insert(anchor_chunk);
+                mark_meshes_dirty(&mut commands, &loaded_chunks, chunk, &ctx_ref);
+                let object_index = world_map
+                    .chunk(anchor_chunk.0, anchor_chunk.1)
+                    .map_or(0, |c| c.objects.len() - 1) as u16;
+                for &(display_cx, display_cy) in loaded_chunks.map.keys() {
+                    if let Some(lit_materials) = lit_materials.as_deref_mut()
+                        && config.wrap_chunk_x(display_cx) == anchor_chunk.0
+                        && display_cy == anchor_chunk.1
+                    {
+                        spawn_object_entity(
+                            &mut commands,
+                            &world_map,
+                            registry,
+                            object_sprites.as_deref(),
+                            quad.as_deref(),
+                            lit_materials,
+                            anchor_chunk,
+                            display_cx,
+                            object_index,
+                            config.tile_size,
+                            config.chunk_size,
+                        );
+                    }
+                }
+            }
+        }
+        tile_changes.write(TileChanged { tile_x, tile_y });
+        if let Some(rc_dirty) = rc_dirty.as_deref_mut() {
+            rc_dirty.0 = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::registry::tile::{TileDef, TileRegistry};
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::{ChunkEntities, ChunkState};
+
+    const LIT: (i32, i32) = (100, 1000);
+    const DARK: (i32, i32) = (200, 1000);
+
+    fn crop() -> GrowthDef {
+        GrowthDef {
+            stages: 3,
+            stage_secs: 1.0,
+            min_light: 0.5,
+            soil: vec!["dirt".into()],
+            matures_into: Maturity::Tile("ripe_crop".into()),
+        }
+    }
+
+    #[test]
+    fn stage_bits_keep_the_rest_of_the_state() {
+        let state = with_growth_stage(0b0000_0101, 9);
+        assert_eq!(growth_stage(state), 9);
+        assert_eq!(state & !TILE_STATE_GROWTH, 0b0000_0101);
+        assert_eq!(growth_stage(with_growth_stage(state, 0)), 0);
+    }
+
+    #[test]
+    fn attenuation_is_per_channel() {
+        let white = [1.0; 3];
+        assert_eq!(attenuate(white, 0, [255, 255, 255]), white);
+        let red = attenuate(white, 0, [255, 51, 0]);
+        assert_eq!(red, [1.0, 0.2, 0.0]);
+        // Opacity comes off every channel before the filter applies.
+        let dim = attenuate([1.0, 0.5, 0.1], 3, [255, 255, 255]);
+        assert!((dim[0] - 0.8).abs() < 1e-6 && (dim[1] - 0.3).abs() < 1e-6);
+        assert_eq!(dim[2], 0.0);
+        assert_eq!(attenuate(white, 15, [255, 255, 255]), [0.0; 3]);
+    }
+
+    #[test]
+    fn red_glass_column_tints_sunlight_below_it() {
+        let (wc, bm, br, _, pc, nc) = fixtures::test_world_ctx();
+        let mut defs = fixtures::test_tile_registry().defs;
+        let glass = TileDef {
+            id: "red_glass".into(),
+            light_filter: [255, 40, 40],
+            light_cone: None,
+            ..defs[0].clone()
+        };
+        defs.push(glass);
+        let tr = TileRegistry::from_defs(defs);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let (x, y) = LIT;
+        // Clear a shaft so only the glass tints the column.
+        for dy in -2..=SKY_SCAN_TILES {
+            map.set_tile(x, y + dy, Layer::Fg, TileId::AIR, &ctx);
+            map.set_tile(x + 1, y + dy, Layer::Fg, TileId::AIR, &ctx);
+        }
+        map.set_tile(x, y + 2, Layer::Fg, tr.by_name("red_glass"), &ctx);
+
+        let chunk_size = wc.chunk_size as i32;
+        let (cx, cy) = tile_to_chunk(x, y, wc.chunk_size);
+        let sunlight = compute_chunk_sunlight(&map, (cx, cy), [1.0; 3], &ctx);
+        let at = |tx: i32, ty: i32| {
+            sunlight[((ty - cy * chunk_size) * chunk_size + tx - cx * chunk_size) as usize]
+        };
+        let below = at(x, y);
+        assert_eq!(below[0], 1.0);
+        assert!((below[1] - 40.0 / 255.0).abs() < 1e-6, "{below:?}");
+        assert_eq!(below[1], below[2]);
+        assert_eq!(at(x + 1, y), [1.0; 3], "open column beside the glass");
+    }
+
+    #[test]
+    fn step_needs_light_and_soil_and_matures_after_the_last_stage() {
+        let def = crop();
+        assert_eq!(
+            step_growth(&def, 0, 0.4, true, 0.0, 1.0),
+            GrowthStep::Stalled
+        );
+        assert_eq!(
+            step_growth(&def, 0, 1.0, false, 0.0, 1.0),
+            GrowthStep::Stalled
+        );
+        assert_eq!(
+            step_growth(&def, 0, 0.5, true, 0.0, 1.0),
+            GrowthStep::Grew(1)
+        );
+        assert_eq!(
+            step_growth(&def, 1, 1.0, true, 0.0, 1.0),
+            GrowthStep::Grew(2)
+        );
+        assert_eq!(
+            step_growth(&def, 2, 1.0, true, 0.0, 1.0),
+            GrowthStep::Matured
+        );
+        // A quarter of a stage's time gives a quarter chance.
+        assert_eq!(
+            step_growth(&def, 0, 1.0, true, 0.2, 0.25),
+            GrowthStep::Grew(1)
+        );
+        assert_eq!(
+            step_growth(&def, 0, 1.0, true, 0.3, 0.25),
+            GrowthStep::Waiting
+        );
+    }
+
+    fn growth_app() -> App {
+        let mut defs = fixtures::test_tile_registry().defs;
+        let air = defs[0].clone();
+        defs.push(TileDef {
+            id: "crop".into(),
+            growth: Some(crop()),
+            ..air.clone()
+        });
+        defs.push(TileDef {
+            id: "ripe_crop".into(),
+            ..air
+        });
+
+        let mut app = fixtures::test_app();
+        app.insert_resource(TileRegistry::from_defs(defs))
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_millis(250),
+            ))
+            .init_resource::<GrowthClock>()
+            .init_resource::<DirtyChunks>()
+            .init_resource::<LoadedChunks>()
+            .add_message::<TileChanged>()
+            .add_systems(Update, tick_growth);
+        app
+    }
+
+    /// Plant a crop on dirt, optionally roofed with stone, in a loaded chunk.
+    fn plant(app: &mut App, (x, y): (i32, i32), roofed: bool) {
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands,
+                      ctx: WorldCtx,
+                      mut map: ResMut<WorldMap>,
+                      mut loaded: ResMut<LoadedChunks>| {
+                    let ctx = ctx.as_ref();
+                    let tiles = ctx.tile_registry;
+                    map.set_tile(x, y - 1, Layer::Fg, tiles.by_name("dirt"), &ctx);
+                    map.set_tile(x, y, Layer::Fg, tiles.by_name("crop"), &ctx);
+                    if roofed {
+                        map.set_tile(x, y + 3, Layer::Fg, tiles.by_name("stone"), &ctx);
+                    }
+                    let entry = ChunkEntities {
+                        fg: commands.spawn_empty().id(),
+                        bg: commands.spawn_empty().id(),
+                        liquid: Entity::PLACEHOLDER,
+                        state: ChunkState::Visible,
+                    };
+                    loaded
+                        .map
+                        .insert(tile_to_chunk(x, y, ctx.config.chunk_size), entry);
+                },
+            )
+            .unwrap();
+    }
+
+    /// Tile name and growth stage at `(x, y)`.
+    fn plant_at(app: &mut App, (x, y): (i32, i32)) -> (String, u8) {
+        app.world_mut()
+            .run_system_once(move |ctx: WorldCtx, map: Res<WorldMap>| {
+                let ctx = ctx.as_ref();
+                let tile = map.get_tile(x, y, Layer::Fg, &ctx).unwrap();
+                let stage = growth_stage(map.get_tile_state(x, y, &ctx));
+                (ctx.tile_registry.get(tile).id.clone(), stage)
+            })
+            .unwrap()
+    }
+
+    /// Update until the next growth roll has run.
+    fn roll(app: &mut App) {
+        let ticks = app.world().resource::<GrowthClock>().ticks;
+        while app.world().resource::<GrowthClock>().ticks == ticks {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn crop_grows_under_open_sky_and_stalls_in_darkness() {
+        let mut app = growth_app();
+        plant(&mut app, LIT, false);
+        plant(&mut app, DARK, true);
+
+        roll(&mut app);
+        assert_eq!(plant_at(&mut app, LIT), ("crop".into(), 1));
+        assert_eq!(plant_at(&mut app, DARK), ("crop".into(), 0));
+        assert!(!app.world().resource::<DirtyChunks>().0.is_empty());
+
+        roll(&mut app);
+        assert_eq!(plant_at(&mut app, LIT), ("crop".into(), 2));
+        roll(&mut app);
+        assert_eq!(plant_at(&mut app, LIT).0, "ripe_crop");
+        assert_eq!(plant_at(&mut app, DARK), ("crop".into(), 0));
+    }
+}
@@ -0,0 +1,172 @@
+//! Directional emitters: which texels a cone light seeds.
+//!
+//! A point light seeds only its own texel and the radiance cascades spread it
+//! evenly around. A cone emitter instead seeds a short wedge of texels in
+//! front of it (its footprint), so the cascades carry most of its light that
+//! way. It's an approximation: light still spreads a little sideways from
+//! every seeded texel.
+
+use bevy::prelude::*;
+
+use crate::registry::tile::LightCone;
+
+/// How far (tiles) a cone footprint reaches from its emitter.
+pub const CONE_REACH: i32 = 4;
+/// Weight of the emitter's own texel, kept low so the cone dominates.
+const CONE_ORIGIN_WEIGHT: f32 = 0.25;
+/// Weight of a neighbour just outside the cone, softening its edge.
+const CONE_EDGE_WEIGHT: f32 = 0.35;
+/// How far (degrees) past the half-angle a neighbour still gets
+/// [`CONE_EDGE_WEIGHT`].
+const CONE_EDGE_SPREAD: f32 = 45.0;
+
+/// The 8 neighbours of an emitter, y up.
+const NEIGHBOURS_8: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+/// Angle (degrees) between `offset` and the cone's axis.
+fn off_axis_angle(cone: &LightCone, offset: IVec2) -> f32 {
+    offset
+        .as_vec2()
+        .angle_to(cone.direction.step().as_vec2())
+        .abs()
+        .to_degrees()
+}
+
+/// Whether `offset` (y up, not the emitter itself) lies inside the cone.
+pub fn in_cone(cone: &LightCone, offset: IVec2) -> bool {
+    offset != IVec2::ZERO && off_axis_angle(cone, offset) <= cone.half_angle + 1e-3
+}
+
+/// Seed weight of each of the 8 neighbours, bottom row first: full inside
+/// the cone, [`CONE_EDGE_WEIGHT`] just outside it and nothing behind it.
+pub fn neighbour_seeds(cone: &LightCone) -> [f32; 8] {
+    NEIGHBOURS_8.map(|offset| {
+        if in_cone(cone, offset) {
+            1.0
+        } else if off_axis_angle(cone, offset) <= cone.half_angle + CONE_EDGE_SPREAD + 1e-3 {
+            CONE_EDGE_WEIGHT
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Texels a cone emitter seeds, as `(offset, weight)` with offsets in tiles
+/// (y up). Holds the emitter itself, its neighbours weighted by
+/// [`neighbour_seeds`] and every texel of the cone within `reach`, fading
+/// linearly with distance.
+pub fn cone_footprint(cone: &LightCone, reach: i32) -> Vec<(IVec2, f32)> {
+    let mut footprint = vec![(IVec2::ZERO, CONE_ORIGIN_WEIGHT)];
+    footprint.extend(
+        NEIGHBOURS_8
+            .into_iter()
+            .zip(neighbour_seeds(cone))
+            .filter(|&(_, weight)| weight > 0.0),
+    );
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            let offset = IVec2::new(dx, dy);
+            let dist = offset.as_vec2().length();
+            if dist < 2.0 || dist > reach as f32 || !in_cone(cone, offset) {
+                continue;
+            }
+            footprint.push((offset, 1.0 - (dist - 1.0) / reach as f32));
+        }
+    }
+    footprint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::tile::LightDirection;
+
+    fn cone(direction: LightDirection, half_angle: f32) -> LightCone {
+        LightCone {
+            direction,
+            half_angle,
+        }
+    }
+
+    fn seeded(cone: &LightCone) -> Vec<IVec2> {
+        NEIGHBOURS_8
+            .into_iter()
+            .zip(neighbour_seeds(cone))
+            .filter(|&(_, w)| w == 1.0)
+            .map(|(offset, _)| offset)
+            .collect()
+    }
+
+    #[test]
+    fn narrow_cone_seeds_only_the_facing_neighbour() {
+        assert_eq!(seeded(&cone(LightDirection::Up, 30.0)), [IVec2::Y]);
+        assert_eq!(seeded(&cone(LightDirection::Down, 30.0)), [IVec2::NEG_Y]);
+        assert_eq!(seeded(&cone(LightDirection::Left, 30.0)), [IVec2::NEG_X]);
+        assert_eq!(seeded(&cone(LightDirection::Right, 30.0)), [IVec2::X]);
+    }
+
+    #[test]
+    fn wide_cone_seeds_the_diagonals_too() {
+        assert_eq!(
+            seeded(&cone(LightDirection::Right, 45.0)),
+            [IVec2::new(1, -1), IVec2::X, IVec2::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn neighbours_outside_the_cone_fall_off() {
+        let seeds = neighbour_seeds(&cone(LightDirection::Up, 30.0));
+        let weight = |offset: IVec2| seeds[NEIGHBOURS_8.iter().position(|&n| n == offset).unwrap()];
+        assert_eq!(weight(IVec2::new(1, 1)), CONE_EDGE_WEIGHT);
+        assert_eq!(weight(IVec2::X), 0.0);
+        assert_eq!(weight(IVec2::NEG_Y), 0.0);
+    }
+
+    #[test]
+    fn footprint_stays_in_front_and_within_reach() {
+        for direction in [
+            LightDirection::Up,
+            LightDirection::Down,
+            LightDirection::Left,
+            LightDirection::Right,
+        ] {
+            let c = cone(direction, 30.0);
+            let footprint = cone_footprint(&c, CONE_REACH);
+            assert!(footprint.contains(&(IVec2::ZERO, CONE_ORIGIN_WEIGHT)));
+            assert!(footprint.contains(&(direction.step() * CONE_REACH, 0.25)));
+            for &(offset, weight) in &footprint {
+                assert!(weight > 0.0 && weight <= 1.0);
+                assert!(offset.as_vec2().length() <= CONE_REACH as f32);
+                // Nothing behind the emitter.
+                assert!(offset.dot(direction.step()) >= 0, "{direction:?}: {offset}");
+            }
+        }
+    }
+
+    #[test]
+    fn footprint_widens_with_half_angle() {
+        let narrow = cone_footprint(&cone(LightDirection::Down, 15.0), CONE_REACH);
+        let wide = cone_footprint(&cone(LightDirection::Down, 60.0), CONE_REACH);
+        assert!(wide.len() > narrow.len());
+        // (2, -3) is ~34° off the axis.
+        assert!(!narrow.iter().any(|&(o, _)| o == IVec2::new(2, -3)));
+        assert!(wide.iter().any(|&(o, _)| o == IVec2::new(2, -3)));
+    }
+
+    #[test]
+    fn footprint_fades_with_distance() {
+        let footprint = cone_footprint(&cone(LightDirection::Left, 30.0), CONE_REACH);
+        let weight = |offset: IVec2| footprint.iter().find(|&&(o, _)| o == offset).unwrap().1;
+        assert!(weight(IVec2::NEG_X) > weight(IVec2::new(-2, 0)));
+        assert!(weight(IVec2::new(-2, 0)) > weight(IVec2::new(-4, 0)));
+    }
+}
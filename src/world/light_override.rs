@@ -0,0 +1,405 @@
+//! Forced lighting for scripted areas: a tile rectangle whose light ignores
+//! the sun and torches around it, e.g. a cursed zone that stays dark even on
+//! the surface, or a set-piece lit in a fixed colour.
+//!
+//! Overrides live in [`LightOverrides`] and are applied by
+//! `rc_lighting::extract_lighting_data` after every other emitter: each
+//! texel inside a rectangle has its emission replaced or clamped per channel
+//! ([`LightOverrideMode`]), and dark zones also block light passing through
+//! their open tiles. Rectangles wrap around the world seam like tiles do.
+//! Cheats-gated `/light` commands add, list and remove them for testing.
+
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::chat::{ChatCommandEvent, ChatState};
+use crate::game_mode::CheatsEnabled;
+use crate::registry::world::ActiveWorld;
+use crate::world::rc_lighting::RcGridDirty;
+
+/// Tile rectangle anchored at its bottom-left tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl TileRect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Whether tile (`tx`, `ty`) lies inside; on wrapping worlds `tx` may be
+    /// given in any copy of the world.
+    pub fn contains(&self, tx: i32, ty: i32, config: &ActiveWorld) -> bool {
+        let dx = if config.wrap_x {
+            (tx - self.x).rem_euclid(config.width_tiles)
+        } else {
+            tx - self.x
+        };
+        (0..self.width).contains(&dx) && (self.y..self.y + self.height).contains(&ty)
+    }
+
+    /// Chunks (wrapped coordinates) with at least one tile inside.
+    pub fn chunks(&self, config: &ActiveWorld) -> Vec<(i32, i32)> {
+        if self.width <= 0 || self.height <= 0 {
+            return Vec::new();
+        }
+        let cs = config.chunk_size as i32;
+        let (min_cy, max_cy) = (
+            self.y.div_euclid(cs),
+            (self.y + self.height - 1).div_euclid(cs),
+        );
+        let (min_cx, max_cx) = (
+            self.x.div_euclid(cs),
+            (self.x + self.width - 1).div_euclid(cs),
+        );
+        let mut chunks = Vec::new();
+        for cx in min_cx..=max_cx {
+            let cx = config.wrap_chunk_x(cx);
+            for cy in min_cy..=max_cy {
+                if !chunks.contains(&(cx, cy)) {
+                    chunks.push((cx, cy));
+                }
+            }
+        }
+        chunks
+    }
+}
+
+/// What an override does to the light emitted inside its rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LightOverrideMode {
+    /// No emission, and light from outside doesn't pass through open tiles.
+    Dark,
+    /// Each channel capped at the given value.
+    Clamp([f32; 3]),
+    /// Every tile emits exactly the given colour.
+    Fill([f32; 3]),
+}
+
+impl LightOverrideMode {
+    /// Apply to one emissive texel.
+    pub fn apply(self, texel: &mut [f32; 4]) {
+        match self {
+            Self::Dark => *texel = [0.0; 4],
+            Self::Clamp(cap) => {
+                for (channel, cap) in texel.iter_mut().zip(cap) {
+                    *channel = channel.min(cap);
+                }
+            }
+            Self::Fill(colour) => *texel = [colour[0], colour[1], colour[2], 1.0],
+        }
+    }
+}
+
+/// One override: a rectangle and its lighting.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LightOverride {
+    pub id: u32,
+    pub rect: TileRect,
+    pub mode: LightOverrideMode,
+}
+
+/// Active light overrides, applied in insertion order (later ones win).
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LightOverrides {
+    overrides: Vec<LightOverride>,
+    next_id: u32,
+    /// Chunks whose lighting changed since the lighting grid last rebuilt.
+    #[serde(skip)]
+    relight: Vec<(i32, i32)>,
+}
+
+impl LightOverrides {
+    /// Force the light in `rect`; returns the id to remove it with.
+    pub fn set_light_override(
+        &mut self,
+        rect: TileRect,
+        mode: LightOverrideMode,
+        config: &ActiveWorld,
+    ) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.overrides.push(LightOverride { id, rect, mode });
+        self.relight.extend(rect.chunks(config));
+        id
+    }
+
+    /// Drop override `id`, relighting the chunks it covered.
+    pub fn remove(&mut self, id: u32, config: &ActiveWorld) -> Option<LightOverride> {
+        let pos = self.overrides.iter().position(|o| o.id == id)?;
+        let removed = self.overrides.remove(pos);
+        self.relight.extend(removed.rect.chunks(config));
+        Some(removed)
+    }
+
+    pub fn clear(&mut self, config: &ActiveWorld) {
+        for id in self.overrides.iter().map(|o| o.id).collect::<Vec<_>>() {
+            self.remove(id, config);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LightOverride> {
+        self.overrides.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
+
+/// Rebuild the lighting grid once overrides covering any chunk changed.
+pub fn relight_overridden_chunks(
+    mut overrides: ResMut<LightOverrides>,
+    mut rc_dirty: ResMut<RcGridDirty>,
+) {
+    if !overrides.relight.is_empty() {
+        overrides.relight.clear();
+        rc_dirty.0 = true;
+    }
+}
+
+/// `/light dark|clamp|fill <x> <y> <w> <h> [r g b]`, `/light remove <id>`,
+/// `/light list`, `/light clear`.
+pub fn handle_light_command(
+    mut commands_in: MessageReader<ChatCommandEvent>,
+    cheats: Res<CheatsEnabled>,
+    config: Res<ActiveWorld>,
+    mut overrides: ResMut<LightOverrides>,
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    for cmd in commands_in.read() {
+        if cmd.command != "light" {
+            continue;
+        }
+        let reply = if !cheats.0 {
+            "Cheats are disabled.".to_string()
+        } else {
+            run_light_command(&mut overrides, &cmd.args, &config)
+        };
+        if let Some(chat) = chat.as_mut() {
+            chat.send_system(&reply, time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Apply one `/light` command and describe the result.
+fn run_light_command(
+    overrides: &mut LightOverrides,
+    args: &[String],
+    config: &ActiveWorld,
+) -> String {
+    let numbers = |range: std::ops::Range<usize>| -> Option<Vec<f32>> {
+        args.get(range)?.iter().map(|a| a.parse().ok()).collect()
+    };
+    let usage =
+        "Usage: /light <dark|clamp|fill> <x> <y> <w> <h> [r g b] | remove <id> | list | clear";
+    match args.first().map(String::as_str) {
+        Some(kind @ ("dark" | "clamp" | "fill")) => {
+            let Some(rect) = numbers(1..5) else {
+                return usage.to_string();
+            };
+            let rect = TileRect::new(
+                rect[0] as i32,
+                rect[1] as i32,
+                rect[2] as i32,
+                rect[3] as i32,
+            );
+            if rect.width <= 0 || rect.height <= 0 {
+                return "The area must be at least one tile.".to_string();
+            }
+            let mode = match (kind, numbers(5..8)) {
+                ("dark", _) => LightOverrideMode::Dark,
+                ("clamp", Some(c)) => LightOverrideMode::Clamp([c[0], c[1], c[2]]),
+                ("fill", Some(c)) => LightOverrideMode::Fill([c[0], c[1], c[2]]),
+                _ => return usage.to_string(),
+            };
+            let id = overrides.set_light_override(rect, mode, config);
+            format!("Light override {id} added.")
+        }
+        Some("remove") => match args.get(1).and_then(|a| a.parse().ok()) {
+            Some(id) => match overrides.remove(id, config) {
+                Some(_) => format!("Light override {id} removed."),
+                None => format!("No light override {id}."),
+            },
+            None => "Usage: /light remove <id>".to_string(),
+        },
+        Some("list") if overrides.is_empty() => "No light overrides.".to_string(),
+        Some("list") => overrides
+            .iter()
+            .map(|o| {
+                let r = o.rect;
+                format!(
+                    "{}: {:?} at {},{} {}x{}",
+                    o.id, o.mode, r.x, r.y, r.width, r.height
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some("clear") => {
+            overrides.clear(config);
+            "Light overrides cleared.".to_string()
+        }
+        _ => usage.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    /// Mode of the last override covering tile (`tx`, `ty`).
+    fn mode_at(
+        overrides: &LightOverrides,
+        tx: i32,
+        ty: i32,
+        config: &ActiveWorld,
+    ) -> Option<LightOverrideMode> {
+        overrides
+            .overrides
+            .iter()
+            .rev()
+            .find(|o| o.rect.contains(tx, ty, config))
+            .map(|o| o.mode)
+    }
+
+    #[test]
+    fn modes_apply_per_channel() {
+        let mut texel = [0.9, 0.3, 1.4, 1.0];
+        LightOverrideMode::Clamp([0.5, 0.5, 0.0]).apply(&mut texel);
+        assert_eq!(texel, [0.5, 0.3, 0.0, 1.0]);
+
+        let mut texel = [0.9, 0.3, 1.4, 1.0];
+        LightOverrideMode::Dark.apply(&mut texel);
+        assert_eq!(texel, [0.0; 4]);
+
+        let mut texel = [0.0; 4];
+        LightOverrideMode::Fill([0.2, 0.1, 0.4]).apply(&mut texel);
+        assert_eq!(texel, [0.2, 0.1, 0.4, 1.0]);
+    }
+
+    #[test]
+    fn rect_wraps_across_the_seam() {
+        let config = fixtures::test_active_world();
+        let w = config.width_tiles;
+        let cs = config.chunk_size as i32;
+        let last_cx = config.width_chunks() - 1;
+        let rect = TileRect::new(w - 4, 10, 8, 4);
+
+        assert!(rect.contains(w - 1, 10, &config));
+        assert!(rect.contains(3, 13, &config));
+        // The same tile seen from the next copy of the world.
+        assert!(rect.contains(w + 3, 12, &config));
+        assert!(!rect.contains(4, 10, &config));
+        assert!(!rect.contains(w - 5, 10, &config));
+        assert!(!rect.contains(0, 14, &config));
+
+        assert_eq!(rect.chunks(&config), [(last_cx, 0), (0, 0)]);
+
+        // Taller than a chunk: every row of chunks it touches.
+        let tall = TileRect::new(cs - 1, cs - 1, 2, cs + 2);
+        assert_eq!(
+            tall.chunks(&config),
+            [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn removing_an_override_relights_its_chunks() {
+        let config = fixtures::test_active_world();
+        let mut overrides = LightOverrides::default();
+        let rect = TileRect::new(config.width_tiles - 2, 0, 4, 4);
+        let id = overrides.set_light_override(rect, LightOverrideMode::Dark, &config);
+        assert_eq!(
+            mode_at(&overrides, 1, 1, &config),
+            Some(LightOverrideMode::Dark)
+        );
+
+        let mut app = App::new();
+        app.insert_resource(overrides)
+            .init_resource::<RcGridDirty>()
+            .add_systems(Update, relight_overridden_chunks);
+        app.update();
+        assert!(app.world().resource::<RcGridDirty>().0);
+        assert!(app.world().resource::<LightOverrides>().relight.is_empty());
+
+        app.world_mut().resource_mut::<RcGridDirty>().0 = false;
+        let mut overrides = app.world_mut().resource_mut::<LightOverrides>();
+        assert!(overrides.remove(id, &config).is_some());
+        let last_cx = config.width_chunks() - 1;
+        assert_eq!(overrides.relight, [(last_cx, 0), (0, 0)]);
+        assert_eq!(mode_at(&overrides, 1, 1, &config), None);
+        app.update();
+        assert!(app.world().resource::<RcGridDirty>().0);
+    }
+
+    #[test]
+    fn commands_add_list_and_remove_overrides() {
+        let config = fixtures::test_active_world();
+        let mut overrides = LightOverrides::default();
+        assert_eq!(
+            run_light_command(&mut overrides, &args("dark 10 20 5 5"), &config),
+            "Light override 0 added."
+        );
+        assert_eq!(
+            run_light_command(&mut overrides, &args("clamp 0 0 2 2 0.1 0.2 0.3"), &config),
+            "Light override 1 added."
+        );
+        assert_eq!(
+            mode_at(&overrides, 1, 1, &config),
+            Some(LightOverrideMode::Clamp([0.1, 0.2, 0.3]))
+        );
+        assert!(
+            run_light_command(&mut overrides, &args("fill 0 0 2 2"), &config).starts_with("Usage")
+        );
+        assert_eq!(
+            run_light_command(&mut overrides, &args("list"), &config)
+                .lines()
+                .count(),
+            2
+        );
+        assert_eq!(
+            run_light_command(&mut overrides, &args("remove 0"), &config),
+            "Light override 0 removed."
+        );
+        assert_eq!(
+            run_light_command(&mut overrides, &args("remove 0"), &config),
+            "No light override 0."
+        );
+        run_light_command(&mut overrides, &args("clear"), &config);
+        assert!(overrides.is_empty());
+    }
+
+    #[test]
+    fn overrides_survive_a_round_trip() {
+        let config = fixtures::test_active_world();
+        let mut overrides = LightOverrides::default();
+        overrides.set_light_override(
+            TileRect::new(1, 2, 3, 4),
+            LightOverrideMode::Fill([0.5; 3]),
+            &config,
+        );
+        let text = ron::to_string(&overrides).unwrap();
+        let back: LightOverrides = ron::from_str(&text).unwrap();
+        assert_eq!(
+            back.iter().collect::<Vec<_>>(),
+            overrides.iter().collect::<Vec<_>>()
+        );
+        assert!(back.relight.is_empty());
+    }
+}
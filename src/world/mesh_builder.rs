@@ -1,18 +1,48 @@
 use bevy::asset::RenderAssetUsages;
-use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
 use bevy::prelude::*;
 
 use super::atlas::{atlas_uv, AtlasParams};
-use super::autotile::{select_variant, AutotileRegistry, CHUNK_TILE_COUNT};
-use crate::registry::tile::{TileId, TileRegistry};
-use crate::world::chunk::Layer;
+use super::autotile::{
+    position_hash, select_variant, AutotileRegistry, BIT_E, BIT_N, BIT_NE, BIT_NW, BIT_S, BIT_SE,
+    BIT_SW, BIT_W, CHUNK_TILE_COUNT,
+};
+use super::tile_renderer::{ATTRIBUTE_ANIM, ATTRIBUTE_SWAY, ATTRIBUTE_TINT};
+use crate::registry::tile::{TileId, TileOrientation, TileRegistry};
+use crate::world::chunk::{ChunkDirty, ChunkLayer, Layer};
+
+/// Tiles of one chunk layer that were drawn with a fallback sprite because
+/// their autotile data was degenerate. Attached to chunk layer entities.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeshDiagnostics {
+    /// Bitmask not mapped by the autotile; drawn with its fallback mask.
+    pub unmapped_bitmask: u32,
+    /// Autotile had no variants at all; drawn with row 0.
+    pub empty_variants: u32,
+    /// Tile names an autotile that isn't loaded; drawn from atlas column 0.
+    pub missing_autotile: u32,
+    /// Bitmask data shorter than the tile data; treated as isolated (mask 0).
+    pub missing_bitmask: u32,
+}
+
+impl MeshDiagnostics {
+    /// Total fallbacks taken.
+    pub fn total(&self) -> u32 {
+        self.unmapped_bitmask + self.empty_variants + self.missing_autotile + self.missing_bitmask
+    }
+}
 
 /// Reusable buffers for building chunk meshes, avoiding per-frame allocations.
 #[derive(Resource)]
 pub struct MeshBuildBuffers {
     pub positions: Vec<[f32; 3]>,
     pub uvs: Vec<[f32; 2]>,
+    pub sway: Vec<f32>,
+    pub tints: Vec<[f32; 3]>,
+    pub anim: Vec<[f32; 3]>,
     pub indices: Vec<u32>,
+    /// Fallbacks taken by the last [`build_chunk_mesh`] call.
+    pub diagnostics: MeshDiagnostics,
 }
 
 impl Default for MeshBuildBuffers {
@@ -20,25 +50,139 @@ impl Default for MeshBuildBuffers {
         Self {
             positions: Vec::with_capacity(CHUNK_TILE_COUNT * 4),
             uvs: Vec::with_capacity(CHUNK_TILE_COUNT * 4),
+            sway: Vec::with_capacity(CHUNK_TILE_COUNT * 4),
+            tints: Vec::with_capacity(CHUNK_TILE_COUNT * 4),
+            anim: Vec::with_capacity(CHUNK_TILE_COUNT * 4),
             indices: Vec::with_capacity(CHUNK_TILE_COUNT * 6),
+            diagnostics: MeshDiagnostics::default(),
         }
     }
 }
 
+/// Darkest brightness a full-strength variation reaches (1.0 - this).
+const VARIATION_MAX_DARKEN: f32 = 0.08;
+/// Largest warm/cool shift of a full-strength variation, per channel.
+const VARIATION_MAX_HUE_SHIFT: f32 = 0.02;
+/// Background tiles vary a bit more so the wall layer reads as further away.
+const BG_VARIATION_SCALE: f32 = 1.25;
+/// Hash layers for the variation, kept apart from variant selection (0/1) so
+/// tint does not correlate with the chosen sprite.
+const VARIATION_HASH_LAYER: u32 = 2;
+
+/// RGB multiplier for a tile from two hashes in [0, 1] and the variation
+/// strength. Brightness drops by up to `VARIATION_MAX_DARKEN * strength` and
+/// red/blue shift in opposite directions by up to
+/// `VARIATION_MAX_HUE_SHIFT * strength`; no channel exceeds 1.0.
+pub fn variation_tint(brightness_hash: f32, hue_hash: f32, strength: f32) -> [f32; 3] {
+    if strength <= 0.0 {
+        return [1.0; 3];
+    }
+    let brightness = 1.0 - VARIATION_MAX_DARKEN * strength * brightness_hash;
+    let shift = VARIATION_MAX_HUE_SHIFT * strength * (hue_hash * 2.0 - 1.0);
+    [
+        (brightness * (1.0 + shift)).min(1.0),
+        brightness,
+        (brightness * (1.0 - shift)).min(1.0),
+    ]
+}
+
+/// Ambient occlusion on foreground tile corners, baked into the vertex tint
+/// and multiplied with the lightmap by the tile shader.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AmbientOcclusion {
+    /// 0.0 disables it; 1.0 darkens a fully enclosed corner by
+    /// `AO_MAX_DARKEN`.
+    pub strength: f32,
+}
+
+impl Default for AmbientOcclusion {
+    fn default() -> Self {
+        Self { strength: 0.5 }
+    }
+}
+
+/// Darkest a corner gets at full strength (1.0 - this).
+const AO_MAX_DARKEN: f32 = 0.35;
+
+/// Brightness of a tile's four corners (bottom-left, bottom-right, top-right,
+/// top-left, the quad's vertex order) from its autotile `bitmask`. Each
+/// corner darkens with the number of solid tiles among the three others
+/// sharing it, so inner corners sit darker than exposed faces.
+pub fn corner_occlusion(bitmask: u8, strength: f32) -> [f32; 4] {
+    if strength <= 0.0 {
+        return [1.0; 4];
+    }
+    // (side, side, diagonal) neighbour bits around each corner.
+    const CORNERS: [[u8; 3]; 4] = [
+        [BIT_W, BIT_S, BIT_SW],
+        [BIT_E, BIT_S, BIT_SE],
+        [BIT_E, BIT_N, BIT_NE],
+        [BIT_W, BIT_N, BIT_NW],
+    ];
+    CORNERS.map(|bits| {
+        let solid = bits.iter().filter(|&&bit| bitmask & bit != 0).count();
+        1.0 - AO_MAX_DARKEN * strength * solid as f32 / 3.0
+    })
+}
+
+/// Rebuild every chunk mesh when the occlusion strength changes.
+pub fn rebuild_on_occlusion_change(
+    mut commands: Commands,
+    occlusion: Res<AmbientOcclusion>,
+    layers: Query<Entity, With<ChunkLayer>>,
+) {
+    if !occlusion.is_changed() || occlusion.is_added() {
+        return;
+    }
+    for entity in &layers {
+        commands.entity(entity).insert(ChunkDirty);
+    }
+}
+
+/// Tint of the tile at `(world_x, world_y)`, deterministic in position, seed
+/// and layer so rebuilding a chunk reproduces the same pattern.
+fn tile_tint(world_x: i32, world_y: i32, seed: u32, layer: Layer, variation: f32) -> [f32; 3] {
+    if variation <= 0.0 {
+        return [1.0; 3];
+    }
+    let (salt, strength) = match layer {
+        Layer::Fg => (0, variation),
+        Layer::Bg => (2, variation * BG_VARIATION_SCALE),
+    };
+    let brightness_hash = position_hash(world_x, world_y, seed, VARIATION_HASH_LAYER + salt);
+    let hue_hash = position_hash(world_x, world_y, seed, VARIATION_HASH_LAYER + salt + 1);
+    variation_tint(brightness_hash, hue_hash, strength)
+}
+
 /// Build a Bevy `Mesh` for a single chunk from its tile and bitmask data.
 ///
 /// Each non-air tile becomes a textured quad. The mesh uses the combined atlas
 /// for UV coordinates, selecting the correct autotile variant per tile.
+/// Tiles flagged `sway` get a weight of 1.0 on their top vertices in
+/// `ATTRIBUTE_SWAY`; the tile shader animates those at no rebuild cost.
+/// Each tile's colour variation goes into `ATTRIBUTE_TINT`; on the fg layer
+/// it is scaled per vertex by [`corner_occlusion`] at `occlusion` strength.
+/// Animated autotiles write their frame cycle into `ATTRIBUTE_ANIM` and the
+/// shader steps through the frame columns, so they never rebuild to animate.
+/// Orientable tiles draw the autotile's variants for the orientation in
+/// their `states` byte; pass an empty slice for layers without state.
+///
+/// Degenerate autotile data never drops a tile: unmapped bitmasks use their
+/// fallback mask, empty variant lists row 0, missing autotiles atlas column
+/// 0, and missing bitmasks mask 0. Each fallback is counted in
+/// `buffers.diagnostics`.
 #[allow(clippy::too_many_arguments)]
 pub fn build_chunk_mesh(
     tiles: &[TileId],
     bitmasks: &[u8],
+    states: &[u8],
     display_chunk_x: i32,
     chunk_y: i32,
     chunk_size: u32,
     tile_size: f32,
     seed: u32,
     layer: Layer,
+    occlusion: f32,
     tile_registry: &TileRegistry,
     autotile_registry: &AutotileRegistry,
     atlas_params: &AtlasParams,
@@ -46,7 +190,11 @@ pub fn build_chunk_mesh(
 ) -> Mesh {
     buffers.positions.clear();
     buffers.uvs.clear();
+    buffers.sway.clear();
+    buffers.tints.clear();
+    buffers.anim.clear();
     buffers.indices.clear();
+    buffers.diagnostics = MeshDiagnostics::default();
 
     let base_x = display_chunk_x * chunk_size as i32;
     let base_y = chunk_y * chunk_size as i32;
@@ -65,13 +213,11 @@ pub fn build_chunk_mesh(
                 None => continue,
             };
 
-            let entry = match autotile_registry.get(autotile_name) {
-                Some(e) => e,
-                None => continue,
-            };
-
-            let bitmask = bitmasks[idx];
-            let variants = entry.variants_for(bitmask);
+            let diagnostics = &mut buffers.diagnostics;
+            let bitmask = bitmasks.get(idx).copied().unwrap_or_else(|| {
+                diagnostics.missing_bitmask += 1;
+                0
+            });
 
             let world_x = base_x + local_x as i32;
             let world_y = base_y + local_y as i32;
@@ -79,13 +225,33 @@ pub fn build_chunk_mesh(
                 Layer::Fg => 0,
                 Layer::Bg => 1,
             };
-            let sprite_row = select_variant(variants, world_x, world_y, seed, layer_val);
+            let def = tile_registry.get(tile_id);
+            let (column, sprite_row, animation) = match autotile_registry.get(autotile_name) {
+                Some(entry) => {
+                    let variants = if def.orientable {
+                        let state = states.get(idx).copied().unwrap_or(0);
+                        entry.oriented_variants(bitmask, TileOrientation::from_state(state))
+                    } else {
+                        entry.variants_for(bitmask)
+                    };
+                    if variants.is_empty() {
+                        diagnostics.empty_variants += 1;
+                    } else if !entry.is_mapped(bitmask) {
+                        diagnostics.unmapped_bitmask += 1;
+                    }
+                    let row = select_variant(variants, world_x, world_y, seed, layer_val);
+                    (entry.column_index, row, entry.animation)
+                }
+                None => {
+                    diagnostics.missing_autotile += 1;
+                    (0, 0, None)
+                }
+            };
 
             let px = world_x as f32 * tile_size;
             let py = world_y as f32 * tile_size;
 
-            let (u_min, u_max, v_min, v_max) =
-                atlas_uv(entry.column_index, sprite_row, atlas_params);
+            let (u_min, u_max, v_min, v_max) = atlas_uv(column, sprite_row, atlas_params);
 
             let vi = buffers.positions.len() as u32;
 
@@ -103,6 +269,26 @@ pub fn build_chunk_mesh(
                 [u_min, v_min],
             ]);
 
+            let tint = tile_tint(world_x, world_y, seed, layer, def.variation);
+            let corners = match layer {
+                Layer::Fg => corner_occlusion(bitmask, occlusion),
+                Layer::Bg => [1.0; 4],
+            };
+            buffers.tints.extend(corners.map(|ao| tint.map(|c| c * ao)));
+
+            // Vertices 2 and 3 form the top edge; the bottom stays anchored.
+            let sway = if def.sway { 1.0 } else { 0.0 };
+            buffers.sway.extend_from_slice(&[0.0, 0.0, sway, sway]);
+
+            let anim = animation.map_or([1.0, 0.0, 0.0], |animation| {
+                [
+                    animation.frames as f32,
+                    animation.frame_secs,
+                    atlas_params.tile_size as f32 / atlas_params.atlas_width as f32,
+                ]
+            });
+            buffers.anim.extend_from_slice(&[anim; 4]);
+
             buffers
                 .indices
                 .extend_from_slice(&[vi, vi + 1, vi + 2, vi, vi + 2, vi + 3]);
@@ -117,17 +303,30 @@ pub fn build_chunk_mesh(
     // (~120KB for a full 32×32 chunk) and within frame budget.
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, buffers.positions.clone());
     mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, buffers.uvs.clone());
+    mesh.insert_attribute(ATTRIBUTE_SWAY, buffers.sway.clone());
+    mesh.insert_attribute(ATTRIBUTE_TINT, buffers.tints.clone());
+    mesh.insert_attribute(ATTRIBUTE_ANIM, buffers.anim.clone());
     mesh.insert_indices(Indices::U32(buffers.indices.clone()));
     mesh
 }
 
+/// Scale the tint of every tile in a mesh from [`build_chunk_mesh`] by
+/// `factor`, e.g. to darken a chunk that is still being revealed.
+pub fn scale_tints(mesh: &mut Mesh, factor: f32) {
+    if let Some(VertexAttributeValues::Float32x3(tints)) = mesh.attribute_mut(ATTRIBUTE_TINT) {
+        for tint in tints {
+            *tint = tint.map(|c| c * factor);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::registry::assets::{AutotileAsset, BitmaskMapping, SpriteVariant};
-    use crate::registry::tile::{TileDef, TileRegistry};
+    use crate::registry::assets::{AutotileAsset, BitmaskMapping, SpriteVariant, TileAnimation};
+    use crate::registry::tile::{TileDef, TileMaterial, TileRegistry};
     use crate::world::atlas::AtlasParams;
-    use crate::world::autotile::{AutotileEntry, AutotileRegistry};
+    use crate::world::autotile::{compute_bitmask, AutotileEntry, AutotileRegistry};
     use std::collections::HashMap;
 
     fn test_registry() -> TileRegistry {
@@ -147,7 +346,19 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
             TileDef {
                 id: "dirt".into(),
@@ -164,7 +375,48 @@ mod tests {
                 flicker_speed: 0.0,
                 flicker_strength: 0.0,
                 flicker_min: 1.0,
+                switchable: false,
+                sway: false,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
+                drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
+            },
+            TileDef {
+                id: "tall_grass".into(),
+                autotile: Some("dirt".into()),
+                solid: false,
+                hardness: 0.0,
+                friction: 0.0,
+                viscosity: 0.0,
+                damage_on_contact: 0.0,
+                effects: vec![],
+                light_emission: [0, 0, 0],
+                light_opacity: 0,
+                albedo: [60, 160, 60],
+                flicker_speed: 0.0,
+                flicker_strength: 0.0,
+                flicker_min: 1.0,
+                switchable: false,
+                sway: true,
+                sign: false,
+                variation: 0.0,
+                additive_light: false,
                 drops: vec![],
+                light_filter: [255, 255, 255],
+                light_cone: None,
+                growth: None,
+                material: TileMaterial::Stone,
+                climbable: false,
+                hanging: false,
+                orientable: false,
             },
         ])
     }
@@ -188,6 +440,10 @@ mod tests {
             atlas_columns: 1,
             atlas_rows: 47,
             tiles,
+            fallback: Default::default(),
+            uv_inset: 0.5,
+            orientations: HashMap::new(),
+            animation: None,
         };
         let mut reg = AutotileRegistry::default();
         reg.insert("dirt".into(), AutotileEntry::from_asset(&asset, 0));
@@ -203,11 +459,16 @@ mod tests {
             rows: 47,
             atlas_width: 16,
             atlas_height: 752,
+            uv_inset: 0.5,
         };
         let mut buffers = MeshBuildBuffers {
             positions: Vec::new(),
             uvs: Vec::new(),
+            sway: Vec::new(),
+            tints: Vec::new(),
+            anim: Vec::new(),
             indices: Vec::new(),
+            diagnostics: MeshDiagnostics::default(),
         };
 
         // 2×2 chunk: [dirt, air, air, dirt]
@@ -219,12 +480,14 @@ mod tests {
         let mesh = build_chunk_mesh(
             &tiles,
             &bitmasks,
+            &[],
             0,
             0,
             chunk_size,
             tile_size,
             42,
             Layer::Fg,
+            0.0,
             &tile_reg,
             &autotile_reg,
             &params,
@@ -257,6 +520,7 @@ mod tests {
         // Mesh should have attributes set
         assert!(mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_some());
         assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some());
+        assert!(mesh.attribute(ATTRIBUTE_SWAY).is_some());
         assert!(mesh.indices().is_some());
     }
 
@@ -269,11 +533,16 @@ mod tests {
             rows: 47,
             atlas_width: 16,
             atlas_height: 752,
+            uv_inset: 0.5,
         };
         let mut buffers = MeshBuildBuffers {
             positions: Vec::new(),
             uvs: Vec::new(),
+            sway: Vec::new(),
+            tints: Vec::new(),
+            anim: Vec::new(),
             indices: Vec::new(),
+            diagnostics: MeshDiagnostics::default(),
         };
 
         let tiles = vec![TileId::AIR; 4];
@@ -282,12 +551,14 @@ mod tests {
         build_chunk_mesh(
             &tiles,
             &bitmasks,
+            &[],
             0,
             0,
             2,
             8.0,
             42,
             Layer::Fg,
+            0.0,
             &tile_reg,
             &autotile_reg,
             &params,
@@ -297,4 +568,490 @@ mod tests {
         assert_eq!(buffers.positions.len(), 0, "all air = no vertices");
         assert_eq!(buffers.indices.len(), 0, "all air = no indices");
     }
+
+    #[test]
+    fn sway_weights_only_on_top_of_flagged_tiles() {
+        let tile_reg = test_registry();
+        let autotile_reg = test_autotile_registry();
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let mut buffers = MeshBuildBuffers::default();
+
+        // 2×1 chunk: [dirt, tall_grass]
+        let tiles = vec![TileId(1), TileId(2), TileId::AIR, TileId::AIR];
+        let bitmasks = vec![0u8; 4];
+
+        build_chunk_mesh(
+            &tiles,
+            &bitmasks,
+            &[],
+            0,
+            0,
+            2,
+            8.0,
+            42,
+            Layer::Fg,
+            0.0,
+            &tile_reg,
+            &autotile_reg,
+            &params,
+            &mut buffers,
+        );
+
+        assert_eq!(buffers.sway.len(), buffers.positions.len());
+        assert_eq!(&buffers.sway[0..4], &[0.0; 4], "unflagged tile never sways");
+        assert_eq!(&buffers.sway[4..8], &[0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn sway_weight_follows_vertex_height() {
+        let tile_reg = test_registry();
+        let autotile_reg = test_autotile_registry();
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let mut buffers = MeshBuildBuffers::default();
+
+        let tiles = vec![TileId(2); 4];
+        let bitmasks = vec![0u8; 4];
+
+        build_chunk_mesh(
+            &tiles,
+            &bitmasks,
+            &[],
+            3,
+            -2,
+            2,
+            8.0,
+            42,
+            Layer::Bg,
+            0.0,
+            &tile_reg,
+            &autotile_reg,
+            &params,
+            &mut buffers,
+        );
+
+        // Within each quad, weighted vertices sit on the top edge and
+        // unweighted ones on the bottom edge.
+        for (quad, weights) in buffers.positions.chunks(4).zip(buffers.sway.chunks(4)) {
+            let bottom = quad.iter().map(|p| p[1]).fold(f32::INFINITY, f32::min);
+            let top = quad.iter().map(|p| p[1]).fold(f32::NEG_INFINITY, f32::max);
+            for (pos, &w) in quad.iter().zip(weights) {
+                let expected_y = if w > 0.0 { top } else { bottom };
+                assert_eq!(pos[1], expected_y, "vertex {pos:?} has weight {w}");
+            }
+        }
+    }
+
+    fn build_dirt_chunk(tile_reg: &TileRegistry, layer: Layer, buffers: &mut MeshBuildBuffers) {
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        build_chunk_mesh(
+            &[TileId(1); 16],
+            &[0u8; 16],
+            &[],
+            5,
+            7,
+            4,
+            8.0,
+            42,
+            layer,
+            0.0,
+            tile_reg,
+            &test_autotile_registry(),
+            &params,
+            buffers,
+        );
+    }
+
+    fn varied_registry() -> TileRegistry {
+        let base = test_registry();
+        let mut defs: Vec<TileDef> = (0..3).map(|i| base.get(TileId(i)).clone()).collect();
+        defs[1].variation = 1.0;
+        TileRegistry::from_defs(defs)
+    }
+
+    #[test]
+    fn variation_tint_stays_within_range() {
+        let low = 1.0 - VARIATION_MAX_DARKEN - VARIATION_MAX_HUE_SHIFT;
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let (b, h) = (i as f32 / 10.0, j as f32 / 10.0);
+                for strength in [0.25, 0.5, 1.0] {
+                    let tint = variation_tint(b, h, strength);
+                    for c in tint {
+                        assert!(
+                            (low..=1.0).contains(&c),
+                            "{tint:?} for ({b}, {h}, {strength})"
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(variation_tint(0.0, 0.5, 1.0), [1.0; 3]);
+        assert_eq!(variation_tint(1.0, 0.5, 1.0)[1], 1.0 - VARIATION_MAX_DARKEN);
+    }
+
+    #[test]
+    fn tints_are_identical_across_rebuilds() {
+        let tile_reg = varied_registry();
+        let mut buffers = MeshBuildBuffers::default();
+        build_dirt_chunk(&tile_reg, Layer::Fg, &mut buffers);
+        let first = buffers.tints.clone();
+        build_dirt_chunk(&tile_reg, Layer::Fg, &mut buffers);
+
+        assert_eq!(first.len(), buffers.positions.len());
+        assert_eq!(first, buffers.tints);
+        // All four vertices of a quad share its tint; tiles differ.
+        assert!(first
+            .chunks(4)
+            .all(|quad| quad.iter().all(|t| *t == quad[0])));
+        assert!(
+            first.iter().any(|t| *t != first[0]),
+            "variation produced a flat chunk"
+        );
+
+        build_dirt_chunk(&tile_reg, Layer::Bg, &mut buffers);
+        assert_ne!(first, buffers.tints, "bg uses its own pattern");
+    }
+
+    #[test]
+    fn zero_variation_leaves_tint_neutral() {
+        let tile_reg = test_registry();
+        let mut buffers = MeshBuildBuffers::default();
+        for layer in [Layer::Fg, Layer::Bg] {
+            build_dirt_chunk(&tile_reg, layer, &mut buffers);
+            assert_eq!(buffers.tints.len(), 64);
+            assert!(buffers.tints.iter().all(|t| *t == [1.0; 3]));
+        }
+    }
+
+    /// Atlas row each quad of a 2×2 all-dirt chunk is drawn from, with the
+    /// tiles' state bytes holding the four orientations in order.
+    fn oriented_rows(tile_reg: &TileRegistry) -> Vec<u32> {
+        let variant = |row| SpriteVariant {
+            row,
+            weight: 1.0,
+            col: 0,
+            index: 0,
+        };
+        let asset = AutotileAsset {
+            tile_size: 16,
+            atlas_columns: 1,
+            atlas_rows: 47,
+            tiles: HashMap::from([(
+                0u8,
+                BitmaskMapping {
+                    description: "isolated".into(),
+                    variants: vec![variant(0)],
+                },
+            )]),
+            fallback: Default::default(),
+            uv_inset: 0.5,
+            orientations: HashMap::from([
+                (TileOrientation::LeftWall, vec![variant(10)]),
+                (TileOrientation::Ceiling, vec![variant(12)]),
+            ]),
+            animation: None,
+        };
+        let mut autotile_reg = AutotileRegistry::default();
+        autotile_reg.insert("dirt".into(), AutotileEntry::from_asset(&asset, 0));
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let states = TileOrientation::ALL.map(|o| o.apply(0));
+        let mut buffers = MeshBuildBuffers::default();
+        build_chunk_mesh(
+            &[TileId(1); 4],
+            &[0u8; 4],
+            &states,
+            0,
+            0,
+            2,
+            8.0,
+            42,
+            Layer::Fg,
+            0.0,
+            tile_reg,
+            &autotile_reg,
+            &params,
+            &mut buffers,
+        );
+        // The top-left vertex of each quad carries (u_min, v_min).
+        buffers
+            .uvs
+            .chunks(4)
+            .map(|quad| {
+                (0..47)
+                    .find(|&row| atlas_uv(0, row, &params).2 == quad[3][1])
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn orientable_tiles_draw_their_orientation_variants() {
+        let mut defs = test_registry().defs;
+        defs[1].orientable = true;
+        let rows = oriented_rows(&TileRegistry::from_defs(defs));
+        // Floor and RightWall have no variants of their own: bitmask row.
+        assert_eq!(rows, vec![0, 10, 0, 12]);
+    }
+
+    #[test]
+    fn plain_tiles_ignore_orientation_bits() {
+        assert_eq!(oriented_rows(&test_registry()), vec![0; 4]);
+    }
+
+    /// Build a 2×2 chunk and return the fallbacks taken and the quad count.
+    fn build_with(
+        tiles: &[TileId],
+        bitmasks: &[u8],
+        autotile_reg: &AutotileRegistry,
+    ) -> (MeshDiagnostics, usize) {
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let mut buffers = MeshBuildBuffers::default();
+        build_chunk_mesh(
+            tiles,
+            bitmasks,
+            &[],
+            0,
+            0,
+            2,
+            8.0,
+            42,
+            Layer::Fg,
+            0.0,
+            &test_registry(),
+            autotile_reg,
+            &params,
+            &mut buffers,
+        );
+        (buffers.diagnostics, buffers.positions.len() / 4)
+    }
+
+    fn empty_autotile_registry() -> AutotileRegistry {
+        let mut tiles = HashMap::new();
+        tiles.insert(
+            0u8,
+            BitmaskMapping {
+                description: "isolated".into(),
+                variants: vec![],
+            },
+        );
+        let asset = AutotileAsset {
+            tile_size: 16,
+            atlas_columns: 1,
+            atlas_rows: 47,
+            tiles,
+            fallback: Default::default(),
+            uv_inset: 0.5,
+            orientations: HashMap::new(),
+            animation: None,
+        };
+        let mut reg = AutotileRegistry::default();
+        reg.insert("dirt".into(), AutotileEntry::from_asset(&asset, 0));
+        reg
+    }
+
+    #[test]
+    fn well_formed_data_takes_no_fallbacks() {
+        let (diag, quads) = build_with(&[TileId(1); 4], &[0; 4], &test_autotile_registry());
+        assert_eq!(diag, MeshDiagnostics::default());
+        assert_eq!(quads, 4);
+    }
+
+    #[test]
+    fn unmapped_bitmask_draws_fallback_mask() {
+        // Only mask 0 is mapped; N+E (5) falls back to it.
+        let (diag, quads) = build_with(&[TileId(1); 4], &[5, 5, 0, 0], &test_autotile_registry());
+        assert_eq!(diag.unmapped_bitmask, 2);
+        assert_eq!(diag.total(), 2);
+        assert_eq!(quads, 4);
+    }
+
+    #[test]
+    fn empty_variants_draw_row_zero() {
+        let (diag, quads) = build_with(&[TileId(1); 4], &[0; 4], &empty_autotile_registry());
+        assert_eq!(diag.empty_variants, 4);
+        assert_eq!(diag.unmapped_bitmask, 0);
+        assert_eq!(quads, 4);
+    }
+
+    #[test]
+    fn missing_autotile_still_draws_the_tile() {
+        let tiles = [TileId(1), TileId::AIR, TileId(2), TileId::AIR];
+        let (diag, quads) = build_with(&tiles, &[0; 4], &AutotileRegistry::default());
+        assert_eq!(diag.missing_autotile, 2);
+        assert_eq!(quads, 2, "no holes for tiles with a missing autotile");
+    }
+
+    #[test]
+    fn short_bitmask_data_is_treated_as_isolated() {
+        let (diag, quads) = build_with(&[TileId(1); 4], &[0, 0], &test_autotile_registry());
+        assert_eq!(diag.missing_bitmask, 2);
+        assert_eq!(diag.unmapped_bitmask, 0, "mask 0 is mapped");
+        assert_eq!(quads, 4);
+    }
+
+    #[test]
+    fn corner_occlusion_counts_solids_around_each_corner() {
+        assert_eq!(corner_occlusion(0, 1.0), [1.0; 4]);
+        assert_eq!(corner_occlusion(0xFF, 0.0), [1.0; 4]);
+        assert_eq!(corner_occlusion(0xFF, 1.0), [1.0 - AO_MAX_DARKEN; 4]);
+
+        // Solid to the west only: the two left corners each see one solid.
+        let [bl, br, tr, tl] = corner_occlusion(BIT_W, 1.0);
+        assert_eq!((br, tr), (1.0, 1.0));
+        assert_eq!(bl, tl);
+        assert!(bl < 1.0);
+    }
+
+    #[test]
+    fn enclosed_corner_is_darker_than_an_exposed_face() {
+        // 3×3 chunk of dirt with the top-right tile dug out:
+        //   D D .
+        //   D D D
+        //   D D D
+        let solid = |x: i32, y: i32| (0..3).contains(&x) && (0..3).contains(&y) && (x, y) != (2, 2);
+        let tiles: Vec<TileId> = (0..9)
+            .map(|i| {
+                if solid(i % 3, i / 3) {
+                    TileId(1)
+                } else {
+                    TileId::AIR
+                }
+            })
+            .collect();
+        let bitmasks: Vec<u8> = (0..9)
+            .map(|i| compute_bitmask(solid, i % 3, i / 3))
+            .collect();
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 16,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let mut buffers = MeshBuildBuffers::default();
+        build_chunk_mesh(
+            &tiles,
+            &bitmasks,
+            &[],
+            0,
+            0,
+            3,
+            8.0,
+            42,
+            Layer::Fg,
+            1.0,
+            &test_registry(),
+            &test_autotile_registry(),
+            &params,
+            &mut buffers,
+        );
+
+        // Quads follow tile order; vertices go BL, BR, TR, TL.
+        let brightness = |quad: usize, vertex: usize| buffers.tints[quad * 4 + vertex][1];
+        // Centre tile's top-right corner: solid above and beside, air diagonal.
+        let inner_corner = brightness(4, 2);
+        // Top-middle tile's top-left corner: open sky above, one solid beside.
+        let exposed_face = brightness(7, 3);
+        // Centre tile's bottom-left corner is buried.
+        let buried = brightness(4, 0);
+        assert!(
+            inner_corner < exposed_face,
+            "{inner_corner} vs {exposed_face}"
+        );
+        assert!(buried < inner_corner);
+
+        // The bg layer is never occluded.
+        build_chunk_mesh(
+            &tiles,
+            &bitmasks,
+            &[],
+            0,
+            0,
+            3,
+            8.0,
+            42,
+            Layer::Bg,
+            1.0,
+            &test_registry(),
+            &test_autotile_registry(),
+            &params,
+            &mut buffers,
+        );
+        assert!(buffers.tints.iter().all(|t| *t == [1.0; 3]));
+    }
+
+    #[test]
+    fn animated_autotiles_carry_their_frame_cycle() {
+        let params = AtlasParams {
+            tile_size: 16,
+            rows: 47,
+            atlas_width: 64,
+            atlas_height: 752,
+            uv_inset: 0.5,
+        };
+        let build = |autotile_reg: &AutotileRegistry, buffers: &mut MeshBuildBuffers| {
+            build_chunk_mesh(
+                &[TileId(1); 4],
+                &[0; 4],
+                &[],
+                0,
+                0,
+                2,
+                8.0,
+                42,
+                Layer::Fg,
+                0.0,
+                &test_registry(),
+                autotile_reg,
+                &params,
+                buffers,
+            )
+        };
+        let mut buffers = MeshBuildBuffers::default();
+
+        let mut animated = test_autotile_registry();
+        animated.entries.get_mut("dirt").unwrap().animation = Some(TileAnimation {
+            frames: 4,
+            frame_secs: 0.5,
+        });
+        let mesh = build(&animated, &mut buffers);
+        assert!(mesh.attribute(ATTRIBUTE_ANIM).is_some());
+        assert_eq!(buffers.anim.len(), buffers.positions.len());
+        assert!(buffers.anim.iter().all(|a| *a == [4.0, 0.5, 0.25]));
+        // The mesh addresses frame 0; the shader shifts to the others.
+        assert!(buffers.uvs.iter().all(|uv| uv[0] < 0.25));
+
+        build(&test_autotile_registry(), &mut buffers);
+        assert!(buffers.anim.iter().all(|a| *a == [1.0, 0.0, 0.0]));
+    }
 }
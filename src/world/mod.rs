@@ -1,16 +1,39 @@
 pub mod atlas;
 pub mod autotile;
 pub mod biome_map;
+pub mod biome_override;
 pub mod chunk;
+pub mod chunk_reveal;
+#[allow(dead_code)] // groundwork for multiplayer; no network layer uses it yet
+pub mod chunk_snapshot;
 pub mod ctx;
+pub mod culling;
 pub mod day_night;
+pub mod exploration;
+pub mod grass_spread;
+pub mod growth;
+pub mod light_cone;
+pub mod light_override;
 pub mod lit_sprite;
 pub mod mesh_builder;
 pub mod rc_lighting;
 pub mod rc_pipeline;
+pub mod rc_sdf;
+pub mod reactions;
+pub mod regenerate;
+pub mod shader_reload;
+pub mod sign;
+pub mod spatial_index;
+pub mod spawn_point;
 pub mod surface_objects;
+pub mod surface_paths;
 pub mod terrain_gen;
 pub mod tile_renderer;
+pub mod world_hash;
+pub mod world_info;
+pub mod worldgen_preview;
+#[cfg(test)]
+mod worldgen_snapshot;
 
 use bevy::prelude::*;
 use bevy::sprite_render::Material2dPlugin;
@@ -19,8 +42,8 @@ use crate::cosmos::persistence::{self, DirtyChunks, Universe};
 use crate::cosmos::ship_hull;
 use crate::liquid::{LiquidFieldMaterial, LiquidMaterial};
 use crate::registry::AppState;
-use crate::sets::GameSet;
-use crate::world::chunk::{LoadedChunks, WorldMap};
+use crate::sets::{GameSet, WorldSet};
+use crate::world::chunk::{ChunkHibernation, ChunkPreload, LoadedChunks, RemeshDebounce, WorldMap};
 use crate::world::lit_sprite::LitSpriteMaterial;
 use crate::world::mesh_builder::MeshBuildBuffers;
 use crate::world::tile_renderer::TileMaterial;
@@ -36,19 +59,42 @@ impl Plugin for WorldPlugin {
             .add_plugins(rc_lighting::RcLightingPlugin)
             .init_resource::<WorldMap>()
             .init_resource::<LoadedChunks>()
+            .init_resource::<ChunkPreload>()
+            .init_resource::<RemeshDebounce>()
+            .init_resource::<ChunkHibernation>()
             .init_resource::<DirtyChunks>()
             .init_resource::<Universe>()
+            .init_resource::<persistence::UnloadedDroppedItems>()
             .init_resource::<MeshBuildBuffers>()
+            .init_resource::<growth::GrowthClock>()
+            .init_resource::<grass_spread::GrassSpread>()
+            .init_resource::<grass_spread::GrassSpreadClock>()
+            .init_resource::<spawn_point::SpawnPlatform>()
+            .init_resource::<exploration::FogOfWar>()
+            .init_resource::<chunk_reveal::LightReveal>()
+            .init_resource::<mesh_builder::AmbientOcclusion>()
+            .init_resource::<culling::CullingConfig>()
+            .init_resource::<culling::ChunkBands>()
+            .init_resource::<reactions::ReactionQueue>()
+            .init_resource::<reactions::ReactionScheduler>()
+            .init_resource::<spatial_index::SpatialIndex>()
+            .init_resource::<biome_override::BiomeOverrides>()
+            .init_resource::<day_night::SkyGradient>()
             .add_message::<day_night::DayPhaseChanged>()
-            .add_systems(OnEnter(AppState::LoadingBiomes), chunk::clear_stale_chunks)
+            .add_message::<chunk::TileChanged>()
+            .add_message::<chunk::TileBroken>()
+            .add_message::<reactions::ReactionSound>()
             .add_systems(
-                OnEnter(AppState::InGame),
-                lit_sprite::init_lit_sprite_resources,
+                OnEnter(AppState::LoadingBiomes),
+                (
+                    chunk::clear_stale_chunks,
+                    reactions::clear_pending_reactions,
+                    biome_override::clear_biome_overrides,
+                ),
             )
             .add_systems(
                 OnEnter(AppState::InGame),
-                persistence::respawn_saved_dropped_items
-                    .run_if(resource_exists::<persistence::PendingDroppedItems>),
+                lit_sprite::init_lit_sprite_resources,
             )
             .add_systems(
                 OnEnter(AppState::InGame),
@@ -56,15 +102,9 @@ impl Plugin for WorldPlugin {
             )
             .add_systems(
                 Update,
-                (chunk::chunk_loading_system, chunk::rebuild_dirty_chunks)
-                    .chain()
-                    .in_set(GameSet::WorldUpdate),
-            )
-            .add_systems(
-                Update,
-                day_night::tick_world_time
-                    .in_set(GameSet::WorldUpdate)
-                    .run_if(resource_exists::<day_night::WorldTime>),
+                exploration::update_unexplored_overlay
+                    .after(GameSet::Camera)
+                    .run_if(in_state(AppState::InGame)),
             )
             .add_systems(
                 Update,
@@ -73,5 +113,57 @@ impl Plugin for WorldPlugin {
                     .after(crate::parallax::transition::parallax_transition_system)
                     .run_if(resource_exists::<day_night::WorldTime>),
             );
+        add_update_systems(app);
+        regenerate::add_systems(app);
+        shader_reload::setup(app);
     }
 }
+
+/// World systems run each frame in [`GameSet::WorldUpdate`], by [`WorldSet`].
+pub(crate) fn add_update_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            chunk::chunk_loading_system,
+            persistence::capture_unloaded_dropped_items,
+            persistence::restore_loaded_dropped_items,
+            chunk_reveal::reveal_chunks,
+        )
+            .chain()
+            .in_set(WorldSet::Gen),
+    )
+    .add_systems(
+        Update,
+        (
+            growth::tick_growth,
+            grass_spread::tick_grass_spread,
+            culling::refresh_distance_bands,
+            exploration::reveal_around_player,
+            world_hash::handle_worldhash_command,
+            biome_override::handle_biome_command,
+            day_night::tick_world_time.run_if(resource_exists::<day_night::WorldTime>),
+            spatial_index::refresh_spatial_index,
+        )
+            .in_set(WorldSet::Sim),
+    )
+    .add_systems(
+        Update,
+        (
+            reactions::queue_tile_reactions,
+            reactions::run_tile_reactions,
+        )
+            .chain()
+            .in_set(WorldSet::Sim)
+            .run_if(resource_exists::<reactions::ReactionRules>),
+    )
+    .add_systems(
+        Update,
+        (
+            mesh_builder::rebuild_on_occlusion_change,
+            chunk::rebuild_dirty_chunks,
+            sign::sync_sign_labels,
+        )
+            .chain()
+            .in_set(WorldSet::RenderPrep),
+    );
+}
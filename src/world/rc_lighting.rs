@@ -1,26 +1,34 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
 use bevy::tasks::ComputeTaskPool;
 
+use crate::cosmos::persistence::DirtyChunks;
+use crate::liquid::registry::LiquidDef;
 use crate::object::definition::ObjectId;
 use crate::object::registry::ObjectRegistry;
-use crate::registry::tile::{TileId, TileRegistry};
+use crate::registry::tile::{LightCone, TileDef, TileId, TileRegistry, NO_LIGHT_FILTER};
+use crate::registry::world::ActiveWorld;
 use crate::registry::AppState;
-use crate::sets::GameSet;
-use crate::world::chunk::{world_to_tile, WorldMap};
+use crate::sets::{GameSet, WorldSet};
+use crate::weather::WeatherState;
+use crate::world::chunk::{world_to_tile, ChunkData, WorldMap};
 use crate::world::ctx::WorldCtx;
+use crate::world::day_night::{SkyGradient, SkyLight, WorldTime};
+use crate::world::light_cone::{cone_footprint, CONE_REACH};
+use crate::world::light_override::{self, LightOverrideMode, LightOverrides};
 use crate::world::lit_sprite::LitSpriteMaterial;
 use crate::world::rc_pipeline;
+use crate::world::rc_sdf::RcSdf;
 use crate::world::tile_renderer::{SharedTileMaterial, TileMaterial};
 
-/// Padding in tiles around the visible viewport for the RC input textures.
+/// Padding in texels around the visible viewport for the RC input textures.
 /// Must be >= interval_end of the highest useful cascade so that rays from
 /// viewport probes don't escape the grid. With 3 cascades the max ray
 /// distance is 4^3 = 64, so padding = 64 keeps all viewport rays in-bounds.
-const RC_PADDING_TILES: i32 = 64;
-
-/// Warm-white sun color used for sky emitters along the top row.
-const SUN_COLOR: [f32; 3] = [1.0, 0.98, 0.90];
+/// At the default [`RcResolutionScale`] a texel is one tile.
+const RC_PADDING_TEXELS: i32 = 64;
 
 /// HDR multiplier for tile-based point lights (torches, lava, etc.).
 /// Point sources occupy a single tile, so RC probe rays hit them from
@@ -28,34 +36,191 @@ const SUN_COLOR: [f32; 3] = [1.0, 0.98, 0.90];
 /// compensates for the small angular coverage so torches look bright.
 const POINT_LIGHT_BOOST: f32 = 4.0;
 
+/// Frames the desired RC input size must stay unchanged before the grid (and
+/// with it the GPU textures) is resized to it.
+const RC_RESIZE_SETTLE_FRAMES: u32 = 8;
+
+/// How touching point light emitters combine when seeding the emissive
+/// buffer.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PointLightMerge {
+    /// Each emitter seeds only its own light, so a cluster is no brighter
+    /// than its brightest member. Tiles with `additive_light` still add.
+    #[default]
+    Max,
+    /// Every tile and object emitter adds the light of the emitters touching
+    /// it, clamped to 255 per channel, so clusters glow brighter.
+    Additive,
+}
+
+impl PointLightMerge {
+    /// Whether an emitting tile of this kind accumulates its neighbours.
+    fn adds(self, def: &TileDef) -> bool {
+        self == PointLightMerge::Additive || def.additive_light
+    }
+}
+
+/// How liquids light their surroundings.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct LiquidGlow {
+    /// Multiplier on each liquid's own `light_emission` (lava); 0 turns
+    /// liquid light off.
+    pub emission_scale: f32,
+    /// Liquids without their own emission (water) glow with this fraction of
+    /// their colour; 0 keeps them dark.
+    pub tint: f32,
+    /// Minimum seconds between lighting grid rebuilds caused by flowing
+    /// liquid.
+    pub relight_interval: f32,
+}
+
+impl Default for LiquidGlow {
+    fn default() -> Self {
+        Self {
+            emission_scale: 1.0,
+            tint: 0.0,
+            relight_interval: 0.1,
+        }
+    }
+}
+
+impl LiquidGlow {
+    /// Light (0–1 per channel) of a full cell of `def`, before flicker.
+    fn colour(&self, def: &LiquidDef) -> [f32; 3] {
+        if def.light_emission != [0, 0, 0] {
+            def.light_emission
+                .map(|c| c as f32 / 255.0 * self.emission_scale)
+        } else {
+            [def.color[0], def.color[1], def.color[2]].map(|c| c * self.tint)
+        }
+    }
+}
+
+/// A cone emitter that isn't a tile, at a world tile position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConeLight {
+    pub tile: IVec2,
+    pub emission: [u8; 3],
+    pub cone: LightCone,
+}
+
+/// Cone emitters that aren't tiles (the player's headlamp). Their owners
+/// rebuild the list every frame before lighting is extracted.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ConeLights(pub Vec<ConeLight>);
+
+/// Lightmap texels per tile: below 1 lights a coarser grid (cheaper on weak
+/// GPUs), above 1 a finer one. Rounded to a power of two between
+/// [`MIN_RESOLUTION_SCALE`] and [`MAX_RESOLUTION_SCALE`].
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct RcResolutionScale(pub f32);
+
+/// Coarsest lightmap resolution: one texel per 4×4 tiles.
+pub const MIN_RESOLUTION_SCALE: f32 = 0.25;
+/// Finest lightmap resolution: 4×4 texels per tile.
+pub const MAX_RESOLUTION_SCALE: f32 = 4.0;
+
+impl Default for RcResolutionScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl RcResolutionScale {
+    /// The scale actually used: a power of two, so texel blocks line up
+    /// with tiles.
+    pub fn texels_per_tile(self) -> f32 {
+        let scale = self.0.clamp(MIN_RESOLUTION_SCALE, MAX_RESOLUTION_SCALE);
+        scale.log2().round().exp2()
+    }
+}
+
+/// Pending lighting grid rebuild requested by the liquid simulation.
+///
+/// Liquid can move every simulation tick, and each move changes the density
+/// grid; rebuilding it that often would stall large flows. Requests are held
+/// until `LiquidGlow::relight_interval` has passed since the last rebuild.
+/// Emission needs no rebuild: it is re-read from the chunks every frame.
+#[derive(Resource, Debug, Default)]
+pub struct LiquidRelight {
+    pending: bool,
+    since_rebuild: f32,
+}
+
+impl LiquidRelight {
+    /// Ask for a grid rebuild once the throttle allows it.
+    pub fn request(&mut self) {
+        self.pending = true;
+    }
+
+    /// Advance by `dt`; true when a pending rebuild is due now. A rebuild
+    /// already happening this frame (`rebuilding`) covers the pending one.
+    fn tick(&mut self, dt: f32, interval: f32, rebuilding: bool) -> bool {
+        self.since_rebuild += dt;
+        if rebuilding {
+            self.pending = false;
+            self.since_rebuild = 0.0;
+            return false;
+        }
+        if !self.pending || self.since_rebuild < interval {
+            return false;
+        }
+        self.pending = false;
+        self.since_rebuild = 0.0;
+        true
+    }
+}
+
+/// Turn a due liquid relight request into a grid rebuild.
+fn flush_liquid_relight(
+    time: Res<Time>,
+    glow: Option<Res<LiquidGlow>>,
+    mut relight: ResMut<LiquidRelight>,
+    mut rc_dirty: ResMut<RcGridDirty>,
+) {
+    let interval = glow.map(|g| *g).unwrap_or_default().relight_interval;
+    if relight.tick(time.delta_secs(), interval, rc_dirty.0) {
+        rc_dirty.0 = true;
+    }
+}
+
 /// Configuration for the radiance cascades lighting pipeline.
 #[derive(Resource, Clone, ExtractResource)]
 pub struct RcLightingConfig {
-    /// Size of RC input textures (viewport + padding) in tiles.
+    /// Size of RC input textures (viewport + padding) in texels.
     pub input_size: UVec2,
-    /// Viewport size in tiles (before padding).
+    /// Viewport size in texels (before padding).
     pub viewport_size: UVec2,
-    /// Offset from input origin to viewport origin (padding amount).
+    /// Offset in texels from input origin to viewport origin (padding
+    /// amount).
     pub viewport_offset: UVec2,
     /// World-space size of one tile in pixels.
     pub tile_size: f32,
+    /// Texels per tile of the input grid and lightmap, from
+    /// [`RcResolutionScale`].
+    pub texels_per_tile: f32,
     /// Number of radiance cascade levels.
     pub cascade_count: u32,
     /// Damping factor for bounce light (0.0 = no bounce, 1.0 = full energy).
     pub bounce_damping: f32,
-    /// Lightmap output size in tiles (set by `resize_gpu_textures`).
+    /// Lightmap output size in texels (set by `resize_gpu_textures`).
     pub lightmap_size: UVec2,
     /// World-space origin of the input grid (min_tx, min_ty).
     /// Passed to the shader so angular jitter can use stable world coordinates.
     pub grid_origin: IVec2,
     /// Previous frame's grid origin, for computing bounce light offset.
     pub prev_grid_origin: IVec2,
-    /// Bounce offset in buffer space: how to shift sample_px when reading
+    /// Bounce offset in buffer texels: how to shift sample_px when reading
     /// lightmap_prev (which was written with prev_grid_origin).
     /// Computed as (dx, -dy) where d = grid_origin - prev_grid_origin.
     pub bounce_offset: IVec2,
     /// Dynamic sun color from day/night cycle.
     pub sun_color: Vec3,
+    /// Ambient sky fill added to the lightmap in the finalize stage.
+    pub sky_ambient: Vec3,
+    /// Penumbra width for rays that graze an occluder, read against the
+    /// distance field: larger values give softer shadow edges, 0 disables.
+    pub shadow_softness: f32,
 }
 
 impl Default for RcLightingConfig {
@@ -65,6 +230,7 @@ impl Default for RcLightingConfig {
             viewport_size: UVec2::ZERO,
             viewport_offset: UVec2::ZERO,
             tile_size: 32.0,
+            texels_per_tile: 1.0,
             cascade_count: 1,
             bounce_damping: 0.4,
             lightmap_size: UVec2::ZERO,
@@ -72,6 +238,8 @@ impl Default for RcLightingConfig {
             prev_grid_origin: IVec2::ZERO,
             bounce_offset: IVec2::ZERO,
             sun_color: Vec3::new(1.0, 0.98, 0.9),
+            sky_ambient: Vec3::ZERO,
+            shadow_softness: 0.05,
         }
     }
 }
@@ -82,9 +250,13 @@ impl Default for RcLightingConfig {
 pub struct RcInputData {
     /// 0 = air, 255 = solid. One byte per tile.
     pub density: Vec<u8>,
+    /// Clamped distance to the nearest occluder, one byte per tile
+    /// (see [`crate::world::rc_sdf`]). Refreshed whenever `density` is rebuilt.
+    pub sdf: Vec<u8>,
     /// RGBA float per tile. Emissive light sources.
     pub emissive: Vec<[f32; 4]>,
-    /// RGBA u8 per tile. Surface albedo for bounce light.
+    /// RGBA u8 per tile. Surface albedo for bounce light, or a light filter
+    /// packed by [`pack_light_filter`].
     pub albedo: Vec<[u8; 4]>,
     /// Width of the input grid in tiles.
     pub width: u32,
@@ -96,6 +268,26 @@ pub struct RcInputData {
 
 // `Default` derived: all Vecs empty, numerics 0, dirty false.
 
+/// Albedo alpha marking a texel as a light filter instead of a surface
+/// albedo. Must match `FILTER_ALPHA` in `radiance_cascades.wgsl`.
+pub const FILTER_ALBEDO_ALPHA: u8 = 128;
+
+/// Albedo texel carrying a tile's light filter. Filtering tiles get no bounce
+/// light of their own; the shader multiplies rays crossing them by `filter`.
+pub fn pack_light_filter(filter: [u8; 3]) -> [u8; 4] {
+    [filter[0], filter[1], filter[2], FILTER_ALBEDO_ALPHA]
+}
+
+/// Split an albedo texel into its surface albedo and light filter.
+pub fn unpack_albedo(texel: [u8; 4]) -> ([u8; 3], [u8; 3]) {
+    let rgb = [texel[0], texel[1], texel[2]];
+    if texel[3] == FILTER_ALBEDO_ALPHA {
+        ([0; 3], rgb)
+    } else {
+        (rgb, NO_LIGHT_FILTER)
+    }
+}
+
 /// Dirty flag: set `true` whenever tiles are modified (block_action, worldgen, etc.)
 /// so that the next `extract_lighting_data` rebuilds density/albedo/flat grids.
 #[derive(Resource, Default)]
@@ -108,8 +300,108 @@ pub struct RcGridDirty(pub bool);
 struct RcCachedGrid {
     fg: Vec<TileId>,
     bg: Vec<TileId>,
+    /// Foreground tile state bytes (switchable lamps etc.).
+    fg_state: Vec<u8>,
     origin: IVec2,
     size: UVec2,
+    /// Buffer indices of the tiles listed in [`PointEmitterIndex`].
+    emitters: Vec<usize>,
+}
+
+/// Foreground tiles that can emit point light, per data chunk, so the
+/// emissive pass visits only those instead of every tile of the RC grid.
+///
+/// A tile is listed if it is solid and has any `light_emission`, whatever
+/// its state; switched-off lamps are skipped when seeding. Each list is
+/// rebuilt when its chunk's [`DirtyChunks`] revision no longer matches the
+/// one it was built at.
+#[derive(Resource, Default)]
+pub struct PointEmitterIndex {
+    chunks: HashMap<(i32, i32), (Option<u64>, Vec<u32>)>,
+}
+
+impl PointEmitterIndex {
+    /// Local tile indices of the possible emitters in `chunk`.
+    fn emitters(
+        &mut self,
+        coords: (i32, i32),
+        chunk: &ChunkData,
+        revision: Option<u64>,
+        tile_reg: &TileRegistry,
+    ) -> &[u32] {
+        let entry = self
+            .chunks
+            .entry(coords)
+            .or_insert_with(|| (revision, scan_chunk_emitters(chunk, tile_reg)));
+        if entry.0 != revision {
+            *entry = (revision, scan_chunk_emitters(chunk, tile_reg));
+        }
+        &entry.1
+    }
+
+    /// Forget every list, e.g. after a warp or a tile registry reload.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    /// Total number of indexed emitters across all chunks.
+    #[allow(dead_code)] // used by tests
+    pub fn len(&self) -> usize {
+        self.chunks.values().map(|(_, list)| list.len()).sum()
+    }
+}
+
+/// Whether a foreground tile can seed point light in some state.
+fn is_point_emitter(id: TileId, tile_reg: &TileRegistry) -> bool {
+    tile_reg.is_solid(id) && tile_reg.light_emission(id) != [0, 0, 0]
+}
+
+fn scan_chunk_emitters(chunk: &ChunkData, tile_reg: &TileRegistry) -> Vec<u32> {
+    chunk
+        .fg
+        .tiles
+        .iter()
+        .enumerate()
+        .filter(|&(_, &id)| is_point_emitter(id, tile_reg))
+        .map(|(idx, _)| idx as u32)
+        .collect()
+}
+
+/// Debounce for RC input-size changes while the window is being resized.
+///
+/// Dragging a window edge changes the viewport every frame, and each new input
+/// size makes `resize_gpu_textures` reallocate every RC texture. Instead the
+/// grid keeps its committed size, so the padding around the viewport shrinks
+/// or grows, until the desired size has held for `settle_frames` frames.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct RcResizeDebounce {
+    committed: UVec2,
+    pending: UVec2,
+    stable_frames: u32,
+}
+
+impl RcResizeDebounce {
+    /// Feed this frame's desired input size; returns the size to use.
+    /// The first size seen is adopted immediately.
+    pub fn update(&mut self, desired: UVec2, settle_frames: u32) -> UVec2 {
+        if self.committed == UVec2::ZERO || desired == self.committed {
+            self.committed = desired;
+            self.pending = desired;
+            self.stable_frames = 0;
+            return desired;
+        }
+        if desired == self.pending {
+            self.stable_frames += 1;
+        } else {
+            self.pending = desired;
+            self.stable_frames = 1;
+        }
+        if self.stable_frames >= settle_frames {
+            self.committed = desired;
+            self.stable_frames = 0;
+        }
+        self.committed
+    }
 }
 
 /// Reset RC lighting state to defaults.
@@ -123,9 +415,16 @@ fn reset_rc_on_loading(
     mut config: ResMut<RcLightingConfig>,
     mut input: ResMut<RcInputData>,
     mut rc_dirty: ResMut<RcGridDirty>,
+    mut debounce: ResMut<RcResizeDebounce>,
+    mut emitters: ResMut<PointEmitterIndex>,
+    mut light_overrides: ResMut<LightOverrides>,
 ) {
     *config = RcLightingConfig::default();
     *input = RcInputData::default();
+    *debounce = RcResizeDebounce::default();
+    emitters.clear();
+    // Overrides belong to the world being left.
+    *light_overrides = LightOverrides::default();
     rc_dirty.0 = true; // Force grid rebuild on next frame
 }
 
@@ -140,8 +439,16 @@ impl Plugin for RcLightingPlugin {
         );
 
         app.init_resource::<RcLightingConfig>()
+            .init_resource::<PointLightMerge>()
+            .init_resource::<LiquidGlow>()
+            .init_resource::<RcResolutionScale>()
+            .init_resource::<LiquidRelight>()
+            .init_resource::<ConeLights>()
             .init_resource::<RcInputData>()
             .init_resource::<RcGridDirty>()
+            .init_resource::<RcResizeDebounce>()
+            .init_resource::<PointEmitterIndex>()
+            .init_resource::<LightOverrides>()
             .insert_resource(gpu_images)
             .add_plugins((
                 ExtractResourcePlugin::<RcLightingConfig>::default(),
@@ -158,8 +465,9 @@ impl Plugin for RcLightingPlugin {
                     // camera position, not the previous frame's. This prevents
                     // the lightmap from being misaligned with the rendered tiles.
                     //
-                    // ALL four systems are gated on InGame to prevent stale data
+                    // ALL of these systems are gated on InGame to prevent stale data
                     // from corrupting lightmaps during loading after a warp.
+                    // Relight requests were made earlier, in WorldSet::Light.
                     extract_lighting_data
                         .after(GameSet::Camera)
                         .run_if(in_state(AppState::InGame)),
@@ -178,11 +486,28 @@ impl Plugin for RcLightingPlugin {
                 ),
             );
 
+        add_relight_systems(app);
+
         // Set up the render-side pipeline (render app systems + graph node).
         rc_pipeline::setup_render_pipeline(app);
     }
 }
 
+/// Systems turning this frame's world and light changes into relight
+/// requests for the next extraction.
+pub(crate) fn add_relight_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            light_override::handle_light_command,
+            light_override::relight_overridden_chunks,
+            flush_liquid_relight,
+        )
+            .chain()
+            .in_set(WorldSet::Light),
+    );
+}
+
 /// Count how many of the 4 cardinal neighbors are "open" (both FG and BG air)
 /// using direct array indexing into the flat tile grids.
 /// Out-of-bounds neighbors (grid edges in padding zone) are treated as open.
@@ -213,6 +538,118 @@ fn count_open_neighbors_grid(
     count
 }
 
+/// The 8 tiles around an emitter that count towards additive emission.
+const NEIGHBOURS_8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A placed object: its data chunk and index in that chunk's object list.
+type ObjectKey = ((i32, i32), u16);
+
+/// Add neighbouring emitters' light to `own`, clamped to 255 per channel.
+fn accumulate_emission(own: [u8; 3], neighbours: impl IntoIterator<Item = [u8; 3]>) -> [u8; 3] {
+    let mut sum = own.map(u16::from);
+    for light in neighbours {
+        for (total, channel) in sum.iter_mut().zip(light) {
+            *total += u16::from(channel);
+        }
+    }
+    sum.map(|total| total.min(255) as u8)
+}
+
+/// Light seeded by the emitting tile at grid cell `(bx, by)`: its own
+/// emission, plus that of touching additive emitters if it is additive.
+#[allow(clippy::too_many_arguments)]
+fn seeded_tile_emission(
+    bx: usize,
+    by: usize,
+    w: usize,
+    h: usize,
+    fg: &[TileId],
+    fg_state: &[u8],
+    tile_reg: &TileRegistry,
+    merge: PointLightMerge,
+) -> [u8; 3] {
+    let idx = by * w + bx;
+    let own = tile_reg.light_emission_in_state(fg[idx], fg_state[idx]);
+    if own == [0, 0, 0] || !merge.adds(tile_reg.get(fg[idx])) {
+        return own;
+    }
+    let neighbours = NEIGHBOURS_8.iter().filter_map(|&(dx, dy)| {
+        let nx = bx as i32 + dx;
+        let ny = by as i32 + dy;
+        if nx < 0 || nx >= w as i32 || ny < 0 || ny >= h as i32 {
+            return None;
+        }
+        let nidx = ny as usize * w + nx as usize;
+        let id = fg[nidx];
+        (tile_reg.is_solid(id) && merge.adds(tile_reg.get(id)))
+            .then(|| tile_reg.light_emission_in_state(id, fg_state[nidx]))
+    });
+    accumulate_emission(own, neighbours)
+}
+
+/// The cached tile grid as seen by [`point_light_texel`].
+struct EmitterGrid<'a> {
+    width: usize,
+    height: usize,
+    fg: &'a [TileId],
+    fg_state: &'a [u8],
+    min_tx: i32,
+    max_ty: i32,
+}
+
+/// Emissive texel seeded by the tile at buffer index `idx`, or `None` if it
+/// seeds no point light: not an emitter, switched off, or a cone emitter
+/// (those are stamped separately).
+fn point_light_texel(
+    idx: usize,
+    grid: &EmitterGrid,
+    tile_reg: &TileRegistry,
+    merge: PointLightMerge,
+    elapsed: f32,
+) -> Option<[f32; 4]> {
+    let fg_id = grid.fg[idx];
+    if !tile_reg.is_solid(fg_id) {
+        return None;
+    }
+    let def = tile_reg.get(fg_id);
+    if def.light_cone.is_some() {
+        return None;
+    }
+    let (bx, by) = (idx % grid.width, idx / grid.width);
+    let emission = seeded_tile_emission(
+        bx,
+        by,
+        grid.width,
+        grid.height,
+        grid.fg,
+        grid.fg_state,
+        tile_reg,
+        merge,
+    );
+    if emission == [0, 0, 0] {
+        return None;
+    }
+    let flicker = flicker_multiplier(
+        grid.min_tx + bx as i32,
+        grid.max_ty - by as i32,
+        elapsed,
+        def.flicker_speed,
+        def.flicker_strength,
+        def.flicker_min,
+    );
+    let [r, g, b] = emission.map(|c| c as f32 / 255.0 * POINT_LIGHT_BOOST * flicker);
+    Some([r, g, b, 1.0])
+}
+
 /// Deterministic hash of a tile position for per-tile flicker phase.
 /// Uses a simple mixing function — quality doesn't need to be cryptographic,
 /// just enough that adjacent tiles get visually different phases.
@@ -238,6 +675,82 @@ fn flicker_multiplier(tx: i32, ty: i32, elapsed: f32, speed: f32, strength: f32,
     min + normalized * strength
 }
 
+/// Tile offset `tiles` in texels. Exact for the offsets the grid produces,
+/// which are whole texel blocks below one texel per tile.
+fn tiles_to_texels(tiles: IVec2, texels_per_tile: f32) -> IVec2 {
+    (tiles.as_vec2() * texels_per_tile).floor().as_ivec2()
+}
+
+/// Resample tile-resolution inputs (one element per tile) into `out` at
+/// `texels_per_tile`. A coarse texel takes the brightest emission, the mean
+/// density and the mean albedo or light filter of the tiles it covers; a
+/// fine one copies its tile. Density and albedo only change when `grid` is
+/// set, like the rebuild they come from.
+fn resample_input(tiles: &RcInputData, texels_per_tile: f32, grid: bool, out: &mut RcInputData) {
+    // Tiles per texel side when coarse, texels per tile side when fine.
+    let block = (1.0 / texels_per_tile).max(1.0) as usize;
+    let repeat = texels_per_tile.max(1.0) as usize;
+    let tiles_w = tiles.width as usize;
+    let out_w = tiles.width as usize * repeat / block;
+    let out_h = tiles.height as usize * repeat / block;
+    let total = out_w * out_h;
+    if out.width as usize != out_w || out.height as usize != out_h {
+        out.density.resize(total, 0);
+        out.emissive.resize(total, [0.0; 4]);
+        out.albedo.resize(total, [0, 0, 0, 0]);
+        out.width = out_w as u32;
+        out.height = out_h as u32;
+    }
+    let samples = (block * block) as u32;
+
+    for y in 0..out_h {
+        for x in 0..out_w {
+            let (x0, y0) = (x * block / repeat, y * block / repeat);
+            let mut emissive = [0.0f32; 4];
+            let mut density = 0u32;
+            let mut albedo = [0u32; 4];
+            let mut filter = [0u32; 3];
+            for ty in y0..y0 + block {
+                for tx in x0..x0 + block {
+                    let idx = ty * tiles_w + tx;
+                    for (e, t) in emissive.iter_mut().zip(tiles.emissive[idx]) {
+                        *e = e.max(t);
+                    }
+                    if !grid {
+                        continue;
+                    }
+                    density += tiles.density[idx] as u32;
+                    let texel = tiles.albedo[idx];
+                    let (a, f) = unpack_albedo(texel);
+                    for c in 0..3 {
+                        albedo[c] += a[c] as u32;
+                        filter[c] += f[c] as u32;
+                    }
+                    if texel[3] != FILTER_ALBEDO_ALPHA {
+                        albedo[3] = albedo[3].max(texel[3] as u32);
+                    }
+                }
+            }
+            let idx = y * out_w + x;
+            out.emissive[idx] = emissive;
+            if grid {
+                out.density[idx] = (density / samples) as u8;
+                let filter = filter.map(|c| (c / samples) as u8);
+                out.albedo[idx] = if filter != NO_LIGHT_FILTER {
+                    pack_light_filter(filter)
+                } else {
+                    [
+                        (albedo[0] / samples) as u8,
+                        (albedo[1] / samples) as u8,
+                        (albedo[2] / samples) as u8,
+                        albedo[3] as u8,
+                    ]
+                };
+            }
+        }
+    }
+}
+
 /// Compute cascade count so the highest cascade's interval_end fits within
 /// the padding. Each cascade N has interval_end = 4^(N+1). We keep adding
 /// cascades while 4^(count+1) <= padding, ensuring rays from viewport probes
@@ -265,25 +778,58 @@ fn compute_cascade_count(padding: u32) -> u32 {
 ///    (`ty < 0`) → skip emissive entirely.
 /// 4. `count_open_neighbors_grid` uses 4 array reads instead of 8 HashMap
 ///    lookups.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn extract_lighting_data(
     camera_query: Query<(&Camera, &Transform, &Projection), With<Camera2d>>,
     mut world_map: ResMut<WorldMap>,
     ctx: WorldCtx,
-    mut input: ResMut<RcInputData>,
+    mut rc_input: ResMut<RcInputData>,
     mut config: ResMut<RcLightingConfig>,
-    world_time: Option<Res<crate::world::day_night::WorldTime>>,
+    sky: (
+        Option<Res<WorldTime>>,
+        Option<Res<SkyGradient>>,
+        Option<Res<WeatherState>>,
+    ),
     time: Res<Time>,
     object_registry: Option<Res<ObjectRegistry>>,
     mut rc_dirty: ResMut<RcGridDirty>,
     mut cache: Local<RcCachedGrid>,
     liquid_registry: Res<crate::liquid::registry::LiquidRegistry>,
+    mut debounce: ResMut<RcResizeDebounce>,
+    settings: (
+        Option<Res<PointLightMerge>>,
+        Option<Res<LiquidGlow>>,
+        Option<Res<RcResolutionScale>>,
+        Option<Res<ConeLights>>,
+    ),
+    mut sdf: Local<RcSdf>,
+    mut tile_input: Local<RcInputData>,
+    (mut emitter_index, dirty_chunks, light_overrides): (
+        ResMut<PointEmitterIndex>,
+        Option<Res<DirtyChunks>>,
+        Option<Res<LightOverrides>>,
+    ),
 ) {
+    let (light_merge, liquid_glow, resolution, cone_lights) = settings;
+    let (world_time, sky_gradient, weather) = sky;
+    let merge = light_merge.map(|m| *m).unwrap_or_default();
+    let glow = liquid_glow.map(|g| *g).unwrap_or_default();
+    let texels_per_tile = resolution.map(|r| *r).unwrap_or_default().texels_per_tile();
     let world_config = &*ctx.config;
     let tile_registry = &*ctx.tile_registry;
     let height_tiles = world_config.height_tiles;
     // Reset dirty flag; will be set true if we produce new data
-    input.dirty = false;
+    rc_input.dirty = false;
+
+    // Everything below works one buffer element per tile. At other
+    // resolutions the tiles go to `tile_input` and are resampled into
+    // `rc_input` at the end.
+    let scaled = texels_per_tile != 1.0;
+    let input: &mut RcInputData = if scaled {
+        &mut tile_input
+    } else {
+        &mut rc_input
+    };
 
     let Ok((camera, camera_tf, projection)) = camera_query.single() else {
         return;
@@ -293,6 +839,11 @@ fn extract_lighting_data(
     let viewport_pixels = camera
         .physical_viewport_size()
         .unwrap_or(UVec2::new(1280, 720));
+    // Minimized window: nothing is drawn, and a zero-tile viewport would
+    // collapse the grid. Keep last frame's lighting data as is.
+    if viewport_pixels.x == 0 || viewport_pixels.y == 0 {
+        return;
+    }
     let scale = match projection {
         Projection::Orthographic(ortho) => ortho.scale,
         _ => 1.0,
@@ -317,38 +868,51 @@ fn extract_lighting_data(
     // Tile range with padding, SNAPPED to the largest cascade probe spacing.
     // This ensures cascade probes always land on the same world tiles
     // regardless of camera position, eliminating view-dependent shadows.
-    let cascade_count = compute_cascade_count(RC_PADDING_TILES as u32);
+    // Padding and probe spacing are in texels; `snap` is the probe spacing
+    // in whole tiles.
+    let cascade_count = compute_cascade_count(RC_PADDING_TEXELS as u32);
     let max_spacing = 1i32 << (cascade_count - 1); // 2^(n-1): 4 for 3 cascades
+    let padding = (RC_PADDING_TEXELS as f32 / texels_per_tile) as i32;
+    let snap = ((max_spacing as f32 / texels_per_tile) as i32).max(1);
 
     let half_w = vp_tiles_w / 2;
     let half_h = vp_tiles_h / 2;
 
-    // Snap min down to a multiple of max_spacing, then round the width UP
-    // to a multiple of max_spacing. This guarantees:
+    // Snap min down to a multiple of `snap`, then round the width UP
+    // to a multiple of `snap`. This guarantees:
     //   1. Probes land on the same world tiles regardless of camera position.
-    //   2. input_w/input_h are exact multiples of every cascade's probe_spacing.
-    let raw_min_tx = cam_tile_x - half_w - RC_PADDING_TILES;
-    let raw_min_ty = cam_tile_y - half_h - RC_PADDING_TILES;
-    let raw_w = (vp_tiles_w + 2 * RC_PADDING_TILES) as u32;
-    let raw_h = (vp_tiles_h + 2 * RC_PADDING_TILES) as u32;
-
-    // Snap min down to multiple of max_spacing (floor towards -∞)
-    let min_tx = raw_min_tx - raw_min_tx.rem_euclid(max_spacing);
-    let min_ty = raw_min_ty - raw_min_ty.rem_euclid(max_spacing);
-
-    // Round width/height UP to next multiple of max_spacing
-    let ms = max_spacing as u32;
-    let input_w = raw_w.div_ceil(ms) * ms;
-    let input_h = raw_h.div_ceil(ms) * ms;
+    //   2. The grid is an exact multiple of every cascade's probe_spacing
+    //      in texels.
+    let raw_w = (vp_tiles_w + 2 * padding) as u32;
+    let raw_h = (vp_tiles_h + 2 * padding) as u32;
+
+    // Round width/height UP to next multiple of snap
+    let ms = snap as u32;
+    let desired = UVec2::new(raw_w.div_ceil(ms) * ms, raw_h.div_ceil(ms) * ms);
+
+    // While a resize settles, keep the committed size and spread the
+    // difference over the padding on both sides.
+    let committed = debounce.update(desired, RC_RESIZE_SETTLE_FRAMES);
+    let (input_w, input_h) = (committed.x, committed.y);
+    let pad_x = padding + (input_w as i32 - desired.x as i32) / 2;
+    let pad_y = padding + (input_h as i32 - desired.y as i32) / 2;
+    let raw_min_tx = cam_tile_x - half_w - pad_x;
+    let raw_min_ty = cam_tile_y - half_h - pad_y;
+
+    // Snap min down to multiple of snap (floor towards -∞)
+    let min_tx = raw_min_tx - raw_min_tx.rem_euclid(snap);
+    let min_ty = raw_min_ty - raw_min_ty.rem_euclid(snap);
 
     let max_tx = min_tx + input_w as i32 - 1;
     let max_ty = min_ty + input_h as i32 - 1;
     let total = (input_w * input_h) as usize;
 
     // Viewport offset: distance from input origin to viewport origin.
-    // Dynamic because the snapped grid may extend further than RC_PADDING_TILES.
-    let vp_offset_x = (cam_tile_x - half_w - min_tx) as u32;
-    let vp_offset_y = (max_ty - cam_tile_y - half_h) as u32; // Y-flipped
+    // Dynamic because the snapped grid may extend further than `padding`.
+    // Clamped because a grid still settling after the window grew can be
+    // narrower than the viewport.
+    let vp_offset_x = (cam_tile_x - half_w - min_tx).max(0) as u32;
+    let vp_offset_y = (max_ty - cam_tile_y - half_h).max(0) as u32; // Y-flipped
 
     // --- Update config ---
     let new_grid_origin = IVec2::new(min_tx, min_ty);
@@ -357,13 +921,19 @@ fn extract_lighting_data(
     // In buffer X: old_buf_x = new_buf_x + dx (buf_x = tx - min_tx)
     // In buffer Y: old_buf_y = new_buf_y - dy (buf_y = max_ty - ty, Y-flipped)
     let d = new_grid_origin - config.prev_grid_origin;
-    config.bounce_offset = IVec2::new(d.x, -d.y);
+    config.bounce_offset = tiles_to_texels(IVec2::new(d.x, -d.y), texels_per_tile);
     config.prev_grid_origin = new_grid_origin;
 
-    config.input_size = UVec2::new(input_w, input_h);
-    config.viewport_size = UVec2::new(vp_tiles_w as u32, vp_tiles_h as u32);
-    config.viewport_offset = UVec2::new(vp_offset_x, vp_offset_y);
+    let texel_size = |tiles: UVec2| (tiles.as_vec2() * texels_per_tile).ceil().as_uvec2();
+    config.input_size = texel_size(UVec2::new(input_w, input_h));
+    config.viewport_size = texel_size(UVec2::new(vp_tiles_w as u32, vp_tiles_h as u32));
+    config.viewport_offset = tiles_to_texels(
+        IVec2::new(vp_offset_x as i32, vp_offset_y as i32),
+        texels_per_tile,
+    )
+    .as_uvec2();
     config.tile_size = tile_size;
+    config.texels_per_tile = texels_per_tile;
     config.cascade_count = cascade_count;
     config.grid_origin = new_grid_origin;
 
@@ -401,7 +971,21 @@ fn extract_lighting_data(
 
     // --- Determine whether to rebuild flat grids + density/albedo ---
     let new_size = UVec2::new(input_w, input_h);
-    let need_rebuild = new_grid_origin != cache.origin || new_size != cache.size || rc_dirty.0;
+    // A reloaded tile registry can change which tiles emit.
+    if ctx.tile_registry.is_changed() {
+        emitter_index.clear();
+    }
+    let need_rebuild = new_grid_origin != cache.origin
+        || new_size != cache.size
+        || rc_dirty.0
+        || ctx.tile_registry.is_changed();
+
+    // Grid tiles inside light overrides, later overrides last.
+    let overridden = light_overrides
+        .as_ref()
+        .filter(|o| !o.is_empty())
+        .map(|o| overridden_texels(o, min_tx, max_ty, input_w, input_h, world_config))
+        .unwrap_or_default();
 
     // --- Rebuild flat tile grids + density/albedo when needed ---
     // Instead of ~63K×2 HashMap lookups (get_fg_tile + get_bg_tile per tile),
@@ -417,6 +1001,9 @@ fn extract_lighting_data(
         cache.bg.resize(total, TileId::AIR);
         cache.fg.fill(TileId::AIR);
         cache.bg.fill(TileId::AIR);
+        cache.fg_state.resize(total, 0);
+        cache.fg_state.fill(0);
+        cache.emitters.clear();
 
         // Fill bedrock rows (ty < 0) with stone
         for ty in min_ty..0_i32.min(max_ty + 1) {
@@ -470,6 +1057,26 @@ fn extract_lighting_data(
                             .copy_from_slice(&chunk.fg.tiles[src_start..src_start + row_len]);
                         cache.bg[dst_start..dst_start + row_len]
                             .copy_from_slice(&chunk.bg.tiles[src_start..src_start + row_len]);
+                        // Chunks saved before tile state existed have no state (all zero).
+                        if !chunk.tile_state.is_empty() {
+                            cache.fg_state[dst_start..dst_start + row_len].copy_from_slice(
+                                &chunk.tile_state[src_start..src_start + row_len],
+                            );
+                        }
+                    }
+
+                    let revision = dirty_chunks
+                        .as_ref()
+                        .and_then(|d| d.0.revision(&(data_cx, cy)));
+                    let emitters =
+                        emitter_index.emitters((data_cx, cy), chunk, revision, tile_registry);
+                    for &local in emitters {
+                        let tx = chunk_tx0 + (local as i32 % cs);
+                        let ty = chunk_ty0 + (local as i32 / cs);
+                        if tx >= tx0 && tx < tx1 && ty >= ty0 && ty < ty1 {
+                            let buf_y = (max_ty - ty) as usize;
+                            cache.emitters.push(buf_y * w_usize + (tx - min_tx) as usize);
+                        }
                     }
                 }
             }
@@ -477,9 +1084,10 @@ fn extract_lighting_data(
 
         // Rebuild density + albedo from flat grids (single pass, no fill).
         // Every element is written — solid tiles get opacity/albedo,
-        // air tiles get explicit zeros.
+        // air tiles get explicit zeros, filtering tiles a packed filter.
         for idx in 0..total {
             let fg_id = cache.fg[idx];
+            let filter = tile_registry.light_filter(fg_id);
             if tile_registry.is_solid(fg_id) {
                 let opacity = tile_registry.light_opacity(fg_id);
                 input.density[idx] = (opacity as f32 / 15.0 * 255.0) as u8;
@@ -505,10 +1113,10 @@ fn extract_lighting_data(
                         liquid_registry.get(cell.liquid_type).map_or((0, [0; 4]), |ldef| {
                             let opacity = (ldef.light_opacity as f32 * cell.level.clamp(0.0, 1.0)) as u8;
                             // Set albedo from liquid color for non-emissive liquids only.
-                            // Emissive liquids (lava) must NOT have albedo — it creates a
-                            // feedback loop where emitted light bounces off its own albedo
-                            // and amplifies deep into surrounding terrain.
-                            let albedo = if opacity > 0 && ldef.light_emission == [0, 0, 0] {
+                            // Emissive liquids (lava, tinted water) must NOT have albedo —
+                            // it creates a feedback loop where emitted light bounces off
+                            // its own albedo and amplifies deep into surrounding terrain.
+                            let albedo = if opacity > 0 && glow.colour(ldef) == [0.0; 3] {
                                 [
                                     (ldef.color[0] * 255.0) as u8,
                                     (ldef.color[1] * 255.0) as u8,
@@ -528,10 +1136,25 @@ fn extract_lighting_data(
                 input.density[idx] = liquid_opacity;
                 input.albedo[idx] = liquid_albedo;
             }
+            if filter != NO_LIGHT_FILTER {
+                input.albedo[idx] = pack_light_filter(filter);
+            }
+        }
+
+        // Dark zones block light crossing their open tiles.
+        for &(idx, mode) in &overridden {
+            if mode == LightOverrideMode::Dark && !tile_registry.is_solid(cache.fg[idx]) {
+                input.albedo[idx] = pack_light_filter([0; 3]);
+            }
         }
 
         cache.origin = new_grid_origin;
         cache.size = new_size;
+
+        if !scaled {
+            let input = &mut *input;
+            sdf.update(&input.density, new_size, new_grid_origin, &mut input.sdf);
+        }
     }
 
     // --- Pre-extract liquid emission data for the parallel emissive pass ---
@@ -562,8 +1185,10 @@ fn extract_lighting_data(
                 let cell = chunk.liquid.get(lx, ly, world_config.chunk_size);
                 if !cell.is_empty() {
                     if let Some(ldef) = liquid_registry.get(cell.liquid_type) {
-                        let e = ldef.light_emission;
-                        if e != [0, 0, 0] {
+                        let e = glow.colour(ldef);
+                        if e != [0.0; 3] {
+                            // Light follows the cell's fill level, so a
+                            // spreading flow brightens as it settles.
                             let scale = cell.level.clamp(0.0, 1.0);
                             let flicker = flicker_multiplier(
                                 tx,
@@ -573,11 +1198,7 @@ fn extract_lighting_data(
                                 ldef.flicker_strength,
                                 ldef.flicker_min,
                             );
-                            liquid_emission[idx] = [
-                                e[0] as f32 / 255.0 * scale * flicker,
-                                e[1] as f32 / 255.0 * scale * flicker,
-                                e[2] as f32 / 255.0 * scale * flicker,
-                            ];
+                            liquid_emission[idx] = e.map(|c| c * scale * flicker);
                         }
                     }
                 }
@@ -586,22 +1207,18 @@ fn extract_lighting_data(
     }
 
     // --- Compute effective sun color from day/night cycle ---
-    // Bake ambient_min into sun emitters: each channel is at least ambient_min.
-    // This ensures sky-visible tiles always emit some light (even at night),
-    // while underground tiles (no sky access) stay pitch black.
-    let (sun, ambient_min) = if let Some(ref wt) = world_time {
-        let amb = wt.ambient_min;
-        (
-            [
-                (wt.sun_color.x * wt.sun_intensity).max(amb),
-                (wt.sun_color.y * wt.sun_intensity).max(amb),
-                (wt.sun_color.z * wt.sun_intensity).max(amb),
-            ],
-            amb,
-        )
-    } else {
-        (SUN_COLOR, 0.0)
+    // The sky gradient tints the sun and bakes ambient_min into it: each
+    // channel is at least ambient_min. This ensures sky-visible tiles always
+    // emit some light (even at night), while underground tiles (no sky
+    // access) stay pitch black.
+    let sky_light = match world_time {
+        Some(wt) => {
+            let gradient = sky_gradient.as_deref().cloned().unwrap_or_default();
+            gradient.sky_light(&wt, weather.map_or(0.0, |w| w.intensity()))
+        }
+        None => SkyLight::default(),
     };
+    let sun = sky_light.emitter.to_array();
 
     // --- Rebuild emissive every frame (parallel across CPU cores) ---
     // Split into horizontal strips, one per thread. Each strip writes only
@@ -647,34 +1264,14 @@ fn extract_lighting_data(
                             continue;
                         }
 
-                        // In-world tiles: sun emitters + tile-specific emissive
+                        // In-world tiles: sun emitters here, tile emitters
+                        // from the emitter index after this pass.
                         for buf_x in 0..w_usize {
                             let local_idx = row_start + buf_x;
                             let global_idx = buf_y * w_usize + buf_x;
                             let fg_id = fg[global_idx];
 
-                            if tr.is_solid(fg_id) {
-                                // Tile emissive (torches, lava, etc.)
-                                let emission = tr.light_emission(fg_id);
-                                if emission != [0, 0, 0] {
-                                    let tx = min_tx + buf_x as i32;
-                                    let def = tr.get(fg_id);
-                                    let flicker = flicker_multiplier(
-                                        tx,
-                                        ty,
-                                        elapsed,
-                                        def.flicker_speed,
-                                        def.flicker_strength,
-                                        def.flicker_min,
-                                    );
-                                    strip[local_idx] = [
-                                        emission[0] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                        emission[1] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                        emission[2] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                        1.0,
-                                    ];
-                                }
-                            } else {
+                            if !tr.is_solid(fg_id) {
                                 // FG is air: check for liquid emission first,
                                 // then fall back to sun emitter if BG is also air.
                                 let le = liq_em[global_idx];
@@ -709,10 +1306,79 @@ fn extract_lighting_data(
         });
     }
 
+    // --- Tile point emitters (torches, lamps, etc.) from the emitter index ---
+    let grid = EmitterGrid {
+        width: w_usize,
+        height: h_usize,
+        fg: &cache.fg,
+        fg_state: &cache.fg_state,
+        min_tx,
+        max_ty,
+    };
+    for &idx in &cache.emitters {
+        if let Some(texel) = point_light_texel(idx, &grid, tile_registry, merge, elapsed) {
+            input.emissive[idx] = texel;
+        }
+    }
+
+    // --- Directional emitters: cone tiles, then worn lights ---
+    let mut cones: Vec<(IVec2, [f32; 3], LightCone)> = Vec::new();
+    if tile_registry.defs.iter().any(|d| d.light_cone.is_some()) {
+        for &idx in &cache.emitters {
+            let fg_id = cache.fg[idx];
+            let def = tile_registry.get(fg_id);
+            let Some(cone) = def.light_cone else {
+                continue;
+            };
+            let emission = tile_registry.light_emission_in_state(fg_id, cache.fg_state[idx]);
+            if emission == [0, 0, 0] {
+                continue;
+            }
+            let tx = min_tx + (idx % w_usize) as i32;
+            let ty = max_ty - (idx / w_usize) as i32;
+            let flicker = flicker_multiplier(
+                tx,
+                ty,
+                elapsed,
+                def.flicker_speed,
+                def.flicker_strength,
+                def.flicker_min,
+            );
+            let light = emission.map(|c| c as f32 / 255.0 * POINT_LIGHT_BOOST * flicker);
+            cones.push((IVec2::new(tx, ty), light, cone));
+        }
+    }
+    if let Some(ref lights) = cone_lights {
+        cones.extend(lights.0.iter().map(|l| {
+            let light = l.emission.map(|c| c as f32 / 255.0 * POINT_LIGHT_BOOST);
+            (l.tile, light, l.cone)
+        }));
+    }
+    for (tile, light, cone) in cones {
+        for (offset, weight) in cone_footprint(&cone, CONE_REACH) {
+            let t = tile + offset;
+            if t.x < min_tx || t.x > max_tx || t.y < min_ty || t.y > max_ty {
+                continue;
+            }
+            let idx = (max_ty - t.y) as usize * w_usize + (t.x - min_tx) as usize;
+            // Light starts in the open, not inside the walls around it.
+            if offset != IVec2::ZERO && tile_registry.is_solid(cache.fg[idx]) {
+                continue;
+            }
+            let texel = &mut input.emissive[idx];
+            for (channel, c) in texel.iter_mut().zip(light) {
+                *channel = channel.max(c * weight);
+            }
+            texel[3] = 1.0;
+        }
+    }
+
     // --- Object emissive (iterate by chunk, not per-tile HashMap) ---
     // Checked after tile emission so an object light on an air tile
     // fills the emissive slot that tile emission left at zero.
     if let Some(ref obj_reg) = object_registry {
+        // (buffer index, owning object, emission, flicker) per lit object tile.
+        let mut object_lights: Vec<(usize, ObjectKey, [u8; 3], f32)> = Vec::new();
         let cs = world_config.chunk_size as i32;
         let cs_u = world_config.chunk_size;
         let clamp_min_ty = min_ty.max(0);
@@ -777,29 +1443,117 @@ fn extract_lighting_data(
                                 def.flicker_strength,
                                 def.flicker_min,
                             );
-                            input.emissive[idx] = [
-                                oe[0] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                oe[1] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                oe[2] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
-                                1.0,
-                            ];
+                            let owner = (occ.data_chunk, occ.object_index);
+                            object_lights.push((idx, owner, oe, flicker));
                         }
                     }
                 }
             }
         }
+
+        // In additive mode each lit tile also takes the light of touching
+        // tiles of *other* objects, so a multi-tile lamp doesn't brighten
+        // itself.
+        let additive = merge == PointLightMerge::Additive;
+        let lit_by_idx: HashMap<usize, (ObjectKey, [u8; 3])> = object_lights
+            .iter()
+            .filter(|_| additive)
+            .map(|&(idx, owner, oe, _)| (idx, (owner, oe)))
+            .collect();
+        for &(idx, owner, oe, flicker) in &object_lights {
+            let bx = (idx % input_w as usize) as i32;
+            let by = (idx / input_w as usize) as i32;
+            let neighbours = NEIGHBOURS_8.iter().filter_map(|&(dx, dy)| {
+                let (nx, ny) = (bx + dx, by + dy);
+                if nx < 0 || nx >= input_w as i32 || ny < 0 || ny >= input_h as i32 {
+                    return None;
+                }
+                let nidx = ny as usize * input_w as usize + nx as usize;
+                lit_by_idx
+                    .get(&nidx)
+                    .filter(|(other, _)| *other != owner)
+                    .map(|&(_, light)| light)
+            });
+            let oe = accumulate_emission(oe, neighbours);
+            input.emissive[idx] = [
+                oe[0] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
+                oe[1] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
+                oe[2] as f32 / 255.0 * POINT_LIGHT_BOOST * flicker,
+                1.0,
+            ];
+        }
+    }
+
+    // --- Light overrides replace or clamp whatever was seeded above ---
+    for &(idx, mode) in &overridden {
+        mode.apply(&mut input.emissive[idx]);
     }
 
     rc_dirty.0 = false;
-    input.dirty = true;
+    if scaled {
+        resample_input(&tile_input, texels_per_tile, need_rebuild, &mut rc_input);
+        if need_rebuild {
+            let rc_input = &mut *rc_input;
+            let origin = tiles_to_texels(new_grid_origin, texels_per_tile);
+            sdf.update(&rc_input.density, config.input_size, origin, &mut rc_input.sdf);
+        }
+    }
+    rc_input.dirty = true;
+
+    // Update config with day/night values for the GPU pipeline. The emitter
+    // has ambient_min baked in, so sky escape in radiance_cascades.wgsl also
+    // returns at least ambient_min per channel.
+    config.sun_color = sky_light.emitter;
+    config.sky_ambient = sky_light.ambient;
+}
+
+/// Buffer index and mode of every tile of the Y-flipped grid (left column
+/// `min_tx`, top row `max_ty`) inside a light override, in override order.
+fn overridden_texels(
+    overrides: &LightOverrides,
+    min_tx: i32,
+    max_ty: i32,
+    width: u32,
+    height: u32,
+    config: &ActiveWorld,
+) -> Vec<(usize, LightOverrideMode)> {
+    let min_ty = max_ty - height as i32 + 1;
+    let mut texels = Vec::new();
+    for o in overrides.iter() {
+        let rows = o.rect.y.max(min_ty)..(o.rect.y + o.rect.height).min(max_ty + 1);
+        for ty in rows {
+            let row_start = (max_ty - ty) as usize * width as usize;
+            for buf_x in 0..width as usize {
+                if o.rect.contains(min_tx + buf_x as i32, ty, config) {
+                    texels.push((row_start + buf_x, o.mode));
+                }
+            }
+        }
+    }
+    texels
+}
 
-    // Update config with day/night values for the GPU pipeline.
-    // Bake ambient_min into sun_color so sky escape in radiance_cascades.wgsl
-    // also returns at least ambient_min per channel.
-    if let Some(ref wt) = world_time {
-        let base = wt.sun_color * wt.sun_intensity;
-        config.sun_color = base.max(Vec3::splat(ambient_min));
+/// Affine transform from world position to lightmap UV:
+/// `lightmap_uv = world_pos * xy + zw`. The lightmap is input-sized, covering
+/// the full RC grid in world space, so the transform only changes on grid
+/// snap, not every frame. `None` before the grid has a size.
+fn lightmap_uv_transform(config: &RcLightingConfig) -> Option<Vec4> {
+    let iw = config.input_size.x as f32;
+    let ih = config.input_size.y as f32;
+    if iw == 0.0 || ih == 0.0 {
+        return None;
     }
+    // World-space size of one lightmap texel.
+    let texel = config.tile_size / config.texels_per_tile;
+    let gx = config.grid_origin.x as f32 * config.tile_size;
+    let gy = config.grid_origin.y as f32 * config.tile_size;
+
+    Some(Vec4::new(
+        1.0 / (texel * iw),      // scale_x
+        -1.0 / (texel * ih),     // scale_y (negated: world Y up, texel Y down)
+        -gx / (texel * iw),      // offset_x
+        1.0 + gy / (texel * ih), // offset_y
+    ))
 }
 
 /// Update the tile material lightmap handles to point to the current RC lightmap
@@ -825,25 +1579,9 @@ fn update_tile_lightmap(
         return;
     };
 
-    // Pre-compute affine transform: world_pos → lightmap UV.
-    // lightmap_uv = world_pos * scale + offset
-    // Lightmap is input-sized, covering the full RC grid in world-space.
-    // This transform is stable (changes only on grid snap, not every frame).
-    let ts = config.tile_size;
-    let iw = config.input_size.x as f32;
-    let ih = config.input_size.y as f32;
-    if iw == 0.0 || ih == 0.0 {
+    let Some(lm_params) = lightmap_uv_transform(&config) else {
         return;
-    }
-    let gx = config.grid_origin.x as f32;
-    let gy = config.grid_origin.y as f32;
-
-    let lm_params = Vec4::new(
-        1.0 / (ts * iw),  // scale_x
-        -1.0 / (ts * ih), // scale_y (negated: world Y up, texel Y down)
-        -gx / iw,         // offset_x
-        1.0 + gy / ih,    // offset_y
-    );
+    };
 
     // Update tile materials (shared FG/BG handles)
     for handle in [&shared_material.fg, &shared_material.bg] {
@@ -923,8 +1661,8 @@ mod tests {
 
     #[test]
     fn cascade_count_current_padding() {
-        // RC_PADDING_TILES = 64 → should give 3 cascades
-        assert_eq!(compute_cascade_count(RC_PADDING_TILES as u32), 3);
+        // RC_PADDING_TEXELS = 64 → should give 3 cascades
+        assert_eq!(compute_cascade_count(RC_PADDING_TEXELS as u32), 3);
     }
 
     #[test]
@@ -983,4 +1721,782 @@ mod tests {
             "adjacent tiles shouldn't sync: a={a}, b={b}"
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Switchable tile emission
+    // -----------------------------------------------------------------------
+
+    use crate::liquid::registry::LiquidRegistry;
+    use crate::registry::tile::TILE_STATE_OFF;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::Layer;
+
+    const LAMP_TILE: (i32, i32) = (100, 200);
+
+    /// Test app with a switchable lamp placed underground at `LAMP_TILE` and a
+    /// camera centred on it, ready to run `extract_lighting_data`.
+    fn lamp_app(lamp_state: u8) -> App {
+        emitter_app(&[LAMP_TILE], lamp_state, |lamp| {
+            lamp.light_emission = [255, 200, 120];
+            lamp.switchable = true;
+        })
+    }
+
+    /// Test app with an emitting tile (a stone copy adjusted by `configure`)
+    /// at each of `tiles`, camera centred on the first.
+    fn emitter_app(tiles: &[(i32, i32)], state: u8, configure: impl FnOnce(&mut TileDef)) -> App {
+        let mut app = fixtures::test_app();
+        let mut defs = fixtures::test_tile_registry().defs;
+        let mut lamp = defs[3].clone(); // solid like stone
+        lamp.id = "lamp".into();
+        configure(&mut lamp);
+        defs.push(lamp);
+        app.insert_resource(TileRegistry::from_defs(defs));
+        app.init_resource::<RcInputData>()
+            .init_resource::<RcLightingConfig>()
+            .init_resource::<RcGridDirty>()
+            .init_resource::<RcResizeDebounce>()
+            .init_resource::<PointEmitterIndex>()
+            .init_resource::<LiquidRegistry>()
+            .add_systems(Update, extract_lighting_data);
+
+        app.world_mut().resource_scope(|world, mut map: Mut<WorldMap>| {
+            let ctx = fixtures::make_ctx(
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+            );
+            let lamp = ctx.tile_registry.by_name("lamp");
+            for &(tx, ty) in tiles {
+                map.set_tile(tx, ty, Layer::Fg, lamp, &ctx);
+                map.set_tile_state(tx, ty, state, &ctx);
+            }
+        });
+
+        let (tx, ty) = tiles[0];
+        let ts = 32.0;
+        app.world_mut().spawn((
+            Camera2d,
+            Projection::Orthographic(OrthographicProjection::default_2d()),
+            Transform::from_xyz(tx as f32 * ts + ts / 2.0, ty as f32 * ts + ts / 2.0, 0.0),
+        ));
+        app
+    }
+
+    fn lamp_emissive(app: &App) -> [f32; 4] {
+        emissive_at(app, LAMP_TILE)
+    }
+
+    fn emissive_at(app: &App, (tx, ty): (i32, i32)) -> [f32; 4] {
+        let config = app.world().resource::<RcLightingConfig>();
+        let input = app.world().resource::<RcInputData>();
+        let max_ty = config.grid_origin.y + config.input_size.y as i32 - 1;
+        let buf_x = (tx - config.grid_origin.x) as u32;
+        let buf_y = (max_ty - ty) as u32;
+        input.emissive[(buf_y * config.input_size.x + buf_x) as usize]
+    }
+
+    #[test]
+    fn lamp_on_emits_light() {
+        let mut app = lamp_app(0);
+        app.update();
+        let e = lamp_emissive(&app);
+        assert!(e[0] > 0.0 && e[1] > 0.0 && e[2] > 0.0, "lamp should emit: {e:?}");
+    }
+
+    #[test]
+    fn lamp_off_contributes_no_light() {
+        let mut app = lamp_app(TILE_STATE_OFF);
+        app.update();
+        assert_eq!(lamp_emissive(&app), [0.0; 4]);
+    }
+
+    #[test]
+    fn light_overrides_replace_and_clamp_emission_until_removed() {
+        use crate::world::light_override::TileRect;
+
+        let mut app = lamp_app(0);
+        app.init_resource::<LightOverrides>().add_systems(
+            Update,
+            light_override::relight_overridden_chunks.before(extract_lighting_data),
+        );
+        app.update();
+        let lit = lamp_emissive(&app);
+        assert!(lit[0] > 0.5, "lamp should emit: {lit:?}");
+
+        let config = app.world().resource::<ActiveWorld>().clone();
+        let rect = TileRect::new(LAMP_TILE.0 - 1, LAMP_TILE.1 - 1, 3, 3);
+        let set = |app: &mut App, mode| {
+            app.world_mut()
+                .resource_mut::<LightOverrides>()
+                .set_light_override(rect, mode, &config)
+        };
+        let dark = set(&mut app, LightOverrideMode::Dark);
+        app.update();
+        assert_eq!(lamp_emissive(&app), [0.0; 4]);
+
+        app.world_mut()
+            .resource_mut::<LightOverrides>()
+            .remove(dark, &config);
+        let clamp = set(&mut app, LightOverrideMode::Clamp([0.5, 10.0, 10.0]));
+        app.update();
+        assert_eq!(lamp_emissive(&app), [0.5, lit[1], lit[2], lit[3]]);
+        // Tiles outside the rectangle keep their light.
+        let outside = (LAMP_TILE.0 + 5, LAMP_TILE.1);
+        let before = emissive_at(&app, outside);
+
+        app.world_mut()
+            .resource_mut::<LightOverrides>()
+            .remove(clamp, &config);
+        app.update();
+        assert!(!app.world().resource::<RcGridDirty>().0);
+        assert_eq!(lamp_emissive(&app), lit);
+        assert_eq!(emissive_at(&app, outside), before);
+    }
+
+    #[test]
+    fn toggling_lamp_on_relights() {
+        let mut app = lamp_app(TILE_STATE_OFF);
+        app.update();
+        assert_eq!(lamp_emissive(&app), [0.0; 4]);
+
+        // Toggle on the same way block interaction does: flip state, mark grid dirty.
+        let (tx, ty) = LAMP_TILE;
+        app.world_mut().resource_scope(|world, mut map: Mut<WorldMap>| {
+            let ctx = fixtures::make_ctx(
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+            );
+            let state = map.get_tile_state(tx, ty, &ctx);
+            map.set_tile_state(tx, ty, state ^ TILE_STATE_OFF, &ctx);
+        });
+        app.world_mut().resource_mut::<RcGridDirty>().0 = true;
+        app.update();
+
+        let e = lamp_emissive(&app);
+        assert!(e[0] > 0.0, "lamp should emit after toggling on: {e:?}");
+    }
+
+    // -----------------------------------------------------------------------
+    // Additive emitter clusters
+    // -----------------------------------------------------------------------
+
+    const CLUSTER: [(i32, i32); 3] = [LAMP_TILE, (101, 200), (100, 201)];
+
+    fn glow(tile: &mut TileDef) {
+        tile.light_emission = [60, 40, 20];
+    }
+
+    fn cluster_emissive(tiles: &[(i32, i32)], merge: PointLightMerge, per_tile: bool) -> [f32; 4] {
+        let mut app = emitter_app(tiles, 0, |tile| {
+            glow(tile);
+            tile.additive_light = per_tile;
+        });
+        app.insert_resource(merge);
+        app.update();
+        lamp_emissive(&app)
+    }
+
+    // -----------------------------------------------------------------------
+    // Point emitter index
+    // -----------------------------------------------------------------------
+
+    /// Point light of every tile of the current RC grid, found the old way:
+    /// by reading each tile back from the world map and seeding it.
+    fn scanned_point_lights(app: &App) -> Vec<(usize, Option<[f32; 4]>)> {
+        let world = app.world();
+        let config = world.resource::<RcLightingConfig>();
+        let map = world.resource::<WorldMap>();
+        let ctx = fixtures::make_ctx(
+            world.resource(),
+            world.resource(),
+            world.resource(),
+            world.resource(),
+            world.resource(),
+            world.resource(),
+        );
+        let (w, h) = (config.input_size.x as usize, config.input_size.y as usize);
+        let min_tx = config.grid_origin.x;
+        let max_ty = config.grid_origin.y + h as i32 - 1;
+        let mut fg = Vec::with_capacity(w * h);
+        let mut fg_state = Vec::with_capacity(w * h);
+        for by in 0..h {
+            for bx in 0..w {
+                let (tx, ty) = (min_tx + bx as i32, max_ty - by as i32);
+                fg.push(map.get_tile(tx, ty, Layer::Fg, &ctx).unwrap_or(TileId::AIR));
+                fg_state.push(map.get_tile_state(tx, ty, &ctx));
+            }
+        }
+        let grid = EmitterGrid {
+            width: w,
+            height: h,
+            fg: &fg,
+            fg_state: &fg_state,
+            min_tx,
+            max_ty,
+        };
+        let elapsed = world.resource::<Time>().elapsed_secs();
+        (0..w * h)
+            .filter(|&idx| ctx.tile_registry.is_solid(fg[idx]))
+            .map(|idx| {
+                let texel = point_light_texel(
+                    idx,
+                    &grid,
+                    ctx.tile_registry,
+                    PointLightMerge::default(),
+                    elapsed,
+                );
+                (idx, texel)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn indexed_point_lights_match_full_scan() {
+        let lamps = [LAMP_TILE, (110, 195), (90, 215)];
+        let mut app = emitter_app(&lamps, 0, glow);
+        app.update();
+
+        let scanned = scanned_point_lights(&app);
+        let emissive = &app.world().resource::<RcInputData>().emissive;
+        let lit = scanned.iter().filter(|(_, texel)| texel.is_some()).count();
+        assert_eq!(lit, lamps.len());
+        for &(idx, texel) in &scanned {
+            assert_eq!(emissive[idx], texel.unwrap_or([0.0; 4]), "texel {idx}");
+        }
+
+        // Only the lamps were visited, not every tile of the grid.
+        let indexed = app.world().resource::<PointEmitterIndex>().len();
+        assert_eq!(indexed, lamps.len());
+        assert!(emissive.len() > 1000 * indexed, "grid of {}", emissive.len());
+    }
+
+    #[test]
+    fn emitter_index_follows_chunk_revisions() {
+        let mut app = emitter_app(&[LAMP_TILE], 0, glow);
+        app.init_resource::<DirtyChunks>();
+        app.update();
+
+        // Place a second lamp the way block interaction does: set the tile,
+        // stamp its chunk dirty and mark the grid dirty.
+        let placed = (104, 203);
+        app.world_mut().resource_scope(|world, mut map: Mut<WorldMap>| {
+            let ctx = fixtures::make_ctx(
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+            );
+            let lamp = ctx.tile_registry.by_name("lamp");
+            map.set_tile(placed.0, placed.1, Layer::Fg, lamp, &ctx);
+            let chunk = crate::world::chunk::tile_to_chunk(
+                placed.0,
+                placed.1,
+                ctx.config.chunk_size,
+            );
+            world.resource_mut::<DirtyChunks>().0.insert(chunk);
+        });
+        app.world_mut().resource_mut::<RcGridDirty>().0 = true;
+        app.update();
+
+        assert!(emissive_at(&app, placed)[0] > 0.0);
+        assert_eq!(app.world().resource::<PointEmitterIndex>().len(), 2);
+    }
+
+    #[test]
+    fn accumulate_emission_sums_and_clamps_per_channel() {
+        assert_eq!(accumulate_emission([10, 20, 30], []), [10, 20, 30]);
+        assert_eq!(
+            accumulate_emission([10, 20, 200], [[5, 0, 40], [1, 2, 100]]),
+            [16, 22, 255]
+        );
+    }
+
+    #[test]
+    fn additive_cluster_is_brighter_than_single_emitter() {
+        let single = cluster_emissive(&[LAMP_TILE], PointLightMerge::Additive, false);
+        let cluster = cluster_emissive(&CLUSTER, PointLightMerge::Additive, false);
+        for c in 0..3 {
+            assert!(
+                (cluster[c] - single[c] * 3.0).abs() < 1e-4,
+                "{cluster:?} vs {single:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn max_merge_cluster_matches_single_emitter() {
+        let single = cluster_emissive(&[LAMP_TILE], PointLightMerge::Max, false);
+        let cluster = cluster_emissive(&CLUSTER, PointLightMerge::Max, false);
+        assert_eq!(cluster, single);
+    }
+
+    #[test]
+    fn additive_tile_flag_works_without_global_mode() {
+        let single = cluster_emissive(&[LAMP_TILE], PointLightMerge::Max, true);
+        let cluster = cluster_emissive(&CLUSTER, PointLightMerge::Max, true);
+        assert!(cluster[0] > single[0], "{cluster:?} vs {single:?}");
+    }
+
+    // -----------------------------------------------------------------------
+    // Resolution scale
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn resolution_scale_rounds_to_powers_of_two() {
+        assert_eq!(RcResolutionScale::default().texels_per_tile(), 1.0);
+        assert_eq!(RcResolutionScale(0.6).texels_per_tile(), 0.5);
+        assert_eq!(RcResolutionScale(3.0).texels_per_tile(), 4.0);
+        assert_eq!(
+            RcResolutionScale(0.01).texels_per_tile(),
+            MIN_RESOLUTION_SCALE
+        );
+        assert_eq!(
+            RcResolutionScale(100.0).texels_per_tile(),
+            MAX_RESOLUTION_SCALE
+        );
+    }
+
+    fn scaled_config(scale: f32) -> RcLightingConfig {
+        let mut app = lamp_app(0);
+        app.insert_resource(RcResolutionScale(scale));
+        app.update();
+        let config = app.world().resource::<RcLightingConfig>().clone();
+        let input = app.world().resource::<RcInputData>();
+        assert_eq!(UVec2::new(input.width, input.height), config.input_size);
+        let total = (config.input_size.x * config.input_size.y) as usize;
+        assert_eq!(input.density.len(), total);
+        assert_eq!(input.emissive.len(), total);
+        assert_eq!(input.albedo.len(), total);
+        assert_eq!(input.sdf.len(), total);
+        config
+    }
+
+    #[test]
+    fn resolution_scale_sizes_the_rc_textures() {
+        // The fallback 1280×720 viewport is 40×23 tiles; the padding is 64
+        // texels a side and the grid a multiple of 4 texels.
+        for (scale, input, viewport) in [
+            (1.0, (168, 152), (40, 23)),
+            (0.5, (148, 140), (20, 12)),
+            (2.0, (208, 176), (80, 46)),
+        ] {
+            let config = scaled_config(scale);
+            assert_eq!(config.texels_per_tile, scale);
+            assert_eq!(
+                config.input_size,
+                UVec2::new(input.0, input.1),
+                "scale {scale}"
+            );
+            assert_eq!(
+                config.viewport_size,
+                UVec2::new(viewport.0, viewport.1),
+                "scale {scale}"
+            );
+            assert_eq!(config.cascade_count, 3);
+        }
+    }
+
+    #[test]
+    fn lightmap_uv_keeps_the_camera_centred_in_the_viewport() {
+        let ts = 32.0;
+        let camera = (Vec2::new(LAMP_TILE.0 as f32, LAMP_TILE.1 as f32) + 0.5) * ts;
+        for scale in [0.25, 0.5, 1.0, 2.0, 4.0] {
+            let config = scaled_config(scale);
+            let lm = lightmap_uv_transform(&config).unwrap();
+            let texel = (camera * lm.xy() + lm.zw()) * config.input_size.as_vec2();
+            let centre = config.viewport_offset.as_vec2() + config.viewport_size.as_vec2() / 2.0;
+            // The viewport is whole tiles around the camera's tile.
+            assert!(
+                (texel - centre).abs().max_element() <= scale.max(1.0),
+                "scale {scale}: camera at texel {texel}, viewport centre {centre}"
+            );
+            // The lamp tile lands on a texel inside the grid.
+            assert!(
+                texel.cmpge(Vec2::ZERO).all() && texel.cmplt(config.input_size.as_vec2()).all()
+            );
+        }
+    }
+
+    #[test]
+    fn resample_averages_coarse_texels_and_copies_fine_ones() {
+        // 2×2 tiles: stone, air, red glass, lamp.
+        let tiles = RcInputData {
+            density: vec![255, 0, 0, 255],
+            sdf: vec![],
+            emissive: vec![[0.0; 4], [0.0; 4], [0.0; 4], [4.0, 2.0, 1.0, 1.0]],
+            albedo: vec![
+                [128, 128, 128, 255],
+                [0; 4],
+                pack_light_filter([255, 40, 40]),
+                [100, 100, 100, 255],
+            ],
+            width: 2,
+            height: 2,
+            dirty: false,
+        };
+
+        let mut coarse = RcInputData::default();
+        resample_input(&tiles, 0.5, true, &mut coarse);
+        assert_eq!((coarse.width, coarse.height), (1, 1));
+        assert_eq!(coarse.density, vec![127]);
+        assert_eq!(coarse.emissive, vec![[4.0, 2.0, 1.0, 1.0]]);
+        // One glass tile in four still tints the block.
+        let (_, filter) = unpack_albedo(coarse.albedo[0]);
+        assert_eq!(filter, [255, 201, 201]);
+
+        let mut fine = RcInputData::default();
+        resample_input(&tiles, 2.0, true, &mut fine);
+        assert_eq!((fine.width, fine.height), (4, 4));
+        // Texel row 1 is still tile row 0; texel row 2 is tile row 1.
+        assert_eq!(&fine.density[4..8], &[255, 255, 0, 0]);
+        assert_eq!(fine.albedo[8], pack_light_filter([255, 40, 40]));
+        assert_eq!(fine.emissive[15], [4.0, 2.0, 1.0, 1.0]);
+
+        // Without a grid rebuild only emission is refreshed.
+        let mut dim = tiles.clone();
+        dim.emissive[3] = [1.0, 1.0, 1.0, 1.0];
+        dim.density = vec![0; 4];
+        resample_input(&dim, 2.0, false, &mut fine);
+        assert_eq!(fine.emissive[15], [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(fine.density[0], 255);
+    }
+
+    // -----------------------------------------------------------------------
+    // Light filters
+    // -----------------------------------------------------------------------
+
+    fn texel_at(app: &App, (tx, ty): (i32, i32)) -> (u8, [u8; 4]) {
+        let config = app.world().resource::<RcLightingConfig>();
+        let input = app.world().resource::<RcInputData>();
+        let max_ty = config.grid_origin.y + config.input_size.y as i32 - 1;
+        let buf_x = (tx - config.grid_origin.x) as u32;
+        let buf_y = (max_ty - ty) as u32;
+        let idx = (buf_y * config.input_size.x + buf_x) as usize;
+        (input.density[idx], input.albedo[idx])
+    }
+
+    #[test]
+    fn light_filter_packing_round_trips() {
+        let red = [255, 40, 40];
+        assert_eq!(unpack_albedo(pack_light_filter(red)), ([0; 3], red));
+        assert_eq!(
+            unpack_albedo([128, 128, 128, 255]),
+            ([128, 128, 128], NO_LIGHT_FILTER)
+        );
+        assert_eq!(unpack_albedo([0; 4]), ([0; 3], NO_LIGHT_FILTER));
+    }
+
+    #[test]
+    fn extraction_packs_glass_filters_into_albedo() {
+        let mut app = emitter_app(&[LAMP_TILE], 0, |glass| {
+            glass.light_opacity = 0;
+            glass.light_filter = [255, 40, 40];
+        });
+        let stone_tile = (LAMP_TILE.0 + 1, LAMP_TILE.1);
+        app.world_mut().resource_scope(|world, mut map: Mut<WorldMap>| {
+            let ctx = fixtures::make_ctx(
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+            );
+            let stone = ctx.tile_registry.by_name("stone");
+            map.set_tile(stone_tile.0, stone_tile.1, Layer::Fg, stone, &ctx);
+        });
+        app.update();
+        let (density, texel) = texel_at(&app, LAMP_TILE);
+        assert_eq!(density, 0, "glass must not occlude");
+        assert_eq!(unpack_albedo(texel), ([0; 3], [255, 40, 40]));
+
+        // Plain stone next to it keeps its surface albedo.
+        let (density, texel) = texel_at(&app, stone_tile);
+        assert_eq!(density, 255);
+        assert_eq!(unpack_albedo(texel), ([128, 128, 128], NO_LIGHT_FILTER));
+    }
+
+    // -----------------------------------------------------------------------
+    // Liquid glow
+    // -----------------------------------------------------------------------
+
+    use crate::liquid::{LiquidCell, LiquidId};
+
+    const POOL: (i32, i32) = LAMP_TILE;
+
+    fn liquid(name: &str, color: [f32; 4], light_emission: [u8; 3]) -> LiquidDef {
+        LiquidDef {
+            name: name.into(),
+            density: 1.0,
+            viscosity: 1.0,
+            color,
+            damage_on_contact: 0.0,
+            light_emission,
+            light_opacity: 0,
+            swim_speed_factor: 0.5,
+            flicker_speed: 0.0,
+            flicker_strength: 0.0,
+            flicker_min: 1.0,
+            reactions: vec![],
+        }
+    }
+
+    fn lava() -> LiquidDef {
+        liquid("lava", [1.0, 0.3, 0.0, 0.7], [255, 120, 40])
+    }
+
+    fn water() -> LiquidDef {
+        liquid("water", [0.2, 0.4, 0.8, 0.35], [0, 0, 0])
+    }
+
+    /// Fill the air tile at `POOL` with `level` of liquid 1 (`None` empties it).
+    fn set_pool(app: &mut App, level: Option<f32>) {
+        let (tx, ty) = POOL;
+        let cell = level.map_or(LiquidCell::EMPTY, |level| LiquidCell {
+            liquid_type: LiquidId(1),
+            level,
+        });
+        app.world_mut()
+            .resource_scope(|world, mut map: Mut<WorldMap>| {
+                let ctx = fixtures::make_ctx(
+                    world.resource(),
+                    world.resource(),
+                    world.resource(),
+                    world.resource(),
+                    world.resource(),
+                    world.resource(),
+                );
+                map.set_tile(tx, ty, Layer::Fg, TileId::AIR, &ctx);
+                map.set_liquid(tx, ty, cell, &ctx);
+            });
+    }
+
+    /// Test app with a pool of `def` at `POOL`, camera centred on it.
+    fn pool_app(def: LiquidDef, level: f32) -> App {
+        let mut app = emitter_app(&[POOL], 0, |_| {});
+        app.insert_resource(LiquidRegistry::from_defs(vec![def]));
+        set_pool(&mut app, Some(level));
+        app
+    }
+
+    #[test]
+    fn lava_emits_by_level_and_clears_when_removed() {
+        let mut app = pool_app(lava(), 1.0);
+        app.update();
+        let full = emissive_at(&app, POOL);
+        assert!(full[0] > full[2] && full[2] > 0.0, "warm: {full:?}");
+
+        set_pool(&mut app, Some(0.5));
+        app.update();
+        let half = emissive_at(&app, POOL);
+        assert!((half[0] - full[0] * 0.5).abs() < 1e-4, "{half:?}");
+
+        // Emission is re-read every frame, no grid rebuild needed.
+        set_pool(&mut app, None);
+        app.update();
+        assert_eq!(emissive_at(&app, POOL), [0.0; 4]);
+    }
+
+    #[test]
+    fn water_is_dark_unless_tinted() {
+        let mut app = pool_app(water(), 1.0);
+        app.update();
+        assert_eq!(emissive_at(&app, POOL), [0.0; 4]);
+
+        app.insert_resource(LiquidGlow {
+            tint: 0.1,
+            ..default()
+        });
+        app.update();
+        let e = emissive_at(&app, POOL);
+        assert!(e[2] > e[0] && e[0] > 0.0, "faint blue glow: {e:?}");
+    }
+
+    #[test]
+    fn liquid_relight_is_throttled() {
+        let mut relight = LiquidRelight::default();
+        assert!(!relight.tick(1.0, 0.1, false), "nothing requested");
+
+        relight.request();
+        assert!(relight.tick(0.2, 0.1, false));
+        // Flowing liquid requests every tick; rebuilds stay interval apart.
+        relight.request();
+        assert!(!relight.tick(0.05, 0.1, false));
+        relight.request();
+        assert!(relight.tick(0.05, 0.1, false));
+    }
+
+    #[test]
+    fn liquid_relight_absorbed_by_other_rebuild() {
+        let mut relight = LiquidRelight::default();
+        relight.request();
+        assert!(!relight.tick(0.2, 0.1, true));
+        assert!(!relight.tick(0.2, 0.1, false), "request was covered");
+    }
+
+    // -----------------------------------------------------------------------
+    // Resize debounce
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn resize_debounce_adopts_first_size() {
+        let mut debounce = RcResizeDebounce::default();
+        let size = UVec2::new(168, 152);
+        assert_eq!(debounce.update(size, 3), size);
+        assert_eq!(debounce.update(size, 3), size);
+    }
+
+    #[test]
+    fn resize_debounce_commits_after_stable_frames() {
+        let mut debounce = RcResizeDebounce::default();
+        let old = UVec2::new(168, 152);
+        let new = UVec2::new(200, 152);
+        debounce.update(old, 3);
+
+        assert_eq!(debounce.update(new, 3), old);
+        assert_eq!(debounce.update(new, 3), old);
+        assert_eq!(debounce.update(new, 3), new);
+        assert_eq!(debounce.update(new, 3), new);
+    }
+
+    #[test]
+    fn resize_debounce_restarts_while_size_keeps_changing() {
+        let mut debounce = RcResizeDebounce::default();
+        let old = UVec2::new(168, 152);
+        debounce.update(old, 3);
+
+        // A drag that changes size every frame never reallocates.
+        for w in (172..260).step_by(4) {
+            assert_eq!(debounce.update(UVec2::new(w, 152), 3), old);
+        }
+        let last = UVec2::new(256, 152);
+        assert_eq!(debounce.update(last, 3), old);
+        assert_eq!(debounce.update(last, 3), last);
+    }
+
+    #[test]
+    fn resize_debounce_cancels_when_size_returns() {
+        let mut debounce = RcResizeDebounce::default();
+        let old = UVec2::new(168, 152);
+        let new = UVec2::new(200, 152);
+        debounce.update(old, 3);
+
+        debounce.update(new, 3);
+        debounce.update(new, 3);
+        assert_eq!(debounce.update(old, 3), old);
+        // The pending count restarted: two more frames are not enough.
+        assert_eq!(debounce.update(new, 3), old);
+        assert_eq!(debounce.update(new, 3), old);
+        assert_eq!(debounce.update(new, 3), new);
+    }
+
+    #[test]
+    fn settling_grid_keeps_size_and_covers_viewport() {
+        let mut app = lamp_app(0);
+        app.update();
+        let size = app.world().resource::<RcLightingConfig>().input_size;
+
+        // Shrink the view (zoom in): the grid keeps its size, the extra
+        // becomes padding, and the viewport stays inside the grid.
+        let mut projection = app
+            .world_mut()
+            .query::<&mut Projection>()
+            .single_mut(app.world_mut())
+            .unwrap();
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = 0.5;
+        }
+        app.update();
+        let config = app.world().resource::<RcLightingConfig>().clone();
+        assert_eq!(config.input_size, size);
+        assert!(config.viewport_offset.x + config.viewport_size.x <= size.x);
+        assert!(config.viewport_offset.y + config.viewport_size.y <= size.y);
+
+        for _ in 0..RC_RESIZE_SETTLE_FRAMES {
+            app.update();
+        }
+        let settled = app.world().resource::<RcLightingConfig>().input_size;
+        assert!(settled.x < size.x && settled.y < size.y);
+        assert_eq!(app.world().resource::<RcInputData>().width, settled.x);
+    }
+
+    // -----------------------------------------------------------------------
+    // Directional emitters
+    // -----------------------------------------------------------------------
+
+    use crate::registry::tile::LightDirection;
+
+    /// Clear the foreground in a 3×9 column around `LAMP_TILE`, keeping the
+    /// lamp itself.
+    fn open_column(app: &mut App) {
+        let (tx, ty) = LAMP_TILE;
+        app.world_mut().resource_scope(|world, mut map: Mut<WorldMap>| {
+            let ctx = fixtures::make_ctx(
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+                world.resource(),
+            );
+            for y in ty - 4..=ty + 4 {
+                for x in tx - 1..=tx + 1 {
+                    if (x, y) != LAMP_TILE {
+                        map.set_tile(x, y, Layer::Fg, TileId::AIR, &ctx);
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn cone_tile_lights_only_its_footprint() {
+        let mut app = emitter_app(&[LAMP_TILE], 0, |lamp| {
+            lamp.light_emission = [255, 255, 255];
+            lamp.light_cone = Some(LightCone {
+                direction: LightDirection::Down,
+                half_angle: 30.0,
+            });
+        });
+        open_column(&mut app);
+        app.update();
+
+        let (tx, ty) = LAMP_TILE;
+        let below = emissive_at(&app, (tx, ty - 2));
+        assert!(below[0] > 0.0, "cone should seed below: {below:?}");
+        assert_eq!(emissive_at(&app, (tx, ty + 2)), [0.0; 4]);
+        assert!(
+            lamp_emissive(&app)[0] < below[0],
+            "the cone outshines the lamp"
+        );
+    }
+
+    #[test]
+    fn cone_lights_seed_the_emissive_buffer() {
+        let mut app = lamp_app(TILE_STATE_OFF);
+        open_column(&mut app);
+        app.insert_resource(ConeLights(vec![ConeLight {
+            tile: IVec2::new(LAMP_TILE.0, LAMP_TILE.1 + 1),
+            emission: [255, 240, 200],
+            cone: LightCone {
+                direction: LightDirection::Up,
+                half_angle: 25.0,
+            },
+        }]));
+        app.update();
+
+        let (tx, ty) = LAMP_TILE;
+        assert!(emissive_at(&app, (tx, ty + 3))[0] > 0.0);
+        assert_eq!(emissive_at(&app, (tx, ty - 2)), [0.0; 4]);
+    }
 }
@@ -10,6 +10,10 @@
 //!    cascade/lightmap textures when dimensions change.
 //! 3. `prepare_rc_bind_groups` creates per-cascade and finalize bind groups.
 //! 4. `RcComputeNode` dispatches cascades (high → low) then finalize.
+//!
+//! `track_rc_pipelines` follows both pipelines through shader hot reloads
+//! (see [`super::shader_reload`]): the node dispatches the last pipeline that
+//! compiled, so editing a shader never blanks the lighting.
 
 use std::borrow::Cow;
 
@@ -25,15 +29,19 @@ use bevy::render::render_resource::binding_types::{
 use bevy::render::render_resource::{
     encase, BindGroup, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
     BufferInitDescriptor, BufferUsages, CachedComputePipelineId, ComputePassDescriptor,
-    ComputePipelineDescriptor, Extent3d, Origin3d, PipelineCache, ShaderStages, ShaderType,
-    StorageTextureAccess, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+    ComputePipeline, ComputePipelineDescriptor, Extent3d, Origin3d, PipelineCache, ShaderStages,
+    ShaderType, StorageTextureAccess, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
     TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
 };
 use bevy::render::renderer::{RenderContext, RenderDevice, RenderQueue};
 use bevy::render::texture::GpuImage;
 use bevy::render::{Render, RenderApp, RenderStartup, RenderSystems};
+use bevy::shader::Shader;
 
 use super::rc_lighting::{RcInputData, RcLightingConfig};
+use super::shader_reload::{
+    self, PipelineSlot, ShaderEdits, ShaderErrors, CASCADE_SHADER, FINALIZE_SHADER,
+};
 
 // ---------------------------------------------------------------------------
 // GPU uniform structs — must match WGSL layout exactly (64 bytes each)
@@ -48,11 +56,11 @@ struct RcUniformsGpu {
     viewport_offset: UVec2,
     viewport_size: UVec2,
     bounce_damping: f32,
-    _pad0: f32,
+    shadow_softness: f32,
     grid_origin: IVec2,
     bounce_offset: IVec2,
     sun_color: Vec3,
-    _pad1: f32,
+    sdf_max_distance: f32,
 }
 
 /// Uniforms for the finalize compute shader (`rc_finalize.wgsl`).
@@ -61,6 +69,7 @@ struct FinalizeUniformsGpu {
     input_size: UVec2,
     viewport_offset: UVec2,
     viewport_size: UVec2,
+    sky_ambient: Vec3,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,6 +84,8 @@ struct FinalizeUniformsGpu {
 #[derive(Resource, Clone, ExtractResource)]
 pub struct RcGpuImages {
     pub density: Handle<Image>,
+    /// Distance-to-occluder field derived from `density`.
+    pub sdf: Handle<Image>,
     pub emissive: Handle<Image>,
     pub albedo: Handle<Image>,
     /// Double-buffer A for cascade storage.
@@ -98,6 +109,17 @@ struct RcPipeline {
     finalize_layout: BindGroupLayoutDescriptor,
     cascade_pipeline: CachedComputePipelineId,
     finalize_pipeline: CachedComputePipelineId,
+    cascade_shader: AssetId<Shader>,
+    finalize_shader: AssetId<Shader>,
+}
+
+/// Pipelines the compute node dispatches, kept across shader reloads.
+#[derive(Resource, Default)]
+struct RcPipelineSlots {
+    cascade: PipelineSlot<ComputePipeline>,
+    finalize: PipelineSlot<ComputePipeline>,
+    /// Shader edits already applied to the slots.
+    seen_edits: (u32, u32),
 }
 
 /// Per-frame bind groups rebuilt in `prepare_rc_bind_groups`.
@@ -136,10 +158,12 @@ pub(crate) fn setup_render_pipeline(app: &mut App) {
     render_app
         .init_resource::<RcBindGroups>()
         .init_resource::<RcTextureMeta>()
+        .init_resource::<RcPipelineSlots>()
         .add_systems(RenderStartup, init_rc_pipeline)
         .add_systems(
             Render,
             (
+                track_rc_pipelines.in_set(RenderSystems::PrepareResources),
                 prepare_rc_textures.in_set(RenderSystems::PrepareResources),
                 prepare_rc_bind_groups.in_set(RenderSystems::PrepareBindGroups),
             ),
@@ -162,8 +186,8 @@ fn init_rc_pipeline(
     pipeline_cache: Res<PipelineCache>,
     asset_server: Res<AssetServer>,
 ) {
-    let cascade_shader = asset_server.load("engine/shaders/radiance_cascades.wgsl");
-    let finalize_shader = asset_server.load("engine/shaders/rc_finalize.wgsl");
+    let cascade_shader: Handle<Shader> = asset_server.load(CASCADE_SHADER);
+    let finalize_shader: Handle<Shader> = asset_server.load(FINALIZE_SHADER);
 
     // --- Cascade bind group layout (matches radiance_cascades.wgsl @group(0)) ---
     let cascade_layout = BindGroupLayoutDescriptor::new(
@@ -178,6 +202,7 @@ fn init_rc_pipeline(
                 texture_2d(TextureSampleType::Float { filterable: false }), // @binding(4) lightmap_prev
                 texture_2d(TextureSampleType::Float { filterable: false }), // @binding(5) cascade_read
                 texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly), // @binding(6)
+                texture_2d(TextureSampleType::Float { filterable: false }), // @binding(7) sdf
             ),
         ),
     );
@@ -196,6 +221,8 @@ fn init_rc_pipeline(
     );
 
     // --- Queue compute pipelines ---
+    let cascade_shader_id = cascade_shader.id();
+    let finalize_shader_id = finalize_shader.id();
     let cascade_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
         label: Some("rc_cascade_pipeline".into()),
         layout: vec![cascade_layout.clone()],
@@ -217,9 +244,63 @@ fn init_rc_pipeline(
         finalize_layout,
         cascade_pipeline,
         finalize_pipeline,
+        cascade_shader: cascade_shader_id,
+        finalize_shader: finalize_shader_id,
     });
 }
 
+// ---------------------------------------------------------------------------
+// Prepare: follow pipelines through shader reloads
+// ---------------------------------------------------------------------------
+
+fn track_rc_pipelines(
+    pipeline: Option<Res<RcPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    edits: Option<Res<ShaderEdits>>,
+    errors: Option<Res<ShaderErrors>>,
+    mut slots: ResMut<RcPipelineSlots>,
+) {
+    let Some(pipeline) = pipeline else {
+        return;
+    };
+    let slots = slots.as_mut();
+
+    if let Some(edits) = edits {
+        let counts = (
+            edits.count(pipeline.cascade_shader),
+            edits.count(pipeline.finalize_shader),
+        );
+        if counts.0 != slots.seen_edits.0 {
+            slots.cascade.shader_changed();
+        }
+        if counts.1 != slots.seen_edits.1 {
+            slots.finalize.shader_changed();
+        }
+        slots.seen_edits = counts;
+    }
+
+    for (shader, slot, id) in [
+        (
+            CASCADE_SHADER,
+            &mut slots.cascade,
+            pipeline.cascade_pipeline,
+        ),
+        (
+            FINALIZE_SHADER,
+            &mut slots.finalize,
+            pipeline.finalize_pipeline,
+        ),
+    ] {
+        let status = shader_reload::compute_status(&pipeline_cache, id);
+        if let Some(change) = slot.observe(status) {
+            shader_reload::log_slot_change(shader, change, slot);
+            if let Some(errors) = &errors {
+                errors.set(shader, slot.error());
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Prepare: upload CPU data to GPU textures
 // ---------------------------------------------------------------------------
@@ -286,6 +367,28 @@ fn prepare_rc_textures(
         );
     }
 
+    // Upload SDF (R8Unorm — same layout as density)
+    if let Some(gpu_img) = gpu_images.get(&handles.sdf)
+        && input.sdf.len() == input.density.len()
+    {
+        let aligned_bpr = pad_rows_into(&mut pad_buf, &input.sdf, w, h);
+        render_queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &gpu_img.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            &pad_buf,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(aligned_bpr),
+                rows_per_image: Some(h),
+            },
+            extent,
+        );
+    }
+
     // Upload emissive (Rgba16Float — 8 bytes per texel)
     if let Some(gpu_img) = gpu_images.get(&handles.emissive) {
         emissive_to_f16_bytes_into(&mut emissive_buf, &input.emissive);
@@ -425,6 +528,7 @@ fn prepare_rc_bind_groups(
     // Resolve all GPU image views
     let (
         Some(density),
+        Some(sdf),
         Some(emissive),
         Some(albedo),
         Some(cascade_a),
@@ -433,6 +537,7 @@ fn prepare_rc_bind_groups(
         Some(lightmap_prev),
     ) = (
         gpu_images.get(&handles.density),
+        gpu_images.get(&handles.sdf),
         gpu_images.get(&handles.emissive),
         gpu_images.get(&handles.albedo),
         gpu_images.get(&handles.cascade_a),
@@ -486,11 +591,11 @@ fn prepare_rc_bind_groups(
             viewport_offset: config.viewport_offset,
             viewport_size: config.viewport_size,
             bounce_damping: config.bounce_damping,
-            _pad0: 0.0,
+            shadow_softness: config.shadow_softness,
             grid_origin: config.grid_origin,
             bounce_offset: config.bounce_offset,
             sun_color: config.sun_color,
-            _pad1: 0.0,
+            sdf_max_distance: super::rc_sdf::SDF_MAX_DISTANCE,
         };
 
         let mut uniform_buf = encase::UniformBuffer::new(Vec::<u8>::new());
@@ -517,6 +622,7 @@ fn prepare_rc_bind_groups(
                 &lightmap_prev.texture_view,
                 cascade_read_view,
                 write_tex,
+                &sdf.texture_view,
             )),
         );
 
@@ -531,6 +637,7 @@ fn prepare_rc_bind_groups(
         input_size: config.input_size,
         viewport_offset: config.viewport_offset,
         viewport_size: config.viewport_size,
+        sky_ambient: config.sky_ambient,
     };
 
     let mut uniform_buf = encase::UniformBuffer::new(Vec::<u8>::new());
@@ -572,8 +679,7 @@ impl Node for RcComputeNode {
         render_context: &mut RenderContext<'w>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
-        let pipeline_cache = world.resource::<PipelineCache>();
-        let Some(pipeline) = world.get_resource::<RcPipeline>() else {
+        let Some(slots) = world.get_resource::<RcPipelineSlots>() else {
             return Ok(());
         };
         let Some(bind_groups) = world.get_resource::<RcBindGroups>() else {
@@ -588,13 +694,9 @@ impl Node for RcComputeNode {
             return Ok(());
         }
 
-        // Check that pipelines are compiled
-        let Some(cascade_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.cascade_pipeline)
-        else {
-            return Ok(());
-        };
-        let Some(finalize_pipeline) =
-            pipeline_cache.get_compute_pipeline(pipeline.finalize_pipeline)
+        // Last pipelines that compiled, possibly older than the shader files.
+        let (Some(cascade_pipeline), Some(finalize_pipeline)) =
+            (slots.cascade.active(), slots.finalize.active())
         else {
             return Ok(());
         };
@@ -731,6 +833,7 @@ pub(crate) fn create_gpu_images(images: &mut Assets<Image>) -> RcGpuImages {
     let s = 64;
     RcGpuImages {
         density: make_gpu_texture(images, s, s, TextureFormat::R8Unorm),
+        sdf: make_gpu_texture(images, s, s, TextureFormat::R8Unorm),
         emissive: make_gpu_texture(images, s, s, TextureFormat::Rgba16Float),
         albedo: make_gpu_texture(images, s, s, TextureFormat::Rgba8Unorm),
         cascade_a: make_gpu_texture(images, s * 4, s * 4, TextureFormat::Rgba16Float),
@@ -780,6 +883,7 @@ pub(crate) fn resize_gpu_textures(
 
     // Recreate input textures at new size
     gpu_images.density = make_gpu_texture(&mut images, input_w, input_h, TextureFormat::R8Unorm);
+    gpu_images.sdf = make_gpu_texture(&mut images, input_w, input_h, TextureFormat::R8Unorm);
     gpu_images.emissive =
         make_gpu_texture(&mut images, input_w, input_h, TextureFormat::Rgba16Float);
     gpu_images.albedo = make_gpu_texture(&mut images, input_w, input_h, TextureFormat::Rgba8Unorm);
@@ -0,0 +1,317 @@
+//! Distance field over the RC density grid.
+//!
+//! Each texel stores the Euclidean distance, in tiles, from its centre to the
+//! nearest occluding texel, clamped to [`SDF_MAX_DISTANCE`] and packed into
+//! one byte (0 = inside an occluder, 255 = at least the clamp away). The
+//! radiance cascades shader reads it next to the density texture to widen
+//! shadow penumbras for rays that graze an occluder.
+//!
+//! A texel's clamped distance only depends on occluders within the clamp
+//! radius, so when tiles change under a still camera only a band around the
+//! changed texels is recomputed. Anything larger than
+//! [`SDF_BAND_BUDGET`] texels, a moved grid, or a resize falls back to a
+//! full pass.
+
+use bevy::prelude::*;
+
+/// Distance (tiles) at which the field saturates.
+pub const SDF_MAX_DISTANCE: f32 = 8.0;
+/// Density at or above which a texel counts as an occluder.
+pub const SDF_SOLID_THRESHOLD: u8 = 128;
+/// Most texels an incremental update may recompute in one frame before a
+/// full recompute is cheaper.
+pub const SDF_BAND_BUDGET: usize = 16_384;
+
+/// Search radius in whole texels that covers [`SDF_MAX_DISTANCE`].
+const RADIUS: usize = SDF_MAX_DISTANCE as usize;
+
+#[inline]
+fn is_occluder(density: u8) -> bool {
+    density >= SDF_SOLID_THRESHOLD
+}
+
+/// Pack a squared texel distance into the R8 range.
+pub fn encode_distance(dist_sq: u32) -> u8 {
+    let d = (dist_sq as f32).sqrt().min(SDF_MAX_DISTANCE);
+    (d / SDF_MAX_DISTANCE * 255.0).round() as u8
+}
+
+/// Recompute the texels in `[x0, x1) × [y0, y1)` of a `w × h` grid.
+///
+/// Exact separable Euclidean transform: a row pass finds each texel's
+/// horizontal distance to the nearest occluder, then a column pass takes
+/// the minimum of `gx² + dy²` over the rows within the clamp radius.
+/// Texels outside the grid count as open.
+fn fill_rect(
+    density: &[u8],
+    w: usize,
+    h: usize,
+    rect: (usize, usize, usize, usize),
+    out: &mut [u8],
+) {
+    let (x0, y0, x1, y1) = rect;
+    if x0 >= x1 || y0 >= y1 {
+        return;
+    }
+    let far = RADIUS + 1;
+    let cols = x1 - x0;
+    let ry0 = y0.saturating_sub(RADIUS);
+    let ry1 = (y1 + RADIUS).min(h);
+    let sx0 = x0.saturating_sub(RADIUS);
+    let sx1 = (x1 + RADIUS).min(w);
+
+    // Horizontal distance per row of the band, for the rect's columns only.
+    let mut gx = vec![far; (ry1 - ry0) * cols];
+    for y in ry0..ry1 {
+        let row = &density[y * w..(y + 1) * w];
+        let g_row = &mut gx[(y - ry0) * cols..(y - ry0 + 1) * cols];
+        let mut last = None;
+        for (x, &d) in row.iter().enumerate().take(x1).skip(sx0) {
+            if is_occluder(d) {
+                last = Some(x);
+            }
+            if x >= x0
+                && let Some(l) = last
+            {
+                g_row[x - x0] = (x - l).min(far);
+            }
+        }
+        let mut next = None;
+        for x in (x0..sx1).rev() {
+            if is_occluder(row[x]) {
+                next = Some(x);
+            }
+            if x < x1
+                && let Some(n) = next
+            {
+                g_row[x - x0] = g_row[x - x0].min(n - x);
+            }
+        }
+    }
+
+    for y in y0..y1 {
+        let wy0 = y.saturating_sub(RADIUS).max(ry0);
+        let wy1 = (y + RADIUS + 1).min(ry1);
+        for x in x0..x1 {
+            let mut best = u32::MAX;
+            for yy in wy0..wy1 {
+                let g = gx[(yy - ry0) * cols + (x - x0)];
+                if g >= far {
+                    continue;
+                }
+                let dy = y.abs_diff(yy);
+                best = best.min((g * g + dy * dy) as u32);
+            }
+            out[y * w + x] = encode_distance(best);
+        }
+    }
+}
+
+/// Build the whole field for a `w × h` density grid into `out`.
+pub fn compute_sdf(density: &[u8], w: usize, h: usize, out: &mut Vec<u8>) {
+    out.resize(w * h, 255);
+    fill_rect(density, w, h, (0, 0, w, h), out);
+}
+
+/// What [`RcSdf::update`] had to do this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdfUpdate {
+    /// No occluder changed; the previous field was kept.
+    Unchanged,
+    /// Only a band of this many texels was recomputed.
+    Band(usize),
+    /// The whole field was recomputed.
+    Full,
+}
+
+/// Incremental SDF state, kept between frames by `extract_lighting_data`.
+#[derive(Default)]
+pub struct RcSdf {
+    prev_density: Vec<u8>,
+    origin: IVec2,
+    size: UVec2,
+}
+
+impl RcSdf {
+    /// Bring `out` up to date with `density` for a grid of `size` texels
+    /// whose world origin is `origin`.
+    ///
+    /// `out` must hold the field produced by the previous call for the band
+    /// path to be taken; if its length doesn't match the grid it is rebuilt.
+    pub fn update(
+        &mut self,
+        density: &[u8],
+        size: UVec2,
+        origin: IVec2,
+        out: &mut Vec<u8>,
+    ) -> SdfUpdate {
+        let (w, h) = (size.x as usize, size.y as usize);
+        let reusable = self.size == size
+            && self.origin == origin
+            && self.prev_density.len() == density.len()
+            && out.len() == density.len();
+
+        let result = if !reusable {
+            compute_sdf(density, w, h, out);
+            SdfUpdate::Full
+        } else {
+            // Bounding box of texels whose occluder state flipped.
+            let mut bbox: Option<(usize, usize, usize, usize)> = None;
+            for (idx, (&new, &old)) in density.iter().zip(&self.prev_density).enumerate() {
+                if is_occluder(new) == is_occluder(old) {
+                    continue;
+                }
+                let (x, y) = (idx % w, idx / w);
+                bbox = Some(match bbox {
+                    None => (x, y, x + 1, y + 1),
+                    Some((bx0, by0, bx1, by1)) => {
+                        (bx0.min(x), by0.min(y), bx1.max(x + 1), by1.max(y + 1))
+                    }
+                });
+            }
+            match bbox {
+                None => SdfUpdate::Unchanged,
+                Some((bx0, by0, bx1, by1)) => {
+                    let rect = (
+                        bx0.saturating_sub(RADIUS),
+                        by0.saturating_sub(RADIUS),
+                        (bx1 + RADIUS).min(w),
+                        (by1 + RADIUS).min(h),
+                    );
+                    let area = (rect.2 - rect.0) * (rect.3 - rect.1);
+                    if area > SDF_BAND_BUDGET {
+                        compute_sdf(density, w, h, out);
+                        SdfUpdate::Full
+                    } else {
+                        fill_rect(density, w, h, rect, out);
+                        SdfUpdate::Band(area)
+                    }
+                }
+            }
+        };
+
+        self.prev_density.clear();
+        self.prev_density.extend_from_slice(density);
+        self.origin = origin;
+        self.size = size;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLID: u8 = 255;
+
+    fn grid(w: usize, h: usize, solids: &[(usize, usize)]) -> Vec<u8> {
+        let mut density = vec![0; w * h];
+        for &(x, y) in solids {
+            density[y * w + x] = SOLID;
+        }
+        density
+    }
+
+    #[test]
+    fn distances_are_exact_around_a_single_occluder() {
+        let (w, h) = (7, 7);
+        let density = grid(w, h, &[(3, 3)]);
+        let mut sdf = Vec::new();
+        compute_sdf(&density, w, h, &mut sdf);
+
+        for y in 0..h {
+            for x in 0..w {
+                let dx = x.abs_diff(3) as u32;
+                let dy = y.abs_diff(3) as u32;
+                assert_eq!(
+                    sdf[y * w + x],
+                    encode_distance(dx * dx + dy * dy),
+                    "texel ({x}, {y})"
+                );
+            }
+        }
+        assert_eq!(sdf[3 * w + 3], 0);
+        assert_eq!(sdf[3 * w + 5], 64); // 2 of 8 tiles
+    }
+
+    #[test]
+    fn nearest_of_several_occluders_wins_and_partial_density_is_open() {
+        let (w, h) = (10, 3);
+        let mut density = grid(w, h, &[(0, 1), (9, 1)]);
+        density[w + 4] = SDF_SOLID_THRESHOLD - 1;
+        let mut sdf = Vec::new();
+        compute_sdf(&density, w, h, &mut sdf);
+
+        // Middle row: distance to the nearer end.
+        for x in 0..w {
+            let d = x.min(9 - x) as u32;
+            assert_eq!(sdf[w + x], encode_distance(d * d), "x = {x}");
+        }
+        // Diagonal from (0, 1) to (2, 0).
+        assert_eq!(sdf[2], encode_distance(5));
+    }
+
+    #[test]
+    fn distances_clamp_and_empty_grids_saturate() {
+        let (w, h) = (24, 1);
+        let density = grid(w, h, &[(0, 0)]);
+        let mut sdf = Vec::new();
+        compute_sdf(&density, w, h, &mut sdf);
+        assert_eq!(sdf[8], 255);
+        assert_eq!(sdf[20], 255);
+        assert!(sdf[7] < 255);
+
+        let empty = vec![0; 16];
+        compute_sdf(&empty, 4, 4, &mut sdf);
+        assert!(sdf.iter().all(|&v| v == 255));
+    }
+
+    #[test]
+    fn incremental_update_matches_full_recompute() {
+        let (w, h) = (64usize, 48usize);
+        let size = UVec2::new(w as u32, h as u32);
+        let origin = IVec2::new(-32, 100);
+        // Deterministic terrain: ground below a wavy line plus scattered blocks.
+        let mut density: Vec<u8> = (0..w * h)
+            .map(|i| {
+                let (x, y) = (i % w, i / w);
+                let ground = 30 + (x * 7 % 5);
+                if y >= ground || (x * 31 + y * 17) % 23 == 0 {
+                    SOLID
+                } else {
+                    0
+                }
+            })
+            .collect();
+
+        let mut state = RcSdf::default();
+        let mut sdf = Vec::new();
+        assert_eq!(
+            state.update(&density, size, origin, &mut sdf),
+            SdfUpdate::Full
+        );
+        assert_eq!(
+            state.update(&density, size, origin, &mut sdf),
+            SdfUpdate::Unchanged
+        );
+
+        // Dig a small tunnel and place a block elsewhere.
+        for x in 20..26 {
+            density[32 * w + x] = 0;
+        }
+        density[10 * w + 12] = SOLID;
+        let update = state.update(&density, size, origin, &mut sdf);
+        assert!(matches!(update, SdfUpdate::Band(_)), "{update:?}");
+
+        let mut full = Vec::new();
+        compute_sdf(&density, w, h, &mut full);
+        assert_eq!(sdf, full);
+
+        // A moved grid always rebuilds from scratch.
+        assert_eq!(
+            state.update(&density, size, origin + IVec2::X, &mut sdf),
+            SdfUpdate::Full
+        );
+        assert_eq!(sdf, full);
+    }
+}
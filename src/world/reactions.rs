@@ -0,0 +1,805 @@
+//! Data-driven reactions to tile changes, read from `content/reactions.ron`.
+//!
+//! Every [`TileChanged`] becomes work for the changed tile (placed, or
+//! broken when it is now air) and for its four neighbours (neighbour
+//! changed). A [`ReactionRule`] fires on work whose trigger it lists when
+//! its tile and conditions match, and runs its actions: replace the tile,
+//! spawn an item, burst particles, send a [`ReactionSound`], or start a
+//! timer on the tile that fires [`ReactionTrigger::Timer`] later (see
+//! [`ReactionScheduler`]). At most [`ReactionRules::budget`] actions run a
+//! frame; the rest of the work stays queued for the next one. Tiles a
+//! reaction replaces are reported like any other change, so reactions chain
+//! a frame apart.
+//!
+//! The change message doesn't say what the tile was, so rules for broken
+//! tiles match on their surroundings only.
+
+use std::collections::VecDeque;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::cosmos::persistence::DirtyChunks;
+use crate::interaction::block_action::spawn_dropped_item;
+use crate::item::dropped_item::SpawnParams;
+use crate::item::{DroppedItemLimits, ItemRegistry};
+use crate::liquid::registry::LiquidRegistry;
+use crate::particles::pool::ParticlePool;
+use crate::registry::tile::{TileId, TileMaterial};
+use crate::ui::game_ui::icon_registry::ItemIconRegistry;
+use crate::world::chunk::{update_bitmasks_around, Layer, LoadedChunks, TileChanged, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::growth::mark_meshes_dirty;
+use crate::world::lit_sprite::{
+    FallbackItemImage, FallbackLightmap, LitSpriteMaterial, SharedLitQuad,
+};
+use crate::world::rc_lighting::RcGridDirty;
+
+/// Actions run per frame unless the rules file says.
+const DEFAULT_BUDGET: usize = 64;
+
+/// The 4 neighbours whose changes a tile reacts to.
+const NEIGHBOURS_4: [(i32, i32); 4] = [(0, 1), (0, -1), (-1, 0), (1, 0)];
+
+/// What a rule reacts to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub enum ReactionTrigger {
+    /// A foreground tile was placed here.
+    Placed,
+    /// The foreground tile here was removed.
+    Broken,
+    /// One of the four neighbouring foreground tiles changed.
+    NeighborChanged,
+    /// A timer of this name started on this tile by
+    /// [`ReactionAction::After`] ran out.
+    Timer(String),
+}
+
+/// Which tiles a rule applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum TileMatch {
+    Id(String),
+    Material(TileMaterial),
+}
+
+/// Surroundings a rule needs. Empty lists and unset fields always pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ReactionConditions {
+    /// Layer the tile and its neighbour tiles are read from; foreground
+    /// when unset.
+    pub layer: Option<Layer>,
+    /// At least one of the four neighbours is one of these tiles.
+    pub adjacent_tiles: Vec<String>,
+    /// None of the four neighbours is one of these tiles.
+    pub no_adjacent_tiles: Vec<String>,
+    /// At least one of the four neighbours holds one of these liquids.
+    pub adjacent_liquids: Vec<String>,
+    /// None of the four neighbours holds one of these liquids.
+    pub no_adjacent_liquids: Vec<String>,
+    /// Tiles below the surface (negative above it), inclusive.
+    pub depth: Option<(i32, i32)>,
+}
+
+/// Something a rule does to the tile it fired on.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum ReactionAction {
+    /// Put this tile in the rule's layer.
+    Replace(String),
+    /// Drop `count` of `item` from the tile.
+    SpawnItem { item: String, count: u16 },
+    /// Burst of `count` particles of `color` (linear RGB 0.0–1.0).
+    Particles { color: [f32; 3], count: u32 },
+    /// Send a [`ReactionSound`].
+    Sound(String),
+    /// Fire [`ReactionTrigger::Timer`] `timer` on the tile in `secs`.
+    After { secs: f32, timer: String },
+}
+
+/// One reaction: when a listed trigger hits a matching tile whose
+/// conditions hold, run the actions in order.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ReactionRule {
+    pub id: String,
+    pub triggers: Vec<ReactionTrigger>,
+    /// Tile the rule applies to; any when unset.
+    #[serde(default)]
+    pub tile: Option<TileMatch>,
+    #[serde(default)]
+    pub conditions: ReactionConditions,
+    pub actions: Vec<ReactionAction>,
+}
+
+impl ReactionRule {
+    fn layer(&self) -> Layer {
+        self.conditions.layer.unwrap_or(Layer::Fg)
+    }
+
+    /// Whether the tile at (`tile_x`, `tile_y`) and its surroundings match
+    /// this rule.
+    pub fn matches(
+        &self,
+        tile_x: i32,
+        tile_y: i32,
+        world_map: &WorldMap,
+        liquids: &LiquidRegistry,
+        ctx: &WorldCtxRef,
+    ) -> bool {
+        let layer = self.layer();
+        let conditions = &self.conditions;
+        if let Some(wanted) = &self.tile {
+            let Some(tile) = world_map.get_tile(tile_x, tile_y, layer, ctx) else {
+                return false;
+            };
+            let def = ctx.tile_registry.get(tile);
+            let matched = match wanted {
+                TileMatch::Id(id) => def.id == *id,
+                TileMatch::Material(material) => def.material == *material,
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if let Some((min, max)) = conditions.depth {
+            let surface = ctx
+                .noise_cache
+                .surface_height_at(tile_x, ctx.config, ctx.planet_config);
+            if !(min..=max).contains(&(surface - tile_y)) {
+                return false;
+            }
+        }
+
+        let neighbours = NEIGHBOURS_4.map(|(dx, dy)| (tile_x + dx, tile_y + dy));
+        let tiles: Vec<&str> = neighbours
+            .iter()
+            .filter_map(|&(x, y)| world_map.get_tile(x, y, layer, ctx))
+            .map(|tile| ctx.tile_registry.get(tile).id.as_str())
+            .collect();
+        let fluids: Vec<&str> = neighbours
+            .iter()
+            .map(|&(x, y)| world_map.get_liquid(x, y, ctx))
+            .filter(|cell| !cell.is_empty())
+            .filter_map(|cell| liquids.get(cell.liquid_type))
+            .map(|def| def.name.as_str())
+            .collect();
+        any_of(&conditions.adjacent_tiles, &tiles)
+            && none_of(&conditions.no_adjacent_tiles, &tiles)
+            && any_of(&conditions.adjacent_liquids, &fluids)
+            && none_of(&conditions.no_adjacent_liquids, &fluids)
+    }
+}
+
+fn any_of(wanted: &[String], found: &[&str]) -> bool {
+    wanted.is_empty() || wanted.iter().any(|name| found.contains(&name.as_str()))
+}
+
+fn none_of(banned: &[String], found: &[&str]) -> bool {
+    !banned.iter().any(|name| found.contains(&name.as_str()))
+}
+
+/// Asset loaded from `reactions.ron`, inserted as a resource once loaded.
+#[derive(Asset, TypePath, Resource, Debug, Clone, Deserialize)]
+pub struct ReactionRules {
+    /// Most actions run in one frame.
+    #[serde(default = "default_budget")]
+    pub budget: usize,
+    pub rules: Vec<ReactionRule>,
+}
+
+fn default_budget() -> usize {
+    DEFAULT_BUDGET
+}
+
+impl Default for ReactionRules {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_BUDGET,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl ReactionRules {
+    /// Rules `work` fires, in file order.
+    pub fn fired_by<'r>(
+        &'r self,
+        work: &ReactionWork,
+        world_map: &WorldMap,
+        liquids: &LiquidRegistry,
+        ctx: &WorldCtxRef,
+    ) -> Vec<&'r ReactionRule> {
+        let (tile_x, tile_y) = work.tile;
+        self.rules
+            .iter()
+            .filter(|rule| rule.triggers.contains(&work.trigger))
+            .filter(|rule| rule.matches(tile_x, tile_y, world_map, liquids, ctx))
+            .collect()
+    }
+}
+
+/// A trigger to check the rules for at a tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionWork {
+    pub tile: (i32, i32),
+    pub trigger: ReactionTrigger,
+}
+
+/// Work waiting for action budget, oldest first.
+#[derive(Resource, Debug, Default)]
+pub struct ReactionQueue {
+    pending: VecDeque<ReactionWork>,
+}
+
+impl ReactionQueue {
+    pub fn push(&mut self, work: ReactionWork) {
+        self.pending.push_back(work);
+    }
+
+    pub fn front(&self) -> Option<&ReactionWork> {
+        self.pending.front()
+    }
+
+    /// Take the next work if its `cost` in actions fits in what is left of
+    /// `budget` after `spent`, and count it. The first work of a frame
+    /// always goes, so one costly rule can't stall the queue.
+    pub fn pop_within(
+        &mut self,
+        cost: usize,
+        spent: &mut usize,
+        budget: usize,
+    ) -> Option<ReactionWork> {
+        if *spent > 0 && *spent + cost > budget {
+            return None;
+        }
+        let work = self.pending.pop_front()?;
+        *spent += cost;
+        Some(work)
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Timers started by [`ReactionAction::After`], by when they run out.
+#[derive(Resource, Debug, Default)]
+pub struct ReactionScheduler {
+    /// Sorted by due time; timers due at the same time keep the order they
+    /// were started in.
+    timers: Vec<(f64, ReactionWork)>,
+}
+
+impl ReactionScheduler {
+    /// Queue `work` for when the game clock reaches `due` seconds.
+    pub fn schedule(&mut self, due: f64, work: ReactionWork) {
+        let at = self.timers.partition_point(|(other, _)| *other <= due);
+        self.timers.insert(at, (due, work));
+    }
+
+    /// Remove and return the work due by `now`, earliest first.
+    pub fn pop_due(&mut self, now: f64) -> Vec<ReactionWork> {
+        let due = self.timers.partition_point(|(at, _)| *at <= now);
+        self.timers.drain(..due).map(|(_, work)| work).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+}
+
+/// A reaction asked for a sound at a tile.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct ReactionSound {
+    pub sound: String,
+    pub tile: (i32, i32),
+}
+
+/// Queue reactions to this frame's tile changes and to the timers that ran
+/// out.
+pub fn queue_tile_reactions(
+    time: Res<Time>,
+    ctx: WorldCtx,
+    world_map: Res<WorldMap>,
+    mut changes: MessageReader<TileChanged>,
+    mut queue: ResMut<ReactionQueue>,
+    mut scheduler: ResMut<ReactionScheduler>,
+) {
+    let ctx_ref = ctx.as_ref();
+    for change in changes.read() {
+        let (tile_x, tile_y) = (ctx_ref.config.wrap_tile_x(change.tile_x), change.tile_y);
+        let broken = world_map.get_tile(tile_x, tile_y, Layer::Fg, &ctx_ref) == Some(TileId::AIR);
+        queue.push(ReactionWork {
+            tile: (tile_x, tile_y),
+            trigger: if broken {
+                ReactionTrigger::Broken
+            } else {
+                ReactionTrigger::Placed
+            },
+        });
+        for (dx, dy) in NEIGHBOURS_4 {
+            queue.push(ReactionWork {
+                tile: (ctx_ref.config.wrap_tile_x(tile_x + dx), tile_y + dy),
+                trigger: ReactionTrigger::NeighborChanged,
+            });
+        }
+    }
+    for work in scheduler.pop_due(time.elapsed_secs_f64()) {
+        queue.push(work);
+    }
+}
+
+/// What dropping an item needs; all of it exists once in game.
+#[derive(SystemParam)]
+pub struct ReactionDrops<'w> {
+    item_registry: Option<Res<'w, ItemRegistry>>,
+    limits: Option<Res<'w, DroppedItemLimits>>,
+    icons: Option<Res<'w, ItemIconRegistry>>,
+    quad: Option<Res<'w, SharedLitQuad>>,
+    fallback_lm: Option<Res<'w, FallbackLightmap>>,
+    fallback_img: Option<Res<'w, FallbackItemImage>>,
+    lit_materials: Option<ResMut<'w, Assets<LitSpriteMaterial>>>,
+}
+
+/// Where reaction actions leave their marks besides the tiles.
+#[derive(SystemParam)]
+pub struct ReactionEffects<'w, 's> {
+    commands: Commands<'w, 's>,
+    loaded_chunks: Res<'w, LoadedChunks>,
+    dirty_chunks: ResMut<'w, DirtyChunks>,
+    tile_changes: MessageWriter<'w, TileChanged>,
+    rc_dirty: Option<ResMut<'w, RcGridDirty>>,
+    particles: Option<ResMut<'w, ParticlePool>>,
+    sounds: MessageWriter<'w, ReactionSound>,
+    drops: ReactionDrops<'w>,
+}
+
+impl ReactionEffects<'_, '_> {
+    fn replace(
+        &mut self,
+        (tile_x, tile_y): (i32, i32),
+        layer: Layer,
+        name: &str,
+        world_map: &mut WorldMap,
+        ctx: &WorldCtxRef,
+    ) {
+        let Some(tile) = ctx.tile_registry.try_by_name(name) else {
+            warn!("Reaction replaces with unknown tile '{name}'");
+            return;
+        };
+        if world_map.get_tile(tile_x, tile_y, layer, ctx) == Some(tile) {
+            return;
+        }
+        world_map.set_tile(tile_x, tile_y, layer, tile, ctx);
+        for changed in update_bitmasks_around(world_map, tile_x, tile_y, layer, ctx) {
+            self.dirty_chunks.0.insert(changed);
+            mark_meshes_dirty(&mut self.commands, &self.loaded_chunks, changed, ctx);
+        }
+        if layer == Layer::Fg {
+            self.tile_changes.write(TileChanged { tile_x, tile_y });
+        }
+        if let Some(rc_dirty) = self.rc_dirty.as_deref_mut() {
+            rc_dirty.0 = true;
+        }
+    }
+
+    fn spawn_item(&mut self, at: Vec2, item: &str, count: u16) {
+        let drops = &mut self.drops;
+        let (
+            Some(item_registry),
+            Some(limits),
+            Some(icons),
+            Some(quad),
+            Some(fallback_lm),
+            Some(fallback_img),
+            Some(lit_materials),
+        ) = (
+            drops.item_registry.as_deref(),
+            drops.limits.as_deref(),
+            drops.icons.as_deref(),
+            drops.quad.as_deref(),
+            drops.fallback_lm.as_deref(),
+            drops.fallback_img.as_deref(),
+            drops.lit_materials.as_deref_mut(),
+        )
+        else {
+            return;
+        };
+        spawn_dropped_item(
+            &mut self.commands,
+            item.to_owned(),
+            count,
+            at,
+            SpawnParams::random(at).velocity(),
+            item_registry,
+            limits,
+            icons,
+            quad,
+            fallback_lm,
+            lit_materials,
+            &fallback_img.0,
+        );
+    }
+
+    fn burst(&mut self, at: Vec2, [r, g, b]: [f32; 3], count: u32) {
+        use rand::Rng;
+        let Some(pool) = self.particles.as_deref_mut() else {
+            return;
+        };
+        let mut rng = rand::thread_rng();
+        for _ in 0..count {
+            let velocity = Vec2::new(rng.gen_range(-40.0..40.0), rng.gen_range(10.0..70.0));
+            pool.spawn(at, velocity, 0.6, 4.0, [r, g, b, 1.0], 1.0, true);
+        }
+    }
+}
+
+/// Run queued reactions until this frame's action budget is spent.
+#[allow(clippy::too_many_arguments)]
+pub fn run_tile_reactions(
+    time: Res<Time>,
+    rules: Res<ReactionRules>,
+    ctx: WorldCtx,
+    liquids: Res<LiquidRegistry>,
+    mut world_map: ResMut<WorldMap>,
+    mut queue: ResMut<ReactionQueue>,
+    mut scheduler: ResMut<ReactionScheduler>,
+    mut effects: ReactionEffects,
+) {
+    let ctx_ref = ctx.as_ref();
+    let now = time.elapsed_secs_f64();
+    let tile_size = ctx_ref.config.tile_size;
+    let mut spent = 0;
+    while let Some(work) = queue.front() {
+        let fired = rules.fired_by(work, &world_map, &liquids, &ctx_ref);
+        let cost = fired.iter().map(|rule| rule.actions.len()).sum();
+        let Some(work) = queue.pop_within(cost, &mut spent, rules.budget) else {
+            break;
+        };
+        let (tile_x, tile_y) = work.tile;
+        let center = Vec2::new(
+            (tile_x as f32 + 0.5) * tile_size,
+            (tile_y as f32 + 0.5) * tile_size,
+        );
+        for rule in fired {
+            for action in &rule.actions {
+                match action {
+                    ReactionAction::Replace(name) => {
+                        effects.replace(work.tile, rule.layer(), name, &mut world_map, &ctx_ref);
+                    }
+                    ReactionAction::SpawnItem { item, count } => {
+                        effects.spawn_item(center, item, *count);
+                    }
+                    ReactionAction::Particles { color, count } => {
+                        effects.burst(center, *color, *count);
+                    }
+                    ReactionAction::Sound(sound) => {
+                        effects.sounds.write(ReactionSound {
+                            sound: sound.clone(),
+                            tile: work.tile,
+                        });
+                    }
+                    ReactionAction::After { secs, timer } => scheduler.schedule(
+                        now + *secs as f64,
+                        ReactionWork {
+                            tile: work.tile,
+                            trigger: ReactionTrigger::Timer(timer.clone()),
+                        },
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Drop pending reactions and timers; their tiles belong to the world being
+/// left.
+pub fn clear_pending_reactions(
+    mut queue: ResMut<ReactionQueue>,
+    mut scheduler: ResMut<ReactionScheduler>,
+) {
+    queue.clear();
+    scheduler.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::liquid::registry::LiquidDef;
+    use crate::liquid::LiquidCell;
+    use crate::test_helpers::fixtures;
+
+    const AT: (i32, i32) = (100, 600);
+
+    fn liquids() -> LiquidRegistry {
+        let ron_str = std::fs::read_to_string("assets/worlds/liquids.liquid.ron")
+            .expect("liquids.liquid.ron should exist");
+        let defs: Vec<LiquidDef> =
+            ron::from_str(&ron_str).expect("liquids.liquid.ron should parse");
+        LiquidRegistry::from_defs(defs)
+    }
+
+    fn rule(tile: &str, conditions: ReactionConditions) -> ReactionRule {
+        ReactionRule {
+            id: format!("{tile}_rule"),
+            triggers: vec![ReactionTrigger::Placed],
+            tile: Some(TileMatch::Id(tile.into())),
+            conditions,
+            actions: vec![ReactionAction::Replace("stone".into())],
+        }
+    }
+
+    fn work(tile: (i32, i32), trigger: ReactionTrigger) -> ReactionWork {
+        ReactionWork { tile, trigger }
+    }
+
+    #[test]
+    fn conditions_check_the_tile_its_neighbours_and_their_liquids() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let liquids = liquids();
+        let (x, y) = AT;
+        let mut map = WorldMap::default();
+        for dx in -2..=2 {
+            for dy in -2..=2 {
+                map.set_tile(x + dx, y + dy, Layer::Fg, TileId::AIR, &ctx);
+                map.set_liquid(x + dx, y + dy, LiquidCell::EMPTY, &ctx);
+            }
+        }
+        map.set_tile(x, y, Layer::Fg, tr.by_name("dirt"), &ctx);
+        map.set_tile(x + 1, y, Layer::Fg, tr.by_name("grass"), &ctx);
+        let water = LiquidCell {
+            liquid_type: liquids.by_name("water"),
+            level: 1.0,
+        };
+        map.set_liquid(x - 1, y, water, &ctx);
+        let check = |rule: &ReactionRule| rule.matches(x, y, &map, &liquids, &ctx);
+
+        assert!(check(&rule("dirt", default())));
+        assert!(!check(&rule("stone", default())));
+        let by_material = ReactionRule {
+            tile: Some(TileMatch::Material(TileMaterial::Dirt)),
+            ..rule("dirt", default())
+        };
+        assert!(check(&by_material));
+
+        let next_to = |tiles: &[&str]| ReactionConditions {
+            adjacent_tiles: tiles.iter().map(|t| t.to_string()).collect(),
+            ..default()
+        };
+        assert!(check(&rule("dirt", next_to(&["stone", "grass"]))));
+        assert!(!check(&rule("dirt", next_to(&["stone"]))));
+        let away_from_grass = ReactionConditions {
+            no_adjacent_tiles: vec!["grass".into()],
+            ..default()
+        };
+        assert!(!check(&rule("dirt", away_from_grass)));
+
+        let wet = ReactionConditions {
+            adjacent_liquids: vec!["water".into()],
+            ..default()
+        };
+        assert!(check(&rule("dirt", wet)));
+        let dry = ReactionConditions {
+            no_adjacent_liquids: vec!["water".into()],
+            ..default()
+        };
+        assert!(!check(&rule("dirt", dry.clone())));
+        map.set_liquid(x - 1, y, LiquidCell::EMPTY, &ctx);
+        assert!(rule("dirt", dry).matches(x, y, &map, &liquids, &ctx));
+    }
+
+    #[test]
+    fn depth_and_layer_conditions() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let liquids = liquids();
+        let (x, _) = AT;
+        let surface = nc.surface_height_at(x, &wc, &pc);
+        let y = surface - 10;
+        let mut map = WorldMap::default();
+        map.set_tile(x, y, Layer::Fg, tr.by_name("dirt"), &ctx);
+        map.set_tile(x, y, Layer::Bg, tr.by_name("stone"), &ctx);
+
+        let at_depth = |depth| ReactionConditions {
+            depth: Some(depth),
+            ..default()
+        };
+        assert!(rule("dirt", at_depth((5, 20))).matches(x, y, &map, &liquids, &ctx));
+        assert!(!rule("dirt", at_depth((11, 20))).matches(x, y, &map, &liquids, &ctx));
+
+        let behind = ReactionConditions {
+            layer: Some(Layer::Bg),
+            ..default()
+        };
+        assert!(rule("stone", behind.clone()).matches(x, y, &map, &liquids, &ctx));
+        assert!(!rule("dirt", behind).matches(x, y, &map, &liquids, &ctx));
+    }
+
+    #[test]
+    fn reactions_ron_parses_and_replaces_with_known_tiles() {
+        let ron_str = std::fs::read_to_string("assets/content/reactions.ron")
+            .expect("reactions.ron should exist");
+        let rules: ReactionRules = ron::from_str(&ron_str).expect("reactions.ron should parse");
+        let tiles_str = std::fs::read_to_string("assets/worlds/tiles.registry.ron")
+            .expect("tiles.registry.ron should exist");
+        let tiles: crate::registry::assets::TileRegistryAsset =
+            ron::from_str(&tiles_str).expect("tiles.registry.ron should parse");
+        assert!(rules.budget > 0);
+        for rule in &rules.rules {
+            for action in &rule.actions {
+                if let ReactionAction::Replace(name) = action {
+                    assert!(
+                        tiles.tiles.iter().any(|def| def.id == *name),
+                        "{} replaces with unknown tile {name}",
+                        rule.id
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scheduler_releases_timers_in_due_order() {
+        let timer = |name: &str| ReactionTrigger::Timer(name.into());
+        let mut scheduler = ReactionScheduler::default();
+        scheduler.schedule(5.0, work((0, 0), timer("late")));
+        scheduler.schedule(1.0, work((1, 0), timer("early")));
+        scheduler.schedule(3.0, work((2, 0), timer("first_at_3")));
+        scheduler.schedule(3.0, work((3, 0), timer("second_at_3")));
+
+        assert!(scheduler.pop_due(0.5).is_empty());
+        assert_eq!(scheduler.pop_due(1.0), vec![work((1, 0), timer("early"))]);
+        assert_eq!(
+            scheduler.pop_due(4.0),
+            vec![
+                work((2, 0), timer("first_at_3")),
+                work((3, 0), timer("second_at_3")),
+            ]
+        );
+        assert_eq!(scheduler.timers.len(), 1);
+        assert_eq!(scheduler.pop_due(10.0), vec![work((0, 0), timer("late"))]);
+        assert!(scheduler.timers.is_empty());
+    }
+
+    #[test]
+    fn queue_pops_within_the_budget_and_always_makes_progress() {
+        let mut queue = ReactionQueue::default();
+        for x in 0..4 {
+            queue.push(work((x, 0), ReactionTrigger::Placed));
+        }
+        let mut spent = 0;
+        assert!(queue.pop_within(3, &mut spent, 5).is_some());
+        assert!(queue.pop_within(3, &mut spent, 5).is_none());
+        assert_eq!((spent, queue.pending.len()), (3, 3));
+
+        // A fresh frame takes work even when it alone is over budget.
+        let mut spent = 0;
+        assert_eq!(
+            queue.pop_within(9, &mut spent, 5),
+            Some(work((1, 0), ReactionTrigger::Placed))
+        );
+        // Nothing more fits once the frame is over budget.
+        assert!(queue.pop_within(0, &mut spent, 5).is_none());
+        assert_eq!(queue.pending.len(), 2);
+    }
+
+    fn reaction_app(rules: Vec<ReactionRule>, budget: usize) -> App {
+        let mut app = fixtures::test_app();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(250),
+        ))
+        .insert_resource(ReactionRules { budget, rules })
+        .insert_resource(liquids())
+        .init_resource::<ReactionQueue>()
+        .init_resource::<ReactionScheduler>()
+        .init_resource::<DirtyChunks>()
+        .init_resource::<LoadedChunks>()
+        .add_message::<TileChanged>()
+        .add_message::<ReactionSound>()
+        .add_systems(Update, (queue_tile_reactions, run_tile_reactions).chain());
+        app
+    }
+
+    fn place(app: &mut App, (x, y): (i32, i32), name: &'static str) {
+        app.world_mut()
+            .run_system_once(
+                move |ctx: WorldCtx,
+                      mut map: ResMut<WorldMap>,
+                      mut changes: MessageWriter<TileChanged>| {
+                    let ctx = ctx.as_ref();
+                    map.set_tile(x, y, Layer::Fg, ctx.tile_registry.by_name(name), &ctx);
+                    changes.write(TileChanged {
+                        tile_x: x,
+                        tile_y: y,
+                    });
+                },
+            )
+            .unwrap();
+    }
+
+    fn tile_at(app: &mut App, (x, y): (i32, i32)) -> String {
+        app.world_mut()
+            .run_system_once(move |ctx: WorldCtx, map: Res<WorldMap>| {
+                let ctx = ctx.as_ref();
+                let tile = map.get_tile(x, y, Layer::Fg, &ctx).unwrap();
+                ctx.tile_registry.get(tile).id.clone()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn work_over_budget_carries_over_to_the_next_frame() {
+        let petrify = ReactionRule {
+            id: "petrify".into(),
+            triggers: vec![ReactionTrigger::Placed],
+            tile: Some(TileMatch::Id("dirt".into())),
+            conditions: default(),
+            actions: vec![
+                ReactionAction::Replace("stone".into()),
+                ReactionAction::Sound("crack".into()),
+            ],
+        };
+        let mut app = reaction_app(vec![petrify], 4);
+        let spots: Vec<(i32, i32)> = (0..5).map(|i| (AT.0 + i * 4, AT.1)).collect();
+        for &spot in &spots {
+            place(&mut app, spot, "dirt");
+        }
+
+        let petrified = |app: &mut App| {
+            spots
+                .iter()
+                .filter(|&&spot| tile_at(app, spot) == "stone")
+                .count()
+        };
+        app.update();
+        assert_eq!(petrified(&mut app), 2);
+        assert!(!app.world().resource::<ReactionQueue>().pending.is_empty());
+        app.update();
+        assert_eq!(petrified(&mut app), 4);
+        app.update();
+        assert_eq!(petrified(&mut app), 5);
+    }
+
+    #[test]
+    fn timers_fire_follow_up_rules_after_their_delay() {
+        let settle = ReactionRule {
+            id: "settle".into(),
+            triggers: vec![ReactionTrigger::Placed],
+            tile: Some(TileMatch::Id("dirt".into())),
+            conditions: default(),
+            actions: vec![ReactionAction::After {
+                secs: 2.0,
+                timer: "harden".into(),
+            }],
+        };
+        let harden = ReactionRule {
+            id: "harden".into(),
+            triggers: vec![ReactionTrigger::Timer("harden".into())],
+            tile: Some(TileMatch::Id("dirt".into())),
+            conditions: default(),
+            actions: vec![ReactionAction::Replace("stone".into())],
+        };
+        let mut app = reaction_app(vec![settle, harden], 64);
+        place(&mut app, AT, "dirt");
+
+        // The first frame doesn't advance the clock; each one after adds 0.25s.
+        app.update();
+        assert_eq!(app.world().resource::<ReactionScheduler>().timers.len(), 1);
+        for _ in 0..7 {
+            app.update();
+            assert_eq!(tile_at(&mut app, AT), "dirt");
+        }
+        app.update();
+        assert_eq!(tile_at(&mut app, AT), "stone");
+        assert!(app
+            .world()
+            .resource::<ReactionScheduler>()
+            .timers
+            .is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+//! Rebuilding the live world when its identity changes under the player.
+//!
+//! Hot-reloading `generation.ron` can change the chunk size, tile size or
+//! default planet size of the world being played. Swapping those into
+//! [`ActiveWorld`] alone would leave chunk keys, the biome map and the
+//! player's position cut for the old world, so [`WorldChange::between`]
+//! sorts a new config into changes that apply in place and changes to the
+//! world's identity. An identity change sends [`RegenerateWorld`]: the old
+//! world is torn down with the same systems a warp uses, generated again
+//! from the new config, and the player is put back on its surface.
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::{Universe, UnloadedDroppedItems};
+use crate::physics::Velocity;
+use crate::player::Player;
+use crate::registry::biome::{BiomeRegistry, PlanetConfig};
+use crate::registry::world::ActiveWorld;
+use crate::sets::GameSet;
+use crate::ui::game_ui::notifications::{NotificationKind, Notify};
+use crate::world::biome_map::{BiomeMap, BorderCliffs};
+use crate::world::rc_lighting::{RcInputData, RcLightingConfig};
+use crate::world::spawn_point::WorldSpawnPoint;
+use crate::world::terrain_gen::TerrainNoiseCache;
+use crate::world::{biome_override, chunk, reactions, spawn_point};
+
+/// How a new [`ActiveWorld`] differs from the live one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldChange {
+    Unchanged,
+    /// Only settings that can be swapped into the running world, like the
+    /// chunk load radius.
+    InPlace,
+    /// Tiles, chunk keys or pixel positions no longer line up with the
+    /// running world: it has to be generated again.
+    Identity,
+}
+
+impl WorldChange {
+    pub fn between(old: &ActiveWorld, new: &ActiveWorld) -> Self {
+        // Every chunk mesh, object and the player sit at `tile * tile_size`,
+        // so the tile size moves the whole world too.
+        let identity = old.seed != new.seed
+            || old.width_tiles != new.width_tiles
+            || old.height_tiles != new.height_tiles
+            || old.chunk_size != new.chunk_size
+            || old.tile_size != new.tile_size
+            || old.planet_type != new.planet_type
+            || old.wrap_x != new.wrap_x;
+        if identity {
+            Self::Identity
+        } else if old.chunk_load_radius != new.chunk_load_radius {
+            Self::InPlace
+        } else {
+            Self::Unchanged
+        }
+    }
+}
+
+/// Replace the live world with `world`, generated from scratch.
+#[derive(Message, Debug, Clone)]
+pub struct RegenerateWorld {
+    pub world: ActiveWorld,
+    /// Planet config rebuilt for the new world's height.
+    pub planet_config: PlanetConfig,
+}
+
+/// Marker resource: the world was just regenerated, so the old one still
+/// has to be torn down and the player placed on the new surface.
+#[derive(Resource)]
+pub struct Regenerated;
+
+/// Biome map for `world`, with the cliff steps at its region borders
+/// stored back into `planet_config`.
+pub fn rebuild_biome_map(
+    planet_config: &mut PlanetConfig,
+    world: &ActiveWorld,
+    biome_registry: &BiomeRegistry,
+) -> BiomeMap {
+    let secondaries: Vec<&str> = planet_config
+        .secondary_biomes
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+    let biome_map = BiomeMap::generate(
+        &planet_config.primary_biome,
+        &secondaries,
+        world.seed as u64,
+        world.width_tiles as u32,
+        planet_config.region_width_min,
+        planet_config.region_width_max,
+        planet_config.primary_region_ratio,
+        planet_config.biome_separation,
+        planet_config.starter_biome.as_deref(),
+        biome_registry,
+    );
+    planet_config.border_cliffs = BorderCliffs::new(
+        &biome_map,
+        &planet_config.biome_borders,
+        biome_registry,
+        world.seed,
+        world.wrap_x,
+    );
+    biome_map
+}
+
+/// Swap in the world requested by the last [`RegenerateWorld`] along with
+/// everything generated from it.
+#[allow(clippy::too_many_arguments)]
+pub fn regenerate_world(
+    mut commands: Commands,
+    mut requests: MessageReader<RegenerateWorld>,
+    mut world_config: ResMut<ActiveWorld>,
+    mut planet_config: ResMut<PlanetConfig>,
+    mut biome_map: ResMut<BiomeMap>,
+    mut noise_cache: ResMut<TerrainNoiseCache>,
+    biome_registry: Res<BiomeRegistry>,
+    mut universe: ResMut<Universe>,
+    rc_state: (
+        Option<ResMut<RcLightingConfig>>,
+        Option<ResMut<RcInputData>>,
+    ),
+    mut notify: MessageWriter<Notify>,
+) {
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+
+    // Saved chunks were cut for the old seed and size; restoring them into
+    // the new world would bring the tearing back.
+    if universe.planets.remove(&world_config.address).is_some() {
+        warn!("Discarded the saved state of the regenerated world");
+    }
+
+    *world_config = request.world.clone();
+    *planet_config = request.planet_config.clone();
+    *biome_map = rebuild_biome_map(&mut planet_config, &world_config, &biome_registry);
+    *noise_cache = TerrainNoiseCache::new(world_config.seed);
+    commands.insert_resource(UnloadedDroppedItems::default());
+
+    let (rc_config, rc_input) = rc_state;
+    if let Some(mut rc_config) = rc_config {
+        *rc_config = RcLightingConfig::default();
+    }
+    if let Some(mut rc_input) = rc_input {
+        *rc_input = RcInputData::default();
+    }
+
+    commands.insert_resource(Regenerated);
+    notify.write(Notify {
+        kind: NotificationKind::Warning,
+        text: "World config changed: the world was regenerated".into(),
+        icon: None,
+    });
+    info!(
+        "Regenerating world: {}×{} tiles, chunk size {}, seed {}",
+        world_config.width_tiles,
+        world_config.height_tiles,
+        world_config.chunk_size,
+        world_config.seed
+    );
+}
+
+/// Put the player on the regenerated world's spawn point.
+pub fn place_player_on_new_surface(
+    mut commands: Commands,
+    spawn: Res<WorldSpawnPoint>,
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+) {
+    if let Ok((mut transform, mut velocity)) = player_query.single_mut() {
+        transform.translation.x = spawn.position.x;
+        transform.translation.y = spawn.position.y;
+        *velocity = Velocity::default();
+    }
+    commands.remove_resource::<Regenerated>();
+}
+
+/// Regeneration runs before [`GameSet::Input`], so nothing reads or moves
+/// through a half-rebuilt world.
+pub(crate) fn add_systems(app: &mut App) {
+    app.add_message::<RegenerateWorld>().add_systems(
+        Update,
+        (
+            regenerate_world.run_if(on_message::<RegenerateWorld>),
+            (
+                chunk::clear_stale_chunks,
+                reactions::clear_pending_reactions,
+                biome_override::clear_biome_overrides,
+                spawn_point::choose_world_spawn,
+                spawn_point::build_spawn_platform,
+                place_player_on_new_surface,
+                crate::camera::snap::snap_camera_to_player,
+            )
+                .chain()
+                .run_if(resource_exists::<Regenerated>),
+        )
+            .chain()
+            .before(GameSet::Input),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::persistence::DirtyChunks;
+    use crate::registry::tile::TileRegistry;
+    use crate::test_helpers::fixtures;
+    use crate::world::biome_override::BiomeOverrides;
+    use crate::world::chunk::{ChunkCoord, LoadedChunks, WorldMap};
+    use crate::world::ctx::WorldCtxRef;
+    use crate::world::reactions::{ReactionQueue, ReactionScheduler};
+    use crate::world::spawn_point::SpawnPlatform;
+
+    /// A named edit to the world config and the change it should count as.
+    type Case = (&'static str, fn(&mut ActiveWorld), WorldChange);
+
+    #[test]
+    fn changes_are_sorted_into_in_place_and_identity() {
+        let old = fixtures::test_active_world();
+        let cases: [Case; 9] = [
+            ("nothing", |_| {}, WorldChange::Unchanged),
+            (
+                "load radius",
+                |w| w.chunk_load_radius = 5,
+                WorldChange::InPlace,
+            ),
+            (
+                "weather",
+                |w| w.base_temperature = -20.0,
+                WorldChange::Unchanged,
+            ),
+            ("seed", |w| w.seed += 1, WorldChange::Identity),
+            ("width", |w| w.width_tiles = 1024, WorldChange::Identity),
+            ("height", |w| w.height_tiles = 512, WorldChange::Identity),
+            ("chunk size", |w| w.chunk_size = 16, WorldChange::Identity),
+            ("tile size", |w| w.tile_size = 16.0, WorldChange::Identity),
+            (
+                "planet type",
+                |w| w.planet_type = "desert".into(),
+                WorldChange::Identity,
+            ),
+        ];
+        for (name, edit, expected) in cases {
+            let mut new = old.clone();
+            edit(&mut new);
+            assert_eq!(WorldChange::between(&old, &new), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn regeneration_leaves_no_stale_chunks_and_the_player_on_ground() {
+        let mut app = fixtures::test_app();
+        app.init_resource::<LoadedChunks>()
+            .init_resource::<DirtyChunks>()
+            .init_resource::<Universe>()
+            .init_resource::<UnloadedDroppedItems>()
+            .init_resource::<SpawnPlatform>()
+            .init_resource::<ReactionQueue>()
+            .init_resource::<ReactionScheduler>()
+            .init_resource::<BiomeOverrides>()
+            .add_message::<Notify>();
+        add_systems(&mut app);
+
+        // A chunk far east that the narrower new world doesn't have.
+        let stale = (60, 5);
+        let old_chunk = app.world_mut().spawn(ChunkCoord { x: 60, y: 5 }).id();
+        app.world_mut()
+            .resource_scope(|world, mut map: Mut<WorldMap>| {
+                let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+                let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+                map.get_tile_mut(60 * 32, 5 * 32, chunk::Layer::Fg, &ctx);
+                assert!(map.chunks.contains_key(&stale));
+                world.resource_mut::<DirtyChunks>().0.insert(stale);
+            });
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(60.0 * 1024.0, 0.0, 0.0),
+                Velocity::default(),
+            ))
+            .id();
+
+        let mut world = fixtures::test_active_world();
+        world.seed = 7;
+        world.width_tiles = 1024;
+        app.world_mut().write_message(RegenerateWorld {
+            world,
+            planet_config: fixtures::test_planet_config(),
+        });
+        app.update();
+
+        let w = app.world();
+        assert!(
+            w.get_entity(old_chunk).is_err(),
+            "old chunk entity survived"
+        );
+        assert!(!w.contains_resource::<Regenerated>());
+        assert!(w.resource::<LoadedChunks>().map.is_empty());
+        assert!(!w.resource::<DirtyChunks>().0.contains(&stale));
+
+        let config = w.resource::<ActiveWorld>();
+        assert_eq!((config.seed, config.width_tiles), (7, 1024));
+        let map = w.resource::<WorldMap>();
+        assert!(!map.chunks.is_empty(), "the spawn platform was not built");
+        for &(cx, cy) in map.chunks.keys() {
+            assert!(
+                (0..config.width_chunks()).contains(&cx)
+                    && (0..config.height_chunks()).contains(&cy),
+                "stale chunk key ({cx}, {cy})"
+            );
+        }
+
+        let ctx = WorldCtxRef {
+            config,
+            biome_map: w.resource::<BiomeMap>(),
+            biome_registry: w.resource::<BiomeRegistry>(),
+            tile_registry: w.resource::<TileRegistry>(),
+            planet_config: w.resource::<PlanetConfig>(),
+            noise_cache: w.resource::<TerrainNoiseCache>(),
+            biome_overrides: None,
+        };
+        let spawn = w.resource::<WorldSpawnPoint>();
+        let position = w.get::<Transform>(player).unwrap().translation;
+        assert_eq!(position.truncate(), spawn.position);
+        let (tx, ty) = spawn.tile;
+        assert!(
+            map.is_solid(tx, ty - 1, &ctx),
+            "no ground under {:?}",
+            spawn.tile
+        );
+        assert!(!map.is_solid(tx, ty, &ctx));
+    }
+}
@@ -0,0 +1,440 @@
+//! Shader hot reload for the lighting pipeline and the tile material.
+//!
+//! With the `file_watcher` feature the asset server reloads an edited `.wgsl`
+//! file and the pipeline cache re-queues every pipeline built from it under
+//! its existing id. On its own that leaves nothing to dispatch while the new
+//! source compiles, and nothing but a log line when it does not compile.
+//!
+//! [`watch_shader_edits`] notices edits to the [`WatchedShaders`] in the main
+//! world and forwards them to the render world through [`ShaderEdits`]. Each
+//! compute pipeline keeps a [`PipelineSlot`] there that holds on to the last
+//! pipeline that compiled, so lighting keeps running on the old shader until
+//! the new one is ready. Compile errors end up in [`ShaderErrors`], shared by
+//! both worlds, for the debug panel to show.
+//!
+//! Material2d pipelines are specialised by Bevy itself, so a broken tile
+//! shader still leaves chunks undrawn until it is fixed; its error is
+//! reported the same way.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::asset::{AssetEvent, AssetId};
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_resource::{
+    CachedComputePipelineId, CachedPipelineState, ComputePipeline, Pipeline, PipelineCache,
+    PipelineDescriptor,
+};
+use bevy::render::{Render, RenderApp, RenderSystems};
+use bevy::shader::{PipelineCacheError, Shader};
+
+/// Tile material shader, watched for errors only (see module docs).
+const TILE_SHADER: &str = "engine/shaders/tile.wgsl";
+pub(crate) const CASCADE_SHADER: &str = "engine/shaders/radiance_cascades.wgsl";
+pub(crate) const FINALIZE_SHADER: &str = "engine/shaders/rc_finalize.wgsl";
+
+/// Shaders whose edits and compile errors are tracked, by asset path.
+#[derive(Resource, Clone, Default)]
+pub struct WatchedShaders(Vec<(&'static str, Handle<Shader>)>);
+
+impl WatchedShaders {
+    /// Asset path of a watched shader.
+    pub fn path_of(&self, id: AssetId<Shader>) -> Option<&'static str> {
+        self.0
+            .iter()
+            .find(|(_, handle)| handle.id() == id)
+            .map(|(path, _)| *path)
+    }
+
+    /// Asset id of the watched shader at `path`.
+    pub fn id_of(&self, path: &str) -> Option<AssetId<Shader>> {
+        self.0
+            .iter()
+            .find(|(watched, _)| *watched == path)
+            .map(|(_, handle)| handle.id())
+    }
+}
+
+/// Number of times each watched shader was edited on disk, mirrored into the
+/// render world.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct ShaderEdits(HashMap<AssetId<Shader>, u32>);
+
+impl ShaderEdits {
+    /// Edits seen so far for `shader`.
+    pub fn count(&self, shader: AssetId<Shader>) -> u32 {
+        self.0.get(&shader).copied().unwrap_or(0)
+    }
+}
+
+/// Current compile error of each broken watched shader, written by the render
+/// world and read by the debug panel.
+#[derive(Resource, Clone, Default)]
+pub struct ShaderErrors(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+impl ShaderErrors {
+    /// Record (`Some`) or clear (`None`) the error of `shader`.
+    pub fn set(&self, shader: &'static str, error: Option<&str>) {
+        let Ok(mut errors) = self.0.lock() else {
+            return;
+        };
+        let existing = errors.iter().position(|(path, _)| *path == shader);
+        match (existing, error) {
+            (Some(i), Some(error)) => errors[i].1 = error.to_string(),
+            (None, Some(error)) => errors.push((shader, error.to_string())),
+            (Some(i), None) => {
+                errors.remove(i);
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Shaders that currently fail to compile, with their errors.
+    pub fn snapshot(&self) -> Vec<(&'static str, String)> {
+        self.0
+            .lock()
+            .map(|errors| errors.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// What the pipeline cache currently holds for a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileStatus<P> {
+    /// Queued or compiling, or waiting for its shader to load.
+    Pending,
+    Ready(P),
+    Error(String),
+}
+
+/// Notable transitions of a [`PipelineSlot`], worth a log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotChange {
+    /// A recompile finished and replaced the last good pipeline.
+    Reloaded,
+    /// A compile failed; the last good pipeline (if any) stays in use.
+    Failed,
+}
+
+/// Which pipeline a compute node dispatches while its shader is reloaded.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PipelineSlot<P> {
+    /// The first compile has not finished yet: nothing to dispatch.
+    #[default]
+    Loading,
+    LastGood(P),
+    /// The shader changed; the old pipeline runs until the new one is ready.
+    PendingRecompile {
+        last_good: Option<P>,
+    },
+    /// The shader does not compile; the old pipeline keeps running.
+    Failed {
+        last_good: Option<P>,
+        error: String,
+    },
+}
+
+impl<P> PipelineSlot<P> {
+    /// Pipeline to dispatch this frame.
+    pub fn active(&self) -> Option<&P> {
+        match self {
+            Self::Loading => None,
+            Self::LastGood(pipeline) => Some(pipeline),
+            Self::PendingRecompile { last_good } | Self::Failed { last_good, .. } => {
+                last_good.as_ref()
+            }
+        }
+    }
+
+    /// Compile error of the current shader source, if it is broken.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Failed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    fn take_last_good(&mut self) -> Option<P> {
+        match std::mem::take(self) {
+            Self::Loading => None,
+            Self::LastGood(pipeline) => Some(pipeline),
+            Self::PendingRecompile { last_good } | Self::Failed { last_good, .. } => last_good,
+        }
+    }
+
+    /// The shader source was edited: expect a recompile.
+    pub fn shader_changed(&mut self) {
+        if matches!(self, Self::Loading) {
+            return;
+        }
+        let last_good = self.take_last_good();
+        *self = Self::PendingRecompile { last_good };
+    }
+
+    /// Follow the pipeline cache. Returns the transition worth logging, if any.
+    pub fn observe(&mut self, status: CompileStatus<P>) -> Option<SlotChange> {
+        match status {
+            CompileStatus::Pending => {
+                // The cache re-queues on its own when a shader changes, which
+                // may be seen before the edit is forwarded.
+                if !matches!(self, Self::Loading | Self::PendingRecompile { .. }) {
+                    self.shader_changed();
+                }
+                None
+            }
+            CompileStatus::Ready(pipeline) => {
+                let reloaded = matches!(self, Self::PendingRecompile { .. } | Self::Failed { .. });
+                *self = Self::LastGood(pipeline);
+                reloaded.then_some(SlotChange::Reloaded)
+            }
+            CompileStatus::Error(error) => {
+                if self.error() == Some(error.as_str()) {
+                    return None;
+                }
+                let last_good = self.take_last_good();
+                *self = Self::Failed { last_good, error };
+                Some(SlotChange::Failed)
+            }
+        }
+    }
+}
+
+/// Cache state of a compute pipeline as a [`CompileStatus`].
+pub fn compute_status(
+    cache: &PipelineCache,
+    id: CachedComputePipelineId,
+) -> CompileStatus<ComputePipeline> {
+    match cache.get_compute_pipeline_state(id) {
+        CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) => {
+            CompileStatus::Ready(pipeline.clone())
+        }
+        CachedPipelineState::Err(error) => error_status(error),
+        _ => CompileStatus::Pending,
+    }
+}
+
+fn error_status<P>(error: &PipelineCacheError) -> CompileStatus<P> {
+    match error {
+        // The cache retries these once the shader (or an import) loads.
+        PipelineCacheError::ShaderNotLoaded(_)
+        | PipelineCacheError::ShaderImportNotYetAvailable => CompileStatus::Pending,
+        error => CompileStatus::Error(error.to_string()),
+    }
+}
+
+/// Log a slot transition for `shader`; failures loudly, they are easy to miss.
+pub fn log_slot_change(shader: &str, change: SlotChange, slot: &PipelineSlot<impl Sized>) {
+    match change {
+        SlotChange::Reloaded => info!("Shader {shader} recompiled, using the new pipeline"),
+        SlotChange::Failed => {
+            let fallback = if slot.active().is_some() {
+                "keeping the last good pipeline"
+            } else {
+                "nothing to fall back to"
+            };
+            error!(
+                "==================== SHADER ERROR ====================\n\
+                 {shader} failed to compile ({fallback}):\n{}\n\
+                 ======================================================",
+                slot.error().unwrap_or_default()
+            );
+        }
+    }
+}
+
+/// Register the edit watcher and error reporting in both worlds.
+pub(crate) fn setup(app: &mut App) {
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    let watched = WatchedShaders(
+        [TILE_SHADER, CASCADE_SHADER, FINALIZE_SHADER]
+            .map(|path| (path, asset_server.load(path)))
+            .to_vec(),
+    );
+    let errors = ShaderErrors::default();
+
+    app.insert_resource(watched.clone())
+        .insert_resource(errors.clone())
+        .init_resource::<ShaderEdits>()
+        .add_plugins(ExtractResourcePlugin::<ShaderEdits>::default())
+        .add_systems(Update, watch_shader_edits);
+
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    render_app
+        .insert_resource(watched)
+        .insert_resource(errors)
+        .add_systems(
+            Render,
+            report_tile_shader_errors.in_set(RenderSystems::PrepareBindGroups),
+        );
+}
+
+/// Count edits to watched shaders.
+pub fn watch_shader_edits(
+    mut events: MessageReader<AssetEvent<Shader>>,
+    watched: Res<WatchedShaders>,
+    mut edits: ResMut<ShaderEdits>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event
+            && let Some(path) = watched.path_of(*id)
+        {
+            *edits.0.entry(*id).or_default() += 1;
+            info!("Shader {path} changed on disk, recompiling");
+        }
+    }
+}
+
+/// Mirror compile errors of tile material pipelines into [`ShaderErrors`].
+fn report_tile_shader_errors(
+    cache: Res<PipelineCache>,
+    watched: Res<WatchedShaders>,
+    errors: Res<ShaderErrors>,
+    mut reported: Local<Option<String>>,
+) {
+    let Some(tile_shader) = watched.id_of(TILE_SHADER) else {
+        return;
+    };
+    let error = cache.pipelines().find_map(|cached| {
+        let PipelineDescriptor::RenderPipelineDescriptor(descriptor) = &cached.descriptor else {
+            return None;
+        };
+        let uses_tile_shader = descriptor.vertex.shader.id() == tile_shader
+            || descriptor
+                .fragment
+                .as_ref()
+                .is_some_and(|fragment| fragment.shader.id() == tile_shader);
+        let CachedPipelineState::Err(error) = &cached.state else {
+            return None;
+        };
+        match error_status::<()>(error) {
+            CompileStatus::Error(error) if uses_tile_shader => Some(error),
+            _ => None,
+        }
+    });
+    if *reported == error {
+        return;
+    }
+    match &error {
+        Some(error) => error!(
+            "==================== SHADER ERROR ====================\n\
+             {TILE_SHADER} failed to compile, chunks are not drawn:\n{error}\n\
+             ======================================================"
+        ),
+        None => info!("Shader {TILE_SHADER} compiles again"),
+    }
+    errors.set(TILE_SHADER, error.as_deref());
+    *reported = error;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pipelines stand in as plain ids: the slot only decides which one runs.
+    type Slot = PipelineSlot<u32>;
+
+    #[test]
+    fn nothing_runs_until_the_first_compile() {
+        let mut slot = Slot::default();
+        assert_eq!(slot.observe(CompileStatus::Pending), None);
+        slot.shader_changed();
+        assert_eq!(slot, Slot::Loading);
+        assert_eq!(slot.active(), None);
+
+        assert_eq!(slot.observe(CompileStatus::Ready(1)), None);
+        assert_eq!(slot.active(), Some(&1));
+    }
+
+    #[test]
+    fn last_good_pipeline_runs_while_recompiling() {
+        let mut slot = Slot::LastGood(1);
+        slot.shader_changed();
+        assert_eq!(slot, Slot::PendingRecompile { last_good: Some(1) });
+        assert_eq!(slot.observe(CompileStatus::Pending), None);
+        assert_eq!(slot.active(), Some(&1));
+
+        assert_eq!(
+            slot.observe(CompileStatus::Ready(2)),
+            Some(SlotChange::Reloaded)
+        );
+        assert_eq!(slot, Slot::LastGood(2));
+        // Steady state is quiet.
+        assert_eq!(slot.observe(CompileStatus::Ready(2)), None);
+    }
+
+    #[test]
+    fn cache_requeue_alone_starts_a_recompile() {
+        let mut slot = Slot::LastGood(1);
+        assert_eq!(slot.observe(CompileStatus::Pending), None);
+        assert_eq!(slot, Slot::PendingRecompile { last_good: Some(1) });
+    }
+
+    #[test]
+    fn compile_errors_keep_the_last_good_pipeline() {
+        let mut slot = Slot::LastGood(1);
+        slot.shader_changed();
+        assert_eq!(
+            slot.observe(CompileStatus::Error("expected ';'".into())),
+            Some(SlotChange::Failed)
+        );
+        assert_eq!(slot.active(), Some(&1));
+        assert_eq!(slot.error(), Some("expected ';'"));
+        // The same error is reported once.
+        assert_eq!(
+            slot.observe(CompileStatus::Error("expected ';'".into())),
+            None
+        );
+
+        // The next edit is still broken, differently.
+        slot.shader_changed();
+        assert_eq!(slot.error(), None);
+        assert_eq!(slot.active(), Some(&1));
+        assert_eq!(
+            slot.observe(CompileStatus::Error("unknown type".into())),
+            Some(SlotChange::Failed)
+        );
+
+        // Fixed: the new pipeline takes over and the error clears.
+        slot.shader_changed();
+        assert_eq!(
+            slot.observe(CompileStatus::Ready(3)),
+            Some(SlotChange::Reloaded)
+        );
+        assert_eq!(slot.active(), Some(&3));
+        assert_eq!(slot.error(), None);
+    }
+
+    #[test]
+    fn broken_first_compile_has_nothing_to_run() {
+        let mut slot = Slot::default();
+        assert_eq!(
+            slot.observe(CompileStatus::Error("bad".into())),
+            Some(SlotChange::Failed)
+        );
+        assert_eq!(slot.active(), None);
+        slot.shader_changed();
+        assert_eq!(slot, Slot::PendingRecompile { last_good: None });
+    }
+
+    #[test]
+    fn shader_errors_are_set_and_cleared_per_shader() {
+        let errors = ShaderErrors::default();
+        errors.set(CASCADE_SHADER, Some("a"));
+        errors.set(TILE_SHADER, Some("b"));
+        errors.set(CASCADE_SHADER, Some("c"));
+        assert_eq!(
+            errors.snapshot(),
+            vec![
+                (CASCADE_SHADER, "c".to_string()),
+                (TILE_SHADER, "b".to_string())
+            ]
+        );
+        // Clones share the same list, as the two worlds do.
+        errors.clone().set(CASCADE_SHADER, None);
+        assert_eq!(errors.snapshot(), vec![(TILE_SHADER, "b".to_string())]);
+    }
+}
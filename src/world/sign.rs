@@ -0,0 +1,261 @@
+//! Sign tiles: short player-written text stored per chunk and shown as a
+//! world-space label above the tile.
+//!
+//! The text lives in [`ChunkData::signs`](super::chunk::ChunkData), so it is
+//! saved and restored with the chunk. Labels are plain `Text2d` entities kept
+//! in step with the signs of every loaded display chunk by
+//! [`sync_sign_labels`].
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::DirtyChunks;
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{tile_to_chunk, LoadedChunks, WorldMap};
+use crate::world::ctx::WorldCtxRef;
+
+/// Longest text a sign holds, in characters.
+pub const SIGN_TEXT_MAX_CHARS: usize = 48;
+
+/// Label font size in world pixels.
+const SIGN_LABEL_FONT_SIZE: f32 = 8.0;
+/// Labels draw above tiles, objects and dropped items.
+const SIGN_LABEL_Z: f32 = 2.0;
+
+/// Text label floating above a sign tile.
+#[derive(Component, Debug)]
+pub struct SignLabel {
+    /// Sign tile in display coordinates (x may lie outside the wrapped range
+    /// for chunks drawn across the world seam).
+    pub tile: (i32, i32),
+}
+
+/// Normalise player input for a sign: one line, trimmed, at most
+/// [`SIGN_TEXT_MAX_CHARS`] characters. Empty means "no text".
+pub fn sanitize_sign_text(text: &str) -> String {
+    let line: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    line.trim().chars().take(SIGN_TEXT_MAX_CHARS).collect()
+}
+
+/// Write (or, with empty text, clear) the sign at the tile and mark its chunk
+/// for saving.
+pub fn write_sign(
+    world_map: &mut WorldMap,
+    dirty_chunks: &mut DirtyChunks,
+    tile_x: i32,
+    tile_y: i32,
+    text: &str,
+    ctx: &WorldCtxRef,
+) {
+    world_map.set_sign_text(tile_x, tile_y, sanitize_sign_text(text), ctx);
+    let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+    dirty_chunks
+        .0
+        .insert(tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size));
+}
+
+/// World position of the label for the sign at display tile `(x, y)`:
+/// centred horizontally, half a tile above the sign's top edge.
+pub fn sign_label_position(tile_x: i32, tile_y: i32, tile_size: f32) -> Vec3 {
+    Vec3::new(
+        (tile_x as f32 + 0.5) * tile_size,
+        (tile_y as f32 + 1.5) * tile_size,
+        SIGN_LABEL_Z,
+    )
+}
+
+/// Spawn, move, update and despawn sign labels so there is exactly one per
+/// written sign in a visible display chunk.
+pub fn sync_sign_labels(
+    mut commands: Commands,
+    world_map: Res<WorldMap>,
+    loaded_chunks: Res<LoadedChunks>,
+    config: Res<ActiveWorld>,
+    mut labels: Query<(Entity, &SignLabel, &mut Text2d, &mut Transform)>,
+) {
+    let chunk_size = config.chunk_size as i32;
+
+    // Display tile -> text for every sign in a visible chunk.
+    let mut wanted: HashMap<(i32, i32), &str> = HashMap::new();
+    for &(display_cx, cy) in loaded_chunks.map.keys() {
+        if !loaded_chunks.is_visible(display_cx, cy) {
+            continue;
+        }
+        let data_cx = config.wrap_chunk_x(display_cx);
+        let Some(chunk) = world_map.chunk(data_cx, cy) else {
+            continue;
+        };
+        for (&idx, text) in &chunk.signs {
+            let idx = idx as i32;
+            let tile = (
+                display_cx * chunk_size + idx % chunk_size,
+                cy * chunk_size + idx / chunk_size,
+            );
+            wanted.insert(tile, text.as_str());
+        }
+    }
+
+    for (entity, label, mut text, mut transform) in &mut labels {
+        match wanted.remove(&label.tile) {
+            Some(wanted_text) => {
+                if text.0 != wanted_text {
+                    text.0 = wanted_text.to_string();
+                }
+                let position = sign_label_position(label.tile.0, label.tile.1, config.tile_size);
+                if transform.translation != position {
+                    transform.translation = position;
+                }
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    for ((tile_x, tile_y), text) in wanted {
+        commands.spawn((
+            SignLabel {
+                tile: (tile_x, tile_y),
+            },
+            Text2d::new(text),
+            TextFont {
+                font_size: SIGN_LABEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Transform::from_translation(sign_label_position(tile_x, tile_y, config.tile_size)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::test_helpers::fixtures;
+    use crate::world::chunk::{ChunkEntities, ChunkState};
+    use crate::world::ctx::WorldCtx;
+
+    #[test]
+    fn sanitize_trims_flattens_and_truncates() {
+        assert_eq!(sanitize_sign_text("  Home  "), "Home");
+        assert_eq!(sanitize_sign_text("two\nlines"), "two lines");
+        assert_eq!(sanitize_sign_text("   "), "");
+        let long = "x".repeat(SIGN_TEXT_MAX_CHARS + 10);
+        assert_eq!(
+            sanitize_sign_text(&long).chars().count(),
+            SIGN_TEXT_MAX_CHARS
+        );
+    }
+
+    fn label_app() -> App {
+        let mut app = fixtures::test_app();
+        app.init_resource::<LoadedChunks>()
+            .add_systems(Update, sync_sign_labels);
+        app
+    }
+
+    fn show_chunk(app: &mut App, display_cx: i32, cy: i32) {
+        let entry = ChunkEntities {
+            fg: Entity::PLACEHOLDER,
+            bg: Entity::PLACEHOLDER,
+            liquid: Entity::PLACEHOLDER,
+            state: ChunkState::Visible,
+        };
+        app.world_mut()
+            .resource_mut::<LoadedChunks>()
+            .map
+            .insert((display_cx, cy), entry);
+    }
+
+    fn set_sign(app: &mut App, tile_x: i32, tile_y: i32, text: &'static str) {
+        app.world_mut()
+            .run_system_once(move |ctx: WorldCtx, mut map: ResMut<WorldMap>| {
+                let ctx = ctx.as_ref();
+                let wrapped_x = ctx.config.wrap_tile_x(tile_x);
+                let (cx, cy) = tile_to_chunk(wrapped_x, tile_y, ctx.config.chunk_size);
+                map.get_or_generate_chunk(cx, cy, &ctx);
+                map.set_sign_text(tile_x, tile_y, text.to_string(), &ctx);
+            })
+            .unwrap();
+    }
+
+    fn labels(app: &mut App) -> Vec<((i32, i32), String, Vec3)> {
+        let mut query = app.world_mut().query::<(&SignLabel, &Text2d, &Transform)>();
+        query
+            .iter(app.world())
+            .map(|(label, text, tf)| (label.tile, text.0.clone(), tf.translation))
+            .collect()
+    }
+
+    #[test]
+    fn label_tracks_sign_position_and_text() {
+        let mut app = label_app();
+        let tile_size = app.world().resource::<ActiveWorld>().tile_size;
+
+        show_chunk(&mut app, 3, 15);
+        set_sign(&mut app, 100, 500, "Home");
+        app.update();
+        assert_eq!(
+            labels(&mut app),
+            vec![(
+                (100, 500),
+                "Home".into(),
+                sign_label_position(100, 500, tile_size)
+            )]
+        );
+
+        // Sign moved: the old label goes, a new one sits above the new tile.
+        set_sign(&mut app, 100, 500, "");
+        set_sign(&mut app, 102, 490, "Mine");
+        app.update();
+        assert_eq!(
+            labels(&mut app),
+            vec![(
+                (102, 490),
+                "Mine".into(),
+                sign_label_position(102, 490, tile_size)
+            )]
+        );
+
+        // Edited text updates the existing label in place.
+        set_sign(&mut app, 102, 490, "Mine ->");
+        app.update();
+        assert_eq!(labels(&mut app)[0].1, "Mine ->");
+    }
+
+    #[test]
+    fn label_follows_display_chunk_across_seam() {
+        let mut app = label_app();
+        let (width_chunks, chunk_size, tile_size) = {
+            let world = app.world().resource::<ActiveWorld>();
+            (
+                world.width_chunks(),
+                world.chunk_size as i32,
+                world.tile_size,
+            )
+        };
+        // Data chunk 0 drawn right of the seam as display chunk `width_chunks`.
+        show_chunk(&mut app, width_chunks, 15);
+        set_sign(&mut app, 5, 500, "Seam");
+        app.update();
+
+        let display_x = width_chunks * chunk_size + 5;
+        assert_eq!(
+            labels(&mut app),
+            vec![(
+                (display_x, 500),
+                "Seam".into(),
+                sign_label_position(display_x, 500, tile_size)
+            )]
+        );
+
+        // Chunk unloaded: label removed.
+        app.world_mut().resource_mut::<LoadedChunks>().map.clear();
+        app.update();
+        assert!(labels(&mut app).is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Per-chunk spatial index for "entities near a position" queries.
+//!
+//! Entities with [`TrackedInIndex`] are filed in the bucket of the chunk
+//! they stand in. [`refresh_spatial_index`] only looks at entities whose
+//! transform changed, and only moves them between buckets when they cross a
+//! chunk boundary; despawned entities drop out through the component's
+//! remove hook. [`SpatialIndex::query_radius`] visits the buckets a circle
+//! covers, wrapping across the world seam, so proximity systems check a few
+//! neighbours instead of every entity of their kind.
+
+use std::collections::HashMap;
+
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{tile_to_chunk, world_to_tile};
+
+/// Files an entity in the [`SpatialIndex`]. Remembers the bucket it was
+/// last filed under, so it is only moved when it crosses a chunk boundary.
+#[derive(Component, Debug, Default)]
+#[component(on_remove = forget_tracked)]
+pub struct TrackedInIndex {
+    bucket: Option<(i32, i32)>,
+}
+
+impl TrackedInIndex {
+    /// Chunk the entity is filed under, `None` before its first refresh.
+    pub fn bucket(&self) -> Option<(i32, i32)> {
+        self.bucket
+    }
+}
+
+fn forget_tracked(mut world: DeferredWorld, context: HookContext) {
+    let Some(bucket) = world
+        .get::<TrackedInIndex>(context.entity)
+        .and_then(TrackedInIndex::bucket)
+    else {
+        return;
+    };
+    if let Some(mut index) = world.get_resource_mut::<SpatialIndex>() {
+        index.remove(context.entity, bucket);
+    }
+}
+
+/// Tracked entities and their positions, bucketed by (wrapped) chunk.
+#[derive(Resource, Debug, Default)]
+pub struct SpatialIndex {
+    buckets: HashMap<(i32, i32), Vec<(Entity, Vec2)>>,
+}
+
+impl SpatialIndex {
+    /// Bucket (chunk coordinates, X wrapped) holding world position `pos`.
+    pub fn bucket_of(pos: Vec2, config: &ActiveWorld) -> (i32, i32) {
+        let (tile_x, tile_y) = world_to_tile(pos.x, pos.y, config.tile_size);
+        let (chunk_x, chunk_y) = tile_to_chunk(tile_x, tile_y, config.chunk_size);
+        (config.wrap_chunk_x(chunk_x), chunk_y)
+    }
+
+    /// File `entity` at `pos` under `bucket`, or update its position if it
+    /// is already there.
+    fn place(&mut self, entity: Entity, pos: Vec2, bucket: (i32, i32)) {
+        let entries = self.buckets.entry(bucket).or_default();
+        match entries.iter_mut().find(|(e, _)| *e == entity) {
+            Some(entry) => entry.1 = pos,
+            None => entries.push((entity, pos)),
+        }
+    }
+
+    fn remove(&mut self, entity: Entity, bucket: (i32, i32)) {
+        let Some(entries) = self.buckets.get_mut(&bucket) else {
+            return;
+        };
+        entries.retain(|(e, _)| *e != entity);
+        if entries.is_empty() {
+            self.buckets.remove(&bucket);
+        }
+    }
+
+    /// Entities filed in the buckets a circle of `radius` around `center`
+    /// touches, unfiltered by distance.
+    pub fn candidates<'a>(
+        &'a self,
+        center: Vec2,
+        radius: f32,
+        config: &ActiveWorld,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + 'a {
+        let chunk_px = config.chunk_size as f32 * config.tile_size;
+        let span = |c: f32| {
+            (
+                ((c - radius) / chunk_px).floor() as i32,
+                ((c + radius) / chunk_px).floor() as i32,
+            )
+        };
+        let (min_x, max_x) = span(center.x);
+        let (min_y, max_y) = span(center.y);
+        let width = config.width_chunks();
+        let columns: Vec<i32> = if config.wrap_x && max_x - min_x + 1 >= width {
+            // The circle covers the whole circumference: visit each once.
+            (0..width).collect()
+        } else {
+            (min_x..=max_x).map(|x| config.wrap_chunk_x(x)).collect()
+        };
+        columns
+            .into_iter()
+            .flat_map(move |x| (min_y..=max_y).filter_map(move |y| self.buckets.get(&(x, y))))
+            .flatten()
+            .copied()
+    }
+
+    /// Entities within `radius` of `center`, measured the short way around a
+    /// wrapping world.
+    pub fn query_radius<'a>(
+        &'a self,
+        center: Vec2,
+        radius: f32,
+        config: &'a ActiveWorld,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + 'a {
+        let radius_sq = radius * radius;
+        self.candidates(center, radius, config)
+            .filter(move |&(_, pos)| {
+                wrapped_offset(center, pos, config).length_squared() <= radius_sq
+            })
+    }
+}
+
+/// Offset from `from` to `to`, taking the shorter way around a wrapping
+/// world on X.
+pub fn wrapped_offset(from: Vec2, to: Vec2, config: &ActiveWorld) -> Vec2 {
+    let mut offset = to - from;
+    if config.wrap_x {
+        let width = config.world_pixel_width();
+        offset.x -= width * (offset.x / width).round();
+    }
+    offset
+}
+
+/// File moved or newly tracked entities under their current chunk.
+pub fn refresh_spatial_index(
+    config: Res<ActiveWorld>,
+    mut index: ResMut<SpatialIndex>,
+    mut tracked: Query<(Entity, &Transform, &mut TrackedInIndex), Changed<Transform>>,
+) {
+    for (entity, tf, mut tracked) in &mut tracked {
+        let pos = tf.translation.truncate();
+        let bucket = SpatialIndex::bucket_of(pos, &config);
+        if let Some(old) = tracked.bucket.filter(|&old| old != bucket) {
+            index.remove(entity, old);
+        }
+        index.place(entity, pos, bucket);
+        if tracked.bucket != Some(bucket) {
+            tracked.bucket = Some(bucket);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn index_app() -> App {
+        let mut app = fixtures::test_app();
+        app.init_resource::<SpatialIndex>()
+            .add_systems(Update, refresh_spatial_index);
+        app
+    }
+
+    fn track(app: &mut App, pos: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                TrackedInIndex::default(),
+                Transform::from_translation(pos.extend(0.0)),
+            ))
+            .id()
+    }
+
+    fn tracked_count(app: &App) -> usize {
+        let index = app.world().resource::<SpatialIndex>();
+        index.buckets.values().map(Vec::len).sum()
+    }
+
+    fn bucket(app: &App, entity: Entity) -> Option<(i32, i32)> {
+        app.world().get::<TrackedInIndex>(entity).unwrap().bucket()
+    }
+
+    fn near(app: &App, center: Vec2, radius: f32) -> Vec<Entity> {
+        let config = app.world().resource::<ActiveWorld>();
+        let mut found: Vec<Entity> = app
+            .world()
+            .resource::<SpatialIndex>()
+            .query_radius(center, radius, config)
+            .map(|(e, _)| e)
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn entities_change_bucket_only_across_chunk_boundaries() {
+        let mut app = index_app();
+        let chunk_px = 32.0 * 32.0;
+        let entity = track(&mut app, Vec2::new(chunk_px - 1.0, 100.0));
+        app.update();
+        assert_eq!(bucket(&app, entity), Some((0, 0)));
+
+        // Inside the same chunk: same bucket, fresh position.
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Transform::from_xyz(chunk_px - 0.5, 200.0, 0.0));
+        app.update();
+        assert_eq!(bucket(&app, entity), Some((0, 0)));
+        assert_eq!(
+            near(&app, Vec2::new(chunk_px - 0.5, 200.0), 0.1),
+            vec![entity]
+        );
+
+        // One pixel further is the next chunk.
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Transform::from_xyz(chunk_px + 0.5, 200.0, 0.0));
+        app.update();
+        assert_eq!(bucket(&app, entity), Some((1, 0)));
+        assert_eq!(tracked_count(&app), 1);
+
+        // Past the right edge of the world wraps to the first column.
+        let world_px = app.world().resource::<ActiveWorld>().world_pixel_width();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Transform::from_xyz(world_px + 10.0, 200.0, 0.0));
+        app.update();
+        assert_eq!(bucket(&app, entity), Some((0, 0)));
+    }
+
+    #[test]
+    fn despawned_entities_leave_the_index() {
+        let mut app = index_app();
+        let entity = track(&mut app, Vec2::new(50.0, 50.0));
+        app.update();
+        assert_eq!(tracked_count(&app), 1);
+
+        app.world_mut().despawn(entity);
+        assert_eq!(tracked_count(&app), 0);
+        assert!(near(&app, Vec2::new(50.0, 50.0), 100.0).is_empty());
+    }
+
+    #[test]
+    fn radius_queries_reach_across_the_world_seam() {
+        let mut app = index_app();
+        let world_px = app.world().resource::<ActiveWorld>().world_pixel_width();
+        let left = track(&mut app, Vec2::new(10.0, 500.0));
+        let right = track(&mut app, Vec2::new(world_px - 10.0, 500.0));
+        let far = track(&mut app, Vec2::new(world_px / 2.0, 500.0));
+        app.update();
+
+        let mut both = vec![left, right];
+        both.sort();
+        assert_eq!(near(&app, Vec2::new(0.0, 500.0), 20.0), both);
+        assert_eq!(near(&app, Vec2::new(world_px, 500.0), 20.0), both);
+        // The precise filter drops bucket neighbours outside the circle.
+        assert_eq!(
+            near(&app, Vec2::new(world_px - 10.0, 500.0), 5.0),
+            vec![right]
+        );
+        assert!(!near(&app, Vec2::new(0.0, 500.0), 1000.0).contains(&far));
+    }
+
+    #[test]
+    fn index_cuts_pair_checks_against_brute_force() {
+        let mut app = index_app();
+        let world_px = app.world().resource::<ActiveWorld>().world_pixel_width();
+        // A few hundred entities spread over a band of the world.
+        let positions: Vec<Vec2> = (0..300)
+            .map(|i| {
+                let x = (i as f32 * 7919.0) % world_px;
+                let y = 400.0 + (i as f32 * 104.729) % 8000.0;
+                Vec2::new(x, y)
+            })
+            .collect();
+        for &pos in &positions {
+            track(&mut app, pos);
+        }
+        app.update();
+
+        let config = app.world().resource::<ActiveWorld>();
+        let index = app.world().resource::<SpatialIndex>();
+        let radius = 64.0;
+        let mut indexed_checks = 0;
+        for &pos in &positions {
+            indexed_checks += index.candidates(pos, radius, config).count();
+            // Same answer as checking every entity.
+            let brute = positions
+                .iter()
+                .filter(|&&other| wrapped_offset(pos, other, config).length() <= radius)
+                .count();
+            assert_eq!(index.query_radius(pos, radius, config).count(), brute);
+        }
+        let brute_checks = positions.len() * positions.len();
+        assert!(
+            indexed_checks * 10 < brute_checks,
+            "{indexed_checks} indexed checks vs {brute_checks} brute-force"
+        );
+    }
+}
@@ -0,0 +1,559 @@
+//! Where the player arrives on a world.
+//!
+//! The surface of column 0 may be a lake, a one-tile pit or a thin crust
+//! over a cave. [`choose_world_spawn`] instead scans the columns around
+//! x = 0 and scores each as a place to stand: flat, solid, dry, harmless
+//! ground in the planet's primary biome wins. The choice is kept in
+//! [`WorldSpawnPoint`] for the first spawn, warps and respawns after death.
+//!
+//! With a [`SpawnPlatform`] configured, the ground around the spawn is then
+//! flattened and cleared so every planet starts with room to build. The
+//! spot is picked on freshly generated terrain, so the platform lands in
+//! the same place on every visit. A road is paved along the surface from
+//! the platform to the trader's post.
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::{ChunkRevisions, DirtyChunks};
+use crate::liquid::LiquidCell;
+use crate::registry::player::PlayerConfig;
+use crate::registry::tile::TileId;
+use crate::trader::spawn::trader_post;
+use crate::world::chunk::{tile_to_chunk, update_bitmasks_around, Layer, LoadedChunks, WorldMap};
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::growth::mark_meshes_dirty;
+use crate::world::rc_lighting::RcGridDirty;
+use crate::world::surface_paths::{pave_surface_paths, spawn_road_style};
+
+/// Columns on each side of a candidate that make up its flatness window.
+pub const FLATNESS_RADIUS: usize = 3;
+/// Solid tiles required from the surface tile down, so a thin crust over a
+/// cave doesn't count as ground.
+const GROUND_DEPTH: i32 = 3;
+/// Minimum air gap (tiles) above the surface.
+const MIN_HEADROOM_TILES: i32 = 2;
+/// Score bonus for standing in the planet's primary biome.
+const PRIMARY_BIOME_BONUS: f32 = 4.0;
+/// Score penalty per unsafe column (hazard or no ground) in the window.
+const NEARBY_HAZARD_PENALTY: f32 = 2.0;
+/// Score penalty per column away from the search centre, so the nearer of
+/// two equally good spots wins.
+const DISTANCE_PENALTY: f32 = 0.01;
+
+/// The spot a player arrives at on the current world.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct WorldSpawnPoint {
+    /// Tile the player's feet are in: the first air tile above the ground.
+    pub tile: (i32, i32),
+    /// Player centre in world pixels.
+    pub position: Vec2,
+}
+
+impl WorldSpawnPoint {
+    /// Spawn point for a player of `player_height` px standing in `tile`.
+    pub fn standing_in(tile: (i32, i32), tile_size: f32, player_height: f32) -> Self {
+        Self {
+            tile,
+            position: Vec2::new(
+                tile.0 as f32 * tile_size + tile_size / 2.0,
+                tile.1 as f32 * tile_size + player_height / 2.0,
+            ),
+        }
+    }
+}
+
+/// Flat, open ground built around the spawn on planets.
+#[derive(Resource, Debug, Clone)]
+pub struct SpawnPlatform {
+    /// Columns flattened, centred on the spawn column; 0 leaves the terrain
+    /// alone.
+    pub width: i32,
+    /// Rows of air cleared above the platform.
+    pub clearance: i32,
+    /// Rows under the platform filled in where the ground is lower.
+    pub foundation: i32,
+}
+
+impl Default for SpawnPlatform {
+    fn default() -> Self {
+        Self {
+            width: 15,
+            clearance: 8,
+            foundation: 4,
+        }
+    }
+}
+
+impl SpawnPlatform {
+    pub fn enabled(&self) -> bool {
+        self.width > 0
+    }
+}
+
+/// What spawn scoring needs to know about one column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnColumn {
+    /// Surface tile height.
+    pub surface_y: i32,
+    /// The surface tile and the tiles just below it are solid.
+    pub solid_ground: bool,
+    /// The air gap above the surface is tall enough for the player.
+    pub headroom: bool,
+    /// The surface hurts on contact or liquid sits on it.
+    pub hazard: bool,
+    /// The column lies in the planet's primary biome.
+    pub primary_biome: bool,
+}
+
+/// Score for standing on the middle column of `window`, which holds
+/// `2 * FLATNESS_RADIUS + 1` columns; higher is better. `None` if the
+/// player can't stand there.
+///
+/// Uneven surface heights (variance), nearby hazards and a foreign biome all
+/// lower the score.
+pub fn spawn_score(window: &[SpawnColumn]) -> Option<f32> {
+    debug_assert_eq!(window.len(), 2 * FLATNESS_RADIUS + 1);
+    let centre = window[FLATNESS_RADIUS];
+    if !centre.solid_ground || !centre.headroom || centre.hazard {
+        return None;
+    }
+    let n = window.len() as f32;
+    let mean = window.iter().map(|c| c.surface_y as f32).sum::<f32>() / n;
+    let variance = window
+        .iter()
+        .map(|c| (c.surface_y as f32 - mean).powi(2))
+        .sum::<f32>()
+        / n;
+    let unsafe_columns = window
+        .iter()
+        .filter(|c| c.hazard || !c.solid_ground)
+        .count() as f32;
+    let biome_bonus = if centre.primary_biome {
+        PRIMARY_BIOME_BONUS
+    } else {
+        0.0
+    };
+    Some(biome_bonus - variance - unsafe_columns * NEARBY_HAZARD_PENALTY)
+}
+
+/// Index of the best column to spawn on, preferring columns near `centre`.
+/// Columns within `FLATNESS_RADIUS` of either end only serve as context.
+/// Equal scores are settled by `seed`, so a world always picks the same spot.
+pub fn pick_spawn_column(columns: &[SpawnColumn], centre: usize, seed: u32) -> Option<usize> {
+    columns
+        .windows(2 * FLATNESS_RADIUS + 1)
+        .enumerate()
+        .filter_map(|(start, window)| {
+            let index = start + FLATNESS_RADIUS;
+            let score = spawn_score(window)? - index.abs_diff(centre) as f32 * DISTANCE_PENALTY;
+            Some((index, score))
+        })
+        .max_by(|a, b| {
+            a.1.total_cmp(&b.1)
+                .then_with(|| tie_break(seed, a.0).cmp(&tie_break(seed, b.0)))
+        })
+        .map(|(index, _)| index)
+}
+
+/// Seeded hash of a column index for breaking score ties.
+fn tie_break(seed: u32, index: usize) -> u32 {
+    let mut h = seed.wrapping_add((index as u32).wrapping_mul(0x9e37_79b9));
+    h = (h ^ (h >> 16)).wrapping_mul(0x85eb_ca6b);
+    h = (h ^ (h >> 13)).wrapping_mul(0xc2b2_ae35);
+    h ^ (h >> 16)
+}
+
+/// Spawn facts for column `tile_x`, generating its chunks as needed.
+fn spawn_column(
+    world_map: &mut WorldMap,
+    tile_x: i32,
+    headroom: i32,
+    ctx: &WorldCtxRef,
+) -> SpawnColumn {
+    let tr = ctx.tile_registry;
+    let surface_y = ctx
+        .noise_cache
+        .surface_height_at(tile_x, ctx.config, ctx.planet_config);
+    let mut fg = |y: i32| world_map.get_tile_mut(tile_x, y, Layer::Fg, ctx);
+
+    let ground = fg(surface_y);
+    let solid_ground = (surface_y - GROUND_DEPTH + 1..=surface_y).all(|y| tr.is_solid(fg(y)));
+    let headroom_clear = (1..=headroom).all(|dy| !tr.is_solid(fg(surface_y + dy)));
+    let wet =
+        (1..=headroom).any(|dy| !world_map.get_liquid(tile_x, surface_y + dy, ctx).is_empty());
+    let primary = ctx
+        .biome_registry
+        .id_by_name(&ctx.planet_config.primary_biome);
+
+    SpawnColumn {
+        surface_y,
+        solid_ground,
+        headroom: headroom_clear,
+        hazard: wet || tr.get(ground).damage_on_contact > 0.0,
+        primary_biome: ctx
+            .biome_map
+            .biome_at(ctx.config.wrap_tile_x(tile_x) as u32)
+            == primary,
+    }
+}
+
+/// Best spawn within `radius` columns of x = 0 on a planet. Falls back to
+/// standing on column 0 if no column qualifies.
+pub fn find_world_spawn(
+    world_map: &mut WorldMap,
+    radius: i32,
+    player_height: f32,
+    ctx: &WorldCtxRef,
+) -> WorldSpawnPoint {
+    let tile_size = ctx.config.tile_size;
+    let headroom = ((player_height / tile_size).ceil() as i32).max(MIN_HEADROOM_TILES);
+    let reach = radius.max(0) + FLATNESS_RADIUS as i32;
+    let columns: Vec<SpawnColumn> = (-reach..=reach)
+        .map(|tx| spawn_column(world_map, tx, headroom, ctx))
+        .collect();
+
+    let centre = reach as usize;
+    let index = pick_spawn_column(&columns, centre, ctx.config.seed).unwrap_or_else(|| {
+        warn!("No safe spawn within {radius} columns of x = 0; using column 0");
+        centre
+    });
+    let tile_x = index as i32 - reach;
+    let tile = (tile_x, columns[index].surface_y + 1);
+    WorldSpawnPoint::standing_in(tile, tile_size, player_height)
+}
+
+/// Choose the spawn point for the world being entered: the hull centre on a
+/// ship, the best scored surface spot on a planet.
+pub fn choose_world_spawn(
+    mut commands: Commands,
+    ctx: WorldCtx,
+    mut world_map: ResMut<WorldMap>,
+    player_config: Res<PlayerConfig>,
+    platform: Res<SpawnPlatform>,
+) {
+    let ctx_ref = ctx.as_ref();
+    let config = ctx_ref.config;
+    let is_ship = matches!(
+        config.address,
+        crate::cosmos::address::CelestialAddress::Ship { .. }
+    );
+
+    let height = player_config.collision_size().y;
+    let spawn = if is_ship {
+        let tile = (config.width_tiles / 2, config.height_tiles / 2 - 2);
+        WorldSpawnPoint::standing_in(tile, config.tile_size, height)
+    } else if platform.enabled() {
+        // Scored on generated terrain only, so neither saved edits nor the
+        // platform itself can move the spot between visits.
+        find_world_spawn(
+            &mut WorldMap::default(),
+            player_config.spawn_search_radius,
+            height,
+            &ctx_ref,
+        )
+    } else {
+        find_world_spawn(
+            &mut world_map,
+            player_config.spawn_search_radius,
+            height,
+            &ctx_ref,
+        )
+    };
+    info!("World spawn point at tile {:?}", spawn.tile);
+    commands.insert_resource(spawn);
+}
+
+/// Flatten `platform` around the ground tile `(tile_x, ground_y)`: every
+/// column gets the ground tile's surface at `ground_y`, the tile under it
+/// filling gaps down to the foundation, and air above. Tiles in `keep`
+/// chunks are left alone. Returns the tiles changed.
+pub fn flatten_spawn_platform(
+    world_map: &mut WorldMap,
+    (tile_x, ground_y): (i32, i32),
+    platform: &SpawnPlatform,
+    keep: &ChunkRevisions,
+    ctx: &WorldCtxRef,
+) -> Vec<(i32, i32)> {
+    let surface = world_map.get_tile_mut(tile_x, ground_y, Layer::Fg, ctx);
+    let fill = world_map.get_tile_mut(tile_x, ground_y - 1, Layer::Fg, ctx);
+    let left = tile_x - platform.width / 2;
+    let rows = ground_y - platform.foundation..=ground_y + platform.clearance;
+    let mut changed = Vec::new();
+    for x in left..left + platform.width {
+        for y in rows.clone() {
+            if y < 0 || y >= ctx.config.height_tiles || ctx.config.outside_x(x) {
+                continue;
+            }
+            let chunk = tile_to_chunk(ctx.config.wrap_tile_x(x), y, ctx.config.chunk_size);
+            if keep.contains(&chunk) {
+                continue;
+            }
+            let current = world_map.get_tile_mut(x, y, Layer::Fg, ctx);
+            let wanted = if y > ground_y {
+                world_map.set_liquid(x, y, LiquidCell::EMPTY, ctx);
+                TileId::AIR
+            } else if y == ground_y {
+                surface
+            } else if ctx.tile_registry.is_solid(current) {
+                current
+            } else {
+                fill
+            };
+            if wanted != current {
+                world_map.set_tile(x, y, Layer::Fg, wanted, ctx);
+                changed.push((x, y));
+            }
+        }
+    }
+    changed
+}
+
+/// Build the [`SpawnPlatform`] under a planet's spawn point and pave the road
+/// from it to the trader's post. Chunks restored from a save already hold
+/// both, and maybe the player's building on them, so only generated chunks
+/// are changed.
+#[allow(clippy::too_many_arguments)]
+pub fn build_spawn_platform(
+    mut commands: Commands,
+    ctx: WorldCtx,
+    spawn: Res<WorldSpawnPoint>,
+    platform: Res<SpawnPlatform>,
+    mut world_map: ResMut<WorldMap>,
+    mut dirty_chunks: ResMut<DirtyChunks>,
+    loaded_chunks: Res<LoadedChunks>,
+    rc_dirty: Option<ResMut<RcGridDirty>>,
+) {
+    let ctx_ref = ctx.as_ref();
+    let is_ship = matches!(
+        ctx_ref.config.address,
+        crate::cosmos::address::CelestialAddress::Ship { .. }
+    );
+    if is_ship || !platform.enabled() {
+        return;
+    }
+    let (tile_x, feet_y) = spawn.tile;
+    // The road goes down first, so the platform levels its end at the spawn.
+    let post = trader_post(ctx_ref.config, ctx_ref.noise_cache, ctx_ref.planet_config);
+    let mut changed = match (spawn_road_style(&ctx_ref), post) {
+        (Some(style), Some(post)) => pave_surface_paths(
+            &mut world_map,
+            &[IVec2::new(tile_x, feet_y - 1), post],
+            &style,
+            &dirty_chunks.0,
+            &ctx_ref,
+        ),
+        _ => Vec::new(),
+    };
+    changed.extend(flatten_spawn_platform(
+        &mut world_map,
+        (tile_x, feet_y - 1),
+        &platform,
+        &dirty_chunks.0,
+        &ctx_ref,
+    ));
+    if changed.is_empty() {
+        return;
+    }
+    for &(x, y) in &changed {
+        for chunk in update_bitmasks_around(&mut world_map, x, y, Layer::Fg, &ctx_ref) {
+            dirty_chunks.0.insert(chunk);
+            mark_meshes_dirty(&mut commands, &loaded_chunks, chunk, &ctx_ref);
+        }
+    }
+    if let Some(mut rc_dirty) = rc_dirty {
+        rc_dirty.0 = true;
+    }
+    info!(
+        "Built spawn platform and road at tile {:?} ({} tiles)",
+        spawn.tile,
+        changed.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    /// Safe, dry primary-biome columns with the given surface heights.
+    fn profile(heights: &[i32]) -> Vec<SpawnColumn> {
+        heights
+            .iter()
+            .map(|&surface_y| SpawnColumn {
+                surface_y,
+                solid_ground: true,
+                headroom: true,
+                hazard: false,
+                primary_biome: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flat_ground_beats_bumpy_ground() {
+        let flat = spawn_score(&profile(&[10; 7])).unwrap();
+        let bumpy = spawn_score(&profile(&[10, 12, 9, 10, 13, 8, 10])).unwrap();
+        assert!(flat > bumpy, "{flat} vs {bumpy}");
+    }
+
+    #[test]
+    fn unusable_centres_have_no_score() {
+        let mut pit = profile(&[10; 7]);
+        pit[FLATNESS_RADIUS].solid_ground = false;
+        assert_eq!(spawn_score(&pit), None);
+
+        let mut lake = profile(&[10; 7]);
+        lake[FLATNESS_RADIUS].hazard = true;
+        assert_eq!(spawn_score(&lake), None);
+
+        let mut low_ceiling = profile(&[10; 7]);
+        low_ceiling[FLATNESS_RADIUS].headroom = false;
+        assert_eq!(spawn_score(&low_ceiling), None);
+    }
+
+    #[test]
+    fn nearby_hazards_and_foreign_biome_lower_the_score() {
+        let base = spawn_score(&profile(&[10; 7])).unwrap();
+
+        let mut near_lava = profile(&[10; 7]);
+        near_lava[0].hazard = true;
+        assert!(spawn_score(&near_lava).unwrap() < base);
+
+        let mut foreign = profile(&[10; 7]);
+        foreign[FLATNESS_RADIUS].primary_biome = false;
+        assert!(spawn_score(&foreign).unwrap() < base);
+    }
+
+    #[test]
+    fn pick_skips_a_one_tile_pit_at_the_centre() {
+        // A pit at index 8 (the centre); flat ground starts at index 11.
+        let columns = profile(&[
+            20, 14, 18, 13, 19, 15, 10, 10, 4, 10, 10, 10, 10, 10, 10, 10, 10, 10,
+        ]);
+        let pick = pick_spawn_column(&columns, 8, 42).unwrap();
+        let window = &columns[pick - FLATNESS_RADIUS..=pick + FLATNESS_RADIUS];
+        assert!(window.iter().all(|c| c.surface_y == 10), "picked {pick}");
+    }
+
+    #[test]
+    fn pick_prefers_nearer_spot_and_is_deterministic_per_seed() {
+        let columns = profile(&[10; 31]);
+        assert_eq!(pick_spawn_column(&columns, 15, 7), Some(15));
+
+        // A hazard at the centre leaves two equally good spots, just out of
+        // its reach on either side: the seed decides, and the same seed
+        // always decides the same way.
+        let mut columns = profile(&[10; 31]);
+        columns[15].hazard = true;
+        let picks: Vec<_> = (0..16)
+            .map(|seed| pick_spawn_column(&columns, 15, seed).unwrap())
+            .collect();
+        assert!(picks.iter().all(|&p| p == 11 || p == 19), "{picks:?}");
+        assert!(picks.contains(&11) && picks.contains(&19), "{picks:?}");
+        for seed in 0..16 {
+            assert_eq!(
+                pick_spawn_column(&columns, 15, seed),
+                Some(picks[seed as usize])
+            );
+        }
+    }
+
+    #[test]
+    fn world_spawn_stands_on_safe_ground() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut world_map = WorldMap::default();
+        let spawn = find_world_spawn(&mut world_map, 32, 40.0, &ctx);
+
+        let (tx, ty) = spawn.tile;
+        assert!(tx.abs() <= 32);
+        assert!(
+            world_map.is_solid(tx, ty - 1, &ctx),
+            "no ground under {:?}",
+            spawn.tile
+        );
+        assert!(!world_map.is_solid(tx, ty, &ctx));
+        assert!(!world_map.is_solid(tx, ty + 1, &ctx));
+        assert_eq!(spawn.position.y, ty as f32 * wc.tile_size + 20.0);
+
+        // Same world, same spot.
+        let again = find_world_spawn(&mut WorldMap::default(), 32, 40.0, &ctx);
+        assert_eq!(again, spawn);
+    }
+
+    #[test]
+    fn spawn_platform_is_flat_and_clear_above() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut world_map = WorldMap::default();
+        let spawn = find_world_spawn(&mut world_map, 32, 40.0, &ctx);
+        let (tx, ground_y) = (spawn.tile.0, spawn.tile.1 - 1);
+        let platform = SpawnPlatform::default();
+        let keep = ChunkRevisions::default();
+        flatten_spawn_platform(&mut world_map, (tx, ground_y), &platform, &keep, &ctx);
+
+        let left = tx - platform.width / 2;
+        let surface = world_map.get_tile(tx, ground_y, Layer::Fg, &ctx).unwrap();
+        for x in left..left + platform.width {
+            assert_eq!(
+                world_map.get_tile(x, ground_y, Layer::Fg, &ctx),
+                Some(surface),
+                "column {x} is not level"
+            );
+            for y in ground_y - platform.foundation..ground_y {
+                assert!(world_map.is_solid(x, y, &ctx), "gap under ({x}, {y})");
+            }
+            for y in ground_y + 1..=ground_y + platform.clearance {
+                assert_eq!(
+                    world_map.get_tile(x, y, Layer::Fg, &ctx),
+                    Some(TileId::AIR),
+                    "({x}, {y}) is not clear"
+                );
+            }
+        }
+
+        // Flattening again changes nothing.
+        let again = flatten_spawn_platform(&mut world_map, (tx, ground_y), &platform, &keep, &ctx);
+        assert!(again.is_empty(), "{again:?}");
+    }
+
+    #[test]
+    fn spawn_road_reaches_the_trader_post() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let spawn = find_world_spawn(&mut WorldMap::default(), 32, 40.0, &ctx);
+        let ground = IVec2::new(spawn.tile.0, spawn.tile.1 - 1);
+        let post = trader_post(&wc, &nc, &pc).unwrap();
+        let style = spawn_road_style(&ctx).unwrap();
+
+        let mut world_map = WorldMap::default();
+        let keep = ChunkRevisions::default();
+        let changed = pave_surface_paths(&mut world_map, &[ground, post], &style, &keep, &ctx);
+        assert!(!changed.is_empty());
+        for end in [ground, post] {
+            assert_eq!(
+                world_map.get_tile(end.x, end.y, Layer::Fg, &ctx),
+                Some(style.tile)
+            );
+        }
+    }
+
+    #[test]
+    fn spawn_platform_leaves_kept_chunks_alone() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut world_map = WorldMap::default();
+        let ground_y = nc.surface_height_at(0, &wc, &pc);
+        let platform = SpawnPlatform::default();
+        let mut keep = ChunkRevisions::default();
+        // The platform straddles x = 0, so the last column of chunks too.
+        let last = wc.width_tiles / wc.chunk_size as i32 - 1;
+        for x in [last, 0] {
+            for y in -1..=1 {
+                keep.insert((x, ground_y / wc.chunk_size as i32 + y));
+            }
+        }
+        let changed = flatten_spawn_platform(&mut world_map, (0, ground_y), &platform, &keep, &ctx);
+        assert!(changed.is_empty(), "{changed:?}");
+    }
+}
@@ -7,7 +7,6 @@ use crate::object::registry::ObjectRegistry;
 use crate::registry::tile::TileId;
 use crate::world::chunk::ChunkData;
 use crate::world::ctx::WorldCtxRef;
-use crate::world::terrain_gen::surface_height;
 
 /// Minimum spacing between trees (in tiles).
 const TREE_MIN_SPACING: i32 = 8;
@@ -79,13 +78,9 @@ pub fn populate_surface_objects(
         }
 
         // Find surface height at the anchor column
-        let surface_y = surface_height(
-            ctx.noise_cache,
-            world_x,
-            ctx.config,
-            ctx.planet_config.layers.surface.terrain_frequency,
-            ctx.planet_config.layers.surface.terrain_amplitude,
-        );
+        let surface_y = ctx
+            .noise_cache
+            .surface_height_at(world_x, ctx.config, ctx.planet_config);
 
         // Verify anchor tiles: surface must be roughly level (±1 tile) under
         // the tree footprint. Use the minimum surface height as the anchor so
@@ -96,13 +91,9 @@ pub fn populate_surface_objects(
         for dx in 1..tree_w as i32 {
             let tx = world_x + dx;
             let wrapped_tx = ctx.config.wrap_tile_x(tx);
-            let sh = surface_height(
-                ctx.noise_cache,
-                wrapped_tx,
-                ctx.config,
-                ctx.planet_config.layers.surface.terrain_frequency,
-                ctx.planet_config.layers.surface.terrain_amplitude,
-            );
+            let sh = ctx
+                .noise_cache
+                .surface_height_at(wrapped_tx, ctx.config, ctx.planet_config);
             min_sh = min_sh.min(sh);
             max_sh = max_sh.max(sh);
             if max_sh - min_sh > 1 {
@@ -123,6 +114,15 @@ pub fn populate_surface_objects(
             continue;
         }
 
+        // The trunk needs ground under it; terrain edits after generation
+        // (the spawn platform) can cut it away.
+        if local_y > 0 {
+            let below = ((local_y as u32 - 1) * chunk_size + local_x) as usize;
+            if chunk.fg.tiles[below] == TileId::AIR {
+                continue;
+            }
+        }
+
         // Verify all tiles in the tree footprint are air
         let mut all_air = true;
         for dy in 0..tree_h {
@@ -177,10 +177,12 @@ pub fn populate_surface_objects(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
     use crate::object::definition::ObjectId;
     use crate::test_helpers::fixtures;
-    use crate::world::terrain_gen::generate_chunk_tiles;
+    use crate::world::terrain_gen::{generate_chunk_tiles, surface_height};
 
     fn test_object_registry_with_tree() -> ObjectRegistry {
         use crate::object::definition::{ObjectDef, ObjectType, PlacementRule};
@@ -264,6 +266,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
 
         let tiles2 = generate_chunk_tiles(chunk_x, chunk_y, &ctx);
@@ -282,6 +286,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
 
         populate_surface_objects(&mut chunk1, chunk_x, chunk_y, &ctx, &obj_reg);
@@ -331,6 +337,8 @@ mod tests {
             objects: Vec::new(),
             occupancy: vec![None; len],
             damage: vec![0; len],
+            tile_state: vec![0; len],
+            signs: HashMap::new(),
         };
 
         populate_surface_objects(&mut chunk, chunk_x, chunk_y, &ctx, &obj_reg);
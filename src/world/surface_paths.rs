@@ -0,0 +1,286 @@
+//! Surface paths: graded routes paved along the surface between structure
+//! anchors.
+//!
+//! A path runs column by column the short way round (across the seam on
+//! wrapping worlds) from one anchor to the next. Its height follows the
+//! surface averaged over a few columns and never changes by more than one
+//! tile per column, so it can be walked without jumping. Paving lays the
+//! path tile on that row, clears the tiles above it up to the old surface and
+//! fills any gap below it. Everything is derived from the anchors and the
+//! memoized surface heights, so the same anchors always give the same tiles.
+
+use bevy::prelude::*;
+
+use crate::cosmos::persistence::ChunkRevisions;
+use crate::registry::tile::TileId;
+use crate::registry::world::ActiveWorld;
+use crate::world::chunk::{tile_to_chunk, Layer, WorldMap};
+use crate::world::ctx::WorldCtxRef;
+
+/// Columns on each side averaged into the path height.
+const PATH_SMOOTHING: i32 = 3;
+/// Open tiles kept above the path.
+const PATH_CLEARANCE: i32 = 3;
+
+/// Tiles a path is paved with, and how far apart anchors may be to be
+/// joined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathStyle {
+    /// Tile laid on the path row.
+    pub tile: TileId,
+    /// Tile filling the gap where the path runs above the surface.
+    pub fill: TileId,
+    /// Most columns between two anchors joined by a path.
+    pub max_gap: i32,
+}
+
+/// Signed column distance from `from` to `to`, the short way round on
+/// wrapping worlds.
+fn column_delta(from: i32, to: i32, wc: &ActiveWorld) -> i32 {
+    let dx = to - from;
+    if !wc.wrap_x {
+        return dx;
+    }
+    let dx = dx.rem_euclid(wc.width_tiles);
+    if dx > wc.width_tiles / 2 {
+        dx - wc.width_tiles
+    } else {
+        dx
+    }
+}
+
+/// Surface height at `tile_x` averaged over [`PATH_SMOOTHING`] columns on
+/// each side.
+fn smoothed_surface(tile_x: i32, ctx: &WorldCtxRef) -> f32 {
+    let sum: i32 = (-PATH_SMOOTHING..=PATH_SMOOTHING)
+        .map(|dx| {
+            ctx.noise_cache
+                .surface_height_at(tile_x + dx, ctx.config, ctx.planet_config)
+        })
+        .sum();
+    sum as f32 / (2 * PATH_SMOOTHING + 1) as f32
+}
+
+/// Path from anchor `from` to anchor `to` (tile coords of the ground each
+/// structure stands on): one tile per column, x wrapped, starting on `from`
+/// and ending on `to`. `None` if the anchors are more than one tile apart in
+/// height per column between them.
+pub fn surface_path(from: IVec2, to: IVec2, ctx: &WorldCtxRef) -> Option<Vec<IVec2>> {
+    let dx = column_delta(from.x, to.x, ctx.config);
+    let len = dx.abs();
+    if (to.y - from.y).abs() > len {
+        return None;
+    }
+    let step = dx.signum();
+    let mut path = Vec::with_capacity(len as usize + 1);
+    let mut y = from.y;
+    for i in 0..=len {
+        let x = from.x + i * step;
+        // Stay within one tile per column of both anchors, and of the
+        // previous column, so the path always ends on `to`.
+        let lo = (from.y - i).max(to.y - (len - i)).max(y - 1);
+        let hi = (from.y + i).min(to.y + (len - i)).min(y + 1);
+        y = (smoothed_surface(x, ctx).round() as i32).clamp(lo, hi);
+        path.push(IVec2::new(ctx.config.wrap_tile_x(x), y));
+    }
+    Some(path)
+}
+
+/// Paths joining each anchor to its nearest neighbours along the surface
+/// that are at most `max_gap` columns away. Anchors are sorted by column
+/// first, so their order doesn't change the result.
+pub fn connect_anchors(anchors: &[IVec2], max_gap: i32, ctx: &WorldCtxRef) -> Vec<Vec<IVec2>> {
+    let mut anchors: Vec<IVec2> = anchors
+        .iter()
+        .map(|a| IVec2::new(ctx.config.wrap_tile_x(a.x), a.y))
+        .collect();
+    anchors.sort_unstable_by_key(|a| (a.x, a.y));
+    anchors.dedup();
+
+    let mut pairs: Vec<(IVec2, IVec2)> = anchors.windows(2).map(|w| (w[0], w[1])).collect();
+    // On a wrapping world the last anchor is also next to the first.
+    if ctx.config.wrap_x && anchors.len() > 2 {
+        pairs.push((anchors[anchors.len() - 1], anchors[0]));
+    }
+    pairs
+        .into_iter()
+        .filter(|(a, b)| column_delta(a.x, b.x, ctx.config).abs() <= max_gap)
+        .filter_map(|(a, b)| surface_path(a, b, ctx))
+        .collect()
+}
+
+/// Foreground tiles paving `path` sets, as `(tile_x, tile_y, tile)`.
+pub fn path_edits(path: &[IVec2], style: &PathStyle, ctx: &WorldCtxRef) -> Vec<(i32, i32, TileId)> {
+    let mut edits = Vec::new();
+    for step in path {
+        let surface = ctx
+            .noise_cache
+            .surface_height_at(step.x, ctx.config, ctx.planet_config);
+        for y in surface + 1..step.y {
+            edits.push((step.x, y, style.fill));
+        }
+        edits.push((step.x, step.y, style.tile));
+        // Up to the old surface plus whatever decoration sat on it.
+        for y in step.y + 1..=(surface + 1).max(step.y + PATH_CLEARANCE) {
+            edits.push((step.x, y, TileId::AIR));
+        }
+    }
+    edits
+}
+
+/// Road between the spawn platform and the trader's post: stone on a dirt
+/// bed, joined however far apart the two are. `None` if the tile registry
+/// lacks either tile.
+pub fn spawn_road_style(ctx: &WorldCtxRef) -> Option<PathStyle> {
+    Some(PathStyle {
+        tile: ctx.tile_registry.try_by_name("stone")?,
+        fill: ctx.tile_registry.try_by_name("dirt")?,
+        max_gap: ctx.config.width_tiles,
+    })
+}
+
+/// Pave paths between `anchors` into `world_map`. Tiles in `keep` chunks are
+/// left alone. Returns the tiles changed; the caller updates bitmasks and
+/// meshes for them.
+pub fn pave_surface_paths(
+    world_map: &mut WorldMap,
+    anchors: &[IVec2],
+    style: &PathStyle,
+    keep: &ChunkRevisions,
+    ctx: &WorldCtxRef,
+) -> Vec<(i32, i32)> {
+    let mut changed = Vec::new();
+    for path in connect_anchors(anchors, style.max_gap, ctx) {
+        for (x, y, tile) in path_edits(&path, style, ctx) {
+            if y < 0 || y >= ctx.config.height_tiles {
+                continue;
+            }
+            if keep.contains(&tile_to_chunk(x, y, ctx.config.chunk_size)) {
+                continue;
+            }
+            if world_map.get_tile(x, y, Layer::Fg, ctx) != Some(tile) {
+                world_map.set_tile(x, y, Layer::Fg, tile, ctx);
+                changed.push((x, y));
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn anchor(x: i32, ctx: &WorldCtxRef) -> IVec2 {
+        let y = ctx
+            .noise_cache
+            .surface_height_at(x, ctx.config, ctx.planet_config);
+        IVec2::new(ctx.config.wrap_tile_x(x), y)
+    }
+
+    fn style(ctx: &WorldCtxRef) -> PathStyle {
+        PathStyle {
+            tile: ctx.tile_registry.by_name("stone"),
+            fill: ctx.tile_registry.by_name("dirt"),
+            max_gap: 80,
+        }
+    }
+
+    /// Whether `path` runs from `a` to `b` one column at a time without
+    /// steps taller than a tile.
+    fn connects(path: &[IVec2], a: IVec2, b: IVec2, wc: &ActiveWorld) -> bool {
+        path.first() == Some(&a)
+            && path.last() == Some(&b)
+            && path.windows(2).all(|w| {
+                column_delta(w[0].x, w[1].x, wc).abs() == 1 && (w[1].y - w[0].y).abs() <= 1
+            })
+    }
+
+    #[test]
+    fn path_connects_two_anchors_along_the_surface() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let (a, b) = (anchor(100, &ctx), anchor(160, &ctx));
+
+        let path = surface_path(a, b, &ctx).unwrap();
+        assert_eq!(path.len(), 61);
+        assert!(connects(&path, a, b, &wc));
+        let heights: Vec<i32> = (100 - PATH_SMOOTHING..=160 + PATH_SMOOTHING)
+            .map(|x| nc.surface_height_at(x, &wc, &pc))
+            .collect();
+        let (lo, hi) = (
+            *heights.iter().min().unwrap(),
+            *heights.iter().max().unwrap(),
+        );
+        assert!(path.iter().all(|p| (lo..=hi).contains(&p.y)), "{path:?}");
+
+        let mut map = WorldMap::default();
+        let keep = ChunkRevisions::default();
+        let changed = pave_surface_paths(&mut map, &[b, a], &style(&ctx), &keep, &ctx);
+        assert!(!changed.is_empty());
+        for step in &path {
+            assert_eq!(
+                map.get_tile(step.x, step.y, Layer::Fg, &ctx),
+                Some(tr.by_name("stone"))
+            );
+            assert_eq!(
+                map.get_tile(step.x, step.y + 1, Layer::Fg, &ctx),
+                Some(TileId::AIR)
+            );
+        }
+    }
+
+    #[test]
+    fn path_crosses_the_seam_on_wrapping_worlds() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let (a, b) = (anchor(wc.width_tiles - 20, &ctx), anchor(20, &ctx));
+
+        let path = surface_path(a, b, &ctx).unwrap();
+        assert_eq!(path.len(), 41);
+        assert!(connects(&path, a, b, &wc));
+        assert!(path.iter().all(|p| (0..wc.width_tiles).contains(&p.x)));
+    }
+
+    #[test]
+    fn regeneration_lays_the_same_path_tiles() {
+        let anchors = [130, 40, 90, 2030, 700];
+        let edits = || {
+            let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+            let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+            let anchors: Vec<IVec2> = anchors.iter().map(|&x| anchor(x, &ctx)).collect();
+            let mut shuffled = anchors.clone();
+            shuffled.reverse();
+            let style = style(&ctx);
+            let paths = connect_anchors(&anchors, style.max_gap, &ctx);
+            assert_eq!(paths, connect_anchors(&shuffled, style.max_gap, &ctx));
+            paths
+                .iter()
+                .flat_map(|path| path_edits(path, &style, &ctx))
+                .collect::<Vec<_>>()
+        };
+        let first = edits();
+        assert!(!first.is_empty());
+        assert_eq!(first, edits());
+    }
+
+    #[test]
+    fn distant_anchors_stay_unconnected() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let anchors = [anchor(100, &ctx), anchor(150, &ctx), anchor(600, &ctx)];
+
+        let paths = connect_anchors(&anchors, 80, &ctx);
+        assert_eq!(paths.len(), 1);
+        assert!(connects(&paths[0], anchors[0], anchors[1], &wc));
+    }
+
+    #[test]
+    fn anchors_too_steep_for_a_path_are_rejected() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let a = IVec2::new(100, 700);
+        assert!(surface_path(a, IVec2::new(105, 710), &ctx).is_none());
+    }
+}
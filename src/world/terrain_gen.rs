@@ -1,8 +1,12 @@
+use std::ops::Range;
+use std::sync::{Mutex, MutexGuard};
+
 use bevy::prelude::*;
 use noise::{NoiseFn, Perlin};
 
 use crate::liquid::data::{LiquidCell, LiquidId};
-use crate::registry::biome::WorldLayer;
+use crate::math::SplitMix64;
+use crate::registry::biome::{PlanetConfig, SurfaceDecoration, WorldLayer};
 use crate::registry::tile::TileId;
 use crate::registry::world::ActiveWorld;
 use crate::world::ctx::WorldCtxRef;
@@ -12,21 +16,162 @@ const SURFACE_BASE: f64 = 0.7;
 /// Cached Perlin noise instances to avoid per-tile allocation.
 #[derive(Resource)]
 pub struct TerrainNoiseCache {
+    /// Seed every noise field below was built from.
+    pub seed: u32,
     pub surface: Perlin,
     pub cave: Perlin,
     pub ore: Perlin,
+    /// Cluster fields of surface decorations, see [`decoration_at`].
+    pub decoration: Perlin,
+    /// Memoized surface heights, see [`TerrainNoiseCache::surface_height_at`].
+    pub surface_heights: SurfaceHeightCache,
 }
 
 impl TerrainNoiseCache {
     pub fn new(seed: u32) -> Self {
         Self {
+            seed,
             surface: Perlin::new(seed),
             cave: Perlin::new(seed.wrapping_add(1)),
             ore: Perlin::new(seed.wrapping_add(2)),
+            decoration: Perlin::new(seed.wrapping_add(3)),
+            surface_heights: SurfaceHeightCache::default(),
+        }
+    }
+
+    /// Surface tile height at column `tile_x`, computed once per column and
+    /// shared by every caller (tile generation, spawning, decoration, trees,
+    /// structures).
+    /// Wrap-aware: `tile_x` and `tile_x + width` resolve to the same entry.
+    pub fn surface_height_at(&self, tile_x: i32, wc: &ActiveWorld, pc: &PlanetConfig) -> i32 {
+        self.surface_heights
+            .lock(self, wc, pc)
+            .get_or_compute(self, tile_x, wc, pc)
+    }
+
+    /// [`Self::surface_height_at`] for every column of `tiles_x`, taking the
+    /// memo's lock once for the whole batch.
+    pub fn surface_heights_in(
+        &self,
+        tiles_x: Range<i32>,
+        wc: &ActiveWorld,
+        pc: &PlanetConfig,
+    ) -> Vec<i32> {
+        let mut columns = self.surface_heights.lock(self, wc, pc);
+        tiles_x
+            .map(|tile_x| columns.get_or_compute(self, tile_x, wc, pc))
+            .collect()
+    }
+}
+
+/// World parameters the cached surface heights were computed for. The seed
+/// is left out: it belongs to the [`TerrainNoiseCache`] owning the memo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SurfaceKey {
+    width_tiles: i32,
+    height_tiles: i32,
+    wrap_x: bool,
+    frequency: u64,
+    amplitude: u64,
+    cliffs: u64,
+}
+
+impl SurfaceKey {
+    fn new(wc: &ActiveWorld, pc: &PlanetConfig) -> Self {
+        Self {
+            width_tiles: wc.width_tiles,
+            height_tiles: wc.height_tiles,
+            wrap_x: wc.wrap_x,
+            frequency: pc.layers.surface.terrain_frequency.to_bits(),
+            amplitude: pc.layers.surface.terrain_amplitude.to_bits(),
+            cliffs: pc.border_cliffs.fingerprint(),
         }
     }
 }
 
+#[derive(Default)]
+struct SurfaceColumns {
+    key: Option<SurfaceKey>,
+    /// One slot per world column, filled on first query.
+    heights: Vec<Option<i32>>,
+}
+
+impl SurfaceColumns {
+    fn get_or_compute(
+        &mut self,
+        noise: &TerrainNoiseCache,
+        tile_x: i32,
+        wc: &ActiveWorld,
+        pc: &PlanetConfig,
+    ) -> i32 {
+        let tile_x = wc.wrap_tile_x(tile_x);
+        let compute = || {
+            let amplitude = pc.layers.surface.terrain_amplitude;
+            let height = surface_height(
+                noise,
+                tile_x,
+                wc,
+                pc.layers.surface.terrain_frequency,
+                amplitude,
+            );
+            // Worlds without a surface stay without one.
+            if amplitude == 0.0 {
+                height
+            } else {
+                height + pc.border_cliffs.offset_at(tile_x)
+            }
+        };
+        if wc.outside_x(tile_x) {
+            return compute();
+        }
+        *self.heights[tile_x as usize].get_or_insert_with(compute)
+    }
+}
+
+/// Per-column surface height memo, including biome border cliffs. A query
+/// made with a different world size, surface noise config or cliffs (e.g.
+/// after a planet hot-reload) drops every cached column before computing.
+/// Columns past the edges of a non-wrapping world are computed without
+/// being stored.
+#[derive(Default)]
+pub struct SurfaceHeightCache {
+    columns: Mutex<SurfaceColumns>,
+}
+
+impl SurfaceHeightCache {
+    /// The memoized columns, emptied first if they were computed for
+    /// another world or config.
+    fn lock(
+        &self,
+        noise: &TerrainNoiseCache,
+        wc: &ActiveWorld,
+        pc: &PlanetConfig,
+    ) -> MutexGuard<'_, SurfaceColumns> {
+        debug_assert_eq!(
+            wc.seed, noise.seed,
+            "surface heights queried for another seed than the noise was built from"
+        );
+        let key = SurfaceKey::new(wc, pc);
+        let mut columns = self.columns.lock().unwrap();
+        if columns.key != Some(key) {
+            columns.heights.clear();
+            columns.heights.resize(wc.width_tiles as usize, None);
+            columns.key = Some(key);
+        }
+        columns
+    }
+
+    /// Number of columns currently memoized.
+    pub fn len(&self) -> usize {
+        let columns = self.columns.lock().unwrap();
+        columns.heights.iter().filter(|h| h.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 pub fn surface_height(
     noise: &TerrainNoiseCache,
     tile_x: i32,
@@ -57,9 +202,137 @@ pub fn surface_height(
     (base + noise_val * amplitude) as i32
 }
 
-/// Check whether a fill_block tile should be replaced with an ore vein.
-/// Uses a separate Perlin noise layer with different frequency offsets per ore type.
-/// Each ore has a depth range (below surface) and a noise threshold.
+/// Decoration tile on top of the surface at column `tile_x`, if any.
+///
+/// Decorations are tried in order. Each has its own cluster field: Perlin
+/// noise sampled on a cylinder on wrapping worlds (so clusters continue
+/// across the seam), offset along a third axis per decoration. A column
+/// inside a cluster then rolls `density` with a seeded per-column hash.
+pub fn decoration_at(
+    tile_x: i32,
+    decorations: &[SurfaceDecoration],
+    noise: &TerrainNoiseCache,
+    wc: &ActiveWorld,
+) -> Option<TileId> {
+    let tile_x = wc.wrap_tile_x(tile_x);
+    decorations.iter().enumerate().find_map(|(i, deco)| {
+        if deco.cluster_scale > 0.0 {
+            // Decorations sample slices of the field far apart.
+            let slice = i as f64 * 97.31;
+            let value = if wc.wrap_x {
+                let angle = tile_x as f64 / wc.width_tiles as f64 * std::f64::consts::TAU;
+                let radius = wc.width_tiles as f64 * deco.cluster_scale / std::f64::consts::TAU;
+                noise
+                    .decoration
+                    .get([radius * angle.cos(), radius * angle.sin(), slice])
+            } else {
+                noise
+                    .decoration
+                    .get([tile_x as f64 * deco.cluster_scale, 0.0, slice])
+            };
+            if value <= deco.cluster_threshold {
+                return None;
+            }
+        }
+        let salt = (tile_x as u32 as u64) << 16 | i as u64;
+        let roll = SplitMix64::salted(wc.seed as u64, salt).next_f32();
+        (roll < deco.density).then_some(deco.tile)
+    })
+}
+
+/// How much region difficulty lowers the rare ore and crystal thresholds:
+/// at difficulty 1.0 they drop by this much.
+const RARE_ORE_DIFFICULTY_BONUS: f64 = 0.1;
+/// Base frequency of the ore noise.
+const ORE_FREQUENCY: f64 = 0.08;
+
+/// Shape of an ore's deposits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OreShape {
+    /// Round, compact blobs.
+    Blob,
+    /// Thin runs stretched along the strata (horizontally), `stretch` times
+    /// longer than they are thick. Deposit area stays about the same as a
+    /// blob's at the same threshold.
+    Vein { stretch: f64 },
+}
+
+/// One ore generated inside a biome's fill block.
+#[derive(Debug, Clone, Copy)]
+pub struct OreDef {
+    /// Tile placed, skipped if the registry doesn't have it.
+    pub tile: &'static str,
+    /// Depth range below the surface (inclusive).
+    pub depth: (i32, i32),
+    /// Noise value the ore needs to exceed.
+    pub threshold: f64,
+    /// Noise domain offset, so ores don't share deposits.
+    pub offset: f64,
+    pub shape: OreShape,
+    /// Whether [`WorldCtxRef::difficulty_at`] lowers the threshold.
+    pub difficulty_scaled: bool,
+}
+
+/// Ores in placement priority: where deposits overlap, the first one wins.
+pub const ORES: [OreDef; 3] = [
+    OreDef {
+        tile: "rare_ore",
+        depth: (50, i32::MAX),
+        threshold: 0.85,
+        offset: 1000.0,
+        shape: OreShape::Blob,
+        difficulty_scaled: true,
+    },
+    OreDef {
+        tile: "crystal",
+        depth: (30, 60),
+        threshold: 0.8,
+        offset: 500.0,
+        shape: OreShape::Blob,
+        difficulty_scaled: true,
+    },
+    OreDef {
+        tile: "iron_ore",
+        depth: (10, 40),
+        threshold: 0.7,
+        offset: 0.0,
+        shape: OreShape::Vein { stretch: 4.0 },
+        difficulty_scaled: false,
+    },
+];
+
+/// Ore noise at a tile for an ore with `shape` and domain `offset`.
+/// Sampled on a cylinder on wrapping worlds so deposits continue across the
+/// seam, the same way caves are.
+pub fn ore_noise(
+    perlin: &Perlin,
+    shape: OreShape,
+    offset: f64,
+    tile_x: i32,
+    tile_y: i32,
+    wc: &ActiveWorld,
+) -> f64 {
+    let (freq_x, freq_y) = match shape {
+        OreShape::Blob => (ORE_FREQUENCY, ORE_FREQUENCY),
+        OreShape::Vein { stretch } => {
+            let stretch = stretch.max(1.0).sqrt();
+            (ORE_FREQUENCY / stretch, ORE_FREQUENCY * stretch)
+        }
+    };
+    let y = tile_y as f64 * freq_y + offset;
+    if wc.wrap_x {
+        let angle = tile_x as f64 / wc.width_tiles as f64 * 2.0 * std::f64::consts::PI;
+        let radius = wc.width_tiles as f64 * freq_x / (2.0 * std::f64::consts::PI);
+        perlin.get([radius * angle.cos(), radius * angle.sin(), y])
+    } else {
+        perlin.get([tile_x as f64 * freq_x + offset, y, 0.0])
+    }
+}
+
+/// Check whether a fill_block tile should be replaced with an ore deposit
+/// from [`ORES`]. Each ore has a depth range (below surface), a noise
+/// threshold and a deposit shape; the rare ores get more common with
+/// [`WorldCtxRef::difficulty_at`].
 fn maybe_place_ore(
     tile_x: i32,
     tile_y: i32,
@@ -68,53 +341,154 @@ fn maybe_place_ore(
     ctx: &WorldCtxRef,
 ) -> TileId {
     let depth_below_surface = surface_y - tile_y;
-    if depth_below_surface < 10 {
-        return fill_block; // Too shallow for any ore
-    }
-
-    let ore_perlin = &ctx.noise_cache.ore;
-    let freq = 0.08;
-
-    // Rare ore: depth 50+, threshold 0.85
-    if depth_below_surface >= 50 {
-        let val = ore_perlin.get([
-            tile_x as f64 * freq + 1000.0,
-            tile_y as f64 * freq + 1000.0,
-        ]);
-        if val > 0.85 {
-            if let Some(id) = ctx.tile_registry.try_by_name("rare_ore") {
-                return id;
-            }
+    let mut bonus = None;
+    for ore in &ORES {
+        if depth_below_surface < ore.depth.0 || depth_below_surface > ore.depth.1 {
+            continue;
+        }
+        let threshold = if ore.difficulty_scaled {
+            let bonus = *bonus.get_or_insert_with(|| {
+                RARE_ORE_DIFFICULTY_BONUS * ctx.difficulty_at(tile_x, tile_y) as f64
+            });
+            ore.threshold - bonus
+        } else {
+            ore.threshold
+        };
+        let val = ore_noise(
+            &ctx.noise_cache.ore,
+            ore.shape,
+            ore.offset,
+            tile_x,
+            tile_y,
+            ctx.config,
+        );
+        if val > threshold
+            && let Some(id) = ctx.tile_registry.try_by_name(ore.tile)
+        {
+            return id;
         }
     }
+    fill_block
+}
 
-    // Crystal: depth 30-60, threshold 0.8
-    if depth_below_surface >= 30 && depth_below_surface <= 60 {
-        let val = ore_perlin.get([
-            tile_x as f64 * freq + 500.0,
-            tile_y as f64 * freq + 500.0,
-        ]);
-        if val > 0.8 {
-            if let Some(id) = ctx.tile_registry.try_by_name("crystal") {
-                return id;
-            }
+/// Cave threshold at `tile_y`: the biome threshold scaled by the layer's
+/// `cave_threshold_scale`, interpolated from the layer top to its bottom.
+pub fn cave_threshold(base: f64, tile_y: i32, layer: WorldLayer, ctx: &WorldCtxRef) -> f64 {
+    let (top_scale, bottom_scale) = ctx.planet_config.layers.get(layer).cave_threshold_scale;
+    let (bottom, top) = layer.y_range(ctx.planet_config, ctx.config.height_tiles);
+    let span = (top - 1 - bottom).max(1) as f64;
+    let depth = ((top - 1 - tile_y) as f64 / span).clamp(0.0, 1.0);
+    base * (top_scale + (bottom_scale - top_scale) * depth)
+}
+
+/// The two layers whose terrain parameters mix at a tile row, and how much
+/// the upper one counts. Within [`PlanetConfig::layer_blend`] rows of a
+/// boundary the weight ramps linearly across it; elsewhere both layers are
+/// the row's own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerBlend {
+    pub lower: WorldLayer,
+    pub upper: WorldLayer,
+    pub upper_weight: f64,
+}
+
+impl LayerBlend {
+    pub fn at(tile_y: i32, pc: &PlanetConfig) -> Self {
+        let layer = WorldLayer::from_tile_y(tile_y, pc);
+        let own = Self {
+            lower: layer,
+            upper: layer,
+            upper_weight: 1.0,
+        };
+        let band = pc.layer_blend as f64;
+        if band <= 0.0 {
+            return own;
         }
+        let b = &pc.layer_boundaries;
+        let tops = [
+            0,
+            b.core_top,
+            b.deep_underground_top,
+            b.underground_top,
+            i32::MAX,
+        ];
+        let layers = [
+            WorldLayer::Core,
+            WorldLayer::DeepUnderground,
+            WorldLayer::Underground,
+            WorldLayer::Surface,
+        ];
+        // Measured from row centres, so the rows either side of a boundary
+        // weigh the same distance from it. Layers without rows don't blend.
+        let row = tile_y as f64 + 0.5;
+        (1..layers.len())
+            .filter(|&i| tops[i - 1] < tops[i] && tops[i] < tops[i + 1])
+            .map(|i| (row - tops[i] as f64, layers[i - 1], layers[i]))
+            .filter(|(offset, ..)| offset.abs() < band)
+            .min_by(|a, b| a.0.abs().total_cmp(&b.0.abs()))
+            .map_or(own, |(offset, lower, upper)| Self {
+                lower,
+                upper,
+                upper_weight: 0.5 + offset / (2.0 * band),
+            })
     }
 
-    // Iron ore: depth 10-40, threshold 0.7
-    if depth_below_surface >= 10 && depth_below_surface <= 40 {
-        let val = ore_perlin.get([tile_x as f64 * freq, tile_y as f64 * freq]);
-        if val > 0.7 {
-            if let Some(id) = ctx.tile_registry.try_by_name("iron_ore") {
-                return id;
-            }
+    /// `value` of the two layers, mixed by weight.
+    pub fn mix(&self, value: impl Fn(WorldLayer) -> f64) -> f64 {
+        if self.lower == self.upper {
+            return value(self.upper);
         }
+        let lower = value(self.lower);
+        lower + (value(self.upper) - lower) * self.upper_weight
     }
+}
 
-    fill_block
+/// Terrain noise frequency at `tile_y`: the row's layer's own, or blended
+/// with the neighbouring layer near a boundary (see [`LayerBlend`]).
+pub fn terrain_frequency(tile_y: i32, pc: &PlanetConfig) -> f64 {
+    LayerBlend::at(tile_y, pc).mix(|layer| pc.layers.get(layer).terrain_frequency)
+}
+
+/// [`cave_threshold`] blended across layer boundaries like
+/// [`terrain_frequency`], each layer with its own biome's base threshold.
+fn blended_cave_threshold(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> f64 {
+    LayerBlend::at(tile_y, ctx.planet_config).mix(|layer| {
+        let biome = ctx.biome_registry.get(ctx.layer_biome(layer, tile_x));
+        cave_threshold(biome.cave_threshold, tile_y, layer, ctx)
+    })
+}
+
+/// Foreground tile past the edges of a non-wrapping world: the planet's
+/// border tile, or air when the edges are open or the tile is unknown.
+pub fn border_tile(ctx: &WorldCtxRef) -> TileId {
+    ctx.planet_config
+        .border_tile
+        .as_deref()
+        .and_then(|name| ctx.tile_registry.try_by_name(name))
+        .unwrap_or(TileId::AIR)
+}
+
+/// Tile lining the bottom of the world and standing in for everything below
+/// it: the planet's bedrock, or stone if it has none.
+pub fn bedrock_tile(ctx: &WorldCtxRef) -> TileId {
+    ctx.planet_config
+        .bedrock_tile
+        .as_deref()
+        .and_then(|name| ctx.tile_registry.try_by_name(name))
+        .unwrap_or_else(|| ctx.tile_registry.by_name("stone"))
 }
 
+#[cfg(test)]
 pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
+    let surface_y = ctx
+        .noise_cache
+        .surface_height_at(tile_x, ctx.config, ctx.planet_config);
+    generate_tile_below(tile_x, tile_y, surface_y, ctx)
+}
+
+/// Generate the foreground tile at a position in a column whose surface
+/// height is already known.
+fn generate_tile_below(tile_x: i32, tile_y: i32, surface_y: i32, ctx: &WorldCtxRef) -> TileId {
     let wc = ctx.config;
     let biome_map = ctx.biome_map;
     let biome_registry = ctx.biome_registry;
@@ -124,9 +498,14 @@ pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
         return TileId::AIR;
     }
 
-    // For non-wrapping worlds, tiles outside [0, width) are air
-    if !wc.wrap_x && (tile_x < 0 || tile_x >= wc.width_tiles) {
-        return TileId::AIR;
+    // For non-wrapping worlds, columns outside [0, width) are the border
+    if wc.outside_x(tile_x) {
+        return border_tile(ctx);
+    }
+
+    // The bedrock band comes before caves so nothing ever carves into it.
+    if tile_y < planet_config.bedrock_depth && planet_config.bedrock_tile.is_some() {
+        return bedrock_tile(ctx);
     }
 
     let tile_x = wc.wrap_tile_x(tile_x);
@@ -134,54 +513,27 @@ pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
     // Determine vertical layer
     let layer = WorldLayer::from_tile_y(tile_y, planet_config);
 
-    // Get biome for this position
-    let biome_id = match layer {
-        WorldLayer::Surface => biome_map.biome_at(tile_x as u32),
-        WorldLayer::Underground => biome_registry.id_by_name(
-            planet_config
-                .layers
-                .underground
-                .primary_biome
-                .as_deref()
-                .unwrap_or("underground_dirt"),
-        ),
-        WorldLayer::DeepUnderground => biome_registry.id_by_name(
-            planet_config
-                .layers
-                .deep_underground
-                .primary_biome
-                .as_deref()
-                .unwrap_or("underground_rock"),
-        ),
-        WorldLayer::Core => biome_registry.id_by_name(
-            planet_config
-                .layers
-                .core
-                .primary_biome
-                .as_deref()
-                .unwrap_or("core_magma"),
-        ),
-    };
+    // Get biome for this position. A forced biome (see `biome_override`)
+    // stands in for both the layer's and the column's biome.
+    let forced = ctx.forced_biome(tile_x, tile_y);
+    let biome_id = forced.unwrap_or_else(|| ctx.layer_biome(layer, tile_x));
 
     let biome = biome_registry.get(biome_id);
 
-    // Surface height (using surface layer params)
-    let surface_y = surface_height(
-        ctx.noise_cache,
-        tile_x,
-        wc,
-        planet_config.layers.surface.terrain_frequency,
-        planet_config.layers.surface.terrain_amplitude,
-    );
+    // Surface/subsurface blocks: always use the surface biome regardless of
+    // vertical layer, since the surface height can straddle layer boundaries.
+    let surface_biome =
+        biome_registry.get(forced.unwrap_or_else(|| biome_map.biome_at(tile_x as u32)));
 
-    // Above surface = air
+    // Above surface = air, apart from decorations resting on it
+    if tile_y == surface_y + 1 && surface_y >= 0 {
+        return decoration_at(tile_x, &surface_biome.decorations, ctx.noise_cache, wc)
+            .unwrap_or(TileId::AIR);
+    }
     if tile_y > surface_y {
         return TileId::AIR;
     }
 
-    // Surface/subsurface blocks: always use the surface biome regardless of
-    // vertical layer, since the surface height can straddle layer boundaries.
-    let surface_biome = biome_registry.get(biome_map.biome_at(tile_x as u32));
     if tile_y == surface_y {
         return surface_biome.surface_block;
     }
@@ -189,14 +541,10 @@ pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
         return surface_biome.subsurface_block;
     }
 
-    // Cave generation using layer-specific frequency
+    // Cave generation using layer-specific frequency, blended near layer
+    // boundaries so cave character changes gradually.
     let cave_perlin = &ctx.noise_cache.cave;
-    let layer_freq = match layer {
-        WorldLayer::Surface => planet_config.layers.surface.terrain_frequency,
-        WorldLayer::Underground => planet_config.layers.underground.terrain_frequency,
-        WorldLayer::DeepUnderground => planet_config.layers.deep_underground.terrain_frequency,
-        WorldLayer::Core => planet_config.layers.core.terrain_frequency,
-    };
+    let layer_freq = terrain_frequency(tile_y, planet_config);
     let cave_val = if wc.wrap_x {
         let angle = tile_x as f64 / wc.width_tiles as f64 * 2.0 * std::f64::consts::PI;
         let radius = wc.width_tiles as f64 * layer_freq / (2.0 * std::f64::consts::PI);
@@ -212,7 +560,8 @@ pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
             0.0,
         ])
     };
-    if cave_val.abs() < biome.cave_threshold {
+    let threshold = blended_cave_threshold(tile_x, tile_y, ctx);
+    if cave_val.abs() < threshold {
         TileId::AIR
     } else {
         // Ore placement: only replace fill_block tiles (stone) with ore veins.
@@ -223,60 +572,37 @@ pub fn generate_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
 
 /// Generate a background tile at the given position.
 /// Below or at surface: always fill_block (including caves). Above surface: AIR.
+#[cfg(test)]
 pub fn generate_bg_tile(tile_x: i32, tile_y: i32, ctx: &WorldCtxRef) -> TileId {
+    let surface_y = ctx
+        .noise_cache
+        .surface_height_at(tile_x, ctx.config, ctx.planet_config);
+    generate_bg_tile_below(tile_x, tile_y, surface_y, ctx)
+}
+
+/// Generate the background tile at a position in a column whose surface
+/// height is already known.
+fn generate_bg_tile_below(tile_x: i32, tile_y: i32, surface_y: i32, ctx: &WorldCtxRef) -> TileId {
     let wc = ctx.config;
     if tile_y < 0 || tile_y >= wc.height_tiles {
         return TileId::AIR;
     }
 
     // For non-wrapping worlds, tiles outside [0, width) are air
-    if !wc.wrap_x && (tile_x < 0 || tile_x >= wc.width_tiles) {
+    if wc.outside_x(tile_x) {
         return TileId::AIR;
     }
 
     let tile_x = wc.wrap_tile_x(tile_x);
 
-    let surface_y = surface_height(
-        ctx.noise_cache,
-        tile_x,
-        wc,
-        ctx.planet_config.layers.surface.terrain_frequency,
-        ctx.planet_config.layers.surface.terrain_amplitude,
-    );
-
     if tile_y > surface_y {
         return TileId::AIR;
     }
 
     // Below (or at) surface: always fill_block from the appropriate biome
-    let layer = WorldLayer::from_tile_y(tile_y, ctx.planet_config);
-    let biome_id = match layer {
-        WorldLayer::Surface => ctx.biome_map.biome_at(tile_x as u32),
-        WorldLayer::Underground => ctx.biome_registry.id_by_name(
-            ctx.planet_config
-                .layers
-                .underground
-                .primary_biome
-                .as_deref()
-                .unwrap_or("underground_dirt"),
-        ),
-        WorldLayer::DeepUnderground => ctx.biome_registry.id_by_name(
-            ctx.planet_config
-                .layers
-                .deep_underground
-                .primary_biome
-                .as_deref()
-                .unwrap_or("underground_rock"),
-        ),
-        WorldLayer::Core => ctx.biome_registry.id_by_name(
-            ctx.planet_config
-                .layers
-                .core
-                .primary_biome
-                .as_deref()
-                .unwrap_or("core_magma"),
-        ),
-    };
+    let biome_id = ctx
+        .forced_biome(tile_x, tile_y)
+        .unwrap_or_else(|| ctx.layer_biome(ctx.layer_at(tile_y), tile_x));
     let biome = ctx.biome_registry.get(biome_id);
     biome.fill_block
 }
@@ -288,30 +614,27 @@ pub struct ChunkTiles {
     pub liquid: Vec<LiquidCell>,
 }
 
-/// Generate liquid for a tile based on its position and the foreground tile.
-/// Water fills air pockets below sea level.
-pub fn generate_liquid(tile_x: i32, tile_y: i32, fg_tile: TileId, ctx: &WorldCtxRef) -> LiquidCell {
-    // Only generate liquid in air tiles.
-    if fg_tile != TileId::AIR {
+/// Generate liquid for a tile based on its position, the foreground tile and
+/// the column's surface height. Water fills air pockets below sea level.
+fn generate_liquid_below(
+    tile_x: i32,
+    tile_y: i32,
+    fg_tile: TileId,
+    surface_y: i32,
+    ctx: &WorldCtxRef,
+) -> LiquidCell {
+    let wc = ctx.config;
+    // Only generate liquid in air tiles inside the world.
+    if fg_tile != TileId::AIR || wc.outside_x(tile_x) {
         return LiquidCell::EMPTY;
     }
 
-    let wc = ctx.config;
-    let planet_config = ctx.planet_config;
-
     // Sea level: slightly below the average surface height (~60% of world height).
     let sea_level = (wc.height_tiles as f64 * SURFACE_BASE * 0.85) as i32;
 
     if tile_y <= sea_level {
         // Check surface height at this x to avoid filling above-ground air.
-        let surface_h = surface_height(
-            ctx.noise_cache,
-            tile_x,
-            wc,
-            planet_config.layers.surface.terrain_frequency,
-            planet_config.layers.surface.terrain_amplitude,
-        );
-        if tile_y < surface_h {
+        if tile_y < surface_y {
             return LiquidCell {
                 liquid_type: LiquidId(1), // water
                 level: 1.0,
@@ -330,15 +653,21 @@ pub fn generate_chunk_tiles(chunk_x: i32, chunk_y: i32, ctx: &WorldCtxRef) -> Ch
     let mut fg = Vec::with_capacity(cap);
     let mut bg = Vec::with_capacity(cap);
     let mut liquid = Vec::with_capacity(cap);
+    // One lock on the surface memo for the whole chunk.
+    let surfaces = ctx.noise_cache.surface_heights_in(
+        base_x..base_x + chunk_size as i32,
+        ctx.config,
+        ctx.planet_config,
+    );
 
     for local_y in 0..chunk_size as i32 {
-        for local_x in 0..chunk_size as i32 {
-            let x = base_x + local_x;
+        for (local_x, &surface_y) in surfaces.iter().enumerate() {
+            let x = base_x + local_x as i32;
             let y = base_y + local_y;
-            let fg_tile = generate_tile(x, y, ctx);
+            let fg_tile = generate_tile_below(x, y, surface_y, ctx);
             fg.push(fg_tile);
-            bg.push(generate_bg_tile(x, y, ctx));
-            liquid.push(generate_liquid(x, y, fg_tile, ctx));
+            bg.push(generate_bg_tile_below(x, y, surface_y, ctx));
+            liquid.push(generate_liquid_below(x, y, fg_tile, surface_y, ctx));
         }
     }
 
@@ -349,6 +678,9 @@ pub fn generate_chunk_tiles(chunk_x: i32, chunk_y: i32, ctx: &WorldCtxRef) -> Ch
 mod tests {
     use super::*;
     use crate::test_helpers::fixtures;
+    use crate::registry::biome::{BiomeDef, LayerBoundaries};
+    use crate::registry::tile::TileRegistry;
+    use crate::world::chunk::{Layer, WorldMap};
 
     const TEST_SEED: u32 = 42;
 
@@ -527,6 +859,30 @@ mod tests {
         assert_eq!(generate_tile(500, wc.height_tiles, &ctx), TileId::AIR);
     }
 
+    #[test]
+    fn bedrock_band_spans_every_column() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        let mut defs = tr.defs.clone();
+        let mut bedrock = tr.get(tr.by_name("stone")).clone();
+        bedrock.id = "bedrock".into();
+        bedrock.hardness = -1.0;
+        defs.push(bedrock);
+        let tr = TileRegistry::from_defs(defs);
+        pc.bedrock_tile = Some("bedrock".into());
+        pc.bedrock_depth = 4;
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let bedrock = tr.by_name("bedrock");
+
+        for x in 0..wc.width_tiles {
+            for y in 0..pc.bedrock_depth {
+                assert_eq!(generate_tile(x, y, &ctx), bedrock, "({x}, {y})");
+            }
+        }
+        assert_ne!(generate_tile(500, pc.bedrock_depth, &ctx), bedrock);
+        let map = WorldMap::default();
+        assert_eq!(map.get_tile(500, -1, Layer::Fg, &ctx), Some(bedrock));
+    }
+
     #[test]
     fn x_wraps_around() {
         let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
@@ -562,4 +918,554 @@ mod tests {
         let cache = TerrainNoiseCache::new(TEST_SEED);
         assert_eq!(surface_height(&cache, 100, &wc, 1.0, 0.0), -1);
     }
+
+    /// Fraction of cave air in `rows` for a column sample across the world.
+    fn air_fraction(rows: std::ops::Range<i32>, ctx: &WorldCtxRef) -> f64 {
+        let mut air = 0;
+        let mut total = 0;
+        for y in rows {
+            for x in (0..ctx.config.width_tiles).step_by(4) {
+                total += 1;
+                if generate_tile(x, y, ctx) == TileId::AIR {
+                    air += 1;
+                }
+            }
+        }
+        air as f64 / total as f64
+    }
+
+    /// Air fractions for `bands` equal row bands of `layer`, top band first.
+    fn band_air_fractions(layer: WorldLayer, bands: i32, ctx: &WorldCtxRef) -> Vec<f64> {
+        let (bottom, top) = layer.y_range(ctx.planet_config, ctx.config.height_tiles);
+        let band_h = (top - bottom) / bands;
+        (0..bands)
+            .map(|i| {
+                let band_top = top - i * band_h;
+                air_fraction(band_top - band_h..band_top, ctx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cave_threshold_is_flat_by_default() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        for y in [130, 300, 458] {
+            assert_eq!(
+                cave_threshold(0.25, y, WorldLayer::DeepUnderground, &ctx),
+                0.25
+            );
+        }
+    }
+
+    #[test]
+    fn cave_threshold_interpolates_from_layer_top_to_bottom() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        pc.layers.deep_underground.cave_threshold_scale = (0.5, 1.5);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let layer = WorldLayer::DeepUnderground;
+        let (bottom, top) = layer.y_range(&pc, wc.height_tiles);
+        let at_top = cave_threshold(0.2, top - 1, layer, &ctx);
+        let at_bottom = cave_threshold(0.2, bottom, layer, &ctx);
+        assert!((at_top - 0.1).abs() < 1e-9, "{at_top}");
+        assert!((at_bottom - 0.3).abs() < 1e-9, "{at_bottom}");
+    }
+
+    #[test]
+    fn caves_widen_with_depth_when_scale_increases() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        pc.layers.deep_underground.cave_threshold_scale = (0.5, 1.5);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let fractions = band_air_fractions(WorldLayer::DeepUnderground, 4, &ctx);
+        for pair in fractions.windows(2) {
+            assert!(pair[1] > pair[0], "air fraction by depth: {fractions:?}");
+        }
+    }
+
+    #[test]
+    fn caves_close_with_depth_when_scale_decreases() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        pc.layers.core.cave_threshold_scale = (1.5, 0.0);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let fractions = band_air_fractions(WorldLayer::Core, 4, &ctx);
+        for pair in fractions.windows(2) {
+            assert!(pair[1] < pair[0], "air fraction by depth: {fractions:?}");
+        }
+    }
+
+    #[test]
+    fn cave_gradient_keeps_generation_deterministic_and_wrapped() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        pc.layers.underground.cave_threshold_scale = (0.8, 1.6);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        for y in [470, 600, 700] {
+            assert_eq!(
+                generate_tile(-3, y, &ctx),
+                generate_tile(wc.width_tiles - 3, y, &ctx)
+            );
+            assert_eq!(generate_tile(17, y, &ctx), generate_tile(17, y, &ctx));
+        }
+    }
+
+    #[test]
+    fn terrain_frequency_blends_across_layer_boundaries() {
+        let mut pc = fixtures::test_planet_config();
+        pc.layer_blend = 8;
+        // Deep underground (0.05) meets underground (0.07) at row 459.
+        let boundary = pc.layer_boundaries.deep_underground_top;
+        let freq = |y| terrain_frequency(y, &pc);
+        assert_eq!(freq(boundary - 9), 0.05);
+        assert_eq!(freq(boundary + 8), 0.07);
+        let band: Vec<f64> = (boundary - 8..boundary + 8).map(freq).collect();
+        assert!(band.iter().all(|&f| f > 0.05 && f < 0.07), "{band:?}");
+        assert!(band.windows(2).all(|w| w[1] > w[0]), "{band:?}");
+        // The rows either side of the boundary sit symmetrically about the
+        // midpoint.
+        assert!((freq(boundary - 1) + freq(boundary) - 0.12).abs() < 1e-12);
+
+        pc.layer_blend = 0;
+        assert_eq!(terrain_frequency(boundary - 1, &pc), 0.05);
+        assert_eq!(terrain_frequency(boundary, &pc), 0.07);
+    }
+
+    #[test]
+    fn cave_threshold_blends_between_layer_biomes() {
+        let (wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        pc.layer_blend = 8;
+        pc.layers.underground.cave_threshold_scale = (2.0, 2.0);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let boundary = pc.layer_boundaries.deep_underground_top;
+        // underground_rock (0.25) below, underground_dirt (0.3, scaled x2) above.
+        assert!((blended_cave_threshold(5, boundary - 9, &ctx) - 0.25).abs() < 1e-12);
+        assert!((blended_cave_threshold(5, boundary + 8, &ctx) - 0.6).abs() < 1e-12);
+        let band: Vec<f64> = (boundary - 8..boundary + 8)
+            .map(|y| blended_cave_threshold(5, y, &ctx))
+            .collect();
+        assert!(band.windows(2).all(|w| w[1] > w[0]), "{band:?}");
+        assert!(band.iter().all(|&t| t > 0.25 && t < 0.6), "{band:?}");
+
+        for y in boundary - 8..boundary + 8 {
+            assert_eq!(generate_tile(17, y, &ctx), generate_tile(17, y, &ctx));
+            assert_eq!(
+                generate_tile(-3, y, &ctx),
+                generate_tile(wc.width_tiles - 3, y, &ctx)
+            );
+        }
+    }
+
+    #[test]
+    fn empty_layers_do_not_blend() {
+        let mut pc = fixtures::test_planet_config();
+        pc.layers.core.depth_ratio = 0.0;
+        pc.layer_boundaries = LayerBoundaries::from_layers(&pc.layers, 1024);
+        pc.layer_blend = 8;
+        assert_eq!(pc.layer_boundaries.core_top, 0);
+        assert_eq!(terrain_frequency(0, &pc), 0.05);
+        assert_eq!(LayerBlend::at(3, &pc).lower, WorldLayer::DeepUnderground);
+    }
+
+    #[test]
+    fn surface_height_at_matches_surface_height() {
+        let wc = fixtures::test_world_config();
+        let pc = fixtures::test_planet_config();
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        for x in (0..wc.width_tiles).step_by(97) {
+            let expected = surface_height(
+                &cache,
+                x,
+                &wc,
+                pc.layers.surface.terrain_frequency,
+                pc.layers.surface.terrain_amplitude,
+            );
+            assert_eq!(cache.surface_height_at(x, &wc, &pc), expected, "x={x}");
+        }
+    }
+
+    #[test]
+    fn surface_height_at_reuses_cached_columns() {
+        let wc = fixtures::test_world_config();
+        let pc = fixtures::test_planet_config();
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        assert!(cache.surface_heights.is_empty());
+
+        let h = cache.surface_height_at(100, &wc, &pc);
+        assert_eq!(cache.surface_height_at(100, &wc, &pc), h);
+        assert_eq!(cache.surface_height_at(100 + wc.width_tiles, &wc, &pc), h);
+        assert_eq!(cache.surface_height_at(100 - wc.width_tiles, &wc, &pc), h);
+        assert_eq!(
+            cache.surface_heights.len(),
+            1,
+            "wrapped queries share a column"
+        );
+
+        // Overwrite the memoized value: a cache hit must return it untouched.
+        let key = SurfaceKey::new(&wc, &pc);
+        {
+            let mut columns = cache.surface_heights.columns.lock().unwrap();
+            assert_eq!(columns.key, Some(key));
+            columns.heights[100] = Some(-7);
+        }
+        assert_eq!(cache.surface_height_at(100, &wc, &pc), -7);
+    }
+
+    #[test]
+    fn surface_height_cache_invalidated_on_config_change() {
+        let wc = fixtures::test_world_config();
+        let mut pc = fixtures::test_planet_config();
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        cache.surface_height_at(100, &wc, &pc);
+        cache.surface_height_at(200, &wc, &pc);
+        assert_eq!(cache.surface_heights.len(), 2);
+
+        pc.layers.surface.terrain_amplitude *= 2.0;
+        let expected = surface_height(
+            &cache,
+            100,
+            &wc,
+            pc.layers.surface.terrain_frequency,
+            pc.layers.surface.terrain_amplitude,
+        );
+        assert_eq!(cache.surface_height_at(100, &wc, &pc), expected);
+        assert_eq!(cache.surface_heights.len(), 1, "stale columns dropped");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "another seed")]
+    fn surface_height_at_rejects_a_world_with_another_seed() {
+        let mut wc = fixtures::test_world_config();
+        let pc = fixtures::test_planet_config();
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        wc.seed = TEST_SEED.wrapping_add(1);
+        cache.surface_height_at(100, &wc, &pc);
+    }
+
+    #[test]
+    fn surface_heights_in_matches_single_lookups() {
+        let wc = fixtures::test_world_config();
+        let pc = fixtures::test_planet_config();
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        // Straddles the seam.
+        let tiles_x = wc.width_tiles - 8..wc.width_tiles + 8;
+        let batch = cache.surface_heights_in(tiles_x.clone(), &wc, &pc);
+        let single: Vec<i32> = tiles_x
+            .map(|x| TerrainNoiseCache::new(TEST_SEED).surface_height_at(x, &wc, &pc))
+            .collect();
+        assert_eq!(batch, single);
+        assert_eq!(cache.surface_heights.len(), 16);
+    }
+
+    /// Meadow | forest | rocky regions over the test world, with a cliff of
+    /// `height` styled between meadow and forest only.
+    fn cliff_world(height: i32) -> (ActiveWorld, PlanetConfig) {
+        use crate::registry::biome::{BiomeBorder, BorderStyle};
+        use crate::world::biome_map::{BiomeMap, BiomeRegion, BorderCliffs};
+
+        let wc = fixtures::test_world_config();
+        let br = fixtures::test_biome_registry();
+        let width = wc.width_tiles as u32;
+        let region = |name: &str, start_x: u32, end_x: u32| BiomeRegion {
+            biome_id: br.id_by_name(name),
+            start_x,
+            width: end_x - start_x,
+        };
+        let bm = BiomeMap {
+            regions: vec![
+                region("meadow", 0, 600),
+                region("forest", 600, 1200),
+                region("rocky", 1200, width),
+            ],
+            world_width: width,
+        };
+        let mut pc = fixtures::test_planet_config();
+        pc.biome_borders = vec![BiomeBorder {
+            between: ("forest".into(), "meadow".into()),
+            style: BorderStyle::Cliff { height },
+        }];
+        pc.border_cliffs = BorderCliffs::new(&bm, &pc.biome_borders, &br, wc.seed, wc.wrap_x);
+        (wc, pc)
+    }
+
+    #[test]
+    fn cliff_borders_step_and_blend_borders_stay_continuous() {
+        let (wc, pc) = cliff_world(24);
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        let h = |x: i32| cache.surface_height_at(x, &wc, &pc);
+        let jump = |x: i32| (h(x) - h(x - 1)).abs();
+
+        assert!(jump(600) >= 20, "cliff at meadow|forest: {}", jump(600));
+        // forest|rocky and rocky|meadow (across the seam) blend.
+        assert!(jump(1200) <= 4, "blend at forest|rocky: {}", jump(1200));
+        assert!(jump(0) <= 4, "blend at the seam: {}", jump(0));
+        // Away from the border the noise surface is untouched.
+        let plain = |x: i32| {
+            surface_height(
+                &cache,
+                x,
+                &wc,
+                pc.layers.surface.terrain_frequency,
+                pc.layers.surface.terrain_amplitude,
+            )
+        };
+        for x in [100, 300, 900, 1600] {
+            assert_eq!(h(x), plain(x), "x={x}");
+        }
+        // The step fades out rather than jumping back.
+        for x in (300..900).filter(|&x| x != 600) {
+            assert!(jump(x) <= 4, "x={x}: {}", jump(x));
+        }
+    }
+
+    #[test]
+    fn cliffs_are_deterministic_and_wrap() {
+        let (wc, pc) = cliff_world(24);
+        let (_, again) = cliff_world(24);
+        assert_eq!(pc.border_cliffs, again.border_cliffs);
+        let cliff = pc.border_cliffs.cliffs()[0];
+        assert_eq!((cliff.x, cliff.step.abs()), (600, 24));
+
+        let cache = TerrainNoiseCache::new(TEST_SEED);
+        for x in [590, 599, 600, 610] {
+            assert_eq!(
+                cache.surface_height_at(x + wc.width_tiles, &wc, &pc),
+                cache.surface_height_at(x, &wc, &pc)
+            );
+        }
+
+        // A different cliff invalidates memoized columns.
+        let before = cache.surface_height_at(600, &wc, &pc);
+        let (_, taller) = cliff_world(60);
+        let after = cache.surface_height_at(600, &wc, &taller);
+        assert_eq!((after - before).abs(), 18);
+    }
+
+    #[test]
+    fn chunk_column_generation_computes_each_surface_column_once() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let rows = wc.height_tiles / wc.chunk_size as i32;
+        let column: Vec<ChunkTiles> = (0..rows)
+            .map(|cy| generate_chunk_tiles(7, cy, &ctx))
+            .collect();
+        assert_eq!(nc.surface_heights.len(), wc.chunk_size as usize);
+
+        // A cold cache filled top-down generates the same tiles.
+        let cold = TerrainNoiseCache::new(TEST_SEED);
+        let cold_ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &cold);
+        for cy in (0..rows).rev() {
+            let tiles = generate_chunk_tiles(7, cy, &cold_ctx);
+            let cached = &column[cy as usize];
+            assert_eq!(tiles.fg, cached.fg, "fg of chunk (7, {cy})");
+            assert_eq!(tiles.bg, cached.bg, "bg of chunk (7, {cy})");
+            let water = |t: &ChunkTiles| t.liquid.iter().map(|c| c.liquid_type).collect::<Vec<_>>();
+            assert_eq!(water(&tiles), water(cached), "liquid of chunk (7, {cy})");
+        }
+    }
+
+    /// Surface lookups while generating a full chunk column, memoized versus
+    /// sampling the noise on every query as generation used to.
+    /// `cargo test --release bench_chunk_column_surface -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_chunk_column_surface_lookups() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let cs = wc.chunk_size as i32;
+        let rows = wc.height_tiles / cs;
+        let (freq, amp) = (
+            pc.layers.surface.terrain_frequency,
+            pc.layers.surface.terrain_amplitude,
+        );
+        // fg, bg and liquid each look the surface up once per tile.
+        let queries = || (0..wc.height_tiles * 3).flat_map(|_| 7 * cs..8 * cs);
+
+        let start = Instant::now();
+        for x in queries() {
+            black_box(surface_height(&nc, x, &wc, freq, amp));
+        }
+        let uncached = start.elapsed();
+
+        let start = Instant::now();
+        for x in queries() {
+            black_box(nc.surface_height_at(x, &wc, &pc));
+        }
+        let memoized = start.elapsed();
+
+        let start = Instant::now();
+        for cy in 0..rows {
+            black_box(generate_chunk_tiles(7, cy, &ctx));
+        }
+        let generation = start.elapsed();
+
+        eprintln!(
+            "chunk column ({rows} chunks): surface lookups uncached {uncached:?}, \
+             memoized {memoized:?}; full generation {generation:?}"
+        );
+    }
+
+    fn flowers(cluster_scale: f64, density: f32) -> SurfaceDecoration {
+        SurfaceDecoration {
+            tile: TileId(1),
+            density,
+            cluster_scale,
+            cluster_threshold: 0.2,
+        }
+    }
+
+    /// Placements per column across the whole test world.
+    fn placements(deco: &SurfaceDecoration, nc: &TerrainNoiseCache, wc: &ActiveWorld) -> Vec<bool> {
+        let decorations = std::slice::from_ref(deco);
+        (0..wc.width_tiles)
+            .map(|x| decoration_at(x, decorations, nc, wc).is_some())
+            .collect()
+    }
+
+    /// Share of placements with a placed neighbour, and the overall rate.
+    fn grouping(placed: &[bool]) -> (f64, f64) {
+        let n = placed.len();
+        let count = placed.iter().filter(|&&p| p).count();
+        let grouped = (0..n)
+            .filter(|&i| placed[i] && (placed[(i + 1) % n] || placed[(i + n - 1) % n]))
+            .count();
+        (grouped as f64 / count as f64, count as f64 / n as f64)
+    }
+
+    #[test]
+    fn clustered_decorations_form_groups() {
+        let wc = fixtures::test_world_config();
+        let nc = TerrainNoiseCache::new(TEST_SEED);
+
+        let (clustered, rate) = grouping(&placements(&flowers(0.03, 0.7), &nc, &wc));
+        assert!(rate > 0.05 && rate < 0.5, "rate {rate}");
+        // Independent columns with the same overall rate.
+        let independent = 1.0 - (1.0 - rate).powi(2);
+        let (flat, flat_rate) = grouping(&placements(&flowers(0.0, rate as f32), &nc, &wc));
+        assert!((flat_rate - rate).abs() < 0.05, "{flat_rate} vs {rate}");
+        assert!((flat - independent).abs() < 0.1, "{flat} vs {independent}");
+        assert!(
+            clustered > independent + 0.2,
+            "clustered {clustered} vs independent {independent}"
+        );
+    }
+
+    #[test]
+    fn decorations_are_seeded_and_wrap() {
+        let wc = fixtures::test_world_config();
+        let decorations = [flowers(0.03, 0.7), flowers(0.0, 0.1)];
+        let nc = TerrainNoiseCache::new(TEST_SEED);
+        let again = TerrainNoiseCache::new(TEST_SEED);
+        for x in 0..200 {
+            let here = decoration_at(x, &decorations, &nc, &wc);
+            assert_eq!(here, decoration_at(x, &decorations, &again, &wc));
+            assert_eq!(here, decoration_at(x + wc.width_tiles, &decorations, &nc, &wc));
+        }
+        let other = TerrainNoiseCache::new(TEST_SEED + 1);
+        assert!((0..200).any(|x| {
+            decoration_at(x, &decorations, &nc, &wc) != decoration_at(x, &decorations, &other, &wc)
+        }));
+    }
+
+    #[test]
+    fn decorations_rest_on_the_surface() {
+        let (wc, bm, mut br, tr, pc, nc) = fixtures::test_world_ctx();
+        for name in ["meadow", "forest", "rocky"] {
+            let def = br.get(br.id_by_name(name)).clone();
+            let decorations = vec![flowers(0.0, 1.0)];
+            br.insert(name, BiomeDef { decorations, ..def });
+        }
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        for x in [0, 500, 1500] {
+            let h = surface_height(
+                &nc,
+                x,
+                &wc,
+                pc.layers.surface.terrain_frequency,
+                pc.layers.surface.terrain_amplitude,
+            );
+            assert_eq!(generate_tile(x, h + 1, &ctx), TileId(1));
+            assert_eq!(generate_tile(x, h + 2, &ctx), TileId::AIR);
+        }
+    }
+
+    /// Ore mask over a 256×128 tile window at `threshold`.
+    fn ore_mask(shape: OreShape, threshold: f64, wc: &ActiveWorld) -> Vec<Vec<bool>> {
+        let perlin = Perlin::new(TEST_SEED);
+        (0..128)
+            .map(|y| {
+                (0..256)
+                    .map(|x| ore_noise(&perlin, shape, 0.0, x, y, wc) > threshold)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Mean length of the runs of ore along rows and along columns, and the
+    /// ore coverage.
+    fn run_lengths(mask: &[Vec<bool>]) -> (f64, f64, f64) {
+        let mean_run = |lines: Vec<Vec<bool>>| {
+            let (mut tiles, mut runs) = (0, 0);
+            for line in lines {
+                for (i, &ore) in line.iter().enumerate() {
+                    tiles += ore as usize;
+                    runs += (ore && (i == 0 || !line[i - 1])) as usize;
+                }
+            }
+            tiles as f64 / runs.max(1) as f64
+        };
+        let (w, h) = (mask[0].len(), mask.len());
+        let columns = (0..w)
+            .map(|x| (0..h).map(|y| mask[y][x]).collect())
+            .collect();
+        let coverage = mask.iter().flatten().filter(|&&o| o).count() as f64 / (w * h) as f64;
+        (mean_run(mask.to_vec()), mean_run(columns), coverage)
+    }
+
+    #[test]
+    fn vein_ores_form_longer_runs_than_blobs() {
+        let wc = fixtures::test_world_config();
+        let (blob_h, blob_v, blob_cover) = run_lengths(&ore_mask(OreShape::Blob, 0.3, &wc));
+        let vein = OreShape::Vein { stretch: 4.0 };
+        let (vein_h, vein_v, vein_cover) = run_lengths(&ore_mask(vein, 0.3, &wc));
+
+        assert!(blob_cover > 0.02 && vein_cover > 0.02);
+        assert!(
+            (vein_cover / blob_cover - 1.0).abs() < 0.5,
+            "{vein_cover} vs {blob_cover}"
+        );
+        // Blobs are about as wide as tall; veins are long and thin.
+        assert!(blob_h / blob_v < 1.5, "blob {blob_h} x {blob_v}");
+        assert!(vein_h / vein_v > 2.5, "vein {vein_h} x {vein_v}");
+        assert!(vein_h > blob_h * 1.5, "vein {vein_h} vs blob {blob_h}");
+    }
+
+    #[test]
+    fn ore_noise_is_seeded_and_wraps() {
+        let wc = fixtures::test_world_config();
+        let perlin = Perlin::new(TEST_SEED);
+        let other = Perlin::new(TEST_SEED + 1);
+        let w = wc.width_tiles;
+        for shape in [OreShape::Blob, OreShape::Vein { stretch: 4.0 }] {
+            for y in [10, 300, 700] {
+                for x in [0, 1, 517] {
+                    let here = ore_noise(&perlin, shape, 500.0, x, y, &wc);
+                    assert_eq!(
+                        here,
+                        ore_noise(&Perlin::new(TEST_SEED), shape, 500.0, x, y, &wc)
+                    );
+                    assert!((here - ore_noise(&perlin, shape, 500.0, x + w, y, &wc)).abs() < 1e-9);
+                }
+                // No seam between the last and first column.
+                let seam = ore_noise(&perlin, shape, 0.0, w - 1, y, &wc)
+                    - ore_noise(&perlin, shape, 0.0, 0, y, &wc);
+                assert!(seam.abs() < 0.1, "{seam}");
+            }
+            assert!((0..100).any(|x| {
+                ore_noise(&perlin, shape, 0.0, x, 50, &wc)
+                    != ore_noise(&other, shape, 0.0, x, 50, &wc)
+            }));
+        }
+    }
 }
@@ -1,11 +1,30 @@
-use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::mesh::{MeshVertexAttribute, MeshVertexBufferLayoutRef};
 use bevy::prelude::*;
 use bevy::render::render_resource::{
-    AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError, VertexFormat,
 };
 use bevy::shader::ShaderRef;
 use bevy::sprite_render::{Material2d, Material2dKey};
 
+/// Per-vertex sway weight: 1.0 on the top edge of swaying tiles, 0.0 elsewhere,
+/// so the base of a plant stays anchored while its top moves.
+pub const ATTRIBUTE_SWAY: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Sway", 988_540_917, VertexFormat::Float32);
+
+/// Per-vertex RGB multiplier for the tile's colour variation, baked at mesh
+/// build time (see `mesh_builder::variation_tint`).
+pub const ATTRIBUTE_TINT: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Tint", 988_540_918, VertexFormat::Float32x3);
+
+/// Per-vertex autotile animation: (frame count, seconds per frame, UV width
+/// of one atlas column). The shader shifts the UV by whole columns over
+/// time; static tiles carry a single frame.
+pub const ATTRIBUTE_ANIM: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Anim", 988_540_919, VertexFormat::Float32x3);
+
+/// Default sway parameters: (amplitude_px, speed_rad_per_sec, phase_per_px, wind_scale).
+pub const DEFAULT_TILE_SWAY: Vec4 = Vec4::new(3.0, 1.6, 0.01, 1.0);
+
 #[derive(Asset, AsBindGroup, Clone, TypePath)]
 pub struct TileMaterial {
     #[texture(0)]
@@ -18,6 +37,8 @@ pub struct TileMaterial {
     pub lightmap: Handle<Image>,
     #[uniform(5)]
     pub lightmap_uv_rect: Vec4, // (scale_x, scale_y, offset_x, offset_y)
+    #[uniform(6)]
+    pub sway: Vec4, // (amplitude_px, speed, phase_per_px, wind_scale)
 }
 
 impl Material2d for TileMaterial {
@@ -37,6 +58,9 @@ impl Material2d for TileMaterial {
         let vertex_layout = layout.0.get_layout(&[
             Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
             Mesh::ATTRIBUTE_UV_0.at_shader_location(1),
+            ATTRIBUTE_SWAY.at_shader_location(2),
+            ATTRIBUTE_TINT.at_shader_location(3),
+            ATTRIBUTE_ANIM.at_shader_location(4),
         ])?;
         descriptor.vertex.buffers = vec![vertex_layout];
         Ok(())
@@ -0,0 +1,425 @@
+//! Deterministic world digest for comparing generation across machines.
+//!
+//! [`hash_world`] regenerates a rectangle of chunks from the seed, row by
+//! row and left to right, and folds their fg and bg tile arrays into one
+//! FNV-1a hash. It also mixes in the biome region layout and surface
+//! heights sampled every [`SURFACE_SAMPLE_STEP`] tiles across the world.
+//! Nothing is read from hash maps or the live [`WorldMap`], so two machines
+//! that agree on the seed, the assets and the generator produce the same
+//! digest, whatever the player has dug.
+//!
+//! `/worldhash [X Y W H]` prints the digest in chat.
+//! `cargo run -- --hash-world <planet_type> <seed> [--chunks X Y W H]` prints
+//! it without starting the game. Both default to [`spawn_band`].
+//!
+//! [`WorldMap`]: crate::world::chunk::WorldMap
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+use crate::chat::{ChatCommandEvent, ChatState};
+use crate::registry::world::ActiveWorld;
+use crate::world::ctx::{WorldCtx, WorldCtxRef};
+use crate::world::spawn_point::WorldSpawnPoint;
+use crate::world::terrain_gen::generate_chunk_tiles;
+use crate::world::worldgen_preview::{load_preview_world, PreviewError, PreviewRegion};
+
+/// Command-line flag that switches `main` into hashing mode.
+pub const HASH_FLAG: &str = "--hash-world";
+
+/// Chunk columns hashed on each side of the spawn column by default.
+pub const SPAWN_BAND_RADIUS: i32 = 4;
+
+/// Horizontal spacing (in tiles) between hashed surface-height samples.
+pub const SURFACE_SAMPLE_STEP: i32 = 32;
+
+/// Most chunks `/worldhash` regenerates. It runs on the main thread, so a
+/// larger rectangle would stall the game; the command line has no limit.
+pub const MAX_COMMAND_CHUNKS: i32 = 512;
+
+#[derive(Debug, Error)]
+pub enum WorldHashError {
+    #[error(transparent)]
+    Load(#[from] PreviewError),
+    #[error("usage: {HASH_FLAG} <planet_type> <seed> [--chunks X Y W H] ({0})")]
+    Usage(String),
+}
+
+/// 64-bit FNV-1a. Unlike `std`'s hashers its output is fixed across
+/// platforms, Rust versions and runs.
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Length-prefixed, so adjacent strings can't run into each other.
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u32(value.len() as u32);
+        self.write(value.as_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Every chunk row, [`SPAWN_BAND_RADIUS`] columns either side of the chunk
+/// holding `spawn_tile_x`. Clamped to the world on worlds that don't wrap.
+pub fn spawn_band(config: &ActiveWorld, spawn_tile_x: i32) -> PreviewRegion {
+    let centre = spawn_tile_x.div_euclid(config.chunk_size as i32);
+    let (mut x, mut width) = (centre - SPAWN_BAND_RADIUS, 2 * SPAWN_BAND_RADIUS + 1);
+    if config.wrap_x {
+        width = width.min(config.width_chunks());
+    } else {
+        let end = (x + width).min(config.width_chunks());
+        x = x.max(0);
+        width = (end - x).max(0);
+    }
+    PreviewRegion {
+        x,
+        y: 0,
+        width,
+        height: config.height_chunks(),
+    }
+}
+
+/// Digest of the world generated for `ctx` over `region`.
+///
+/// Hashes, in order: the biome regions (by name, so registry ids don't
+/// matter), the sampled surface profile, then each chunk's coordinates and
+/// its fg and bg tiles as little-endian `u16`s.
+pub fn hash_world(ctx: &WorldCtxRef, region: PreviewRegion) -> u64 {
+    let mut hasher = StableHasher::new();
+
+    hasher.write_u32(ctx.biome_map.regions.len() as u32);
+    for r in &ctx.biome_map.regions {
+        hasher.write_str(ctx.biome_registry.name_of(r.biome_id));
+        hasher.write_u32(r.start_x);
+        hasher.write_u32(r.width);
+    }
+
+    for x in (0..ctx.config.width_tiles).step_by(SURFACE_SAMPLE_STEP as usize) {
+        let height = ctx
+            .noise_cache
+            .surface_height_at(x, ctx.config, ctx.planet_config);
+        hasher.write_i32(height);
+    }
+
+    for cy in region.y..region.y + region.height {
+        for cx in region.x..region.x + region.width {
+            let chunk_x = ctx.config.wrap_chunk_x(cx);
+            hasher.write_i32(chunk_x);
+            hasher.write_i32(cy);
+            let tiles = generate_chunk_tiles(chunk_x, cy, ctx);
+            for tile in tiles.fg.iter().chain(&tiles.bg) {
+                hasher.write_u16(tile.0);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// The digest as printed: 16 lowercase hex digits.
+pub fn format_digest(hash: u64) -> String {
+    format!("{hash:016x}")
+}
+
+/// Parse `X Y W H` chunk coordinates.
+fn parse_region(values: &[String]) -> Result<PreviewRegion, String> {
+    let [x, y, w, h] = values else {
+        return Err("expected X Y W H".to_string());
+    };
+    let number = |s: &String| {
+        s.parse::<i32>()
+            .map_err(|_| format!("'{s}' is not a number"))
+    };
+    Ok(PreviewRegion {
+        x: number(x)?,
+        y: number(y)?,
+        width: number(w)?,
+        height: number(h)?,
+    })
+}
+
+/// A `/worldhash` rectangle: `X Y W H` with at most [`MAX_COMMAND_CHUNKS`]
+/// chunks.
+fn parse_command_region(values: &[String]) -> Result<PreviewRegion, String> {
+    let region = parse_region(values)?;
+    if region.width <= 0 || region.height <= 0 {
+        return Err("W and H must be positive".to_string());
+    }
+    if region.width as i64 * region.height as i64 > MAX_COMMAND_CHUNKS as i64 {
+        return Err(format!("at most {MAX_COMMAND_CHUNKS} chunks"));
+    }
+    Ok(region)
+}
+
+/// `/worldhash [X Y W H]`: print the digest of this world, over the band
+/// around spawn or the given chunk rectangle.
+pub fn handle_worldhash_command(
+    mut commands_in: MessageReader<ChatCommandEvent>,
+    ctx: WorldCtx,
+    spawn_point: Option<Res<WorldSpawnPoint>>,
+    mut chat: Option<ResMut<ChatState>>,
+    time: Res<Time>,
+) {
+    for cmd in commands_in.read() {
+        if cmd.command != "worldhash" {
+            continue;
+        }
+        let ctx_ref = ctx.as_ref();
+        let region = if cmd.args.is_empty() {
+            let spawn_x = spawn_point.as_ref().map_or(0, |s| s.tile.0);
+            Ok(spawn_band(ctx_ref.config, spawn_x))
+        } else {
+            parse_command_region(&cmd.args)
+        };
+        let reply = match region {
+            Ok(region) => format!(
+                "World hash: {} (seed {}, {}x{} chunks from {}, {})",
+                format_digest(hash_world(&ctx_ref, region)),
+                ctx_ref.config.seed,
+                region.width,
+                region.height,
+                region.x,
+                region.y
+            ),
+            Err(msg) => format!("Usage: /worldhash [X Y W H] ({msg})"),
+        };
+        if let Some(chat) = chat.as_mut() {
+            chat.send_system(&reply, time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Parsed hash command line (everything after [`HASH_FLAG`]).
+#[derive(Debug, PartialEq)]
+struct HashArgs {
+    planet_type: String,
+    seed: u32,
+    region: Option<PreviewRegion>,
+}
+
+fn parse_args(args: &[String]) -> Result<HashArgs, WorldHashError> {
+    let mut positional = Vec::new();
+    let mut region = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--chunks" {
+            let values: Vec<String> = iter.by_ref().take(4).cloned().collect();
+            region = Some(parse_region(&values).map_err(WorldHashError::Usage)?);
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let [planet_type, seed] = positional[..] else {
+        return Err(WorldHashError::Usage(
+            "expected planet type and seed".to_string(),
+        ));
+    };
+    let seed = seed
+        .parse::<u32>()
+        .map_err(|_| WorldHashError::Usage(format!("'{seed}' is not a valid seed")))?;
+    Ok(HashArgs {
+        planet_type: planet_type.clone(),
+        seed,
+        region,
+    })
+}
+
+/// Print the digest if the command line asks for it. Returns `None` when
+/// the game should start normally.
+pub fn run_from_args(args: &[String]) -> Option<Result<String, WorldHashError>> {
+    let flag = args.iter().position(|a| a == HASH_FLAG)?;
+    Some(run(&args[flag + 1..]))
+}
+
+fn run(args: &[String]) -> Result<String, WorldHashError> {
+    let args = parse_args(args)?;
+    let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
+    let world = load_preview_world(&assets_dir, &args.planet_type, args.seed)?;
+    // Preview worlds have no spawn point; the search is centred on x = 0.
+    let region = args.region.unwrap_or_else(|| spawn_band(&world.config, 0));
+    Ok(format_digest(hash_world(&world.ctx(), region)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn region() -> PreviewRegion {
+        // Straddles the wrap seam and the surface of the fixture world.
+        PreviewRegion {
+            x: 63,
+            y: 21,
+            width: 3,
+            height: 3,
+        }
+    }
+
+    fn digest_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/world_hash.digest")
+    }
+
+    #[test]
+    fn hasher_is_pinned_fnv1a() {
+        // Same bytes as `ChunkData::tile_hash`'s pinned case.
+        let mut hasher = StableHasher::new();
+        hasher.write_u16(1);
+        hasher.write_u16(0x0203);
+        assert_eq!(hasher.finish(), 0xad34_fe77_47a1_0445);
+        assert_eq!(format_digest(0xab), "00000000000000ab");
+    }
+
+    #[test]
+    fn hash_is_deterministic_and_sensitive_to_the_world() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let hash = hash_world(&ctx, region());
+        assert_eq!(hash_world(&ctx, region()), hash);
+
+        // A fresh noise cache (nothing memoised yet) gives the same answer.
+        let (wc2, bm2, br2, tr2, pc2, nc2) = fixtures::test_world_ctx();
+        let fresh = fixtures::make_ctx(&wc2, &bm2, &br2, &tr2, &pc2, &nc2);
+        assert_eq!(hash_world(&fresh, region()), hash);
+
+        let moved = PreviewRegion { y: 20, ..region() };
+        assert_ne!(hash_world(&ctx, moved), hash);
+
+        let mut reseeded = wc.clone();
+        reseeded.seed += 1;
+        let nc = crate::world::terrain_gen::TerrainNoiseCache::new(reseeded.seed);
+        let other = fixtures::make_ctx(&reseeded, &bm, &br, &tr, &pc, &nc);
+        assert_ne!(hash_world(&other, region()), hash);
+    }
+
+    #[test]
+    fn spawn_band_centres_on_the_spawn_column() {
+        let mut wc = fixtures::test_world_ctx().0;
+        let band = spawn_band(&wc, 40);
+        assert_eq!((band.x, band.width), (1 - SPAWN_BAND_RADIUS, 9));
+        assert_eq!((band.y, band.height), (0, wc.height_chunks()));
+
+        // Without wrapping the band stops at the world edge.
+        wc.wrap_x = false;
+        let band = spawn_band(&wc, 0);
+        assert_eq!((band.x, band.width), (0, SPAWN_BAND_RADIUS + 1));
+    }
+
+    /// The fixture world's digest is checked in, so a generation change
+    /// shows up as a diff. Regenerate with
+    /// `UPDATE_SNAPSHOTS=1 cargo test world_hash` when it's intentional.
+    #[test]
+    fn fixture_world_matches_checked_in_digest() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let actual = format_digest(hash_world(&ctx, region()));
+        let path = digest_path();
+
+        if std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+            std::fs::write(&path, format!("{actual}\n")).expect("failed to write world digest");
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "missing {} ({e}); run with UPDATE_SNAPSHOTS=1 to create it",
+                path.display()
+            )
+        });
+        assert_eq!(
+            expected.trim(),
+            actual,
+            "world generation changed; if intentional, rerun with UPDATE_SNAPSHOTS=1"
+        );
+    }
+
+    #[test]
+    fn parses_command_line() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert!(run_from_args(&args("starbeam")).is_none());
+
+        let parsed = parse_args(&args("garden 7 --chunks 1 2 3 4")).unwrap();
+        assert_eq!(parsed.seed, 7);
+        assert_eq!(
+            parsed.region,
+            Some(PreviewRegion {
+                x: 1,
+                y: 2,
+                width: 3,
+                height: 4
+            })
+        );
+        assert_eq!(parse_args(&args("barren 9")).unwrap().region, None);
+
+        assert!(parse_args(&args("garden")).is_err());
+        assert!(parse_args(&args("garden 1 out.png")).is_err());
+        assert!(parse_args(&args("garden 1 --chunks 1 2")).is_err());
+    }
+
+    #[test]
+    fn command_regions_are_capped() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(parse_command_region(&args("63 21 3 3")), Ok(region()));
+        assert!(parse_command_region(&args("0 0 64 32")).is_err());
+        assert!(parse_command_region(&args("0 0 0 3")).is_err());
+        assert!(parse_command_region(&args("0 0 -2 -300")).is_err());
+    }
+
+    #[test]
+    fn worldhash_command_replies_with_the_digest() {
+        let mut app = fixtures::test_app();
+        app.add_message::<ChatCommandEvent>()
+            .insert_resource(ChatState::new(10))
+            .add_systems(Update, handle_worldhash_command);
+        app.world_mut().write_message(ChatCommandEvent {
+            command: "worldhash".into(),
+            args: ["63", "21", "3", "3"].map(String::from).to_vec(),
+        });
+        app.update();
+
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let digest = format_digest(hash_world(&ctx, region()));
+        let chat = app.world().resource::<ChatState>();
+        let reply = &chat.messages.last().unwrap().text;
+        assert!(
+            reply.starts_with(&format!("World hash: {digest}")),
+            "{reply}"
+        );
+    }
+}
@@ -0,0 +1,416 @@
+//! "Where is this position" queries for gameplay systems (music, mob
+//! spawning, weather gating, ambient particles, parallax, difficulty).
+//!
+//! The biome rules mirror `terrain_gen::generate_tile`, which uses
+//! [`WorldCtxRef::biome_at_tile`] itself, so a system asking for the biome at a
+//! tile always gets the biome the generator used for that tile.
+
+use crate::registry::biome::{BiomeId, WorldLayer};
+use crate::world::ctx::WorldCtxRef;
+
+/// Column region difficulty is measured from. The spawn search starts at
+/// x = 0 (see `spawn_point`) and settles close to it, and world generation
+/// needs the reference before any spawn has been chosen.
+pub const DIFFICULTY_ORIGIN_X: i32 = 0;
+
+/// Per-chunk biome/layer summary, computed once when the chunk generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkSummary {
+    /// Biome covering most columns of the chunk's middle row.
+    pub dominant_biome: BiomeId,
+    /// Layer of the chunk's middle row.
+    pub layer: WorldLayer,
+}
+
+impl WorldCtxRef<'_> {
+    /// Vertical layer containing `tile_y`.
+    pub fn layer_at(&self, tile_y: i32) -> WorldLayer {
+        WorldLayer::from_tile_y(tile_y, self.planet_config)
+    }
+
+    /// True for every layer below the surface layer.
+    pub fn is_underground(&self, tile_y: i32) -> bool {
+        self.layer_at(tile_y) != WorldLayer::Surface
+    }
+
+    /// How deep `tile_y` is: 0.0 at the top row of the world, 1.0 at the
+    /// bottom row. Clamped for positions outside the world.
+    pub fn depth_fraction(&self, tile_y: i32) -> f32 {
+        let top = (self.config.height_tiles - 1).max(1);
+        ((top - tile_y) as f32 / top as f32).clamp(0.0, 1.0)
+    }
+
+    /// Region difficulty (0.0–1.0) at a tile, for scaling enemies, ore and
+    /// loot. It grows with horizontal distance from spawn and peaks on the far
+    /// side of a wrapping world (the far edge otherwise). It also grows with
+    /// the depth layer. The planet's [`DifficultyCurve`] shapes both terms.
+    ///
+    /// [`DifficultyCurve`]: crate::registry::assets::DifficultyCurve
+    pub fn difficulty_at(&self, tile_x: i32, tile_y: i32) -> f32 {
+        let curve = &self.planet_config.difficulty;
+        let width = self.config.width_tiles;
+        let (distance, far) = if self.config.wrap_x {
+            let dx = (tile_x - DIFFICULTY_ORIGIN_X).rem_euclid(width);
+            (dx.min(width - dx), width / 2)
+        } else {
+            let x = tile_x.clamp(0, width - 1);
+            let far = DIFFICULTY_ORIGIN_X.max(width - 1 - DIFFICULTY_ORIGIN_X);
+            ((x - DIFFICULTY_ORIGIN_X).abs(), far)
+        };
+        let reach = (distance as f32 / far.max(1) as f32).min(1.0);
+        // Power curve through (half_distance, 0.5) and (1, 1).
+        let half = curve.half_distance.clamp(0.01, 0.99);
+        let horizontal = reach.powf(0.5f32.ln() / half.ln());
+
+        let depth = match self.layer_at(tile_y) {
+            WorldLayer::Surface => 0.0,
+            WorldLayer::Underground => 1.0 / 3.0,
+            WorldLayer::DeepUnderground => 2.0 / 3.0,
+            WorldLayer::Core => 1.0,
+        };
+        let weight = curve.depth_weight.max(0.0);
+        (horizontal + weight * depth) / (1.0 + weight)
+    }
+
+    /// Biome a layer generates from: the surface layer follows the biome map
+    /// column, deeper layers use their configured primary biome.
+    pub fn layer_biome(&self, layer: WorldLayer, tile_x: i32) -> BiomeId {
+        let layers = &self.planet_config.layers;
+        let (name, fallback) = match layer {
+            WorldLayer::Surface => {
+                let tile_x = self.config.wrap_tile_x(tile_x);
+                return self.biome_map.biome_at(tile_x as u32);
+            }
+            WorldLayer::Underground => (&layers.underground.primary_biome, "underground_dirt"),
+            WorldLayer::DeepUnderground => {
+                (&layers.deep_underground.primary_biome, "underground_rock")
+            }
+            WorldLayer::Core => (&layers.core.primary_biome, "core_magma"),
+        };
+        self.biome_registry
+            .id_by_name(name.as_deref().unwrap_or(fallback))
+    }
+
+    /// Biome forced onto a tile by [`BiomeOverrides`], if any.
+    ///
+    /// [`BiomeOverrides`]: crate::world::biome_override::BiomeOverrides
+    pub fn forced_biome(&self, tile_x: i32, tile_y: i32) -> Option<BiomeId> {
+        let tile_x = self.config.wrap_tile_x(tile_x);
+        self.biome_overrides?
+            .biome_at(tile_x, tile_y, self.config.chunk_size)
+    }
+
+    /// Effective biome at a tile, using the same rules as `generate_tile`:
+    /// a forced biome covers the whole tile; otherwise the sky, surface block
+    /// and subsurface band belong to the surface biome of the column (even
+    /// where the surface dips below `underground_top`), and everything deeper
+    /// belongs to the layer's biome.
+    pub fn biome_at_tile(&self, tile_x: i32, tile_y: i32) -> BiomeId {
+        if let Some(forced) = self.forced_biome(tile_x, tile_y) {
+            return forced;
+        }
+        let surface_biome = self.layer_biome(WorldLayer::Surface, tile_x);
+        let surface_y = self
+            .noise_cache
+            .surface_height_at(tile_x, self.config, self.planet_config);
+        let subsurface_depth = self.biome_registry.get(surface_biome).subsurface_depth;
+        if tile_y > surface_y - subsurface_depth {
+            return surface_biome;
+        }
+        self.layer_biome(self.layer_at(tile_y), tile_x)
+    }
+
+    /// Compute the summary for a chunk. Prefer [`WorldMap::chunk_info`],
+    /// which returns the summary stored at generation time.
+    ///
+    /// [`WorldMap::chunk_info`]: crate::world::chunk::WorldMap::chunk_info
+    pub fn chunk_summary(&self, chunk_x: i32, chunk_y: i32) -> ChunkSummary {
+        let size = self.config.chunk_size as i32;
+        let mid_y = chunk_y * size + size / 2;
+
+        let mut counts: Vec<(BiomeId, u32)> = Vec::new();
+        for x in chunk_x * size..(chunk_x + 1) * size {
+            let biome = self.biome_at_tile(x, mid_y);
+            match counts.iter_mut().find(|(b, _)| *b == biome) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((biome, 1)),
+            }
+        }
+        // max_by_key keeps the last maximum; iterate reversed so ties go to
+        // the leftmost biome.
+        let dominant_biome = counts
+            .iter()
+            .rev()
+            .max_by_key(|(_, n)| *n)
+            .map(|(b, _)| *b)
+            .expect("chunk has at least one column");
+
+        ChunkSummary {
+            dominant_biome,
+            layer: self.layer_at(mid_y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::assets::DifficultyCurve;
+    use crate::registry::biome::{BiomeRegistry, PlanetConfig};
+    use crate::registry::tile::{TileId, TileRegistry};
+    use crate::registry::world::ActiveWorld;
+    use crate::test_helpers::fixtures;
+    use crate::world::biome_map::BiomeMap;
+    use crate::world::chunk::WorldMap;
+    use crate::world::terrain_gen::TerrainNoiseCache;
+    use crate::world::terrain_gen::{generate_tile, surface_height};
+
+    #[test]
+    fn layer_queries_follow_boundaries() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let b = &pc.layer_boundaries;
+
+        assert_eq!(ctx.layer_at(b.core_top - 1), WorldLayer::Core);
+        assert_eq!(ctx.layer_at(b.core_top), WorldLayer::DeepUnderground);
+        assert_eq!(ctx.layer_at(b.underground_top - 1), WorldLayer::Underground);
+        assert_eq!(ctx.layer_at(b.underground_top), WorldLayer::Surface);
+        assert!(ctx.is_underground(b.underground_top - 1));
+        assert!(!ctx.is_underground(b.underground_top));
+    }
+
+    #[test]
+    fn depth_fraction_spans_world_height() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+
+        assert_eq!(ctx.depth_fraction(wc.height_tiles - 1), 0.0);
+        assert_eq!(ctx.depth_fraction(0), 1.0);
+        assert_eq!(ctx.depth_fraction(-50), 1.0);
+        assert_eq!(ctx.depth_fraction(wc.height_tiles + 50), 0.0);
+        assert!(ctx.depth_fraction(100) > ctx.depth_fraction(500));
+    }
+
+    fn difficulty_ctx_parts(
+        curve: DifficultyCurve,
+        wrap_x: bool,
+    ) -> (
+        ActiveWorld,
+        BiomeMap,
+        BiomeRegistry,
+        TileRegistry,
+        PlanetConfig,
+        TerrainNoiseCache,
+    ) {
+        let (mut wc, bm, br, tr, mut pc, nc) = fixtures::test_world_ctx();
+        wc.wrap_x = wrap_x;
+        pc.difficulty = curve;
+        (wc, bm, br, tr, pc, nc)
+    }
+
+    #[test]
+    fn difficulty_peaks_on_the_far_side_of_a_wrapping_world() {
+        let curve = DifficultyCurve {
+            half_distance: 0.3,
+            depth_weight: 0.0,
+        };
+        let (wc, bm, br, tr, pc, nc) = difficulty_ctx_parts(curve, true);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let (w, top) = (wc.width_tiles, wc.height_tiles - 1);
+
+        assert_eq!(ctx.difficulty_at(0, top), 0.0);
+        assert_eq!(ctx.difficulty_at(w / 2, top), 1.0);
+        // Half-distance lands on 0.5.
+        let half = (0.3 * (w / 2) as f32) as i32;
+        assert!((ctx.difficulty_at(half, top) - 0.5).abs() < 0.01);
+
+        // Symmetric around the planet, including across the seam.
+        for x in [1, 37, 300, w / 2 - 1] {
+            assert_eq!(ctx.difficulty_at(x, top), ctx.difficulty_at(-x, top));
+            assert_eq!(ctx.difficulty_at(x, top), ctx.difficulty_at(w - x, top));
+        }
+        // Rises all the way to the far side, then falls back to spawn.
+        let rising: Vec<f32> = (0..=w / 2)
+            .step_by(16)
+            .map(|x| ctx.difficulty_at(x, top))
+            .collect();
+        assert!(rising.windows(2).all(|p| p[0] < p[1]), "{rising:?}");
+        let falling: Vec<f32> = (w / 2..=w)
+            .step_by(16)
+            .map(|x| ctx.difficulty_at(x, top))
+            .collect();
+        assert!(falling.windows(2).all(|p| p[0] > p[1]), "{falling:?}");
+    }
+
+    #[test]
+    fn difficulty_on_a_walled_world_peaks_at_the_far_edge() {
+        let (wc, bm, br, tr, pc, nc) = difficulty_ctx_parts(DifficultyCurve::default(), false);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let top = wc.height_tiles - 1;
+        let far = ctx.difficulty_at(wc.width_tiles - 1, top);
+        assert!(far > ctx.difficulty_at(wc.width_tiles / 2, top));
+        assert_eq!(ctx.difficulty_at(wc.width_tiles + 40, top), far);
+        assert_eq!(ctx.difficulty_at(-40, top), ctx.difficulty_at(0, top));
+    }
+
+    #[test]
+    fn deeper_layers_add_difficulty_by_weight() {
+        let curve = DifficultyCurve {
+            half_distance: 0.5,
+            depth_weight: 1.0,
+        };
+        let (wc, bm, br, tr, pc, nc) = difficulty_ctx_parts(curve, true);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let b = &pc.layer_boundaries;
+        let ys = [
+            wc.height_tiles - 1,
+            b.underground_top - 1,
+            b.deep_underground_top - 1,
+            0,
+        ];
+        for x in [0, 200, wc.width_tiles / 2] {
+            let by_depth: Vec<f32> = ys.iter().map(|&y| ctx.difficulty_at(x, y)).collect();
+            assert!(by_depth.windows(2).all(|p| p[0] < p[1]), "{by_depth:?}");
+        }
+        assert_eq!(ctx.difficulty_at(0, 0), 0.5);
+        assert_eq!(ctx.difficulty_at(wc.width_tiles / 2, 0), 1.0);
+
+        // Without depth weight the layer makes no difference.
+        let flat = DifficultyCurve {
+            depth_weight: 0.0,
+            ..curve
+        };
+        let (wc, bm, br, tr, pc, nc) = difficulty_ctx_parts(flat, true);
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        assert_eq!(
+            ctx.difficulty_at(200, 0),
+            ctx.difficulty_at(200, wc.height_tiles - 1)
+        );
+    }
+
+    #[test]
+    fn biome_at_tile_agrees_with_generate_tile() {
+        let (wc, bm, mut br, tr, pc, nc) = fixtures::test_world_ctx();
+        // Fixture biomes all fill with stone; give each deep layer a distinct
+        // fill so a wrong biome pick shows up in the generated tile.
+        for (name, fill) in [
+            ("underground_dirt", "dirt"),
+            ("underground_rock", "grass"),
+            ("core_magma", "stone"),
+        ] {
+            let mut def = br.get(br.id_by_name(name)).clone();
+            def.fill_block = tr.by_name(fill);
+            br.insert(name, def);
+        }
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+
+        // Columns across the wrap seam plus both sides of every biome boundary.
+        let mut columns = vec![0, 1, wc.width_tiles - 1, wc.width_tiles, -1];
+        for region in &bm.regions {
+            let start = region.start_x as i32;
+            columns.extend([start - 1, start]);
+        }
+
+        let mut checked = 0;
+        for &x in &columns {
+            let surface = surface_height(
+                &nc,
+                wc.wrap_tile_x(x),
+                &wc,
+                pc.layers.surface.terrain_frequency,
+                pc.layers.surface.terrain_amplitude,
+            );
+            let ys = (0..surface).step_by(7).chain(surface - 3..=surface + 2);
+            for y in ys {
+                let biome = br.get(ctx.biome_at_tile(x, y));
+                let expected = if y > surface {
+                    TileId::AIR
+                } else if y == surface {
+                    biome.surface_block
+                } else if y > surface - biome.subsurface_depth {
+                    biome.subsurface_block
+                } else {
+                    biome.fill_block
+                };
+                let tile = generate_tile(x, y, &ctx);
+                // Caves carve air out of anything below the surface.
+                if tile == TileId::AIR && y < surface {
+                    continue;
+                }
+                assert_eq!(
+                    tile,
+                    expected,
+                    "({x}, {y}) surface={surface} layer={:?}",
+                    ctx.layer_at(y)
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked > 100, "too few solid samples: {checked}");
+    }
+
+    #[test]
+    fn biome_at_tile_uses_surface_biome_near_surface() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let x = 100;
+        let surface = nc.surface_height_at(x, &wc, &pc);
+        let surface_biome = bm.biome_at(x as u32);
+
+        assert_eq!(ctx.biome_at_tile(x, surface + 20), surface_biome);
+        assert_eq!(ctx.biome_at_tile(x, surface), surface_biome);
+        assert_eq!(
+            ctx.biome_at_tile(x, pc.layer_boundaries.core_top - 1),
+            br.id_by_name("core_magma")
+        );
+    }
+
+    #[test]
+    fn biome_at_tile_wraps_across_seam() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let y = wc.height_tiles - 1;
+
+        assert_eq!(
+            ctx.biome_at_tile(-1, y),
+            ctx.biome_at_tile(wc.width_tiles - 1, y)
+        );
+        assert_eq!(
+            ctx.biome_at_tile(wc.width_tiles, y),
+            ctx.biome_at_tile(0, y)
+        );
+    }
+
+    #[test]
+    fn chunk_summary_stored_on_generation() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+        let mut map = WorldMap::default();
+        let size = wc.chunk_size as i32;
+
+        // A chunk column lying entirely inside one biome region.
+        let region = bm
+            .regions
+            .iter()
+            .find(|r| r.width as i32 >= 2 * size)
+            .unwrap();
+        let cx = (region.start_x as i32 + size - 1).div_euclid(size);
+        let region_biome = region.biome_id;
+
+        assert_eq!(map.chunk_summary(cx, 30), None);
+        map.get_or_generate_chunk(cx, 30, &ctx);
+        map.get_or_generate_chunk(cx, 2, &ctx);
+
+        let sky = map.chunk_summary(cx, 30).copied().unwrap();
+        assert_eq!(sky, ctx.chunk_summary(cx, 30));
+        assert_eq!(sky.layer, WorldLayer::Surface);
+        assert_eq!(sky.dominant_biome, region_biome);
+
+        let core = map.chunk_info(cx, 2, &ctx);
+        assert_eq!(core.layer, WorldLayer::Core);
+        assert_eq!(core.dominant_biome, br.id_by_name("core_magma"));
+
+        // Chunks restored from a save have no stored summary; fall back.
+        assert_eq!(map.chunk_info(7, 10, &ctx), ctx.chunk_summary(7, 10));
+    }
+}
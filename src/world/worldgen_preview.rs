@@ -0,0 +1,400 @@
+//! Headless world-generation preview.
+//!
+//! `cargo run -- --worldgen-preview <planet_type> <seed> [out.png] [--chunks X Y W H]`
+//! reads the tile, generation, planet-type and biome RON files straight from
+//! `assets/`, generates a region of chunks with [`generate_chunk_tiles`] and
+//! writes it as a PNG, one pixel per tile coloured by the tile's `albedo`.
+//! No window, renderer or asset server is started, so it also runs in CI.
+//!
+//! Foreground tiles use their albedo, exposed background walls a darkened
+//! albedo and open sky a flat colour. Liquids are not drawn.
+
+use std::path::{Path, PathBuf};
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::cosmos::address::{CelestialAddress, CelestialSeeds};
+use crate::cosmos::assets::GenerationConfigAsset;
+use crate::registry::assets::{BiomeAsset, PlanetTypeAsset, TileRegistryAsset};
+use crate::registry::biome::{BiomeRegistry, PlanetConfig};
+use crate::registry::loading::{biome_def_from_asset, planet_biome_ids, planet_config_from_asset};
+use crate::registry::tile::{TileId, TileRegistry};
+use crate::registry::world::ActiveWorld;
+use crate::world::biome_map::{BiomeMap, BorderCliffs};
+use crate::world::ctx::WorldCtxRef;
+use crate::world::terrain_gen::{generate_chunk_tiles, TerrainNoiseCache};
+
+/// Command-line flag that switches `main` into preview mode.
+pub const PREVIEW_FLAG: &str = "--worldgen-preview";
+
+/// Colour of tiles with neither a foreground block nor a wall.
+const SKY_COLOR: [u8; 4] = [24, 28, 40, 255];
+/// Brightness of exposed background walls relative to their albedo.
+const BG_SHADE: f32 = 0.5;
+
+#[derive(Debug, Error)]
+pub enum PreviewError {
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{path}: {source}")]
+    Ron {
+        path: PathBuf,
+        source: Box<ron::error::SpannedError>,
+    },
+    #[error("failed to write PNG: {0}")]
+    Png(String),
+    #[error("usage: {PREVIEW_FLAG} <planet_type> <seed> [out.png] [--chunks X Y W H] ({0})")]
+    Usage(String),
+}
+
+/// Chunk rectangle to render: `width` × `height` chunks from `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Everything terrain generation needs, owned instead of held in resources.
+pub struct PreviewWorld {
+    pub config: ActiveWorld,
+    pub biome_map: BiomeMap,
+    pub biome_registry: BiomeRegistry,
+    pub tile_registry: TileRegistry,
+    pub planet_config: PlanetConfig,
+    pub noise_cache: TerrainNoiseCache,
+}
+
+impl PreviewWorld {
+    pub fn ctx(&self) -> WorldCtxRef<'_> {
+        WorldCtxRef {
+            config: &self.config,
+            biome_map: &self.biome_map,
+            biome_registry: &self.biome_registry,
+            tile_registry: &self.tile_registry,
+            planet_config: &self.planet_config,
+            noise_cache: &self.noise_cache,
+            biome_overrides: None,
+        }
+    }
+
+    /// The whole world as a region.
+    pub fn full_region(&self) -> PreviewRegion {
+        PreviewRegion {
+            x: 0,
+            y: 0,
+            width: self.config.width_chunks(),
+            height: self.config.height_chunks(),
+        }
+    }
+}
+
+fn read_ron<T: DeserializeOwned>(path: &Path) -> Result<T, PreviewError> {
+    let bytes = std::fs::read(path).map_err(|source| PreviewError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    ron::de::from_bytes(&bytes).map_err(|source| PreviewError::Ron {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    })
+}
+
+/// Build the generation inputs for `planet_type` with terrain seed `seed` from
+/// the RON files under `assets_dir`, the same way the loading states do.
+pub fn load_preview_world(
+    assets_dir: &Path,
+    planet_type: &str,
+    seed: u32,
+) -> Result<PreviewWorld, PreviewError> {
+    let tiles: TileRegistryAsset = read_ron(&assets_dir.join("worlds/tiles.registry.ron"))?;
+    let tile_registry = TileRegistry::from_defs(tiles.tiles);
+    let gen_config: GenerationConfigAsset = read_ron(&assets_dir.join("worlds/generation.ron"))?;
+    let planet_asset: PlanetTypeAsset = read_ron(&assets_dir.join(format!(
+        "worlds/planet_types/{planet_type}/{planet_type}.planet.ron"
+    )))?;
+
+    let (width_tiles, height_tiles) = planet_asset.size.unwrap_or((
+        gen_config.default_planet_size.width,
+        gen_config.default_planet_size.height,
+    ));
+    let address = CelestialAddress::planet(IVec2::ZERO, IVec2::ZERO, 0);
+    let config = ActiveWorld {
+        seeds: CelestialSeeds::derive(seed as u64, &address),
+        address,
+        width_tiles,
+        height_tiles,
+        chunk_size: gen_config.chunk_size,
+        tile_size: gen_config.tile_size,
+        chunk_load_radius: gen_config.chunk_load_radius,
+        seed,
+        planet_type: planet_type.to_string(),
+        wrap_x: true,
+        base_temperature: planet_asset.base_temperature.unwrap_or(15.0),
+        weather_config: None,
+    };
+
+    let mut planet_config = planet_config_from_asset(&planet_asset, height_tiles);
+    let mut biome_ids: Vec<String> = planet_biome_ids(&planet_asset).into_iter().collect();
+    // Registry ids follow insertion order; keep them stable between runs.
+    biome_ids.sort();
+    let mut biome_registry = BiomeRegistry::default();
+    for id in &biome_ids {
+        let asset: BiomeAsset =
+            read_ron(&assets_dir.join(format!("content/biomes/{id}/{id}.biome.ron")))?;
+        biome_registry.insert(id, biome_def_from_asset(&asset, &tile_registry));
+    }
+
+    let secondaries: Vec<&str> = planet_config
+        .secondary_biomes
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let biome_map = BiomeMap::generate(
+        &planet_config.primary_biome,
+        &secondaries,
+        seed as u64,
+        width_tiles as u32,
+        planet_config.region_width_min,
+        planet_config.region_width_max,
+        planet_config.primary_region_ratio,
+        planet_config.biome_separation,
+        planet_config.starter_biome.as_deref(),
+        &biome_registry,
+    );
+    planet_config.border_cliffs = BorderCliffs::new(
+        &biome_map,
+        &planet_config.biome_borders,
+        &biome_registry,
+        seed,
+        config.wrap_x,
+    );
+
+    Ok(PreviewWorld {
+        config,
+        biome_map,
+        biome_registry,
+        tile_registry,
+        planet_config,
+        noise_cache: TerrainNoiseCache::new(seed),
+    })
+}
+
+/// Preview colour of one tile.
+fn tile_color(fg: TileId, bg: TileId, tile_registry: &TileRegistry) -> [u8; 4] {
+    if fg != TileId::AIR {
+        let [r, g, b] = tile_registry.get(fg).albedo;
+        [r, g, b, 255]
+    } else if bg != TileId::AIR {
+        let [r, g, b] = tile_registry
+            .get(bg)
+            .albedo
+            .map(|c| (c as f32 * BG_SHADE) as u8);
+        [r, g, b, 255]
+    } else {
+        SKY_COLOR
+    }
+}
+
+/// Generate `region` and return it as an sRGB RGBA image, one pixel per tile,
+/// with the top row of the image at the highest tile row.
+pub fn render_region(ctx: &WorldCtxRef, region: PreviewRegion) -> Image {
+    let chunk_size = ctx.config.chunk_size as i32;
+    let width = (region.width * chunk_size).max(0) as usize;
+    let height = (region.height * chunk_size).max(0) as usize;
+    let mut data = vec![0u8; width * height * 4];
+
+    for cy in 0..region.height {
+        for cx in 0..region.width {
+            let chunk_x = ctx.config.wrap_chunk_x(region.x + cx);
+            let tiles = generate_chunk_tiles(chunk_x, region.y + cy, ctx);
+            for local_y in 0..chunk_size {
+                for local_x in 0..chunk_size {
+                    let idx = (local_y * chunk_size + local_x) as usize;
+                    let px = (cx * chunk_size + local_x) as usize;
+                    let py = height - 1 - (cy * chunk_size + local_y) as usize;
+                    let offset = (py * width + px) * 4;
+                    data[offset..offset + 4].copy_from_slice(&tile_color(
+                        tiles.fg[idx],
+                        tiles.bg[idx],
+                        ctx.tile_registry,
+                    ));
+                }
+            }
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    )
+}
+
+/// Write an image produced by [`render_region`] to `path` as PNG.
+pub fn save_png(image: Image, path: &Path) -> Result<(), PreviewError> {
+    image
+        .try_into_dynamic()
+        .map_err(|e| PreviewError::Png(e.to_string()))?
+        .save(path)
+        .map_err(|e| PreviewError::Png(e.to_string()))
+}
+
+/// Parsed preview command line (everything after [`PREVIEW_FLAG`]).
+#[derive(Debug, PartialEq)]
+struct PreviewArgs {
+    planet_type: String,
+    seed: u32,
+    output: PathBuf,
+    region: Option<PreviewRegion>,
+}
+
+fn parse_args(args: &[String]) -> Result<PreviewArgs, PreviewError> {
+    let usage = |msg: &str| PreviewError::Usage(msg.to_string());
+    let number = |s: &String| {
+        s.parse::<i32>()
+            .map_err(|_| usage(&format!("'{s}' is not a number")))
+    };
+
+    let mut positional = Vec::new();
+    let mut region = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--chunks" {
+            let values: Vec<&String> = iter.by_ref().take(4).collect();
+            let [x, y, w, h] = values[..] else {
+                return Err(usage("--chunks needs X Y W H"));
+            };
+            region = Some(PreviewRegion {
+                x: number(x)?,
+                y: number(y)?,
+                width: number(w)?,
+                height: number(h)?,
+            });
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let (planet_type, seed, output) = match positional[..] {
+        [planet, seed] => (planet, seed, None),
+        [planet, seed, output] => (planet, seed, Some(output)),
+        _ => return Err(usage("expected planet type and seed")),
+    };
+    let seed = seed
+        .parse::<u32>()
+        .map_err(|_| usage(&format!("'{seed}' is not a valid seed")))?;
+    Ok(PreviewArgs {
+        planet_type: planet_type.clone(),
+        seed,
+        output: output
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(format!("worldgen_{planet_type}_{seed}.png"))),
+        region,
+    })
+}
+
+/// Run the preview if the command line asks for it. Returns `None` when the
+/// game should start normally.
+pub fn run_from_args(args: &[String]) -> Option<Result<PathBuf, PreviewError>> {
+    let flag = args.iter().position(|a| a == PREVIEW_FLAG)?;
+    Some(run(&args[flag + 1..]))
+}
+
+fn run(args: &[String]) -> Result<PathBuf, PreviewError> {
+    let args = parse_args(args)?;
+    let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
+    let world = load_preview_world(&assets_dir, &args.planet_type, args.seed)?;
+    let region = args.region.unwrap_or_else(|| world.full_region());
+    save_png(render_region(&world.ctx(), region), &args.output)?;
+    Ok(args.output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::fixtures;
+
+    fn region() -> PreviewRegion {
+        // Straddles the wrap seam and the surface of the fixture world.
+        PreviewRegion {
+            x: 63,
+            y: 21,
+            width: 3,
+            height: 3,
+        }
+    }
+
+    #[test]
+    fn render_is_deterministic_with_expected_size() {
+        let (wc, bm, br, tr, pc, nc) = fixtures::test_world_ctx();
+        let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+
+        let first = render_region(&ctx, region());
+        let second = render_region(&ctx, region());
+        assert_eq!(first.width(), 3 * 32);
+        assert_eq!(first.height(), 3 * 32);
+        assert_eq!(first.data.as_ref().unwrap().len(), 96 * 96 * 4);
+        assert_eq!(first.data, second.data);
+
+        // The surface runs through this region: both sky and ground appear.
+        let pixels: Vec<&[u8]> = first.data.as_ref().unwrap().chunks(4).collect();
+        assert!(pixels.contains(&SKY_COLOR.as_slice()));
+        assert!(pixels.iter().any(|p| *p != SKY_COLOR.as_slice()));
+    }
+
+    #[test]
+    fn loads_real_assets_and_renders() {
+        let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
+        let world = load_preview_world(&assets_dir, "garden", 42).unwrap();
+        let small = PreviewRegion {
+            x: 0,
+            y: 0,
+            width: 2,
+            height: 1,
+        };
+        let image = render_region(&world.ctx(), small);
+        let chunk_size = world.config.chunk_size;
+        assert_eq!(image.width(), 2 * chunk_size);
+        assert_eq!(image.height(), chunk_size);
+    }
+
+    #[test]
+    fn parses_command_line() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert!(run_from_args(&args("starbeam")).is_none());
+
+        let parsed = parse_args(&args("garden 7 out.png --chunks 1 2 3 4")).unwrap();
+        assert_eq!(parsed.seed, 7);
+        assert_eq!(parsed.output, PathBuf::from("out.png"));
+        assert_eq!(
+            parsed.region,
+            Some(PreviewRegion {
+                x: 1,
+                y: 2,
+                width: 3,
+                height: 4
+            })
+        );
+        let parsed = parse_args(&args("barren 9")).unwrap();
+        assert_eq!(parsed.output, PathBuf::from("worldgen_barren_9.png"));
+
+        assert!(parse_args(&args("garden")).is_err());
+        assert!(parse_args(&args("garden x")).is_err());
+        assert!(parse_args(&args("garden 1 --chunks 1 2")).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Seeded world-generation regression snapshots.
+//!
+//! Generates a fixed set of chunks, the biome region layout and a sampled
+//! surface-height profile for a few known seeds using the fixture registries,
+//! and compares them against `tests/worldgen_snapshots.ron`.
+//!
+//! When a worldgen change is intentional, regenerate the file with
+//! `UPDATE_SNAPSHOTS=1 cargo test worldgen_snapshot` and commit the diff.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::test_helpers::fixtures;
+use crate::world::biome_map::BiomeMap;
+use crate::world::chunk::WorldMap;
+use crate::world::terrain_gen::TerrainNoiseCache;
+
+const SEEDS: [u32; 3] = [42, 1337, 0x00C0_FFEE];
+
+/// Chunk rows sampled for every seed: core, deep underground, underground,
+/// and the three rows the surface can fall into (fixture world is 1024 tall).
+const CHUNK_ROWS: [i32; 6] = [1, 9, 18, 21, 22, 23];
+
+/// Chunk columns sampled for every seed, in addition to the columns on both
+/// sides of each biome region boundary. Includes both ends of the wrap seam.
+const CHUNK_COLUMNS: [i32; 3] = [0, 31, 63];
+
+/// Horizontal spacing (in tiles) between surface-height samples.
+const SURFACE_SAMPLE_STEP: i32 = 32;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct WorldgenSnapshot {
+    seeds: Vec<SeedSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SeedSnapshot {
+    seed: u32,
+    biome_regions: Vec<RegionSnapshot>,
+    surface_profile: Vec<i32>,
+    chunks: Vec<ChunkSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RegionSnapshot {
+    biome: String,
+    start_x: u32,
+    width: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ChunkSnapshot {
+    x: i32,
+    y: i32,
+    hash: u64,
+}
+
+fn snapshot_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/worldgen_snapshots.ron")
+}
+
+fn capture_seed(seed: u32) -> SeedSnapshot {
+    let mut wc = fixtures::test_active_world();
+    wc.seed = seed;
+    let br = fixtures::test_biome_registry();
+    let tr = fixtures::test_tile_registry();
+    let pc = fixtures::test_planet_config();
+    let secondaries: Vec<&str> = pc.secondary_biomes.iter().map(String::as_str).collect();
+    let bm = BiomeMap::generate(
+        &pc.primary_biome,
+        &secondaries,
+        seed as u64,
+        wc.width_tiles as u32,
+        pc.region_width_min,
+        pc.region_width_max,
+        pc.primary_region_ratio,
+        pc.biome_separation,
+        pc.starter_biome.as_deref(),
+        &br,
+    );
+    let nc = TerrainNoiseCache::new(seed);
+    let ctx = fixtures::make_ctx(&wc, &bm, &br, &tr, &pc, &nc);
+
+    let biome_regions = bm
+        .regions
+        .iter()
+        .map(|r| RegionSnapshot {
+            biome: br.name_of(r.biome_id).to_string(),
+            start_x: r.start_x,
+            width: r.width,
+        })
+        .collect();
+
+    // The memoized heights terrain generation reads, cliffs included.
+    let surface_profile = (0..wc.width_tiles)
+        .step_by(SURFACE_SAMPLE_STEP as usize)
+        .map(|x| nc.surface_height_at(x, &wc, &pc))
+        .collect();
+
+    // Columns straddling every biome boundary, plus the fixed ones.
+    let chunk_size = wc.chunk_size as i32;
+    let mut columns: Vec<i32> = CHUNK_COLUMNS.to_vec();
+    for region in &bm.regions {
+        let start = region.start_x as i32;
+        columns.push(wc.wrap_tile_x(start - 1).div_euclid(chunk_size));
+        columns.push(start.div_euclid(chunk_size));
+    }
+
+    let mut map = WorldMap::default();
+    for &cy in &CHUNK_ROWS {
+        for &cx in &columns {
+            map.get_or_generate_chunk(cx, cy, &ctx);
+        }
+    }
+    let chunks = map
+        .sorted_chunk_coords()
+        .into_iter()
+        .map(|(x, y)| ChunkSnapshot {
+            x,
+            y,
+            hash: map.chunk(x, y).unwrap().tile_hash(),
+        })
+        .collect();
+
+    SeedSnapshot {
+        seed,
+        biome_regions,
+        surface_profile,
+        chunks,
+    }
+}
+
+fn capture() -> WorldgenSnapshot {
+    WorldgenSnapshot {
+        seeds: SEEDS.iter().map(|&seed| capture_seed(seed)).collect(),
+    }
+}
+
+/// Human-readable list of differences between two snapshots.
+fn diff(expected: &WorldgenSnapshot, actual: &WorldgenSnapshot) -> Vec<String> {
+    let mut out = Vec::new();
+    if expected.seeds.len() != actual.seeds.len() {
+        out.push(format!(
+            "seed count: expected {}, got {}",
+            expected.seeds.len(),
+            actual.seeds.len()
+        ));
+    }
+    for (exp, act) in expected.seeds.iter().zip(&actual.seeds) {
+        let seed = act.seed;
+        if exp.seed != act.seed {
+            out.push(format!("seed order: expected {}, got {}", exp.seed, act.seed));
+            continue;
+        }
+        if exp.biome_regions != act.biome_regions {
+            out.push(format!(
+                "seed {seed}: biome regions changed\n  expected {:?}\n  got      {:?}",
+                exp.biome_regions, act.biome_regions
+            ));
+        }
+        for (i, (e, a)) in exp.surface_profile.iter().zip(&act.surface_profile).enumerate() {
+            if e != a {
+                let x = i as i32 * SURFACE_SAMPLE_STEP;
+                out.push(format!("seed {seed}: surface_height(x={x}) expected {e}, got {a}"));
+            }
+        }
+        if exp.surface_profile.len() != act.surface_profile.len() {
+            out.push(format!("seed {seed}: surface profile length changed"));
+        }
+        if exp.chunks.len() != act.chunks.len() {
+            out.push(format!(
+                "seed {seed}: sampled chunk set changed ({} -> {})",
+                exp.chunks.len(),
+                act.chunks.len()
+            ));
+        }
+        for (e, a) in exp.chunks.iter().zip(&act.chunks) {
+            if e != a {
+                out.push(format!(
+                    "seed {seed}: chunk ({}, {}) expected {:#018x}, got chunk ({}, {}) {:#018x}",
+                    e.x, e.y, e.hash, a.x, a.y, a.hash
+                ));
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn worldgen_matches_snapshots() {
+    let actual = capture();
+    let path = snapshot_path();
+
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+        let ron = ron::ser::to_string_pretty(&actual, ron::ser::PrettyConfig::default().depth_limit(4))
+            .expect("snapshot should serialize");
+        std::fs::write(&path, ron + "\n").expect("failed to write worldgen snapshots");
+        return;
+    }
+
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "missing {} ({e}); run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    let expected: WorldgenSnapshot =
+        ron::from_str(&text).expect("worldgen_snapshots.ron should parse");
+
+    let diffs = diff(&expected, &actual);
+    assert!(
+        diffs.is_empty(),
+        "world generation changed:\n{}\n\nIf intentional, rerun with UPDATE_SNAPSHOTS=1",
+        diffs.join("\n")
+    );
+}
+
+#[test]
+fn seeds_generate_different_worlds() {
+    let snapshot = capture();
+    for (i, a) in snapshot.seeds.iter().enumerate() {
+        for b in &snapshot.seeds[i + 1..] {
+            assert_ne!(
+                a.surface_profile, b.surface_profile,
+                "seeds {} and {} share a surface",
+                a.seed, b.seed
+            );
+            let hashes = |s: &SeedSnapshot| s.chunks.iter().map(|c| c.hash).collect::<Vec<_>>();
+            assert_ne!(
+                hashes(a),
+                hashes(b),
+                "seeds {} and {} share their chunks",
+                a.seed,
+                b.seed
+            );
+        }
+    }
+}